@@ -0,0 +1,126 @@
+//! Compares finding a cached bind group by descriptor and by [`GroupKey`],
+//! and measures the counters behind [`Instance::stats_frame`].
+//!
+//! Run with `cargo bench --bench bind_group_cache`.
+
+use std::{
+    hint::black_box,
+    num::NonZeroU64,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Instant,
+};
+
+use dashmap::DashMap;
+use shatter::*;
+
+const ITERATIONS: u32 = 1_000_000;
+
+fn main() {
+    let layouts = IdMap::<wgpu::BindGroupLayout>::new();
+    let buffers = IdMap::<wgpu::Buffer>::new();
+    let bind_groups = IdMap::<wgpu::BindGroup>::new();
+
+    let layout = layouts.next_id();
+    let particles = buffers.next_id();
+    let uniforms = buffers.next_id();
+
+    // what the particle example builds every frame
+    let descriptor = || {
+        BindGroupDescriptor::new(
+            layout.clone(),
+            vec![
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::Buffer(BufferBinding {
+                        buffer: particles.clone(),
+                        offset: 0,
+                        size: None,
+                    }),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Buffer(BufferBinding {
+                        buffer: uniforms.clone(),
+                        offset: 0,
+                        size: NonZeroU64::new(4),
+                    }),
+                },
+            ],
+        )
+    };
+
+    let bind_group = bind_groups.next_id();
+
+    let by_descriptor = DashMap::new();
+    by_descriptor.insert(descriptor(), bind_group.clone_untracked());
+
+    let by_key = DashMap::new();
+    by_key.insert(
+        GroupKey::from_descriptor(&descriptor()),
+        bind_group.clone_untracked(),
+    );
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(by_descriptor.get(&descriptor()).unwrap().raw());
+    }
+    let descriptor_time = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let mut key = GroupKey::new(&layout);
+
+        key.push(
+            0,
+            &BindingResource::Buffer(BufferBinding {
+                buffer: particles.clone(),
+                offset: 0,
+                size: None,
+            }),
+        );
+        key.push(
+            1,
+            &BindingResource::Buffer(BufferBinding {
+                buffer: uniforms.clone(),
+                offset: 0,
+                size: NonZeroU64::new(4),
+            }),
+        );
+
+        black_box(by_key.get(&key).unwrap().raw());
+    }
+    let key_time = start.elapsed();
+
+    // a hit with and without the relaxed increment the instance counts it with
+    let key = GroupKey::from_descriptor(&descriptor());
+    let hits = AtomicU64::new(0);
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(by_key.get(&key).unwrap().raw());
+    }
+    let uncounted_time = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(by_key.get(&key).unwrap().raw());
+        hits.fetch_add(1, Ordering::Relaxed);
+    }
+    let counted_time = start.elapsed();
+
+    black_box(hits.load(Ordering::Relaxed));
+
+    println!("descriptor lookup: {:?}/iter", descriptor_time / ITERATIONS);
+    println!("group key lookup:  {:?}/iter", key_time / ITERATIONS);
+    println!("uncounted hit:     {:?}/iter", uncounted_time / ITERATIONS);
+    println!("counted hit:       {:?}/iter", counted_time / ITERATIONS);
+
+    if let Some(instance) = Instance::try_global() {
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            black_box(instance.stats_frame());
+        }
+
+        println!("stats frame:       {:?}/iter", start.elapsed() / ITERATIONS);
+    }
+}
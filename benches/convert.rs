@@ -0,0 +1,148 @@
+//! Compares the chunked conversion kernels against naive per-pixel loops on a
+//! 4k rgba8 image.
+//!
+//! Run with `cargo bench --features bench --bench convert`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use shatter::convert;
+
+const WIDTH: usize = 3840;
+const HEIGHT: usize = 2160;
+const CHANNELS: usize = 4;
+const LEN: usize = WIDTH * HEIGHT * CHANNELS;
+/// Row stride of a 4k rgba8 texture copy, rows are padded to 256 bytes.
+const PADDED_STRIDE: usize = (WIDTH * CHANNELS).div_ceil(256) * 256;
+
+fn image() -> Vec<u8> {
+    (0..LEN).map(|i| (i * 7 % 251) as u8).collect()
+}
+
+// the naive loops convert a pixel at a time, indexing every channel
+
+fn naive_unorm8_to_f32(src: &[u8], dst: &mut [f32]) {
+    for pixel in 0..src.len() / CHANNELS {
+        for channel in 0..CHANNELS {
+            let i = pixel * CHANNELS + channel;
+            dst[i] = src[i] as f32 / 255.0;
+        }
+    }
+}
+
+fn naive_f32_to_unorm8(src: &[f32], dst: &mut [u8]) {
+    for pixel in 0..src.len() / CHANNELS {
+        for channel in 0..CHANNELS {
+            let i = pixel * CHANNELS + channel;
+            dst[i] = (src[i].clamp(0.0, 1.0) * 255.0).round() as u8;
+        }
+    }
+}
+
+fn naive_srgb8_to_linear(src: &[u8], dst: &mut [f32]) {
+    for pixel in 0..src.len() / CHANNELS {
+        for channel in 0..CHANNELS {
+            let i = pixel * CHANNELS + channel;
+            dst[i] = convert::srgb_to_linear_f32(src[i] as f32 / 255.0);
+        }
+    }
+}
+
+fn naive_linear_to_srgb8(src: &[f32], dst: &mut [u8]) {
+    for pixel in 0..src.len() / CHANNELS {
+        for channel in 0..CHANNELS {
+            let i = pixel * CHANNELS + channel;
+            let value = convert::linear_to_srgb_f32(src[i].clamp(0.0, 1.0));
+            dst[i] = (value * 255.0).round() as u8;
+        }
+    }
+}
+
+fn naive_copy_rows(src: &[u8], src_stride: usize, dst: &mut [u8], row_bytes: usize) {
+    for row in 0..HEIGHT {
+        for byte in 0..row_bytes {
+            dst[row * row_bytes + byte] = src[row * src_stride + byte];
+        }
+    }
+}
+
+fn unorm8(c: &mut Criterion) {
+    let src = image();
+    let mut floats = vec![0.0; LEN];
+    let mut bytes = vec![0; LEN];
+    convert::unorm8_to_f32(&src, &mut floats);
+
+    let mut group = c.benchmark_group("unorm8");
+    group.throughput(Throughput::Bytes(LEN as u64));
+    group.sample_size(20);
+
+    group.bench_function("to_f32/naive", |b| {
+        b.iter(|| naive_unorm8_to_f32(black_box(&src), &mut floats))
+    });
+    group.bench_function("to_f32/chunked", |b| {
+        b.iter(|| convert::unorm8_to_f32(black_box(&src), &mut floats))
+    });
+    group.bench_function("from_f32/naive", |b| {
+        b.iter(|| naive_f32_to_unorm8(black_box(&floats), &mut bytes))
+    });
+    group.bench_function("from_f32/chunked", |b| {
+        b.iter(|| convert::f32_to_unorm8(black_box(&floats), &mut bytes))
+    });
+
+    group.finish();
+}
+
+fn srgb(c: &mut Criterion) {
+    let src = image();
+    let mut floats = vec![0.0; LEN];
+    let mut bytes = vec![0; LEN];
+    convert::srgb8_to_linear(&src, &mut floats);
+
+    let mut group = c.benchmark_group("srgb");
+    group.throughput(Throughput::Bytes(LEN as u64));
+    group.sample_size(10);
+
+    group.bench_function("to_linear/naive", |b| {
+        b.iter(|| naive_srgb8_to_linear(black_box(&src), &mut floats))
+    });
+    group.bench_function("to_linear/chunked", |b| {
+        b.iter(|| convert::srgb8_to_linear(black_box(&src), &mut floats))
+    });
+    group.bench_function("from_linear/naive", |b| {
+        b.iter(|| naive_linear_to_srgb8(black_box(&floats), &mut bytes))
+    });
+    group.bench_function("from_linear/chunked", |b| {
+        b.iter(|| convert::linear_to_srgb8(black_box(&floats), &mut bytes))
+    });
+
+    group.finish();
+}
+
+fn rows(c: &mut Criterion) {
+    let row_bytes = WIDTH * CHANNELS;
+    let padded = vec![1; PADDED_STRIDE * HEIGHT];
+    let mut packed = vec![0; LEN];
+
+    let mut group = c.benchmark_group("rows");
+    group.throughput(Throughput::Bytes(LEN as u64));
+    group.sample_size(20);
+
+    group.bench_function("depad/naive", |b| {
+        b.iter(|| naive_copy_rows(black_box(&padded), PADDED_STRIDE, &mut packed, row_bytes))
+    });
+    group.bench_function("depad/chunked", |b| {
+        b.iter(|| {
+            convert::copy_rows(
+                black_box(&padded),
+                PADDED_STRIDE,
+                &mut packed,
+                row_bytes,
+                row_bytes,
+                HEIGHT,
+            )
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, unorm8, srgb, rows);
+criterion_main!(benches);
@@ -0,0 +1,591 @@
+//! Compares shatter against hand-rolled wgpu running the same kernels on the
+//! same device: CPU overhead per dispatch at steady state, latency of the
+//! first dispatch of a shader, upload throughput and readback latency.
+//!
+//! Run with `cargo bench --features bench --bench dispatch_overhead`. The
+//! fallback adapter is preferred so numbers are comparable between machines.
+//! Results are printed as a ratio to wgpu, set `SHATTER_BENCH_README=1` to
+//! also regenerate the table in `README.md`.
+
+use std::{
+    env, fs,
+    hint::black_box,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use criterion::{BenchmarkId, Criterion, Throughput};
+use shatter::*;
+
+mod particles {
+    use shatter::*;
+
+    wgsl! {
+        struct Particle {
+            position: vec2<f32>;
+            velocity: vec2<f32>;
+            radius: f32;
+        };
+
+        [[block]]
+        struct Particles {
+            particles: array<Particle>;
+        };
+
+        [[group(0), binding(0)]]
+        var<storage, read_write> particles: Particles;
+
+        [[block]]
+        struct Uniforms {
+            delta_time: f32;
+        };
+
+        [[group(0), binding(1)]]
+        var<uniform> uniforms: Uniforms;
+
+        [[stage(compute), workgroup_size(64)]]
+        fn update([[builtin(global_invocation_id)]] id: vec3<u32>) {
+            if (id.x >= arrayLength(&particles.particles)) {
+                return;
+            }
+
+            let particle = &particles.particles[id.x];
+
+            (*particle).position = (*particle).position + (*particle).velocity * uniforms.delta_time;
+        }
+    }
+}
+
+mod memcpy {
+    use shatter::*;
+
+    wgsl! {
+        [[block]]
+        struct Words {
+            words: array<u32>;
+        };
+
+        [[group(0), binding(0)]]
+        var<storage, read> src: Words;
+
+        [[group(0), binding(1)]]
+        var<storage, read_write> dst: Words;
+
+        [[stage(compute), workgroup_size(64)]]
+        fn copy([[builtin(global_invocation_id)]] id: vec3<u32>) {
+            if (id.x >= arrayLength(&dst.words)) {
+                return;
+            }
+
+            dst.words[id.x] = src.words[id.x];
+        }
+    }
+}
+
+use memcpy::Words;
+use particles::{Particles, Uniforms};
+
+const PARTICLES: usize = 4096;
+/// Stride of `Particle` in WGSL, two `vec2<f32>` and an `f32` rounded up to 8 bytes.
+const PARTICLE_STRIDE: u64 = 24;
+const WORDS: usize = 65536;
+
+const MIB: usize = 1 << 20;
+const UPLOAD_SIZES: [(&str, usize); 3] =
+    [("1MiB", MIB), ("16MiB", 16 * MIB), ("256MiB", 256 * MIB)];
+const READBACK_SIZES: [(&str, usize); 2] = [("4KiB", 4096), ("1MiB", MIB)];
+
+/// Every benchmark measured on both sides, as `(group, case)`.
+const CASES: [(&str, &str); 8] = [
+    ("dispatch", "particles"),
+    ("dispatch", "memcpy"),
+    ("first_dispatch", "memcpy"),
+    ("upload", "1MiB"),
+    ("upload", "16MiB"),
+    ("upload", "256MiB"),
+    ("readback", "4KiB"),
+    ("readback", "1MiB"),
+];
+
+const README_START: &str = "<!-- benchmarks:start -->";
+const README_END: &str = "<!-- benchmarks:end -->";
+
+fn dispatch_size(len: usize) -> Dispatch {
+    Dispatch::new(len.div_ceil(64) as u32, 1, 1)
+}
+
+/// Creates the global instance on a device the wgpu side can use as well.
+fn instance() -> Option<&'static Instance> {
+    let instance = wgpu::Instance::new(wgpu::Backends::all());
+
+    // any adapter will do on machines without a fallback adapter
+    let adapter = [true, false]
+        .into_iter()
+        .find_map(|force_fallback_adapter| {
+            pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                force_fallback_adapter,
+                compatible_surface: None,
+            }))
+        })?;
+
+    let (device, queue) = pollster::block_on(adapter.request_device(
+        &wgpu::DeviceDescriptor {
+            label: Some("dispatch_overhead"),
+            features: wgpu::Features::empty(),
+            limits: wgpu::Limits::default(),
+        },
+        None,
+    ))
+    .ok()?;
+
+    let instance = Instance::from_device(
+        instance,
+        adapter,
+        device,
+        queue,
+        &InstanceDescriptor::default(),
+    );
+
+    Instance::set_global(instance).ok()
+}
+
+fn buffer(device: &wgpu::Device, size: u64, usage: wgpu::BufferUsages) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size,
+        usage,
+        mapped_at_creation: false,
+    })
+}
+
+/// A kernel set up by hand, the way an application without shatter would.
+struct RawKernel {
+    pipeline: wgpu::ComputePipeline,
+    bind_group: wgpu::BindGroup,
+}
+
+impl RawKernel {
+    /// Binds `buffers` to group 0 in order.
+    fn new(
+        device: &wgpu::Device,
+        source: &str,
+        entry_point: &str,
+        buffers: &[&wgpu::Buffer],
+    ) -> Self {
+        let module = device.create_shader_module(&wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &module,
+            entry_point,
+        });
+
+        let entries = buffers
+            .iter()
+            .enumerate()
+            .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                binding: binding as u32,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect::<Vec<_>>();
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &entries,
+        });
+
+        Self {
+            pipeline,
+            bind_group,
+        }
+    }
+
+    fn dispatch(&self, instance: &Instance, dispatch: Dispatch) {
+        let mut encoder = instance
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &self.bind_group, &[]);
+            pass.dispatch(dispatch.x, dispatch.y, dispatch.z);
+        }
+
+        instance.queue.submit(Some(encoder.finish()));
+    }
+}
+
+fn words(len: usize) -> Buffer<Words> {
+    let mut words = Buffer::<Words>::new();
+
+    for i in 0..len {
+        words.push(i as u32);
+    }
+
+    words.upload();
+    words
+}
+
+/// Asserts that the last phase ran entirely from the caches and without
+/// transfers, so it measured dispatch overhead only. A phase without
+/// dispatches was filtered out.
+fn assert_steady(instance: &Instance, name: &str) {
+    let frame = instance.stats_frame();
+
+    if frame.dispatches == 0 {
+        return;
+    }
+
+    assert_eq!(
+        frame.cache_misses, 0,
+        "{}: cache misses at steady state",
+        name
+    );
+    assert_eq!(frame.uploaded_bytes, 0, "{}: uploads at steady state", name);
+    assert_eq!(
+        frame.downloaded_bytes, 0,
+        "{}: downloads at steady state",
+        name
+    );
+}
+
+fn steady_state(c: &mut Criterion, instance: &'static Instance) {
+    let device = &instance.device;
+
+    let mut group = c.benchmark_group("dispatch");
+
+    // particles
+    let mut particles = GpuBuffer::<Particles>::with_len(PARTICLES);
+    let mut uniforms = Buffer::<Uniforms>::new();
+    uniforms.delta_time = 0.016;
+
+    let raw_particles = buffer(
+        device,
+        PARTICLES as u64 * PARTICLE_STRIDE,
+        wgpu::BufferUsages::STORAGE,
+    );
+    let raw_uniforms = buffer(device, 4, wgpu::BufferUsages::UNIFORM);
+    let raw = RawKernel::new(
+        device,
+        <particles::update::Shader as ComputeShader>::SOURCE,
+        "update",
+        &[&raw_particles, &raw_uniforms],
+    );
+
+    group.bench_function(BenchmarkId::new("wgpu", "particles"), |b| {
+        b.iter(|| {
+            raw.dispatch(instance, dispatch_size(PARTICLES));
+            device.poll(wgpu::Maintain::Poll);
+        })
+    });
+
+    let dispatch = |particles: &mut GpuBuffer<Particles>| {
+        particles::update(
+            particles::update::Bindings {
+                particles,
+                uniforms: &uniforms,
+            },
+            dispatch_size(PARTICLES),
+        );
+        device.poll(wgpu::Maintain::Poll);
+    };
+
+    // creates the pipeline and bind group outside the measurement
+    dispatch(&mut particles);
+    instance.stats_frame();
+
+    group.bench_function(BenchmarkId::new("shatter", "particles"), |b| {
+        b.iter(|| dispatch(&mut particles))
+    });
+
+    assert_steady(instance, "dispatch/shatter/particles");
+
+    // memcpy
+    let src = words(WORDS);
+    let mut dst = GpuBuffer::<Words>::with_len(WORDS);
+
+    let raw_src = buffer(device, WORDS as u64 * 4, wgpu::BufferUsages::STORAGE);
+    let raw_dst = buffer(device, WORDS as u64 * 4, wgpu::BufferUsages::STORAGE);
+    let raw = RawKernel::new(
+        device,
+        <memcpy::copy::Shader as ComputeShader>::SOURCE,
+        "copy",
+        &[&raw_src, &raw_dst],
+    );
+
+    group.bench_function(BenchmarkId::new("wgpu", "memcpy"), |b| {
+        b.iter(|| {
+            raw.dispatch(instance, dispatch_size(WORDS));
+            device.poll(wgpu::Maintain::Poll);
+        })
+    });
+
+    let dispatch = |dst: &mut GpuBuffer<Words>| {
+        memcpy::copy(
+            memcpy::copy::Bindings { src: &src, dst },
+            dispatch_size(WORDS),
+        );
+        device.poll(wgpu::Maintain::Poll);
+    };
+
+    dispatch(&mut dst);
+    instance.stats_frame();
+
+    group.bench_function(BenchmarkId::new("shatter", "memcpy"), |b| {
+        b.iter(|| dispatch(&mut dst))
+    });
+
+    assert_steady(instance, "dispatch/shatter/memcpy");
+
+    group.finish();
+}
+
+fn first_dispatch(c: &mut Criterion, instance: &'static Instance) {
+    let device = &instance.device;
+    let source = <memcpy::copy::Shader as ComputeShader>::SOURCE;
+
+    let mut group = c.benchmark_group("first_dispatch");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    let raw_src = buffer(device, WORDS as u64 * 4, wgpu::BufferUsages::STORAGE);
+    let raw_dst = buffer(device, WORDS as u64 * 4, wgpu::BufferUsages::STORAGE);
+
+    group.bench_function(BenchmarkId::new("wgpu", "memcpy"), |b| {
+        b.iter(|| {
+            let raw = RawKernel::new(device, source, "copy", &[&raw_src, &raw_dst]);
+            raw.dispatch(instance, dispatch_size(WORDS));
+            device.poll(wgpu::Maintain::Wait);
+        })
+    });
+
+    let src = words(WORDS);
+    let mut dst = GpuBuffer::<Words>::with_len(WORDS);
+
+    // every iteration creates a shader, which is the point here
+    instance.set_cache_churn_hook(|_| {});
+
+    // a unique source per iteration misses the shader module and pipeline caches
+    let mut iteration = 0u64;
+
+    group.bench_function(BenchmarkId::new("shatter", "memcpy"), |b| {
+        b.iter(|| {
+            iteration += 1;
+
+            memcpy::copy::build(memcpy::copy::Bindings {
+                src: &src,
+                dst: &mut dst,
+            })
+            .with_source(format!("{}\n// {}\n", source, iteration))
+            .dispatch(dispatch_size(WORDS));
+            device.poll(wgpu::Maintain::Wait);
+        })
+    });
+
+    instance.clear_cache_churn_hook();
+
+    let frame = instance.stats_frame();
+    assert!(
+        frame.cache_misses >= iteration,
+        "first_dispatch/shatter/memcpy: pipelines were cached"
+    );
+
+    group.finish();
+}
+
+fn upload(c: &mut Criterion, instance: &'static Instance) {
+    let device = &instance.device;
+
+    let mut group = c.benchmark_group("upload");
+    group.sample_size(10);
+
+    for (name, size) in UPLOAD_SIZES {
+        group.throughput(Throughput::Bytes(size as u64));
+
+        let data = vec![1u8; size];
+        let raw = buffer(device, size as u64, wgpu::BufferUsages::COPY_DST);
+
+        group.bench_function(BenchmarkId::new("wgpu", name), |b| {
+            b.iter(|| {
+                instance.queue.write_buffer(&raw, 0, &data);
+                instance.queue.submit(None);
+                device.poll(wgpu::Maintain::Wait);
+            })
+        });
+
+        drop(raw);
+
+        let words = words(size / 4);
+
+        group.bench_function(BenchmarkId::new("shatter", name), |b| {
+            b.iter(|| {
+                words.mark_needs_upload();
+                black_box(words.upload());
+                instance.queue.submit(None);
+                device.poll(wgpu::Maintain::Wait);
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn readback(c: &mut Criterion, instance: &'static Instance) {
+    let device = &instance.device;
+
+    let mut group = c.benchmark_group("readback");
+
+    for (name, size) in READBACK_SIZES {
+        group.throughput(Throughput::Bytes(size as u64));
+
+        let raw = buffer(
+            device,
+            size as u64,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        );
+        let staging = buffer(
+            device,
+            size as u64,
+            wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        );
+
+        group.bench_function(BenchmarkId::new("wgpu", name), |b| {
+            b.iter(|| {
+                let mut encoder =
+                    device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+                encoder.copy_buffer_to_buffer(&raw, 0, &staging, 0, size as u64);
+                instance.queue.submit(Some(encoder.finish()));
+
+                let slice = staging.slice(..);
+                let mapped = slice.map_async(wgpu::MapMode::Read);
+                device.poll(wgpu::Maintain::Wait);
+                pollster::block_on(mapped).unwrap();
+
+                black_box(&slice.get_mapped_range()[..]);
+                staging.unmap();
+            })
+        });
+
+        let mut words = words(size / 4);
+
+        group.bench_function(BenchmarkId::new("shatter", name), |b| {
+            b.iter(|| {
+                words.mark_needs_download();
+                black_box(words.download());
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn criterion_dir() -> PathBuf {
+    env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("target"))
+        .join("criterion")
+}
+
+/// Mean time of a benchmark in nanoseconds, from the estimates saved by criterion.
+fn mean(dir: &Path, group: &str, side: &str, case: &str) -> Option<f64> {
+    let path = dir
+        .join(group)
+        .join(side)
+        .join(case)
+        .join("new/estimates.json");
+    let estimates: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+
+    estimates["mean"]["point_estimate"].as_f64()
+}
+
+fn format_time(nanos: f64) -> String {
+    if nanos >= 1e6 {
+        format!("{:.2} ms", nanos / 1e6)
+    } else if nanos >= 1e3 {
+        format!("{:.2} µs", nanos / 1e3)
+    } else {
+        format!("{:.0} ns", nanos)
+    }
+}
+
+/// Builds a markdown table of the measured cases, skipping those filtered out.
+fn table(instance: &Instance, dir: &Path) -> String {
+    let info = instance.adapter.get_info();
+
+    let mut table = format!(
+        "Measured on {} ({:?}), ratio is shatter over wgpu.\n\n\
+         | Benchmark | wgpu | shatter | Ratio |\n\
+         | --- | --: | --: | --: |\n",
+        info.name, info.backend
+    );
+
+    for (group, case) in CASES {
+        let (wgpu, shatter) = match (
+            mean(dir, group, "wgpu", case),
+            mean(dir, group, "shatter", case),
+        ) {
+            (Some(wgpu), Some(shatter)) => (wgpu, shatter),
+            _ => continue,
+        };
+
+        table += &format!(
+            "| {}/{} | {} | {} | {:.2} |\n",
+            group,
+            case,
+            format_time(wgpu),
+            format_time(shatter),
+            shatter / wgpu
+        );
+    }
+
+    table
+}
+
+/// Replaces the table between the markers in `README.md`.
+fn update_readme(table: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("README.md");
+    let readme = fs::read_to_string(&path).unwrap();
+
+    let start = readme.find(README_START).expect("missing start marker") + README_START.len();
+    let end = readme.find(README_END).expect("missing end marker");
+
+    let readme = format!("{}\n{}{}", &readme[..start], table, &readme[end..]);
+    fs::write(path, readme).unwrap();
+}
+
+fn main() {
+    let instance = match instance() {
+        Some(instance) => instance,
+        None => {
+            eprintln!("no adapter available, skipping");
+            return;
+        }
+    };
+
+    let dir = criterion_dir();
+    let mut c = Criterion::default()
+        .output_directory(&dir)
+        .configure_from_args();
+
+    steady_state(&mut c, instance);
+    first_dispatch(&mut c, instance);
+    upload(&mut c, instance);
+    readback(&mut c, instance);
+
+    c.final_summary();
+
+    let table = table(instance, &dir);
+    println!("\n{}", table);
+
+    if env::var_os("SHATTER_BENCH_README").is_some_and(|value| value != "0") {
+        update_readme(&table);
+    }
+}
@@ -0,0 +1 @@
+//! Depends on a wgpu version other than shatter's, see `tests/mismatch.rs`.
@@ -0,0 +1,7 @@
+// the `wgpu` dependency of this crate is deliberately not shatter's, pins the
+// error documented on `shatter::wgpu`
+#[test]
+fn mismatch() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,3 @@
+const _: fn(wgpu::Features) -> shatter::wgpu::Features = |features| features;
+
+fn main() {}
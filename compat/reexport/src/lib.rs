@@ -0,0 +1,35 @@
+//! Uses shatter's API through `shatter::wgpu` only, this crate doesn't depend
+//! on wgpu and must keep building as shatter moves between wgpu versions.
+
+use shatter::{
+    color::Rgba32, wgpu, BufferUsage, InstanceDescriptor, RenderShader, RenderShaderBuilder,
+    Sampler,
+};
+
+/// Requests a device that can collect pipeline statistics if the adapter allows.
+pub fn descriptor() -> InstanceDescriptor {
+    InstanceDescriptor {
+        features: wgpu::Features::empty(),
+        optional_features: wgpu::Features::PIPELINE_STATISTICS_QUERY,
+        limits: wgpu::Limits::downlevel_defaults(),
+        ..Default::default()
+    }
+}
+
+pub fn repeating_sampler() -> Sampler {
+    Sampler::new(&wgpu::SamplerDescriptor {
+        address_mode_u: wgpu::AddressMode::Repeat,
+        address_mode_v: wgpu::AddressMode::Repeat,
+        ..Default::default()
+    })
+}
+
+pub fn uniform_usages() -> wgpu::BufferUsages {
+    BufferUsage::Uniform.into()
+}
+
+pub fn clear_black<'a, S: RenderShader<'a>>(builder: &mut RenderShaderBuilder<'a, S>) {
+    builder
+        .with_clear(Rgba32::BLACK)
+        .with_clear(wgpu::Color::BLACK);
+}
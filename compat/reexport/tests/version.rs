@@ -0,0 +1,11 @@
+#[test]
+fn wgpu_version_matches_manifest() {
+    let manifest = include_str!("../../../Cargo.toml");
+    let requirement = format!("wgpu = \"{}\"", shatter::wgpu_version());
+
+    assert!(
+        manifest.lines().any(|line| line == requirement),
+        "shatter::wgpu_version() is out of date, expected `{}` in Cargo.toml",
+        requirement,
+    );
+}
@@ -0,0 +1,171 @@
+//! The separable Gaussian blur of the `blur` example, shared with its test.
+//!
+//! The horizontal pass reads the image and writes a scratch texture, the
+//! vertical pass reads the scratch texture and writes the image back, so
+//! chaining kernels only needs two textures however many passes there are.
+
+use shatter::*;
+
+wgsl_group! {
+    name: Params,
+    group: 0,
+
+    [[block]]
+    struct Blur {
+        radius: i32;
+        sigma: f32;
+    };
+
+    [[binding(0)]]
+    var<uniform> blur: Blur;
+}
+
+wgsl! {
+    use_group!(Params);
+
+    [[group(1), binding(0)]]
+    var input: texture_2d<f32>;
+
+    [[group(1), binding(1)]]
+    var output: texture_storage_2d<rgba8unorm, write>;
+
+    fn weight(offset: i32) -> f32 {
+        return exp(-f32(offset * offset) / (2.0 * blur.sigma * blur.sigma));
+    }
+
+    // sums the taps along `step`, clamping to the edge of the image
+    fn convolve(texel: vec2<i32>, step: vec2<i32>) -> vec4<f32> {
+        let last = textureDimensions(input) - vec2<i32>(1, 1);
+
+        var color = vec4<f32>(0.0);
+        var total = 0.0;
+
+        for (var i = -blur.radius; i <= blur.radius; i = i + 1) {
+            let tap = clamp(texel + step * i, vec2<i32>(0, 0), last);
+            let w = weight(i);
+
+            color = color + w * textureLoad(input, tap, 0);
+            total = total + w;
+        }
+
+        return color / total;
+    }
+
+    [[stage(compute), workgroup_size(8, 8, 1)]]
+    fn horizontal([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        let texel = vec2<i32>(id.xy);
+
+        if (any(texel >= textureDimensions(input))) {
+            return;
+        }
+
+        textureStore(output, texel, convolve(texel, vec2<i32>(1, 0)));
+    }
+
+    [[stage(compute), workgroup_size(8, 8, 1)]]
+    fn vertical([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        let texel = vec2<i32>(id.xy);
+
+        if (any(texel >= textureDimensions(input))) {
+            return;
+        }
+
+        textureStore(output, texel, convolve(texel, vec2<i32>(0, 1)));
+    }
+}
+
+/// A gradient with a checkerboard of `cell` sized squares, sharp edges show
+/// the blur best.
+pub fn test_image(width: usize, height: usize, cell: usize) -> Texture2d<Rgba8Unorm> {
+    let mut image = Texture2d::<Rgba8Unorm>::new(width, height);
+
+    for y in 0..height {
+        for x in 0..width {
+            let checker = (x / cell + y / cell).is_multiple_of(2);
+
+            image[(x, y)] = Rgba8U::rgb(
+                (x * 255 / width.max(2).saturating_sub(1)) as u8,
+                (y * 255 / height.max(2).saturating_sub(1)) as u8,
+                if checker { 255 } else { 0 },
+            );
+        }
+    }
+
+    image
+}
+
+/// Blurs `image` in place, `scratch` holds the horizontal pass and must be
+/// the same size.
+///
+/// Both passes are recorded into one submission. Each dispatch is its own
+/// compute pass, so the vertical pass sees everything the horizontal pass wrote.
+pub fn blur(
+    image: &mut Texture2d<Rgba8Unorm>,
+    scratch: &mut Texture2d<Rgba8Unorm>,
+    params: &Buffer<Blur>,
+) {
+    assert_eq!(
+        (image.width(), image.height()),
+        (scratch.width(), scratch.height()),
+        "the scratch texture must be the size of the image"
+    );
+
+    let extent = [image.width() as u64, image.height() as u64, 1];
+
+    submit_encoded("shatter_blur", |encoder| {
+        horizontal::build(horizontal::Bindings {
+            params: Params { blur: params },
+            input: image,
+            output: scratch,
+        })
+        .encoder(encoder)
+        .dispatch(horizontal::dispatch_for_extent(extent));
+
+        vertical::build(vertical::Bindings {
+            params: Params { blur: params },
+            input: scratch,
+            output: image,
+        })
+        .encoder(encoder)
+        .dispatch(vertical::dispatch_for_extent(extent));
+    });
+
+    // dispatches recorded into an encoder leave marking the outputs to us
+    scratch.mark_needs_download();
+    image.mark_needs_download();
+}
+
+/// Blurs tightly packed RGBA8 on the CPU like [`blur`], rounding to 8 bits
+/// between the passes like the scratch texture does.
+pub fn reference(rgba: &[u8], width: usize, height: usize, blur: &Blur) -> Vec<u8> {
+    let weights = (-blur.radius..=blur.radius)
+        .map(|i| (-((i * i) as f32) / (2.0 * blur.sigma * blur.sigma)).exp())
+        .collect::<Vec<_>>();
+    let total = weights.iter().sum::<f32>();
+
+    let pass = |src: &[u8], step: (isize, isize)| {
+        let mut dst = vec![0; src.len()];
+
+        for y in 0..height as isize {
+            for x in 0..width as isize {
+                for channel in 0..4 {
+                    let mut sum = 0.0;
+
+                    for (i, weight) in (-blur.radius as isize..).zip(&weights) {
+                        let tx = (x + step.0 * i).clamp(0, width as isize - 1) as usize;
+                        let ty = (y + step.1 * i).clamp(0, height as isize - 1) as usize;
+
+                        sum += weight * src[(ty * width + tx) * 4 + channel] as f32 / 255.0;
+                    }
+
+                    let index = (y as usize * width + x as usize) * 4 + channel;
+                    dst[index] = (sum / total * 255.0).round().clamp(0.0, 255.0) as u8;
+                }
+            }
+        }
+
+        dst
+    };
+
+    pass(&pass(rgba, (1, 0)), (0, 1))
+}
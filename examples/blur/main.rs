@@ -0,0 +1,59 @@
+//! Chains two image kernels, a separable Gaussian blur, see `kernels.rs`.
+//!
+//! Writes the input and the blurred image to `images/`.
+
+use std::time::Instant;
+
+use shatter::{
+    test_util::{diff_rgba8, texture_rgba8, write_png},
+    tune::{GpuTimer, Timer},
+    *,
+};
+
+mod kernels;
+
+use kernels::Blur;
+
+const WIDTH: usize = 512;
+const HEIGHT: usize = 384;
+
+fn main() {
+    // the passes are timed with timestamp queries if the adapter has them
+    Instance::init(&InstanceDescriptor {
+        optional_features: wgpu::Features::TIMESTAMP_QUERY,
+        ..Default::default()
+    });
+
+    let mut image = kernels::test_image(WIDTH, HEIGHT, 32);
+    let mut scratch = Texture2d::<Rgba8Unorm>::gpu_only(WIDTH, HEIGHT);
+
+    let input = texture_rgba8(&image);
+    write_png("images/blur_input.png", WIDTH as u32, HEIGHT as u32, &input).unwrap();
+
+    let mut params = Buffer::<Blur>::new();
+    params.radius = 8;
+    params.sigma = 4.0;
+
+    let mut timer = GpuTimer::new();
+
+    let start = Instant::now();
+    let gpu = timer.time(&mut || kernels::blur(&mut image, &mut scratch, &params));
+    let blurred = texture_rgba8(&image);
+
+    println!("blur and readback: {:?}", start.elapsed());
+
+    match gpu {
+        Some(gpu) => println!("gpu: {:?}", gpu),
+        None => println!("gpu: not timed, the adapter has no timestamp queries"),
+    }
+
+    write_png("images/blur.png", WIDTH as u32, HEIGHT as u32, &blurred).unwrap();
+
+    let reference = kernels::reference(&input, WIDTH, HEIGHT, &params);
+    let (diff, _) = diff_rgba8(&blurred, &reference, 2);
+
+    println!(
+        "{} of {} pixels differ from the CPU reference, by at most {}",
+        diff.differing, diff.pixels, diff.max_channel_diff
+    );
+}
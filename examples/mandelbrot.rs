@@ -1,6 +1,12 @@
-use std::{fs, path::Path};
+use std::{
+    fs,
+    path::Path,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
 
-use shatter::*;
+use shatter::{util::FrameReadbackQueue, *};
 
 // inspired by:
 // https://www.shadertoy.com/view/4df3Rn
@@ -83,6 +89,9 @@ macro_rules! seg {
     };
 }
 
+/// Frames being read back while the next ones are dispatched.
+const FRAMES_IN_FLIGHT: usize = 2;
+
 fn main() {
     seg! {
         "initialize",
@@ -101,22 +110,43 @@ fn main() {
 
     uniforms.position = Vec2::new(0.745, 0.186);
 
-    let dispatch = Dispatch::new(
-        texture.width() as u32 / mandelbrot::WORK_GROUP_SIZE.x,
-        texture.height() as u32 / mandelbrot::WORK_GROUP_SIZE.y,
-        1,
-    );
+    let dispatch =
+        mandelbrot::dispatch_for_extent([texture.width() as u64, texture.height() as u64, 1]);
 
     if !Path::new("images").exists() {
         fs::create_dir("images").unwrap();
     }
 
-    let file = fs::File::create("images/mandelbrot.gif").unwrap();
-    let mut encoder =
-        gif::Encoder::new(file, texture.width() as u16, texture.height() as u16, &[]).unwrap();
+    let width = texture.width() as u16;
+    let height = texture.height() as u16;
+
+    // a single frame waits for the encoder, anything more waits for the queue
+    let (frames_tx, frames_rx) = mpsc::sync_channel::<Vec<u8>>(1);
+
+    let encoder = thread::spawn(move || {
+        let file = fs::File::create("images/mandelbrot.gif").unwrap();
+        let mut encoder = gif::Encoder::new(file, width, height, &[]).unwrap();
+
+        encoder.set_repeat(gif::Repeat::Infinite).unwrap();
+
+        let mut encoding = Duration::ZERO;
+
+        for mut rgba in frames_rx {
+            let t = Instant::now();
+
+            let frame = gif::Frame::from_rgba(width, height, &mut rgba);
+            encoder.write_frame(&frame).unwrap();
+
+            encoding += t.elapsed();
+        }
+
+        encoding
+    });
 
-    encoder.set_repeat(gif::Repeat::Infinite).unwrap();
+    let mut readback = FrameReadbackQueue::new(FRAMES_IN_FLIGHT);
+    let mut gpu = Duration::ZERO;
 
+    let start = Instant::now();
     let frames = 200u32;
 
     for frame in 0..frames {
@@ -129,18 +159,35 @@ fn main() {
             uniforms: &uniforms,
         };
 
-        seg! {
-            "dispatch",
-            mandelbrot(bindings, dispatch);
-            let mut bytes = texture.bytes().to_vec();
-        }
+        let t = Instant::now();
 
-        seg! {
-            "encode frame",
-            let frame =
-                gif::Frame::from_rgba(texture.width() as u16, texture.height() as u16, &mut bytes);
+        mandelbrot(bindings, dispatch);
 
-            encoder.write_frame(&frame).unwrap();
+        // blocks on the oldest frame once FRAMES_IN_FLIGHT are in flight
+        let oldest = readback.submit(&texture);
+
+        gpu += t.elapsed();
+
+        if let Some(rgba) = oldest {
+            frames_tx.send(rgba).unwrap();
+        }
+
+        while let Some(rgba) = readback.try_recv() {
+            frames_tx.send(rgba).unwrap();
         }
     }
+
+    for rgba in readback.drain() {
+        frames_tx.send(rgba).unwrap();
+    }
+
+    drop(frames_tx);
+
+    let encoding = encoder.join().unwrap();
+    let total = start.elapsed();
+
+    // overlapping keeps the total near the slower side rather than the sum
+    println!("gpu and readback: {:?}", gpu);
+    println!("encode: {:?}", encoding);
+    println!("total: {:?} (sum {:?})", total, gpu + encoding);
 }
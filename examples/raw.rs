@@ -0,0 +1,39 @@
+use std::{fs, path::Path};
+
+use shatter::*;
+
+// only the buffer layout is declared at compile time, the kernel is loaded
+// from disk and can be edited without recompiling
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<f32>;
+    };
+}
+
+const LEN: usize = 1000;
+
+fn main() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/raw.wgsl");
+    let source = fs::read_to_string(path).unwrap();
+
+    let work_group_size = WorkGroupSize::new(64, 1, 1);
+    let shader = RawComputeShader::new(source, "square").with_work_group_size(work_group_size);
+
+    let mut input = Buffer::<Values>::new();
+    let mut output = Buffer::<Values>::new();
+
+    for i in 0..LEN {
+        input.push(i as f32);
+        output.push(0.0);
+    }
+
+    let bindings = DynamicBindings::new()
+        .storage_read(0, 0, &input)
+        .storage(0, 1, &mut output);
+
+    let dispatch = input.dispatch_for(work_group_size);
+    shader.dispatch(bindings, dispatch);
+
+    println!("{:?}", &output.values[..10]);
+}
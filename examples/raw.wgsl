@@ -0,0 +1,19 @@
+[[block]]
+struct Values {
+    values: array<f32>;
+};
+
+[[group(0), binding(0)]]
+var<storage, read> input: Values;
+
+[[group(0), binding(1)]]
+var<storage, read_write> output: Values;
+
+[[stage(compute), workgroup_size(64)]]
+fn square([[builtin(global_invocation_id)]] id: vec3<u32>) {
+    if (id.x >= arrayLength(&output.values)) {
+        return;
+    }
+
+    output.values[id.x] = input.values[id.x] * input.values[id.x];
+}
@@ -0,0 +1,162 @@
+use std::{fs, path::Path, time::Duration};
+
+use shatter::{util::SegmentedDispatch, *};
+
+// the mandelbrot example, accumulating its anti aliasing samples over
+// several submissions
+wgsl! {
+    [[block]]
+    struct Accum {
+        colors: array<vec4<f32>>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> accum: Accum;
+
+    [[block]]
+    struct Uniforms {
+        position: vec2<f32>;
+        zoom: f32;
+        width: u32;
+        height: u32;
+        // the segment, a range of samples
+        first_sample: u32;
+        samples: u32;
+    };
+
+    [[group(0), binding(1)]]
+    var<uniform> uniforms: Uniforms;
+
+    let SCALE = 4.0;
+    let AA = 4u;
+
+    [[stage(compute), workgroup_size(8, 8, 1)]]
+    fn accumulate([[builtin(global_invocation_id)]] param: vec3<u32>) {
+        if (param.x >= uniforms.width || param.y >= uniforms.height) {
+            return;
+        }
+
+        var color = vec3<f32>(0.0);
+
+        for (var s = 0u; s < uniforms.samples; s = s + 1u) {
+            let sample = uniforms.first_sample + s;
+
+            let x_offset = f32(sample % AA) / f32(AA) - 0.5;
+            let y_offset = f32(sample / AA) / f32(AA) - 0.5;
+
+            var x = (f32(param.x) + x_offset) / f32(uniforms.width) * SCALE - SCALE / 2.0;
+            var y = (f32(param.y) + y_offset) / f32(uniforms.height) * SCALE - SCALE / 2.0;
+
+            x = x / uniforms.zoom - uniforms.position.x;
+            y = y / uniforms.zoom - uniforms.position.y;
+
+            var l = 0.0;
+            var z = vec2<f32>(0.0);
+            for (var i = 0; i < 512; i = i + 1) {
+                z = vec2<f32>(
+                    z.x * z.x - z.y * z.y + x,
+                    z.y * z.x + z.x * z.y + y,
+                );
+
+                if (dot(z, z) > pow(256.0, 2.0)) {
+                    break;
+                }
+
+                l = l + 1.0;
+            }
+
+            if (l > 511.0) {
+                l = 0.0;
+            }
+
+            let smooth = l - log2(log2(dot(z, z))) + 4.0;
+
+            color = color + 0.5 + 0.5 * cos(3.0 + smooth * 0.15 + vec3<f32>(0.0, 0.6, 1.0));
+        }
+
+        let i = param.y * uniforms.width + param.x;
+        accum.colors[i] = accum.colors[i] + vec4<f32>(color, f32(uniforms.samples));
+    }
+}
+
+const SIZE: u32 = 256;
+
+/// Segments issued before the render is cancelled, out of 8.
+const CANCEL_AFTER: u64 = 5;
+
+fn main() {
+    let mut accum = Buffer::<Accum>::new();
+
+    for _ in 0..SIZE * SIZE {
+        accum.push(Vec4::<f32>::ZERO);
+    }
+
+    let mut uniforms = Buffer::<Uniforms>::new();
+    uniforms.position = Vec2::new(0.745, 0.186);
+    uniforms.zoom = 4.0f32.powi(8);
+    uniforms.width = SIZE;
+    uniforms.height = SIZE;
+
+    let dispatch = accumulate::dispatch_for_extent([SIZE as u64, SIZE as u64, 1]);
+
+    // two of the 16 samples per segment
+    let mut segments = SegmentedDispatch::new(16, 2).with_budget(Duration::from_millis(100));
+
+    while let Some(report) = segments.step(|segment| {
+        uniforms.first_sample = segment.offset as u32;
+        uniforms.samples = segment.len as u32;
+
+        let bindings = accumulate::Bindings {
+            accum: &mut accum,
+            uniforms: &uniforms,
+        };
+
+        accumulate(bindings, dispatch);
+    }) {
+        println!(
+            "segment {}: {:?}{} ({:.0}%)",
+            report.segment.index,
+            report.elapsed,
+            if report.over_budget {
+                ", over budget"
+            } else {
+                ""
+            },
+            segments.progress() * 100.0,
+        );
+
+        // e.g. the user navigated away
+        if report.segment.index + 1 == CANCEL_AFTER {
+            segments.cancel();
+        }
+    }
+
+    println!(
+        "cancelled with {} of {} samples",
+        segments.completed(),
+        segments.total()
+    );
+
+    // the partial result, averaged over the samples taken
+    let mut rgba = Vec::with_capacity(accum.colors.len() * 4);
+
+    for color in &accum.colors {
+        let samples = color.w.max(1.0);
+
+        for channel in [color.x, color.y, color.z] {
+            rgba.push((channel / samples * 255.0) as u8);
+        }
+
+        rgba.push(255);
+    }
+
+    if !Path::new("images").exists() {
+        fs::create_dir("images").unwrap();
+    }
+
+    let file = fs::File::create("images/segmented.gif").unwrap();
+    let mut encoder = gif::Encoder::new(file, SIZE as u16, SIZE as u16, &[]).unwrap();
+
+    let frame = gif::Frame::from_rgba(SIZE as u16, SIZE as u16, &mut rgba);
+    encoder.write_frame(&frame).unwrap();
+}
@@ -0,0 +1,94 @@
+use shatter::*;
+use winit::{
+    event::{Event, WindowEvent},
+    event_loop::{ControlFlow, EventLoop},
+    window::WindowBuilder,
+};
+
+wgsl! {
+    [[group(0), binding(0)]]
+    var frame: texture_storage_2d<rgba8unorm, write>;
+
+    [[block]]
+    struct Uniforms {
+        time: f32;
+    };
+
+    [[group(0), binding(1)]]
+    var<uniform> uniforms: Uniforms;
+
+    [[stage(compute), workgroup_size(8, 8, 1)]]
+    fn render([[builtin(global_invocation_id)]] param: vec3<u32>) {
+        let size = textureDimensions(frame);
+
+        if (i32(param.x) >= size.x || i32(param.y) >= size.y) {
+            return;
+        }
+
+        let uv = vec2<f32>(param.xy) / vec2<f32>(size);
+        let color = 0.5 + 0.5 * cos(uniforms.time + uv.xyx + vec3<f32>(0.0, 2.0, 4.0));
+
+        textureStore(frame, vec2<i32>(param.xy), vec4<f32>(color, 1.0));
+    }
+}
+
+fn main() {
+    let event_loop = EventLoop::new();
+    let window = WindowBuilder::new()
+        .with_title("shatter")
+        .build(&event_loop)
+        .unwrap();
+
+    let size = window.inner_size();
+
+    // SAFETY: the window outlives the surface, both are moved into the event loop
+    let mut surface = unsafe { Surface::new(&window, size.width, size.height) };
+
+    println!(
+        "surface format: {:?}, direct: {}",
+        surface.format(),
+        surface.is_direct()
+    );
+
+    let mut uniforms = Buffer::<Uniforms>::new();
+    let start = std::time::Instant::now();
+
+    event_loop.run(move |event, _, control_flow| {
+        *control_flow = ControlFlow::Poll;
+
+        match event {
+            Event::WindowEvent {
+                event: WindowEvent::CloseRequested,
+                ..
+            } => *control_flow = ControlFlow::Exit,
+            Event::WindowEvent {
+                event: WindowEvent::Resized(size),
+                ..
+            } => surface.resize(size.width.max(1), size.height.max(1)),
+            Event::MainEventsCleared => window.request_redraw(),
+            Event::RedrawRequested(_) => {
+                uniforms.time = start.elapsed().as_secs_f32();
+
+                let mut frame = match surface.frame() {
+                    Ok(frame) => frame,
+                    Err(wgpu::SurfaceError::Timeout) => return,
+                    Err(err) => panic!("failed to acquire frame: {}", err),
+                };
+
+                let dispatch =
+                    render::dispatch_for_extent([frame.width() as u64, frame.height() as u64, 1]);
+
+                render(
+                    render::Bindings {
+                        frame: &mut frame,
+                        uniforms: &uniforms,
+                    },
+                    dispatch,
+                );
+
+                frame.present();
+            }
+            _ => {}
+        }
+    });
+}
@@ -0,0 +1,76 @@
+use shatter::*;
+
+wgsl! {
+    for SIZE in [64, 128, 256];
+
+    struct Particle {
+        position: vec2<f32>;
+        velocity: vec2<f32>;
+    };
+
+    [[block]]
+    struct Particles {
+        particles: array<Particle>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> particles: Particles;
+
+    [[stage(compute), workgroup_size(SIZE)]]
+    fn step([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&particles.particles)) {
+            return;
+        }
+
+        let particle = particles.particles[id.x];
+        particles.particles[id.x].position = particle.position + particle.velocity;
+    }
+}
+
+const PARTICLES: u64 = 1_000_000;
+
+fn main() {
+    // the variants are timed with timestamp queries if the adapter has them
+    Instance::init(&InstanceDescriptor {
+        optional_features: wgpu::Features::TIMESTAMP_QUERY,
+        ..Default::default()
+    });
+
+    let mut particles = Buffer::<Particles>::new();
+    particles.extend((0..PARTICLES).map(|i| Particle {
+        position: Vec2::new(i as f32, 0.0),
+        velocity: Vec2::new(0.5, 0.25),
+    }));
+
+    let variants = step::SIZE
+        .iter()
+        .map(|&size| step::with_size(size).unwrap())
+        .collect::<Vec<_>>();
+
+    // timed on the first run, read from the cache file afterwards
+    let mut tuner = tune::Tuner::new().with_hook(|event| println!("{}", event));
+
+    let best = tuner.pick(&variants, [PARTICLES, 1, 1], |i, dispatch| {
+        let shader = step::with_size(step::SIZE[i]).unwrap();
+
+        shader.dispatch(
+            step::Bindings {
+                particles: &mut particles,
+            },
+            dispatch,
+        );
+    });
+
+    println!("work group size: {:?}", best);
+
+    for _ in 0..100 {
+        step::with_size(best.x).unwrap().dispatch(
+            step::Bindings {
+                particles: &mut particles,
+            },
+            Dispatch::try_linear(PARTICLES, best).unwrap(),
+        );
+    }
+
+    println!("{:?}", particles.particles[1]);
+}
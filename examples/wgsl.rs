@@ -1,8 +1,8 @@
-use std::time::Instant;
-
 use shatter::*;
 
 wgsl! {
+    #![auto_guard]
+
     struct Particle {
         position: vec2<f32>;
         velocity: vec2<f32>;
@@ -39,23 +39,18 @@ fn main() {
 
     uniforms.simulation_speed = 0.0;
 
-    for _ in 0..1_000_000 {
-        particles.push(Particle {
-            position: Vec2::new(0.0, 0.0),
-            velocity: Vec2::new(0.0, 0.0),
-            radius: 5.0,
-        });
-    }
+    particles.extend((0..1_000_000).map(|_| Particle {
+        position: Vec2::new(0.0, 0.0),
+        velocity: Vec2::new(0.0, 0.0),
+        radius: 5.0,
+    }));
 
     let bindings = comp::Bindings {
         particles: &mut particles,
         uniforms: &uniforms,
     };
 
-    comp(
-        bindings,
-        Dispatch::new(1_000_000 / comp::WORK_GROUP_SIZE.x, 1, 1),
-    );
+    comp(bindings, comp::dispatch_for(1_000_000));
 
     uniforms.simulation_speed = 2.0;
 
@@ -64,8 +59,7 @@ fn main() {
         uniforms: &uniforms,
     };
 
-    comp::build(bindings)
-        .dispatch_multiple(&[Dispatch::new(1_000_000 / comp::WORK_GROUP_SIZE.x, 1, 1); 100]);
+    comp::build(bindings).dispatch_multiple(&[comp::dispatch_for(1_000_000); 100]);
 
     println!("{:?}", &particles.particles[0]);
 }
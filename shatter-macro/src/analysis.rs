@@ -0,0 +1,687 @@
+//! Static checks for shaders naga either rejects with messages that don't point
+//! at the cause, or accepts but that are slow or unportable on the GPU. None of
+//! them change the generated code.
+
+use std::collections::HashMap;
+
+use naga::{
+    valid::{FunctionInfo, ModuleInfo, UniformityRequirements},
+    BinaryOperator, Block, ConstantInner, Expression, Function, Handle, LocalVariable, Module,
+    ScalarValue, Statement, TypeInner, UnaryOperator,
+};
+use proc_macro2::{Delimiter, Ident, TokenStream, TokenTree};
+use proc_macro_error::abort;
+
+/// Aborts if the functions of `input` call each other recursively, naming the
+/// functions of the cycle.
+///
+/// naga can only report the recursive call as an unknown identifier, as
+/// functions are in scope after their declaration, so this runs on the tokens
+/// before parsing.
+pub fn check_recursion(input: &TokenStream) {
+    let functions = functions(input);
+
+    // depth first search from every function, in source order
+    fn visit(
+        functions: &[(Ident, Vec<Ident>)],
+        index: usize,
+        path: &mut Vec<usize>,
+        done: &mut [bool],
+    ) {
+        path.push(index);
+
+        for call in &functions[index].1 {
+            let callee = match functions.iter().position(|(name, _)| name == call) {
+                Some(callee) => callee,
+                None => continue,
+            };
+
+            if callee == index {
+                abort!(
+                    call,
+                    "`{}` calls itself, WGSL doesn't support recursion", call;
+                    help = "rewrite it as a loop"
+                );
+            }
+
+            if let Some(start) = path.iter().position(|&i| i == callee) {
+                let cycle = path[start..]
+                    .iter()
+                    .chain(Some(&callee))
+                    .map(|&i| format!("`{}`", functions[i].0))
+                    .collect::<Vec<_>>();
+
+                abort!(
+                    call,
+                    "{} call each other recursively, WGSL doesn't support recursion",
+                    cycle.join(" -> ");
+                    help = "rewrite the cycle as a loop"
+                );
+            }
+
+            if !done[callee] {
+                visit(functions, callee, path, done);
+            }
+        }
+
+        path.pop();
+        done[index] = true;
+    }
+
+    let mut done = vec![false; functions.len()];
+
+    for index in 0..functions.len() {
+        if !done[index] {
+            visit(&functions, index, &mut Vec::new(), &mut done);
+        }
+    }
+}
+
+/// Gets the functions of `input` along with the identifiers they call.
+fn functions(input: &TokenStream) -> Vec<(Ident, Vec<Ident>)> {
+    let mut functions = Vec::new();
+    let mut tokens = input.clone().into_iter();
+
+    while let Some(tree) = tokens.next() {
+        match tree {
+            TokenTree::Ident(ident) if ident == "fn" => {}
+            _ => continue,
+        }
+
+        let name = match tokens.next() {
+            Some(TokenTree::Ident(name)) => name,
+            _ => continue,
+        };
+
+        let body = tokens.by_ref().find_map(|tree| match tree {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => Some(group),
+            _ => None,
+        });
+
+        let mut calls = Vec::new();
+
+        if let Some(body) = body {
+            collect_calls(body.stream(), &mut calls);
+        }
+
+        functions.push((name, calls));
+    }
+
+    functions
+}
+
+fn collect_calls(stream: TokenStream, calls: &mut Vec<Ident>) {
+    let mut tokens = stream.into_iter().peekable();
+
+    while let Some(tree) = tokens.next() {
+        match tree {
+            TokenTree::Ident(ident) => {
+                if let Some(TokenTree::Group(group)) = tokens.peek() {
+                    if group.delimiter() == Delimiter::Parenthesis {
+                        calls.push(ident);
+                    }
+                }
+            }
+            TokenTree::Group(group) => collect_calls(group.stream(), calls),
+            _ => {}
+        }
+    }
+}
+
+/// Constant bounded loop iterations run by a function, see [`loop_iterations`].
+pub struct LoopIterations {
+    /// Iterations of every constant bounded loop, multiplied by the iterations
+    /// of the loops around it.
+    pub total: u64,
+    /// Condition of the loop contributing the most iterations.
+    pub heaviest: Option<naga::Span>,
+}
+
+/// Estimates how many iterations of constant bounded loops `function` runs,
+/// including those of the functions it calls. Backends may unroll such loops,
+/// so large counts make pipeline creation slow.
+///
+/// Only `for` and `loop` loops counting a local variable with a constant start
+/// and step up to a constant bound are counted.
+pub fn loop_iterations(module: &Module, function: &Function) -> LoopIterations {
+    let mut counter = LoopCounter {
+        module,
+        function,
+        callees: HashMap::new(),
+        heaviest: None,
+    };
+
+    let total = counter.block(&function.body, 1);
+
+    LoopIterations {
+        total,
+        heaviest: counter.heaviest.map(|(_, span)| span),
+    }
+}
+
+struct LoopCounter<'a> {
+    module: &'a Module,
+    function: &'a Function,
+    callees: HashMap<Handle<Function>, u64>,
+    heaviest: Option<(u64, naga::Span)>,
+}
+
+impl LoopCounter<'_> {
+    fn block(&mut self, block: &Block, multiplier: u64) -> u64 {
+        let mut total = 0u64;
+
+        for (i, statement) in block.iter().enumerate() {
+            let iterations = match *statement {
+                Statement::Block(ref block) => self.block(block, multiplier),
+                Statement::If {
+                    ref accept,
+                    ref reject,
+                    ..
+                } => self
+                    .block(accept, multiplier)
+                    .saturating_add(self.block(reject, multiplier)),
+                Statement::Switch {
+                    ref cases,
+                    ref default,
+                    ..
+                } => cases
+                    .iter()
+                    .map(|case| &case.body)
+                    .chain(Some(default))
+                    .fold(0u64, |total, body| {
+                        total.saturating_add(self.block(body, multiplier))
+                    }),
+                Statement::Loop {
+                    ref body,
+                    ref continuing,
+                } => {
+                    let count =
+                        trip_count(self.module, self.function, &block[..i], body, continuing);
+
+                    let inner = multiplier.saturating_mul(count.map_or(1, |(count, _)| count));
+                    let own = if count.is_some() { inner } else { 0 };
+                    let iterations = own
+                        .saturating_add(self.block(body, inner))
+                        .saturating_add(self.block(continuing, inner));
+
+                    if let Some((_, condition)) = count {
+                        let span = self.function.expressions.get_span(condition);
+
+                        if self.heaviest.is_none_or(|(most, _)| iterations > most) {
+                            self.heaviest = Some((iterations, span));
+                        }
+                    }
+
+                    iterations
+                }
+                Statement::Call { function, .. } => {
+                    let module = self.module;
+
+                    let callee = *self.callees.entry(function).or_insert_with(|| {
+                        loop_iterations(module, &module.functions[function]).total
+                    });
+
+                    multiplier.saturating_mul(callee)
+                }
+                _ => 0,
+            };
+
+            total = total.saturating_add(iterations);
+        }
+
+        total
+    }
+}
+
+/// Gets the trip count of a loop counting a local variable from a constant to
+/// a constant bound, along with the condition of the loop.
+fn trip_count(
+    module: &Module,
+    function: &Function,
+    preceding: &[Statement],
+    body: &Block,
+    continuing: &Block,
+) -> Option<(u64, Handle<Expression>)> {
+    // the first statement breaks out of the loop, `for` loops break unless
+    // the condition holds and `if (cond) { break; }` loops break if it does
+    let (condition, breaks_if) = match body.iter().find(|s| !matches!(s, Statement::Emit(_)))? {
+        Statement::If {
+            condition,
+            accept,
+            reject,
+        } => match (&accept[..], &reject[..]) {
+            ([], [Statement::Break]) => (*condition, false),
+            ([Statement::Break], []) => (*condition, true),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let (local, op, bound) = counted_condition(module, function, condition, breaks_if)?;
+
+    let start = match preceding
+        .iter()
+        .rev()
+        .find_map(|statement| stored_value(function, statement, local))
+    {
+        Some(value) => constant_value(module, function, value)?,
+        None => {
+            let init = function.local_variables[local].init?;
+
+            scalar_value(&module.constants[init].inner)?
+        }
+    };
+
+    // a single step, either in `continuing` or at the end of the body
+    let mut steps = continuing
+        .iter()
+        .chain(body.iter())
+        .filter_map(|statement| stored_value(function, statement, local));
+
+    let step = steps.next()?;
+
+    if steps.next().is_some() {
+        return None;
+    }
+
+    let step = match function.expressions[step] {
+        Expression::Binary {
+            op: op @ (BinaryOperator::Add | BinaryOperator::Subtract),
+            left,
+            right,
+        } if loaded_local(function, left) == Some(local) => {
+            let step = constant_value(module, function, right)?;
+
+            if op == BinaryOperator::Subtract {
+                step.checked_neg()?
+            } else {
+                step
+            }
+        }
+        _ => return None,
+    };
+
+    let count = match op {
+        BinaryOperator::Less if step > 0 => (bound - start + step - 1) / step,
+        BinaryOperator::LessEqual if step > 0 => (bound - start) / step + 1,
+        BinaryOperator::Greater if step < 0 => (start - bound - step - 1) / -step,
+        BinaryOperator::GreaterEqual if step < 0 => (start - bound) / -step + 1,
+        BinaryOperator::NotEqual if step != 0 && (bound - start) % step == 0 => {
+            (bound - start) / step
+        }
+        _ => return None,
+    };
+
+    Some((count.max(0) as u64, condition))
+}
+
+/// Splits a loop condition comparing a local to a constant into the local,
+/// the comparison with the local on the left, and the bound. `negate` gives
+/// the comparison for which the loop continues when it breaks if `condition`.
+fn counted_condition(
+    module: &Module,
+    function: &Function,
+    condition: Handle<Expression>,
+    negate: bool,
+) -> Option<(Handle<LocalVariable>, BinaryOperator, i64)> {
+    let (op, left, right, negate) = match function.expressions[condition] {
+        Expression::Unary {
+            op: UnaryOperator::Not,
+            expr,
+        } => match function.expressions[expr] {
+            Expression::Binary { op, left, right } => (op, left, right, !negate),
+            _ => return None,
+        },
+        Expression::Binary { op, left, right } => (op, left, right, negate),
+        _ => return None,
+    };
+
+    let (local, op, bound) = match (loaded_local(function, left), loaded_local(function, right)) {
+        (Some(local), _) => (local, op, constant_value(module, function, right)?),
+        (None, Some(local)) => {
+            let flipped = match op {
+                BinaryOperator::Less => BinaryOperator::Greater,
+                BinaryOperator::LessEqual => BinaryOperator::GreaterEqual,
+                BinaryOperator::Greater => BinaryOperator::Less,
+                BinaryOperator::GreaterEqual => BinaryOperator::LessEqual,
+                op => op,
+            };
+
+            (local, flipped, constant_value(module, function, left)?)
+        }
+        _ => return None,
+    };
+
+    let op = if negate {
+        match op {
+            BinaryOperator::Less => BinaryOperator::GreaterEqual,
+            BinaryOperator::LessEqual => BinaryOperator::Greater,
+            BinaryOperator::Greater => BinaryOperator::LessEqual,
+            BinaryOperator::GreaterEqual => BinaryOperator::Less,
+            BinaryOperator::Equal => BinaryOperator::NotEqual,
+            _ => return None,
+        }
+    } else {
+        op
+    };
+
+    Some((local, op, bound))
+}
+
+/// Gets the value `statement` stores to `local`.
+fn stored_value(
+    function: &Function,
+    statement: &Statement,
+    local: Handle<LocalVariable>,
+) -> Option<Handle<Expression>> {
+    match *statement {
+        Statement::Store { pointer, value } => match function.expressions[pointer] {
+            Expression::LocalVariable(stored) if stored == local => Some(value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn loaded_local(function: &Function, expr: Handle<Expression>) -> Option<Handle<LocalVariable>> {
+    match function.expressions[expr] {
+        Expression::Load { pointer } => match function.expressions[pointer] {
+            Expression::LocalVariable(local) => Some(local),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn constant_value(module: &Module, function: &Function, expr: Handle<Expression>) -> Option<i64> {
+    match function.expressions[expr] {
+        Expression::Constant(constant) => scalar_value(&module.constants[constant].inner),
+        _ => None,
+    }
+}
+
+fn scalar_value(constant: &ConstantInner) -> Option<i64> {
+    match *constant {
+        ConstantInner::Scalar {
+            value: ScalarValue::Sint(value),
+            ..
+        } => Some(value),
+        ConstantInner::Scalar {
+            value: ScalarValue::Uint(value),
+            ..
+        } => i64::try_from(value).ok(),
+        _ => None,
+    }
+}
+
+/// A branch on a non-uniform value around a work group barrier.
+pub struct NonUniformBarrier {
+    /// The condition or selector of the branch.
+    pub condition: Handle<Expression>,
+    /// Describes the non-uniform value the branch depends on, e.g. `` `id.x` ``.
+    pub value: String,
+    /// The branch leaves the function or loop before a later barrier, rather
+    /// than containing one.
+    pub exits_early: bool,
+}
+
+/// Finds `if` and `switch` statements of `function` branching on non-uniform
+/// values, i.e. values differing between the invocations of a work group,
+/// around work group barriers. Invocations skipping a barrier others wait on
+/// hang or crash on some GPUs.
+///
+/// Values derived from `var` locals aren't tracked, as naga treats every
+/// local as non-uniform.
+pub fn non_uniform_barriers(
+    module: &Module,
+    info: &ModuleInfo,
+    function: &Function,
+    function_info: &FunctionInfo,
+) -> Vec<NonUniformBarrier> {
+    let checker = UniformityChecker {
+        module,
+        info,
+        function,
+        function_info,
+    };
+
+    let mut found = Vec::new();
+    checker.block(&function.body, false, &mut found);
+
+    found
+}
+
+struct UniformityChecker<'a> {
+    module: &'a Module,
+    info: &'a ModuleInfo,
+    function: &'a Function,
+    function_info: &'a FunctionInfo,
+}
+
+impl UniformityChecker<'_> {
+    /// `barrier_after` is true if a barrier may run after the block.
+    fn block(&self, block: &Block, barrier_after: bool, found: &mut Vec<NonUniformBarrier>) {
+        for (i, statement) in block.iter().enumerate() {
+            let barrier_after = barrier_after || block[i + 1..].iter().any(|s| self.has_barrier(s));
+
+            match *statement {
+                Statement::Block(ref block) => self.block(block, barrier_after, found),
+                Statement::If {
+                    condition,
+                    ref accept,
+                    ref reject,
+                } => self.branch(condition, &[accept, reject], barrier_after, found),
+                Statement::Switch {
+                    selector,
+                    ref cases,
+                    ref default,
+                } => {
+                    let bodies = cases
+                        .iter()
+                        .map(|case| &case.body)
+                        .chain(Some(default))
+                        .collect::<Vec<_>>();
+
+                    self.branch(selector, &bodies, barrier_after, found);
+                }
+                Statement::Loop {
+                    ref body,
+                    ref continuing,
+                } => {
+                    // the next iteration runs after the body
+                    let barrier_after = barrier_after
+                        || body
+                            .iter()
+                            .chain(continuing.iter())
+                            .any(|s| self.has_barrier(s));
+
+                    self.block(body, barrier_after, found);
+                    self.block(continuing, barrier_after, found);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn branch(
+        &self,
+        condition: Handle<Expression>,
+        bodies: &[&Block],
+        barrier_after: bool,
+        found: &mut Vec<NonUniformBarrier>,
+    ) {
+        if let Some(value) = self.non_uniform_value(condition) {
+            let contains_barrier = bodies
+                .iter()
+                .any(|body| body.iter().any(|s| self.has_barrier(s)));
+            let exits = bodies.iter().any(|body| body.iter().any(exits));
+
+            if contains_barrier || (barrier_after && exits) {
+                found.push(NonUniformBarrier {
+                    condition,
+                    value,
+                    exits_early: !contains_barrier,
+                });
+            }
+        }
+
+        for body in bodies {
+            self.block(body, barrier_after, found);
+        }
+    }
+
+    fn has_barrier(&self, statement: &Statement) -> bool {
+        match *statement {
+            Statement::Barrier(_) => true,
+            Statement::Block(ref block) => block.iter().any(|s| self.has_barrier(s)),
+            Statement::If {
+                ref accept,
+                ref reject,
+                ..
+            } => accept
+                .iter()
+                .chain(reject.iter())
+                .any(|s| self.has_barrier(s)),
+            Statement::Switch {
+                ref cases,
+                ref default,
+                ..
+            } => cases
+                .iter()
+                .flat_map(|case| case.body.iter())
+                .chain(default.iter())
+                .any(|s| self.has_barrier(s)),
+            Statement::Loop {
+                ref body,
+                ref continuing,
+            } => body
+                .iter()
+                .chain(continuing.iter())
+                .any(|s| self.has_barrier(s)),
+            Statement::Call { function, .. } => self.info[function]
+                .uniformity
+                .requirements
+                .contains(UniformityRequirements::WORK_GROUP_BARRIER),
+            _ => false,
+        }
+    }
+
+    /// Names the first non-uniform value `expr` depends on.
+    fn non_uniform_value(&self, expr: Handle<Expression>) -> Option<String> {
+        let is_source = self.function_info[expr].uniformity.non_uniform_result == Some(expr);
+
+        match self.function.expressions[expr] {
+            // naga treats every local as non-uniform
+            Expression::LocalVariable(_) => return None,
+            Expression::FunctionArgument(index) if is_source => {
+                let argument = &self.function.arguments[index as usize];
+
+                return Some(format!(
+                    "`{}`",
+                    argument.name.as_deref().unwrap_or_default()
+                ));
+            }
+            Expression::GlobalVariable(handle) if is_source => {
+                let variable = &self.module.global_variables[handle];
+
+                return Some(format!(
+                    "`{}`",
+                    variable.name.as_deref().unwrap_or_default()
+                ));
+            }
+            Expression::AtomicResult { .. } if is_source => {
+                return Some(String::from("the result of an atomic operation"));
+            }
+            Expression::AccessIndex { base, index } => {
+                // members of struct arguments, e.g. builtins of a struct
+                if let Expression::FunctionArgument(argument) = self.function.expressions[base] {
+                    let argument = &self.function.arguments[argument as usize];
+
+                    if let TypeInner::Struct { ref members, .. } =
+                        self.module.types[argument.ty].inner
+                    {
+                        self.function_info[expr].uniformity.non_uniform_result?;
+
+                        return Some(format!(
+                            "`{}.{}`",
+                            argument.name.as_deref().unwrap_or_default(),
+                            members[index as usize].name.as_deref().unwrap_or_default(),
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        children(&self.function.expressions[expr])
+            .into_iter()
+            .find_map(|child| self.non_uniform_value(child))
+    }
+}
+
+/// Returns true if `statement` may leave the enclosing function or loop.
+fn exits(statement: &Statement) -> bool {
+    match *statement {
+        Statement::Return { .. } | Statement::Kill | Statement::Break | Statement::Continue => true,
+        Statement::Block(ref block) => block.iter().any(exits),
+        Statement::If {
+            ref accept,
+            ref reject,
+            ..
+        } => accept.iter().chain(reject.iter()).any(exits),
+        Statement::Switch {
+            ref cases,
+            ref default,
+            ..
+        } => cases
+            .iter()
+            .flat_map(|case| case.body.iter())
+            .chain(default.iter())
+            .any(exits),
+        // breaking out of an inner loop stays in the function
+        Statement::Loop {
+            ref body,
+            ref continuing,
+        } => body
+            .iter()
+            .chain(continuing.iter())
+            .any(|s| matches!(s, Statement::Return { .. } | Statement::Kill)),
+        _ => false,
+    }
+}
+
+fn children(expr: &Expression) -> Vec<Handle<Expression>> {
+    match *expr {
+        Expression::Access { base, index } => vec![base, index],
+        Expression::AccessIndex { base, .. } => vec![base],
+        Expression::Splat { value, .. } => vec![value],
+        Expression::Swizzle { vector, .. } => vec![vector],
+        Expression::Compose { ref components, .. } => components.clone(),
+        Expression::Load { pointer } => vec![pointer],
+        Expression::ImageLoad {
+            image,
+            coordinate,
+            array_index,
+            index,
+        } => [Some(image), Some(coordinate), array_index, index]
+            .into_iter()
+            .flatten()
+            .collect(),
+        Expression::ImageQuery { image, .. } => vec![image],
+        Expression::Unary { expr, .. } => vec![expr],
+        Expression::Binary { left, right, .. } => vec![left, right],
+        Expression::Select {
+            condition,
+            accept,
+            reject,
+        } => vec![condition, accept, reject],
+        Expression::Derivative { expr, .. } => vec![expr],
+        Expression::Relational { argument, .. } => vec![argument],
+        Expression::Math {
+            arg, arg1, arg2, ..
+        } => [Some(arg), arg1, arg2].into_iter().flatten().collect(),
+        Expression::As { expr, .. } => vec![expr],
+        Expression::ArrayLength(expr) => vec![expr],
+        _ => Vec::new(),
+    }
+}
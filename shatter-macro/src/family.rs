@@ -0,0 +1,111 @@
+use proc_macro2::{Delimiter, Group, Ident, Literal, TokenStream, TokenTree};
+use proc_macro_error::abort;
+
+/// A shader family, `for PARAM in [values...]; <wgsl>`.
+pub struct Family {
+    pub param: Ident,
+    pub values: Vec<(u32, Literal)>,
+    pub body: TokenStream,
+}
+
+impl Family {
+    /// Parses a family header, returns `None` if `input` doesn't start with `for`.
+    pub fn parse(input: &TokenStream) -> Option<Self> {
+        let mut tokens = input.clone().into_iter();
+
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) if ident == "for" => {}
+            _ => return None,
+        }
+
+        let param = match tokens.next() {
+            Some(TokenTree::Ident(ident)) => ident,
+            Some(tree) => abort!(tree, "expected a parameter name"),
+            None => abort!(input, "expected a parameter name"),
+        };
+
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) if ident == "in" => {}
+            Some(tree) => abort!(tree, "expected `in`"),
+            None => abort!(param, "expected `in`"),
+        }
+
+        let values = match tokens.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => {
+                Self::parse_values(&group)
+            }
+            Some(tree) => abort!(tree, "expected a list of values, e.g. `[1, 2, 4]`"),
+            None => abort!(param, "expected a list of values, e.g. `[1, 2, 4]`"),
+        };
+
+        match tokens.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ';' => {}
+            Some(tree) => abort!(tree, "expected `;`"),
+            None => abort!(param, "expected `;`"),
+        }
+
+        if values.is_empty() {
+            abort!(param, "shader family `{}` has no values", param);
+        }
+
+        Some(Self {
+            param,
+            values,
+            body: tokens.collect(),
+        })
+    }
+
+    fn parse_values(group: &Group) -> Vec<(u32, Literal)> {
+        let mut values: Vec<(u32, Literal)> = Vec::new();
+
+        for tree in group.stream() {
+            match tree {
+                TokenTree::Punct(punct) if punct.as_char() == ',' => {}
+                TokenTree::Literal(literal) => {
+                    let value = match syn::parse_str::<syn::LitInt>(&literal.to_string()) {
+                        Ok(lit) => lit.base10_parse::<u32>(),
+                        Err(err) => Err(err),
+                    };
+
+                    let value = value.unwrap_or_else(|_| {
+                        abort!(literal, "family values must be unsigned integers")
+                    });
+
+                    if values.iter().any(|(other, _)| *other == value) {
+                        abort!(literal, "duplicate family value `{}`", value);
+                    }
+
+                    values.push((value, literal));
+                }
+                tree => abort!(tree, "family values must be unsigned integers"),
+            }
+        }
+
+        values
+    }
+
+    /// Gets the body with every occurrence of the parameter replaced by `value`.
+    pub fn instantiate(&self, value: &Literal) -> TokenStream {
+        substitute(self.body.clone(), &self.param, value)
+    }
+}
+
+fn substitute(stream: TokenStream, param: &Ident, value: &Literal) -> TokenStream {
+    stream
+        .into_iter()
+        .map(|tree| match tree {
+            TokenTree::Ident(ident) if ident == *param => {
+                let mut value = value.clone();
+                value.set_span(ident.span());
+                TokenTree::Literal(value)
+            }
+            TokenTree::Group(group) => {
+                let mut new =
+                    Group::new(group.delimiter(), substitute(group.stream(), param, value));
+                new.set_span(group.span());
+                TokenTree::Group(new)
+            }
+            tree => tree,
+        })
+        .collect()
+}
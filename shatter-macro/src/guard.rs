@@ -0,0 +1,168 @@
+use std::str::FromStr;
+
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+
+/// Guards every compute entry point of `input` against invocations past the
+/// element count, for `#![auto_guard]`.
+///
+/// The count is read from the launch parameters, see [`crate::launch`]. Each
+/// entry point returns early if its flattened global invocation index is at
+/// least the count, reusing the `global_invocation_id` and `num_workgroups`
+/// parameters if declared and adding them otherwise.
+pub fn inject(input: &TokenStream) -> TokenStream {
+    let mut work_group_size = None;
+    let mut output = Vec::new();
+
+    let mut tokens = input.clone().into_iter();
+
+    while let Some(tree) = tokens.next() {
+        match tree {
+            TokenTree::Group(ref group) if group.delimiter() == Delimiter::Bracket => {
+                if let Some(attributes) = attributes(group) {
+                    for (name, args) in attributes {
+                        if name == "workgroup_size" {
+                            let size = |i: usize| args.get(i).copied().unwrap_or(1);
+                            work_group_size = Some([size(0), size(1), size(2)]);
+                        }
+                    }
+                }
+
+                output.push(tree);
+            }
+            TokenTree::Ident(ref ident) if ident == "fn" && work_group_size.is_some() => {
+                let size = work_group_size.take().unwrap();
+
+                output.push(tree);
+                output.extend(tokens.next());
+
+                // parameters and body
+                let mut guarded = None;
+
+                for tree in tokens.by_ref() {
+                    match tree {
+                        TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+                            let (params, guard) = guard_params(&group, size);
+                            guarded = Some(guard);
+
+                            output.push(TokenTree::Group(params));
+                        }
+                        TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                            let mut body = guarded.take().unwrap_or_default();
+                            body.extend(group.stream());
+
+                            let mut body = Group::new(Delimiter::Brace, body);
+                            body.set_span(group.span());
+
+                            output.push(TokenTree::Group(body));
+                            break;
+                        }
+                        tree => output.push(tree),
+                    }
+                }
+            }
+            tree => output.push(tree),
+        }
+    }
+
+    output.into_iter().collect()
+}
+
+/// Parses `[[name(args), ..]]` into the names and their integer arguments,
+/// `None` if `group` isn't an attribute.
+pub fn attributes(group: &Group) -> Option<Vec<(String, Vec<u32>)>> {
+    let mut trees = group.stream().into_iter();
+
+    let inner = match (trees.next(), trees.next()) {
+        (Some(TokenTree::Group(inner)), None) if inner.delimiter() == Delimiter::Bracket => inner,
+        _ => return None,
+    };
+
+    let mut attributes = Vec::new();
+    let mut trees = inner.stream().into_iter().peekable();
+
+    while let Some(tree) = trees.next() {
+        let name = match tree {
+            TokenTree::Ident(ident) => ident.to_string(),
+            _ => continue,
+        };
+
+        let args = match trees.peek() {
+            Some(TokenTree::Group(args)) if args.delimiter() == Delimiter::Parenthesis => args
+                .stream()
+                .into_iter()
+                .filter_map(|tree| match tree {
+                    TokenTree::Literal(literal) => literal
+                        .to_string()
+                        .trim_end_matches(|c: char| c.is_alphabetic())
+                        .parse()
+                        .ok(),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        attributes.push((name, args));
+    }
+
+    Some(attributes)
+}
+
+/// Gets the name of the parameter with `builtin` in `params`.
+fn builtin_param(params: &[TokenTree], builtin: &str) -> Option<String> {
+    params.windows(2).find_map(|window| match window {
+        [TokenTree::Group(group), TokenTree::Ident(name)] => {
+            let is_builtin = attributes(group)?.iter().any(|(name, _)| name == "builtin")
+                && group.stream().to_string().contains(builtin);
+
+            is_builtin.then(|| name.to_string())
+        }
+        _ => None,
+    })
+}
+
+/// Adds the builtins the guard needs to `params`, returns the parameters and
+/// the guard statement.
+fn guard_params(params: &Group, size: [u32; 3]) -> (Group, TokenStream) {
+    let span = params.span();
+    let mut trees = params.stream().into_iter().collect::<Vec<_>>();
+
+    let mut builtin = |builtin: &str, default: &str| match builtin_param(&trees, builtin) {
+        Some(name) => name,
+        None => {
+            let trailing_comma =
+                matches!(trees.last(), Some(TokenTree::Punct(punct)) if punct.as_char() == ',');
+
+            let param = format!(
+                "{}[[builtin({})]] {}: vec3<u32>",
+                if trees.is_empty() || trailing_comma {
+                    ""
+                } else {
+                    ", "
+                },
+                builtin,
+                default
+            );
+
+            trees.extend(TokenStream::from_str(&param).unwrap());
+
+            String::from(default)
+        }
+    };
+
+    let id = builtin("global_invocation_id", "shatter_guard_id");
+    let groups = builtin("num_workgroups", "shatter_guard_groups");
+
+    let guard = format!(
+        "if ({id}.x + ({id}.y + {id}.z * {groups}.y * {y}u) * {groups}.x * {x}u >= shatter_guard_count()) {{ return; }}",
+        id = id,
+        groups = groups,
+        x = size[0],
+        y = size[1],
+    );
+
+    let mut params = Group::new(Delimiter::Parenthesis, trees.into_iter().collect());
+    params.set_span(span);
+
+    (params, TokenStream::from_str(&guard).unwrap())
+}
@@ -0,0 +1,176 @@
+//! Expansion for IDEs.
+//!
+//! rust-analyzer expands every `wgsl!` block again as the crate is edited, but
+//! completions only need the names and types of the generated items. When
+//! expanding for it, the shader is validated only as far as generating needs,
+//! e.g. the globals used by every entry point, spans aren't tracked, the
+//! checks and warnings are skipped and families are parsed once. The items are
+//! the same as in a build, which still reports every error.
+
+/// Environment variable forcing the IDE expansion on with `1` or off with `0`.
+pub const IDE_EXPANSION_VAR: &str = "SHATTER_IDE_EXPANSION";
+
+/// Returns true if the macro is expanded by an IDE rather than a build.
+pub fn is_ide_expansion() -> bool {
+    match std::env::var(IDE_EXPANSION_VAR).as_deref() {
+        Ok("1") => return true,
+        Ok("0") => return false,
+        _ => {}
+    }
+
+    // rust-analyzer expands proc macros in its own server process
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.file_stem()?.to_string_lossy().into_owned()))
+        .is_some_and(|name| name.contains("rust-analyzer") || name.contains("proc-macro-srv"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, fmt::Write};
+
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
+    use syn::{ImplItem, Item, Visibility};
+
+    /// A shader of about `blocks * 20` lines, each block declaring constants,
+    /// structs, a helper function and an entry point with its own binding.
+    fn shader(blocks: usize) -> String {
+        let mut source = String::new();
+
+        for i in 0..blocks {
+            write!(
+                source,
+                "
+                let DAMPING_{i}: f32 = 0.0{i};
+
+                struct Item{i} {{
+                    position: vec4<f32>;
+                    velocity: vec4<f32>;
+                    mass: f32;
+                    id: u32;
+                }};
+
+                [[block]]
+                struct Items{i} {{
+                    items: array<Item{i}>;
+                }};
+
+                [[group(0), binding({i})]]
+                var<storage, read_write> items_{i}: Items{i};
+
+                fn step_{i}(item: Item{i}, dt: f32) -> Item{i} {{
+                    var out = item;
+                    out.position = item.position + item.velocity * dt;
+                    out.velocity = item.velocity * (1.0 - DAMPING_{i} * item.mass);
+                    return out;
+                }}
+
+                [[stage(compute), workgroup_size(64)]]
+                fn main_{i}([[builtin(global_invocation_id)]] id: vec3<u32>) {{
+                    if (id.x >= arrayLength(&items_{i}.items)) {{
+                        return;
+                    }}
+
+                    items_{i}.items[id.x] = step_{i}(items_{i}.items[id.x], 0.016);
+                }}
+                ",
+                i = i,
+            )
+            .unwrap();
+        }
+
+        source
+    }
+
+    /// Paths of the public items of `tokens`, along with the items of impls.
+    fn public_items(tokens: TokenStream) -> BTreeSet<String> {
+        let file = syn::parse2::<syn::File>(tokens).unwrap();
+
+        let mut names = BTreeSet::new();
+        collect(&file.items, "", &mut names);
+
+        names
+    }
+
+    fn collect(items: &[Item], path: &str, names: &mut BTreeSet<String>) {
+        for item in items {
+            let (vis, ident) = match item {
+                Item::Const(item) => (&item.vis, &item.ident),
+                Item::Enum(item) => (&item.vis, &item.ident),
+                Item::Fn(item) => (&item.vis, &item.sig.ident),
+                Item::Static(item) => (&item.vis, &item.ident),
+                Item::Struct(item) => (&item.vis, &item.ident),
+                Item::Trait(item) => (&item.vis, &item.ident),
+                Item::Type(item) => (&item.vis, &item.ident),
+                Item::Mod(item) => {
+                    if let (Visibility::Public(_), Some((_, content))) = (&item.vis, &item.content)
+                    {
+                        let path = format!("{}{}::", path, item.ident);
+
+                        names.insert(path.clone());
+                        collect(content, &path, names);
+                    }
+
+                    continue;
+                }
+                Item::Impl(item) => {
+                    let target = match item.trait_ {
+                        Some((_, ref trait_, _)) => format!(
+                            "<{} as {}>",
+                            item.self_ty.to_token_stream(),
+                            trait_.to_token_stream()
+                        ),
+                        None => item.self_ty.to_token_stream().to_string(),
+                    };
+
+                    for impl_item in &item.items {
+                        let ident = match impl_item {
+                            ImplItem::Const(item) => &item.ident,
+                            ImplItem::Method(item) => &item.sig.ident,
+                            ImplItem::Type(item) => &item.ident,
+                            _ => continue,
+                        };
+
+                        names.insert(format!("{}{}::{}", path, target, ident));
+                    }
+
+                    continue;
+                }
+                _ => continue,
+            };
+
+            if matches!(vis, Visibility::Public(_)) && ident != "_" {
+                names.insert(format!("{}{}", path, ident));
+            }
+        }
+    }
+
+    fn expand_both(input: &str) -> (BTreeSet<String>, BTreeSet<String>) {
+        let input = input.parse::<TokenStream>().unwrap();
+
+        let full = crate::expand(input.clone(), false);
+        let ide = crate::expand(input, true);
+
+        (public_items(full), public_items(ide))
+    }
+
+    #[test]
+    fn ide_expansion_has_the_same_items() {
+        let (full, ide) = expand_both(&shader(25));
+
+        assert!(full.contains("main_0::Bindings"));
+        assert!(full.contains("main_24::WORK_GROUP_SIZE"));
+        assert_eq!(ide, full);
+    }
+
+    #[test]
+    fn ide_expansion_has_the_same_family_items() {
+        let input = format!("for SIZE in [1, 2, 4];\n{}", shader(4)).replace("64", "SIZE");
+        let (full, ide) = expand_both(&input);
+
+        assert!(full.contains("main_0_4::WORK_GROUP_SIZE"));
+        assert!(full.contains("main_0::with_size"));
+        assert_eq!(ide, full);
+    }
+}
@@ -0,0 +1,223 @@
+use std::{cell::RefCell, path::PathBuf};
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use proc_macro_error::abort;
+use quote::quote;
+
+use crate::wgsl::Wgsl;
+
+thread_local! {
+    /// The file included by the current invocation, if any.
+    static FILE: RefCell<Option<File>> = const { RefCell::new(None) };
+}
+
+/// A shader read from a file, with the location of every token, as the
+/// tokens themselves only span the macro invocation.
+struct File {
+    // as written in the invocation
+    path: String,
+    tokens: Vec<Token>,
+}
+
+struct Token {
+    text: String,
+    line: usize,
+    column: usize,
+}
+
+/// Reads the shader of `wgsl!(include "path")` from `path` relative to
+/// `CARGO_MANIFEST_DIR`.
+///
+/// Returns the input as is for inline shaders, otherwise the tokens of the
+/// file along with an `include_str!` of it, so the crate is rebuilt when the
+/// file changes.
+pub fn resolve(input: TokenStream) -> (TokenStream, Option<TokenStream>) {
+    FILE.with(|file| *file.borrow_mut() = None);
+
+    let tokens = input.clone().into_iter().collect::<Vec<_>>();
+
+    let literal = match tokens.as_slice() {
+        [TokenTree::Ident(ident), TokenTree::Literal(literal)]
+        | [TokenTree::Ident(ident), TokenTree::Literal(literal), TokenTree::Punct(_)]
+            if ident == "include" =>
+        {
+            literal
+        }
+        _ => return (input, None),
+    };
+
+    let relative = match syn::parse2::<syn::LitStr>(TokenTree::Literal(literal.clone()).into()) {
+        Ok(relative) => relative.value(),
+        Err(_) => abort!(literal, "expected the path of the shader as a string"),
+    };
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = PathBuf::from(manifest_dir).join(&relative);
+
+    let source = match std::fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(err) => abort!(literal, "failed to read `{}`: {}", path.display(), err),
+    };
+
+    let stream = match source.parse::<TokenStream>() {
+        Ok(stream) => stream,
+        Err(err) => abort!(literal, "failed to tokenize `{}`: {}", path.display(), err),
+    };
+
+    let mut texts = Vec::new();
+    flatten(stream.clone(), &mut texts);
+
+    let tracked = path.to_string_lossy().into_owned();
+    let track = quote! {
+        const _: &::std::primitive::str = ::std::include_str!(#tracked);
+    };
+
+    FILE.with(|file| {
+        *file.borrow_mut() = Some(File {
+            path: relative,
+            tokens: locate_tokens(&source, texts),
+        })
+    });
+
+    (stream, Some(track))
+}
+
+/// Gets the path, line and column in the included file of the token at
+/// `offset` into the source of `wgsl`, `None` for inline shaders.
+///
+/// The source may have been rewritten, e.g. by `#![auto_guard]`, so the token
+/// is found by the tokens around it.
+pub fn location(wgsl: &Wgsl, offset: usize) -> Option<(String, usize, usize)> {
+    FILE.with(|file| {
+        let file = file.borrow();
+        let file = file.as_ref()?;
+
+        let starts = wgsl.spans.keys().copied().collect::<Vec<_>>();
+        let texts = starts
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| {
+                let end = starts.get(i + 1).copied().unwrap_or(wgsl.source.len());
+                wgsl.source[start..end].trim_end()
+            })
+            .collect::<Vec<_>>();
+
+        let index = starts
+            .partition_point(|&start| start <= offset)
+            .checked_sub(1)?;
+
+        for radius in (0..=3).rev() {
+            let start = index.saturating_sub(radius);
+            let end = (index + radius + 1).min(texts.len());
+            let window = &texts[start..end];
+
+            let found = file.tokens.windows(window.len()).find(|tokens| {
+                tokens
+                    .iter()
+                    .zip(window)
+                    .all(|(token, text)| token.text == *text)
+            });
+
+            if let Some(tokens) = found {
+                let token = &tokens[index - start];
+                return Some((file.path.clone(), token.line, token.column));
+            }
+        }
+
+        None
+    })
+}
+
+/// Collects the text of every token the way [`Wgsl`] does, delimiters apart.
+fn flatten(stream: TokenStream, texts: &mut Vec<String>) {
+    for tree in stream {
+        match tree {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::None => ("", ""),
+                };
+
+                if !open.is_empty() {
+                    texts.push(open.into());
+                }
+
+                flatten(group.stream(), texts);
+
+                if !close.is_empty() {
+                    texts.push(close.into());
+                }
+            }
+            tree => texts.push(tree.to_string()),
+        }
+    }
+}
+
+/// Finds `texts` in order in `source`, skipping whitespace and comments.
+/// Tokens past one that can't be found have no location.
+fn locate_tokens(source: &str, texts: Vec<String>) -> Vec<Token> {
+    let mut tokens = Vec::with_capacity(texts.len());
+    let mut cursor = 0;
+
+    for text in texts {
+        cursor = skip_trivia(source, cursor);
+
+        if !source[cursor..].starts_with(&text) {
+            break;
+        }
+
+        let before = &source[..cursor];
+        let line = before.matches('\n').count() + 1;
+        let column = before[before.rfind('\n').map_or(0, |i| i + 1)..]
+            .chars()
+            .count()
+            + 1;
+
+        cursor += text.len();
+        tokens.push(Token { text, line, column });
+    }
+
+    tokens
+}
+
+fn skip_trivia(source: &str, mut cursor: usize) -> usize {
+    loop {
+        let rest = &source[cursor..];
+        let trimmed = rest.trim_start();
+        cursor += rest.len() - trimmed.len();
+
+        if trimmed.starts_with("//") {
+            cursor += trimmed.find('\n').unwrap_or(trimmed.len());
+        } else if trimmed.starts_with("/*") {
+            cursor += block_comment_len(trimmed);
+        } else {
+            return cursor;
+        }
+    }
+}
+
+/// Length of the possibly nested block comment `comment` starts with.
+fn block_comment_len(comment: &str) -> usize {
+    let mut depth = 0;
+    let mut i = 0;
+
+    while i < comment.len() {
+        if comment[i..].starts_with("/*") {
+            depth += 1;
+            i += 2;
+        } else if comment[i..].starts_with("*/") {
+            depth -= 1;
+            i += 2;
+
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += comment[i..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+
+    comment.len()
+}
@@ -0,0 +1,141 @@
+use std::str::FromStr;
+
+use naga::Module;
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::quote;
+
+use crate::{guard, options::Options};
+
+/// Name of the uniform holding the launch parameters.
+pub const VAR: &str = "shatter_launch";
+/// Name of the struct of [`VAR`].
+pub const STRUCT: &str = "ShatterLaunch";
+
+/// Members of the launch parameters, a uniform the builder fills before every
+/// dispatch. Options needing a value from the host opt a member in, so all of
+/// them share a single binding.
+///
+/// Members are declared in the order of the fields, which must match
+/// `LaunchParams::encode` of the runtime.
+#[derive(Clone, Copy, Default)]
+pub struct Members {
+    /// Element count of `#![auto_guard]`.
+    pub guard_count: bool,
+    /// Base element of `#![dispatch_offset]`.
+    pub dispatch_offset: bool,
+}
+
+impl Members {
+    pub fn new(options: &Options) -> Self {
+        Self {
+            guard_count: options.auto_guard,
+            dispatch_offset: options.dispatch_offset,
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.guard_count && !self.dispatch_offset
+    }
+
+    /// Names of the declared members, each read by an accessor function of
+    /// the same name prefixed with `shatter_`.
+    fn names(&self) -> impl Iterator<Item = &'static str> {
+        [
+            (self.guard_count, "guard_count"),
+            (self.dispatch_offset, "dispatch_offset"),
+        ]
+        .into_iter()
+        .filter_map(|(declared, name)| declared.then_some(name))
+    }
+
+    /// Generates the `LaunchParams` of the shader's `ComputeShader` impl.
+    pub fn tokens(&self) -> TokenStream {
+        let guard_count = self.guard_count;
+        let dispatch_offset = self.dispatch_offset;
+
+        quote! {
+            ::shatter::__abi::LaunchParams {
+                guard_count: #guard_count,
+                dispatch_offset: #dispatch_offset,
+            }
+        }
+    }
+}
+
+/// Gets the group of the launch parameters, `None` if the shader declares none.
+pub fn group(module: &Module) -> Option<u32> {
+    module
+        .global_variables
+        .iter()
+        .find(|(_, variable)| variable.name.as_deref() == Some(VAR))
+        .and_then(|(_, variable)| variable.binding.as_ref())
+        .map(|binding| binding.group)
+}
+
+/// Applies the options of `input` needing launch parameters, declaring them if
+/// any member is needed. Shaders without such options are left untouched.
+pub fn inject(input: TokenStream, options: &Options) -> TokenStream {
+    let members = Members::new(options);
+
+    if members.is_empty() {
+        return input;
+    }
+
+    let input = if options.auto_guard {
+        guard::inject(&input)
+    } else {
+        input
+    };
+
+    declare(input, &members)
+}
+
+/// Declares the launch parameters ahead of the shader as a uniform in the group
+/// after the last one of the shader, along with their accessor functions.
+fn declare(input: TokenStream, members: &Members) -> TokenStream {
+    let max_group = input
+        .clone()
+        .into_iter()
+        .filter_map(|tree| match tree {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Bracket => {
+                guard::attributes(&group)
+            }
+            _ => None,
+        })
+        .flatten()
+        .filter(|(name, _)| name == "group")
+        .filter_map(|(_, args)| args.first().copied())
+        .max()
+        .unwrap_or(0);
+
+    let fields = members
+        .names()
+        .map(|name| format!("{}: u32;", name))
+        .collect::<String>();
+
+    let accessors = members
+        .names()
+        .map(|name| {
+            format!(
+                "fn shatter_{name}() -> u32 {{ return {var}.{name}; }}",
+                name = name,
+                var = VAR
+            )
+        })
+        .collect::<String>();
+
+    let declarations = format!(
+        "[[block]] struct {struct_} {{ {fields} }}; [[group({group}), binding(0)]] var<uniform> {var}: {struct_}; {accessors}",
+        struct_ = STRUCT,
+        fields = fields,
+        group = max_group + 1,
+        var = VAR,
+        accessors = accessors,
+    );
+
+    // naga resolves identifiers in one pass, the parameters are declared first
+    let mut declared = TokenStream::from_str(&declarations).unwrap();
+    declared.extend(input);
+    declared
+}
@@ -1,10 +1,254 @@
+mod analysis;
+mod family;
+mod guard;
+mod ide;
+mod include;
+mod launch;
+mod names;
+mod namespace;
+mod options;
+mod pipeline;
+mod shared_group;
 mod shatter;
+mod transparent;
 mod wgsl;
 
+#[cfg(test)]
+mod snapshots;
+
+/// Generates bindings for a WGSL shader.
+///
+/// # Shader families
+/// Starting the shader with `for PARAM in [1, 2, 4];` instantiates it once per
+/// value, replacing every occurrence of `PARAM` with the value. Each entry point
+/// `name` gets a module `name_<value>` per value, along with a module `name`
+/// containing the shared `Bindings` and `with_param(value)`, selecting a variant
+/// at runtime. Constants and types must not depend on the parameter.
+///
+/// # Shared bindings
+/// Entry points with identical bindings share the `Bindings` of the first one,
+/// and get an enum `ShaderSelect` selecting between them at runtime.
+/// `dispatch_selected` dispatches the selected entry point and
+/// `dispatch_selected_batch` records several dispatches in a single submission,
+/// reusing the bind groups. When a shader has several such groups, the items are
+/// named after the first entry point `first`, e.g. `FirstSelect` and
+/// `dispatch_first_selected`.
+///
+/// # Pipelines
+/// A trailing `pipeline! { cull -> compact -> shade }` names compute entry
+/// points run one after the other. Every pass must read a resource the pass
+/// before it writes, a pass declared before the one producing what it reads is
+/// an error. `Pipeline::run` takes a `pipeline::Bindings` holding the resources
+/// of every pass and one dispatch per pass, recording the passes in order into
+/// a single submission.
+///
+/// # Resource registries
+/// Every `Bindings` struct has `from_registry`, borrowing its resources from a
+/// `ResourceRegistry` by WGSL name, and `from_registry_renamed` taking a map
+/// from WGSL names to registry names.
+///
+/// # Bind groups
+/// Compute entry points binding several groups get a struct per group,
+/// `name::Group0<'a>`, `name::Group1<'a>`, .., holding the bindings of that
+/// group, with `Bindings::from_groups` and `into_groups` converting between
+/// them and a tuple of groups. Unused groups in between are `EmptyGroup`,
+/// shared groups their `wgsl_group!` struct. `name::build_groups` takes the
+/// tuple, each element either a group or a mutable reference to one, so a
+/// group can be reused across dispatches of several entry points, and groups
+/// of the same resources resolve to the same cached bind group.
+///
+/// # Async dispatch
+/// Every entry point `name` also gets `name_async`, which doesn't block while the
+/// GPU works and downloads what the dispatch wrote before resolving. It takes
+/// `name::OwnedBindings`, holding the resources by value so the future is `Send`,
+/// and hands them back when done. `OwnedBindings::as_bindings` borrows them as
+/// the regular `Bindings`.
+///
+/// # Dispatch helpers
+/// Every entry point module has `dispatch_for(elements)` and
+/// `dispatch_for_extent([x, y, z])`, rounding the invocations up to whole work
+/// groups. They panic naming the entry point if an axis needs more than 65535
+/// work groups or the invocations overflow u32, `checked_dispatch` and
+/// `checked_dispatch_for_extent` return the error instead.
+///
+/// # Push constants
+/// A compute entry point using a `var<push_constant>` struct gets
+/// `name::PushConstants`, an alias of the struct, and its `Shader` sets them
+/// from `ComputeShaderBuilder::push_constants`, zeroed until set. They need
+/// `wgpu::Features::PUSH_CONSTANTS` and a large enough `max_push_constant_size`,
+/// dispatching without them panics.
+///
+/// # Render entry points
+/// Every fragment entry point `name` gets a module `name` whose `Shader`
+/// implements `RenderShader`, drawing it with the shader's only vertex entry
+/// point. `name::build(bindings)` returns a `RenderShaderBuilder`, drawing into
+/// a texture set with `with_color_target`. Bindings are visible to both stages.
+/// Vertex attributes aren't supported, vertices are derived from
+/// `vertex_index` or read from storage buffers.
+///
+/// # Textures and samplers
+/// Storage textures are bound as `StorageTextureBinding`, sampled textures like
+/// `texture_2d<f32>` as `TextureBinding` and `sampler` as `SamplerBinding`,
+/// implemented by `Sampler`. Float textures are bound as filterable. Storage
+/// textures take the formats of `texel_format`, e.g. `r32float` binds a
+/// `Texture2d<R32Float>`. Depth textures and comparison samplers aren't
+/// supported.
+///
+/// # Work group arrays
+/// Every entry point module exports `WORK_GROUP_INVOCATIONS`, the product of
+/// `WORK_GROUP_SIZE`, for sizing host side scratch buffers. A `var<workgroup>`
+/// array whose length is neither the number of invocations nor any axis of the
+/// work group size emits a warning, as it usually drifted from `workgroup_size`.
+///
+/// # Names
+/// Items keep their WGSL names, the naming lints are allowed on them. Names
+/// that are Rust keywords become raw identifiers, e.g. `r#match`, except
+/// `self`, `Self`, `super` and `crate`, which are suffixed with `_`. Structs
+/// and constants named like the items of entry point modules, e.g. `Bindings`
+/// or `build`, and names that would generate the same item, e.g. a struct
+/// `main` and an entry point `main`, are an error.
+///
+/// # Static checks
+/// The size, member offsets and array strides of every struct are checked
+/// against naga's layout by constant assertions naming the member and both
+/// values, so a divergence fails to compile rather than shifting every
+/// element of a buffer. Functions calling each other recursively are an error naming the cycle.
+/// Entry points running more iterations of loops with constant bounds than
+/// `#![max_loop_iterations]`, nested loops multiplying, emit a warning as
+/// backends may unroll them. `if` and `switch` statements branching on values
+/// that differ between the invocations of a work group, e.g. builtins or
+/// `read_write` storage, emit a warning if they contain a work group barrier or
+/// leave before one.
+///
+/// # Options
+/// Options are given as attributes at the start of the shader.
+/// * `#![lenient_bindings]` makes read-only buffer bindings optional, a zeroed
+///   placeholder buffer is bound when `None` is passed. Useful for globals the
+///   kernel never accesses at runtime, e.g. debug only globals.
+/// * `#![keep_unused_bindings]` keeps bindings an entry point doesn't use in
+///   its `Bindings`, with a layout entry but never read back, so host code
+///   doesn't change while iterating on a shader. Without it they're omitted,
+///   and bindings unused by every entry point emit a warning.
+/// * `#![max_type_size = N]` sets the size in bytes above which generated
+///   types emit a warning, 4 MiB by default.
+/// * `#![max_loop_iterations = N]` sets the number of constant bounded loop
+///   iterations per entry point above which a warning is emitted, 65536 by
+///   default.
+/// * `#![auto_guard]` makes every compute entry point return early for
+///   invocations whose flattened `global_invocation_id` is past the element
+///   count, so dispatches rounded up to whole work groups don't index out of
+///   bounds. The count is the length of the first bound runtime sized array,
+///   or set with `with_guard_count` on the builder.
+/// * `#![dispatch_offset]` declares `shatter_dispatch_offset()`, the base
+///   element set with `with_dispatch_offset` on the builder, e.g. the
+///   `offset` of a `Segment` the kernel adds to its invocation ids.
+/// * `#![extern_type(A, B)]` takes the structs `A` and `B` from the Rust
+///   scope instead of generating them, e.g. to derive more traits. They must
+///   be `#[repr(C)]`, `Clone` and `Copy`, valid when zeroed, and have fields of
+///   the same names, which are checked against the WGSL layout at compile time.
+///
+/// # Launch parameters
+/// Options needing a value from the host share a single uniform, declared
+/// ahead of the shader as `var<uniform> shatter_launch: ShatterLaunch` in the
+/// group after the last one of the shader. It holds only the members the
+/// enabled options need, read through the functions they declare, e.g.
+/// `shatter_guard_count()`. The builder fills it before every dispatch from
+/// the bindings and its settings, writing it to a ring of uniform slots
+/// rather than creating a buffer per dispatch. Shaders enabling none of these
+/// options are passed to wgpu unchanged and bind nothing extra. Sources given
+/// to `with_source` must declare the launch parameters the same way.
+///
+/// # Including files
+/// `wgsl!(include "shaders/sim.wgsl");` reads the shader from a file relative
+/// to `CARGO_MANIFEST_DIR`, generating the same items as the inline shader.
+/// The file is tracked with `include_str!`, so the crate is rebuilt when it
+/// changes. Diagnostics point at the invocation, parse errors name the line
+/// and column in the file.
+///
+/// # Namespaces
+/// `wgsl!(mod sim { <shader> })` generates every item inside `pub mod sim`,
+/// so several shaders declaring the same structs or entry points can live in
+/// one module. The module glob imports its parent, `include` and shared
+/// groups work as usual, e.g. `wgsl!(mod sim { include "shaders/sim.wgsl" })`.
+///
+/// # IDEs
+/// Expanded by rust-analyzer, the shader is neither validated nor checked and
+/// no warnings are emitted, generating the same items faster as completions
+/// only need their names and types. Errors are still reported by the build.
+/// Setting `SHATTER_IDE_EXPANSION` to `1` or `0` forces this on or off.
 #[proc_macro_error::proc_macro_error]
 #[proc_macro]
 pub fn wgsl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let wgsl = wgsl::Wgsl::new(&input.into());
+    let (namespace, input) = namespace::resolve(input.into());
+    let (input, track) = include::resolve(input);
+
+    let output = expand(input, ide::is_ide_expansion());
+
+    namespace::wrap(namespace, quote::quote!(#output #track)).into()
+}
+
+fn expand(input: proc_macro2::TokenStream, ide: bool) -> proc_macro2::TokenStream {
+    let (shared_groups, input) = match shared_group::resolve(input) {
+        shared_group::Imports::Resolved(shared_groups, input) => (shared_groups, input),
+        shared_group::Imports::Callback(callback) => return callback,
+    };
+
+    let (mut options, input) = options::Options::parse(input);
+    let (pipeline, input) = pipeline::Pipeline::extract(input);
+    options.shared_groups = shared_groups;
+    options.pipeline = pipeline;
+    options.ide = ide;
+
+    if !options.ide {
+        analysis::check_recursion(&input);
+    }
+
+    if let Some(family) = family::Family::parse(&input) {
+        return shatter::shatter_family(&family, &options);
+    }
+
+    let input = launch::inject(input, &options);
+
+    let wgsl = wgsl::Wgsl::with_options(&input, &options);
+
+    shatter::shatter(&wgsl, &options)
+}
+
+/// Declares bindings shared by several `wgsl!` blocks at the same group,
+/// `wgsl_group! { name: Globals, group: 0, <declarations> }`.
+///
+/// The declarations are WGSL, with bindings declared `[[binding(N)]]`, the
+/// group is added to them. The types and constants are generated once here,
+/// along with `Globals<'a>` holding the bindings and the layout of the group.
+///
+/// `use_group!(Globals);` in a `wgsl!` block, or `use_group!(path::Globals);`,
+/// splices the declarations into its shader. The block doesn't generate the
+/// declared items again, they have to be in scope if the shader's own types
+/// use them. Its `Bindings` hold the group as a field named after the struct,
+/// e.g. `globals: Globals<'a>`, so every importing shader has the same layout
+/// for the group and the same resources resolve to the same bind group.
+/// Blocks importing groups have no async dispatch.
+///
+/// The import goes through a `macro_rules!` defined next to the struct and
+/// re-exported with `pub(crate)`, so groups can only be used within their crate.
+#[proc_macro_error::proc_macro_error]
+#[proc_macro]
+pub fn wgsl_group(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let def = shared_group::GroupDef::parse(input.into());
+
+    shatter::shatter_group(&def).into()
+}
+
+/// Implements `BufferData` for a `#[repr(transparent)]` newtype by forwarding
+/// to its field, along with `BufferVec` when the field implements it.
+///
+/// This lets domain types wrap generated structs, e.g. `struct Enemies(pub Particles)`,
+/// and still be stored in a `Buffer`. `Buffer::as_inner_binding` binds such a
+/// buffer where the generated `Bindings` expect the wrapped type.
+#[proc_macro_error::proc_macro_error]
+#[proc_macro_derive(BufferDataTransparent)]
+pub fn buffer_data_transparent(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
 
-    shatter::shatter(&wgsl)
+    transparent::buffer_data_transparent(&input).into()
 }
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use naga::{Module, ShaderStage, TypeInner};
+use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro_error::abort;
+use quote::quote;
+
+use crate::wgsl::Wgsl;
+
+/// Rust keywords, WGSL allows most of them as names.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Keywords that can't be raw identifiers, suffixed with `_` instead.
+const NOT_RAW: &[&str] = &["crate", "self", "Self", "super"];
+
+/// Items generated into every entry point module, a struct or constant of the
+/// same name would be shadowed by them within the module.
+const RESERVED: &[&str] = &[
+    "Bindings",
+    "OwnedBindings",
+    "PushConstants",
+    "Shader",
+    "WORK_GROUP_SIZE",
+    "WORK_GROUP_INVOCATIONS",
+    "build",
+    "validate",
+    "checked_dispatch",
+    "checked_dispatch_for_extent",
+    "dispatch_for",
+    "dispatch_for_extent",
+];
+
+/// Gets the identifier of a WGSL name, raw for Rust keywords, e.g. `r#match`,
+/// and suffixed with `_` for those that can't be raw, e.g. `self_`.
+pub fn ident(name: &str) -> Ident {
+    if NOT_RAW.contains(&name) {
+        Ident::new(&format!("{}_", name), Span::call_site())
+    } else if KEYWORDS.contains(&name) {
+        Ident::new_raw(name, Span::call_site())
+    } else {
+        Ident::new(name, Span::call_site())
+    }
+}
+
+/// Gets the name of `ident` without the `r#` of raw identifiers, for naming
+/// items after it.
+pub fn unraw(ident: &Ident) -> String {
+    let name = ident.to_string();
+
+    match name.strip_prefix("r#") {
+        Some(name) => name.to_owned(),
+        None => name,
+    }
+}
+
+/// Allows the naming lints on items named after WGSL, which follows its own
+/// conventions or none at all.
+pub fn allow_naming() -> TokenStream {
+    quote!(#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)])
+}
+
+/// Aborts if a name of `module` collides with a generated item or another
+/// name once converted by [`ident`].
+pub fn check(wgsl: &Wgsl, module: &Module) {
+    // item names in the scope of the macro invocation, with what declared them
+    let mut items = HashMap::new();
+
+    let mut declare = |name: String, what: String, span: naga::Span| {
+        if let Some(other) = items.insert(name.clone(), what.clone()) {
+            abort!(
+                wgsl.span(span),
+                "{} generates `{}`, which is also generated by {}",
+                what,
+                name,
+                other;
+                help = "rename one of them"
+            );
+        }
+    };
+
+    for (handle, ty) in module.types.iter() {
+        let (name, members) = match (&ty.name, &ty.inner) {
+            (Some(name), TypeInner::Struct { members, .. }) => (name, members),
+            _ => continue,
+        };
+
+        let span = module.types.get_span(handle);
+        let what = format!("struct `{}`", name);
+
+        reserved(wgsl, name, &what, span);
+
+        declare(format!("{}_Sized", name), what.clone(), span);
+        declare(format!("{}Std140", name), what.clone(), span);
+        declare(ident(name).to_string(), what, span);
+
+        let mut fields = HashMap::new();
+
+        for member in members {
+            let member_name = member.name.as_deref().unwrap_or_default();
+            let field = self::ident(member_name).to_string();
+
+            if let Some(other) = fields.insert(field.clone(), member_name) {
+                abort!(
+                    wgsl.span(span),
+                    "`{}` and `{}` of struct `{}` both become the field `{}`",
+                    other,
+                    member_name,
+                    name,
+                    field;
+                    help = "rename one of them"
+                );
+            }
+        }
+    }
+
+    for (handle, constant) in module.constants.iter() {
+        if let Some(ref name) = constant.name {
+            let span = module.constants.get_span(handle);
+            let what = format!("constant `{}`", name);
+
+            reserved(wgsl, name, &what, span);
+            declare(ident(name).to_string(), what, span);
+        }
+    }
+
+    let mut globals = HashMap::new();
+
+    for (handle, variable) in module.global_variables.iter() {
+        if let (Some(name), Some(_)) = (&variable.name, &variable.binding) {
+            let field = ident(name).to_string();
+
+            if let Some(other) = globals.insert(field.clone(), name) {
+                abort!(
+                    wgsl.span(module.global_variables.get_span(handle)),
+                    "`{}` and `{}` both become the binding `{}`",
+                    other,
+                    name,
+                    field;
+                    help = "rename one of them"
+                );
+            }
+        }
+    }
+
+    for entry_point in &module.entry_points {
+        let name = &entry_point.name;
+        // naga doesn't keep the spans of entry points
+        let span = naga::Span::default();
+        let what = format!("entry point `{}`", name);
+
+        let ident = ident(name);
+
+        match entry_point.stage {
+            ShaderStage::Compute => {
+                declare(format!("{}_async", unraw(&ident)), what.clone(), span);
+                declare(ident.to_string(), what, span);
+            }
+            ShaderStage::Fragment => declare(ident.to_string(), what, span),
+            // drawn by the modules of the fragment entry points
+            ShaderStage::Vertex => {}
+        }
+    }
+}
+
+fn reserved(wgsl: &Wgsl, name: &str, what: &str, span: naga::Span) {
+    if RESERVED.contains(&name) {
+        abort!(
+            wgsl.span(span),
+            "{} would be shadowed by the `{}` generated for every entry point",
+            what,
+            name;
+            help = "rename it"
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use proc_macro2::{Delimiter, Ident, TokenStream, TokenTree};
+use proc_macro_error::abort;
+use quote::quote;
+
+/// Strips `mod name { ... }` around the input of `wgsl!`, returning the name
+/// of the module the generated items go in, if any.
+pub fn resolve(input: TokenStream) -> (Option<Ident>, TokenStream) {
+    let tokens = input.clone().into_iter().collect::<Vec<_>>();
+
+    match tokens.as_slice() {
+        [TokenTree::Ident(keyword), TokenTree::Ident(name), TokenTree::Group(body)]
+            if keyword == "mod" && body.delimiter() == Delimiter::Brace =>
+        {
+            (Some(name.clone()), body.stream())
+        }
+        [TokenTree::Ident(keyword), ..] if keyword == "mod" => {
+            abort!(keyword, "expected `mod name { <shader> }`")
+        }
+        _ => (None, input),
+    }
+}
+
+/// Wraps `output` in `pub mod name`, glob importing the parent so shared
+/// groups and other paths resolve as they would outside.
+pub fn wrap(name: Option<Ident>, output: TokenStream) -> TokenStream {
+    match name {
+        Some(name) => quote! {
+            pub mod #name {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #output
+            }
+        },
+        None => output,
+    }
+}
@@ -0,0 +1,171 @@
+use proc_macro2::{Delimiter, Ident, TokenStream, TokenTree};
+use proc_macro_error::abort;
+
+use crate::{pipeline::Pipeline, shared_group::SharedGroup};
+
+/// Default of [`Options::max_type_size`], 4 MiB.
+pub const DEFAULT_MAX_TYPE_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Default of [`Options::max_loop_iterations`].
+pub const DEFAULT_MAX_LOOP_ITERATIONS: u64 = 65536;
+
+/// Options set by leading `#![option]` attributes.
+#[derive(Clone)]
+pub struct Options {
+    /// Read-only buffer bindings become optional, binding a placeholder when `None`.
+    pub lenient_bindings: bool,
+    /// Bindings an entry point doesn't use are kept in its `Bindings` instead of omitted.
+    pub keep_unused_bindings: bool,
+    /// Generated types larger than this many bytes emit a warning, set by `#![max_type_size = N]`.
+    pub max_type_size: u64,
+    /// Entry points running more constant bounded loop iterations emit a
+    /// warning, set by `#![max_loop_iterations = N]`.
+    pub max_loop_iterations: u64,
+    /// Compute entry points return early for invocations past the element count, see [`crate::guard`].
+    pub auto_guard: bool,
+    /// Compute entry points read the base element of the dispatch, see [`crate::launch`].
+    pub dispatch_offset: bool,
+    /// Structs declared in Rust instead of generated, set by `#![extern_type(A, B)]`.
+    pub extern_types: Vec<String>,
+    /// Groups imported with `use_group!`, sorted by group.
+    pub shared_groups: Vec<SharedGroup>,
+    /// Passes of a trailing `pipeline!`, see [`crate::pipeline`].
+    pub pipeline: Option<Pipeline>,
+    /// Expanding for an IDE, only the generated items matter, see [`crate::ide`].
+    pub ide: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            lenient_bindings: false,
+            keep_unused_bindings: false,
+            max_type_size: DEFAULT_MAX_TYPE_SIZE,
+            max_loop_iterations: DEFAULT_MAX_LOOP_ITERATIONS,
+            auto_guard: false,
+            dispatch_offset: false,
+            extern_types: Vec::new(),
+            shared_groups: Vec::new(),
+            pipeline: None,
+            ide: false,
+        }
+    }
+}
+
+impl Options {
+    /// Returns true if the type `name` is declared by an imported group.
+    pub fn imports_type(&self, name: &str) -> bool {
+        self.shared_groups
+            .iter()
+            .any(|group| group.types.contains(name))
+    }
+
+    /// Returns true if the struct `name` is declared in Rust with `#![extern_type]`.
+    pub fn is_extern_type(&self, name: &str) -> bool {
+        self.extern_types.iter().any(|ty| ty == name)
+    }
+
+    /// Returns true if the constant `name` is declared by an imported group.
+    pub fn imports_constant(&self, name: &str) -> bool {
+        self.shared_groups
+            .iter()
+            .any(|group| group.constants.contains(name))
+    }
+
+    /// Gets the imported group bound at `group`.
+    pub fn shared_group(&self, group: u32) -> Option<&SharedGroup> {
+        self.shared_groups
+            .iter()
+            .find(|shared| shared.group == group)
+    }
+
+    /// Parses the leading options of `input`, returns the options and the remaining input.
+    pub fn parse(input: TokenStream) -> (Self, TokenStream) {
+        let mut options = Self::default();
+        let mut tokens = input.into_iter().peekable();
+
+        loop {
+            match tokens.peek() {
+                Some(TokenTree::Punct(punct)) if punct.as_char() == '#' => {}
+                _ => break,
+            }
+
+            let pound = tokens.next().unwrap();
+
+            match tokens.next() {
+                Some(TokenTree::Punct(punct)) if punct.as_char() == '!' => {}
+                _ => abort!(pound, "expected an option, e.g. `#![lenient_bindings]`"),
+            }
+
+            let group = match tokens.next() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => group,
+                _ => abort!(pound, "expected an option, e.g. `#![lenient_bindings]`"),
+            };
+
+            let mut trees = group.stream().into_iter();
+
+            while let Some(tree) = trees.next() {
+                match tree {
+                    TokenTree::Ident(ident) if ident == "lenient_bindings" => {
+                        options.lenient_bindings = true;
+                    }
+                    TokenTree::Ident(ident) if ident == "keep_unused_bindings" => {
+                        options.keep_unused_bindings = true;
+                    }
+                    TokenTree::Ident(ident) if ident == "auto_guard" => {
+                        options.auto_guard = true;
+                    }
+                    TokenTree::Ident(ident) if ident == "dispatch_offset" => {
+                        options.dispatch_offset = true;
+                    }
+                    TokenTree::Ident(ident) if ident == "max_type_size" => {
+                        options.max_type_size = parse_value(&ident, &mut trees, "bytes");
+                    }
+                    TokenTree::Ident(ident) if ident == "max_loop_iterations" => {
+                        options.max_loop_iterations = parse_value(&ident, &mut trees, "iterations");
+                    }
+                    TokenTree::Ident(ident) if ident == "extern_type" => {
+                        options.extern_types.extend(parse_list(&ident, &mut trees));
+                    }
+                    TokenTree::Punct(punct) if punct.as_char() == ',' => {}
+                    tree => abort!(tree, "unknown option `{}`", tree),
+                }
+            }
+        }
+
+        (options, tokens.collect())
+    }
+}
+
+/// Parses the ` = N` following the option `ident`, where `N` counts `unit`.
+fn parse_value(ident: &Ident, trees: &mut impl Iterator<Item = TokenTree>, unit: &str) -> u64 {
+    match trees.next() {
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => {}
+        _ => abort!(ident, "expected `{} = <{}>`", ident, unit),
+    }
+
+    match trees.next() {
+        Some(TokenTree::Literal(literal)) => syn::parse_str::<syn::LitInt>(&literal.to_string())
+            .and_then(|lit| lit.base10_parse::<u64>())
+            .unwrap_or_else(|_| abort!(literal, "`{}` must be a number of {}", ident, unit)),
+        _ => abort!(ident, "expected `{} = <{}>`", ident, unit),
+    }
+}
+
+/// Parses the `(A, B)` following the option `ident`.
+fn parse_list(ident: &Ident, trees: &mut impl Iterator<Item = TokenTree>) -> Vec<String> {
+    let group = match trees.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => group,
+        _ => abort!(ident, "expected `{}(<names>)`", ident),
+    };
+
+    group
+        .stream()
+        .into_iter()
+        .filter_map(|tree| match tree {
+            TokenTree::Ident(name) => Some(name.to_string()),
+            TokenTree::Punct(punct) if punct.as_char() == ',' => None,
+            tree => abort!(tree, "expected the name of a struct"),
+        })
+        .collect()
+}
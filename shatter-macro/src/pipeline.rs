@@ -0,0 +1,148 @@
+//! Trailing `pipeline! { first -> second -> third }` sections, naming compute
+//! entry points dispatched in order by the generated `Pipeline`.
+
+use naga::{
+    valid::{GlobalUse, ModuleInfo},
+    Module, ShaderStage,
+};
+use proc_macro2::{Delimiter, Ident, Span, TokenStream, TokenTree};
+use proc_macro_error::abort;
+
+/// The passes of a `pipeline!` section.
+#[derive(Clone)]
+pub struct Pipeline {
+    /// The `pipeline` of `pipeline!`.
+    pub span: Span,
+    /// The entry points, in the order they run.
+    pub passes: Vec<Ident>,
+}
+
+impl Pipeline {
+    /// Takes the trailing `pipeline!` section out of `input`, returns it and
+    /// the remaining input.
+    pub fn extract(input: TokenStream) -> (Option<Self>, TokenStream) {
+        let trees = input.into_iter().collect::<Vec<_>>();
+
+        let start = trees.windows(2).position(|window| match window {
+            [TokenTree::Ident(ident), TokenTree::Punct(punct)] => {
+                ident == "pipeline" && punct.as_char() == '!'
+            }
+            _ => false,
+        });
+
+        let start = match start {
+            Some(start) => start,
+            None => return (None, trees.into_iter().collect()),
+        };
+
+        let span = trees[start].span();
+
+        let group = match trees.get(start + 2) {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+            _ => abort!(span, "expected `pipeline! {{ first -> second }}`"),
+        };
+
+        if let Some(tree) = trees.get(start + 3) {
+            abort!(tree, "`pipeline!` must come last in the shader");
+        }
+
+        let passes = parse_passes(span, group.stream());
+        let input = trees[..start].iter().cloned().collect();
+
+        (Some(Self { span, passes }), input)
+    }
+
+    /// Gets the entry point index of every pass, aborts if a pass isn't a
+    /// compute entry point of `module` or doesn't read anything the pass
+    /// before it writes.
+    pub fn check(&self, module: &Module, info: &ModuleInfo, ide: bool) -> Vec<usize> {
+        let indices = self
+            .passes
+            .iter()
+            .map(|pass| entry_point_index(module, pass))
+            .collect::<Vec<_>>();
+
+        // only the names matter to an IDE
+        if ide {
+            return indices;
+        }
+
+        for (i, pair) in indices.windows(2).enumerate() {
+            let (producer, consumer) = (&self.passes[i], &self.passes[i + 1]);
+            let (written, read) = (info.get_entry_point(pair[0]), info.get_entry_point(pair[1]));
+
+            let passed = module.global_variables.iter().any(|(handle, _)| {
+                written[handle].contains(GlobalUse::WRITE) && read[handle].contains(GlobalUse::READ)
+            });
+
+            if passed {
+                continue;
+            }
+
+            // the consumer reading what a later pass produces
+            let reversed = module.global_variables.iter().find(|&(handle, _)| {
+                read[handle].contains(GlobalUse::WRITE) && written[handle].contains(GlobalUse::READ)
+            });
+
+            match reversed {
+                Some((_, variable)) => abort!(
+                    producer,
+                    "`{}` reads `{}` before `{}` writes it",
+                    producer,
+                    variable.name.as_deref().unwrap_or_default(),
+                    consumer;
+                    help = "declare the producer first, `{} -> {}`", consumer, producer
+                ),
+                None => abort!(
+                    consumer,
+                    "`{}` doesn't read anything `{}` writes, so they can't form a pipeline",
+                    consumer,
+                    producer
+                ),
+            }
+        }
+
+        indices
+    }
+}
+
+/// Parses `first -> second -> third`.
+fn parse_passes(span: Span, input: TokenStream) -> Vec<Ident> {
+    let mut passes = Vec::new();
+    let mut trees = input.into_iter();
+
+    loop {
+        match trees.next() {
+            Some(TokenTree::Ident(pass)) => passes.push(pass),
+            Some(tree) => abort!(tree, "expected the name of an entry point"),
+            None => abort!(span, "expected `pipeline! {{ first -> second }}`"),
+        }
+
+        let arrow = match trees.next() {
+            None => break,
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '-' => punct,
+            Some(tree) => abort!(tree, "expected `->` between entry points"),
+        };
+
+        match trees.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '>' => {}
+            _ => abort!(arrow, "expected `->` between entry points"),
+        }
+    }
+
+    passes
+}
+
+/// Gets the index of the compute entry point `pass`.
+fn entry_point_index(module: &Module, pass: &Ident) -> usize {
+    let index = module
+        .entry_points
+        .iter()
+        .position(|entry_point| pass == &entry_point.name);
+
+    match index {
+        Some(index) if module.entry_points[index].stage == ShaderStage::Compute => index,
+        Some(_) => abort!(pass, "`{}` isn't a compute entry point", pass),
+        None => abort!(pass, "no entry point named `{}`", pass),
+    }
+}
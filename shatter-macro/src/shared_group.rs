@@ -0,0 +1,337 @@
+use std::{collections::HashSet, str::FromStr};
+
+use heck::ToSnakeCase;
+use naga::Module;
+use proc_macro2::{Delimiter, Group, Ident, Literal, Span, TokenStream, TokenTree};
+use proc_macro_error::abort;
+use quote::{quote, ToTokens};
+use syn::Path;
+
+use crate::{
+    guard,
+    wgsl::{Wgsl, WgslResult},
+};
+
+/// Marker the callback of a group prepends to the input of `wgsl!`, followed
+/// by `(path, group) { declarations }`.
+const MARKER: &str = "__shatter_shared_group";
+
+/// A bind group declared once by `wgsl_group!`,
+/// `wgsl_group! { name: Globals, group: 0, <declarations> }`.
+pub struct GroupDef {
+    pub name: Ident,
+    pub group: u32,
+    /// The declarations with `group(N)` added to their bindings.
+    pub declarations: TokenStream,
+}
+
+impl GroupDef {
+    pub fn parse(input: TokenStream) -> Self {
+        let mut tokens = input.into_iter();
+
+        let name = match Self::parse_key(&mut tokens, "name") {
+            TokenTree::Ident(ident) => ident,
+            tree => abort!(tree, "expected the name of the bindings struct"),
+        };
+
+        let group = match Self::parse_key(&mut tokens, "group") {
+            TokenTree::Literal(literal) => literal
+                .to_string()
+                .parse()
+                .unwrap_or_else(|_| abort!(literal, "expected a group index")),
+            tree => abort!(tree, "expected a group index"),
+        };
+
+        let declarations = tokens.collect::<TokenStream>();
+
+        if declarations.is_empty() {
+            abort!(name, "shared group `{}` declares no bindings", name);
+        }
+
+        Self {
+            name,
+            group,
+            declarations: set_group(declarations, group),
+        }
+    }
+
+    /// Parses `key: value,` and returns the value.
+    fn parse_key(tokens: &mut impl Iterator<Item = TokenTree>, key: &str) -> TokenTree {
+        let expected = || format!("expected `{}: ..`", key);
+
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) if ident == key => {}
+            Some(tree) => abort!(tree, expected()),
+            None => abort!(Span::call_site(), expected()),
+        }
+
+        match tokens.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => {}
+            Some(tree) => abort!(tree, expected()),
+            None => abort!(Span::call_site(), expected()),
+        }
+
+        let value = tokens
+            .next()
+            .unwrap_or_else(|| abort!(Span::call_site(), expected()));
+
+        match tokens.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => {}
+            Some(tree) => abort!(tree, "expected `,`"),
+            None => abort!(value, "expected `,`"),
+        }
+
+        value
+    }
+
+    /// Name of the `macro_rules!` splicing the declarations into `wgsl!`.
+    pub fn callback(&self) -> Ident {
+        callback_ident(&self.name)
+    }
+}
+
+fn callback_ident(name: &Ident) -> Ident {
+    Ident::new(&format!("__shatter_group_{}", name), name.span())
+}
+
+/// Adds `group(N)` to the attributes of every binding in `declarations`.
+fn set_group(declarations: TokenStream, group: u32) -> TokenStream {
+    declarations
+        .into_iter()
+        .map(|tree| match tree {
+            TokenTree::Group(ref outer) if outer.delimiter() == Delimiter::Bracket => {
+                let attributes = match guard::attributes(outer) {
+                    Some(attributes) => attributes,
+                    None => return tree,
+                };
+
+                if !attributes.iter().any(|(name, _)| name == "binding") {
+                    return tree;
+                }
+
+                if attributes.iter().any(|(name, _)| name == "group") {
+                    abort!(
+                        outer,
+                        "the group of shared bindings is set by `group: {}`",
+                        group
+                    );
+                }
+
+                let inner = match outer.stream().into_iter().next() {
+                    Some(TokenTree::Group(inner)) => inner,
+                    _ => unreachable!(),
+                };
+
+                let mut stream = TokenStream::from_str(&format!("group({}),", group)).unwrap();
+                stream.extend(inner.stream());
+
+                let mut inner_group = Group::new(Delimiter::Bracket, stream);
+                inner_group.set_span(inner.span());
+
+                let mut outer_group = Group::new(
+                    Delimiter::Bracket,
+                    TokenTree::Group(inner_group).into_token_stream(),
+                );
+                outer_group.set_span(outer.span());
+
+                TokenTree::Group(outer_group)
+            }
+            tree => tree,
+        })
+        .collect()
+}
+
+/// A group imported into `wgsl!` with `use_group!(path)`.
+#[derive(Clone)]
+pub struct SharedGroup {
+    /// Path of the bindings struct, as given to `use_group!`.
+    pub path: Path,
+    pub group: u32,
+    pub declarations: TokenStream,
+    /// Names of the types, constants and bound variables of the group, which
+    /// the importing shader doesn't generate.
+    pub types: HashSet<String>,
+    pub constants: HashSet<String>,
+    pub variables: HashSet<String>,
+}
+
+impl SharedGroup {
+    fn new(path: Path, group: u32, declarations: TokenStream) -> Self {
+        let wgsl = Wgsl::new(&declarations);
+        let module: Module = naga::front::wgsl::parse_str(&wgsl.source).wgsl_unwrap(&wgsl);
+
+        let types = module
+            .types
+            .iter()
+            .filter_map(|(_, ty)| ty.name.clone())
+            .collect();
+        let constants = module
+            .constants
+            .iter()
+            .filter_map(|(_, constant)| constant.name.clone())
+            .collect();
+        let variables = module
+            .global_variables
+            .iter()
+            .filter(|(_, variable)| variable.binding.is_some())
+            .filter_map(|(_, variable)| variable.name.clone())
+            .collect();
+
+        Self {
+            path,
+            group,
+            declarations,
+            types,
+            constants,
+            variables,
+        }
+    }
+
+    /// Field holding the group in generated bindings, the snake cased name
+    /// of the struct.
+    pub fn field(&self) -> Ident {
+        let name = &self.path.segments.last().unwrap().ident;
+
+        Ident::new(&name.to_string().to_snake_case(), name.span())
+    }
+
+    fn path_string(path: &Path) -> String {
+        path.to_token_stream().to_string()
+    }
+}
+
+/// Imports of a `wgsl!` input, see [`resolve`].
+pub enum Imports {
+    /// Every `use_group!` was replaced by the declarations of its group.
+    Resolved(Vec<SharedGroup>, TokenStream),
+    /// The input has to go through the callback of a group first.
+    Callback(TokenStream),
+}
+
+/// Resolves the `use_group!(path)` items of `input`.
+///
+/// A macro can't see the input of another, so each `wgsl_group!` defines a
+/// `macro_rules!` callback re-invoking `wgsl!` with its declarations prepended.
+/// The first unresolved import expands to its callback, once every import has
+/// been prepended they're spliced in place of the `use_group!` items.
+pub fn resolve(input: TokenStream) -> Imports {
+    let mut tokens = input.clone().into_iter().peekable();
+    let mut groups = Vec::new();
+
+    while matches!(tokens.peek(), Some(TokenTree::Ident(ident)) if ident == MARKER) {
+        let marker = tokens.next().unwrap();
+
+        let (args, declarations) = match (tokens.next(), tokens.next()) {
+            (Some(TokenTree::Group(args)), Some(TokenTree::Group(declarations))) => {
+                (args, declarations)
+            }
+            _ => abort!(marker, "malformed shared group"),
+        };
+
+        let mut args = args.stream().into_iter().collect::<Vec<_>>();
+
+        let group = match args.pop() {
+            Some(TokenTree::Literal(literal)) => literal.to_string().parse().unwrap(),
+            _ => abort!(marker, "malformed shared group"),
+        };
+
+        // the separating comma
+        args.pop();
+
+        let path = syn::parse2(args.into_iter().collect()).unwrap();
+
+        groups.push(SharedGroup::new(path, group, declarations.stream()));
+    }
+
+    let rest = tokens.collect::<Vec<_>>();
+    let mut output = Vec::new();
+    let mut i = 0;
+
+    while i < rest.len() {
+        let (ident, args) = match &rest[i..] {
+            [TokenTree::Ident(ident), TokenTree::Punct(bang), TokenTree::Group(args), ..]
+                if ident == "use_group"
+                    && bang.as_char() == '!'
+                    && args.delimiter() == Delimiter::Parenthesis =>
+            {
+                (ident, args)
+            }
+            _ => {
+                output.push(rest[i].clone());
+                i += 1;
+                continue;
+            }
+        };
+
+        let path = syn::parse2::<Path>(args.stream()).unwrap_or_else(|_| {
+            abort!(
+                args,
+                "expected the path of a `wgsl_group!` struct, e.g. `use_group!(Globals)`"
+            )
+        });
+
+        let key = SharedGroup::path_string(&path);
+
+        let group = match groups
+            .iter()
+            .find(|group| SharedGroup::path_string(&group.path) == key)
+        {
+            Some(group) => group,
+            None => return Imports::Callback(callback(&path, &input)),
+        };
+
+        if groups
+            .iter()
+            .any(|other| other.group == group.group && !std::ptr::eq(other, group))
+        {
+            abort!(ident, "group {} is imported more than once", group.group);
+        }
+
+        output.extend(group.declarations.clone());
+
+        i += 3;
+
+        if matches!(rest.get(i), Some(TokenTree::Punct(punct)) if punct.as_char() == ';') {
+            i += 1;
+        }
+    }
+
+    groups.sort_by_key(|group| group.group);
+
+    Imports::Resolved(groups, output.into_iter().collect())
+}
+
+/// Invokes the callback of the group at `path` with `input`.
+fn callback(path: &Path, input: &TokenStream) -> TokenStream {
+    let mut callback = path.clone();
+    let last = callback.segments.last_mut().unwrap();
+    last.ident = callback_ident(&last.ident);
+
+    quote! {
+        #callback! { (#path) #input }
+    }
+}
+
+/// Generates the callback of `def`, see [`resolve`].
+pub fn gen_callback(def: &GroupDef) -> TokenStream {
+    let callback = def.callback();
+    let marker = Ident::new(MARKER, Span::call_site());
+    let group = Literal::u32_unsuffixed(def.group);
+    let declarations = &def.declarations;
+
+    quote! {
+        #[doc(hidden)]
+        macro_rules! #callback {
+            (($($path:tt)*) $($input:tt)*) => {
+                ::shatter::wgsl! {
+                    #marker($($path)*, #group) { #declarations }
+                    $($input)*
+                }
+            };
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub(crate) use #callback;
+    }
+}
@@ -1,24 +1,32 @@
-use std::collections::HashMap;
-
-use crate::wgsl::{Wgsl, WgslResult};
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    analysis,
+    family::Family,
+    launch, names,
+    options::Options,
+    pipeline::Pipeline,
+    shared_group::{self, GroupDef, SharedGroup},
+    wgsl::{Wgsl, WgslResult},
+};
+use heck::{ToSnakeCase, ToUpperCamelCase};
 use naga::{
-    proc::TypeResolution,
+    proc::{Layouter, TypeResolution},
     valid::{
         Capabilities, ExpressionError, FunctionError, FunctionInfo, GlobalUse, ModuleInfo,
-        ValidationError, ValidationFlags, Validator,
+        TypeError, ValidationError, ValidationFlags, Validator,
     },
-    ArraySize, Constant, ConstantInner, EntryPoint, Handle, ImageClass, ImageDimension, Module,
-    ScalarKind, ScalarValue, ShaderStage, StorageAccess, StorageClass, StorageFormat, Type,
-    TypeInner, VectorSize,
+    ArraySize, Binding, Constant, ConstantInner, EntryPoint, GlobalVariable, Handle, ImageClass,
+    ImageDimension, Module, ScalarKind, ScalarValue, ShaderStage, StorageAccess, StorageClass,
+    StorageFormat, StructMember, Type, TypeInner, VectorSize,
 };
-use proc_macro2::{Ident, Span, TokenStream};
-use proc_macro_error::{Diagnostic, Level};
-use quote::quote;
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use proc_macro_error::{abort, Diagnostic, Level};
+use quote::{quote, ToTokens};
+use syn::Lifetime;
 
-fn expression_error_span(_module: &Module, err: &ExpressionError) -> Option<naga::Span> {
-    match err {
-        _ => return None,
-    }
+fn expression_error_span(_module: &Module, _err: &ExpressionError) -> Option<naga::Span> {
+    None
 }
 
 fn validation_error_span(module: &Module, err: &ValidationError) -> Option<naga::Span> {
@@ -44,591 +52,3567 @@ fn validation_error_span(module: &Module, err: &ValidationError) -> Option<naga:
     })
 }
 
-pub fn shatter(wgsl: &Wgsl) -> proc_macro::TokenStream {
+fn parse(wgsl: &Wgsl, options: &Options) -> (Module, ModuleInfo) {
     let module = naga::front::wgsl::parse_str(&wgsl.source).wgsl_unwrap(wgsl);
 
-    let mut validator = Validator::new(ValidationFlags::all(), Capabilities::all());
+    // an IDE only needs the uses of the globals, errors are reported by the build
+    let flags = if options.ide {
+        ValidationFlags::empty()
+    } else {
+        names::check(wgsl, &module);
+
+        ValidationFlags::all()
+    };
+
+    let mut validator = Validator::new(flags, Capabilities::all());
     let info = validator.validate(&module).unwrap_or_else(|err| {
         let span = if let Some(span) = validation_error_span(&module, &err) {
-            *wgsl.get_span(span.to_range().map_or(0, |range| range.start))
+            wgsl.span(span)
         } else {
             Span::call_site()
         };
 
-        Diagnostic::spanned(span, Level::Error, format!("{}", err)).abort()
-    });
-
-    let consts = gen_consts(&module);
-    let types = gen_types(&module);
-    let entry_points = gen_entry_points(&module, &info, &wgsl.source);
+        let message = match err {
+            // naga parses `f16` but the backends can't compile it yet
+            ValidationError::Type {
+                error: TypeError::InvalidWidth(ScalarKind::Float, 2),
+                ..
+            } => String::from("`f16` is not supported by the shader backend yet"),
+            err => format!("{}", err),
+        };
 
-    let expanded = quote! {
-        #consts
-        #types
-        #entry_points
-    };
+        Diagnostic::spanned(span, Level::Error, message).abort()
+    });
 
-    proc_macro::TokenStream::from(expanded)
+    (module, info)
 }
 
-fn gen_entry_points(module: &Module, info: &ModuleInfo, source: &str) -> TokenStream {
-    let entry_points = module
-        .entry_points
-        .iter()
-        .enumerate()
-        .map(|(i, entry_point)| {
-            let name = &entry_point.name;
-            let ident = Ident::new(name, Span::call_site());
+pub fn shatter(wgsl: &Wgsl, options: &Options) -> TokenStream {
+    let (module, info) = parse(wgsl, options);
 
-            let function_info = info.get_entry_point(i);
+    let consts = gen_consts(wgsl, &module, options);
+    let types = gen_types(wgsl, &module, options);
+    let warnings = gen_module_warnings(wgsl, &module, &info, options);
+    let entry_points = gen_entry_points(wgsl, &module, &info, options, None);
+    let pipeline = options
+        .pipeline
+        .as_ref()
+        .map(|pipeline| gen_pipeline(wgsl, &module, &info, pipeline, options));
 
-            match entry_point.stage {
-                ShaderStage::Compute => gen_compute_entry_point(
-                    module,
-                    entry_point,
-                    source,
-                    name,
-                    &ident,
-                    function_info,
-                ),
-                _ => unimplemented!(),
-            }
-        });
+    let abi_guard = gen_abi_guard();
 
     quote! {
-        #(#entry_points)*
+        #abi_guard
+        #warnings
+        #consts
+        #types
+        #entry_points
+        #pipeline
     }
 }
 
-fn gen_compute_entry_point(
-    module: &Module,
-    entry_point: &EntryPoint,
-    source: &str,
-    name: &str,
-    ident: &Ident,
-    function_info: &FunctionInfo,
-) -> TokenStream {
-    let bindings_ident = Ident::new("Bindings", Span::call_site());
-
-    let bindings = gen_entry_point_bindings(module, &function_info, &bindings_ident);
+/// Generates a shader family, every entry point gets a module per value
+/// suffixed with the value, and a family module to select them at runtime.
+pub fn shatter_family(family: &Family, options: &Options) -> TokenStream {
+    if let Some(ref pipeline) = options.pipeline {
+        abort!(pipeline.span, "shader families can't declare a `pipeline!`");
+    }
 
-    let bindings_param = if bindings.is_some() {
-        Some(quote!(mut bindings: #ident::#bindings_ident<'a>,))
-    } else {
-        None
+    // an IDE only needs the items of every variant, which the first one has
+    let instantiated = match options.ide {
+        true => &family.values[..1],
+        false => &family.values[..],
     };
 
-    let bindings_build_var = if bindings.is_some() {
-        quote!(bindings)
-    } else {
-        quote!(())
-    };
+    let parsed = instantiated
+        .iter()
+        .map(|(value, literal)| {
+            let input = family.instantiate(literal);
+            let input = launch::inject(input, options);
 
-    let bindings_var = if bindings.is_some() {
-        quote!(bindings)
-    } else {
-        quote!()
-    };
+            let wgsl = Wgsl::with_options(&input, options);
+            let (module, info) = parse(&wgsl, options);
 
-    let shader_bindings = if bindings.is_some() {
-        quote!(#bindings_ident<'a>)
-    } else {
-        quote!(())
-    };
+            (*value, wgsl, module, info)
+        })
+        .collect::<Vec<_>>();
 
-    let work_group_size = {
-        let x = entry_point.workgroup_size[0];
-        let y = entry_point.workgroup_size[1];
-        let z = entry_point.workgroup_size[2];
+    let variants = family
+        .values
+        .iter()
+        .enumerate()
+        .map(|(i, (value, _))| {
+            let (_, wgsl, module, info) = parsed.get(i).unwrap_or(&parsed[0]);
 
-        quote!(::shatter::WorkGroupSize::new(
-            #x as ::std::primitive::u32,
-            #y as ::std::primitive::u32,
-            #z as ::std::primitive::u32,
-        ))
-    };
+            (*value, wgsl, module, info)
+        })
+        .collect::<Vec<_>>();
 
-    quote! {
-        pub mod #ident {
-            use super::*;
+    // constants and types are shared by every variant
+    let (_, first_wgsl, first, first_info) = &parsed[0];
+    let consts = gen_consts(first_wgsl, first, options);
+    let types = gen_types(first_wgsl, first, options);
+    let warnings = gen_module_warnings(first_wgsl, first, first_info, options);
+
+    for (value, wgsl, module, _) in &parsed[1..] {
+        if gen_consts(wgsl, module, options).to_string() != consts.to_string()
+            || gen_types(wgsl, module, options).to_string() != types.to_string()
+        {
+            abort!(
+                family.param,
+                "constants and types must not depend on `{}`, they differ for `{}`",
+                family.param,
+                value
+            );
+        }
+    }
 
-            pub const WORK_GROUP_SIZE: ::shatter::WorkGroupSize = #work_group_size;
+    let entry_points = variants
+        .iter()
+        .map(|(value, wgsl, module, info)| {
+            gen_entry_points(wgsl, module, info, options, Some(*value))
+        })
+        .collect::<Vec<_>>();
 
-            #bindings
+    let families = first
+        .entry_points
+        .iter()
+        .enumerate()
+        .map(|(i, entry_point)| gen_family_module(family, options, &variants, i, entry_point));
 
-            pub struct Shader;
+    let abi_guard = gen_abi_guard();
 
-            impl<'a> ::shatter::ComputeShader<'a> for Shader {
-                type Bindings = #shader_bindings;
+    quote! {
+        #abi_guard
+        #warnings
+        #consts
+        #types
+        #(#entry_points)*
+        #(#families)*
+    }
+}
 
-                const SOURCE: &'static ::std::primitive::str = #source;
-                const ENTRY_POINT: &'static ::std::primitive::str = #name;
-            }
+/// Generates a group shared by several `wgsl!` blocks, see [`shared_group`].
+pub fn shatter_group(def: &GroupDef) -> TokenStream {
+    let options = Options::default();
 
-            pub fn build<'a>(#bindings_param) -> ::shatter::ComputeShaderBuilder<'a, Shader> {
-                ::shatter::ComputeShaderBuilder::new(#bindings_build_var)
-            }
-        }
+    let wgsl = Wgsl::new(&def.declarations);
+    let (module, _) = parse(&wgsl, &options);
 
-        pub fn #ident<'a>(#bindings_param dispatch: ::shatter::Dispatch) {
-            #ident::build(#bindings_var).dispatch(dispatch);
-        }
+    let consts = gen_consts(&wgsl, &module, &options);
+    let types = gen_types(&wgsl, &module, &options);
+    let size_warnings = gen_size_warnings(&wgsl, &module, &options);
+    let stride_warnings = gen_uniform_stride_warnings(&wgsl, &module);
+    let bindings = gen_shared_group_bindings(&wgsl, &module, def);
+    let callback = shared_group::gen_callback(def);
+
+    let abi_guard = gen_abi_guard();
+
+    quote! {
+        #abi_guard
+        #size_warnings
+        #stride_warnings
+        #consts
+        #types
+        #bindings
+        #callback
     }
 }
 
-fn gen_entry_point_bindings(
-    module: &Module,
-    function: &FunctionInfo,
-    ident: &Ident,
-) -> Option<TokenStream> {
-    let mut max_group = 0;
-    let mut bind_group_layout_descriptors = HashMap::new();
-    let mut bind_group_descriptors = HashMap::new();
-    let mut prepare = Vec::new();
-    let mut read = Vec::new();
-    let mut write = Vec::new();
-
-    let fields = module
+/// Generates the struct holding the bindings of a shared group, which the
+/// `Bindings` of importing entry points delegate the group to.
+fn gen_shared_group_bindings(wgsl: &Wgsl, module: &Module, def: &GroupDef) -> TokenStream {
+    let mut variables = module
         .global_variables
         .iter()
-        .filter_map(|(handle, variable)| {
-            let binding = variable.binding.as_ref()?;
-
-            let var_use = function[handle];
+        .filter(|(_, variable)| variable.binding.is_some())
+        .collect::<Vec<_>>();
 
-            // terminate if variable is unused
-            if var_use.is_empty() {
-                return None;
-            }
+    variables.sort_by_key(|(_, variable)| variable.binding.as_ref().unwrap().binding);
 
-            let name = variable.name.as_ref()?;
-            let ident = Ident::new(name, Span::call_site());
+    let mut fields = Vec::new();
+    let mut layout_entries = Vec::new();
+    let mut resources = Vec::new();
+    let mut expectations = Vec::new();
+    let mut calls = Vec::new();
+    let mut write = Vec::new();
+    let mut needs_allocation = Vec::new();
+    let mut registry_requests = Vec::new();
+    let mut registry_fields = Vec::new();
+
+    for (index, &(handle, variable)) in variables.iter().enumerate() {
+        let name = variable.name.as_ref().unwrap_or_else(|| {
+            let span = wgsl.span(module.global_variables.get_span(handle));
+            abort!(span, "shared bindings must be named")
+        });
 
-            max_group = max_group.max(binding.group);
+        let ident = names::ident(name);
+        let binding = variable.binding.as_ref().unwrap().binding;
 
-            let ty = &module.types[variable.ty].inner;
+        let binding_type = gen_binding_type(wgsl, module, handle);
+        let min_size = binding_min_size(module, &module.types[variable.ty].inner);
+        let writable = is_writable(module, variable);
+        let ty = rust_type(module, variable.ty, &mut None, false);
 
-            let binding_type = match ty {
-                &TypeInner::Image {
-                    ref dim,
-                    arrayed,
-                    ref class,
-                } => {
-                    let dimension = wgpu_view_dimension(dim, arrayed);
-
-                    match class {
-                        ImageClass::Storage { format, access } => {
-                            let access = match access {
-                                _ if access.contains(StorageAccess::LOAD)
-                                    && access.contains(StorageAccess::STORE) =>
-                                {
-                                    quote!(::shatter::wgpu::StorageTextureAccess::ReadWrite)
-                                }
-                                _ if access.contains(StorageAccess::LOAD) => {
-                                    quote!(::shatter::wgpu::StorageTextureAccess::ReadOnly)
-                                }
-                                _ if access.contains(StorageAccess::STORE) => {
-                                    quote!(::shatter::wgpu::StorageTextureAccess::WriteOnly)
-                                }
-                                _ => unreachable!(),
-                            };
-
-                            let format = wgpu_texture_format(format);
-
-                            quote!(::shatter::BindingType::StorageTexture {
-                                access: #access,
-                                format: #format,
-                                view_dimension: #dimension,
-                            })
-                        }
-                        _ => unimplemented!(),
-                    }
-                }
-                _ => {
-                    let buffer_binding_type = match variable.class {
-                        StorageClass::Uniform => quote!(::shatter::BufferBindingType::Uniform),
-                        StorageClass::Storage { access } => {
-                            let read_only = !access.contains(StorageAccess::STORE);
+        layout_entries.push(quote! {
+            ::shatter::__abi::BindGroupLayoutEntry {
+                binding: #binding,
+                visibility: ::shatter::__abi::ShaderStages::COMPUTE,
+                ty: #binding_type,
+                count: ::std::option::Option::None,
+            }
+        });
 
-                            quote!(::shatter::BufferBindingType::Storage { read_only: #read_only })
-                        }
-                        _ => unimplemented!(),
-                    };
+        resources.push((
+            binding,
+            quote!(::shatter::__abi::Binding::binding_resource(self.#ident)),
+        ));
+
+        expectations.push(quote! {
+            bindings.push((
+                ::shatter::__abi::BindingExpectation {
+                    name: #name,
+                    ty: #binding_type,
+                    min_size: #min_size,
+                    writable: #writable,
+                },
+                ::shatter::__abi::Binding::resource_info(self.#ident),
+            ));
+        });
 
-                    quote! {
-                        ::shatter::BindingType::Buffer {
-                            ty: #buffer_binding_type,
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        }
-                    }
-                }
-            };
+        calls.push(BindingCall {
+            ident: ident.clone(),
+            optional: false,
+        });
+        needs_allocation.push(quote!(::shatter::__abi::Binding::needs_allocation(self.#ident)));
 
-            let layout_descriptor = bind_group_layout_descriptors
-                .entry(binding.group)
-                .or_insert_with(|| HashMap::new());
+        registry_requests
+            .push(quote!(::shatter::__abi::RegistryRequest::new::<#ty>(#name, #writable, false)));
 
-            let b = binding.binding;
+        if writable {
+            write.push(BindingCall {
+                ident: ident.clone(),
+                optional: false,
+            });
+            registry_fields.push(quote!(#ident: borrows.take_mut::<#ty>(offset + #index)));
+            fields.push(quote!(pub #ident: &'a mut dyn ::shatter::__abi::BindingMut<#ty>));
+        } else {
+            registry_fields.push(quote!(#ident: borrows.take::<#ty>(offset + #index)));
+            fields.push(quote!(pub #ident: &'a dyn ::shatter::__abi::Binding<#ty>));
+        }
+    }
 
-            layout_descriptor.insert(
-                binding.binding,
-                quote! {
-                    ::shatter::BindGroupLayoutEntry {
-                        binding: #b,
-                        visibility: ::shatter::ShaderStages::COMPUTE,
-                        ty: #binding_type,
-                        count: ::std::option::Option::None,
-                    }
-                },
-            );
+    let prepare = gen_binding_calls(
+        &calls,
+        |binding| quote!(::shatter::__abi::Binding::prepare(#binding, report)),
+    );
+    let read = gen_binding_calls(
+        &calls,
+        |binding| quote!(::shatter::__abi::Binding::read(#binding, report)),
+    );
+    let write = gen_binding_calls(
+        &write,
+        |binding| quote!(::shatter::__abi::BindingMut::write(#binding)),
+    );
+
+    let entries = resources.iter().map(|(binding, resource)| {
+        quote! {
+            ::shatter::__abi::BindGroupEntry {
+                binding: #binding as u32,
+                resource: #resource,
+            }
+        }
+    });
+    let keys = resources
+        .iter()
+        .map(|(binding, resource)| quote!(key.push(#binding as u32, &#resource);));
 
-            let descriptor = bind_group_descriptors
-                .entry(binding.group)
-                .or_insert_with(|| HashMap::new());
+    let name = &def.name;
+    let group = def.group;
+    let doc = format!(
+        "Bindings of group {} shared by the `wgsl!` blocks importing `{}`.",
+        group, name
+    );
 
-            descriptor.insert(
-                binding.binding,
-                quote!(::shatter::Binding::binding_resource(self.#ident)),
-            );
+    let allow = names::allow_naming();
 
-            let ty = rust_type(module, variable.ty, &mut None, false);
+    quote! {
+        #[doc = #doc]
+        #allow
+        pub struct #name<'a> {
+            #(#fields),*
+        }
 
-            // prepare binding
-            prepare.push(quote!(::shatter::Binding::prepare(self.#ident)));
+        impl<'a> #name<'a> {
+            pub const GROUP: ::std::primitive::u32 = #group;
 
-            // only read and write as necessary
-            if var_use.contains(GlobalUse::READ) {
-                read.push(quote!(::shatter::Binding::read(self.#ident)));
+            pub fn bind_group_layout_descriptor() -> ::shatter::__abi::BindGroupLayoutDescriptor {
+                ::shatter::__abi::BindGroupLayoutDescriptor {
+                    entries: ::std::vec![#(#layout_entries),*],
+                }
             }
 
-            if var_use.contains(GlobalUse::WRITE) {
-                write.push(quote!(::shatter::Binding::write(self.#ident)));
+            pub fn bind_group_descriptor(
+                &self,
+                layout: &::shatter::__abi::BindGroupLayoutId,
+            ) -> ::shatter::__abi::BindGroupDescriptor {
+                ::shatter::__abi::BindGroupDescriptor {
+                    layout: layout.clone(),
+                    entries: ::std::vec![#(#entries),*],
+                }
             }
 
-            if var_use.contains(GlobalUse::WRITE) {
-                return Some(quote!(pub #ident: &'a mut dyn ::shatter::Binding<#ty>));
+            pub fn bind_group_key(
+                &self,
+                layout: &::shatter::__abi::BindGroupLayoutId,
+            ) -> ::shatter::__abi::GroupKey {
+                let mut key = ::shatter::__abi::GroupKey::new(layout);
+                #(#keys)*
+                key
             }
 
-            if var_use.contains(GlobalUse::READ) {
-                return Some(quote!(pub #ident: &'a dyn ::shatter::Binding<#ty>));
+            #[inline]
+            pub fn prepare(&self, report: &mut ::shatter::__abi::TransferReport) {
+                #prepare
             }
 
-            None
-        })
-        .collect::<Vec<_>>();
+            #[inline]
+            pub fn read(&self, report: &mut ::shatter::__abi::TransferReport) {
+                #read
+            }
 
-    let bind_group_layout_descriptors = (0..=max_group).into_iter().map(|group| {
-        if let Some(descriptor) = bind_group_layout_descriptors.get(&group) {
-            let mut entries = descriptor.iter().collect::<Vec<_>>();
+            #[inline]
+            pub fn write(&mut self) {
+                #write
+            }
 
-            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            #[inline]
+            pub fn needs_allocation(&self) -> bool {
+                false #(|| #needs_allocation)*
+            }
 
-            let entries = entries.into_iter().map(|(_binding, entry)| entry);
+            #[doc(hidden)]
+            pub fn __push_expectations(
+                &self,
+                bindings: &mut ::std::vec::Vec<(
+                    ::shatter::__abi::BindingExpectation,
+                    ::shatter::__abi::ResourceInfo,
+                )>,
+            ) {
+                #(#expectations)*
+            }
 
-            quote! {
-                ::shatter::BindGroupLayoutDescriptor {
-                    entries: ::std::vec![#(#entries),*],
-                }
+            #[doc(hidden)]
+            pub fn __registry_requests() -> ::std::vec::Vec<::shatter::__abi::RegistryRequest> {
+                ::std::vec![#(#registry_requests),*]
             }
-        } else {
-            quote! {
-                ::shatter::BindGroupLayoutDescriptor {
-                    entries: ::std::vec::Vec::new(),
+
+            #[doc(hidden)]
+            pub fn __from_borrows(
+                borrows: &mut ::shatter::__abi::RegistryBorrows<'a>,
+                offset: ::std::primitive::usize,
+            ) -> Self {
+                Self {
+                    #(#registry_fields),*
                 }
             }
         }
-    });
 
-    let bind_group_descriptors = (0..=max_group).into_iter().map(|group| {
-        if let Some(descriptor) = bind_group_descriptors.get(&group) {
-            let mut resources = descriptor.iter().collect::<Vec<_>>();
+        // the single group, composed with those of entry points by `build_groups`
+        impl<'a> ::shatter::__abi::Bindings for #name<'a> {
+            fn bind_group_layout_descriptors(
+                &self,
+            ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+                ::std::vec![Self::bind_group_layout_descriptor()]
+            }
 
-            resources.sort_by(|(a, _), (b, _)| a.cmp(b));
+            fn bind_group_descriptors(
+                &self,
+                layouts: &[::shatter::__abi::BindGroupLayoutId],
+            ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+                ::std::vec![self.bind_group_descriptor(&layouts[0])]
+            }
 
-            let resources = resources.into_iter().map(|(binding, resource)| {
-                quote! {
-                    ::shatter::BindGroupEntry {
-                        binding: #binding as u32,
-                        resource: #resource,
-                    }
-                }
-            });
+            fn bind_group_keys(
+                &self,
+                layouts: &[::shatter::__abi::BindGroupLayoutId],
+            ) -> ::shatter::__abi::GroupKeys {
+                ::std::iter::once(self.bind_group_key(&layouts[0])).collect()
+            }
 
-            quote! {
-                ::shatter::BindGroupDescriptor {
-                    layout: layouts.next().unwrap().clone(),
-                    entries: ::std::vec![
-                        #(#resources),*
-                    ],
-                }
+            #[inline]
+            fn group_count(&self) -> ::std::primitive::usize {
+                1
             }
-        } else {
-            quote! {
-                ::shatter::BindGroupDescriptor {
-                    layout: layouts.next().unwrap().clone(),
-                    entries: ::std::vec::Vec::new(),
-                }
+
+            fn validate(&self) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+                let mut bindings = ::std::vec::Vec::new();
+                self.__push_expectations(&mut bindings);
+
+                ::shatter::__abi::validate_bindings(&bindings)
             }
-        }
-    });
 
-    if !fields.is_empty() {
-        Some(quote! {
-            pub struct #ident<'a> {
-                #(#fields),*
+            #[inline]
+            fn prepare(&self, report: &mut ::shatter::__abi::TransferReport) {
+                self.prepare(report);
             }
 
-            impl<'a> #ident<'a> {
-                pub fn bind_group_layout_descriptors(
-                    &self,
-                ) -> ::std::vec::Vec<::shatter::BindGroupLayoutDescriptor> {
-                    ::std::vec![#(#bind_group_layout_descriptors),*]
-                }
+            #[inline]
+            fn read(&self, report: &mut ::shatter::__abi::TransferReport) {
+                self.read(report);
+            }
 
-                pub fn bind_group_descriptors(
-                    &self,
-                    layouts: &[::shatter::BindGroupLayoutId],
-                ) -> ::std::vec::Vec<::shatter::BindGroupDescriptor> {
-                    let mut layouts = layouts.iter();
+            #[inline]
+            fn write(&mut self) {
+                self.write();
+            }
 
-                    ::std::vec![#(#bind_group_descriptors),*]
-                }
+            #[inline]
+            fn needs_allocation(&self) -> bool {
+                self.needs_allocation()
             }
+        }
+    }
+}
 
-            impl<'a> ::shatter::Bindings for #ident<'a> {
-                #[inline]
-                fn bind_group_layout_descriptors(
-                    &self,
-                ) -> ::std::vec::Vec<::shatter::BindGroupLayoutDescriptor> {
-                    self.bind_group_layout_descriptors()
+/// Returns true if the shader may write to `variable`, regardless of whether
+/// an entry point does.
+fn is_writable(module: &Module, variable: &GlobalVariable) -> bool {
+    match module.types[variable.ty].inner {
+        TypeInner::Image {
+            class: ImageClass::Storage { access, .. },
+            ..
+        } => access.contains(StorageAccess::STORE),
+        _ => matches!(
+            variable.class,
+            StorageClass::Storage { access } if access.contains(StorageAccess::STORE)
+        ),
+    }
+}
+
+fn gen_family_module(
+    family: &Family,
+    options: &Options,
+    variants: &[(u32, &Wgsl, &Module, &ModuleInfo)],
+    index: usize,
+    entry_point: &EntryPoint,
+) -> TokenStream {
+    let name = &entry_point.name;
+    let ident = names::ident(name);
+    let bindings_ident = Ident::new("Bindings", Span::call_site());
+
+    let mut variant_bindings = variants.iter().map(|(_, wgsl, module, info)| {
+        gen_entry_point_bindings(
+            wgsl,
+            module,
+            &[info.get_entry_point(index)],
+            &compute_visibility(),
+            &bindings_ident,
+            None,
+            options,
+        )
+    });
+
+    let bindings = variant_bindings
+        .next()
+        .unwrap()
+        .map(|bindings| bindings.tokens);
+
+    for ((value, ..), other) in variants.iter().zip(variant_bindings).skip(1) {
+        let other = other.map(|other| other.tokens.to_string());
+
+        if other != bindings.as_ref().map(ToString::to_string) {
+            abort!(
+                family.param,
+                "every variant of `{}` must use the same bindings, they differ for `{}` = `{}`",
+                name,
+                family.param,
+                value
+            );
+        }
+    }
+
+    let shader_bindings = if bindings.is_some() {
+        quote!(#bindings_ident<'a>)
+    } else {
+        quote!(())
+    };
+
+    let param = &family.param;
+    let param_name = param.to_string().to_snake_case();
+    let with_param = Ident::new(&format!("with_{}", param_name), Span::call_site());
+    let param_ident = Ident::new(&param_name, Span::call_site());
+
+    let values = variants.iter().map(|(value, ..)| value).collect::<Vec<_>>();
+    let variant_idents = values
+        .iter()
+        .map(|value| Ident::new(&format!("{}_{}", name, value), Span::call_site()));
+
+    let doc = format!("Values of `{}` the family was instantiated with.", param);
+
+    let allow = names::allow_naming();
+
+    quote! {
+        #allow
+        pub mod #ident {
+            use super::*;
+
+            #bindings
+
+            #[doc = #doc]
+            pub const #param: &[::std::primitive::u32] = &[#(#values),*];
+
+            /// Selects the variant instantiated with the given value.
+            pub fn #with_param<'a>(
+                #param_ident: ::std::primitive::u32,
+            ) -> ::std::option::Option<&'a dyn ::shatter::__abi::DispatchableShader<#shader_bindings>> {
+                match #param_ident {
+                    #(#values => ::std::option::Option::Some(&super::#variant_idents::Shader),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+    }
+}
+
+/// Version of the runtime interface the generated code is written against,
+/// must match `shatter::ABI_VERSION`.
+const ABI_VERSION: u32 = 32;
+
+pub fn gen_abi_guard() -> TokenStream {
+    let message = format!(
+        "shatter-macro {} requires shatter ABI {}, update the shatter crate",
+        env!("CARGO_PKG_VERSION"),
+        ABI_VERSION,
+    );
+
+    quote! {
+        const _: () = ::std::assert!(::shatter::ABI_VERSION == #ABI_VERSION, #message);
+    }
+}
+
+/// Generates the entry points, `variant` is the value of a family variant
+/// which is appended to the module names.
+///
+/// Outside of families, entry points with identical bindings share the
+/// `Bindings` of the first one and get a select enum, see [`gen_shader_select`].
+fn gen_entry_points(
+    wgsl: &Wgsl,
+    module: &Module,
+    info: &ModuleInfo,
+    options: &Options,
+    variant: Option<u32>,
+) -> TokenStream {
+    let bindings_ident = Ident::new("Bindings", Span::call_site());
+
+    let bindings = module
+        .entry_points
+        .iter()
+        .enumerate()
+        .map(|(i, entry_point)| {
+            if variant.is_some() || entry_point.stage != ShaderStage::Compute {
+                return None;
+            }
+
+            let function_info = info.get_entry_point(i);
+            let bindings = gen_entry_point_bindings(
+                wgsl,
+                module,
+                &[function_info],
+                &compute_visibility(),
+                &bindings_ident,
+                None,
+                options,
+            )?;
+
+            Some(bindings.tokens.to_string())
+        })
+        .collect::<Vec<_>>();
+
+    // index of the first entry point with the same bindings
+    let shared = (0..bindings.len())
+        .map(|i| {
+            let own = bindings[i].as_ref()?;
+
+            (0..i).find(|&j| bindings[j].as_ref() == Some(own))
+        })
+        .collect::<Vec<_>>();
+
+    let vertex = render_vertex_entry_point(module, options, variant);
+
+    let entry_points = module
+        .entry_points
+        .iter()
+        .enumerate()
+        .map(|(i, entry_point)| {
+            let name = &entry_point.name;
+
+            let (ident, bindings_from) = match variant {
+                Some(value) => (
+                    Ident::new(&format!("{}_{}", name, value), Span::call_site()),
+                    Some(names::ident(name)),
+                ),
+                None => (
+                    names::ident(name),
+                    shared[i].map(|j| names::ident(&module.entry_points[j].name)),
+                ),
+            };
+
+            let function_info = info.get_entry_point(i);
+
+            match entry_point.stage {
+                ShaderStage::Compute => gen_compute_entry_point(
+                    wgsl,
+                    module,
+                    entry_point,
+                    &ident,
+                    bindings_from.as_ref(),
+                    function_info,
+                    options,
+                ),
+                // drawn by the modules of the fragment entry points
+                ShaderStage::Vertex => TokenStream::new(),
+                ShaderStage::Fragment => {
+                    let vertex = vertex.unwrap();
+
+                    gen_render_entry_point(
+                        wgsl,
+                        module,
+                        &module.entry_points[vertex],
+                        entry_point,
+                        &ident,
+                        &[info.get_entry_point(vertex), function_info],
+                        options,
+                    )
+                }
+            }
+        });
+
+    let groups = (0..shared.len())
+        .filter(|&i| bindings[i].is_some() && shared[i].is_none())
+        .map(|i| {
+            let members = (0..shared.len()).filter(|&j| j == i || shared[j] == Some(i));
+
+            members.map(|j| &module.entry_points[j]).collect::<Vec<_>>()
+        })
+        .filter(|group| group.len() > 1)
+        .collect::<Vec<_>>();
+
+    let selects = groups
+        .iter()
+        .map(|group| gen_shader_select(group, groups.len() == 1));
+
+    let analysis_warnings = module
+        .entry_points
+        .iter()
+        .enumerate()
+        .filter(|_| !options.ide)
+        .map(|(i, entry_point)| {
+            let function_info = info.get_entry_point(i);
+
+            let loop_warning = gen_loop_iteration_warning(wgsl, module, entry_point, options);
+            let barrier_warnings =
+                gen_non_uniform_barrier_warnings(wgsl, module, info, entry_point, function_info);
+
+            quote!(#loop_warning #barrier_warnings)
+        });
+
+    quote! {
+        #(#analysis_warnings)*
+        #(#entry_points)*
+        #(#selects)*
+    }
+}
+
+/// Gets the index of the vertex entry point every fragment entry point is
+/// drawn with, `None` if the shader has no render entry points.
+fn render_vertex_entry_point(
+    module: &Module,
+    options: &Options,
+    variant: Option<u32>,
+) -> Option<usize> {
+    let stages = |stage| {
+        module
+            .entry_points
+            .iter()
+            .enumerate()
+            .filter(move |(_, entry_point)| entry_point.stage == stage)
+    };
+
+    let vertices = stages(ShaderStage::Vertex).collect::<Vec<_>>();
+    let fragments = stages(ShaderStage::Fragment).collect::<Vec<_>>();
+
+    let (_, first) = vertices.first().or_else(|| fragments.first())?;
+
+    let error = |message: String| Diagnostic::spanned(Span::call_site(), Level::Error, message);
+
+    if variant.is_some() {
+        error(format!(
+            "render entry point `{}` can't be part of a shader family",
+            first.name
+        ))
+        .abort();
+    }
+
+    if !options.shared_groups.is_empty() {
+        error(format!(
+            "render entry point `{}` can't use shared groups",
+            first.name
+        ))
+        .help("the layouts of `wgsl_group!` are only visible to compute shaders".into())
+        .abort();
+    }
+
+    let vertex = match (&vertices[..], fragments.first()) {
+        ([(vertex, _)], Some(_)) => *vertex,
+        ([], Some((_, fragment))) => error(format!(
+            "fragment entry point `{}` has no vertex entry point to be drawn with",
+            fragment.name
+        ))
+        .abort(),
+        (_, None) => error(format!(
+            "vertex entry point `{}` has no fragment entry point to be drawn with",
+            first.name
+        ))
+        .abort(),
+        _ => error(format!(
+            "found {} vertex entry points, fragment entry points are drawn with the only one",
+            vertices.len()
+        ))
+        .help("split the shader into one `wgsl!` block per vertex entry point".into())
+        .abort(),
+    };
+
+    let entry_point = &module.entry_points[vertex];
+
+    let has_vertex_buffers =
+        entry_point
+            .function
+            .arguments
+            .iter()
+            .any(|argument| match argument.binding {
+                Some(ref binding) => matches!(binding, Binding::Location { .. }),
+                // structs of inputs
+                None => match module.types[argument.ty].inner {
+                    TypeInner::Struct { ref members, .. } => members
+                        .iter()
+                        .any(|member| matches!(member.binding, Some(Binding::Location { .. }))),
+                    _ => false,
+                },
+            });
+
+    if has_vertex_buffers {
+        error(format!(
+            "vertex entry point `{}` takes vertex attributes, which aren't supported",
+            entry_point.name
+        ))
+        .help("derive the vertices from `vertex_index` or read them from a storage buffer".into())
+        .abort();
+    }
+
+    Some(vertex)
+}
+
+/// Generates the module of a fragment entry point, drawn with `vertex`.
+fn gen_render_entry_point(
+    wgsl: &Wgsl,
+    module: &Module,
+    vertex: &EntryPoint,
+    fragment: &EntryPoint,
+    ident: &Ident,
+    functions: &[&FunctionInfo],
+    options: &Options,
+) -> TokenStream {
+    let bindings_ident = Ident::new("Bindings", Span::call_site());
+
+    let bindings = gen_entry_point_bindings(
+        wgsl,
+        module,
+        functions,
+        &quote!(::shatter::__abi::ShaderStages::VERTEX_FRAGMENT),
+        &bindings_ident,
+        None,
+        options,
+    );
+
+    let bindings_def = bindings.as_ref().map(|bindings| &bindings.tokens);
+
+    let (bindings_param, bindings_var, shader_bindings) = if bindings.is_some() {
+        (
+            Some(quote!(bindings: #bindings_ident<'a>)),
+            quote!(bindings),
+            quote!(#bindings_ident<'a>),
+        )
+    } else {
+        (None, quote!(()), quote!(()))
+    };
+
+    let source = shader_source(wgsl, options);
+    let vertex_name = &vertex.name;
+    let fragment_name = &fragment.name;
+
+    let allow = names::allow_naming();
+
+    quote! {
+        #allow
+        pub mod #ident {
+            use super::*;
+
+            #bindings_def
+
+            pub struct Shader;
+
+            impl<'a> ::shatter::__abi::RenderShader<'a> for Shader {
+                type Bindings = #shader_bindings;
+
+                const SOURCE: &'static ::std::primitive::str = #source;
+                const VERTEX_ENTRY_POINT: &'static ::std::primitive::str = #vertex_name;
+                const FRAGMENT_ENTRY_POINT: &'static ::std::primitive::str = #fragment_name;
+            }
+
+            pub fn build<'a>(#bindings_param) -> ::shatter::__abi::RenderShaderBuilder<'a, Shader> {
+                ::shatter::__abi::RenderShaderBuilder::new(#bindings_var)
+            }
+        }
+    }
+}
+
+/// Generates an enum selecting between entry points sharing `Bindings`, with
+/// functions dispatching the selected entry point.
+///
+/// A module's only group is named `ShaderSelect`, otherwise groups are named
+/// after their first entry point, e.g. `LambertSelect`.
+fn gen_shader_select(entry_points: &[&EntryPoint], only_group: bool) -> TokenStream {
+    let first = &entry_points[0].name;
+    let first_ident = names::ident(first);
+
+    let (select, dispatch, dispatch_batch) = if only_group {
+        (
+            String::from("ShaderSelect"),
+            String::from("dispatch_selected"),
+            String::from("dispatch_selected_batch"),
+        )
+    } else {
+        (
+            format!("{}Select", first.to_upper_camel_case()),
+            format!("dispatch_{}_selected", first),
+            format!("dispatch_{}_selected_batch", first),
+        )
+    };
+
+    let select = Ident::new(&select, Span::call_site());
+    let batch_name = dispatch_batch.clone();
+    let dispatch = Ident::new(&dispatch, Span::call_site());
+    let dispatch_batch = Ident::new(&dispatch_batch, Span::call_site());
+
+    let names = entry_points
+        .iter()
+        .map(|entry_point| &entry_point.name)
+        .collect::<Vec<_>>();
+    let variants = names
+        .iter()
+        .map(|name| names::ident(&name.to_upper_camel_case()))
+        .collect::<Vec<_>>();
+    let modules = names
+        .iter()
+        .map(|name| names::ident(name))
+        .collect::<Vec<_>>();
+
+    let doc = format!(
+        "Entry points sharing `{}::Bindings`, selected at runtime.",
+        first
+    );
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum #select {
+            #(#variants),*
+        }
+
+        impl #select {
+            /// Every entry point, in declaration order.
+            pub const ALL: &'static [Self] = &[#(Self::#variants),*];
+
+            pub const fn entry_point(self) -> &'static ::std::primitive::str {
+                match self {
+                    #(Self::#variants => #names),*
+                }
+            }
+
+            pub fn shader<'a>(
+                self,
+            ) -> &'a dyn ::shatter::__abi::DispatchableShader<#first_ident::Bindings<'a>> {
+                match self {
+                    #(Self::#variants => &#modules::Shader),*
+                }
+            }
+        }
+
+        /// Dispatches the selected entry point.
+        pub fn #dispatch<'a>(
+            select: #select,
+            bindings: #first_ident::Bindings<'a>,
+            dispatch: ::shatter::__abi::Dispatch,
+        ) {
+            match select {
+                #(#select::#variants => #modules(bindings, dispatch)),*
+            }
+        }
+
+        /// Dispatches every selected entry point in order, in a single compute
+        /// pass of a single submission.
+        ///
+        /// The entry points share their bind groups, so they're bound once and
+        /// only the pipeline changes between dispatches.
+        pub fn #dispatch_batch<'a>(
+            bindings: #first_ident::Bindings<'a>,
+            batch: &[(#select, ::shatter::__abi::Dispatch)],
+        ) {
+            let batch = batch
+                .iter()
+                .map(|&(select, dispatch)| {
+                    let shader = match select {
+                        #(#select::#variants => ::shatter::__abi::BatchShader::of::<#modules::Shader>()),*
+                    };
+
+                    (shader, dispatch)
+                })
+                .collect::<::std::vec::Vec<_>>();
+
+            ::shatter::__abi::dispatch_shared_batch(#batch_name, bindings, &batch);
+        }
+    }
+}
+
+/// Generates the `Pipeline` of a `pipeline!` section, dispatching its passes
+/// in order with bindings borrowed from the `Bindings` of every pass.
+fn gen_pipeline(
+    wgsl: &Wgsl,
+    module: &Module,
+    info: &ModuleInfo,
+    pipeline: &Pipeline,
+    options: &Options,
+) -> TokenStream {
+    if !options.shared_groups.is_empty() {
+        abort!(
+            pipeline.span,
+            "shaders importing groups can't declare a `pipeline!`"
+        );
+    }
+
+    let indices = pipeline.check(module, info, options.ide);
+    let functions = indices
+        .iter()
+        .map(|&i| info.get_entry_point(i))
+        .collect::<Vec<_>>();
+
+    let bindings_ident = Ident::new("Bindings", Span::call_site());
+    let bindings = gen_entry_point_bindings(
+        wgsl,
+        module,
+        &functions,
+        &compute_visibility(),
+        &bindings_ident,
+        None,
+        options,
+    )
+    .unwrap_or_else(|| abort!(pipeline.span, "the passes of `pipeline!` bind nothing"));
+
+    // how a field is borrowed, mirroring `gen_entry_point_bindings`
+    #[derive(Clone, Copy)]
+    enum Field {
+        Mut,
+        Ref,
+        Optional,
+    }
+
+    let field = |variable: &GlobalVariable, var_use: GlobalUse| {
+        let ty = &module.types[variable.ty].inner;
+
+        if var_use.is_empty() && !options.keep_unused_bindings {
+            None
+        } else if var_use.contains(GlobalUse::WRITE) {
+            Some(Field::Mut)
+        } else if options.lenient_bindings
+            && !matches!(ty, TypeInner::Image { .. } | TypeInner::Sampler { .. })
+        {
+            Some(Field::Optional)
+        } else {
+            Some(Field::Ref)
+        }
+    };
+
+    let passes = pipeline.passes.iter().zip(&functions).enumerate().map(|(i, (pass, function))| {
+        let fields = module
+            .global_variables
+            .iter()
+            .filter(|(_, variable)| variable.binding.is_some())
+            .filter_map(|(handle, variable)| {
+                let name = variable.name.as_ref()?;
+
+                if name == launch::VAR {
+                    return None;
+                }
+
+                let var_use = functions
+                    .iter()
+                    .fold(GlobalUse::empty(), |var_use, function| var_use | function[handle]);
+
+                let ident = names::ident(name);
+                let ty = rust_type(module, variable.ty, &mut None, false);
+
+                let value = match (field(variable, var_use)?, field(variable, function[handle])?) {
+                    (Field::Mut, Field::Mut) => quote!(&mut *bindings.#ident),
+                    (Field::Mut, Field::Ref) => quote!(&*bindings.#ident),
+                    (Field::Mut, Field::Optional) => quote! {
+                        ::std::option::Option::Some(&*bindings.#ident as &dyn ::shatter::__abi::Binding<#ty>)
+                    },
+                    _ => quote!(bindings.#ident),
+                };
+
+                Some(quote!(#ident: #value))
+            })
+            .collect::<Vec<_>>();
+
+        let module = names::ident(&pass.to_string());
+        let pass_bindings = (!fields.is_empty()).then(|| quote!(#module::Bindings { #(#fields),* }));
+
+        quote! {
+            #module::build(#pass_bindings).dispatch_in(encoder, dispatches[#i]);
+        }
+    });
+
+    let names = pipeline
+        .passes
+        .iter()
+        .map(|pass| pass.to_string())
+        .collect::<Vec<_>>();
+    let count = names.len();
+    let label = "shatter_command_encoder(Pipeline::run)";
+
+    let tokens = &bindings.tokens;
+    let allow = names::allow_naming();
+
+    let doc = format!(
+        "Dispatches {} in order, see [`Pipeline::run`].",
+        names
+            .iter()
+            .map(|name| format!("`{}`", name))
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    );
+
+    quote! {
+        /// The resources of every pass of [`Pipeline`].
+        #allow
+        pub mod pipeline {
+            use super::*;
+
+            #tokens
+        }
+
+        #[doc = #doc]
+        pub struct Pipeline;
+
+        impl Pipeline {
+            /// The entry points of the passes, in the order they run.
+            pub const PASSES: &'static [&'static ::std::primitive::str] = &[#(#names),*];
+
+            /// Runs every pass in a single submission, `dispatches[i]` dispatching
+            /// the pass `PASSES[i]`.
+            ///
+            /// The passes borrow their resources from `bindings`, which are
+            /// marked as written once every pass is submitted.
+            pub fn run(
+                mut bindings: pipeline::Bindings<'_>,
+                dispatches: [::shatter::__abi::Dispatch; #count],
+            ) {
+                ::shatter::__abi::submit_encoded(#label, |encoder| {
+                    #(#passes)*
+                });
+
+                ::shatter::__abi::Bindings::write(&mut bindings);
+            }
+        }
+    }
+}
+
+fn gen_compute_entry_point(
+    wgsl: &Wgsl,
+    module: &Module,
+    entry_point: &EntryPoint,
+    ident: &Ident,
+    bindings_from: Option<&Ident>,
+    function_info: &FunctionInfo,
+    options: &Options,
+) -> TokenStream {
+    let name = &entry_point.name;
+    let bindings_ident = Ident::new("Bindings", Span::call_site());
+
+    let bindings = gen_entry_point_bindings(
+        wgsl,
+        module,
+        &[function_info],
+        &compute_visibility(),
+        &bindings_ident,
+        None,
+        options,
+    );
+
+    let groups = bindings
+        .as_ref()
+        .and_then(|bindings| gen_groups(wgsl, module, function_info, bindings, options));
+    let group_idents = groups.iter().flat_map(|groups| &groups.idents);
+
+    // variants of a family share the bindings of the family module, other
+    // entry points those of the first entry point with identical bindings
+    let bindings_def = match (&bindings, bindings_from) {
+        (Some(_), Some(from)) if !options.shared_groups.is_empty() => {
+            Some(quote!(pub use super::#from::{#bindings_ident #(, #group_idents)*};))
+        }
+        (Some(_), Some(from)) => Some(
+            quote!(pub use super::#from::{#bindings_ident, OwnedBindings #(, #group_idents)*};),
+        ),
+        (bindings, _) => bindings.as_ref().map(|bindings| {
+            let tokens = &bindings.tokens;
+            let groups = groups.as_ref().map(|groups| &groups.tokens);
+
+            quote! {
+                #tokens
+                #groups
+            }
+        }),
+    };
+
+    let build_groups = groups.as_ref().map(|groups| &groups.build);
+
+    let async_ident = Ident::new(&format!("{}_async", names::unraw(ident)), Span::call_site());
+
+    let allow = names::allow_naming();
+
+    let dispatch_async = match bindings {
+        Some(_) if !options.shared_groups.is_empty() => None,
+        Some(EntryBindings {
+            ref owned_params,
+            ref owned_bounds,
+            ..
+        }) => Some(quote! {
+            /// Like the blocking function without waiting on the GPU, resolves once the
+            /// dispatch is done and what it wrote is downloaded. Takes the resources
+            /// by value so the future is `Send`, they are handed back when it resolves.
+            #allow
+            pub async fn #async_ident<#(#owned_params),*>(
+                mut bindings: #ident::OwnedBindings<#(#owned_params),*>,
+                dispatch: ::shatter::__abi::Dispatch,
+            ) -> #ident::OwnedBindings<#(#owned_params),*>
+            where
+                #(#owned_bounds + ::std::marker::Send),*
+            {
+                let work = #ident::build(bindings.as_bindings()).tag(#name).dispatch_async(dispatch);
+                work.await;
+
+                if let ::std::result::Result::Err(err) = bindings.download_async().await {
+                    ::std::panic!("{}", err);
+                }
+
+                bindings
+            }
+        }),
+        None => Some(quote! {
+            /// Like the blocking function without waiting on the GPU, resolves once the
+            /// dispatch is done.
+            #allow
+            pub async fn #async_ident(dispatch: ::shatter::__abi::Dispatch) {
+                let work = #ident::build().tag(#name).dispatch_async(dispatch);
+                work.await;
+            }
+        }),
+    };
+
+    let bindings_param = if bindings.is_some() {
+        Some(quote!(mut bindings: #ident::#bindings_ident<'a>,))
+    } else {
+        None
+    };
+
+    let bindings_build_var = if bindings.is_some() {
+        quote!(bindings)
+    } else {
+        quote!(())
+    };
+
+    let bindings_var = if bindings.is_some() {
+        quote!(bindings)
+    } else {
+        quote!()
+    };
+
+    let validate = bindings.as_ref().map(|_| {
+        quote! {
+            /// Checks that `bindings` are compatible with the shader without dispatching.
+            pub fn validate(
+                bindings: &#bindings_ident<'_>,
+            ) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+                ::shatter::__abi::Bindings::validate(bindings)
+            }
+        }
+    });
+
+    let shader_bindings = if bindings.is_some() {
+        quote!(#bindings_ident<'a>)
+    } else {
+        quote!(())
+    };
+
+    let source = shader_source(wgsl, options);
+
+    let work_group_size = {
+        let x = entry_point.workgroup_size[0];
+        let y = entry_point.workgroup_size[1];
+        let z = entry_point.workgroup_size[2];
+
+        quote!(::shatter::__abi::WorkGroupSize::new(
+            #x as ::std::primitive::u32,
+            #y as ::std::primitive::u32,
+            #z as ::std::primitive::u32,
+        ))
+    };
+
+    let invocations = entry_point.workgroup_size.iter().product::<u32>();
+
+    let members = launch::Members::new(options);
+    let launch_params = (!members.is_empty()).then(|| {
+        let members = members.tokens();
+
+        quote!(
+            const LAUNCH_PARAMS: ::shatter::__abi::LaunchParams = #members;
+        )
+    });
+
+    let workgroup_warnings = (!options.ide)
+        .then(|| gen_workgroup_array_warnings(wgsl, module, entry_point, function_info));
+
+    let (push_constants_def, push_constants) = match gen_push_constants(wgsl, module, function_info)
+    {
+        Some(def) => (Some(def), quote!(PushConstants)),
+        None => (None, quote!(::shatter::__abi::NoPushConstants)),
+    };
+
+    let allow = names::allow_naming();
+
+    quote! {
+        #allow
+        pub mod #ident {
+            use super::*;
+
+            pub const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = #work_group_size;
+
+            /// Invocations per work group, the product of [`WORK_GROUP_SIZE`].
+            pub const WORK_GROUP_INVOCATIONS: ::std::primitive::u32 = #invocations;
+
+            /// Dispatch covering `elements` invocations along x, see
+            /// [`Dispatch::try_linear`](::shatter::__abi::Dispatch::try_linear).
+            pub fn checked_dispatch(
+                elements: ::std::primitive::u64,
+            ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+                ::shatter::__abi::Dispatch::try_linear(elements, WORK_GROUP_SIZE)
+            }
+
+            /// Dispatch covering `extent` invocations along each axis, see
+            /// [`Dispatch::try_for_extent`](::shatter::__abi::Dispatch::try_for_extent).
+            pub fn checked_dispatch_for_extent(
+                extent: [::std::primitive::u64; 3],
+            ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+                ::shatter::__abi::Dispatch::try_for_extent(extent, WORK_GROUP_SIZE)
+            }
+
+            /// Like [`checked_dispatch`], panics if the dispatch is too large.
+            pub fn dispatch_for(elements: ::std::primitive::u64) -> ::shatter::__abi::Dispatch {
+                match checked_dispatch(elements) {
+                    ::std::result::Result::Ok(dispatch) => dispatch,
+                    ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", #name, err),
+                }
+            }
+
+            /// Like [`checked_dispatch_for_extent`], panics if the dispatch is too large.
+            pub fn dispatch_for_extent(extent: [::std::primitive::u64; 3]) -> ::shatter::__abi::Dispatch {
+                match checked_dispatch_for_extent(extent) {
+                    ::std::result::Result::Ok(dispatch) => dispatch,
+                    ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", #name, err),
+                }
+            }
+
+            #workgroup_warnings
+
+            #bindings_def
+
+            #push_constants_def
+
+            #validate
+
+            pub struct Shader;
+
+            impl<'a> ::shatter::__abi::ComputeShader<'a> for Shader {
+                type Bindings = #shader_bindings;
+
+                const SOURCE: &'static ::std::primitive::str = #source;
+                const ENTRY_POINT: &'static ::std::primitive::str = #name;
+                const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = WORK_GROUP_SIZE;
+                #launch_params
+
+                type PushConstants = #push_constants;
+            }
+
+            impl<'a> ::shatter::__abi::DispatchableShader<#shader_bindings> for Shader {
+                fn entry_point(&self) -> &'static ::std::primitive::str {
+                    #name
+                }
+
+                fn work_group_size(&self) -> ::shatter::__abi::WorkGroupSize {
+                    WORK_GROUP_SIZE
+                }
+
+                fn dispatch(&self, bindings: #shader_bindings, dispatch: ::shatter::__abi::Dispatch) {
+                    ::shatter::__abi::ComputeShaderBuilder::<'a, Shader>::new(bindings).tag(#name).dispatch(dispatch);
+                }
+            }
+
+            pub fn build<'a>(#bindings_param) -> ::shatter::__abi::ComputeShaderBuilder<'a, Shader> {
+                ::shatter::__abi::ComputeShaderBuilder::new(#bindings_build_var)
+            }
+
+            #build_groups
+        }
+
+        #allow
+        pub fn #ident<'a>(#bindings_param dispatch: ::shatter::__abi::Dispatch) {
+            #ident::build(#bindings_var).tag(#name).dispatch(dispatch);
+        }
+
+        #dispatch_async
+    }
+}
+
+/// The bindings of a compute entry point split by group, see [`gen_groups`].
+struct EntryGroups {
+    /// The `GroupN` structs and the conversions of `Bindings` from and to them.
+    tokens: TokenStream,
+    /// The `build_groups` function.
+    build: TokenStream,
+    /// The `GroupN` structs, re-exported along with `Bindings`.
+    idents: Vec<Ident>,
+}
+
+/// Generates a `GroupN` struct per group of `bindings`, converting from and
+/// to `Bindings`, and `build_groups` taking them composed as a tuple, so a
+/// group can be reused across dispatches of several entry points.
+///
+/// Shared groups are composed as their `wgsl_group!` struct and groups
+/// without bindings as `EmptyGroup`. `None` for bindings of a single group.
+fn gen_groups(
+    wgsl: &Wgsl,
+    module: &Module,
+    function_info: &FunctionInfo,
+    bindings: &EntryBindings,
+    options: &Options,
+) -> Option<EntryGroups> {
+    // tuples of bindings have at most 4 elements, as many as wgpu binds
+    if bindings.max_group == 0 || bindings.max_group > 3 {
+        return None;
+    }
+
+    let mut structs = Vec::new();
+    let mut idents = Vec::new();
+    let mut types = Vec::new();
+    let mut bound_types = Vec::new();
+    let mut from_fields = Vec::new();
+    let mut into_elements = Vec::new();
+
+    for group in 0..=bindings.max_group {
+        let index = Literal::u32_unsuffixed(group);
+        // a lifetime per group, so a reused group can outlive the others
+        let lifetime = Lifetime::new(&format!("'g{}", group), Span::call_site());
+
+        if let Some(shared) = options.shared_group(group) {
+            let path = &shared.path;
+            let field = shared.field();
+
+            types.push(quote!(#path<'a>));
+            bound_types.push(quote!(#path<#lifetime>));
+            from_fields.push(quote!(#field: groups.#index));
+            into_elements.push(quote!(self.#field));
+
+            continue;
+        }
+
+        let ident = Ident::new(&format!("Group{}", group), Span::call_site());
+
+        let group_bindings = gen_entry_point_bindings(
+            wgsl,
+            module,
+            &[function_info],
+            &compute_visibility(),
+            &ident,
+            Some(group),
+            options,
+        );
+
+        match group_bindings {
+            Some(EntryBindings {
+                tokens,
+                field_idents,
+                ..
+            }) => {
+                let doc = format!(
+                    "The bindings of group {}, composed into [`Bindings`] by [`build_groups`].",
+                    group
+                );
+
+                structs.push(quote! {
+                    #[doc = #doc]
+                    #tokens
+                });
+
+                types.push(quote!(#ident<'a>));
+                bound_types.push(quote!(#ident<#lifetime>));
+                from_fields.extend(
+                    field_idents
+                        .iter()
+                        .map(|field| quote!(#field: groups.#index.#field)),
+                );
+                into_elements.push(quote!(#ident { #(#field_idents: self.#field_idents),* }));
+                idents.push(ident);
+            }
+            None => {
+                types.push(quote!(::shatter::__abi::EmptyGroup));
+                bound_types.push(quote!(::shatter::__abi::EmptyGroup));
+                into_elements.push(quote!(::shatter::__abi::EmptyGroup));
+            }
+        }
+    }
+
+    let params = (0..types.len())
+        .map(|group| Ident::new(&format!("G{}", group), Span::call_site()))
+        .collect::<Vec<_>>();
+    let lifetimes =
+        (0..types.len()).map(|group| Lifetime::new(&format!("'g{}", group), Span::call_site()));
+
+    let tokens = quote! {
+        #(#structs)*
+
+        impl<'a> Bindings<'a> {
+            /// Composes the bindings of each group.
+            pub fn from_groups(groups: (#(#types,)*)) -> Self {
+                Self {
+                    #(#from_fields),*
+                }
+            }
+
+            /// Splits the bindings into those of each group.
+            pub fn into_groups(self) -> (#(#types,)*) {
+                (#(#into_elements,)*)
+            }
+        }
+    };
+
+    let build = quote! {
+        /// Like [`build`] with the bindings of each group, or a mutable
+        /// reference to them, so a group can be reused for other dispatches
+        /// and entry points.
+        pub fn build_groups<'a, #(#lifetimes,)* #(#params),*>(
+            groups: (#(#params,)*),
+        ) -> ::shatter::__abi::ComputeShaderBuilder<'a, ::shatter::__abi::Composed<Shader, (#(#params,)*)>>
+        where
+            #(#params: ::shatter::__abi::Bindings + ::std::borrow::BorrowMut<#bound_types>),*
+        {
+            ::shatter::__abi::ComputeShaderBuilder::new(groups)
+        }
+    };
+
+    Some(EntryGroups {
+        tokens,
+        build,
+        idents,
+    })
+}
+
+/// Generates the `PushConstants` alias of the `var<push_constant>` global used
+/// by an entry point, if any.
+fn gen_push_constants(
+    wgsl: &Wgsl,
+    module: &Module,
+    function_info: &FunctionInfo,
+) -> Option<TokenStream> {
+    let (handle, variable) = module.global_variables.iter().find(|&(handle, variable)| {
+        variable.class == StorageClass::PushConstant && !function_info[handle].is_empty()
+    })?;
+
+    if !matches!(module.types[variable.ty].inner, TypeInner::Struct { .. }) {
+        let span = wgsl.span(module.global_variables.get_span(handle));
+
+        abort!(
+            span,
+            "push constants must be a struct";
+            help = "wrap `{}` in a struct", variable.name.as_deref().unwrap_or("_")
+        );
+    }
+
+    let ty = rust_type(module, variable.ty, &mut None, false);
+
+    Some(quote! {
+        /// The `var<push_constant>` global, set with
+        /// [`ComputeShaderBuilder::push_constants`](::shatter::__abi::ComputeShaderBuilder::push_constants).
+        pub type PushConstants = #ty;
+    })
+}
+
+/// A field of generated bindings visited by `prepare`, `read` or `write`.
+struct BindingCall {
+    ident: Ident,
+    optional: bool,
+}
+
+/// Generates `call` for every binding. With several bindings a resource bound
+/// to more than one of them is only visited through the first.
+fn gen_binding_calls(
+    calls: &[BindingCall],
+    call: impl Fn(TokenStream) -> TokenStream,
+) -> TokenStream {
+    let dedup = calls.len() > 1;
+
+    let calls = calls.iter().map(|BindingCall { ident, optional }| {
+        let binding = if *optional {
+            quote!(binding)
+        } else {
+            quote!(self.#ident)
+        };
+
+        let mut stmt = call(binding.clone());
+
+        if dedup {
+            stmt = quote! {
+                if seen.first_use(::shatter::__abi::Binding::binding_resource(#binding)) {
+                    #stmt;
+                }
+            };
+        } else {
+            stmt = quote!(#stmt;);
+        }
+
+        if *optional {
+            quote! {
+                if let ::std::option::Option::Some(binding) = self.#ident {
+                    #stmt
+                }
+            }
+        } else {
+            stmt
+        }
+    });
+
+    let seen = dedup.then(|| quote!(let mut seen = ::shatter::__abi::SeenResources::new();));
+
+    quote! {
+        #seen
+        #(#calls)*
+    }
+}
+
+fn compute_visibility() -> TokenStream {
+    quote!(::shatter::__abi::ShaderStages::COMPUTE)
+}
+
+/// Generates the bindings of the globals used by any of `functions`, visible
+/// to the shader stages `visibility`.
+///
+/// With `group`, only the bindings of that group are generated, as the single
+/// group of the bindings, for the `GroupN` structs of [`gen_groups`].
+fn gen_entry_point_bindings(
+    wgsl: &Wgsl,
+    module: &Module,
+    functions: &[&FunctionInfo],
+    visibility: &TokenStream,
+    ident: &Ident,
+    group: Option<u32>,
+    options: &Options,
+) -> Option<EntryBindings> {
+    // guarded entry points bind every group before that of the guard
+    let mut max_group = launch::group(module).map_or(0, |group| group - 1);
+    let mut field_idents = Vec::new();
+    // the guard counts the first runtime sized array, even of another group
+    let mut guard_taken = false;
+    let mut bind_group_layout_descriptors = HashMap::new();
+    let mut bind_group_descriptors = HashMap::new();
+    let mut expectations = Vec::new();
+    let mut prepare = Vec::new();
+    let mut read = Vec::new();
+    let mut write = Vec::new();
+    let mut needs_allocation = Vec::new();
+    let mut guard_count = None;
+    let mut registry_requests = Vec::new();
+    let mut registry_fields = Vec::new();
+    let mut owned = Vec::new();
+
+    let mut fields = module
+        .global_variables
+        .iter()
+        .filter_map(|(handle, variable)| {
+            let binding = variable.binding.as_ref()?;
+
+            // bound through the field of the group
+            if let Some(shared) = options.shared_group(binding.group) {
+                let name = variable.name.as_deref().unwrap_or_default();
+
+                if !shared.variables.contains(name) {
+                    let span = wgsl.span(module.global_variables.get_span(handle));
+
+                    abort!(
+                        span,
+                        "group {} is shared by `{}`, declare `{}` there or use another group",
+                        binding.group,
+                        shared.path.to_token_stream(),
+                        name
+                    );
+                }
+
+                return None;
+            }
+
+            let var_use = functions
+                .iter()
+                .fold(GlobalUse::empty(), |var_use, function| var_use | function[handle]);
+
+            // unused variables are omitted unless kept, see `gen_unused_binding_warnings`
+            if var_use.is_empty() && !options.keep_unused_bindings {
+                return None;
+            }
+
+            let name = variable.name.as_ref()?;
+
+            // bound by the builder
+            if name == launch::VAR {
+                return None;
+            }
+
+            if group.is_some_and(|group| group != binding.group) {
+                guard_taken |= runtime_array(module, variable.ty).is_some();
+                return None;
+            }
+
+            let ident = names::ident(name);
+            field_idents.push(ident.clone());
+
+            max_group = max_group.max(binding.group);
+
+            let ty = &module.types[variable.ty].inner;
+
+            let binding_type = gen_binding_type(wgsl, module, handle);
+
+            let layout_descriptor = bind_group_layout_descriptors
+                .entry(binding.group)
+                .or_insert_with(HashMap::new);
+
+            let min_size = binding_min_size(module, ty);
+            let writable = var_use.contains(GlobalUse::WRITE);
+
+            // the placeholder is shared, so only read-only buffers can be optional
+            let optional = options.lenient_bindings
+                && !writable
+                && !matches!(ty, TypeInner::Image { .. } | TypeInner::Sampler { .. });
+
+            if guard_count.is_none() && !guard_taken {
+                if let Some((offset, stride)) = runtime_array(module, variable.ty) {
+                    let offset = offset as u64;
+                    let stride = stride as u64;
+
+                    let data_size = if optional {
+                        quote!(self.#ident.and_then(|binding| ::shatter::__abi::Binding::data_size(binding)))
+                    } else {
+                        quote!(::shatter::__abi::Binding::data_size(self.#ident))
+                    };
+
+                    guard_count = Some(quote! {
+                        #data_size.map(|size| (size.saturating_sub(#offset) / #stride) as ::std::primitive::u32)
+                    });
+                }
+            }
+
+            let expectation = quote! {
+                ::shatter::__abi::BindingExpectation {
+                    name: #name,
+                    ty: #binding_type,
+                    min_size: #min_size,
+                    writable: #writable,
+                }
+            };
+
+            expectations.push(if optional {
+                quote! {
+                    if let ::std::option::Option::Some(binding) = self.#ident {
+                        bindings.push((#expectation, ::shatter::__abi::Binding::resource_info(binding)));
+                    }
+                }
+            } else {
+                quote! {
+                    bindings.push((#expectation, ::shatter::__abi::Binding::resource_info(self.#ident)));
+                }
+            });
+
+            let b = binding.binding;
+
+            layout_descriptor.insert(
+                binding.binding,
+                quote! {
+                    ::shatter::__abi::BindGroupLayoutEntry {
+                        binding: #b,
+                        visibility: #visibility,
+                        ty: #binding_type,
+                        count: ::std::option::Option::None,
+                    }
+                },
+            );
+
+            let descriptor = bind_group_descriptors
+                .entry(binding.group)
+                .or_insert_with(HashMap::new);
+
+            let ty = rust_type(module, variable.ty, &mut None, false);
+
+            if optional {
+                descriptor.insert(
+                    binding.binding,
+                    quote!(::shatter::__abi::optional_binding_resource(self.#ident, #min_size)),
+                );
+
+                prepare.push(BindingCall {
+                    ident: ident.clone(),
+                    optional: true,
+                });
+                read.push(BindingCall {
+                    ident: ident.clone(),
+                    optional: true,
+                });
+
+                needs_allocation.push(quote! {
+                    self.#ident.is_some_and(|binding| ::shatter::__abi::Binding::needs_allocation(binding))
+                });
+
+                let index = registry_requests.len();
+                registry_requests.push(quote!(::shatter::__abi::RegistryRequest::new::<#ty>(#name, false, true)));
+                registry_fields.push(quote!(#ident: borrows.take_optional::<#ty>(#index)));
+
+                let param = Ident::new(&format!("B{}", owned.len()), Span::call_site());
+                owned.push(OwnedField {
+                    field: quote!(pub #ident: ::std::option::Option<#param>),
+                    borrow: quote! {
+                        #ident: self.#ident.as_ref().map(|binding| binding as &dyn ::shatter::__abi::Binding<#ty>)
+                    },
+                    bound: quote!(#param: ::shatter::__abi::Binding<#ty>),
+                    download: None,
+                    param,
+                });
+
+                return Some(quote! {
+                    pub #ident: ::std::option::Option<&'a dyn ::shatter::__abi::Binding<#ty>>
+                });
+            }
+
+            descriptor.insert(
+                binding.binding,
+                quote!(::shatter::__abi::Binding::binding_resource(self.#ident)),
+            );
+
+            // prepare binding
+            prepare.push(BindingCall {
+                ident: ident.clone(),
+                optional: false,
+            });
+            needs_allocation.push(quote!(::shatter::__abi::Binding::needs_allocation(self.#ident)));
+
+            // only read and write as necessary
+            if var_use.contains(GlobalUse::READ) {
+                read.push(BindingCall {
+                    ident: ident.clone(),
+                    optional: false,
+                });
+            }
+
+            if var_use.contains(GlobalUse::WRITE) {
+                write.push(BindingCall {
+                    ident: ident.clone(),
+                    optional: false,
+                });
+            }
+
+            let index = registry_requests.len();
+
+            if var_use.contains(GlobalUse::WRITE) {
+                registry_requests.push(quote!(::shatter::__abi::RegistryRequest::new::<#ty>(#name, true, false)));
+                registry_fields.push(quote!(#ident: borrows.take_mut::<#ty>(#index)));
+
+                let param = Ident::new(&format!("B{}", owned.len()), Span::call_site());
+                owned.push(OwnedField {
+                    field: quote!(pub #ident: #param),
+                    borrow: quote!(#ident: &mut self.#ident),
+                    bound: quote!(#param: ::shatter::__abi::BindingMut<#ty>),
+                    download: Some(quote!(::shatter::__abi::Binding::<#ty>::download_async(&mut self.#ident).await?)),
+                    param,
+                });
+
+                return Some(quote!(pub #ident: &'a mut dyn ::shatter::__abi::BindingMut<#ty>));
+            }
+
+            // read, or kept while unused
+            registry_requests.push(quote!(::shatter::__abi::RegistryRequest::new::<#ty>(#name, false, false)));
+            registry_fields.push(quote!(#ident: borrows.take::<#ty>(#index)));
+
+            let param = Ident::new(&format!("B{}", owned.len()), Span::call_site());
+            owned.push(OwnedField {
+                field: quote!(pub #ident: #param),
+                borrow: quote!(#ident: &self.#ident),
+                bound: quote!(#param: ::shatter::__abi::Binding<#ty>),
+                download: None,
+                param,
+            });
+
+            Some(quote!(pub #ident: &'a dyn ::shatter::__abi::Binding<#ty>))
+        })
+        .collect::<Vec<_>>();
+
+    // imported groups are held as a whole, in place of their variables
+    let shared_groups = match group {
+        Some(_) => &[][..],
+        None => &options.shared_groups[..],
+    };
+    let shared_fields = shared_groups
+        .iter()
+        .map(SharedGroup::field)
+        .collect::<Vec<_>>();
+
+    for (shared, field) in shared_groups.iter().zip(&shared_fields) {
+        let path = &shared.path;
+
+        let clashes = module
+            .global_variables
+            .iter()
+            .any(|(_, variable)| variable.name.as_deref() == Some(&field.to_string()));
+
+        if clashes {
+            abort!(
+                field,
+                "the field of `{}` clashes with the binding `{}`",
+                path.to_token_stream(),
+                field
+            );
+        }
+
+        max_group = max_group.max(shared.group);
+
+        field_idents.push(field.clone());
+        fields.push(quote!(pub #field: #path<'a>));
+        expectations.push(quote!(self.#field.__push_expectations(&mut bindings);));
+        needs_allocation.push(quote!(self.#field.needs_allocation()));
+        registry_fields
+            .push(quote!(#field: #path::__from_borrows(&mut borrows, offsets.next().unwrap())));
+    }
+
+    let registry_borrows = if shared_groups.is_empty() {
+        quote! {
+            let mut borrows = registry.borrow_bindings(&[#(#registry_requests),*], rename)?;
+        }
+    } else {
+        let shared_paths = shared_groups.iter().map(|shared| &shared.path);
+
+        quote! {
+            let mut requests = ::std::vec![#(#registry_requests),*];
+            let mut offsets = ::std::vec::Vec::new();
+
+            #(
+                offsets.push(requests.len());
+                requests.extend(#shared_paths::__registry_requests());
+            )*
+
+            let mut offsets = offsets.into_iter();
+            let mut borrows = registry.borrow_bindings(&requests, rename)?;
+        }
+    };
+
+    let prepare = gen_binding_calls(
+        &prepare,
+        |binding| quote!(::shatter::__abi::Binding::prepare(#binding, report)),
+    );
+    let read = gen_binding_calls(
+        &read,
+        |binding| quote!(::shatter::__abi::Binding::read(#binding, report)),
+    );
+    let write = gen_binding_calls(
+        &write,
+        |binding| quote!(::shatter::__abi::BindingMut::write(#binding)),
+    );
+
+    let shared_prepare = quote!(#(self.#shared_fields.prepare(report);)*);
+    let shared_read = quote!(#(self.#shared_fields.read(report);)*);
+    let shared_write = quote!(#(self.#shared_fields.write();)*);
+
+    let guard_count = guard_count
+        .filter(|_| options.auto_guard)
+        .map(|guard_count| {
+            quote! {
+                #[inline]
+                fn guard_count(&self) -> ::std::option::Option<::std::primitive::u32> {
+                    #guard_count
+                }
+            }
+        });
+
+    let group_count = group.map(|_| {
+        quote! {
+            #[inline]
+            fn group_count(&self) -> ::std::primitive::usize {
+                1
+            }
+        }
+    });
+
+    let needs_allocation = if needs_allocation.is_empty() {
+        quote!(false)
+    } else {
+        quote!(#(#needs_allocation)||*)
+    };
+
+    let groups = match group {
+        Some(group) => group..=group,
+        None => 0..=max_group,
+    };
+
+    let bind_group_layout_descriptors = groups.clone().map(|group| {
+        if let Some(shared) = options.shared_group(group) {
+            let path = &shared.path;
+
+            return quote!(#path::bind_group_layout_descriptor());
+        }
+
+        if let Some(descriptor) = bind_group_layout_descriptors.get(&group) {
+            let mut entries = descriptor.iter().collect::<Vec<_>>();
+
+            entries.sort_by_key(|(binding, _)| **binding);
+
+            let entries = entries.into_iter().map(|(_binding, entry)| entry);
+
+            quote! {
+                ::shatter::__abi::BindGroupLayoutDescriptor {
+                    entries: ::std::vec![#(#entries),*],
+                }
+            }
+        } else {
+            quote! {
+                ::shatter::__abi::BindGroupLayoutDescriptor {
+                    entries: ::std::vec::Vec::new(),
+                }
+            }
+        }
+    });
+
+    let bind_group_keys = groups.clone().map(|group| {
+        if let Some(shared) = options.shared_group(group) {
+            let field = shared.field();
+
+            return quote!(self.#field.bind_group_key(layouts.next().unwrap()));
+        }
+
+        if let Some(descriptor) = bind_group_descriptors.get(&group) {
+            let mut resources = descriptor.iter().collect::<Vec<_>>();
+
+            resources.sort_by_key(|(binding, _)| **binding);
+
+            let entries = resources
+                .into_iter()
+                .map(|(binding, resource)| quote!(key.push(#binding as u32, &#resource);));
+
+            quote! {{
+                let mut key = ::shatter::__abi::GroupKey::new(layouts.next().unwrap());
+                #(#entries)*
+                key
+            }}
+        } else {
+            quote!(::shatter::__abi::GroupKey::new(layouts.next().unwrap()))
+        }
+    });
+
+    let bind_group_descriptors = groups.map(|group| {
+        if let Some(shared) = options.shared_group(group) {
+            let field = shared.field();
+
+            return quote!(self.#field.bind_group_descriptor(layouts.next().unwrap()));
+        }
+
+        if let Some(descriptor) = bind_group_descriptors.get(&group) {
+            let mut resources = descriptor.iter().collect::<Vec<_>>();
+
+            resources.sort_by_key(|(binding, _)| **binding);
+
+            let resources = resources.into_iter().map(|(binding, resource)| {
+                quote! {
+                    ::shatter::__abi::BindGroupEntry {
+                        binding: #binding as u32,
+                        resource: #resource,
+                    }
+                }
+            });
+
+            quote! {
+                ::shatter::__abi::BindGroupDescriptor {
+                    layout: layouts.next().unwrap().clone(),
+                    entries: ::std::vec![
+                        #(#resources),*
+                    ],
+                }
+            }
+        } else {
+            quote! {
+                ::shatter::__abi::BindGroupDescriptor {
+                    layout: layouts.next().unwrap().clone(),
+                    entries: ::std::vec::Vec::new(),
+                }
+            }
+        }
+    });
+
+    if fields.is_empty() {
+        return None;
+    }
+
+    let owned_params = owned
+        .iter()
+        .map(|owned| owned.param.clone())
+        .collect::<Vec<_>>();
+    let owned_bounds = owned
+        .iter()
+        .map(|owned| owned.bound.clone())
+        .collect::<Vec<_>>();
+    let owned_fields = owned.iter().map(|owned| &owned.field);
+    let owned_borrows = owned.iter().map(|owned| &owned.borrow);
+    let owned_downloads = owned.iter().filter_map(|owned| owned.download.as_ref());
+
+    let allow = names::allow_naming();
+
+    // owned bindings hold resources by value, which groups don't
+    let owned_bindings = (options.shared_groups.is_empty() && group.is_none()).then(|| {
+        quote! {
+            /// Like [`Bindings`] owning the resources, taken by the `_async`
+            /// dispatch so its future is `Send` and handed back once it's done.
+            #allow
+            pub struct OwnedBindings<#(#owned_params),*> {
+                #(#owned_fields),*
+            }
+
+            impl<#(#owned_params),*> OwnedBindings<#(#owned_params),*>
+            where
+                #(#owned_bounds),*
+            {
+                /// Borrows the resources as [`Bindings`].
+                pub fn as_bindings(&mut self) -> #ident<'_> {
+                    #ident {
+                        #(#owned_borrows),*
+                    }
+                }
+
+                /// Downloads what the GPU wrote to the writable resources
+                /// without blocking, returns the number of bytes downloaded.
+                pub async fn download_async(
+                    &mut self,
+                ) -> ::std::result::Result<::std::primitive::u64, ::shatter::__abi::Error> {
+                    ::std::result::Result::Ok(0 #(+ #owned_downloads)*)
+                }
+            }
+        }
+    });
+
+    let tokens = quote! {
+            #allow
+            pub struct #ident<'a> {
+                #(#fields),*
+            }
+
+            impl<'a> #ident<'a> {
+                /// Borrows every binding from `registry` by its WGSL name,
+                /// reporting every missing or mismatched resource.
+                pub fn from_registry(
+                    registry: &'a mut ::shatter::__abi::ResourceRegistry,
+                ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+                    Self::from_registry_renamed(registry, &[])
+                }
+
+                /// Like [`Self::from_registry`], `rename` maps WGSL names to
+                /// the names resources are stored under.
+                pub fn from_registry_renamed(
+                    registry: &'a mut ::shatter::__abi::ResourceRegistry,
+                    rename: &[(&::std::primitive::str, &::std::primitive::str)],
+                ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+                    #registry_borrows
+
+                    ::std::result::Result::Ok(Self {
+                        #(#registry_fields),*
+                    })
+                }
+
+                pub fn bind_group_layout_descriptors(
+                    &self,
+                ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+                    ::std::vec![#(#bind_group_layout_descriptors),*]
+                }
+
+                pub fn bind_group_descriptors(
+                    &self,
+                    layouts: &[::shatter::__abi::BindGroupLayoutId],
+                ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+                    let mut layouts = layouts.iter();
+
+                    ::std::vec![#(#bind_group_descriptors),*]
+                }
+
+                pub fn bind_group_keys(
+                    &self,
+                    layouts: &[::shatter::__abi::BindGroupLayoutId],
+                ) -> ::shatter::__abi::GroupKeys {
+                    let mut layouts = layouts.iter();
+                    let mut keys = ::shatter::__abi::GroupKeys::new();
+
+                    #(keys.push(#bind_group_keys);)*
+
+                    keys
+                }
+            }
+
+            impl<'a> ::shatter::__abi::Bindings for #ident<'a> {
+                #[inline]
+                fn bind_group_layout_descriptors(
+                    &self,
+                ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+                    self.bind_group_layout_descriptors()
+                }
+
+                #[inline]
+                fn bind_group_descriptors(
+                    &self,
+                    layouts: &[::shatter::__abi::BindGroupLayoutId],
+                ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+                    self.bind_group_descriptors(layouts)
+                }
+
+                #[inline]
+                fn bind_group_keys(
+                    &self,
+                    layouts: &[::shatter::__abi::BindGroupLayoutId],
+                ) -> ::shatter::__abi::GroupKeys {
+                    self.bind_group_keys(layouts)
+                }
+
+                fn validate(&self) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+                    let mut bindings = ::std::vec::Vec::new();
+
+                    #(#expectations)*
+
+                    ::shatter::__abi::validate_bindings(&bindings)
+                }
+
+                #[inline]
+                fn prepare(&self, report: &mut ::shatter::__abi::TransferReport) {
+                    #prepare
+                    #shared_prepare
+                }
+
+                #[inline]
+                fn read(&self, report: &mut ::shatter::__abi::TransferReport) {
+                    #read
+                    #shared_read
+                }
+
+                #[inline]
+                fn write(&mut self) {
+                    #write
+                    #shared_write
+                }
+
+                #[inline]
+                fn needs_allocation(&self) -> bool {
+                    #needs_allocation
+                }
+
+                #group_count
+
+                #guard_count
+            }
+
+            #owned_bindings
+    };
+
+    Some(EntryBindings {
+        tokens,
+        owned_params,
+        owned_bounds,
+        max_group,
+        field_idents,
+    })
+}
+
+/// Gets the minimum size in bytes of a resource bound to a variable of type `ty`.
+fn binding_min_size(module: &Module, ty: &TypeInner) -> u64 {
+    match ty {
+        TypeInner::Image { .. } | TypeInner::Sampler { .. } => 0,
+        _ => ty.span(&module.constants) as u64,
+    }
+}
+
+/// Generates the `BindingType` of the global variable `handle`.
+fn gen_binding_type(wgsl: &Wgsl, module: &Module, handle: Handle<GlobalVariable>) -> TokenStream {
+    let variable = &module.global_variables[handle];
+    let ty = &module.types[variable.ty].inner;
+
+    match ty {
+        &TypeInner::Image {
+            ref dim,
+            arrayed,
+            ref class,
+        } => {
+            let dimension = wgpu_view_dimension(dim, arrayed);
+
+            match class {
+                ImageClass::Storage { format, access } => {
+                    let access = match access {
+                        _ if access.contains(StorageAccess::LOAD)
+                            && access.contains(StorageAccess::STORE) =>
+                        {
+                            quote!(::shatter::__abi::wgpu::StorageTextureAccess::ReadWrite)
+                        }
+                        _ if access.contains(StorageAccess::LOAD) => {
+                            quote!(::shatter::__abi::wgpu::StorageTextureAccess::ReadOnly)
+                        }
+                        _ if access.contains(StorageAccess::STORE) => {
+                            quote!(::shatter::__abi::wgpu::StorageTextureAccess::WriteOnly)
+                        }
+                        _ => unreachable!(),
+                    };
+
+                    let format = wgpu_texture_format(format).unwrap_or_else(|| {
+                        unsupported_binding(
+                            wgsl,
+                            module,
+                            handle,
+                            format!("storage texture format `{:?}` is not supported", format),
+                        )
+                    });
+
+                    quote!(::shatter::__abi::BindingType::StorageTexture {
+                        access: #access,
+                        format: #format,
+                        view_dimension: #dimension,
+                    })
+                }
+                ImageClass::Sampled { kind, multi } => {
+                    // matches `texture_sample_type::Float<true>` of the binding
+                    let sample_type = match kind {
+                        ScalarKind::Float => {
+                            quote!(::shatter::__abi::wgpu::TextureSampleType::Float {
+                                filterable: true
+                            })
+                        }
+                        ScalarKind::Sint => quote!(::shatter::__abi::wgpu::TextureSampleType::Sint),
+                        ScalarKind::Uint => quote!(::shatter::__abi::wgpu::TextureSampleType::Uint),
+                        ScalarKind::Bool => unreachable!(),
+                    };
+
+                    quote!(::shatter::__abi::BindingType::Texture {
+                        sample_type: #sample_type,
+                        view_dimension: #dimension,
+                        multisampled: #multi,
+                    })
+                }
+                ImageClass::Depth { .. } => {
+                    unsupported_binding(wgsl, module, handle, "depth textures are not supported")
+                }
+            }
+        }
+        TypeInner::Sampler { comparison } => {
+            if *comparison {
+                unsupported_binding(
+                    wgsl,
+                    module,
+                    handle,
+                    "comparison samplers are not supported",
+                )
+            }
+
+            quote!(::shatter::__abi::BindingType::Sampler {
+                filtering: true,
+                comparison: false,
+            })
+        }
+        _ => {
+            let buffer_binding_type = match variable.class {
+                StorageClass::Uniform => quote!(::shatter::__abi::BufferBindingType::Uniform),
+                StorageClass::Storage { access } => {
+                    let read_only = !access.contains(StorageAccess::STORE);
+
+                    quote!(::shatter::__abi::BufferBindingType::Storage { read_only: #read_only })
+                }
+                class => unsupported_binding(
+                    wgsl,
+                    module,
+                    handle,
+                    format!("bindings in storage class `{:?}` are not supported", class),
+                ),
+            };
+
+            quote! {
+                ::shatter::__abi::BindingType::Buffer {
+                    ty: #buffer_binding_type,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                }
+            }
+        }
+    }
+}
+
+/// Bindings generated for an entry point by [`gen_entry_point_bindings`].
+struct EntryBindings {
+    tokens: TokenStream,
+    /// Type parameters of `OwnedBindings`, one per field.
+    owned_params: Vec<Ident>,
+    owned_bounds: Vec<TokenStream>,
+    /// The last group of the bindings, at least the one before the launch
+    /// parameters of guarded entry points.
+    max_group: u32,
+    field_idents: Vec<Ident>,
+}
+
+/// A field of the `OwnedBindings` of an entry point.
+struct OwnedField {
+    param: Ident,
+    field: TokenStream,
+    /// Field of `Bindings` borrowing the resource.
+    borrow: TokenStream,
+    bound: TokenStream,
+    /// Download of what the dispatch wrote, for writable resources.
+    download: Option<TokenStream>,
+}
+
+/// Aborts with an error pointing at the global variable `handle`.
+fn unsupported_binding(
+    wgsl: &Wgsl,
+    module: &Module,
+    handle: Handle<GlobalVariable>,
+    message: impl Into<String>,
+) -> ! {
+    let span = wgsl.span(module.global_variables.get_span(handle));
+
+    Diagnostic::spanned(span, Level::Error, message.into()).abort()
+}
+
+/// Gets the name of both the `wgpu::TextureFormat` and the `texel_format` of
+/// `format`, `None` if shatter has no texel format for it.
+fn storage_format_name(format: &StorageFormat) -> Option<&'static str> {
+    Some(match format {
+        StorageFormat::Rgba8Unorm => "Rgba8Unorm",
+        StorageFormat::Rgba8Snorm => "Rgba8Snorm",
+        StorageFormat::Rgba8Uint => "Rgba8Uint",
+        StorageFormat::Rgba8Sint => "Rgba8Sint",
+        StorageFormat::Rgba16Uint => "Rgba16Uint",
+        StorageFormat::Rgba16Sint => "Rgba16Sint",
+        StorageFormat::Rgba16Float => "Rgba16Float",
+        StorageFormat::R32Uint => "R32Uint",
+        StorageFormat::R32Sint => "R32Sint",
+        StorageFormat::R32Float => "R32Float",
+        StorageFormat::Rg32Uint => "Rg32Uint",
+        StorageFormat::Rg32Sint => "Rg32Sint",
+        StorageFormat::Rg32Float => "Rg32Float",
+        StorageFormat::Rgba32Uint => "Rgba32Uint",
+        StorageFormat::Rgba32Sint => "Rgba32Sint",
+        StorageFormat::Rgba32Float => "Rgba32Float",
+        _ => return None,
+    })
+}
+
+fn wgpu_texture_format(format: &StorageFormat) -> Option<TokenStream> {
+    let name = Ident::new(storage_format_name(format)?, Span::call_site());
+
+    Some(quote!(::shatter::__abi::wgpu::TextureFormat::#name))
+}
+
+fn texel_format(format: &StorageFormat) -> Option<TokenStream> {
+    let name = Ident::new(storage_format_name(format)?, Span::call_site());
+
+    Some(quote!(::shatter::__abi::texel_format::#name))
+}
+
+fn wgpu_view_dimension(dimension: &ImageDimension, arrayed: bool) -> TokenStream {
+    match dimension {
+        ImageDimension::D1 => quote!(::shatter::__abi::wgpu::TextureViewDimension::D1),
+        ImageDimension::D2 => {
+            if arrayed {
+                quote!(::shatter::__abi::wgpu::TextureViewDimension::D2Array)
+            } else {
+                quote!(::shatter::__abi::wgpu::TextureViewDimension::D2)
+            }
+        }
+        ImageDimension::D3 => quote!(::shatter::__abi::wgpu::TextureViewDimension::D3),
+        ImageDimension::Cube => {
+            if arrayed {
+                quote!(::shatter::__abi::wgpu::TextureViewDimension::CubeArray)
+            } else {
+                quote!(::shatter::__abi::wgpu::TextureViewDimension::Cube)
+            }
+        }
+    }
+}
+
+/// Generates the named constants, except those of imported groups.
+fn gen_consts(wgsl: &Wgsl, module: &Module, options: &Options) -> TokenStream {
+    let consts = module
+        .constants
+        .iter()
+        .filter(|(_, constant)| {
+            !constant
+                .name
+                .as_deref()
+                .is_some_and(|name| options.imports_constant(name))
+        })
+        .map(|(handle, constant)| gen_const(wgsl, module, handle, constant));
+
+    quote! {
+        #(#consts)*
+    }
+}
+
+fn gen_const(
+    wgsl: &Wgsl,
+    module: &Module,
+    handle: Handle<Constant>,
+    constant: &Constant,
+) -> Option<TokenStream> {
+    let name = constant.name.as_ref()?;
+
+    if let ConstantInner::Composite { ty, .. } = constant.inner {
+        if contains_struct(module, ty) {
+            let span = wgsl.span(module.constants.get_span(handle));
+
+            Diagnostic::spanned(
+                span,
+                Level::Error,
+                format!(
+                    "`{}` is a constant of a struct, which isn't supported",
+                    name
+                ),
+            )
+            .abort()
+        }
+    }
+
+    let ident = names::ident(name);
+
+    let ty = constant.inner.resolve_type();
+
+    let ty = match ty {
+        TypeResolution::Value(ref inner) => rust_type_inner(module, inner, &mut None, false),
+        TypeResolution::Handle(handle) => rust_type(module, handle, &mut None, false),
+    };
+
+    let value = const_value(module, constant);
+
+    let allow = names::allow_naming();
+
+    Some(quote! {
+        #allow
+        pub const #ident: #ty = #value;
+    })
+}
+
+fn const_value(module: &Module, constant: &Constant) -> TokenStream {
+    match constant.inner {
+        ConstantInner::Scalar { width, value } => match value {
+            ScalarValue::Bool(value) => quote!(#value),
+            ScalarValue::Float(value) => match width {
+                4 => quote!(#value as f32),
+                8 => quote!(#value as f64),
+                _ => unimplemented!("float of width '{}' not supported", width),
+            },
+            ScalarValue::Sint(value) => match width {
+                1 => quote!(#value as i8),
+                2 => quote!(#value as i16),
+                4 => quote!(#value as i32),
+                8 => quote!(#value as i64),
+                _ => unimplemented!("signed integer of width '{}' not supported", width),
+            },
+            ScalarValue::Uint(value) => match width {
+                1 => quote!(#value as u8),
+                2 => quote!(#value as u16),
+                4 => quote!(#value as u32),
+                8 => quote!(#value as u64),
+                _ => unimplemented!("unsigned integer of width '{}' not supported", width),
+            },
+        },
+        ConstantInner::Composite { ty, ref components } => {
+            let components = components
+                .iter()
+                .map(|&component| rust_const(module, component));
+
+            match module.types[ty].inner {
+                TypeInner::Vector { size, .. } => {
+                    let vector = match size {
+                        VectorSize::Bi => quote!(::shatter::__abi::Vec2),
+                        VectorSize::Tri => quote!(::shatter::__abi::Vec3),
+                        VectorSize::Quad => quote!(::shatter::__abi::Vec4),
+                    };
+
+                    quote!(#vector::new(#(#components),*))
+                }
+                // the columns are composites themselves
+                TypeInner::Matrix { columns, rows, .. } => match (columns, rows) {
+                    (VectorSize::Bi, VectorSize::Bi) => {
+                        quote!(::shatter::__abi::Mat2::from_cols(#(#components),*))
+                    }
+                    (VectorSize::Tri, VectorSize::Tri) => {
+                        quote!(::shatter::__abi::Mat3::from_cols(#(#components),*))
+                    }
+                    (VectorSize::Quad, VectorSize::Quad) => {
+                        quote!(::shatter::__abi::Mat4::from_cols(#(#components),*))
+                    }
+                    _ => quote!([#(#components),*]),
+                },
+                TypeInner::Array { base, stride, .. } => {
+                    if array_padding(module, base, stride) > 0 {
+                        quote!([#(::shatter::__abi::Padded::new(#components)),*])
+                    } else {
+                        quote!([#(#components),*])
+                    }
+                }
+                // rejected by `gen_const`
+                _ => unreachable!("constants of structs are not supported"),
+            }
+        }
+    }
+}
+
+/// Whether `ty` is a struct or an array of them.
+fn contains_struct(module: &Module, ty: Handle<Type>) -> bool {
+    match module.types[ty].inner {
+        TypeInner::Struct { .. } => true,
+        TypeInner::Array { base, .. } => contains_struct(module, base),
+        _ => false,
+    }
+}
+
+/// Gets the source shaders are created from, empty when expanding for an IDE
+/// as the source of every entry point adds up to a lot of tokens.
+fn shader_source<'a>(wgsl: &'a Wgsl, options: &Options) -> &'a str {
+    match options.ide {
+        true => "",
+        false => &wgsl.source,
+    }
+}
+
+/// Generates the warnings about the whole module, none when expanding for an IDE.
+fn gen_module_warnings(
+    wgsl: &Wgsl,
+    module: &Module,
+    info: &ModuleInfo,
+    options: &Options,
+) -> TokenStream {
+    if options.ide {
+        return TokenStream::new();
+    }
+
+    let size_warnings = gen_size_warnings(wgsl, module, options);
+    let stride_warnings = gen_uniform_stride_warnings(wgsl, module);
+    let unused_warnings = gen_unused_binding_warnings(wgsl, module, info, options);
+
+    quote! {
+        #size_warnings
+        #stride_warnings
+        #unused_warnings
+    }
+}
+
+/// Warns with `note` at `span` by using a deprecated item named `name`, as
+/// proc macros can't emit warnings on stable.
+fn deprecated_warning(name: &str, span: Span, note: String) -> TokenStream {
+    let ident = Ident::new(name, span);
+
+    quote! {
+        const _: () = {
+            #[deprecated(note = #note)]
+            struct #ident;
+
+            let _ = #ident;
+        };
+    }
+}
+
+/// Warns about generated types larger than [`Options::max_type_size`].
+fn gen_size_warnings(wgsl: &Wgsl, module: &Module, options: &Options) -> TokenStream {
+    let warnings = module.types.iter().filter_map(|(handle, ty)| {
+        let name = ty.name.as_ref()?;
+
+        if !matches!(ty.inner, TypeInner::Struct { .. }) {
+            return None;
+        }
+
+        let size = ty.inner.span(&module.constants) as u64;
+
+        if size <= options.max_type_size {
+            return None;
+        }
+
+        let note = format!(
+            "`{}` is {} bytes, over the {} byte budget for generated types; \
+            consider a runtime sized array with `Buffer::push` instead, \
+            or raise the budget with `#![max_type_size = <bytes>]`",
+            name, size, options.max_type_size,
+        );
+
+        let span = wgsl.span(module.types.get_span(handle));
+        Some(deprecated_warning("MaxTypeSizeExceeded", span, note))
+    });
+
+    quote!(#(#warnings)*)
+}
+
+/// Warns about bindings no entry point uses, which are omitted from every
+/// `Bindings` unless [`Options::keep_unused_bindings`] is set.
+fn gen_unused_binding_warnings(
+    wgsl: &Wgsl,
+    module: &Module,
+    info: &ModuleInfo,
+    options: &Options,
+) -> TokenStream {
+    if options.keep_unused_bindings || module.entry_points.is_empty() {
+        return TokenStream::new();
+    }
+
+    let warnings = module
+        .global_variables
+        .iter()
+        .filter_map(|(handle, variable)| {
+            let binding = variable.binding.as_ref()?;
+            let name = variable.name.as_deref()?;
+
+            // bound by the builder or through the field of the group
+            if name == launch::VAR || options.shared_group(binding.group).is_some() {
+                return None;
+            }
+
+            let used =
+                (0..module.entry_points.len()).any(|i| !info.get_entry_point(i)[handle].is_empty());
+
+            if used {
+                return None;
+            }
+
+            let entry_points = match module.entry_points[..] {
+                [ref entry_point] => format!("entry point `{}`", entry_point.name),
+                ref entry_points => format!(
+                    "every entry point ({})",
+                    entry_points
+                        .iter()
+                        .map(|entry_point| format!("`{}`", entry_point.name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            };
+
+            let note = format!(
+                "`{}` (group {}, binding {}) is unused by {} and was omitted from `Bindings`; \
+                use it in the shader, or keep it with `#![keep_unused_bindings]`",
+                name, binding.group, binding.binding, entry_points,
+            );
+
+            let span = wgsl.span(module.global_variables.get_span(handle));
+            Some(deprecated_warning("UnusedBinding", span, note))
+        });
+
+    quote!(#(#warnings)*)
+}
+
+/// Warns about `var<workgroup>` arrays used by `entry_point` whose length is
+/// neither the number of invocations per work group nor any axis of the work
+/// group size, usually a size that drifted from `workgroup_size`.
+fn gen_workgroup_array_warnings(
+    wgsl: &Wgsl,
+    module: &Module,
+    entry_point: &EntryPoint,
+    function_info: &FunctionInfo,
+) -> TokenStream {
+    let [x, y, z] = entry_point.workgroup_size;
+    let invocations = x * y * z;
+
+    let warnings = module
+        .global_variables
+        .iter()
+        .filter_map(|(handle, variable)| {
+            if variable.class != StorageClass::WorkGroup || function_info[handle].is_empty() {
+                return None;
+            }
+
+            let size = match module.types[variable.ty].inner {
+                TypeInner::Array {
+                    size: ArraySize::Constant(size),
+                    ..
+                } => &module.constants[size],
+                _ => return None,
+            };
+
+            let len = match size.inner {
+                ConstantInner::Scalar {
+                    value: ScalarValue::Uint(len),
+                    ..
+                } => len,
+                ConstantInner::Scalar {
+                    value: ScalarValue::Sint(len),
+                    ..
+                } => len as u64,
+                _ => return None,
+            };
+
+            if len == invocations as u64 || [x, y, z].iter().any(|&axis| len == axis as u64) {
+                return None;
+            }
+
+            let length = match size.name {
+                Some(ref name) => format!("`{}` ({})", name, len),
+                None => len.to_string(),
+            };
+
+            let note = format!(
+                "`var<workgroup> {}` has {} elements, but `{}` runs {} invocations per work group \
+                (workgroup_size({}, {}, {})); size it to {} and use `{}::WORK_GROUP_INVOCATIONS` \
+                on the host",
+                variable.name.as_deref().unwrap_or("_"),
+                length,
+                entry_point.name,
+                invocations,
+                x,
+                y,
+                z,
+                invocations,
+                entry_point.name,
+            );
+
+            let span = wgsl.span(module.global_variables.get_span(handle));
+            Some(deprecated_warning("WorkGroupArraySizeMismatch", span, note))
+        });
+
+    quote!(#(#warnings)*)
+}
+
+/// Warns if `entry_point` runs more constant bounded loop iterations than
+/// [`Options::max_loop_iterations`], see [`analysis::loop_iterations`].
+fn gen_loop_iteration_warning(
+    wgsl: &Wgsl,
+    module: &Module,
+    entry_point: &EntryPoint,
+    options: &Options,
+) -> TokenStream {
+    let iterations = analysis::loop_iterations(module, &entry_point.function);
+
+    if iterations.total <= options.max_loop_iterations {
+        return TokenStream::new();
+    }
+
+    let note = format!(
+        "`{}` runs about {} iterations of loops with constant bounds, over the budget of {}; \
+        backends may unroll them and take long to create the pipeline, pass a bound in a \
+        uniform instead or raise the budget with `#![max_loop_iterations = <iterations>]`",
+        entry_point.name, iterations.total, options.max_loop_iterations,
+    );
+
+    let span = iterations
+        .heaviest
+        .map_or_else(Span::call_site, |span| wgsl.span(span));
+
+    deprecated_warning("LoopIterationsExceeded", span, note)
+}
+
+/// Warns about branches of `entry_point` on values differing between the
+/// invocations of a work group around work group barriers, see
+/// [`analysis::non_uniform_barriers`].
+fn gen_non_uniform_barrier_warnings(
+    wgsl: &Wgsl,
+    module: &Module,
+    info: &ModuleInfo,
+    entry_point: &EntryPoint,
+    function_info: &FunctionInfo,
+) -> TokenStream {
+    let function = &entry_point.function;
+
+    let warnings = analysis::non_uniform_barriers(module, info, function, function_info)
+        .into_iter()
+        .map(|branch| {
+            let note = if branch.exits_early {
+                format!(
+                    "`{}` may leave early depending on {} before a work group barrier, but {} \
+                    differs between the invocations of a work group; invocations skipping the \
+                    barrier hang or crash on some GPUs, skip the work instead of leaving",
+                    entry_point.name, branch.value, branch.value,
+                )
+            } else {
+                format!(
+                    "`{}` branches on {} around a work group barrier, but {} differs between \
+                    the invocations of a work group; invocations skipping the barrier hang or \
+                    crash on some GPUs, move the barrier out of the branch",
+                    entry_point.name, branch.value, branch.value,
+                )
+            };
+
+            let span = wgsl.span(function.expressions.get_span(branch.condition));
+            deprecated_warning("NonUniformBarrier", span, note)
+        });
+
+    quote!(#(#warnings)*)
+}
+
+/// Warns about arrays in `var<uniform>` globals whose stride isn't a multiple
+/// of 16, which uniform buffers require, e.g. arrays of small structs.
+fn gen_uniform_stride_warnings(wgsl: &Wgsl, module: &Module) -> TokenStream {
+    fn find_arrays(module: &Module, ty: Handle<Type>, arrays: &mut Vec<Handle<Type>>) {
+        match module.types[ty].inner {
+            TypeInner::Struct { ref members, .. } => {
+                for member in members {
+                    find_arrays(module, member.ty, arrays);
+                }
+            }
+            TypeInner::Array { base, stride, .. } => {
+                if stride % 16 != 0 && !arrays.contains(&ty) {
+                    arrays.push(ty);
+                }
+
+                find_arrays(module, base, arrays);
+            }
+            _ => {}
+        }
+    }
+
+    let warnings = module
+        .global_variables
+        .iter()
+        .filter(|(_, variable)| variable.class == StorageClass::Uniform)
+        .flat_map(|(handle, variable)| {
+            let mut arrays = Vec::new();
+            find_arrays(module, variable.ty, &mut arrays);
+
+            arrays.into_iter().map(move |array| {
+                let stride = match module.types[array].inner {
+                    TypeInner::Array { stride, .. } => stride,
+                    _ => unreachable!(),
+                };
+
+                let note = format!(
+                    "`{}` in `var<uniform> {}` has a stride of {} bytes, but arrays in uniform \
+                    buffers need a stride that's a multiple of 16; declare it with \
+                    `[[stride({})]]`, the generated elements are padded to match",
+                    wgsl_type_name(module, array),
+                    variable.name.as_deref().unwrap_or("_"),
+                    stride,
+                    stride.next_multiple_of(16),
+                );
+
+                let span = wgsl.span(module.global_variables.get_span(handle));
+                deprecated_warning("UniformArrayStride", span, note)
+            })
+        });
+
+    quote!(#(#warnings)*)
+}
+
+/// Gets the offset and stride of the runtime sized array ending the struct `ty`.
+fn runtime_array(module: &Module, ty: Handle<Type>) -> Option<(u32, u32)> {
+    let member = match module.types[ty].inner {
+        TypeInner::Struct { ref members, .. } => members.last()?,
+        _ => return None,
+    };
+
+    match module.types[member.ty].inner {
+        TypeInner::Array {
+            size: ArraySize::Dynamic,
+            stride,
+            ..
+        } => Some((member.offset, stride)),
+        _ => None,
+    }
+}
+
+/// Bytes of padding after every element of an array, only arrays with a
+/// `[[stride(N)]]` larger than their element have any.
+fn array_padding(module: &Module, base: Handle<Type>, stride: u32) -> u32 {
+    let mut layouter = Layouter::default();
+    layouter.update(&module.types, &module.constants).unwrap();
+
+    let layout = &layouter[base];
+
+    stride - Layouter::round_up(layout.alignment, layout.size)
+}
+
+/// Converts structs back from their padded array elements, and names the
+/// elements padded to the uniform buffer stride `{Struct}Std140`.
+fn gen_padded_elements(module: &Module, options: &Options) -> TokenStream {
+    let mut padded = Vec::new();
+
+    for (_, ty) in module.types.iter() {
+        if let TypeInner::Array { base, stride, .. } = ty.inner {
+            let padding = array_padding(module, base, stride);
+
+            let is_struct = matches!(module.types[base].inner, TypeInner::Struct { .. });
+            let is_imported = module.types[base]
+                .name
+                .as_deref()
+                .is_some_and(|name| options.imports_type(name));
+
+            if padding > 0
+                && is_struct
+                && !is_imported
+                && !padded.contains(&(base, stride, padding))
+            {
+                padded.push((base, stride, padding));
+            }
+        }
+    }
+
+    let impls = padded.into_iter().map(|(base, stride, padding)| {
+        let name = module.types[base].name.as_ref().unwrap();
+        let ident = names::ident(name);
+        let natural = stride - padding;
+        let padding = Literal::usize_unsuffixed(padding as usize);
+
+        let std140 = if stride == natural.next_multiple_of(16) {
+            let alias = Ident::new(&format!("{}Std140", name), Span::call_site());
+            let allow = names::allow_naming();
+
+            Some(quote! {
+                #allow
+                pub type #alias = ::shatter::__abi::Padded<#ident, #padding>;
+            })
+        } else {
+            None
+        };
+
+        quote! {
+            impl ::std::convert::From<::shatter::__abi::Padded<#ident, #padding>> for #ident {
+                #[inline]
+                fn from(padded: ::shatter::__abi::Padded<#ident, #padding>) -> Self {
+                    padded.value
                 }
+            }
+
+            #std140
+        }
+    });
+
+    quote!(#(#impls)*)
+}
+
+/// Generates the named types, except those of imported groups.
+fn gen_types(wgsl: &Wgsl, module: &Module, options: &Options) -> TokenStream {
+    // buffer impls are keyed by the types of buffer globals rather than by
+    // the struct declarations, so a struct can be both nested and bound
+    let buffer_types = module
+        .global_variables
+        .iter()
+        .filter(|(_, variable)| {
+            matches!(
+                variable.class,
+                StorageClass::Uniform | StorageClass::Storage { .. }
+            )
+        })
+        .map(|(_, variable)| variable.ty)
+        .collect::<HashSet<_>>();
+
+    check_member_types(wgsl, module);
+
+    let mut layouter = Layouter::default();
+    layouter.update(&module.types, &module.constants).unwrap();
+
+    let types = module
+        .types
+        .iter()
+        .filter(|(_, ty)| {
+            ty.name
+                .as_deref()
+                .is_none_or(|name| name != launch::STRUCT && !options.imports_type(name))
+        })
+        .map(|(handle, ty)| {
+            gen_type(
+                module,
+                &layouter,
+                handle,
+                ty,
+                buffer_types.contains(&handle),
+                ty.name
+                    .as_deref()
+                    .is_some_and(|name| options.is_extern_type(name)),
+                // an IDE doesn't report the failures
+                !options.ide,
+            )
+        });
+
+    let padded_elements = gen_padded_elements(module, options);
+
+    quote! {
+        #(#types)*
+
+        #padded_elements
+    }
+}
+
+/// Aborts if a struct has a member of a type that can't be one, rather than
+/// panicking while laying out the struct.
+fn check_member_types(wgsl: &Wgsl, module: &Module) {
+    for (handle, ty) in module.types.iter() {
+        let members = match ty.inner {
+            TypeInner::Struct { ref members, .. } => members,
+            _ => continue,
+        };
+
+        let invalid = members
+            .iter()
+            .find(|member| !is_member_type(module, member.ty));
+
+        if let Some(member) = invalid {
+            let span = wgsl.span(module.types.get_span(handle));
+
+            Diagnostic::spanned(
+                span,
+                Level::Error,
+                format!(
+                    "`{}` of `{}` has a type that can't be a struct member",
+                    member.name.as_deref().unwrap_or("_"),
+                    ty.name.as_deref().unwrap_or("_")
+                ),
+            )
+            .abort()
+        }
+    }
+}
+
+fn gen_type(
+    module: &Module,
+    layouter: &Layouter,
+    handle: Handle<Type>,
+    ty: &Type,
+    is_buffer: bool,
+    is_extern: bool,
+    check_layout: bool,
+) -> Option<TokenStream> {
+    let wgsl_name = ty.name.as_ref()?;
+    let name_sized = Ident::new(&format!("{}_Sized", wgsl_name), Span::call_site());
+    let allow = names::allow_naming();
+
+    let (members, span) = match ty.inner {
+        TypeInner::Struct {
+            ref members, span, ..
+        } => (members, span),
+        _ => return None,
+    };
+
+    let layout = StructPadding::new(module, layouter, handle, wgsl_name, members, span);
+    let name = names::ident(wgsl_name);
+
+    let buffer_impl = if is_buffer {
+        Some(buffer_impl(&name))
+    } else {
+        None
+    };
+
+    let reflect_impl = reflect_impl(module, &name, members);
+    let assertions = check_layout
+        .then(|| layout.assertions(module, layouter, wgsl_name, &name, members, Some(span)));
+
+    // the type is declared by the user, only the layout is checked
+    if is_extern {
+        if runtime_array(module, handle).is_some() {
+            abort!(
+                Span::call_site(),
+                "the extern type `{}` ends in a runtime sized array, only sized structs can be extern types",
+                wgsl_name,
+            );
+        }
+
+        return Some(quote! {
+            #assertions
+
+            #buffer_impl
+
+            #reflect_impl
+        });
+    }
+
+    let repr = layout.repr();
+    let derive = layout.derive();
+
+    let mut buffer = None;
+    let fields = layout.fields(module, members, &mut buffer, false);
+    let impls = layout.padded_impls(&name, members);
+
+    // structs ending in a runtime sized array can only be used as buffers
+    if let Some(buffer_ty) = buffer {
+        let sized_fields = layout.fields(module, members, &mut None, true);
+        let sized_impls = layout.padded_impls(&name_sized, members);
+        let assertions = check_layout
+            .then(|| layout.assertions(module, layouter, wgsl_name, &name_sized, members, None));
+
+        let buffer_impl =
+            array_buffer_impl(module, layouter, handle, &name, &name_sized, &buffer_ty);
+
+        return Some(quote! {
+            #repr
+            #derive
+            #allow
+            pub struct #name {
+                #(#fields),*
+            }
+
+            #impls
 
+            #repr
+            #derive
+            #allow
+            pub struct #name_sized {
+                #(#sized_fields),*
+            }
+
+            #sized_impls
+
+            #assertions
+
+            impl ::std::default::Default for #name_sized {
                 #[inline]
-                fn bind_group_descriptors(
-                    &self,
-                    layouts: &[::shatter::BindGroupLayoutId],
-                ) -> ::std::vec::Vec<::shatter::BindGroupDescriptor> {
-                    self.bind_group_descriptors(layouts)
+                fn default() -> Self {
+                    // SAFETY: every member of a generated type is valid when zeroed
+                    unsafe { ::std::mem::zeroed() }
+                }
+            }
+
+            #buffer_impl
+        });
+    }
+
+    Some(quote! {
+        #repr
+        #[derive(Clone, Copy)]
+        #derive
+        #allow
+        pub struct #name {
+            #(#fields),*
+        }
+
+        #impls
+
+        #assertions
+
+        // derived defaults don't support arrays longer than 32
+        impl ::std::default::Default for #name {
+            #[inline]
+            fn default() -> Self {
+                // SAFETY: every member of a generated type is valid when zeroed
+                unsafe { ::std::mem::zeroed() }
+            }
+        }
+
+        #buffer_impl
+
+        #reflect_impl
+    })
+}
+
+/// How the members of a struct are laid out in Rust to match its WGSL layout.
+///
+/// Rust places `#[repr(C)]` fields at the next multiple of their alignment,
+/// which differs from WGSL where a member is placed in the tail of a `vec3`,
+/// or offset by `[[align]]` and `[[size]]`. Padding fields fill the gaps, and a
+/// `vec3` followed by a member in its tail becomes a `PackedVec3`.
+struct StructPadding {
+    /// Bytes of padding before every member.
+    padding: Vec<u32>,
+    /// Whether each member is a `vec3` packed with the next member.
+    packed: Vec<bool>,
+    /// Bytes of padding after the last member.
+    trailing: u32,
+    /// Alignment the struct needs in addition to that of its fields.
+    align: Option<u32>,
+}
+
+impl StructPadding {
+    fn new(
+        module: &Module,
+        layouter: &Layouter,
+        handle: Handle<Type>,
+        name: &str,
+        members: &[StructMember],
+        span: u32,
+    ) -> Self {
+        let mut padding = Vec::with_capacity(members.len());
+        let mut packed = Vec::with_capacity(members.len());
+
+        let mut cursor = 0;
+        let mut natural_align = 1;
+
+        for (i, member) in members.iter().enumerate() {
+            let next = members.get(i + 1).map_or(span, |next| next.offset);
+
+            let (size, align) = rust_layout(module, layouter, member.ty);
+
+            let (size, align, is_packed) = match module.types[member.ty].inner {
+                TypeInner::Vector {
+                    size: VectorSize::Tri,
+                    width,
+                    ..
+                } if next < member.offset + size => (3 * width as u32, width as u32, true),
+                _ => (size, align, false),
+            };
+
+            let member_padding = if round_up(align, cursor) == member.offset {
+                0
+            } else if cursor <= member.offset && member.offset % align == 0 {
+                member.offset - cursor
+            } else {
+                abort!(
+                    Span::call_site(),
+                    "member `{}` of `{}` is at offset {} in WGSL, which its Rust type can't be placed at",
+                    member.name.as_deref().unwrap_or_default(),
+                    name,
+                    member.offset,
+                )
+            };
+
+            padding.push(member_padding);
+            packed.push(is_packed);
+
+            cursor = member.offset + size;
+            natural_align = natural_align.max(align);
+        }
+
+        let wgsl_align = layouter[handle].alignment.get();
+        let align = (wgsl_align > natural_align).then_some(wgsl_align);
+
+        // structs ending in a runtime sized array have no size of their own
+        let runtime_sized = members.last().is_some_and(|member| {
+            matches!(
+                module.types[member.ty].inner,
+                TypeInner::Array {
+                    size: ArraySize::Dynamic,
+                    ..
+                }
+            )
+        });
+
+        let trailing = match round_up(wgsl_align.max(natural_align), cursor) {
+            _ if runtime_sized => 0,
+            size if size == span => 0,
+            _ => span.saturating_sub(cursor),
+        };
+
+        Self {
+            padding,
+            packed,
+            trailing,
+            align,
+        }
+    }
+
+    fn is_padded(&self) -> bool {
+        self.trailing > 0 || self.padding.iter().any(|&padding| padding > 0)
+    }
+
+    fn repr(&self) -> TokenStream {
+        match self.align {
+            Some(align) => {
+                let align = Literal::u32_unsuffixed(align);
+                quote!(#[repr(C, align(#align))])
+            }
+            None => quote!(#[repr(C)]),
+        }
+    }
+
+    /// Derives `Debug` and `PartialEq` unless there's padding, which they
+    /// would include, see [`Self::padded_impls`].
+    fn derive(&self) -> Option<TokenStream> {
+        (!self.is_padded()).then(|| quote!(#[derive(Debug, PartialEq)]))
+    }
+
+    fn fields(
+        &self,
+        module: &Module,
+        members: &[StructMember],
+        buffer: &mut Option<TokenStream>,
+        force_sized: bool,
+    ) -> Vec<TokenStream> {
+        let mut fields = Vec::new();
+        let mut pads = 0;
+
+        let mut pad = |fields: &mut Vec<TokenStream>, bytes: u32| {
+            if bytes > 0 {
+                let ident = Ident::new(&format!("_pad{}", pads), Span::call_site());
+                let bytes = Literal::u32_unsuffixed(bytes);
+
+                fields.push(quote!(#ident: [::std::primitive::u8; #bytes]));
+                pads += 1;
+            }
+        };
+
+        for (i, member) in members.iter().enumerate() {
+            pad(&mut fields, self.padding[i]);
+
+            let ident = names::ident(member.name.as_ref().unwrap());
+
+            let ty = match module.types[member.ty].inner {
+                TypeInner::Vector { kind, width, .. } if self.packed[i] => {
+                    let scalar = rust_scalar(kind, width);
+                    quote!(::shatter::__abi::PackedVec3<#scalar>)
                 }
+                _ => rust_type(module, member.ty, buffer, force_sized),
+            };
+
+            fields.push(quote!(pub #ident: #ty));
+        }
+
+        pad(&mut fields, self.trailing);
+
+        fields
+    }
 
-                #[inline]
-                fn prepare(&self) {
-                    #(#prepare;)*
-                }
+    /// Implements `Debug` and `PartialEq` of padded structs, skipping the
+    /// padding the GPU may leave undefined.
+    fn padded_impls(&self, name: &Ident, members: &[StructMember]) -> Option<TokenStream> {
+        if !self.is_padded() {
+            return None;
+        }
 
-                #[inline]
-                fn read(&self) {
-                    #(#read;)*
+        let name_str = name.to_string();
+        let field_names = members
+            .iter()
+            .map(|member| member.name.as_ref().unwrap())
+            .collect::<Vec<_>>();
+        let idents = field_names
+            .iter()
+            .map(|name| names::ident(name))
+            .collect::<Vec<_>>();
+
+        Some(quote! {
+            impl ::std::fmt::Debug for #name {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    f.debug_struct(#name_str)
+                        #(.field(#field_names, &self.#idents))*
+                        .finish()
                 }
+            }
 
+            impl ::std::cmp::PartialEq for #name {
                 #[inline]
-                fn write(&mut self) {
-                    #(#write;)*
+                fn eq(&self, other: &Self) -> ::std::primitive::bool {
+                    true #(&& self.#idents == other.#idents)*
                 }
             }
         })
-    } else {
-        None
     }
-}
 
-fn wgpu_texture_format(format: &StorageFormat) -> TokenStream {
-    match format {
-        StorageFormat::Rgba8Unorm => quote!(::shatter::wgpu::TextureFormat::Rgba8Unorm),
-        _ => todo!(),
-    }
-}
+    /// Asserts at compile time that the size, if given, the member offsets
+    /// and the array strides of `name` are those of the WGSL struct `wgsl_name`.
+    /// Failures name the member and both values, see `shatter::assert_layout`.
+    fn assertions(
+        &self,
+        module: &Module,
+        layouter: &Layouter,
+        wgsl_name: &str,
+        name: &Ident,
+        members: &[StructMember],
+        span: Option<u32>,
+    ) -> TokenStream {
+        let assert = |item: &str, what: &str, wgsl: u32, rust: TokenStream| {
+            let wgsl = Literal::usize_unsuffixed(wgsl as usize);
 
-fn wgpu_view_dimension(dimension: &ImageDimension, arrayed: bool) -> TokenStream {
-    match dimension {
-        ImageDimension::D1 => quote!(::shatter::wgpu::TextureViewDimension::D1),
-        ImageDimension::D2 => {
-            if arrayed {
-                quote!(::shatter::wgpu::TextureViewDimension::D2Array)
-            } else {
-                quote!(::shatter::wgpu::TextureViewDimension::D2)
+            quote! {
+                ::shatter::__abi::assert_layout(#item, #what, #wgsl, #rust);
             }
-        }
-        ImageDimension::D3 => quote!(::shatter::wgpu::TextureViewDimension::D3),
-        ImageDimension::Cube => {
-            if arrayed {
-                quote!(::shatter::wgpu::TextureViewDimension::CubeArray)
-            } else {
-                quote!(::shatter::wgpu::TextureViewDimension::Cube)
+        };
+
+        let mut assertions = Vec::new();
+
+        for member in members {
+            let field = member.name.as_ref().unwrap();
+            let ident = names::ident(field);
+            let mut item = format!("{}::{}", wgsl_name, field);
+
+            assertions.push(assert(
+                &item,
+                "offset",
+                member.offset,
+                quote!(::std::mem::offset_of!(#name, #ident)),
+            ));
+
+            // every level of nested arrays, an element off by a few bytes moves
+            // every element after it further
+            let mut ty = member.ty;
+
+            while let TypeInner::Array { base, size, stride } = module.types[ty].inner {
+                let element = rust_element_type(module, base, stride, &mut None, false);
+
+                assertions.push(assert(
+                    &item,
+                    "stride",
+                    stride,
+                    quote!(::std::mem::size_of::<#element>()),
+                ));
+
+                if size == ArraySize::Dynamic {
+                    // the items are placed after the header, not at the offset
+                    assertions.push(assert(
+                        wgsl_name,
+                        "header size",
+                        member.offset,
+                        quote!(::std::mem::size_of::<#name>()),
+                    ));
+                    assertions.push(assert(
+                        &item,
+                        "element alignment",
+                        layouter[base].alignment.get(),
+                        quote!(::std::mem::align_of::<#element>()),
+                    ));
+                }
+
+                item.push_str("[]");
+                ty = base;
             }
         }
-    }
-}
 
-fn gen_consts(module: &Module) -> TokenStream {
-    let consts = module
-        .constants
-        .iter()
-        .map(|(_, constant)| gen_const(module, constant));
+        // after the members, so the first failure names the member that's off
+        if let Some(span) = span {
+            assertions.push(assert(
+                wgsl_name,
+                "size",
+                span,
+                quote!(::std::mem::size_of::<#name>()),
+            ));
+        }
 
-    quote! {
-        #(#consts)*
+        quote! {
+            const _: () = {
+                #(#assertions)*
+            };
+        }
     }
 }
 
-fn gen_const(module: &Module, constant: &Constant) -> Option<TokenStream> {
-    let name = constant.name.as_ref()?;
-
-    let ident = Ident::new(name, Span::call_site());
-
-    let ty = constant.inner.resolve_type();
-
-    let ty = match ty {
-        TypeResolution::Value(ref inner) => rust_type_inner(module, inner, &mut None, false),
-        TypeResolution::Handle(handle) => rust_type(module, handle, &mut None, false),
-    };
-
-    let value = const_value(module, constant);
-
-    Some(quote! {
-        pub const #ident: #ty = #value;
-    })
-}
-
-fn const_value(_module: &Module, constant: &Constant) -> TokenStream {
-    match constant.inner {
-        ConstantInner::Scalar { width, value } => match value {
-            ScalarValue::Bool(value) => quote!(#value),
-            ScalarValue::Float(value) => match width {
-                4 => quote!(#value as f32),
-                8 => quote!(#value as f64),
-                _ => unimplemented!("float of width '{}' not supported", width),
-            },
-            ScalarValue::Sint(value) => match width {
-                1 => quote!(#value as i8),
-                2 => quote!(#value as i16),
-                4 => quote!(#value as i32),
-                8 => quote!(#value as i64),
-                _ => unimplemented!("signed integer of width '{}' not supported", width),
-            },
-            ScalarValue::Uint(value) => match width {
-                1 => quote!(#value as i8),
-                2 => quote!(#value as i16),
-                4 => quote!(#value as i32),
-                8 => quote!(#value as i64),
-                _ => unimplemented!("unsigned integer of width '{}' not supported", width),
-            },
-        },
-        _ => unimplemented!(),
+/// Whether a struct member can be of type `ty`.
+fn is_member_type(module: &Module, ty: Handle<Type>) -> bool {
+    match module.types[ty].inner {
+        TypeInner::Array { base, .. } => is_member_type(module, base),
+        TypeInner::Scalar { .. }
+        | TypeInner::Vector { .. }
+        | TypeInner::Matrix { .. }
+        | TypeInner::Atomic { .. }
+        | TypeInner::Struct { .. } => true,
+        _ => false,
     }
 }
 
-fn gen_types(module: &Module) -> TokenStream {
-    let types = module.types.iter().map(|(_, ty)| gen_type(module, ty));
-
-    quote! {
-        #(#types)*
-    }
+fn round_up(align: u32, offset: u32) -> u32 {
+    offset.div_ceil(align) * align
 }
 
-fn gen_type(module: &Module, ty: &Type) -> Option<TokenStream> {
-    let name = ty.name.as_ref()?;
-    let name_sized = Ident::new(&format!("{}_Sized", name), Span::call_site());
-    let name = Ident::new(name, Span::call_site());
-
-    match ty.inner {
-        TypeInner::Struct {
-            top_level: false,
-            ref members,
-            ..
-        } => {
-            let members = members.iter().map(|member| {
-                let ident = Ident::new(member.name.as_ref().unwrap(), Span::call_site());
-
-                let ty = rust_type(module, member.ty, &mut None, false);
-
-                quote! {
-                    pub #ident: #ty
-                }
-            });
-
-            Some(quote! {
-                #[derive(Clone, Copy, Debug, PartialEq)]
-                pub struct #name {
-                    #(#members),*
-                }
-            })
+/// Gets the size and alignment of the Rust type generated for `ty`, the size
+/// of runtime sized arrays is 0.
+fn rust_layout(module: &Module, layouter: &Layouter, ty: Handle<Type>) -> (u32, u32) {
+    match module.types[ty].inner {
+        TypeInner::Scalar { width, .. } | TypeInner::Atomic { width, .. } => {
+            (width as u32, width as u32)
         }
-        TypeInner::Struct {
-            top_level: true,
-            ref members,
-            ..
+        TypeInner::Vector { size, width, .. } => vector_layout(size, width),
+        // columns of vectors
+        TypeInner::Matrix {
+            columns,
+            rows,
+            width,
         } => {
-            let mut buffer = None;
+            let (size, align) = vector_layout(rows, width);
+            (size * columns as u32, align)
+        }
+        TypeInner::Array { base, size, stride } => {
+            let (base_size, align) = rust_layout(module, layouter, base);
+            let element = round_up(align, base_size + array_padding(module, base, stride));
 
-            let unsized_members = members
-                .iter()
-                .map(|member| {
-                    let ident = Ident::new(member.name.as_ref().unwrap(), Span::call_site());
+            match size {
+                ArraySize::Constant(size) => match module.constants[size].inner {
+                    ConstantInner::Scalar {
+                        value: ScalarValue::Uint(len),
+                        ..
+                    } => (element * len as u32, align),
+                    ConstantInner::Scalar {
+                        value: ScalarValue::Sint(len),
+                        ..
+                    } => (element * len as u32, align),
+                    _ => unreachable!("array size must be an integer"),
+                },
+                ArraySize::Dynamic => (0, align),
+            }
+        }
+        TypeInner::Struct { span, .. } => (span, layouter[ty].alignment.get()),
+        // rejected by `check_member_types`
+        _ => unreachable!("type cannot be a struct member"),
+    }
+}
 
-                    let ty = rust_type(module, member.ty, &mut buffer, false);
+/// Gets the size and alignment of `Vec2`, `Vec3` or `Vec4` of a scalar `width`.
+fn vector_layout(size: VectorSize, width: u8) -> (u32, u32) {
+    let width = width as u32;
 
-                    quote! {
-                        pub #ident: #ty
-                    }
-                })
-                .collect::<Vec<_>>();
+    let (len, align) = match size {
+        VectorSize::Bi => (2, 8),
+        VectorSize::Tri => (3, 16),
+        VectorSize::Quad => (4, 16),
+    };
 
-            let sized_struct = if buffer.is_some() {
-                let sized_members = members.iter().map(|member| {
-                    let ident = Ident::new(member.name.as_ref().unwrap(), Span::call_site());
+    let align = width.max(align);
+    (round_up(align, len * width), align)
+}
 
-                    let ty = rust_type(module, member.ty, &mut None, true);
+fn reflect_impl(module: &Module, name: &Ident, members: &[StructMember]) -> TokenStream {
+    let name_str = name.to_string();
 
-                    quote! {
-                        pub #ident: #ty
-                    }
-                });
+    let fields = members.iter().map(|member| {
+        let field_name = member.name.as_ref().unwrap();
+        let ident = names::ident(field_name);
 
-                Some(quote! {
-                    #[repr(C)]
-                    #[derive(Debug, Default, PartialEq)]
-                    pub struct #name_sized {
-                        #(#sized_members),*
-                    }
-                })
-            } else {
-                None
-            };
+        let ty = rust_type(module, member.ty, &mut None, false);
+        let wgsl_ty = wgsl_type_name(module, member.ty);
 
-            let derives = if buffer.is_some() {
-                quote!(#[derive(Debug, PartialEq)])
-            } else {
-                quote!(#[derive(Debug, Default, PartialEq)])
-            };
+        quote! {
+            ::shatter::__abi::FieldLayout {
+                name: ::std::borrow::Cow::Borrowed(#field_name),
+                ty: ::std::borrow::Cow::Borrowed(#wgsl_ty),
+                offset: ::std::mem::offset_of!(#name, #ident) as u64,
+                size: ::std::mem::size_of::<#ty>() as u64,
+            }
+        }
+    });
 
-            let buffer_impl = if let Some(buffer_ty) = buffer {
-                array_buffer_impl(&name, &name_sized, &buffer_ty)
-            } else {
-                buffer_impl(&name)
+    quote! {
+        unsafe impl ::shatter::__abi::Reflect for #name {
+            const LAYOUT: ::shatter::__abi::StructLayout = ::shatter::__abi::StructLayout {
+                name: ::std::borrow::Cow::Borrowed(#name_str),
+                size: ::std::mem::size_of::<#name>() as u64,
+                fields: ::std::borrow::Cow::Borrowed(&[#(#fields),*]),
             };
-
-            Some(quote! {
-                #[repr(C)]
-                #derives
-                pub struct #name {
-                    #(#unsized_members),*
-                }
-
-                #sized_struct
-
-                #buffer_impl
-            })
         }
-        _ => None,
     }
 }
 
 fn buffer_impl(name: &Ident) -> TokenStream {
     quote! {
-        unsafe impl ::shatter::BufferData for #name {
+        unsafe impl ::shatter::__abi::BufferData for #name {
             type State = ();
+            type Contents = Self;
+
+            const NEEDS_DROP: bool = false;
 
             fn init() -> Self::State {}
 
@@ -641,11 +3625,32 @@ fn buffer_impl(name: &Ident) -> TokenStream {
                     return ::std::ptr::NonNull::<#name>::dangling().cast();
                 }
 
+                // the default is all zeros, so it's constructed in place rather
+                // than on the stack, which large fixed size arrays would overflow
+                let layout = ::std::alloc::Layout::new::<#name>();
+                let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+
+                let ptr = match ::std::ptr::NonNull::new(ptr) {
+                    ::std::option::Option::Some(ptr) => ptr,
+                    ::std::option::Option::None => ::std::alloc::handle_alloc_error(layout),
+                };
+
+                ::shatter::__abi::record_alloc::<#name>(ptr, layout);
+
+                ptr
+            }
+
+            unsafe fn alloc_zeroed(_: usize) -> (::std::ptr::NonNull<u8>, Self::State) {
+                if ::std::mem::size_of::<#name>() == 0 {
+                    return (::std::ptr::NonNull::<#name>::dangling().cast(), ());
+                }
+
                 let layout = ::std::alloc::Layout::new::<#name>();
-                let ptr = unsafe { ::std::alloc::alloc(layout) };
+                let ptr = ::std::ptr::NonNull::new(unsafe { ::std::alloc::alloc_zeroed(layout) }).unwrap();
 
-                unsafe { ::std::ptr::write(ptr as *mut #name, ::std::default::Default::default()) };
-                ::std::ptr::NonNull::new(ptr).unwrap()
+                ::shatter::__abi::record_alloc::<#name>(ptr, layout);
+
+                (ptr, ())
             }
 
             unsafe fn dealloc(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) {
@@ -655,20 +3660,60 @@ fn buffer_impl(name: &Ident) -> TokenStream {
                     return;
                 }
 
+                ::shatter::__abi::record_dealloc::<#name>(ptr, layout);
+
                 unsafe { ::std::alloc::dealloc(ptr.as_ptr(), layout) };
             }
 
             unsafe fn as_ptr(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) -> *mut Self {
                 ptr.as_ptr() as *mut Self
             }
+
+            unsafe fn contents(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) -> Self {
+                unsafe { ::std::ptr::read(ptr.as_ptr() as *const Self) }
+            }
         }
     }
 }
 
-fn array_buffer_impl(name: &Ident, name_sized: &Ident, buffer_ty: &TokenStream) -> TokenStream {
+fn array_buffer_impl(
+    module: &Module,
+    layouter: &Layouter,
+    handle: Handle<Type>,
+    name: &Ident,
+    name_sized: &Ident,
+    buffer_ty: &TokenStream,
+) -> TokenStream {
+    let (stride, align, head_members) = match module.types[handle].inner {
+        TypeInner::Struct { ref members, .. } => {
+            match module.types[members.last().unwrap().ty].inner {
+                TypeInner::Array { base, stride, .. } => {
+                    (stride, layouter[base].alignment.get(), members.len() - 1)
+                }
+                _ => unreachable!("only structs ending in a runtime sized array are vectors"),
+            }
+        }
+        _ => unreachable!("only structs ending in a runtime sized array are vectors"),
+    };
+    let stride = Literal::usize_unsuffixed(stride as usize);
+    let align = Literal::usize_unsuffixed(align as usize);
+
+    // a struct of only the runtime sized array has nothing to initialize
+    let head = match head_members {
+        0 => quote!(()),
+        _ => quote!(#name_sized),
+    };
+
+    // every allocation goes through the same layout, so growing and
+    // deallocating agree with how the data was allocated
+    let layout = quote!(::shatter::__abi::buffer_vec_layout::<#name_sized, #buffer_ty>);
+
     quote! {
-        unsafe impl ::shatter::BufferData for #name {
+        unsafe impl ::shatter::__abi::BufferData for #name {
             type State = (usize, usize);
+            type Contents = ::std::vec::Vec<#buffer_ty>;
+
+            const NEEDS_DROP: bool = false;
 
             fn init() -> Self::State {
                 let cap = if ::std::mem::size_of::<#buffer_ty>() == 0 { !0 } else { 0 };
@@ -681,32 +3726,61 @@ fn array_buffer_impl(name: &Ident, name_sized: &Ident, buffer_ty: &TokenStream)
             }
 
             unsafe fn alloc() -> ::std::ptr::NonNull<u8> {
-                if ::std::mem::size_of::<#name_sized>() == 0 {
+                let (_, capacity) = Self::init();
+                let layout = #layout(capacity);
+
+                if layout.size() == 0 {
                     return ::std::ptr::NonNull::<#name_sized>::dangling().cast();
                 }
 
-                let layout = ::std::alloc::Layout::new::<#name_sized>();
-                let ptr = unsafe { ::std::alloc::alloc(layout) };
+                // constructs the all zero default in place, see the sized buffer impl
+                let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+
+                let ptr = match ::std::ptr::NonNull::new(ptr) {
+                    ::std::option::Option::Some(ptr) => ptr,
+                    ::std::option::Option::None => ::std::alloc::handle_alloc_error(layout),
+                };
 
-                unsafe { ::std::ptr::write(ptr as *mut #name_sized, ::std::default::Default::default()) };
-                ::std::ptr::NonNull::new(ptr).unwrap()
+                ::shatter::__abi::record_alloc::<#name>(ptr, layout);
+
+                ptr
             }
 
-            unsafe fn dealloc(ptr: ::std::ptr::NonNull<u8>, &(_length, capacity): &Self::State) {
-                let sized_layout = ::std::alloc::Layout::new::<#name_sized>();
+            unsafe fn alloc_zeroed(size: usize) -> (::std::ptr::NonNull<u8>, Self::State) {
+                if ::std::mem::size_of::<#buffer_ty>() == 0 {
+                    return (unsafe { Self::alloc() }, Self::init());
+                }
 
-                let layout = if ::std::mem::size_of::<#buffer_ty>() > 0 {
-                    let array_layout = ::std::alloc::Layout::array::<#buffer_ty>(capacity).unwrap();
+                let length = size.saturating_sub(::std::mem::size_of::<#name_sized>())
+                    / ::std::mem::size_of::<#buffer_ty>();
 
-                    sized_layout.extend(array_layout).unwrap().0
-                } else {
-                    sized_layout
+                let layout = #layout(length);
+
+                if layout.size() == 0 {
+                    return (::std::ptr::NonNull::<#name_sized>::dangling().cast(), (0, 0));
+                }
+
+                let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+
+                let ptr = match ::std::ptr::NonNull::new(ptr) {
+                    Some(ptr) => ptr,
+                    None => ::std::alloc::handle_alloc_error(layout),
                 };
 
+                ::shatter::__abi::record_alloc::<#name>(ptr, layout);
+
+                (ptr, (length, length))
+            }
+
+            unsafe fn dealloc(ptr: ::std::ptr::NonNull<u8>, &(_length, capacity): &Self::State) {
+                let layout = #layout(capacity);
+
                 if layout.size() == 0 {
                     return;
                 }
 
+                ::shatter::__abi::record_dealloc::<#name>(ptr, layout);
+
                 unsafe { ::std::alloc::dealloc(ptr.as_ptr(), layout) };
             }
 
@@ -715,57 +3789,80 @@ fn array_buffer_impl(name: &Ident, name_sized: &Ident, buffer_ty: &TokenStream)
 
                 unsafe { ::std::mem::transmute(slice as *mut [u8]) }
             }
+
+            unsafe fn contents(
+                ptr: ::std::ptr::NonNull<u8>,
+                &(length, _capacity): &Self::State,
+            ) -> Self::Contents {
+                let layout = ::std::alloc::Layout::new::<#name_sized>();
+                let items = unsafe { ptr.as_ptr().add(layout.size()) as *const #buffer_ty };
+
+                (0..length)
+                    .map(|i| unsafe { ::std::ptr::read(items.add(i)) })
+                    .collect()
+            }
         }
 
-        unsafe impl ::shatter::BufferVec for #name {
+        unsafe impl ::shatter::__abi::BufferVec for #name {
+            type Head = #head;
             type Item = #buffer_ty;
 
+            const ITEM_STRIDE: usize = #stride;
+            const ITEM_ALIGN: usize = #align;
+
             fn len(&(length, _): &Self::State) -> usize {
                 length
             }
 
-            unsafe fn grow(
+            fn capacity(&(_, capacity): &Self::State) -> usize {
+                capacity
+            }
+
+            unsafe fn grow(ptr: &mut ::std::ptr::NonNull<u8>, state: &mut Self::State) {
+                // one past the capacity doubles it
+                let additional = state.1 - state.0 + 1;
+
+                unsafe { Self::reserve(ptr, state, additional) };
+            }
+
+            unsafe fn reserve(
                 ptr: &mut ::std::ptr::NonNull<u8>,
-                (length, capacity): &mut Self::State,
+                &mut (length, ref mut capacity): &mut Self::State,
+                additional: usize,
             ) {
-                assert!(::std::mem::size_of::<Self::Item>() != 0, "capacity overflow");
+                let required = length.checked_add(additional).expect("capacity overflow");
 
-                let (new_cap, new_layout) = if *capacity == 0 {
-                    let sized_layout = ::std::alloc::Layout::new::<#name_sized>();
-                    let array_layout = ::std::alloc::Layout::array::<Self::Item>(1).unwrap();
-                    let new_layout = sized_layout.extend(array_layout).unwrap().0.pad_to_align();
+                if required <= *capacity {
+                    return;
+                }
 
-                    (1, new_layout)
-                } else {
-                    let new_cap = 2 * *capacity;
+                // everything that may panic comes before the reallocation, see
+                // the panic safety of `BufferVec`
+                assert!(::std::mem::size_of::<Self::Item>() != 0, "capacity overflow");
 
-                    let sized_layout = ::std::alloc::Layout::new::<#name_sized>();
-                    let array_layout = ::std::alloc::Layout::array::<Self::Item>(new_cap).unwrap();
-                    let new_layout = sized_layout.extend(array_layout).unwrap().0.pad_to_align();
-                    (new_cap, new_layout)
-                };
+                let new_cap = required.max(capacity.saturating_mul(2));
 
-                assert!(
-                    new_layout.size() <= ::std::primitive::isize::MAX as usize,
-                    "Allocation too large"
-                );
+                let old_layout = #layout(*capacity);
+                let new_layout = #layout(new_cap);
 
-                let new_ptr = if *capacity == 0 && ::std::mem::size_of::<#name_sized>() == 0 {
+                ::shatter::__abi::fault_point(::shatter::__abi::FaultPoint::Grow);
+
+                let new_ptr = if old_layout.size() == 0 {
                     unsafe { ::std::alloc::alloc(new_layout) }
                 } else {
-                    let sized_layout = ::std::alloc::Layout::new::<#name_sized>();
-                    let array_layout = ::std::alloc::Layout::array::<Self::Item>(*capacity).unwrap();
-                    let old_layout = sized_layout.extend(array_layout).unwrap().0.pad_to_align();
-                    let old_ptr = ptr.as_ptr();
-                    unsafe { ::std::alloc::realloc(old_ptr, old_layout, new_layout.size()) }
+                    unsafe { ::std::alloc::realloc(ptr.as_ptr(), old_layout, new_layout.size()) }
                 };
 
-                *ptr = match ::std::ptr::NonNull::new(new_ptr) {
+                // a failed allocation leaves the old one valid
+                let new_ptr = match ::std::ptr::NonNull::new(new_ptr) {
                     Some(ptr) => ptr,
                     None => ::std::alloc::handle_alloc_error(new_layout),
                 };
 
+                let old_ptr = ::std::mem::replace(ptr, new_ptr);
                 *capacity = new_cap;
+
+                ::shatter::__abi::record_realloc::<#name>(old_ptr, old_layout, new_ptr, new_layout);
             }
 
             unsafe fn push(
@@ -774,41 +3871,40 @@ fn array_buffer_impl(name: &Ident, name_sized: &Ident, buffer_ty: &TokenStream)
                 item: Self::Item
             ) {
                 if state.0 == state.1 {
-                    Self::grow(ptr, state);
+                    unsafe { Self::grow(ptr, state) };
                 }
 
-                let layout = ::std::alloc::Layout::new::<#name_sized>();
+                ::shatter::__abi::fault_point(::shatter::__abi::FaultPoint::Push);
 
-                unsafe {
-                    ::std::ptr::write(
-                        (ptr.as_ptr().add(layout.size()) as *mut Self::Item).add(state.0),
-                        item,
-                    );
-                }
+                unsafe { ::std::ptr::write(Self::items(*ptr, state).add(state.0), item) };
 
                 state.0 += 1;
             }
 
             unsafe fn pop(
                 ptr: ::std::ptr::NonNull<u8>,
-                (length, _capacity): &mut Self::State,
+                state: &mut Self::State,
             ) -> ::std::option::Option<Self::Item> {
-                if *length == 0 {
+                if state.0 == 0 {
                     None
                 } else {
-                    *length -= 1;
+                    state.0 -= 1;
 
-                    let layout = ::std::alloc::Layout::new::<#name_sized>();
-
-                    unsafe {
-                        Some(
-                            ::std::ptr::read(
-                                (ptr.as_ptr().add(layout.size()) as *mut Self::Item).add(*length)
-                            )
-                        )
-                    }
+                    unsafe { Some(::std::ptr::read(Self::items(ptr, state).add(state.0))) }
                 }
             }
+
+            unsafe fn set_len((length, _capacity): &mut Self::State, len: usize) {
+                *length = len;
+            }
+
+            unsafe fn items(
+                ptr: ::std::ptr::NonNull<u8>,
+                _state: &Self::State,
+            ) -> *mut Self::Item {
+                // the items start right after the header, as laid out by WGSL
+                unsafe { ptr.as_ptr().add(::std::mem::size_of::<#name_sized>()) as *mut Self::Item }
+            }
         }
     }
 }
@@ -825,7 +3921,7 @@ fn rust_type(
         TypeInner::Struct { .. } => {
             let name = ty.name.as_ref().unwrap();
 
-            let ident = Ident::new(name, Span::call_site());
+            let ident = names::ident(name);
 
             quote! { #ident }
         }
@@ -833,6 +3929,27 @@ fn rust_type(
     }
 }
 
+/// Gets the Rust type of the elements `base` of an array with `stride`,
+/// padded if the stride is larger than the element.
+fn rust_element_type(
+    module: &Module,
+    base: Handle<Type>,
+    stride: u32,
+    buffer: &mut Option<TokenStream>,
+    force_sized: bool,
+) -> TokenStream {
+    let padding = array_padding(module, base, stride);
+    let base = rust_type(module, base, buffer, force_sized);
+
+    if padding > 0 {
+        let padding = Literal::usize_unsuffixed(padding as usize);
+
+        quote!(::shatter::__abi::Padded<#base, #padding>)
+    } else {
+        base
+    }
+}
+
 fn rust_type_inner(
     module: &Module,
     inner: &TypeInner,
@@ -845,9 +3962,9 @@ fn rust_type_inner(
             let scalar = rust_scalar(kind, width);
 
             match size {
-                VectorSize::Bi => quote!(::shatter::Vec2<#scalar>),
-                VectorSize::Tri => quote!(::shatter::Vec3<#scalar>),
-                VectorSize::Quad => quote!(::shatter::Vec4<#scalar>),
+                VectorSize::Bi => quote!(::shatter::__abi::Vec2<#scalar>),
+                VectorSize::Tri => quote!(::shatter::__abi::Vec3<#scalar>),
+                VectorSize::Quad => quote!(::shatter::__abi::Vec4<#scalar>),
             }
         }
         TypeInner::Matrix {
@@ -857,27 +3974,28 @@ fn rust_type_inner(
         } => {
             let scalar = rust_scalar(ScalarKind::Float, width);
 
-            match columns {
-                VectorSize::Bi => match rows {
-                    VectorSize::Bi => quote!([[#scalar; 2]; 2]),
-                    VectorSize::Tri => quote!([[#scalar; 3]; 2]),
-                    VectorSize::Quad => quote!([[#scalar; 4]; 2]),
-                },
-                VectorSize::Tri => match rows {
-                    VectorSize::Bi => quote!([[#scalar; 2]; 3]),
-                    VectorSize::Tri => quote!([[#scalar; 3]; 3]),
-                    VectorSize::Quad => quote!([[#scalar; 4]; 3]),
-                },
-                VectorSize::Quad => match rows {
-                    VectorSize::Bi => quote!([[#scalar; 2]; 4]),
-                    VectorSize::Tri => quote!([[#scalar; 3]; 4]),
-                    VectorSize::Quad => quote!([[#scalar; 4]; 4]),
-                },
+            // columns are laid out like vectors, a `mat3x3` has the padding
+            // of three `vec3`
+            let column = match rows {
+                VectorSize::Bi => quote!(::shatter::__abi::Vec2<#scalar>),
+                VectorSize::Tri => quote!(::shatter::__abi::Vec3<#scalar>),
+                VectorSize::Quad => quote!(::shatter::__abi::Vec4<#scalar>),
+            };
+
+            match (columns, rows) {
+                (VectorSize::Bi, VectorSize::Bi) => quote!(::shatter::__abi::Mat2<#scalar>),
+                (VectorSize::Tri, VectorSize::Tri) => quote!(::shatter::__abi::Mat3<#scalar>),
+                (VectorSize::Quad, VectorSize::Quad) => quote!(::shatter::__abi::Mat4<#scalar>),
+                _ => {
+                    let columns = columns as usize;
+
+                    quote!([#column; #columns])
+                }
             }
         }
         TypeInner::Atomic { kind, width } => rust_scalar(kind, width),
-        TypeInner::Array { base, size, .. } => {
-            let base = rust_type(module, base, buffer, force_sized);
+        TypeInner::Array { base, size, stride } => {
+            let base = rust_element_type(module, base, stride, buffer, force_sized);
 
             match size {
                 ArraySize::Constant(size) => {
@@ -902,20 +4020,20 @@ fn rust_type_inner(
             class,
         } => {
             let dimension = match dim {
-                ImageDimension::D1 => quote!(::shatter::texture_view_dimension::D1),
+                ImageDimension::D1 => quote!(::shatter::__abi::texture_view_dimension::D1),
                 ImageDimension::D2 => {
                     if arrayed {
-                        quote!(::shatter::texture_view_dimension::D2Array)
+                        quote!(::shatter::__abi::texture_view_dimension::D2Array)
                     } else {
-                        quote!(::shatter::texture_view_dimension::D2)
+                        quote!(::shatter::__abi::texture_view_dimension::D2)
                     }
                 }
-                ImageDimension::D3 => quote!(::shatter::texture_View_dimension::D3),
+                ImageDimension::D3 => quote!(::shatter::__abi::texture_view_dimension::D3),
                 ImageDimension::Cube => {
                     if arrayed {
-                        quote!(::shatter::texture_view_dimension::CubeArray)
+                        quote!(::shatter::__abi::texture_view_dimension::CubeArray)
                     } else {
-                        quote!(::shatter::texture_view_dimension::Cube)
+                        quote!(::shatter::__abi::texture_view_dimension::Cube)
                     }
                 }
             };
@@ -923,25 +4041,26 @@ fn rust_type_inner(
             match class {
                 ImageClass::Sampled { kind, multi } => {
                     let sample_type = match kind {
-                        ScalarKind::Float => quote!(::shatter::texture_sample_type::Float<true>),
-                        ScalarKind::Sint => quote!(::shatter::texture_sample_type::Sint),
-                        ScalarKind::Uint => quote!(::shatter::texture_sample_type::Uint),
+                        ScalarKind::Float => {
+                            quote!(::shatter::__abi::texture_sample_type::Float<true>)
+                        }
+                        ScalarKind::Sint => quote!(::shatter::__abi::texture_sample_type::Sint),
+                        ScalarKind::Uint => quote!(::shatter::__abi::texture_sample_type::Uint),
                         ScalarKind::Bool => panic!(),
                     };
 
-                    quote!(::shatter::TextureBinding<#sample_type, #dimension, #multi>)
+                    quote!(::shatter::__abi::TextureBinding<#sample_type, #dimension, #multi>)
                 }
                 ImageClass::Storage { format, .. } => {
-                    let texel_format = match format {
-                        StorageFormat::Rgba8Unorm => quote!(::shatter::texel_format::Rgba8Unorm),
-                        _ => unimplemented!(),
-                    };
+                    // unsupported formats are rejected by `gen_binding_type`
+                    let texel_format = texel_format(&format).unwrap();
 
-                    quote!(::shatter::StorageTextureBinding<#texel_format, #dimension>)
+                    quote!(::shatter::__abi::StorageTextureBinding<#texel_format, #dimension>)
                 }
                 _ => unimplemented!(),
             }
         }
+        TypeInner::Sampler { .. } => quote!(::shatter::__abi::SamplerBinding),
         _ => unimplemented!("type cannot be resolved"),
     }
 }
@@ -951,7 +4070,7 @@ fn rust_const(module: &Module, constant: Handle<Constant>) -> TokenStream {
 
     match constant.name {
         Some(ref name) => {
-            let ident = Ident::new(name, Span::call_site());
+            let ident = names::ident(name);
 
             quote!(#ident)
         }
@@ -977,9 +4096,75 @@ fn rust_scalar(kind: ScalarKind, width: u8) -> TokenStream {
             width => unreachable!("scalar with of '{}' not supported", width),
         },
         ScalarKind::Float => match width {
+            2 => quote!(::shatter::__abi::f16),
             4 => quote!(::std::primitive::f32),
             8 => quote!(::std::primitive::f64),
             width => unreachable!("scalar with of '{}' not supported", width),
         },
     }
 }
+
+/// Gets the WGSL spelling of `ty`, used in the layout tables.
+fn wgsl_type_name(module: &Module, ty: Handle<Type>) -> String {
+    let ty = &module.types[ty];
+
+    if let Some(ref name) = ty.name {
+        return name.clone();
+    }
+
+    match ty.inner {
+        TypeInner::Scalar { kind, width } => wgsl_scalar_name(kind, width),
+        TypeInner::Vector { size, kind, width } => {
+            format!("vec{}<{}>", size as u8, wgsl_scalar_name(kind, width))
+        }
+        TypeInner::Matrix {
+            columns,
+            rows,
+            width,
+        } => format!(
+            "mat{}x{}<{}>",
+            columns as u8,
+            rows as u8,
+            wgsl_scalar_name(ScalarKind::Float, width)
+        ),
+        TypeInner::Atomic { kind, width } => {
+            format!("atomic<{}>", wgsl_scalar_name(kind, width))
+        }
+        TypeInner::Array { base, size, stride } => {
+            let prefix = match array_padding(module, base, stride) {
+                0 => String::new(),
+                _ => format!("[[stride({})]] ", stride),
+            };
+
+            let base = wgsl_type_name(module, base);
+
+            let array = match size {
+                ArraySize::Constant(size) => match module.constants[size].inner {
+                    ConstantInner::Scalar {
+                        value: ScalarValue::Uint(size),
+                        ..
+                    } => format!("array<{}, {}>", base, size),
+                    ConstantInner::Scalar {
+                        value: ScalarValue::Sint(size),
+                        ..
+                    } => format!("array<{}, {}>", base, size),
+                    _ => unreachable!("array size must be an integer"),
+                },
+                ArraySize::Dynamic => format!("array<{}>", base),
+            };
+
+            prefix + &array
+        }
+        // rejected by `check_member_types`
+        _ => unreachable!("type cannot be a struct member"),
+    }
+}
+
+fn wgsl_scalar_name(kind: ScalarKind, width: u8) -> String {
+    match kind {
+        ScalarKind::Bool => String::from("bool"),
+        ScalarKind::Sint => format!("i{}", width * 8),
+        ScalarKind::Uint => format!("u{}", width * 8),
+        ScalarKind::Float => format!("f{}", width * 8),
+    }
+}
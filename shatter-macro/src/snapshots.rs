@@ -0,0 +1,157 @@
+//! Snapshots of the expansion of representative shaders, the examples and
+//! entries of the gallery, catching unintended changes to the generated code.
+//!
+//! The `wgsl!` blocks are read from their files, expanded and formatted with
+//! rustfmt, then compared against `tests/expansions/<name>.rs`. Set
+//! `SHATTER_BLESS=1` to regenerate them, snapshots are only ever written then
+//! and a missing snapshot fails the comparison.
+
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use proc_macro2::TokenStream;
+use syn::Item;
+
+/// Snapshot names and the files, relative to the workspace, of their shaders.
+const SHADERS: &[(&str, &str)] = &[
+    ("mandelbrot", "examples/mandelbrot.rs"),
+    ("wgsl", "examples/wgsl.rs"),
+    ("constants", "tests/shaders/constants.rs"),
+    ("family", "tests/shaders/family.rs"),
+    ("multiple_groups", "tests/shaders/multiple_groups.rs"),
+    ("pipeline", "tests/shaders/pipeline.rs"),
+    ("push_constants", "tests/shaders/push_constants.rs"),
+    ("render", "tests/shaders/render.rs"),
+    ("select", "tests/shaders/select.rs"),
+    ("storage_texture", "tests/shaders/storage_texture.rs"),
+    ("struct_layout", "tests/shaders/struct_layout.rs"),
+];
+
+fn bless() -> bool {
+    env::var_os("SHATTER_BLESS").is_some_and(|value| value != "0")
+}
+
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Bodies of the top level `wgsl!` blocks of the Rust file at `path`, including
+/// their `mod` if namespaced.
+fn wgsl_blocks(path: &Path) -> Vec<TokenStream> {
+    let source = fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("failed to read {}: {}", path.display(), err));
+    let file = syn::parse_file(&source)
+        .unwrap_or_else(|err| panic!("failed to parse {}: {}", path.display(), err));
+
+    file.items
+        .into_iter()
+        .filter_map(|item| match item {
+            Item::Macro(item) if item.mac.path.is_ident("wgsl") => Some(item.mac.tokens),
+            _ => None,
+        })
+        .collect()
+}
+
+fn rustfmt(tokens: &TokenStream) -> String {
+    let mut child = Command::new(env::var_os("RUSTFMT").unwrap_or_else(|| "rustfmt".into()))
+        .args(["--edition", "2021"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("snapshots are formatted with rustfmt, which failed to start");
+
+    let mut stdin = child.stdin.take().unwrap();
+    let source = tokens.to_string();
+    let writer = std::thread::spawn(move || stdin.write_all(source.as_bytes()));
+
+    let output = child.wait_with_output().unwrap();
+    writer.join().unwrap().unwrap();
+
+    assert!(output.status.success(), "rustfmt failed on the expansion");
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Describes the first line where `actual` differs from `expected`.
+fn first_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+
+    for line in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(expected), Some(actual)) if expected == actual => {}
+            (None, None) => break,
+            (expected, actual) => {
+                return format!(
+                    "line {}:\n  expected: {}\n  actual:   {}",
+                    line,
+                    expected.unwrap_or("<end of file>"),
+                    actual.unwrap_or("<end of file>"),
+                );
+            }
+        }
+    }
+
+    String::from("line endings differ")
+}
+
+fn check_snapshot(name: &str, source: &str) {
+    let workspace = manifest_dir().join("..");
+    let blocks = wgsl_blocks(&workspace.join(source));
+    assert!(!blocks.is_empty(), "{} has no `wgsl!` block", source);
+
+    let mut actual = String::new();
+
+    for block in blocks {
+        let (namespace, block) = crate::namespace::resolve(block);
+        let expansion = crate::namespace::wrap(namespace, crate::expand(block, false));
+
+        actual.push_str(&rustfmt(&expansion));
+    }
+
+    let path = manifest_dir()
+        .join("tests/expansions")
+        .join(format!("{}.rs", name));
+
+    if bless() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap();
+
+        eprintln!("wrote snapshot {}", path.display());
+
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!(
+            "snapshot {}: {}\nrun with SHATTER_BLESS=1 to regenerate snapshots",
+            path.display(),
+            err
+        )
+    });
+
+    if expected != actual {
+        let actual_path = path.with_extension("actual.rs");
+        fs::write(&actual_path, &actual).unwrap();
+
+        panic!(
+            "expansion of {} doesn't match {}, {}\nactual: {}\n\
+            run with SHATTER_BLESS=1 to regenerate snapshots",
+            source,
+            path.display(),
+            first_difference(&expected, &actual),
+            actual_path.display(),
+        );
+    }
+}
+
+#[test]
+fn expansions_match_snapshots() {
+    for (name, source) in SHADERS {
+        check_snapshot(name, source);
+    }
+}
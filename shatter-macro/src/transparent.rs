@@ -0,0 +1,180 @@
+use proc_macro2::TokenStream;
+use proc_macro_error::abort;
+use quote::quote;
+use syn::{Data, DeriveInput, Meta, NestedMeta};
+
+use crate::shatter::gen_abi_guard;
+
+/// Returns true if `input` is `#[repr(transparent)]`.
+fn is_transparent(input: &DeriveInput) -> bool {
+    input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| attr.parse_meta().ok())
+        .any(|meta| match meta {
+            Meta::List(list) => list.nested.iter().any(|nested| match nested {
+                NestedMeta::Meta(meta) => meta.path().is_ident("transparent"),
+                NestedMeta::Lit(_) => false,
+            }),
+            _ => false,
+        })
+}
+
+pub fn buffer_data_transparent(input: &DeriveInput) -> TokenStream {
+    let name = &input.ident;
+
+    let fields = match input.data {
+        Data::Struct(ref data) => &data.fields,
+        _ => abort!(
+            name,
+            "`BufferDataTransparent` can only be derived for structs"
+        ),
+    };
+
+    if fields.len() != 1 {
+        abort!(
+            name,
+            "`BufferDataTransparent` requires exactly one field, `{}` has {}",
+            name,
+            fields.len()
+        );
+    }
+
+    if !is_transparent(input) {
+        abort!(
+            name,
+            "`BufferDataTransparent` requires `#[repr(transparent)]` on `{}`",
+            name
+        );
+    }
+
+    if !input.generics.params.is_empty() {
+        abort!(
+            input.generics,
+            "`BufferDataTransparent` can't be derived for generic structs"
+        );
+    }
+
+    let inner = &fields.iter().next().unwrap().ty;
+    let abi_guard = gen_abi_guard();
+
+    quote! {
+        #abi_guard
+
+        // SAFETY: the struct is `repr(transparent)` over `#inner`, so every
+        // allocation of `#inner` is a valid allocation of the struct.
+        unsafe impl ::shatter::__abi::BufferData for #name {
+            type State = <#inner as ::shatter::__abi::BufferData>::State;
+            type Contents = <#inner as ::shatter::__abi::BufferData>::Contents;
+
+            const NEEDS_DROP: bool = <#inner as ::shatter::__abi::BufferData>::NEEDS_DROP;
+
+            #[inline]
+            fn init() -> Self::State {
+                <#inner as ::shatter::__abi::BufferData>::init()
+            }
+
+            #[inline]
+            fn size(state: &Self::State) -> usize {
+                <#inner as ::shatter::__abi::BufferData>::size(state)
+            }
+
+            #[inline]
+            unsafe fn alloc() -> ::std::ptr::NonNull<u8> {
+                unsafe { <#inner as ::shatter::__abi::BufferData>::alloc() }
+            }
+
+            #[inline]
+            unsafe fn alloc_zeroed(size: usize) -> (::std::ptr::NonNull<u8>, Self::State) {
+                unsafe { <#inner as ::shatter::__abi::BufferData>::alloc_zeroed(size) }
+            }
+
+            #[inline]
+            unsafe fn dealloc(ptr: ::std::ptr::NonNull<u8>, state: &Self::State) {
+                unsafe { <#inner as ::shatter::__abi::BufferData>::dealloc(ptr, state) }
+            }
+
+            #[inline]
+            unsafe fn as_ptr(ptr: ::std::ptr::NonNull<u8>, state: &Self::State) -> *mut Self {
+                unsafe { <#inner as ::shatter::__abi::BufferData>::as_ptr(ptr, state) as *mut Self }
+            }
+
+            #[inline]
+            unsafe fn contents(ptr: ::std::ptr::NonNull<u8>, state: &Self::State) -> Self::Contents {
+                unsafe { <#inner as ::shatter::__abi::BufferData>::contents(ptr, state) }
+            }
+        }
+
+        // the higher ranked bound makes the impl conditional instead of an
+        // error when `#inner` can't grow
+        unsafe impl ::shatter::__abi::BufferVec for #name
+        where
+            for<'__shatter> #inner: ::shatter::__abi::BufferVec,
+        {
+            type Head = <#inner as ::shatter::__abi::BufferVec>::Head;
+            type Item = <#inner as ::shatter::__abi::BufferVec>::Item;
+
+            const ITEM_STRIDE: usize = <#inner as ::shatter::__abi::BufferVec>::ITEM_STRIDE;
+            const ITEM_ALIGN: usize = <#inner as ::shatter::__abi::BufferVec>::ITEM_ALIGN;
+
+            #[inline]
+            fn len(state: &Self::State) -> usize {
+                <#inner as ::shatter::__abi::BufferVec>::len(state)
+            }
+
+            #[inline]
+            fn capacity(state: &Self::State) -> usize {
+                <#inner as ::shatter::__abi::BufferVec>::capacity(state)
+            }
+
+            #[inline]
+            unsafe fn grow(ptr: &mut ::std::ptr::NonNull<u8>, state: &mut Self::State) {
+                unsafe { <#inner as ::shatter::__abi::BufferVec>::grow(ptr, state) }
+            }
+
+            #[inline]
+            unsafe fn reserve(
+                ptr: &mut ::std::ptr::NonNull<u8>,
+                state: &mut Self::State,
+                additional: usize,
+            ) {
+                unsafe { <#inner as ::shatter::__abi::BufferVec>::reserve(ptr, state, additional) }
+            }
+
+            #[inline]
+            unsafe fn push(
+                ptr: &mut ::std::ptr::NonNull<u8>,
+                state: &mut Self::State,
+                item: Self::Item,
+            ) {
+                unsafe { <#inner as ::shatter::__abi::BufferVec>::push(ptr, state, item) }
+            }
+
+            #[inline]
+            unsafe fn pop(
+                ptr: ::std::ptr::NonNull<u8>,
+                state: &mut Self::State,
+            ) -> ::std::option::Option<Self::Item> {
+                unsafe { <#inner as ::shatter::__abi::BufferVec>::pop(ptr, state) }
+            }
+
+            #[inline]
+            unsafe fn set_len(state: &mut Self::State, len: usize) {
+                unsafe { <#inner as ::shatter::__abi::BufferVec>::set_len(state, len) }
+            }
+
+            #[inline]
+            unsafe fn items(
+                ptr: ::std::ptr::NonNull<u8>,
+                state: &Self::State,
+            ) -> *mut Self::Item {
+                unsafe { <#inner as ::shatter::__abi::BufferVec>::items(ptr, state) }
+            }
+        }
+
+        unsafe impl ::shatter::__abi::BufferDataTransparent for #name {
+            type Inner = #inner;
+        }
+    }
+}
@@ -3,6 +3,8 @@ use std::collections::BTreeMap;
 use proc_macro2::{Delimiter, Spacing, TokenTree};
 use proc_macro_error::{Diagnostic, Level};
 
+use crate::options::Options;
+
 pub trait WgslResult {
     type Ok;
 
@@ -20,7 +22,15 @@ impl<T> WgslResult for Result<T, naga::front::wgsl::ParseError> {
 
                 let span = wgsl.get_span(column);
 
-                Diagnostic::spanned(*span, Level::Error, error.to_string()).abort()
+                // tokens of included files only span the invocation
+                let message = match crate::include::location(wgsl, column.saturating_sub(1)) {
+                    Some((path, line, column)) => {
+                        format!("{}:{}:{}: {}", path, line, column, error)
+                    }
+                    None => error.to_string(),
+                };
+
+                Diagnostic::spanned(*span, Level::Error, message).abort()
             }
         }
     }
@@ -30,6 +40,7 @@ impl<T> WgslResult for Result<T, naga::front::wgsl::ParseError> {
 pub struct Wgsl {
     pub spans: BTreeMap<usize, proc_macro2::Span>,
     pub source: String,
+    skip_spans: bool,
 }
 
 impl Wgsl {
@@ -49,10 +60,30 @@ impl Wgsl {
         }
     }
 
+    /// Gets the span of the tokens a naga span was parsed from.
+    pub fn span(&self, span: naga::Span) -> proc_macro2::Span {
+        match span.to_range() {
+            Some(range) => *self.get_span(range.start),
+            None => proc_macro2::Span::call_site(),
+        }
+    }
+
     #[inline]
     pub fn new(source: &proc_macro2::TokenStream) -> Self {
+        Self::with_options(source, &Options::default())
+    }
+
+    /// Collects the source of `source` without the spans of its tokens when
+    /// expanding for an IDE, as errors are reported by the build.
+    pub fn with_options(source: &proc_macro2::TokenStream, options: &Options) -> Self {
         let mut wgsl = Self::default();
 
+        // diagnostics point at the call site
+        if options.ide {
+            wgsl.skip_spans = true;
+            wgsl.spans.insert(0, proc_macro2::Span::call_site());
+        }
+
         for tree in source.clone() {
             wgsl.add_tree(tree);
         }
@@ -117,6 +148,8 @@ impl Wgsl {
 
         self.source += string;
 
-        self.spans.insert(start, span);
+        if !self.skip_spans {
+            self.spans.insert(start, span);
+        }
     }
 }
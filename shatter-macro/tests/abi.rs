@@ -0,0 +1,51 @@
+//! Compiles an expansion touching every item of `shatter::__abi`, catching
+//! drift between the generated code and the runtime.
+
+shatter::wgsl! {
+    let SCALE: f32 = 2.0;
+
+    struct Pair {
+        a: vec2<f32>;
+        b: vec4<u32>;
+    };
+
+    [[block]]
+    struct Uniforms {
+        pair: Pair;
+        offset: vec3<f32>;
+    };
+
+    [[block]]
+    struct Values {
+        values: array<f32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<uniform> uniforms: Uniforms;
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> values: Values;
+
+    [[group(1), binding(0)]]
+    var image: texture_storage_2d<rgba8unorm, write>;
+
+    [[stage(compute), workgroup_size(8, 1, 1)]]
+    fn main([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] * SCALE + uniforms.offset.x;
+        textureStore(image, vec2<i32>(id.xy), vec4<f32>(uniforms.pair.a, 0.0, 1.0));
+    }
+}
+
+#[test]
+fn abi_version() {
+    assert_eq!(shatter::ABI_VERSION, 32);
+}
+
+#[test]
+fn expansion() {
+    assert_eq!(main::WORK_GROUP_SIZE, shatter::WorkGroupSize::new(8, 1, 1));
+    assert_eq!(SCALE, 2.0);
+
+    let _: fn(main::Bindings<'_>, shatter::Dispatch) = main;
+    let _: fn(&main::Bindings<'_>) -> Result<(), shatter::ValidationReport> = main::validate;
+}
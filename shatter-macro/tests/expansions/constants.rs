@@ -0,0 +1,794 @@
+const _: () = ::std::assert!(
+    ::shatter::ABI_VERSION == 32u32,
+    "shatter-macro 0.0.1 requires shatter ABI 32, update the shatter crate"
+);
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub const FACTOR: ::std::primitive::i32 = 3i64 as i32;
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub const OFFSET: ::std::primitive::f32 = 0.5f64 as f32;
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub struct Data {
+    pub int: ::std::primitive::i32,
+    pub float: ::std::primitive::f32,
+}
+const _: () = {
+    ::shatter::__abi::assert_layout("Data::int", "offset", 0, ::std::mem::offset_of!(Data, int));
+    ::shatter::__abi::assert_layout(
+        "Data::float",
+        "offset",
+        4,
+        ::std::mem::offset_of!(Data, float),
+    );
+    ::shatter::__abi::assert_layout("Data", "size", 8, ::std::mem::size_of::<Data>());
+};
+impl ::std::default::Default for Data {
+    #[inline]
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+unsafe impl ::shatter::__abi::BufferData for Data {
+    type State = ();
+    type Contents = Self;
+    const NEEDS_DROP: bool = false;
+    fn init() -> Self::State {}
+    fn size(_: &Self::State) -> usize {
+        ::std::mem::size_of::<Data>()
+    }
+    unsafe fn alloc() -> ::std::ptr::NonNull<u8> {
+        if ::std::mem::size_of::<Data>() == 0 {
+            return ::std::ptr::NonNull::<Data>::dangling().cast();
+        }
+        let layout = ::std::alloc::Layout::new::<Data>();
+        let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+        let ptr = match ::std::ptr::NonNull::new(ptr) {
+            ::std::option::Option::Some(ptr) => ptr,
+            ::std::option::Option::None => ::std::alloc::handle_alloc_error(layout),
+        };
+        ::shatter::__abi::record_alloc::<Data>(ptr, layout);
+        ptr
+    }
+    unsafe fn alloc_zeroed(_: usize) -> (::std::ptr::NonNull<u8>, Self::State) {
+        if ::std::mem::size_of::<Data>() == 0 {
+            return (::std::ptr::NonNull::<Data>::dangling().cast(), ());
+        }
+        let layout = ::std::alloc::Layout::new::<Data>();
+        let ptr = ::std::ptr::NonNull::new(unsafe { ::std::alloc::alloc_zeroed(layout) }).unwrap();
+        ::shatter::__abi::record_alloc::<Data>(ptr, layout);
+        (ptr, ())
+    }
+    unsafe fn dealloc(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) {
+        let layout = ::std::alloc::Layout::new::<Data>();
+        if layout.size() == 0 {
+            return;
+        }
+        ::shatter::__abi::record_dealloc::<Data>(ptr, layout);
+        unsafe { ::std::alloc::dealloc(ptr.as_ptr(), layout) };
+    }
+    unsafe fn as_ptr(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) -> *mut Self {
+        ptr.as_ptr() as *mut Self
+    }
+    unsafe fn contents(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) -> Self {
+        unsafe { ::std::ptr::read(ptr.as_ptr() as *const Self) }
+    }
+}
+unsafe impl ::shatter::__abi::Reflect for Data {
+    const LAYOUT: ::shatter::__abi::StructLayout = ::shatter::__abi::StructLayout {
+        name: ::std::borrow::Cow::Borrowed("Data"),
+        size: ::std::mem::size_of::<Data>() as u64,
+        fields: ::std::borrow::Cow::Borrowed(&[
+            ::shatter::__abi::FieldLayout {
+                name: ::std::borrow::Cow::Borrowed("int"),
+                ty: ::std::borrow::Cow::Borrowed("i32"),
+                offset: ::std::mem::offset_of!(Data, int) as u64,
+                size: ::std::mem::size_of::<::std::primitive::i32>() as u64,
+            },
+            ::shatter::__abi::FieldLayout {
+                name: ::std::borrow::Cow::Borrowed("float"),
+                ty: ::std::borrow::Cow::Borrowed("f32"),
+                offset: ::std::mem::offset_of!(Data, float) as u64,
+                size: ::std::mem::size_of::<::std::primitive::f32>() as u64,
+            },
+        ]),
+    };
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod apply {
+    use super::*;
+    pub const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize =
+        ::shatter::__abi::WorkGroupSize::new(
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+        );
+    #[doc = r" Invocations per work group, the product of [`WORK_GROUP_SIZE`]."]
+    pub const WORK_GROUP_INVOCATIONS: ::std::primitive::u32 = 1u32;
+    #[doc = r" Dispatch covering `elements` invocations along x, see"]
+    #[doc = r" [`Dispatch::try_linear`](::shatter::__abi::Dispatch::try_linear)."]
+    pub fn checked_dispatch(
+        elements: ::std::primitive::u64,
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_linear(elements, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Dispatch covering `extent` invocations along each axis, see"]
+    #[doc = r" [`Dispatch::try_for_extent`](::shatter::__abi::Dispatch::try_for_extent)."]
+    pub fn checked_dispatch_for_extent(
+        extent: [::std::primitive::u64; 3],
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_for_extent(extent, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Like [`checked_dispatch`], panics if the dispatch is too large."]
+    pub fn dispatch_for(elements: ::std::primitive::u64) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch(elements) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "apply", err),
+        }
+    }
+    #[doc = r" Like [`checked_dispatch_for_extent`], panics if the dispatch is too large."]
+    pub fn dispatch_for_extent(extent: [::std::primitive::u64; 3]) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch_for_extent(extent) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "apply", err),
+        }
+    }
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct Bindings<'a> {
+        pub data: &'a mut dyn ::shatter::__abi::BindingMut<Data>,
+    }
+    impl<'a> Bindings<'a> {
+        #[doc = r" Borrows every binding from `registry` by its WGSL name,"]
+        #[doc = r" reporting every missing or mismatched resource."]
+        pub fn from_registry(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            Self::from_registry_renamed(registry, &[])
+        }
+        #[doc = r" Like [`Self::from_registry`], `rename` maps WGSL names to"]
+        #[doc = r" the names resources are stored under."]
+        pub fn from_registry_renamed(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+            rename: &[(&::std::primitive::str, &::std::primitive::str)],
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            let mut borrows = registry.borrow_bindings(
+                &[::shatter::__abi::RegistryRequest::new::<Data>(
+                    "data", true, false,
+                )],
+                rename,
+            )?;
+            ::std::result::Result::Ok(Self {
+                data: borrows.take_mut::<Data>(0usize),
+            })
+        }
+        pub fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            ::std::vec![::shatter::__abi::BindGroupLayoutDescriptor {
+                entries: ::std::vec![::shatter::__abi::BindGroupLayoutEntry {
+                    binding: 0u32,
+                    visibility: ::shatter::__abi::ShaderStages::COMPUTE,
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: ::std::option::Option::None,
+                }],
+            }]
+        }
+        pub fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            let mut layouts = layouts.iter();
+            ::std::vec![::shatter::__abi::BindGroupDescriptor {
+                layout: layouts.next().unwrap().clone(),
+                entries: ::std::vec![::shatter::__abi::BindGroupEntry {
+                    binding: 0u32 as u32,
+                    resource: ::shatter::__abi::Binding::binding_resource(self.data),
+                }],
+            }]
+        }
+        pub fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            let mut layouts = layouts.iter();
+            let mut keys = ::shatter::__abi::GroupKeys::new();
+            keys.push({
+                let mut key = ::shatter::__abi::GroupKey::new(layouts.next().unwrap());
+                key.push(
+                    0u32 as u32,
+                    &::shatter::__abi::Binding::binding_resource(self.data),
+                );
+                key
+            });
+            keys
+        }
+    }
+    impl<'a> ::shatter::__abi::Bindings for Bindings<'a> {
+        #[inline]
+        fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            self.bind_group_layout_descriptors()
+        }
+        #[inline]
+        fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            self.bind_group_descriptors(layouts)
+        }
+        #[inline]
+        fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            self.bind_group_keys(layouts)
+        }
+        fn validate(&self) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+            let mut bindings = ::std::vec::Vec::new();
+            bindings.push((
+                ::shatter::__abi::BindingExpectation {
+                    name: "data",
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    min_size: 8u64,
+                    writable: true,
+                },
+                ::shatter::__abi::Binding::resource_info(self.data),
+            ));
+            ::shatter::__abi::validate_bindings(&bindings)
+        }
+        #[inline]
+        fn prepare(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::prepare(self.data, report);
+        }
+        #[inline]
+        fn read(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::read(self.data, report);
+        }
+        #[inline]
+        fn write(&mut self) {
+            ::shatter::__abi::BindingMut::write(self.data);
+        }
+        #[inline]
+        fn needs_allocation(&self) -> bool {
+            ::shatter::__abi::Binding::needs_allocation(self.data)
+        }
+    }
+    #[doc = r" Like [`Bindings`] owning the resources, taken by the `_async`"]
+    #[doc = r" dispatch so its future is `Send` and handed back once it's done."]
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct OwnedBindings<B0> {
+        pub data: B0,
+    }
+    impl<B0> OwnedBindings<B0>
+    where
+        B0: ::shatter::__abi::BindingMut<Data>,
+    {
+        #[doc = r" Borrows the resources as [`Bindings`]."]
+        pub fn as_bindings(&mut self) -> Bindings<'_> {
+            Bindings {
+                data: &mut self.data,
+            }
+        }
+        #[doc = r" Downloads what the GPU wrote to the writable resources"]
+        #[doc = r" without blocking, returns the number of bytes downloaded."]
+        pub async fn download_async(
+            &mut self,
+        ) -> ::std::result::Result<::std::primitive::u64, ::shatter::__abi::Error> {
+            ::std::result::Result::Ok(
+                0 + ::shatter::__abi::Binding::<Data>::download_async(&mut self.data).await?,
+            )
+        }
+    }
+    #[doc = r" Checks that `bindings` are compatible with the shader without dispatching."]
+    pub fn validate(
+        bindings: &Bindings<'_>,
+    ) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+        ::shatter::__abi::Bindings::validate(bindings)
+    }
+    pub struct Shader;
+    impl<'a> ::shatter::__abi::ComputeShader<'a> for Shader {
+        type Bindings = Bindings<'a>;
+        const SOURCE : & 'static :: std :: primitive :: str = "let FACTOR : i32 = 3 ; let OFFSET : f32 = 0.5 ; [[block ]]struct Data {int : i32 ; float : f32 ; }; [[group (0 ), binding (0 )]]var < storage , read_write > data : Data ; [[stage (compute ), workgroup_size (1 )]]fn apply (){data . int = data . int * FACTOR ; data . float = data . float + OFFSET ; }" ;
+        const ENTRY_POINT: &'static ::std::primitive::str = "apply";
+        const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = WORK_GROUP_SIZE;
+        type PushConstants = ::shatter::__abi::NoPushConstants;
+    }
+    impl<'a> ::shatter::__abi::DispatchableShader<Bindings<'a>> for Shader {
+        fn entry_point(&self) -> &'static ::std::primitive::str {
+            "apply"
+        }
+        fn work_group_size(&self) -> ::shatter::__abi::WorkGroupSize {
+            WORK_GROUP_SIZE
+        }
+        fn dispatch(&self, bindings: Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+            ::shatter::__abi::ComputeShaderBuilder::<'a, Shader>::new(bindings)
+                .tag("apply")
+                .dispatch(dispatch);
+        }
+    }
+    pub fn build<'a>(
+        mut bindings: apply::Bindings<'a>,
+    ) -> ::shatter::__abi::ComputeShaderBuilder<'a, Shader> {
+        ::shatter::__abi::ComputeShaderBuilder::new(bindings)
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub fn apply<'a>(mut bindings: apply::Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+    apply::build(bindings).tag("apply").dispatch(dispatch);
+}
+#[doc = r" Like the blocking function without waiting on the GPU, resolves once the"]
+#[doc = r" dispatch is done and what it wrote is downloaded. Takes the resources"]
+#[doc = r" by value so the future is `Send`, they are handed back when it resolves."]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub async fn apply_async<B0>(
+    mut bindings: apply::OwnedBindings<B0>,
+    dispatch: ::shatter::__abi::Dispatch,
+) -> apply::OwnedBindings<B0>
+where
+    B0: ::shatter::__abi::BindingMut<Data> + ::std::marker::Send,
+{
+    let work = apply::build(bindings.as_bindings())
+        .tag("apply")
+        .dispatch_async(dispatch);
+    work.await;
+    if let ::std::result::Result::Err(err) = bindings.download_async().await {
+        ::std::panic!("{}", err);
+    }
+    bindings
+}
+pub mod composite {
+    #[allow(unused_imports)]
+    use super::*;
+    const _: () = ::std::assert!(
+        ::shatter::ABI_VERSION == 32u32,
+        "shatter-macro 0.0.1 requires shatter ABI 32, update the shatter crate"
+    );
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub const COUNT: ::std::primitive::u32 = 1024u64 as u32;
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub const MASK: ::std::primitive::u32 = 4294967295u64 as u32;
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub const DIR: ::shatter::__abi::Vec2<::std::primitive::f32> =
+        ::shatter::__abi::Vec2::new(1f64 as f32, 0f64 as f32);
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub const TINT: ::shatter::__abi::Vec4<::std::primitive::u32> =
+        ::shatter::__abi::Vec4::new(1u64 as u32, 2u64 as u32, 3u64 as u32, 4294967295u64 as u32);
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub const BASIS: ::shatter::__abi::Mat2<::std::primitive::f32> =
+        ::shatter::__abi::Mat2::from_cols(
+            ::shatter::__abi::Vec2::new(0f64 as f32, 1f64 as f32),
+            ::shatter::__abi::Vec2::new(2f64 as f32, 0.5f64 as f32),
+        );
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub const WEIGHTS: [::std::primitive::f32; 3i64 as i32 as ::std::primitive::usize] =
+        [0.25f64 as f32, 0.5f64 as f32, 0.25f64 as f32];
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct Output {
+        pub count: ::std::primitive::u32,
+        pub mask: ::std::primitive::u32,
+        pub dir: ::shatter::__abi::Vec2<::std::primitive::f32>,
+        pub tint: ::shatter::__abi::Vec4<::std::primitive::u32>,
+        pub basis: ::shatter::__abi::Mat2<::std::primitive::f32>,
+        pub weights: [::std::primitive::f32; 3i64 as i32 as ::std::primitive::usize],
+    }
+    const _: () = {
+        ::shatter::__abi::assert_layout(
+            "Output::count",
+            "offset",
+            0,
+            ::std::mem::offset_of!(Output, count),
+        );
+        ::shatter::__abi::assert_layout(
+            "Output::mask",
+            "offset",
+            4,
+            ::std::mem::offset_of!(Output, mask),
+        );
+        ::shatter::__abi::assert_layout(
+            "Output::dir",
+            "offset",
+            8,
+            ::std::mem::offset_of!(Output, dir),
+        );
+        ::shatter::__abi::assert_layout(
+            "Output::tint",
+            "offset",
+            16,
+            ::std::mem::offset_of!(Output, tint),
+        );
+        ::shatter::__abi::assert_layout(
+            "Output::basis",
+            "offset",
+            32,
+            ::std::mem::offset_of!(Output, basis),
+        );
+        ::shatter::__abi::assert_layout(
+            "Output::weights",
+            "offset",
+            48,
+            ::std::mem::offset_of!(Output, weights),
+        );
+        ::shatter::__abi::assert_layout(
+            "Output::weights",
+            "stride",
+            4,
+            ::std::mem::size_of::<::std::primitive::f32>(),
+        );
+        ::shatter::__abi::assert_layout("Output", "size", 64, ::std::mem::size_of::<Output>());
+    };
+    impl ::std::default::Default for Output {
+        #[inline]
+        fn default() -> Self {
+            unsafe { ::std::mem::zeroed() }
+        }
+    }
+    unsafe impl ::shatter::__abi::BufferData for Output {
+        type State = ();
+        type Contents = Self;
+        const NEEDS_DROP: bool = false;
+        fn init() -> Self::State {}
+        fn size(_: &Self::State) -> usize {
+            ::std::mem::size_of::<Output>()
+        }
+        unsafe fn alloc() -> ::std::ptr::NonNull<u8> {
+            if ::std::mem::size_of::<Output>() == 0 {
+                return ::std::ptr::NonNull::<Output>::dangling().cast();
+            }
+            let layout = ::std::alloc::Layout::new::<Output>();
+            let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+            let ptr = match ::std::ptr::NonNull::new(ptr) {
+                ::std::option::Option::Some(ptr) => ptr,
+                ::std::option::Option::None => ::std::alloc::handle_alloc_error(layout),
+            };
+            ::shatter::__abi::record_alloc::<Output>(ptr, layout);
+            ptr
+        }
+        unsafe fn alloc_zeroed(_: usize) -> (::std::ptr::NonNull<u8>, Self::State) {
+            if ::std::mem::size_of::<Output>() == 0 {
+                return (::std::ptr::NonNull::<Output>::dangling().cast(), ());
+            }
+            let layout = ::std::alloc::Layout::new::<Output>();
+            let ptr =
+                ::std::ptr::NonNull::new(unsafe { ::std::alloc::alloc_zeroed(layout) }).unwrap();
+            ::shatter::__abi::record_alloc::<Output>(ptr, layout);
+            (ptr, ())
+        }
+        unsafe fn dealloc(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) {
+            let layout = ::std::alloc::Layout::new::<Output>();
+            if layout.size() == 0 {
+                return;
+            }
+            ::shatter::__abi::record_dealloc::<Output>(ptr, layout);
+            unsafe { ::std::alloc::dealloc(ptr.as_ptr(), layout) };
+        }
+        unsafe fn as_ptr(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) -> *mut Self {
+            ptr.as_ptr() as *mut Self
+        }
+        unsafe fn contents(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) -> Self {
+            unsafe { ::std::ptr::read(ptr.as_ptr() as *const Self) }
+        }
+    }
+    unsafe impl ::shatter::__abi::Reflect for Output {
+        const LAYOUT: ::shatter::__abi::StructLayout = ::shatter::__abi::StructLayout {
+            name: ::std::borrow::Cow::Borrowed("Output"),
+            size: ::std::mem::size_of::<Output>() as u64,
+            fields: ::std::borrow::Cow::Borrowed(&[
+                ::shatter::__abi::FieldLayout {
+                    name: ::std::borrow::Cow::Borrowed("count"),
+                    ty: ::std::borrow::Cow::Borrowed("u32"),
+                    offset: ::std::mem::offset_of!(Output, count) as u64,
+                    size: ::std::mem::size_of::<::std::primitive::u32>() as u64,
+                },
+                ::shatter::__abi::FieldLayout {
+                    name: ::std::borrow::Cow::Borrowed("mask"),
+                    ty: ::std::borrow::Cow::Borrowed("u32"),
+                    offset: ::std::mem::offset_of!(Output, mask) as u64,
+                    size: ::std::mem::size_of::<::std::primitive::u32>() as u64,
+                },
+                ::shatter::__abi::FieldLayout {
+                    name: ::std::borrow::Cow::Borrowed("dir"),
+                    ty: ::std::borrow::Cow::Borrowed("vec2<f32>"),
+                    offset: ::std::mem::offset_of!(Output, dir) as u64,
+                    size: ::std::mem::size_of::<::shatter::__abi::Vec2<::std::primitive::f32>>()
+                        as u64,
+                },
+                ::shatter::__abi::FieldLayout {
+                    name: ::std::borrow::Cow::Borrowed("tint"),
+                    ty: ::std::borrow::Cow::Borrowed("vec4<u32>"),
+                    offset: ::std::mem::offset_of!(Output, tint) as u64,
+                    size: ::std::mem::size_of::<::shatter::__abi::Vec4<::std::primitive::u32>>()
+                        as u64,
+                },
+                ::shatter::__abi::FieldLayout {
+                    name: ::std::borrow::Cow::Borrowed("basis"),
+                    ty: ::std::borrow::Cow::Borrowed("mat2x2<f32>"),
+                    offset: ::std::mem::offset_of!(Output, basis) as u64,
+                    size: ::std::mem::size_of::<::shatter::__abi::Mat2<::std::primitive::f32>>()
+                        as u64,
+                },
+                ::shatter::__abi::FieldLayout {
+                    name: ::std::borrow::Cow::Borrowed("weights"),
+                    ty: ::std::borrow::Cow::Borrowed("array<f32, 3>"),
+                    offset: ::std::mem::offset_of!(Output, weights) as u64,
+                    size: ::std::mem::size_of::<
+                        [::std::primitive::f32; 3i64 as i32 as ::std::primitive::usize],
+                    >() as u64,
+                },
+            ]),
+        };
+    }
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub mod write_constants {
+        use super::*;
+        pub const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize =
+            ::shatter::__abi::WorkGroupSize::new(
+                1u32 as ::std::primitive::u32,
+                1u32 as ::std::primitive::u32,
+                1u32 as ::std::primitive::u32,
+            );
+        #[doc = r" Invocations per work group, the product of [`WORK_GROUP_SIZE`]."]
+        pub const WORK_GROUP_INVOCATIONS: ::std::primitive::u32 = 1u32;
+        #[doc = r" Dispatch covering `elements` invocations along x, see"]
+        #[doc = r" [`Dispatch::try_linear`](::shatter::__abi::Dispatch::try_linear)."]
+        pub fn checked_dispatch(
+            elements: ::std::primitive::u64,
+        ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge>
+        {
+            ::shatter::__abi::Dispatch::try_linear(elements, WORK_GROUP_SIZE)
+        }
+        #[doc = r" Dispatch covering `extent` invocations along each axis, see"]
+        #[doc = r" [`Dispatch::try_for_extent`](::shatter::__abi::Dispatch::try_for_extent)."]
+        pub fn checked_dispatch_for_extent(
+            extent: [::std::primitive::u64; 3],
+        ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge>
+        {
+            ::shatter::__abi::Dispatch::try_for_extent(extent, WORK_GROUP_SIZE)
+        }
+        #[doc = r" Like [`checked_dispatch`], panics if the dispatch is too large."]
+        pub fn dispatch_for(elements: ::std::primitive::u64) -> ::shatter::__abi::Dispatch {
+            match checked_dispatch(elements) {
+                ::std::result::Result::Ok(dispatch) => dispatch,
+                ::std::result::Result::Err(err) => {
+                    ::std::panic!("`{}`: {}", "write_constants", err)
+                }
+            }
+        }
+        #[doc = r" Like [`checked_dispatch_for_extent`], panics if the dispatch is too large."]
+        pub fn dispatch_for_extent(
+            extent: [::std::primitive::u64; 3],
+        ) -> ::shatter::__abi::Dispatch {
+            match checked_dispatch_for_extent(extent) {
+                ::std::result::Result::Ok(dispatch) => dispatch,
+                ::std::result::Result::Err(err) => {
+                    ::std::panic!("`{}`: {}", "write_constants", err)
+                }
+            }
+        }
+        #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+        pub struct Bindings<'a> {
+            pub output: &'a mut dyn ::shatter::__abi::BindingMut<Output>,
+        }
+        impl<'a> Bindings<'a> {
+            #[doc = r" Borrows every binding from `registry` by its WGSL name,"]
+            #[doc = r" reporting every missing or mismatched resource."]
+            pub fn from_registry(
+                registry: &'a mut ::shatter::__abi::ResourceRegistry,
+            ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+                Self::from_registry_renamed(registry, &[])
+            }
+            #[doc = r" Like [`Self::from_registry`], `rename` maps WGSL names to"]
+            #[doc = r" the names resources are stored under."]
+            pub fn from_registry_renamed(
+                registry: &'a mut ::shatter::__abi::ResourceRegistry,
+                rename: &[(&::std::primitive::str, &::std::primitive::str)],
+            ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+                let mut borrows = registry.borrow_bindings(
+                    &[::shatter::__abi::RegistryRequest::new::<Output>(
+                        "output", true, false,
+                    )],
+                    rename,
+                )?;
+                ::std::result::Result::Ok(Self {
+                    output: borrows.take_mut::<Output>(0usize),
+                })
+            }
+            pub fn bind_group_layout_descriptors(
+                &self,
+            ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+                ::std::vec![::shatter::__abi::BindGroupLayoutDescriptor {
+                    entries: ::std::vec![::shatter::__abi::BindGroupLayoutEntry {
+                        binding: 0u32,
+                        visibility: ::shatter::__abi::ShaderStages::COMPUTE,
+                        ty: ::shatter::__abi::BindingType::Buffer {
+                            ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: ::std::option::Option::None,
+                    }],
+                }]
+            }
+            pub fn bind_group_descriptors(
+                &self,
+                layouts: &[::shatter::__abi::BindGroupLayoutId],
+            ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+                let mut layouts = layouts.iter();
+                ::std::vec![::shatter::__abi::BindGroupDescriptor {
+                    layout: layouts.next().unwrap().clone(),
+                    entries: ::std::vec![::shatter::__abi::BindGroupEntry {
+                        binding: 0u32 as u32,
+                        resource: ::shatter::__abi::Binding::binding_resource(self.output),
+                    }],
+                }]
+            }
+            pub fn bind_group_keys(
+                &self,
+                layouts: &[::shatter::__abi::BindGroupLayoutId],
+            ) -> ::shatter::__abi::GroupKeys {
+                let mut layouts = layouts.iter();
+                let mut keys = ::shatter::__abi::GroupKeys::new();
+                keys.push({
+                    let mut key = ::shatter::__abi::GroupKey::new(layouts.next().unwrap());
+                    key.push(
+                        0u32 as u32,
+                        &::shatter::__abi::Binding::binding_resource(self.output),
+                    );
+                    key
+                });
+                keys
+            }
+        }
+        impl<'a> ::shatter::__abi::Bindings for Bindings<'a> {
+            #[inline]
+            fn bind_group_layout_descriptors(
+                &self,
+            ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+                self.bind_group_layout_descriptors()
+            }
+            #[inline]
+            fn bind_group_descriptors(
+                &self,
+                layouts: &[::shatter::__abi::BindGroupLayoutId],
+            ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+                self.bind_group_descriptors(layouts)
+            }
+            #[inline]
+            fn bind_group_keys(
+                &self,
+                layouts: &[::shatter::__abi::BindGroupLayoutId],
+            ) -> ::shatter::__abi::GroupKeys {
+                self.bind_group_keys(layouts)
+            }
+            fn validate(&self) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+                let mut bindings = ::std::vec::Vec::new();
+                bindings.push((
+                    ::shatter::__abi::BindingExpectation {
+                        name: "output",
+                        ty: ::shatter::__abi::BindingType::Buffer {
+                            ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        min_size: 64u64,
+                        writable: true,
+                    },
+                    ::shatter::__abi::Binding::resource_info(self.output),
+                ));
+                ::shatter::__abi::validate_bindings(&bindings)
+            }
+            #[inline]
+            fn prepare(&self, report: &mut ::shatter::__abi::TransferReport) {
+                ::shatter::__abi::Binding::prepare(self.output, report);
+            }
+            #[inline]
+            fn read(&self, report: &mut ::shatter::__abi::TransferReport) {}
+            #[inline]
+            fn write(&mut self) {
+                ::shatter::__abi::BindingMut::write(self.output);
+            }
+            #[inline]
+            fn needs_allocation(&self) -> bool {
+                ::shatter::__abi::Binding::needs_allocation(self.output)
+            }
+        }
+        #[doc = r" Like [`Bindings`] owning the resources, taken by the `_async`"]
+        #[doc = r" dispatch so its future is `Send` and handed back once it's done."]
+        #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+        pub struct OwnedBindings<B0> {
+            pub output: B0,
+        }
+        impl<B0> OwnedBindings<B0>
+        where
+            B0: ::shatter::__abi::BindingMut<Output>,
+        {
+            #[doc = r" Borrows the resources as [`Bindings`]."]
+            pub fn as_bindings(&mut self) -> Bindings<'_> {
+                Bindings {
+                    output: &mut self.output,
+                }
+            }
+            #[doc = r" Downloads what the GPU wrote to the writable resources"]
+            #[doc = r" without blocking, returns the number of bytes downloaded."]
+            pub async fn download_async(
+                &mut self,
+            ) -> ::std::result::Result<::std::primitive::u64, ::shatter::__abi::Error> {
+                ::std::result::Result::Ok(
+                    0 + ::shatter::__abi::Binding::<Output>::download_async(&mut self.output)
+                        .await?,
+                )
+            }
+        }
+        #[doc = r" Checks that `bindings` are compatible with the shader without dispatching."]
+        pub fn validate(
+            bindings: &Bindings<'_>,
+        ) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+            ::shatter::__abi::Bindings::validate(bindings)
+        }
+        pub struct Shader;
+        impl<'a> ::shatter::__abi::ComputeShader<'a> for Shader {
+            type Bindings = Bindings<'a>;
+            const SOURCE : & 'static :: std :: primitive :: str = "let COUNT : u32 = 1024u ; let MASK : u32 = 4294967295u ; let DIR : vec2 < f32 > = vec2 < f32 > (1.0 , 0.0 ); let TINT : vec4 < u32 > = vec4 < u32 > (1u , 2u , 3u , 4294967295u ); let BASIS : mat2x2 < f32 > = mat2x2 < f32 > (vec2 < f32 > (0.0 , 1.0 ), vec2 < f32 > (2.0 , 0.5 )); let WEIGHTS : array < f32 , 3 > = array < f32 , 3 > (0.25 , 0.5 , 0.25 ); [[block ]]struct Output {count : u32 ; mask : u32 ; dir : vec2 < f32 >; tint : vec4 < u32 >; basis : mat2x2 < f32 >; weights : array < f32 , 3 >; }; [[group (0 ), binding (0 )]]var < storage , read_write > output : Output ; [[stage (compute ), workgroup_size (1 )]]fn write_constants (){output . count = COUNT ; output . mask = MASK ; output . dir = DIR ; output . tint = TINT ; output . basis = BASIS ; output . weights = WEIGHTS ; }" ;
+            const ENTRY_POINT: &'static ::std::primitive::str = "write_constants";
+            const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = WORK_GROUP_SIZE;
+            type PushConstants = ::shatter::__abi::NoPushConstants;
+        }
+        impl<'a> ::shatter::__abi::DispatchableShader<Bindings<'a>> for Shader {
+            fn entry_point(&self) -> &'static ::std::primitive::str {
+                "write_constants"
+            }
+            fn work_group_size(&self) -> ::shatter::__abi::WorkGroupSize {
+                WORK_GROUP_SIZE
+            }
+            fn dispatch(&self, bindings: Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+                ::shatter::__abi::ComputeShaderBuilder::<'a, Shader>::new(bindings)
+                    .tag("write_constants")
+                    .dispatch(dispatch);
+            }
+        }
+        pub fn build<'a>(
+            mut bindings: write_constants::Bindings<'a>,
+        ) -> ::shatter::__abi::ComputeShaderBuilder<'a, Shader> {
+            ::shatter::__abi::ComputeShaderBuilder::new(bindings)
+        }
+    }
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub fn write_constants<'a>(
+        mut bindings: write_constants::Bindings<'a>,
+        dispatch: ::shatter::__abi::Dispatch,
+    ) {
+        write_constants::build(bindings)
+            .tag("write_constants")
+            .dispatch(dispatch);
+    }
+    #[doc = r" Like the blocking function without waiting on the GPU, resolves once the"]
+    #[doc = r" dispatch is done and what it wrote is downloaded. Takes the resources"]
+    #[doc = r" by value so the future is `Send`, they are handed back when it resolves."]
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub async fn write_constants_async<B0>(
+        mut bindings: write_constants::OwnedBindings<B0>,
+        dispatch: ::shatter::__abi::Dispatch,
+    ) -> write_constants::OwnedBindings<B0>
+    where
+        B0: ::shatter::__abi::BindingMut<Output> + ::std::marker::Send,
+    {
+        let work = write_constants::build(bindings.as_bindings())
+            .tag("write_constants")
+            .dispatch_async(dispatch);
+        work.await;
+        if let ::std::result::Result::Err(err) = bindings.download_async().await {
+            ::std::panic!("{}", err);
+        }
+        bindings
+    }
+}
@@ -0,0 +1,904 @@
+const _: () = ::std::assert!(
+    ::shatter::ABI_VERSION == 32u32,
+    "shatter-macro 0.0.1 requires shatter ABI 32, update the shatter crate"
+);
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub struct Values {
+    pub values: [::std::primitive::u32],
+}
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub struct Values_Sized {
+    pub values: [::std::primitive::u32; 0],
+}
+const _: () = {
+    ::shatter::__abi::assert_layout(
+        "Values::values",
+        "offset",
+        0,
+        ::std::mem::offset_of!(Values_Sized, values),
+    );
+    ::shatter::__abi::assert_layout(
+        "Values::values",
+        "stride",
+        4,
+        ::std::mem::size_of::<::std::primitive::u32>(),
+    );
+    ::shatter::__abi::assert_layout(
+        "Values",
+        "header size",
+        0,
+        ::std::mem::size_of::<Values_Sized>(),
+    );
+    ::shatter::__abi::assert_layout(
+        "Values::values",
+        "element alignment",
+        4,
+        ::std::mem::align_of::<::std::primitive::u32>(),
+    );
+};
+impl ::std::default::Default for Values_Sized {
+    #[inline]
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+unsafe impl ::shatter::__abi::BufferData for Values {
+    type State = (usize, usize);
+    type Contents = ::std::vec::Vec<::std::primitive::u32>;
+    const NEEDS_DROP: bool = false;
+    fn init() -> Self::State {
+        let cap = if ::std::mem::size_of::<::std::primitive::u32>() == 0 {
+            !0
+        } else {
+            0
+        };
+        (0, cap)
+    }
+    fn size(&(length, _capacity): &Self::State) -> usize {
+        ::std::mem::size_of::<Values_Sized>()
+            + length * ::std::mem::size_of::<::std::primitive::u32>()
+    }
+    unsafe fn alloc() -> ::std::ptr::NonNull<u8> {
+        let (_, capacity) = Self::init();
+        let layout =
+            ::shatter::__abi::buffer_vec_layout::<Values_Sized, ::std::primitive::u32>(capacity);
+        if layout.size() == 0 {
+            return ::std::ptr::NonNull::<Values_Sized>::dangling().cast();
+        }
+        let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+        let ptr = match ::std::ptr::NonNull::new(ptr) {
+            ::std::option::Option::Some(ptr) => ptr,
+            ::std::option::Option::None => ::std::alloc::handle_alloc_error(layout),
+        };
+        ::shatter::__abi::record_alloc::<Values>(ptr, layout);
+        ptr
+    }
+    unsafe fn alloc_zeroed(size: usize) -> (::std::ptr::NonNull<u8>, Self::State) {
+        if ::std::mem::size_of::<::std::primitive::u32>() == 0 {
+            return (unsafe { Self::alloc() }, Self::init());
+        }
+        let length = size.saturating_sub(::std::mem::size_of::<Values_Sized>())
+            / ::std::mem::size_of::<::std::primitive::u32>();
+        let layout =
+            ::shatter::__abi::buffer_vec_layout::<Values_Sized, ::std::primitive::u32>(length);
+        if layout.size() == 0 {
+            return (
+                ::std::ptr::NonNull::<Values_Sized>::dangling().cast(),
+                (0, 0),
+            );
+        }
+        let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+        let ptr = match ::std::ptr::NonNull::new(ptr) {
+            Some(ptr) => ptr,
+            None => ::std::alloc::handle_alloc_error(layout),
+        };
+        ::shatter::__abi::record_alloc::<Values>(ptr, layout);
+        (ptr, (length, length))
+    }
+    unsafe fn dealloc(ptr: ::std::ptr::NonNull<u8>, &(_length, capacity): &Self::State) {
+        let layout =
+            ::shatter::__abi::buffer_vec_layout::<Values_Sized, ::std::primitive::u32>(capacity);
+        if layout.size() == 0 {
+            return;
+        }
+        ::shatter::__abi::record_dealloc::<Values>(ptr, layout);
+        unsafe { ::std::alloc::dealloc(ptr.as_ptr(), layout) };
+    }
+    unsafe fn as_ptr(
+        ptr: ::std::ptr::NonNull<u8>,
+        &(length, _capacity): &Self::State,
+    ) -> *mut Self {
+        let slice = unsafe { ::std::slice::from_raw_parts_mut(ptr.as_ptr(), length) };
+        unsafe { ::std::mem::transmute(slice as *mut [u8]) }
+    }
+    unsafe fn contents(
+        ptr: ::std::ptr::NonNull<u8>,
+        &(length, _capacity): &Self::State,
+    ) -> Self::Contents {
+        let layout = ::std::alloc::Layout::new::<Values_Sized>();
+        let items = unsafe { ptr.as_ptr().add(layout.size()) as *const ::std::primitive::u32 };
+        (0..length)
+            .map(|i| unsafe { ::std::ptr::read(items.add(i)) })
+            .collect()
+    }
+}
+unsafe impl ::shatter::__abi::BufferVec for Values {
+    type Head = ();
+    type Item = ::std::primitive::u32;
+    const ITEM_STRIDE: usize = 4;
+    const ITEM_ALIGN: usize = 4;
+    fn len(&(length, _): &Self::State) -> usize {
+        length
+    }
+    fn capacity(&(_, capacity): &Self::State) -> usize {
+        capacity
+    }
+    unsafe fn grow(ptr: &mut ::std::ptr::NonNull<u8>, state: &mut Self::State) {
+        let additional = state.1 - state.0 + 1;
+        unsafe { Self::reserve(ptr, state, additional) };
+    }
+    unsafe fn reserve(
+        ptr: &mut ::std::ptr::NonNull<u8>,
+        &mut (length, ref mut capacity): &mut Self::State,
+        additional: usize,
+    ) {
+        let required = length.checked_add(additional).expect("capacity overflow");
+        if required <= *capacity {
+            return;
+        }
+        assert!(
+            ::std::mem::size_of::<Self::Item>() != 0,
+            "capacity overflow"
+        );
+        let new_cap = required.max(capacity.saturating_mul(2));
+        let old_layout =
+            ::shatter::__abi::buffer_vec_layout::<Values_Sized, ::std::primitive::u32>(*capacity);
+        let new_layout =
+            ::shatter::__abi::buffer_vec_layout::<Values_Sized, ::std::primitive::u32>(new_cap);
+        ::shatter::__abi::fault_point(::shatter::__abi::FaultPoint::Grow);
+        let new_ptr = if old_layout.size() == 0 {
+            unsafe { ::std::alloc::alloc(new_layout) }
+        } else {
+            unsafe { ::std::alloc::realloc(ptr.as_ptr(), old_layout, new_layout.size()) }
+        };
+        let new_ptr = match ::std::ptr::NonNull::new(new_ptr) {
+            Some(ptr) => ptr,
+            None => ::std::alloc::handle_alloc_error(new_layout),
+        };
+        let old_ptr = ::std::mem::replace(ptr, new_ptr);
+        *capacity = new_cap;
+        ::shatter::__abi::record_realloc::<Values>(old_ptr, old_layout, new_ptr, new_layout);
+    }
+    unsafe fn push(ptr: &mut ::std::ptr::NonNull<u8>, state: &mut Self::State, item: Self::Item) {
+        if state.0 == state.1 {
+            unsafe { Self::grow(ptr, state) };
+        }
+        ::shatter::__abi::fault_point(::shatter::__abi::FaultPoint::Push);
+        unsafe { ::std::ptr::write(Self::items(*ptr, state).add(state.0), item) };
+        state.0 += 1;
+    }
+    unsafe fn pop(
+        ptr: ::std::ptr::NonNull<u8>,
+        state: &mut Self::State,
+    ) -> ::std::option::Option<Self::Item> {
+        if state.0 == 0 {
+            None
+        } else {
+            state.0 -= 1;
+            unsafe { Some(::std::ptr::read(Self::items(ptr, state).add(state.0))) }
+        }
+    }
+    unsafe fn set_len((length, _capacity): &mut Self::State, len: usize) {
+        *length = len;
+    }
+    unsafe fn items(ptr: ::std::ptr::NonNull<u8>, _state: &Self::State) -> *mut Self::Item {
+        unsafe { ptr.as_ptr().add(::std::mem::size_of::<Values_Sized>()) as *mut Self::Item }
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod fill_1 {
+    use super::*;
+    pub const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize =
+        ::shatter::__abi::WorkGroupSize::new(
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+        );
+    #[doc = r" Invocations per work group, the product of [`WORK_GROUP_SIZE`]."]
+    pub const WORK_GROUP_INVOCATIONS: ::std::primitive::u32 = 1u32;
+    #[doc = r" Dispatch covering `elements` invocations along x, see"]
+    #[doc = r" [`Dispatch::try_linear`](::shatter::__abi::Dispatch::try_linear)."]
+    pub fn checked_dispatch(
+        elements: ::std::primitive::u64,
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_linear(elements, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Dispatch covering `extent` invocations along each axis, see"]
+    #[doc = r" [`Dispatch::try_for_extent`](::shatter::__abi::Dispatch::try_for_extent)."]
+    pub fn checked_dispatch_for_extent(
+        extent: [::std::primitive::u64; 3],
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_for_extent(extent, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Like [`checked_dispatch`], panics if the dispatch is too large."]
+    pub fn dispatch_for(elements: ::std::primitive::u64) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch(elements) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "fill", err),
+        }
+    }
+    #[doc = r" Like [`checked_dispatch_for_extent`], panics if the dispatch is too large."]
+    pub fn dispatch_for_extent(extent: [::std::primitive::u64; 3]) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch_for_extent(extent) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "fill", err),
+        }
+    }
+    pub use super::fill::{Bindings, OwnedBindings};
+    #[doc = r" Checks that `bindings` are compatible with the shader without dispatching."]
+    pub fn validate(
+        bindings: &Bindings<'_>,
+    ) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+        ::shatter::__abi::Bindings::validate(bindings)
+    }
+    pub struct Shader;
+    impl<'a> ::shatter::__abi::ComputeShader<'a> for Shader {
+        type Bindings = Bindings<'a>;
+        const SOURCE : & 'static :: std :: primitive :: str = "[[block ]]struct Values {values : array < u32 >; }; [[group (0 ), binding (0 )]]var < storage , read_write > values : Values ; [[stage (compute ), workgroup_size (1 )]]fn fill ([[builtin (global_invocation_id )]]id : vec3 < u32 > ){values . values [id . x ]= u32 (1 ); }[[stage (compute ), workgroup_size (1 )]]fn noop (){}" ;
+        const ENTRY_POINT: &'static ::std::primitive::str = "fill";
+        const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = WORK_GROUP_SIZE;
+        type PushConstants = ::shatter::__abi::NoPushConstants;
+    }
+    impl<'a> ::shatter::__abi::DispatchableShader<Bindings<'a>> for Shader {
+        fn entry_point(&self) -> &'static ::std::primitive::str {
+            "fill"
+        }
+        fn work_group_size(&self) -> ::shatter::__abi::WorkGroupSize {
+            WORK_GROUP_SIZE
+        }
+        fn dispatch(&self, bindings: Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+            ::shatter::__abi::ComputeShaderBuilder::<'a, Shader>::new(bindings)
+                .tag("fill")
+                .dispatch(dispatch);
+        }
+    }
+    pub fn build<'a>(
+        mut bindings: fill_1::Bindings<'a>,
+    ) -> ::shatter::__abi::ComputeShaderBuilder<'a, Shader> {
+        ::shatter::__abi::ComputeShaderBuilder::new(bindings)
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub fn fill_1<'a>(mut bindings: fill_1::Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+    fill_1::build(bindings).tag("fill").dispatch(dispatch);
+}
+#[doc = r" Like the blocking function without waiting on the GPU, resolves once the"]
+#[doc = r" dispatch is done and what it wrote is downloaded. Takes the resources"]
+#[doc = r" by value so the future is `Send`, they are handed back when it resolves."]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub async fn fill_1_async<B0>(
+    mut bindings: fill_1::OwnedBindings<B0>,
+    dispatch: ::shatter::__abi::Dispatch,
+) -> fill_1::OwnedBindings<B0>
+where
+    B0: ::shatter::__abi::BindingMut<Values> + ::std::marker::Send,
+{
+    let work = fill_1::build(bindings.as_bindings())
+        .tag("fill")
+        .dispatch_async(dispatch);
+    work.await;
+    if let ::std::result::Result::Err(err) = bindings.download_async().await {
+        ::std::panic!("{}", err);
+    }
+    bindings
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod noop_1 {
+    use super::*;
+    pub const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize =
+        ::shatter::__abi::WorkGroupSize::new(
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+        );
+    #[doc = r" Invocations per work group, the product of [`WORK_GROUP_SIZE`]."]
+    pub const WORK_GROUP_INVOCATIONS: ::std::primitive::u32 = 1u32;
+    #[doc = r" Dispatch covering `elements` invocations along x, see"]
+    #[doc = r" [`Dispatch::try_linear`](::shatter::__abi::Dispatch::try_linear)."]
+    pub fn checked_dispatch(
+        elements: ::std::primitive::u64,
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_linear(elements, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Dispatch covering `extent` invocations along each axis, see"]
+    #[doc = r" [`Dispatch::try_for_extent`](::shatter::__abi::Dispatch::try_for_extent)."]
+    pub fn checked_dispatch_for_extent(
+        extent: [::std::primitive::u64; 3],
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_for_extent(extent, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Like [`checked_dispatch`], panics if the dispatch is too large."]
+    pub fn dispatch_for(elements: ::std::primitive::u64) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch(elements) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "noop", err),
+        }
+    }
+    #[doc = r" Like [`checked_dispatch_for_extent`], panics if the dispatch is too large."]
+    pub fn dispatch_for_extent(extent: [::std::primitive::u64; 3]) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch_for_extent(extent) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "noop", err),
+        }
+    }
+    pub struct Shader;
+    impl<'a> ::shatter::__abi::ComputeShader<'a> for Shader {
+        type Bindings = ();
+        const SOURCE : & 'static :: std :: primitive :: str = "[[block ]]struct Values {values : array < u32 >; }; [[group (0 ), binding (0 )]]var < storage , read_write > values : Values ; [[stage (compute ), workgroup_size (1 )]]fn fill ([[builtin (global_invocation_id )]]id : vec3 < u32 > ){values . values [id . x ]= u32 (1 ); }[[stage (compute ), workgroup_size (1 )]]fn noop (){}" ;
+        const ENTRY_POINT: &'static ::std::primitive::str = "noop";
+        const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = WORK_GROUP_SIZE;
+        type PushConstants = ::shatter::__abi::NoPushConstants;
+    }
+    impl<'a> ::shatter::__abi::DispatchableShader<()> for Shader {
+        fn entry_point(&self) -> &'static ::std::primitive::str {
+            "noop"
+        }
+        fn work_group_size(&self) -> ::shatter::__abi::WorkGroupSize {
+            WORK_GROUP_SIZE
+        }
+        fn dispatch(&self, bindings: (), dispatch: ::shatter::__abi::Dispatch) {
+            ::shatter::__abi::ComputeShaderBuilder::<'a, Shader>::new(bindings)
+                .tag("noop")
+                .dispatch(dispatch);
+        }
+    }
+    pub fn build<'a>() -> ::shatter::__abi::ComputeShaderBuilder<'a, Shader> {
+        ::shatter::__abi::ComputeShaderBuilder::new(())
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub fn noop_1<'a>(dispatch: ::shatter::__abi::Dispatch) {
+    noop_1::build().tag("noop").dispatch(dispatch);
+}
+#[doc = r" Like the blocking function without waiting on the GPU, resolves once the"]
+#[doc = r" dispatch is done."]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub async fn noop_1_async(dispatch: ::shatter::__abi::Dispatch) {
+    let work = noop_1::build().tag("noop").dispatch_async(dispatch);
+    work.await;
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod fill_2 {
+    use super::*;
+    pub const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize =
+        ::shatter::__abi::WorkGroupSize::new(
+            2u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+        );
+    #[doc = r" Invocations per work group, the product of [`WORK_GROUP_SIZE`]."]
+    pub const WORK_GROUP_INVOCATIONS: ::std::primitive::u32 = 2u32;
+    #[doc = r" Dispatch covering `elements` invocations along x, see"]
+    #[doc = r" [`Dispatch::try_linear`](::shatter::__abi::Dispatch::try_linear)."]
+    pub fn checked_dispatch(
+        elements: ::std::primitive::u64,
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_linear(elements, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Dispatch covering `extent` invocations along each axis, see"]
+    #[doc = r" [`Dispatch::try_for_extent`](::shatter::__abi::Dispatch::try_for_extent)."]
+    pub fn checked_dispatch_for_extent(
+        extent: [::std::primitive::u64; 3],
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_for_extent(extent, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Like [`checked_dispatch`], panics if the dispatch is too large."]
+    pub fn dispatch_for(elements: ::std::primitive::u64) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch(elements) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "fill", err),
+        }
+    }
+    #[doc = r" Like [`checked_dispatch_for_extent`], panics if the dispatch is too large."]
+    pub fn dispatch_for_extent(extent: [::std::primitive::u64; 3]) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch_for_extent(extent) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "fill", err),
+        }
+    }
+    pub use super::fill::{Bindings, OwnedBindings};
+    #[doc = r" Checks that `bindings` are compatible with the shader without dispatching."]
+    pub fn validate(
+        bindings: &Bindings<'_>,
+    ) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+        ::shatter::__abi::Bindings::validate(bindings)
+    }
+    pub struct Shader;
+    impl<'a> ::shatter::__abi::ComputeShader<'a> for Shader {
+        type Bindings = Bindings<'a>;
+        const SOURCE : & 'static :: std :: primitive :: str = "[[block ]]struct Values {values : array < u32 >; }; [[group (0 ), binding (0 )]]var < storage , read_write > values : Values ; [[stage (compute ), workgroup_size (2 )]]fn fill ([[builtin (global_invocation_id )]]id : vec3 < u32 > ){values . values [id . x ]= u32 (2 ); }[[stage (compute ), workgroup_size (2 )]]fn noop (){}" ;
+        const ENTRY_POINT: &'static ::std::primitive::str = "fill";
+        const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = WORK_GROUP_SIZE;
+        type PushConstants = ::shatter::__abi::NoPushConstants;
+    }
+    impl<'a> ::shatter::__abi::DispatchableShader<Bindings<'a>> for Shader {
+        fn entry_point(&self) -> &'static ::std::primitive::str {
+            "fill"
+        }
+        fn work_group_size(&self) -> ::shatter::__abi::WorkGroupSize {
+            WORK_GROUP_SIZE
+        }
+        fn dispatch(&self, bindings: Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+            ::shatter::__abi::ComputeShaderBuilder::<'a, Shader>::new(bindings)
+                .tag("fill")
+                .dispatch(dispatch);
+        }
+    }
+    pub fn build<'a>(
+        mut bindings: fill_2::Bindings<'a>,
+    ) -> ::shatter::__abi::ComputeShaderBuilder<'a, Shader> {
+        ::shatter::__abi::ComputeShaderBuilder::new(bindings)
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub fn fill_2<'a>(mut bindings: fill_2::Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+    fill_2::build(bindings).tag("fill").dispatch(dispatch);
+}
+#[doc = r" Like the blocking function without waiting on the GPU, resolves once the"]
+#[doc = r" dispatch is done and what it wrote is downloaded. Takes the resources"]
+#[doc = r" by value so the future is `Send`, they are handed back when it resolves."]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub async fn fill_2_async<B0>(
+    mut bindings: fill_2::OwnedBindings<B0>,
+    dispatch: ::shatter::__abi::Dispatch,
+) -> fill_2::OwnedBindings<B0>
+where
+    B0: ::shatter::__abi::BindingMut<Values> + ::std::marker::Send,
+{
+    let work = fill_2::build(bindings.as_bindings())
+        .tag("fill")
+        .dispatch_async(dispatch);
+    work.await;
+    if let ::std::result::Result::Err(err) = bindings.download_async().await {
+        ::std::panic!("{}", err);
+    }
+    bindings
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod noop_2 {
+    use super::*;
+    pub const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize =
+        ::shatter::__abi::WorkGroupSize::new(
+            2u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+        );
+    #[doc = r" Invocations per work group, the product of [`WORK_GROUP_SIZE`]."]
+    pub const WORK_GROUP_INVOCATIONS: ::std::primitive::u32 = 2u32;
+    #[doc = r" Dispatch covering `elements` invocations along x, see"]
+    #[doc = r" [`Dispatch::try_linear`](::shatter::__abi::Dispatch::try_linear)."]
+    pub fn checked_dispatch(
+        elements: ::std::primitive::u64,
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_linear(elements, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Dispatch covering `extent` invocations along each axis, see"]
+    #[doc = r" [`Dispatch::try_for_extent`](::shatter::__abi::Dispatch::try_for_extent)."]
+    pub fn checked_dispatch_for_extent(
+        extent: [::std::primitive::u64; 3],
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_for_extent(extent, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Like [`checked_dispatch`], panics if the dispatch is too large."]
+    pub fn dispatch_for(elements: ::std::primitive::u64) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch(elements) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "noop", err),
+        }
+    }
+    #[doc = r" Like [`checked_dispatch_for_extent`], panics if the dispatch is too large."]
+    pub fn dispatch_for_extent(extent: [::std::primitive::u64; 3]) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch_for_extent(extent) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "noop", err),
+        }
+    }
+    pub struct Shader;
+    impl<'a> ::shatter::__abi::ComputeShader<'a> for Shader {
+        type Bindings = ();
+        const SOURCE : & 'static :: std :: primitive :: str = "[[block ]]struct Values {values : array < u32 >; }; [[group (0 ), binding (0 )]]var < storage , read_write > values : Values ; [[stage (compute ), workgroup_size (2 )]]fn fill ([[builtin (global_invocation_id )]]id : vec3 < u32 > ){values . values [id . x ]= u32 (2 ); }[[stage (compute ), workgroup_size (2 )]]fn noop (){}" ;
+        const ENTRY_POINT: &'static ::std::primitive::str = "noop";
+        const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = WORK_GROUP_SIZE;
+        type PushConstants = ::shatter::__abi::NoPushConstants;
+    }
+    impl<'a> ::shatter::__abi::DispatchableShader<()> for Shader {
+        fn entry_point(&self) -> &'static ::std::primitive::str {
+            "noop"
+        }
+        fn work_group_size(&self) -> ::shatter::__abi::WorkGroupSize {
+            WORK_GROUP_SIZE
+        }
+        fn dispatch(&self, bindings: (), dispatch: ::shatter::__abi::Dispatch) {
+            ::shatter::__abi::ComputeShaderBuilder::<'a, Shader>::new(bindings)
+                .tag("noop")
+                .dispatch(dispatch);
+        }
+    }
+    pub fn build<'a>() -> ::shatter::__abi::ComputeShaderBuilder<'a, Shader> {
+        ::shatter::__abi::ComputeShaderBuilder::new(())
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub fn noop_2<'a>(dispatch: ::shatter::__abi::Dispatch) {
+    noop_2::build().tag("noop").dispatch(dispatch);
+}
+#[doc = r" Like the blocking function without waiting on the GPU, resolves once the"]
+#[doc = r" dispatch is done."]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub async fn noop_2_async(dispatch: ::shatter::__abi::Dispatch) {
+    let work = noop_2::build().tag("noop").dispatch_async(dispatch);
+    work.await;
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod fill_4 {
+    use super::*;
+    pub const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize =
+        ::shatter::__abi::WorkGroupSize::new(
+            4u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+        );
+    #[doc = r" Invocations per work group, the product of [`WORK_GROUP_SIZE`]."]
+    pub const WORK_GROUP_INVOCATIONS: ::std::primitive::u32 = 4u32;
+    #[doc = r" Dispatch covering `elements` invocations along x, see"]
+    #[doc = r" [`Dispatch::try_linear`](::shatter::__abi::Dispatch::try_linear)."]
+    pub fn checked_dispatch(
+        elements: ::std::primitive::u64,
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_linear(elements, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Dispatch covering `extent` invocations along each axis, see"]
+    #[doc = r" [`Dispatch::try_for_extent`](::shatter::__abi::Dispatch::try_for_extent)."]
+    pub fn checked_dispatch_for_extent(
+        extent: [::std::primitive::u64; 3],
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_for_extent(extent, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Like [`checked_dispatch`], panics if the dispatch is too large."]
+    pub fn dispatch_for(elements: ::std::primitive::u64) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch(elements) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "fill", err),
+        }
+    }
+    #[doc = r" Like [`checked_dispatch_for_extent`], panics if the dispatch is too large."]
+    pub fn dispatch_for_extent(extent: [::std::primitive::u64; 3]) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch_for_extent(extent) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "fill", err),
+        }
+    }
+    pub use super::fill::{Bindings, OwnedBindings};
+    #[doc = r" Checks that `bindings` are compatible with the shader without dispatching."]
+    pub fn validate(
+        bindings: &Bindings<'_>,
+    ) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+        ::shatter::__abi::Bindings::validate(bindings)
+    }
+    pub struct Shader;
+    impl<'a> ::shatter::__abi::ComputeShader<'a> for Shader {
+        type Bindings = Bindings<'a>;
+        const SOURCE : & 'static :: std :: primitive :: str = "[[block ]]struct Values {values : array < u32 >; }; [[group (0 ), binding (0 )]]var < storage , read_write > values : Values ; [[stage (compute ), workgroup_size (4 )]]fn fill ([[builtin (global_invocation_id )]]id : vec3 < u32 > ){values . values [id . x ]= u32 (4 ); }[[stage (compute ), workgroup_size (4 )]]fn noop (){}" ;
+        const ENTRY_POINT: &'static ::std::primitive::str = "fill";
+        const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = WORK_GROUP_SIZE;
+        type PushConstants = ::shatter::__abi::NoPushConstants;
+    }
+    impl<'a> ::shatter::__abi::DispatchableShader<Bindings<'a>> for Shader {
+        fn entry_point(&self) -> &'static ::std::primitive::str {
+            "fill"
+        }
+        fn work_group_size(&self) -> ::shatter::__abi::WorkGroupSize {
+            WORK_GROUP_SIZE
+        }
+        fn dispatch(&self, bindings: Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+            ::shatter::__abi::ComputeShaderBuilder::<'a, Shader>::new(bindings)
+                .tag("fill")
+                .dispatch(dispatch);
+        }
+    }
+    pub fn build<'a>(
+        mut bindings: fill_4::Bindings<'a>,
+    ) -> ::shatter::__abi::ComputeShaderBuilder<'a, Shader> {
+        ::shatter::__abi::ComputeShaderBuilder::new(bindings)
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub fn fill_4<'a>(mut bindings: fill_4::Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+    fill_4::build(bindings).tag("fill").dispatch(dispatch);
+}
+#[doc = r" Like the blocking function without waiting on the GPU, resolves once the"]
+#[doc = r" dispatch is done and what it wrote is downloaded. Takes the resources"]
+#[doc = r" by value so the future is `Send`, they are handed back when it resolves."]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub async fn fill_4_async<B0>(
+    mut bindings: fill_4::OwnedBindings<B0>,
+    dispatch: ::shatter::__abi::Dispatch,
+) -> fill_4::OwnedBindings<B0>
+where
+    B0: ::shatter::__abi::BindingMut<Values> + ::std::marker::Send,
+{
+    let work = fill_4::build(bindings.as_bindings())
+        .tag("fill")
+        .dispatch_async(dispatch);
+    work.await;
+    if let ::std::result::Result::Err(err) = bindings.download_async().await {
+        ::std::panic!("{}", err);
+    }
+    bindings
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod noop_4 {
+    use super::*;
+    pub const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize =
+        ::shatter::__abi::WorkGroupSize::new(
+            4u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+        );
+    #[doc = r" Invocations per work group, the product of [`WORK_GROUP_SIZE`]."]
+    pub const WORK_GROUP_INVOCATIONS: ::std::primitive::u32 = 4u32;
+    #[doc = r" Dispatch covering `elements` invocations along x, see"]
+    #[doc = r" [`Dispatch::try_linear`](::shatter::__abi::Dispatch::try_linear)."]
+    pub fn checked_dispatch(
+        elements: ::std::primitive::u64,
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_linear(elements, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Dispatch covering `extent` invocations along each axis, see"]
+    #[doc = r" [`Dispatch::try_for_extent`](::shatter::__abi::Dispatch::try_for_extent)."]
+    pub fn checked_dispatch_for_extent(
+        extent: [::std::primitive::u64; 3],
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_for_extent(extent, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Like [`checked_dispatch`], panics if the dispatch is too large."]
+    pub fn dispatch_for(elements: ::std::primitive::u64) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch(elements) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "noop", err),
+        }
+    }
+    #[doc = r" Like [`checked_dispatch_for_extent`], panics if the dispatch is too large."]
+    pub fn dispatch_for_extent(extent: [::std::primitive::u64; 3]) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch_for_extent(extent) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "noop", err),
+        }
+    }
+    pub struct Shader;
+    impl<'a> ::shatter::__abi::ComputeShader<'a> for Shader {
+        type Bindings = ();
+        const SOURCE : & 'static :: std :: primitive :: str = "[[block ]]struct Values {values : array < u32 >; }; [[group (0 ), binding (0 )]]var < storage , read_write > values : Values ; [[stage (compute ), workgroup_size (4 )]]fn fill ([[builtin (global_invocation_id )]]id : vec3 < u32 > ){values . values [id . x ]= u32 (4 ); }[[stage (compute ), workgroup_size (4 )]]fn noop (){}" ;
+        const ENTRY_POINT: &'static ::std::primitive::str = "noop";
+        const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = WORK_GROUP_SIZE;
+        type PushConstants = ::shatter::__abi::NoPushConstants;
+    }
+    impl<'a> ::shatter::__abi::DispatchableShader<()> for Shader {
+        fn entry_point(&self) -> &'static ::std::primitive::str {
+            "noop"
+        }
+        fn work_group_size(&self) -> ::shatter::__abi::WorkGroupSize {
+            WORK_GROUP_SIZE
+        }
+        fn dispatch(&self, bindings: (), dispatch: ::shatter::__abi::Dispatch) {
+            ::shatter::__abi::ComputeShaderBuilder::<'a, Shader>::new(bindings)
+                .tag("noop")
+                .dispatch(dispatch);
+        }
+    }
+    pub fn build<'a>() -> ::shatter::__abi::ComputeShaderBuilder<'a, Shader> {
+        ::shatter::__abi::ComputeShaderBuilder::new(())
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub fn noop_4<'a>(dispatch: ::shatter::__abi::Dispatch) {
+    noop_4::build().tag("noop").dispatch(dispatch);
+}
+#[doc = r" Like the blocking function without waiting on the GPU, resolves once the"]
+#[doc = r" dispatch is done."]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub async fn noop_4_async(dispatch: ::shatter::__abi::Dispatch) {
+    let work = noop_4::build().tag("noop").dispatch_async(dispatch);
+    work.await;
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod fill {
+    use super::*;
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct Bindings<'a> {
+        pub values: &'a mut dyn ::shatter::__abi::BindingMut<Values>,
+    }
+    impl<'a> Bindings<'a> {
+        #[doc = r" Borrows every binding from `registry` by its WGSL name,"]
+        #[doc = r" reporting every missing or mismatched resource."]
+        pub fn from_registry(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            Self::from_registry_renamed(registry, &[])
+        }
+        #[doc = r" Like [`Self::from_registry`], `rename` maps WGSL names to"]
+        #[doc = r" the names resources are stored under."]
+        pub fn from_registry_renamed(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+            rename: &[(&::std::primitive::str, &::std::primitive::str)],
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            let mut borrows = registry.borrow_bindings(
+                &[::shatter::__abi::RegistryRequest::new::<Values>(
+                    "values", true, false,
+                )],
+                rename,
+            )?;
+            ::std::result::Result::Ok(Self {
+                values: borrows.take_mut::<Values>(0usize),
+            })
+        }
+        pub fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            ::std::vec![::shatter::__abi::BindGroupLayoutDescriptor {
+                entries: ::std::vec![::shatter::__abi::BindGroupLayoutEntry {
+                    binding: 0u32,
+                    visibility: ::shatter::__abi::ShaderStages::COMPUTE,
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: ::std::option::Option::None,
+                }],
+            }]
+        }
+        pub fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            let mut layouts = layouts.iter();
+            ::std::vec![::shatter::__abi::BindGroupDescriptor {
+                layout: layouts.next().unwrap().clone(),
+                entries: ::std::vec![::shatter::__abi::BindGroupEntry {
+                    binding: 0u32 as u32,
+                    resource: ::shatter::__abi::Binding::binding_resource(self.values),
+                }],
+            }]
+        }
+        pub fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            let mut layouts = layouts.iter();
+            let mut keys = ::shatter::__abi::GroupKeys::new();
+            keys.push({
+                let mut key = ::shatter::__abi::GroupKey::new(layouts.next().unwrap());
+                key.push(
+                    0u32 as u32,
+                    &::shatter::__abi::Binding::binding_resource(self.values),
+                );
+                key
+            });
+            keys
+        }
+    }
+    impl<'a> ::shatter::__abi::Bindings for Bindings<'a> {
+        #[inline]
+        fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            self.bind_group_layout_descriptors()
+        }
+        #[inline]
+        fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            self.bind_group_descriptors(layouts)
+        }
+        #[inline]
+        fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            self.bind_group_keys(layouts)
+        }
+        fn validate(&self) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+            let mut bindings = ::std::vec::Vec::new();
+            bindings.push((
+                ::shatter::__abi::BindingExpectation {
+                    name: "values",
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    min_size: 4u64,
+                    writable: true,
+                },
+                ::shatter::__abi::Binding::resource_info(self.values),
+            ));
+            ::shatter::__abi::validate_bindings(&bindings)
+        }
+        #[inline]
+        fn prepare(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::prepare(self.values, report);
+        }
+        #[inline]
+        fn read(&self, report: &mut ::shatter::__abi::TransferReport) {}
+        #[inline]
+        fn write(&mut self) {
+            ::shatter::__abi::BindingMut::write(self.values);
+        }
+        #[inline]
+        fn needs_allocation(&self) -> bool {
+            ::shatter::__abi::Binding::needs_allocation(self.values)
+        }
+    }
+    #[doc = r" Like [`Bindings`] owning the resources, taken by the `_async`"]
+    #[doc = r" dispatch so its future is `Send` and handed back once it's done."]
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct OwnedBindings<B0> {
+        pub values: B0,
+    }
+    impl<B0> OwnedBindings<B0>
+    where
+        B0: ::shatter::__abi::BindingMut<Values>,
+    {
+        #[doc = r" Borrows the resources as [`Bindings`]."]
+        pub fn as_bindings(&mut self) -> Bindings<'_> {
+            Bindings {
+                values: &mut self.values,
+            }
+        }
+        #[doc = r" Downloads what the GPU wrote to the writable resources"]
+        #[doc = r" without blocking, returns the number of bytes downloaded."]
+        pub async fn download_async(
+            &mut self,
+        ) -> ::std::result::Result<::std::primitive::u64, ::shatter::__abi::Error> {
+            ::std::result::Result::Ok(
+                0 + ::shatter::__abi::Binding::<Values>::download_async(&mut self.values).await?,
+            )
+        }
+    }
+    #[doc = "Values of `SIZE` the family was instantiated with."]
+    pub const SIZE: &[::std::primitive::u32] = &[1u32, 2u32, 4u32];
+    #[doc = r" Selects the variant instantiated with the given value."]
+    pub fn with_size<'a>(
+        size: ::std::primitive::u32,
+    ) -> ::std::option::Option<&'a dyn ::shatter::__abi::DispatchableShader<Bindings<'a>>> {
+        match size {
+            1u32 => ::std::option::Option::Some(&super::fill_1::Shader),
+            2u32 => ::std::option::Option::Some(&super::fill_2::Shader),
+            4u32 => ::std::option::Option::Some(&super::fill_4::Shader),
+            _ => ::std::option::Option::None,
+        }
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod noop {
+    use super::*;
+    #[doc = "Values of `SIZE` the family was instantiated with."]
+    pub const SIZE: &[::std::primitive::u32] = &[1u32, 2u32, 4u32];
+    #[doc = r" Selects the variant instantiated with the given value."]
+    pub fn with_size<'a>(
+        size: ::std::primitive::u32,
+    ) -> ::std::option::Option<&'a dyn ::shatter::__abi::DispatchableShader<()>> {
+        match size {
+            1u32 => ::std::option::Option::Some(&super::noop_1::Shader),
+            2u32 => ::std::option::Option::Some(&super::noop_2::Shader),
+            4u32 => ::std::option::Option::Some(&super::noop_4::Shader),
+            _ => ::std::option::Option::None,
+        }
+    }
+}
@@ -0,0 +1,435 @@
+const _: () = ::std::assert!(
+    ::shatter::ABI_VERSION == 32u32,
+    "shatter-macro 0.0.1 requires shatter ABI 32, update the shatter crate"
+);
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub const SCALE: ::std::primitive::f32 = 4f64 as f32;
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub const AA: ::std::primitive::i32 = 3i64 as i32;
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub struct Uniforms {
+    pub position: ::shatter::__abi::Vec2<::std::primitive::f32>,
+    pub zoom: ::std::primitive::f32,
+}
+const _: () = {
+    ::shatter::__abi::assert_layout(
+        "Uniforms::position",
+        "offset",
+        0,
+        ::std::mem::offset_of!(Uniforms, position),
+    );
+    ::shatter::__abi::assert_layout(
+        "Uniforms::zoom",
+        "offset",
+        8,
+        ::std::mem::offset_of!(Uniforms, zoom),
+    );
+    ::shatter::__abi::assert_layout("Uniforms", "size", 16, ::std::mem::size_of::<Uniforms>());
+};
+impl ::std::default::Default for Uniforms {
+    #[inline]
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+unsafe impl ::shatter::__abi::BufferData for Uniforms {
+    type State = ();
+    type Contents = Self;
+    const NEEDS_DROP: bool = false;
+    fn init() -> Self::State {}
+    fn size(_: &Self::State) -> usize {
+        ::std::mem::size_of::<Uniforms>()
+    }
+    unsafe fn alloc() -> ::std::ptr::NonNull<u8> {
+        if ::std::mem::size_of::<Uniforms>() == 0 {
+            return ::std::ptr::NonNull::<Uniforms>::dangling().cast();
+        }
+        let layout = ::std::alloc::Layout::new::<Uniforms>();
+        let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+        let ptr = match ::std::ptr::NonNull::new(ptr) {
+            ::std::option::Option::Some(ptr) => ptr,
+            ::std::option::Option::None => ::std::alloc::handle_alloc_error(layout),
+        };
+        ::shatter::__abi::record_alloc::<Uniforms>(ptr, layout);
+        ptr
+    }
+    unsafe fn alloc_zeroed(_: usize) -> (::std::ptr::NonNull<u8>, Self::State) {
+        if ::std::mem::size_of::<Uniforms>() == 0 {
+            return (::std::ptr::NonNull::<Uniforms>::dangling().cast(), ());
+        }
+        let layout = ::std::alloc::Layout::new::<Uniforms>();
+        let ptr = ::std::ptr::NonNull::new(unsafe { ::std::alloc::alloc_zeroed(layout) }).unwrap();
+        ::shatter::__abi::record_alloc::<Uniforms>(ptr, layout);
+        (ptr, ())
+    }
+    unsafe fn dealloc(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) {
+        let layout = ::std::alloc::Layout::new::<Uniforms>();
+        if layout.size() == 0 {
+            return;
+        }
+        ::shatter::__abi::record_dealloc::<Uniforms>(ptr, layout);
+        unsafe { ::std::alloc::dealloc(ptr.as_ptr(), layout) };
+    }
+    unsafe fn as_ptr(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) -> *mut Self {
+        ptr.as_ptr() as *mut Self
+    }
+    unsafe fn contents(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) -> Self {
+        unsafe { ::std::ptr::read(ptr.as_ptr() as *const Self) }
+    }
+}
+unsafe impl ::shatter::__abi::Reflect for Uniforms {
+    const LAYOUT: ::shatter::__abi::StructLayout = ::shatter::__abi::StructLayout {
+        name: ::std::borrow::Cow::Borrowed("Uniforms"),
+        size: ::std::mem::size_of::<Uniforms>() as u64,
+        fields: ::std::borrow::Cow::Borrowed(&[
+            ::shatter::__abi::FieldLayout {
+                name: ::std::borrow::Cow::Borrowed("position"),
+                ty: ::std::borrow::Cow::Borrowed("vec2<f32>"),
+                offset: ::std::mem::offset_of!(Uniforms, position) as u64,
+                size: ::std::mem::size_of::<::shatter::__abi::Vec2<::std::primitive::f32>>() as u64,
+            },
+            ::shatter::__abi::FieldLayout {
+                name: ::std::borrow::Cow::Borrowed("zoom"),
+                ty: ::std::borrow::Cow::Borrowed("f32"),
+                offset: ::std::mem::offset_of!(Uniforms, zoom) as u64,
+                size: ::std::mem::size_of::<::std::primitive::f32>() as u64,
+            },
+        ]),
+    };
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod mandelbrot {
+    use super::*;
+    pub const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize =
+        ::shatter::__abi::WorkGroupSize::new(
+            8u32 as ::std::primitive::u32,
+            8u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+        );
+    #[doc = r" Invocations per work group, the product of [`WORK_GROUP_SIZE`]."]
+    pub const WORK_GROUP_INVOCATIONS: ::std::primitive::u32 = 64u32;
+    #[doc = r" Dispatch covering `elements` invocations along x, see"]
+    #[doc = r" [`Dispatch::try_linear`](::shatter::__abi::Dispatch::try_linear)."]
+    pub fn checked_dispatch(
+        elements: ::std::primitive::u64,
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_linear(elements, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Dispatch covering `extent` invocations along each axis, see"]
+    #[doc = r" [`Dispatch::try_for_extent`](::shatter::__abi::Dispatch::try_for_extent)."]
+    pub fn checked_dispatch_for_extent(
+        extent: [::std::primitive::u64; 3],
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_for_extent(extent, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Like [`checked_dispatch`], panics if the dispatch is too large."]
+    pub fn dispatch_for(elements: ::std::primitive::u64) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch(elements) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "mandelbrot", err),
+        }
+    }
+    #[doc = r" Like [`checked_dispatch_for_extent`], panics if the dispatch is too large."]
+    pub fn dispatch_for_extent(extent: [::std::primitive::u64; 3]) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch_for_extent(extent) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "mandelbrot", err),
+        }
+    }
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct Bindings<'a> {
+        pub texture: &'a mut dyn ::shatter::__abi::BindingMut<
+            ::shatter::__abi::StorageTextureBinding<
+                ::shatter::__abi::texel_format::Rgba8Unorm,
+                ::shatter::__abi::texture_view_dimension::D2,
+            >,
+        >,
+        pub uniforms: &'a dyn ::shatter::__abi::Binding<Uniforms>,
+    }
+    impl<'a> Bindings<'a> {
+        #[doc = r" Borrows every binding from `registry` by its WGSL name,"]
+        #[doc = r" reporting every missing or mismatched resource."]
+        pub fn from_registry(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            Self::from_registry_renamed(registry, &[])
+        }
+        #[doc = r" Like [`Self::from_registry`], `rename` maps WGSL names to"]
+        #[doc = r" the names resources are stored under."]
+        pub fn from_registry_renamed(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+            rename: &[(&::std::primitive::str, &::std::primitive::str)],
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            let mut borrows = registry.borrow_bindings(
+                &[
+                    ::shatter::__abi::RegistryRequest::new::<
+                        ::shatter::__abi::StorageTextureBinding<
+                            ::shatter::__abi::texel_format::Rgba8Unorm,
+                            ::shatter::__abi::texture_view_dimension::D2,
+                        >,
+                    >("texture", true, false),
+                    ::shatter::__abi::RegistryRequest::new::<Uniforms>("uniforms", false, false),
+                ],
+                rename,
+            )?;
+            ::std::result::Result::Ok(Self {
+                texture: borrows.take_mut::<::shatter::__abi::StorageTextureBinding<
+                    ::shatter::__abi::texel_format::Rgba8Unorm,
+                    ::shatter::__abi::texture_view_dimension::D2,
+                >>(0usize),
+                uniforms: borrows.take::<Uniforms>(1usize),
+            })
+        }
+        pub fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            ::std::vec![::shatter::__abi::BindGroupLayoutDescriptor {
+                entries: ::std::vec![
+                    ::shatter::__abi::BindGroupLayoutEntry {
+                        binding: 0u32,
+                        visibility: ::shatter::__abi::ShaderStages::COMPUTE,
+                        ty: ::shatter::__abi::BindingType::StorageTexture {
+                            access: ::shatter::__abi::wgpu::StorageTextureAccess::WriteOnly,
+                            format: ::shatter::__abi::wgpu::TextureFormat::Rgba8Unorm,
+                            view_dimension: ::shatter::__abi::wgpu::TextureViewDimension::D2,
+                        },
+                        count: ::std::option::Option::None,
+                    },
+                    ::shatter::__abi::BindGroupLayoutEntry {
+                        binding: 1u32,
+                        visibility: ::shatter::__abi::ShaderStages::COMPUTE,
+                        ty: ::shatter::__abi::BindingType::Buffer {
+                            ty: ::shatter::__abi::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: ::std::option::Option::None,
+                    }
+                ],
+            }]
+        }
+        pub fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            let mut layouts = layouts.iter();
+            ::std::vec![::shatter::__abi::BindGroupDescriptor {
+                layout: layouts.next().unwrap().clone(),
+                entries: ::std::vec![
+                    ::shatter::__abi::BindGroupEntry {
+                        binding: 0u32 as u32,
+                        resource: ::shatter::__abi::Binding::binding_resource(self.texture),
+                    },
+                    ::shatter::__abi::BindGroupEntry {
+                        binding: 1u32 as u32,
+                        resource: ::shatter::__abi::Binding::binding_resource(self.uniforms),
+                    }
+                ],
+            }]
+        }
+        pub fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            let mut layouts = layouts.iter();
+            let mut keys = ::shatter::__abi::GroupKeys::new();
+            keys.push({
+                let mut key = ::shatter::__abi::GroupKey::new(layouts.next().unwrap());
+                key.push(
+                    0u32 as u32,
+                    &::shatter::__abi::Binding::binding_resource(self.texture),
+                );
+                key.push(
+                    1u32 as u32,
+                    &::shatter::__abi::Binding::binding_resource(self.uniforms),
+                );
+                key
+            });
+            keys
+        }
+    }
+    impl<'a> ::shatter::__abi::Bindings for Bindings<'a> {
+        #[inline]
+        fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            self.bind_group_layout_descriptors()
+        }
+        #[inline]
+        fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            self.bind_group_descriptors(layouts)
+        }
+        #[inline]
+        fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            self.bind_group_keys(layouts)
+        }
+        fn validate(&self) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+            let mut bindings = ::std::vec::Vec::new();
+            bindings.push((
+                ::shatter::__abi::BindingExpectation {
+                    name: "texture",
+                    ty: ::shatter::__abi::BindingType::StorageTexture {
+                        access: ::shatter::__abi::wgpu::StorageTextureAccess::WriteOnly,
+                        format: ::shatter::__abi::wgpu::TextureFormat::Rgba8Unorm,
+                        view_dimension: ::shatter::__abi::wgpu::TextureViewDimension::D2,
+                    },
+                    min_size: 0u64,
+                    writable: true,
+                },
+                ::shatter::__abi::Binding::resource_info(self.texture),
+            ));
+            bindings.push((
+                ::shatter::__abi::BindingExpectation {
+                    name: "uniforms",
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    min_size: 16u64,
+                    writable: false,
+                },
+                ::shatter::__abi::Binding::resource_info(self.uniforms),
+            ));
+            ::shatter::__abi::validate_bindings(&bindings)
+        }
+        #[inline]
+        fn prepare(&self, report: &mut ::shatter::__abi::TransferReport) {
+            let mut seen = ::shatter::__abi::SeenResources::new();
+            if seen.first_use(::shatter::__abi::Binding::binding_resource(self.texture)) {
+                ::shatter::__abi::Binding::prepare(self.texture, report);
+            }
+            if seen.first_use(::shatter::__abi::Binding::binding_resource(self.uniforms)) {
+                ::shatter::__abi::Binding::prepare(self.uniforms, report);
+            }
+        }
+        #[inline]
+        fn read(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::read(self.uniforms, report);
+        }
+        #[inline]
+        fn write(&mut self) {
+            ::shatter::__abi::BindingMut::write(self.texture);
+        }
+        #[inline]
+        fn needs_allocation(&self) -> bool {
+            ::shatter::__abi::Binding::needs_allocation(self.texture)
+                || ::shatter::__abi::Binding::needs_allocation(self.uniforms)
+        }
+    }
+    #[doc = r" Like [`Bindings`] owning the resources, taken by the `_async`"]
+    #[doc = r" dispatch so its future is `Send` and handed back once it's done."]
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct OwnedBindings<B0, B1> {
+        pub texture: B0,
+        pub uniforms: B1,
+    }
+    impl<B0, B1> OwnedBindings<B0, B1>
+    where
+        B0: ::shatter::__abi::BindingMut<
+            ::shatter::__abi::StorageTextureBinding<
+                ::shatter::__abi::texel_format::Rgba8Unorm,
+                ::shatter::__abi::texture_view_dimension::D2,
+            >,
+        >,
+        B1: ::shatter::__abi::Binding<Uniforms>,
+    {
+        #[doc = r" Borrows the resources as [`Bindings`]."]
+        pub fn as_bindings(&mut self) -> Bindings<'_> {
+            Bindings {
+                texture: &mut self.texture,
+                uniforms: &self.uniforms,
+            }
+        }
+        #[doc = r" Downloads what the GPU wrote to the writable resources"]
+        #[doc = r" without blocking, returns the number of bytes downloaded."]
+        pub async fn download_async(
+            &mut self,
+        ) -> ::std::result::Result<::std::primitive::u64, ::shatter::__abi::Error> {
+            ::std::result::Result::Ok(
+                0 + ::shatter::__abi::Binding::<
+                    ::shatter::__abi::StorageTextureBinding<
+                        ::shatter::__abi::texel_format::Rgba8Unorm,
+                        ::shatter::__abi::texture_view_dimension::D2,
+                    >,
+                >::download_async(&mut self.texture)
+                .await?,
+            )
+        }
+    }
+    #[doc = r" Checks that `bindings` are compatible with the shader without dispatching."]
+    pub fn validate(
+        bindings: &Bindings<'_>,
+    ) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+        ::shatter::__abi::Bindings::validate(bindings)
+    }
+    pub struct Shader;
+    impl<'a> ::shatter::__abi::ComputeShader<'a> for Shader {
+        type Bindings = Bindings<'a>;
+        const SOURCE : & 'static :: std :: primitive :: str = "[[group (0 ), binding (0 )]]var texture : texture_storage_2d < rgba8unorm , write >; [[block ]]struct Uniforms {position : vec2 < f32 >; zoom : f32 ; }; [[group (0 ), binding (1 )]]var < uniform > uniforms : Uniforms ; let SCALE = 4.0 ; let AA = 3 ; [[stage (compute ), workgroup_size (8 , 8 , 1 )]]fn mandelbrot ([[builtin (global_invocation_id )]]param : vec3 < u32 > ){var color = vec3 < f32 > (0.0 ); for (var m = 0 ; m < AA ; m = m + 1 ){let x_offset = f32 (m )/ f32 (AA )- 0.5 ; for (var n = 0 ; n < AA ; n = n + 1 ){let y_offset = f32 (n )/ f32 (AA )- 0.5 ; let size = textureDimensions (texture ); var x = (f32 (param . x )+ x_offset )/ f32 (size . x )* SCALE - SCALE / 2.0 ; var y = (f32 (param . y )+ y_offset )/ f32 (size . y )* SCALE - SCALE / 2.0 ; x = x / uniforms . zoom - uniforms . position . x ; y = y / uniforms . zoom - uniforms . position . y ; var l = 0.0 ; var z = vec2 < f32 > (0.0 ); for (var i = 0 ; i < 512 ; i = i + 1 ){z = vec2 < f32 > (z . x * z . x - z . y * z . y + x , z . y * z . x + z . x * z . y + y , ); if (dot (z , z )> pow (256.0 , 2.0 )){break ; }l = l + 1.0 ; }if (l > 511.0 ){l = 0.0 ; }let smooth = l - log2 (log2 (dot (z , z )))+ 4.0 ; let sub_color = 0.5 + 0.5 * cos (3.0 + smooth * 0.15 + vec3 < f32 > (0.0 , 0.6 , 1.0 )); color = color + sub_color ; }}color = color / f32 (AA * AA ); let out_color = vec4 < f32 > (color , 1.0 ); textureStore (texture , vec2 < i32 > (param . xy ), out_color ); }" ;
+        const ENTRY_POINT: &'static ::std::primitive::str = "mandelbrot";
+        const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = WORK_GROUP_SIZE;
+        type PushConstants = ::shatter::__abi::NoPushConstants;
+    }
+    impl<'a> ::shatter::__abi::DispatchableShader<Bindings<'a>> for Shader {
+        fn entry_point(&self) -> &'static ::std::primitive::str {
+            "mandelbrot"
+        }
+        fn work_group_size(&self) -> ::shatter::__abi::WorkGroupSize {
+            WORK_GROUP_SIZE
+        }
+        fn dispatch(&self, bindings: Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+            ::shatter::__abi::ComputeShaderBuilder::<'a, Shader>::new(bindings)
+                .tag("mandelbrot")
+                .dispatch(dispatch);
+        }
+    }
+    pub fn build<'a>(
+        mut bindings: mandelbrot::Bindings<'a>,
+    ) -> ::shatter::__abi::ComputeShaderBuilder<'a, Shader> {
+        ::shatter::__abi::ComputeShaderBuilder::new(bindings)
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub fn mandelbrot<'a>(
+    mut bindings: mandelbrot::Bindings<'a>,
+    dispatch: ::shatter::__abi::Dispatch,
+) {
+    mandelbrot::build(bindings)
+        .tag("mandelbrot")
+        .dispatch(dispatch);
+}
+#[doc = r" Like the blocking function without waiting on the GPU, resolves once the"]
+#[doc = r" dispatch is done and what it wrote is downloaded. Takes the resources"]
+#[doc = r" by value so the future is `Send`, they are handed back when it resolves."]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub async fn mandelbrot_async<B0, B1>(
+    mut bindings: mandelbrot::OwnedBindings<B0, B1>,
+    dispatch: ::shatter::__abi::Dispatch,
+) -> mandelbrot::OwnedBindings<B0, B1>
+where
+    B0: ::shatter::__abi::BindingMut<
+            ::shatter::__abi::StorageTextureBinding<
+                ::shatter::__abi::texel_format::Rgba8Unorm,
+                ::shatter::__abi::texture_view_dimension::D2,
+            >,
+        > + ::std::marker::Send,
+    B1: ::shatter::__abi::Binding<Uniforms> + ::std::marker::Send,
+{
+    let work = mandelbrot::build(bindings.as_bindings())
+        .tag("mandelbrot")
+        .dispatch_async(dispatch);
+    work.await;
+    if let ::std::result::Result::Err(err) = bindings.download_async().await {
+        ::std::panic!("{}", err);
+    }
+    bindings
+}
@@ -0,0 +1,763 @@
+const _: () = ::std::assert!(
+    ::shatter::ABI_VERSION == 32u32,
+    "shatter-macro 0.0.1 requires shatter ABI 32, update the shatter crate"
+);
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub struct Input {
+    pub value: ::std::primitive::i32,
+}
+const _: () = {
+    ::shatter::__abi::assert_layout(
+        "Input::value",
+        "offset",
+        0,
+        ::std::mem::offset_of!(Input, value),
+    );
+    ::shatter::__abi::assert_layout("Input", "size", 4, ::std::mem::size_of::<Input>());
+};
+impl ::std::default::Default for Input {
+    #[inline]
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+unsafe impl ::shatter::__abi::BufferData for Input {
+    type State = ();
+    type Contents = Self;
+    const NEEDS_DROP: bool = false;
+    fn init() -> Self::State {}
+    fn size(_: &Self::State) -> usize {
+        ::std::mem::size_of::<Input>()
+    }
+    unsafe fn alloc() -> ::std::ptr::NonNull<u8> {
+        if ::std::mem::size_of::<Input>() == 0 {
+            return ::std::ptr::NonNull::<Input>::dangling().cast();
+        }
+        let layout = ::std::alloc::Layout::new::<Input>();
+        let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+        let ptr = match ::std::ptr::NonNull::new(ptr) {
+            ::std::option::Option::Some(ptr) => ptr,
+            ::std::option::Option::None => ::std::alloc::handle_alloc_error(layout),
+        };
+        ::shatter::__abi::record_alloc::<Input>(ptr, layout);
+        ptr
+    }
+    unsafe fn alloc_zeroed(_: usize) -> (::std::ptr::NonNull<u8>, Self::State) {
+        if ::std::mem::size_of::<Input>() == 0 {
+            return (::std::ptr::NonNull::<Input>::dangling().cast(), ());
+        }
+        let layout = ::std::alloc::Layout::new::<Input>();
+        let ptr = ::std::ptr::NonNull::new(unsafe { ::std::alloc::alloc_zeroed(layout) }).unwrap();
+        ::shatter::__abi::record_alloc::<Input>(ptr, layout);
+        (ptr, ())
+    }
+    unsafe fn dealloc(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) {
+        let layout = ::std::alloc::Layout::new::<Input>();
+        if layout.size() == 0 {
+            return;
+        }
+        ::shatter::__abi::record_dealloc::<Input>(ptr, layout);
+        unsafe { ::std::alloc::dealloc(ptr.as_ptr(), layout) };
+    }
+    unsafe fn as_ptr(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) -> *mut Self {
+        ptr.as_ptr() as *mut Self
+    }
+    unsafe fn contents(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) -> Self {
+        unsafe { ::std::ptr::read(ptr.as_ptr() as *const Self) }
+    }
+}
+unsafe impl ::shatter::__abi::Reflect for Input {
+    const LAYOUT: ::shatter::__abi::StructLayout = ::shatter::__abi::StructLayout {
+        name: ::std::borrow::Cow::Borrowed("Input"),
+        size: ::std::mem::size_of::<Input>() as u64,
+        fields: ::std::borrow::Cow::Borrowed(&[::shatter::__abi::FieldLayout {
+            name: ::std::borrow::Cow::Borrowed("value"),
+            ty: ::std::borrow::Cow::Borrowed("i32"),
+            offset: ::std::mem::offset_of!(Input, value) as u64,
+            size: ::std::mem::size_of::<::std::primitive::i32>() as u64,
+        }]),
+    };
+}
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub struct Output {
+    pub value: ::std::primitive::i32,
+}
+const _: () = {
+    ::shatter::__abi::assert_layout(
+        "Output::value",
+        "offset",
+        0,
+        ::std::mem::offset_of!(Output, value),
+    );
+    ::shatter::__abi::assert_layout("Output", "size", 4, ::std::mem::size_of::<Output>());
+};
+impl ::std::default::Default for Output {
+    #[inline]
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+unsafe impl ::shatter::__abi::BufferData for Output {
+    type State = ();
+    type Contents = Self;
+    const NEEDS_DROP: bool = false;
+    fn init() -> Self::State {}
+    fn size(_: &Self::State) -> usize {
+        ::std::mem::size_of::<Output>()
+    }
+    unsafe fn alloc() -> ::std::ptr::NonNull<u8> {
+        if ::std::mem::size_of::<Output>() == 0 {
+            return ::std::ptr::NonNull::<Output>::dangling().cast();
+        }
+        let layout = ::std::alloc::Layout::new::<Output>();
+        let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+        let ptr = match ::std::ptr::NonNull::new(ptr) {
+            ::std::option::Option::Some(ptr) => ptr,
+            ::std::option::Option::None => ::std::alloc::handle_alloc_error(layout),
+        };
+        ::shatter::__abi::record_alloc::<Output>(ptr, layout);
+        ptr
+    }
+    unsafe fn alloc_zeroed(_: usize) -> (::std::ptr::NonNull<u8>, Self::State) {
+        if ::std::mem::size_of::<Output>() == 0 {
+            return (::std::ptr::NonNull::<Output>::dangling().cast(), ());
+        }
+        let layout = ::std::alloc::Layout::new::<Output>();
+        let ptr = ::std::ptr::NonNull::new(unsafe { ::std::alloc::alloc_zeroed(layout) }).unwrap();
+        ::shatter::__abi::record_alloc::<Output>(ptr, layout);
+        (ptr, ())
+    }
+    unsafe fn dealloc(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) {
+        let layout = ::std::alloc::Layout::new::<Output>();
+        if layout.size() == 0 {
+            return;
+        }
+        ::shatter::__abi::record_dealloc::<Output>(ptr, layout);
+        unsafe { ::std::alloc::dealloc(ptr.as_ptr(), layout) };
+    }
+    unsafe fn as_ptr(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) -> *mut Self {
+        ptr.as_ptr() as *mut Self
+    }
+    unsafe fn contents(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) -> Self {
+        unsafe { ::std::ptr::read(ptr.as_ptr() as *const Self) }
+    }
+}
+unsafe impl ::shatter::__abi::Reflect for Output {
+    const LAYOUT: ::shatter::__abi::StructLayout = ::shatter::__abi::StructLayout {
+        name: ::std::borrow::Cow::Borrowed("Output"),
+        size: ::std::mem::size_of::<Output>() as u64,
+        fields: ::std::borrow::Cow::Borrowed(&[::shatter::__abi::FieldLayout {
+            name: ::std::borrow::Cow::Borrowed("value"),
+            ty: ::std::borrow::Cow::Borrowed("i32"),
+            offset: ::std::mem::offset_of!(Output, value) as u64,
+            size: ::std::mem::size_of::<::std::primitive::i32>() as u64,
+        }]),
+    };
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod negate {
+    use super::*;
+    pub const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize =
+        ::shatter::__abi::WorkGroupSize::new(
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+        );
+    #[doc = r" Invocations per work group, the product of [`WORK_GROUP_SIZE`]."]
+    pub const WORK_GROUP_INVOCATIONS: ::std::primitive::u32 = 1u32;
+    #[doc = r" Dispatch covering `elements` invocations along x, see"]
+    #[doc = r" [`Dispatch::try_linear`](::shatter::__abi::Dispatch::try_linear)."]
+    pub fn checked_dispatch(
+        elements: ::std::primitive::u64,
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_linear(elements, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Dispatch covering `extent` invocations along each axis, see"]
+    #[doc = r" [`Dispatch::try_for_extent`](::shatter::__abi::Dispatch::try_for_extent)."]
+    pub fn checked_dispatch_for_extent(
+        extent: [::std::primitive::u64; 3],
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_for_extent(extent, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Like [`checked_dispatch`], panics if the dispatch is too large."]
+    pub fn dispatch_for(elements: ::std::primitive::u64) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch(elements) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "negate", err),
+        }
+    }
+    #[doc = r" Like [`checked_dispatch_for_extent`], panics if the dispatch is too large."]
+    pub fn dispatch_for_extent(extent: [::std::primitive::u64; 3]) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch_for_extent(extent) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "negate", err),
+        }
+    }
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct Bindings<'a> {
+        pub input: &'a dyn ::shatter::__abi::Binding<Input>,
+        pub output: &'a mut dyn ::shatter::__abi::BindingMut<Output>,
+    }
+    impl<'a> Bindings<'a> {
+        #[doc = r" Borrows every binding from `registry` by its WGSL name,"]
+        #[doc = r" reporting every missing or mismatched resource."]
+        pub fn from_registry(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            Self::from_registry_renamed(registry, &[])
+        }
+        #[doc = r" Like [`Self::from_registry`], `rename` maps WGSL names to"]
+        #[doc = r" the names resources are stored under."]
+        pub fn from_registry_renamed(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+            rename: &[(&::std::primitive::str, &::std::primitive::str)],
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            let mut borrows = registry.borrow_bindings(
+                &[
+                    ::shatter::__abi::RegistryRequest::new::<Input>("input", false, false),
+                    ::shatter::__abi::RegistryRequest::new::<Output>("output", true, false),
+                ],
+                rename,
+            )?;
+            ::std::result::Result::Ok(Self {
+                input: borrows.take::<Input>(0usize),
+                output: borrows.take_mut::<Output>(1usize),
+            })
+        }
+        pub fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            ::std::vec![
+                ::shatter::__abi::BindGroupLayoutDescriptor {
+                    entries: ::std::vec![::shatter::__abi::BindGroupLayoutEntry {
+                        binding: 0u32,
+                        visibility: ::shatter::__abi::ShaderStages::COMPUTE,
+                        ty: ::shatter::__abi::BindingType::Buffer {
+                            ty: ::shatter::__abi::BufferBindingType::Storage { read_only: true },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: ::std::option::Option::None,
+                    }],
+                },
+                ::shatter::__abi::BindGroupLayoutDescriptor {
+                    entries: ::std::vec![::shatter::__abi::BindGroupLayoutEntry {
+                        binding: 0u32,
+                        visibility: ::shatter::__abi::ShaderStages::COMPUTE,
+                        ty: ::shatter::__abi::BindingType::Buffer {
+                            ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: ::std::option::Option::None,
+                    }],
+                }
+            ]
+        }
+        pub fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            let mut layouts = layouts.iter();
+            ::std::vec![
+                ::shatter::__abi::BindGroupDescriptor {
+                    layout: layouts.next().unwrap().clone(),
+                    entries: ::std::vec![::shatter::__abi::BindGroupEntry {
+                        binding: 0u32 as u32,
+                        resource: ::shatter::__abi::Binding::binding_resource(self.input),
+                    }],
+                },
+                ::shatter::__abi::BindGroupDescriptor {
+                    layout: layouts.next().unwrap().clone(),
+                    entries: ::std::vec![::shatter::__abi::BindGroupEntry {
+                        binding: 0u32 as u32,
+                        resource: ::shatter::__abi::Binding::binding_resource(self.output),
+                    }],
+                }
+            ]
+        }
+        pub fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            let mut layouts = layouts.iter();
+            let mut keys = ::shatter::__abi::GroupKeys::new();
+            keys.push({
+                let mut key = ::shatter::__abi::GroupKey::new(layouts.next().unwrap());
+                key.push(
+                    0u32 as u32,
+                    &::shatter::__abi::Binding::binding_resource(self.input),
+                );
+                key
+            });
+            keys.push({
+                let mut key = ::shatter::__abi::GroupKey::new(layouts.next().unwrap());
+                key.push(
+                    0u32 as u32,
+                    &::shatter::__abi::Binding::binding_resource(self.output),
+                );
+                key
+            });
+            keys
+        }
+    }
+    impl<'a> ::shatter::__abi::Bindings for Bindings<'a> {
+        #[inline]
+        fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            self.bind_group_layout_descriptors()
+        }
+        #[inline]
+        fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            self.bind_group_descriptors(layouts)
+        }
+        #[inline]
+        fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            self.bind_group_keys(layouts)
+        }
+        fn validate(&self) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+            let mut bindings = ::std::vec::Vec::new();
+            bindings.push((
+                ::shatter::__abi::BindingExpectation {
+                    name: "input",
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    min_size: 4u64,
+                    writable: false,
+                },
+                ::shatter::__abi::Binding::resource_info(self.input),
+            ));
+            bindings.push((
+                ::shatter::__abi::BindingExpectation {
+                    name: "output",
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    min_size: 4u64,
+                    writable: true,
+                },
+                ::shatter::__abi::Binding::resource_info(self.output),
+            ));
+            ::shatter::__abi::validate_bindings(&bindings)
+        }
+        #[inline]
+        fn prepare(&self, report: &mut ::shatter::__abi::TransferReport) {
+            let mut seen = ::shatter::__abi::SeenResources::new();
+            if seen.first_use(::shatter::__abi::Binding::binding_resource(self.input)) {
+                ::shatter::__abi::Binding::prepare(self.input, report);
+            }
+            if seen.first_use(::shatter::__abi::Binding::binding_resource(self.output)) {
+                ::shatter::__abi::Binding::prepare(self.output, report);
+            }
+        }
+        #[inline]
+        fn read(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::read(self.input, report);
+        }
+        #[inline]
+        fn write(&mut self) {
+            ::shatter::__abi::BindingMut::write(self.output);
+        }
+        #[inline]
+        fn needs_allocation(&self) -> bool {
+            ::shatter::__abi::Binding::needs_allocation(self.input)
+                || ::shatter::__abi::Binding::needs_allocation(self.output)
+        }
+    }
+    #[doc = r" Like [`Bindings`] owning the resources, taken by the `_async`"]
+    #[doc = r" dispatch so its future is `Send` and handed back once it's done."]
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct OwnedBindings<B0, B1> {
+        pub input: B0,
+        pub output: B1,
+    }
+    impl<B0, B1> OwnedBindings<B0, B1>
+    where
+        B0: ::shatter::__abi::Binding<Input>,
+        B1: ::shatter::__abi::BindingMut<Output>,
+    {
+        #[doc = r" Borrows the resources as [`Bindings`]."]
+        pub fn as_bindings(&mut self) -> Bindings<'_> {
+            Bindings {
+                input: &self.input,
+                output: &mut self.output,
+            }
+        }
+        #[doc = r" Downloads what the GPU wrote to the writable resources"]
+        #[doc = r" without blocking, returns the number of bytes downloaded."]
+        pub async fn download_async(
+            &mut self,
+        ) -> ::std::result::Result<::std::primitive::u64, ::shatter::__abi::Error> {
+            ::std::result::Result::Ok(
+                0 + ::shatter::__abi::Binding::<Output>::download_async(&mut self.output).await?,
+            )
+        }
+    }
+    #[doc = "The bindings of group 0, composed into [`Bindings`] by [`build_groups`]."]
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct Group0<'a> {
+        pub input: &'a dyn ::shatter::__abi::Binding<Input>,
+    }
+    impl<'a> Group0<'a> {
+        #[doc = r" Borrows every binding from `registry` by its WGSL name,"]
+        #[doc = r" reporting every missing or mismatched resource."]
+        pub fn from_registry(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            Self::from_registry_renamed(registry, &[])
+        }
+        #[doc = r" Like [`Self::from_registry`], `rename` maps WGSL names to"]
+        #[doc = r" the names resources are stored under."]
+        pub fn from_registry_renamed(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+            rename: &[(&::std::primitive::str, &::std::primitive::str)],
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            let mut borrows = registry.borrow_bindings(
+                &[::shatter::__abi::RegistryRequest::new::<Input>(
+                    "input", false, false,
+                )],
+                rename,
+            )?;
+            ::std::result::Result::Ok(Self {
+                input: borrows.take::<Input>(0usize),
+            })
+        }
+        pub fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            ::std::vec![::shatter::__abi::BindGroupLayoutDescriptor {
+                entries: ::std::vec![::shatter::__abi::BindGroupLayoutEntry {
+                    binding: 0u32,
+                    visibility: ::shatter::__abi::ShaderStages::COMPUTE,
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: ::std::option::Option::None,
+                }],
+            }]
+        }
+        pub fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            let mut layouts = layouts.iter();
+            ::std::vec![::shatter::__abi::BindGroupDescriptor {
+                layout: layouts.next().unwrap().clone(),
+                entries: ::std::vec![::shatter::__abi::BindGroupEntry {
+                    binding: 0u32 as u32,
+                    resource: ::shatter::__abi::Binding::binding_resource(self.input),
+                }],
+            }]
+        }
+        pub fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            let mut layouts = layouts.iter();
+            let mut keys = ::shatter::__abi::GroupKeys::new();
+            keys.push({
+                let mut key = ::shatter::__abi::GroupKey::new(layouts.next().unwrap());
+                key.push(
+                    0u32 as u32,
+                    &::shatter::__abi::Binding::binding_resource(self.input),
+                );
+                key
+            });
+            keys
+        }
+    }
+    impl<'a> ::shatter::__abi::Bindings for Group0<'a> {
+        #[inline]
+        fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            self.bind_group_layout_descriptors()
+        }
+        #[inline]
+        fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            self.bind_group_descriptors(layouts)
+        }
+        #[inline]
+        fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            self.bind_group_keys(layouts)
+        }
+        fn validate(&self) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+            let mut bindings = ::std::vec::Vec::new();
+            bindings.push((
+                ::shatter::__abi::BindingExpectation {
+                    name: "input",
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    min_size: 4u64,
+                    writable: false,
+                },
+                ::shatter::__abi::Binding::resource_info(self.input),
+            ));
+            ::shatter::__abi::validate_bindings(&bindings)
+        }
+        #[inline]
+        fn prepare(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::prepare(self.input, report);
+        }
+        #[inline]
+        fn read(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::read(self.input, report);
+        }
+        #[inline]
+        fn write(&mut self) {}
+        #[inline]
+        fn needs_allocation(&self) -> bool {
+            ::shatter::__abi::Binding::needs_allocation(self.input)
+        }
+        #[inline]
+        fn group_count(&self) -> ::std::primitive::usize {
+            1
+        }
+    }
+    #[doc = "The bindings of group 1, composed into [`Bindings`] by [`build_groups`]."]
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct Group1<'a> {
+        pub output: &'a mut dyn ::shatter::__abi::BindingMut<Output>,
+    }
+    impl<'a> Group1<'a> {
+        #[doc = r" Borrows every binding from `registry` by its WGSL name,"]
+        #[doc = r" reporting every missing or mismatched resource."]
+        pub fn from_registry(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            Self::from_registry_renamed(registry, &[])
+        }
+        #[doc = r" Like [`Self::from_registry`], `rename` maps WGSL names to"]
+        #[doc = r" the names resources are stored under."]
+        pub fn from_registry_renamed(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+            rename: &[(&::std::primitive::str, &::std::primitive::str)],
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            let mut borrows = registry.borrow_bindings(
+                &[::shatter::__abi::RegistryRequest::new::<Output>(
+                    "output", true, false,
+                )],
+                rename,
+            )?;
+            ::std::result::Result::Ok(Self {
+                output: borrows.take_mut::<Output>(0usize),
+            })
+        }
+        pub fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            ::std::vec![::shatter::__abi::BindGroupLayoutDescriptor {
+                entries: ::std::vec![::shatter::__abi::BindGroupLayoutEntry {
+                    binding: 0u32,
+                    visibility: ::shatter::__abi::ShaderStages::COMPUTE,
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: ::std::option::Option::None,
+                }],
+            }]
+        }
+        pub fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            let mut layouts = layouts.iter();
+            ::std::vec![::shatter::__abi::BindGroupDescriptor {
+                layout: layouts.next().unwrap().clone(),
+                entries: ::std::vec![::shatter::__abi::BindGroupEntry {
+                    binding: 0u32 as u32,
+                    resource: ::shatter::__abi::Binding::binding_resource(self.output),
+                }],
+            }]
+        }
+        pub fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            let mut layouts = layouts.iter();
+            let mut keys = ::shatter::__abi::GroupKeys::new();
+            keys.push({
+                let mut key = ::shatter::__abi::GroupKey::new(layouts.next().unwrap());
+                key.push(
+                    0u32 as u32,
+                    &::shatter::__abi::Binding::binding_resource(self.output),
+                );
+                key
+            });
+            keys
+        }
+    }
+    impl<'a> ::shatter::__abi::Bindings for Group1<'a> {
+        #[inline]
+        fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            self.bind_group_layout_descriptors()
+        }
+        #[inline]
+        fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            self.bind_group_descriptors(layouts)
+        }
+        #[inline]
+        fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            self.bind_group_keys(layouts)
+        }
+        fn validate(&self) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+            let mut bindings = ::std::vec::Vec::new();
+            bindings.push((
+                ::shatter::__abi::BindingExpectation {
+                    name: "output",
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    min_size: 4u64,
+                    writable: true,
+                },
+                ::shatter::__abi::Binding::resource_info(self.output),
+            ));
+            ::shatter::__abi::validate_bindings(&bindings)
+        }
+        #[inline]
+        fn prepare(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::prepare(self.output, report);
+        }
+        #[inline]
+        fn read(&self, report: &mut ::shatter::__abi::TransferReport) {}
+        #[inline]
+        fn write(&mut self) {
+            ::shatter::__abi::BindingMut::write(self.output);
+        }
+        #[inline]
+        fn needs_allocation(&self) -> bool {
+            ::shatter::__abi::Binding::needs_allocation(self.output)
+        }
+        #[inline]
+        fn group_count(&self) -> ::std::primitive::usize {
+            1
+        }
+    }
+    impl<'a> Bindings<'a> {
+        #[doc = r" Composes the bindings of each group."]
+        pub fn from_groups(groups: (Group0<'a>, Group1<'a>)) -> Self {
+            Self {
+                input: groups.0.input,
+                output: groups.1.output,
+            }
+        }
+        #[doc = r" Splits the bindings into those of each group."]
+        pub fn into_groups(self) -> (Group0<'a>, Group1<'a>) {
+            (
+                Group0 { input: self.input },
+                Group1 {
+                    output: self.output,
+                },
+            )
+        }
+    }
+    #[doc = r" Checks that `bindings` are compatible with the shader without dispatching."]
+    pub fn validate(
+        bindings: &Bindings<'_>,
+    ) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+        ::shatter::__abi::Bindings::validate(bindings)
+    }
+    pub struct Shader;
+    impl<'a> ::shatter::__abi::ComputeShader<'a> for Shader {
+        type Bindings = Bindings<'a>;
+        const SOURCE : & 'static :: std :: primitive :: str = "[[block ]]struct Input {value : i32 ; }; [[group (0 ), binding (0 )]]var < storage , read > input : Input ; [[block ]]struct Output {value : i32 ; }; [[group (1 ), binding (0 )]]var < storage , read_write > output : Output ; [[stage (compute ), workgroup_size (1 )]]fn negate (){output . value = - input . value ; }" ;
+        const ENTRY_POINT: &'static ::std::primitive::str = "negate";
+        const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = WORK_GROUP_SIZE;
+        type PushConstants = ::shatter::__abi::NoPushConstants;
+    }
+    impl<'a> ::shatter::__abi::DispatchableShader<Bindings<'a>> for Shader {
+        fn entry_point(&self) -> &'static ::std::primitive::str {
+            "negate"
+        }
+        fn work_group_size(&self) -> ::shatter::__abi::WorkGroupSize {
+            WORK_GROUP_SIZE
+        }
+        fn dispatch(&self, bindings: Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+            ::shatter::__abi::ComputeShaderBuilder::<'a, Shader>::new(bindings)
+                .tag("negate")
+                .dispatch(dispatch);
+        }
+    }
+    pub fn build<'a>(
+        mut bindings: negate::Bindings<'a>,
+    ) -> ::shatter::__abi::ComputeShaderBuilder<'a, Shader> {
+        ::shatter::__abi::ComputeShaderBuilder::new(bindings)
+    }
+    #[doc = r" Like [`build`] with the bindings of each group, or a mutable"]
+    #[doc = r" reference to them, so a group can be reused for other dispatches"]
+    #[doc = r" and entry points."]
+    pub fn build_groups<'a, 'g0, 'g1, G0, G1>(
+        groups: (G0, G1),
+    ) -> ::shatter::__abi::ComputeShaderBuilder<'a, ::shatter::__abi::Composed<Shader, (G0, G1)>>
+    where
+        G0: ::shatter::__abi::Bindings + ::std::borrow::BorrowMut<Group0<'g0>>,
+        G1: ::shatter::__abi::Bindings + ::std::borrow::BorrowMut<Group1<'g1>>,
+    {
+        ::shatter::__abi::ComputeShaderBuilder::new(groups)
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub fn negate<'a>(mut bindings: negate::Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+    negate::build(bindings).tag("negate").dispatch(dispatch);
+}
+#[doc = r" Like the blocking function without waiting on the GPU, resolves once the"]
+#[doc = r" dispatch is done and what it wrote is downloaded. Takes the resources"]
+#[doc = r" by value so the future is `Send`, they are handed back when it resolves."]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub async fn negate_async<B0, B1>(
+    mut bindings: negate::OwnedBindings<B0, B1>,
+    dispatch: ::shatter::__abi::Dispatch,
+) -> negate::OwnedBindings<B0, B1>
+where
+    B0: ::shatter::__abi::Binding<Input> + ::std::marker::Send,
+    B1: ::shatter::__abi::BindingMut<Output> + ::std::marker::Send,
+{
+    let work = negate::build(bindings.as_bindings())
+        .tag("negate")
+        .dispatch_async(dispatch);
+    work.await;
+    if let ::std::result::Result::Err(err) = bindings.download_async().await {
+        ::std::panic!("{}", err);
+    }
+    bindings
+}
@@ -0,0 +1,1043 @@
+const _: () = ::std::assert!(
+    ::shatter::ABI_VERSION == 32u32,
+    "shatter-macro 0.0.1 requires shatter ABI 32, update the shatter crate"
+);
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub struct Values {
+    pub values: [::std::primitive::u32],
+}
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub struct Values_Sized {
+    pub values: [::std::primitive::u32; 0],
+}
+const _: () = {
+    ::shatter::__abi::assert_layout(
+        "Values::values",
+        "offset",
+        0,
+        ::std::mem::offset_of!(Values_Sized, values),
+    );
+    ::shatter::__abi::assert_layout(
+        "Values::values",
+        "stride",
+        4,
+        ::std::mem::size_of::<::std::primitive::u32>(),
+    );
+    ::shatter::__abi::assert_layout(
+        "Values",
+        "header size",
+        0,
+        ::std::mem::size_of::<Values_Sized>(),
+    );
+    ::shatter::__abi::assert_layout(
+        "Values::values",
+        "element alignment",
+        4,
+        ::std::mem::align_of::<::std::primitive::u32>(),
+    );
+};
+impl ::std::default::Default for Values_Sized {
+    #[inline]
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+unsafe impl ::shatter::__abi::BufferData for Values {
+    type State = (usize, usize);
+    type Contents = ::std::vec::Vec<::std::primitive::u32>;
+    const NEEDS_DROP: bool = false;
+    fn init() -> Self::State {
+        let cap = if ::std::mem::size_of::<::std::primitive::u32>() == 0 {
+            !0
+        } else {
+            0
+        };
+        (0, cap)
+    }
+    fn size(&(length, _capacity): &Self::State) -> usize {
+        ::std::mem::size_of::<Values_Sized>()
+            + length * ::std::mem::size_of::<::std::primitive::u32>()
+    }
+    unsafe fn alloc() -> ::std::ptr::NonNull<u8> {
+        let (_, capacity) = Self::init();
+        let layout =
+            ::shatter::__abi::buffer_vec_layout::<Values_Sized, ::std::primitive::u32>(capacity);
+        if layout.size() == 0 {
+            return ::std::ptr::NonNull::<Values_Sized>::dangling().cast();
+        }
+        let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+        let ptr = match ::std::ptr::NonNull::new(ptr) {
+            ::std::option::Option::Some(ptr) => ptr,
+            ::std::option::Option::None => ::std::alloc::handle_alloc_error(layout),
+        };
+        ::shatter::__abi::record_alloc::<Values>(ptr, layout);
+        ptr
+    }
+    unsafe fn alloc_zeroed(size: usize) -> (::std::ptr::NonNull<u8>, Self::State) {
+        if ::std::mem::size_of::<::std::primitive::u32>() == 0 {
+            return (unsafe { Self::alloc() }, Self::init());
+        }
+        let length = size.saturating_sub(::std::mem::size_of::<Values_Sized>())
+            / ::std::mem::size_of::<::std::primitive::u32>();
+        let layout =
+            ::shatter::__abi::buffer_vec_layout::<Values_Sized, ::std::primitive::u32>(length);
+        if layout.size() == 0 {
+            return (
+                ::std::ptr::NonNull::<Values_Sized>::dangling().cast(),
+                (0, 0),
+            );
+        }
+        let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+        let ptr = match ::std::ptr::NonNull::new(ptr) {
+            Some(ptr) => ptr,
+            None => ::std::alloc::handle_alloc_error(layout),
+        };
+        ::shatter::__abi::record_alloc::<Values>(ptr, layout);
+        (ptr, (length, length))
+    }
+    unsafe fn dealloc(ptr: ::std::ptr::NonNull<u8>, &(_length, capacity): &Self::State) {
+        let layout =
+            ::shatter::__abi::buffer_vec_layout::<Values_Sized, ::std::primitive::u32>(capacity);
+        if layout.size() == 0 {
+            return;
+        }
+        ::shatter::__abi::record_dealloc::<Values>(ptr, layout);
+        unsafe { ::std::alloc::dealloc(ptr.as_ptr(), layout) };
+    }
+    unsafe fn as_ptr(
+        ptr: ::std::ptr::NonNull<u8>,
+        &(length, _capacity): &Self::State,
+    ) -> *mut Self {
+        let slice = unsafe { ::std::slice::from_raw_parts_mut(ptr.as_ptr(), length) };
+        unsafe { ::std::mem::transmute(slice as *mut [u8]) }
+    }
+    unsafe fn contents(
+        ptr: ::std::ptr::NonNull<u8>,
+        &(length, _capacity): &Self::State,
+    ) -> Self::Contents {
+        let layout = ::std::alloc::Layout::new::<Values_Sized>();
+        let items = unsafe { ptr.as_ptr().add(layout.size()) as *const ::std::primitive::u32 };
+        (0..length)
+            .map(|i| unsafe { ::std::ptr::read(items.add(i)) })
+            .collect()
+    }
+}
+unsafe impl ::shatter::__abi::BufferVec for Values {
+    type Head = ();
+    type Item = ::std::primitive::u32;
+    const ITEM_STRIDE: usize = 4;
+    const ITEM_ALIGN: usize = 4;
+    fn len(&(length, _): &Self::State) -> usize {
+        length
+    }
+    fn capacity(&(_, capacity): &Self::State) -> usize {
+        capacity
+    }
+    unsafe fn grow(ptr: &mut ::std::ptr::NonNull<u8>, state: &mut Self::State) {
+        let additional = state.1 - state.0 + 1;
+        unsafe { Self::reserve(ptr, state, additional) };
+    }
+    unsafe fn reserve(
+        ptr: &mut ::std::ptr::NonNull<u8>,
+        &mut (length, ref mut capacity): &mut Self::State,
+        additional: usize,
+    ) {
+        let required = length.checked_add(additional).expect("capacity overflow");
+        if required <= *capacity {
+            return;
+        }
+        assert!(
+            ::std::mem::size_of::<Self::Item>() != 0,
+            "capacity overflow"
+        );
+        let new_cap = required.max(capacity.saturating_mul(2));
+        let old_layout =
+            ::shatter::__abi::buffer_vec_layout::<Values_Sized, ::std::primitive::u32>(*capacity);
+        let new_layout =
+            ::shatter::__abi::buffer_vec_layout::<Values_Sized, ::std::primitive::u32>(new_cap);
+        ::shatter::__abi::fault_point(::shatter::__abi::FaultPoint::Grow);
+        let new_ptr = if old_layout.size() == 0 {
+            unsafe { ::std::alloc::alloc(new_layout) }
+        } else {
+            unsafe { ::std::alloc::realloc(ptr.as_ptr(), old_layout, new_layout.size()) }
+        };
+        let new_ptr = match ::std::ptr::NonNull::new(new_ptr) {
+            Some(ptr) => ptr,
+            None => ::std::alloc::handle_alloc_error(new_layout),
+        };
+        let old_ptr = ::std::mem::replace(ptr, new_ptr);
+        *capacity = new_cap;
+        ::shatter::__abi::record_realloc::<Values>(old_ptr, old_layout, new_ptr, new_layout);
+    }
+    unsafe fn push(ptr: &mut ::std::ptr::NonNull<u8>, state: &mut Self::State, item: Self::Item) {
+        if state.0 == state.1 {
+            unsafe { Self::grow(ptr, state) };
+        }
+        ::shatter::__abi::fault_point(::shatter::__abi::FaultPoint::Push);
+        unsafe { ::std::ptr::write(Self::items(*ptr, state).add(state.0), item) };
+        state.0 += 1;
+    }
+    unsafe fn pop(
+        ptr: ::std::ptr::NonNull<u8>,
+        state: &mut Self::State,
+    ) -> ::std::option::Option<Self::Item> {
+        if state.0 == 0 {
+            None
+        } else {
+            state.0 -= 1;
+            unsafe { Some(::std::ptr::read(Self::items(ptr, state).add(state.0))) }
+        }
+    }
+    unsafe fn set_len((length, _capacity): &mut Self::State, len: usize) {
+        *length = len;
+    }
+    unsafe fn items(ptr: ::std::ptr::NonNull<u8>, _state: &Self::State) -> *mut Self::Item {
+        unsafe { ptr.as_ptr().add(::std::mem::size_of::<Values_Sized>()) as *mut Self::Item }
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod seed {
+    use super::*;
+    pub const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize =
+        ::shatter::__abi::WorkGroupSize::new(
+            64u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+        );
+    #[doc = r" Invocations per work group, the product of [`WORK_GROUP_SIZE`]."]
+    pub const WORK_GROUP_INVOCATIONS: ::std::primitive::u32 = 64u32;
+    #[doc = r" Dispatch covering `elements` invocations along x, see"]
+    #[doc = r" [`Dispatch::try_linear`](::shatter::__abi::Dispatch::try_linear)."]
+    pub fn checked_dispatch(
+        elements: ::std::primitive::u64,
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_linear(elements, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Dispatch covering `extent` invocations along each axis, see"]
+    #[doc = r" [`Dispatch::try_for_extent`](::shatter::__abi::Dispatch::try_for_extent)."]
+    pub fn checked_dispatch_for_extent(
+        extent: [::std::primitive::u64; 3],
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_for_extent(extent, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Like [`checked_dispatch`], panics if the dispatch is too large."]
+    pub fn dispatch_for(elements: ::std::primitive::u64) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch(elements) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "seed", err),
+        }
+    }
+    #[doc = r" Like [`checked_dispatch_for_extent`], panics if the dispatch is too large."]
+    pub fn dispatch_for_extent(extent: [::std::primitive::u64; 3]) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch_for_extent(extent) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "seed", err),
+        }
+    }
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct Bindings<'a> {
+        pub values: &'a mut dyn ::shatter::__abi::BindingMut<Values>,
+    }
+    impl<'a> Bindings<'a> {
+        #[doc = r" Borrows every binding from `registry` by its WGSL name,"]
+        #[doc = r" reporting every missing or mismatched resource."]
+        pub fn from_registry(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            Self::from_registry_renamed(registry, &[])
+        }
+        #[doc = r" Like [`Self::from_registry`], `rename` maps WGSL names to"]
+        #[doc = r" the names resources are stored under."]
+        pub fn from_registry_renamed(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+            rename: &[(&::std::primitive::str, &::std::primitive::str)],
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            let mut borrows = registry.borrow_bindings(
+                &[::shatter::__abi::RegistryRequest::new::<Values>(
+                    "values", true, false,
+                )],
+                rename,
+            )?;
+            ::std::result::Result::Ok(Self {
+                values: borrows.take_mut::<Values>(0usize),
+            })
+        }
+        pub fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            ::std::vec![::shatter::__abi::BindGroupLayoutDescriptor {
+                entries: ::std::vec![::shatter::__abi::BindGroupLayoutEntry {
+                    binding: 0u32,
+                    visibility: ::shatter::__abi::ShaderStages::COMPUTE,
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: ::std::option::Option::None,
+                }],
+            }]
+        }
+        pub fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            let mut layouts = layouts.iter();
+            ::std::vec![::shatter::__abi::BindGroupDescriptor {
+                layout: layouts.next().unwrap().clone(),
+                entries: ::std::vec![::shatter::__abi::BindGroupEntry {
+                    binding: 0u32 as u32,
+                    resource: ::shatter::__abi::Binding::binding_resource(self.values),
+                }],
+            }]
+        }
+        pub fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            let mut layouts = layouts.iter();
+            let mut keys = ::shatter::__abi::GroupKeys::new();
+            keys.push({
+                let mut key = ::shatter::__abi::GroupKey::new(layouts.next().unwrap());
+                key.push(
+                    0u32 as u32,
+                    &::shatter::__abi::Binding::binding_resource(self.values),
+                );
+                key
+            });
+            keys
+        }
+    }
+    impl<'a> ::shatter::__abi::Bindings for Bindings<'a> {
+        #[inline]
+        fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            self.bind_group_layout_descriptors()
+        }
+        #[inline]
+        fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            self.bind_group_descriptors(layouts)
+        }
+        #[inline]
+        fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            self.bind_group_keys(layouts)
+        }
+        fn validate(&self) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+            let mut bindings = ::std::vec::Vec::new();
+            bindings.push((
+                ::shatter::__abi::BindingExpectation {
+                    name: "values",
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    min_size: 4u64,
+                    writable: true,
+                },
+                ::shatter::__abi::Binding::resource_info(self.values),
+            ));
+            ::shatter::__abi::validate_bindings(&bindings)
+        }
+        #[inline]
+        fn prepare(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::prepare(self.values, report);
+        }
+        #[inline]
+        fn read(&self, report: &mut ::shatter::__abi::TransferReport) {}
+        #[inline]
+        fn write(&mut self) {
+            ::shatter::__abi::BindingMut::write(self.values);
+        }
+        #[inline]
+        fn needs_allocation(&self) -> bool {
+            ::shatter::__abi::Binding::needs_allocation(self.values)
+        }
+    }
+    #[doc = r" Like [`Bindings`] owning the resources, taken by the `_async`"]
+    #[doc = r" dispatch so its future is `Send` and handed back once it's done."]
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct OwnedBindings<B0> {
+        pub values: B0,
+    }
+    impl<B0> OwnedBindings<B0>
+    where
+        B0: ::shatter::__abi::BindingMut<Values>,
+    {
+        #[doc = r" Borrows the resources as [`Bindings`]."]
+        pub fn as_bindings(&mut self) -> Bindings<'_> {
+            Bindings {
+                values: &mut self.values,
+            }
+        }
+        #[doc = r" Downloads what the GPU wrote to the writable resources"]
+        #[doc = r" without blocking, returns the number of bytes downloaded."]
+        pub async fn download_async(
+            &mut self,
+        ) -> ::std::result::Result<::std::primitive::u64, ::shatter::__abi::Error> {
+            ::std::result::Result::Ok(
+                0 + ::shatter::__abi::Binding::<Values>::download_async(&mut self.values).await?,
+            )
+        }
+    }
+    #[doc = r" Checks that `bindings` are compatible with the shader without dispatching."]
+    pub fn validate(
+        bindings: &Bindings<'_>,
+    ) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+        ::shatter::__abi::Bindings::validate(bindings)
+    }
+    pub struct Shader;
+    impl<'a> ::shatter::__abi::ComputeShader<'a> for Shader {
+        type Bindings = Bindings<'a>;
+        const SOURCE : & 'static :: std :: primitive :: str = "[[block ]]struct Values {values : array < u32 >; }; [[group (0 ), binding (0 )]]var < storage , read_write > values : Values ; [[stage (compute ), workgroup_size (64 )]]fn seed ([[builtin (global_invocation_id )]]id : vec3 < u32 > ){if (id . x < arrayLength (& values . values )){values . values [id . x ]= id . x * 3u ; }}[[stage (compute ), workgroup_size (64 )]]fn scale ([[builtin (global_invocation_id )]]id : vec3 < u32 > ){if (id . x < arrayLength (& values . values )){values . values [id . x ]= values . values [id . x ]* 2u ; }}[[stage (compute ), workgroup_size (64 )]]fn bias ([[builtin (global_invocation_id )]]id : vec3 < u32 > ){if (id . x < arrayLength (& values . values )){values . values [id . x ]= values . values [id . x ]+ 7u ; }}" ;
+        const ENTRY_POINT: &'static ::std::primitive::str = "seed";
+        const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = WORK_GROUP_SIZE;
+        type PushConstants = ::shatter::__abi::NoPushConstants;
+    }
+    impl<'a> ::shatter::__abi::DispatchableShader<Bindings<'a>> for Shader {
+        fn entry_point(&self) -> &'static ::std::primitive::str {
+            "seed"
+        }
+        fn work_group_size(&self) -> ::shatter::__abi::WorkGroupSize {
+            WORK_GROUP_SIZE
+        }
+        fn dispatch(&self, bindings: Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+            ::shatter::__abi::ComputeShaderBuilder::<'a, Shader>::new(bindings)
+                .tag("seed")
+                .dispatch(dispatch);
+        }
+    }
+    pub fn build<'a>(
+        mut bindings: seed::Bindings<'a>,
+    ) -> ::shatter::__abi::ComputeShaderBuilder<'a, Shader> {
+        ::shatter::__abi::ComputeShaderBuilder::new(bindings)
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub fn seed<'a>(mut bindings: seed::Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+    seed::build(bindings).tag("seed").dispatch(dispatch);
+}
+#[doc = r" Like the blocking function without waiting on the GPU, resolves once the"]
+#[doc = r" dispatch is done and what it wrote is downloaded. Takes the resources"]
+#[doc = r" by value so the future is `Send`, they are handed back when it resolves."]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub async fn seed_async<B0>(
+    mut bindings: seed::OwnedBindings<B0>,
+    dispatch: ::shatter::__abi::Dispatch,
+) -> seed::OwnedBindings<B0>
+where
+    B0: ::shatter::__abi::BindingMut<Values> + ::std::marker::Send,
+{
+    let work = seed::build(bindings.as_bindings())
+        .tag("seed")
+        .dispatch_async(dispatch);
+    work.await;
+    if let ::std::result::Result::Err(err) = bindings.download_async().await {
+        ::std::panic!("{}", err);
+    }
+    bindings
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod scale {
+    use super::*;
+    pub const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize =
+        ::shatter::__abi::WorkGroupSize::new(
+            64u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+        );
+    #[doc = r" Invocations per work group, the product of [`WORK_GROUP_SIZE`]."]
+    pub const WORK_GROUP_INVOCATIONS: ::std::primitive::u32 = 64u32;
+    #[doc = r" Dispatch covering `elements` invocations along x, see"]
+    #[doc = r" [`Dispatch::try_linear`](::shatter::__abi::Dispatch::try_linear)."]
+    pub fn checked_dispatch(
+        elements: ::std::primitive::u64,
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_linear(elements, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Dispatch covering `extent` invocations along each axis, see"]
+    #[doc = r" [`Dispatch::try_for_extent`](::shatter::__abi::Dispatch::try_for_extent)."]
+    pub fn checked_dispatch_for_extent(
+        extent: [::std::primitive::u64; 3],
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_for_extent(extent, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Like [`checked_dispatch`], panics if the dispatch is too large."]
+    pub fn dispatch_for(elements: ::std::primitive::u64) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch(elements) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "scale", err),
+        }
+    }
+    #[doc = r" Like [`checked_dispatch_for_extent`], panics if the dispatch is too large."]
+    pub fn dispatch_for_extent(extent: [::std::primitive::u64; 3]) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch_for_extent(extent) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "scale", err),
+        }
+    }
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct Bindings<'a> {
+        pub values: &'a mut dyn ::shatter::__abi::BindingMut<Values>,
+    }
+    impl<'a> Bindings<'a> {
+        #[doc = r" Borrows every binding from `registry` by its WGSL name,"]
+        #[doc = r" reporting every missing or mismatched resource."]
+        pub fn from_registry(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            Self::from_registry_renamed(registry, &[])
+        }
+        #[doc = r" Like [`Self::from_registry`], `rename` maps WGSL names to"]
+        #[doc = r" the names resources are stored under."]
+        pub fn from_registry_renamed(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+            rename: &[(&::std::primitive::str, &::std::primitive::str)],
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            let mut borrows = registry.borrow_bindings(
+                &[::shatter::__abi::RegistryRequest::new::<Values>(
+                    "values", true, false,
+                )],
+                rename,
+            )?;
+            ::std::result::Result::Ok(Self {
+                values: borrows.take_mut::<Values>(0usize),
+            })
+        }
+        pub fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            ::std::vec![::shatter::__abi::BindGroupLayoutDescriptor {
+                entries: ::std::vec![::shatter::__abi::BindGroupLayoutEntry {
+                    binding: 0u32,
+                    visibility: ::shatter::__abi::ShaderStages::COMPUTE,
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: ::std::option::Option::None,
+                }],
+            }]
+        }
+        pub fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            let mut layouts = layouts.iter();
+            ::std::vec![::shatter::__abi::BindGroupDescriptor {
+                layout: layouts.next().unwrap().clone(),
+                entries: ::std::vec![::shatter::__abi::BindGroupEntry {
+                    binding: 0u32 as u32,
+                    resource: ::shatter::__abi::Binding::binding_resource(self.values),
+                }],
+            }]
+        }
+        pub fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            let mut layouts = layouts.iter();
+            let mut keys = ::shatter::__abi::GroupKeys::new();
+            keys.push({
+                let mut key = ::shatter::__abi::GroupKey::new(layouts.next().unwrap());
+                key.push(
+                    0u32 as u32,
+                    &::shatter::__abi::Binding::binding_resource(self.values),
+                );
+                key
+            });
+            keys
+        }
+    }
+    impl<'a> ::shatter::__abi::Bindings for Bindings<'a> {
+        #[inline]
+        fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            self.bind_group_layout_descriptors()
+        }
+        #[inline]
+        fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            self.bind_group_descriptors(layouts)
+        }
+        #[inline]
+        fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            self.bind_group_keys(layouts)
+        }
+        fn validate(&self) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+            let mut bindings = ::std::vec::Vec::new();
+            bindings.push((
+                ::shatter::__abi::BindingExpectation {
+                    name: "values",
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    min_size: 4u64,
+                    writable: true,
+                },
+                ::shatter::__abi::Binding::resource_info(self.values),
+            ));
+            ::shatter::__abi::validate_bindings(&bindings)
+        }
+        #[inline]
+        fn prepare(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::prepare(self.values, report);
+        }
+        #[inline]
+        fn read(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::read(self.values, report);
+        }
+        #[inline]
+        fn write(&mut self) {
+            ::shatter::__abi::BindingMut::write(self.values);
+        }
+        #[inline]
+        fn needs_allocation(&self) -> bool {
+            ::shatter::__abi::Binding::needs_allocation(self.values)
+        }
+    }
+    #[doc = r" Like [`Bindings`] owning the resources, taken by the `_async`"]
+    #[doc = r" dispatch so its future is `Send` and handed back once it's done."]
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct OwnedBindings<B0> {
+        pub values: B0,
+    }
+    impl<B0> OwnedBindings<B0>
+    where
+        B0: ::shatter::__abi::BindingMut<Values>,
+    {
+        #[doc = r" Borrows the resources as [`Bindings`]."]
+        pub fn as_bindings(&mut self) -> Bindings<'_> {
+            Bindings {
+                values: &mut self.values,
+            }
+        }
+        #[doc = r" Downloads what the GPU wrote to the writable resources"]
+        #[doc = r" without blocking, returns the number of bytes downloaded."]
+        pub async fn download_async(
+            &mut self,
+        ) -> ::std::result::Result<::std::primitive::u64, ::shatter::__abi::Error> {
+            ::std::result::Result::Ok(
+                0 + ::shatter::__abi::Binding::<Values>::download_async(&mut self.values).await?,
+            )
+        }
+    }
+    #[doc = r" Checks that `bindings` are compatible with the shader without dispatching."]
+    pub fn validate(
+        bindings: &Bindings<'_>,
+    ) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+        ::shatter::__abi::Bindings::validate(bindings)
+    }
+    pub struct Shader;
+    impl<'a> ::shatter::__abi::ComputeShader<'a> for Shader {
+        type Bindings = Bindings<'a>;
+        const SOURCE : & 'static :: std :: primitive :: str = "[[block ]]struct Values {values : array < u32 >; }; [[group (0 ), binding (0 )]]var < storage , read_write > values : Values ; [[stage (compute ), workgroup_size (64 )]]fn seed ([[builtin (global_invocation_id )]]id : vec3 < u32 > ){if (id . x < arrayLength (& values . values )){values . values [id . x ]= id . x * 3u ; }}[[stage (compute ), workgroup_size (64 )]]fn scale ([[builtin (global_invocation_id )]]id : vec3 < u32 > ){if (id . x < arrayLength (& values . values )){values . values [id . x ]= values . values [id . x ]* 2u ; }}[[stage (compute ), workgroup_size (64 )]]fn bias ([[builtin (global_invocation_id )]]id : vec3 < u32 > ){if (id . x < arrayLength (& values . values )){values . values [id . x ]= values . values [id . x ]+ 7u ; }}" ;
+        const ENTRY_POINT: &'static ::std::primitive::str = "scale";
+        const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = WORK_GROUP_SIZE;
+        type PushConstants = ::shatter::__abi::NoPushConstants;
+    }
+    impl<'a> ::shatter::__abi::DispatchableShader<Bindings<'a>> for Shader {
+        fn entry_point(&self) -> &'static ::std::primitive::str {
+            "scale"
+        }
+        fn work_group_size(&self) -> ::shatter::__abi::WorkGroupSize {
+            WORK_GROUP_SIZE
+        }
+        fn dispatch(&self, bindings: Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+            ::shatter::__abi::ComputeShaderBuilder::<'a, Shader>::new(bindings)
+                .tag("scale")
+                .dispatch(dispatch);
+        }
+    }
+    pub fn build<'a>(
+        mut bindings: scale::Bindings<'a>,
+    ) -> ::shatter::__abi::ComputeShaderBuilder<'a, Shader> {
+        ::shatter::__abi::ComputeShaderBuilder::new(bindings)
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub fn scale<'a>(mut bindings: scale::Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+    scale::build(bindings).tag("scale").dispatch(dispatch);
+}
+#[doc = r" Like the blocking function without waiting on the GPU, resolves once the"]
+#[doc = r" dispatch is done and what it wrote is downloaded. Takes the resources"]
+#[doc = r" by value so the future is `Send`, they are handed back when it resolves."]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub async fn scale_async<B0>(
+    mut bindings: scale::OwnedBindings<B0>,
+    dispatch: ::shatter::__abi::Dispatch,
+) -> scale::OwnedBindings<B0>
+where
+    B0: ::shatter::__abi::BindingMut<Values> + ::std::marker::Send,
+{
+    let work = scale::build(bindings.as_bindings())
+        .tag("scale")
+        .dispatch_async(dispatch);
+    work.await;
+    if let ::std::result::Result::Err(err) = bindings.download_async().await {
+        ::std::panic!("{}", err);
+    }
+    bindings
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod bias {
+    use super::*;
+    pub const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize =
+        ::shatter::__abi::WorkGroupSize::new(
+            64u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+        );
+    #[doc = r" Invocations per work group, the product of [`WORK_GROUP_SIZE`]."]
+    pub const WORK_GROUP_INVOCATIONS: ::std::primitive::u32 = 64u32;
+    #[doc = r" Dispatch covering `elements` invocations along x, see"]
+    #[doc = r" [`Dispatch::try_linear`](::shatter::__abi::Dispatch::try_linear)."]
+    pub fn checked_dispatch(
+        elements: ::std::primitive::u64,
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_linear(elements, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Dispatch covering `extent` invocations along each axis, see"]
+    #[doc = r" [`Dispatch::try_for_extent`](::shatter::__abi::Dispatch::try_for_extent)."]
+    pub fn checked_dispatch_for_extent(
+        extent: [::std::primitive::u64; 3],
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_for_extent(extent, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Like [`checked_dispatch`], panics if the dispatch is too large."]
+    pub fn dispatch_for(elements: ::std::primitive::u64) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch(elements) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "bias", err),
+        }
+    }
+    #[doc = r" Like [`checked_dispatch_for_extent`], panics if the dispatch is too large."]
+    pub fn dispatch_for_extent(extent: [::std::primitive::u64; 3]) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch_for_extent(extent) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "bias", err),
+        }
+    }
+    pub use super::scale::{Bindings, OwnedBindings};
+    #[doc = r" Checks that `bindings` are compatible with the shader without dispatching."]
+    pub fn validate(
+        bindings: &Bindings<'_>,
+    ) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+        ::shatter::__abi::Bindings::validate(bindings)
+    }
+    pub struct Shader;
+    impl<'a> ::shatter::__abi::ComputeShader<'a> for Shader {
+        type Bindings = Bindings<'a>;
+        const SOURCE : & 'static :: std :: primitive :: str = "[[block ]]struct Values {values : array < u32 >; }; [[group (0 ), binding (0 )]]var < storage , read_write > values : Values ; [[stage (compute ), workgroup_size (64 )]]fn seed ([[builtin (global_invocation_id )]]id : vec3 < u32 > ){if (id . x < arrayLength (& values . values )){values . values [id . x ]= id . x * 3u ; }}[[stage (compute ), workgroup_size (64 )]]fn scale ([[builtin (global_invocation_id )]]id : vec3 < u32 > ){if (id . x < arrayLength (& values . values )){values . values [id . x ]= values . values [id . x ]* 2u ; }}[[stage (compute ), workgroup_size (64 )]]fn bias ([[builtin (global_invocation_id )]]id : vec3 < u32 > ){if (id . x < arrayLength (& values . values )){values . values [id . x ]= values . values [id . x ]+ 7u ; }}" ;
+        const ENTRY_POINT: &'static ::std::primitive::str = "bias";
+        const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = WORK_GROUP_SIZE;
+        type PushConstants = ::shatter::__abi::NoPushConstants;
+    }
+    impl<'a> ::shatter::__abi::DispatchableShader<Bindings<'a>> for Shader {
+        fn entry_point(&self) -> &'static ::std::primitive::str {
+            "bias"
+        }
+        fn work_group_size(&self) -> ::shatter::__abi::WorkGroupSize {
+            WORK_GROUP_SIZE
+        }
+        fn dispatch(&self, bindings: Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+            ::shatter::__abi::ComputeShaderBuilder::<'a, Shader>::new(bindings)
+                .tag("bias")
+                .dispatch(dispatch);
+        }
+    }
+    pub fn build<'a>(
+        mut bindings: bias::Bindings<'a>,
+    ) -> ::shatter::__abi::ComputeShaderBuilder<'a, Shader> {
+        ::shatter::__abi::ComputeShaderBuilder::new(bindings)
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub fn bias<'a>(mut bindings: bias::Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+    bias::build(bindings).tag("bias").dispatch(dispatch);
+}
+#[doc = r" Like the blocking function without waiting on the GPU, resolves once the"]
+#[doc = r" dispatch is done and what it wrote is downloaded. Takes the resources"]
+#[doc = r" by value so the future is `Send`, they are handed back when it resolves."]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub async fn bias_async<B0>(
+    mut bindings: bias::OwnedBindings<B0>,
+    dispatch: ::shatter::__abi::Dispatch,
+) -> bias::OwnedBindings<B0>
+where
+    B0: ::shatter::__abi::BindingMut<Values> + ::std::marker::Send,
+{
+    let work = bias::build(bindings.as_bindings())
+        .tag("bias")
+        .dispatch_async(dispatch);
+    work.await;
+    if let ::std::result::Result::Err(err) = bindings.download_async().await {
+        ::std::panic!("{}", err);
+    }
+    bindings
+}
+#[doc = "Entry points sharing `scale::Bindings`, selected at runtime."]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ShaderSelect {
+    Scale,
+    Bias,
+}
+impl ShaderSelect {
+    #[doc = r" Every entry point, in declaration order."]
+    pub const ALL: &'static [Self] = &[Self::Scale, Self::Bias];
+    pub const fn entry_point(self) -> &'static ::std::primitive::str {
+        match self {
+            Self::Scale => "scale",
+            Self::Bias => "bias",
+        }
+    }
+    pub fn shader<'a>(self) -> &'a dyn ::shatter::__abi::DispatchableShader<scale::Bindings<'a>> {
+        match self {
+            Self::Scale => &scale::Shader,
+            Self::Bias => &bias::Shader,
+        }
+    }
+}
+#[doc = r" Dispatches the selected entry point."]
+pub fn dispatch_selected<'a>(
+    select: ShaderSelect,
+    bindings: scale::Bindings<'a>,
+    dispatch: ::shatter::__abi::Dispatch,
+) {
+    match select {
+        ShaderSelect::Scale => scale(bindings, dispatch),
+        ShaderSelect::Bias => bias(bindings, dispatch),
+    }
+}
+#[doc = r" Dispatches every selected entry point in order, in a single compute"]
+#[doc = r" pass of a single submission."]
+#[doc = r""]
+#[doc = r" The entry points share their bind groups, so they're bound once and"]
+#[doc = r" only the pipeline changes between dispatches."]
+pub fn dispatch_selected_batch<'a>(
+    bindings: scale::Bindings<'a>,
+    batch: &[(ShaderSelect, ::shatter::__abi::Dispatch)],
+) {
+    let batch = batch
+        .iter()
+        .map(|&(select, dispatch)| {
+            let shader = match select {
+                ShaderSelect::Scale => ::shatter::__abi::BatchShader::of::<scale::Shader>(),
+                ShaderSelect::Bias => ::shatter::__abi::BatchShader::of::<bias::Shader>(),
+            };
+            (shader, dispatch)
+        })
+        .collect::<::std::vec::Vec<_>>();
+    ::shatter::__abi::dispatch_shared_batch("dispatch_selected_batch", bindings, &batch);
+}
+#[doc = r" The resources of every pass of [`Pipeline`]."]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod pipeline {
+    use super::*;
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct Bindings<'a> {
+        pub values: &'a mut dyn ::shatter::__abi::BindingMut<Values>,
+    }
+    impl<'a> Bindings<'a> {
+        #[doc = r" Borrows every binding from `registry` by its WGSL name,"]
+        #[doc = r" reporting every missing or mismatched resource."]
+        pub fn from_registry(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            Self::from_registry_renamed(registry, &[])
+        }
+        #[doc = r" Like [`Self::from_registry`], `rename` maps WGSL names to"]
+        #[doc = r" the names resources are stored under."]
+        pub fn from_registry_renamed(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+            rename: &[(&::std::primitive::str, &::std::primitive::str)],
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            let mut borrows = registry.borrow_bindings(
+                &[::shatter::__abi::RegistryRequest::new::<Values>(
+                    "values", true, false,
+                )],
+                rename,
+            )?;
+            ::std::result::Result::Ok(Self {
+                values: borrows.take_mut::<Values>(0usize),
+            })
+        }
+        pub fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            ::std::vec![::shatter::__abi::BindGroupLayoutDescriptor {
+                entries: ::std::vec![::shatter::__abi::BindGroupLayoutEntry {
+                    binding: 0u32,
+                    visibility: ::shatter::__abi::ShaderStages::COMPUTE,
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: ::std::option::Option::None,
+                }],
+            }]
+        }
+        pub fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            let mut layouts = layouts.iter();
+            ::std::vec![::shatter::__abi::BindGroupDescriptor {
+                layout: layouts.next().unwrap().clone(),
+                entries: ::std::vec![::shatter::__abi::BindGroupEntry {
+                    binding: 0u32 as u32,
+                    resource: ::shatter::__abi::Binding::binding_resource(self.values),
+                }],
+            }]
+        }
+        pub fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            let mut layouts = layouts.iter();
+            let mut keys = ::shatter::__abi::GroupKeys::new();
+            keys.push({
+                let mut key = ::shatter::__abi::GroupKey::new(layouts.next().unwrap());
+                key.push(
+                    0u32 as u32,
+                    &::shatter::__abi::Binding::binding_resource(self.values),
+                );
+                key
+            });
+            keys
+        }
+    }
+    impl<'a> ::shatter::__abi::Bindings for Bindings<'a> {
+        #[inline]
+        fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            self.bind_group_layout_descriptors()
+        }
+        #[inline]
+        fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            self.bind_group_descriptors(layouts)
+        }
+        #[inline]
+        fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            self.bind_group_keys(layouts)
+        }
+        fn validate(&self) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+            let mut bindings = ::std::vec::Vec::new();
+            bindings.push((
+                ::shatter::__abi::BindingExpectation {
+                    name: "values",
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    min_size: 4u64,
+                    writable: true,
+                },
+                ::shatter::__abi::Binding::resource_info(self.values),
+            ));
+            ::shatter::__abi::validate_bindings(&bindings)
+        }
+        #[inline]
+        fn prepare(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::prepare(self.values, report);
+        }
+        #[inline]
+        fn read(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::read(self.values, report);
+        }
+        #[inline]
+        fn write(&mut self) {
+            ::shatter::__abi::BindingMut::write(self.values);
+        }
+        #[inline]
+        fn needs_allocation(&self) -> bool {
+            ::shatter::__abi::Binding::needs_allocation(self.values)
+        }
+    }
+    #[doc = r" Like [`Bindings`] owning the resources, taken by the `_async`"]
+    #[doc = r" dispatch so its future is `Send` and handed back once it's done."]
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct OwnedBindings<B0> {
+        pub values: B0,
+    }
+    impl<B0> OwnedBindings<B0>
+    where
+        B0: ::shatter::__abi::BindingMut<Values>,
+    {
+        #[doc = r" Borrows the resources as [`Bindings`]."]
+        pub fn as_bindings(&mut self) -> Bindings<'_> {
+            Bindings {
+                values: &mut self.values,
+            }
+        }
+        #[doc = r" Downloads what the GPU wrote to the writable resources"]
+        #[doc = r" without blocking, returns the number of bytes downloaded."]
+        pub async fn download_async(
+            &mut self,
+        ) -> ::std::result::Result<::std::primitive::u64, ::shatter::__abi::Error> {
+            ::std::result::Result::Ok(
+                0 + ::shatter::__abi::Binding::<Values>::download_async(&mut self.values).await?,
+            )
+        }
+    }
+}
+#[doc = "Dispatches `seed` -> `scale` -> `bias` in order, see [`Pipeline::run`]."]
+pub struct Pipeline;
+impl Pipeline {
+    #[doc = r" The entry points of the passes, in the order they run."]
+    pub const PASSES: &'static [&'static ::std::primitive::str] = &["seed", "scale", "bias"];
+    #[doc = r" Runs every pass in a single submission, `dispatches[i]` dispatching"]
+    #[doc = r" the pass `PASSES[i]`."]
+    #[doc = r""]
+    #[doc = r" The passes borrow their resources from `bindings`, which are"]
+    #[doc = r" marked as written once every pass is submitted."]
+    pub fn run(
+        mut bindings: pipeline::Bindings<'_>,
+        dispatches: [::shatter::__abi::Dispatch; 3usize],
+    ) {
+        ::shatter::__abi::submit_encoded("shatter_command_encoder(Pipeline::run)", |encoder| {
+            seed::build(seed::Bindings {
+                values: &mut *bindings.values,
+            })
+            .dispatch_in(encoder, dispatches[0usize]);
+            scale::build(scale::Bindings {
+                values: &mut *bindings.values,
+            })
+            .dispatch_in(encoder, dispatches[1usize]);
+            bias::build(bias::Bindings {
+                values: &mut *bindings.values,
+            })
+            .dispatch_in(encoder, dispatches[2usize]);
+        });
+        ::shatter::__abi::Bindings::write(&mut bindings);
+    }
+}
@@ -0,0 +1,245 @@
+const _: () = ::std::assert!(
+    ::shatter::ABI_VERSION == 32u32,
+    "shatter-macro 0.0.1 requires shatter ABI 32, update the shatter crate"
+);
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub struct Tint {
+    pub color: ::shatter::__abi::Vec4<::std::primitive::f32>,
+}
+const _: () = {
+    ::shatter::__abi::assert_layout(
+        "Tint::color",
+        "offset",
+        0,
+        ::std::mem::offset_of!(Tint, color),
+    );
+    ::shatter::__abi::assert_layout("Tint", "size", 16, ::std::mem::size_of::<Tint>());
+};
+impl ::std::default::Default for Tint {
+    #[inline]
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+unsafe impl ::shatter::__abi::BufferData for Tint {
+    type State = ();
+    type Contents = Self;
+    const NEEDS_DROP: bool = false;
+    fn init() -> Self::State {}
+    fn size(_: &Self::State) -> usize {
+        ::std::mem::size_of::<Tint>()
+    }
+    unsafe fn alloc() -> ::std::ptr::NonNull<u8> {
+        if ::std::mem::size_of::<Tint>() == 0 {
+            return ::std::ptr::NonNull::<Tint>::dangling().cast();
+        }
+        let layout = ::std::alloc::Layout::new::<Tint>();
+        let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+        let ptr = match ::std::ptr::NonNull::new(ptr) {
+            ::std::option::Option::Some(ptr) => ptr,
+            ::std::option::Option::None => ::std::alloc::handle_alloc_error(layout),
+        };
+        ::shatter::__abi::record_alloc::<Tint>(ptr, layout);
+        ptr
+    }
+    unsafe fn alloc_zeroed(_: usize) -> (::std::ptr::NonNull<u8>, Self::State) {
+        if ::std::mem::size_of::<Tint>() == 0 {
+            return (::std::ptr::NonNull::<Tint>::dangling().cast(), ());
+        }
+        let layout = ::std::alloc::Layout::new::<Tint>();
+        let ptr = ::std::ptr::NonNull::new(unsafe { ::std::alloc::alloc_zeroed(layout) }).unwrap();
+        ::shatter::__abi::record_alloc::<Tint>(ptr, layout);
+        (ptr, ())
+    }
+    unsafe fn dealloc(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) {
+        let layout = ::std::alloc::Layout::new::<Tint>();
+        if layout.size() == 0 {
+            return;
+        }
+        ::shatter::__abi::record_dealloc::<Tint>(ptr, layout);
+        unsafe { ::std::alloc::dealloc(ptr.as_ptr(), layout) };
+    }
+    unsafe fn as_ptr(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) -> *mut Self {
+        ptr.as_ptr() as *mut Self
+    }
+    unsafe fn contents(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) -> Self {
+        unsafe { ::std::ptr::read(ptr.as_ptr() as *const Self) }
+    }
+}
+unsafe impl ::shatter::__abi::Reflect for Tint {
+    const LAYOUT: ::shatter::__abi::StructLayout = ::shatter::__abi::StructLayout {
+        name: ::std::borrow::Cow::Borrowed("Tint"),
+        size: ::std::mem::size_of::<Tint>() as u64,
+        fields: ::std::borrow::Cow::Borrowed(&[::shatter::__abi::FieldLayout {
+            name: ::std::borrow::Cow::Borrowed("color"),
+            ty: ::std::borrow::Cow::Borrowed("vec4<f32>"),
+            offset: ::std::mem::offset_of!(Tint, color) as u64,
+            size: ::std::mem::size_of::<::shatter::__abi::Vec4<::std::primitive::f32>>() as u64,
+        }]),
+    };
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod shade {
+    use super::*;
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct Bindings<'a> {
+        pub tint: &'a dyn ::shatter::__abi::Binding<Tint>,
+    }
+    impl<'a> Bindings<'a> {
+        #[doc = r" Borrows every binding from `registry` by its WGSL name,"]
+        #[doc = r" reporting every missing or mismatched resource."]
+        pub fn from_registry(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            Self::from_registry_renamed(registry, &[])
+        }
+        #[doc = r" Like [`Self::from_registry`], `rename` maps WGSL names to"]
+        #[doc = r" the names resources are stored under."]
+        pub fn from_registry_renamed(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+            rename: &[(&::std::primitive::str, &::std::primitive::str)],
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            let mut borrows = registry.borrow_bindings(
+                &[::shatter::__abi::RegistryRequest::new::<Tint>(
+                    "tint", false, false,
+                )],
+                rename,
+            )?;
+            ::std::result::Result::Ok(Self {
+                tint: borrows.take::<Tint>(0usize),
+            })
+        }
+        pub fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            ::std::vec![::shatter::__abi::BindGroupLayoutDescriptor {
+                entries: ::std::vec![::shatter::__abi::BindGroupLayoutEntry {
+                    binding: 0u32,
+                    visibility: ::shatter::__abi::ShaderStages::VERTEX_FRAGMENT,
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: ::std::option::Option::None,
+                }],
+            }]
+        }
+        pub fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            let mut layouts = layouts.iter();
+            ::std::vec![::shatter::__abi::BindGroupDescriptor {
+                layout: layouts.next().unwrap().clone(),
+                entries: ::std::vec![::shatter::__abi::BindGroupEntry {
+                    binding: 0u32 as u32,
+                    resource: ::shatter::__abi::Binding::binding_resource(self.tint),
+                }],
+            }]
+        }
+        pub fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            let mut layouts = layouts.iter();
+            let mut keys = ::shatter::__abi::GroupKeys::new();
+            keys.push({
+                let mut key = ::shatter::__abi::GroupKey::new(layouts.next().unwrap());
+                key.push(
+                    0u32 as u32,
+                    &::shatter::__abi::Binding::binding_resource(self.tint),
+                );
+                key
+            });
+            keys
+        }
+    }
+    impl<'a> ::shatter::__abi::Bindings for Bindings<'a> {
+        #[inline]
+        fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            self.bind_group_layout_descriptors()
+        }
+        #[inline]
+        fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            self.bind_group_descriptors(layouts)
+        }
+        #[inline]
+        fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            self.bind_group_keys(layouts)
+        }
+        fn validate(&self) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+            let mut bindings = ::std::vec::Vec::new();
+            bindings.push((
+                ::shatter::__abi::BindingExpectation {
+                    name: "tint",
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    min_size: 16u64,
+                    writable: false,
+                },
+                ::shatter::__abi::Binding::resource_info(self.tint),
+            ));
+            ::shatter::__abi::validate_bindings(&bindings)
+        }
+        #[inline]
+        fn prepare(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::prepare(self.tint, report);
+        }
+        #[inline]
+        fn read(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::read(self.tint, report);
+        }
+        #[inline]
+        fn write(&mut self) {}
+        #[inline]
+        fn needs_allocation(&self) -> bool {
+            ::shatter::__abi::Binding::needs_allocation(self.tint)
+        }
+    }
+    #[doc = r" Like [`Bindings`] owning the resources, taken by the `_async`"]
+    #[doc = r" dispatch so its future is `Send` and handed back once it's done."]
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct OwnedBindings<B0> {
+        pub tint: B0,
+    }
+    impl<B0> OwnedBindings<B0>
+    where
+        B0: ::shatter::__abi::Binding<Tint>,
+    {
+        #[doc = r" Borrows the resources as [`Bindings`]."]
+        pub fn as_bindings(&mut self) -> Bindings<'_> {
+            Bindings { tint: &self.tint }
+        }
+        #[doc = r" Downloads what the GPU wrote to the writable resources"]
+        #[doc = r" without blocking, returns the number of bytes downloaded."]
+        pub async fn download_async(
+            &mut self,
+        ) -> ::std::result::Result<::std::primitive::u64, ::shatter::__abi::Error> {
+            ::std::result::Result::Ok(0)
+        }
+    }
+    pub struct Shader;
+    impl<'a> ::shatter::__abi::RenderShader<'a> for Shader {
+        type Bindings = Bindings<'a>;
+        const SOURCE : & 'static :: std :: primitive :: str = "[[block ]]struct Tint {color : vec4 < f32 >; }; [[group (0 ), binding (0 )]]var < uniform > tint : Tint ; [[stage (vertex )]]fn fullscreen ([[builtin (vertex_index )]]index : u32 )-> [[builtin (position )]]vec4 < f32 > {let uv = vec2 < f32 > (f32 ((index << 1u )& 2u ), f32 (index & 2u )); return vec4 < f32 > (uv * 2.0 - 1.0 , 0.0 , 1.0 ); }[[stage (fragment )]]fn shade ()-> [[location (0 )]]vec4 < f32 > {return tint . color ; }" ;
+        const VERTEX_ENTRY_POINT: &'static ::std::primitive::str = "fullscreen";
+        const FRAGMENT_ENTRY_POINT: &'static ::std::primitive::str = "shade";
+    }
+    pub fn build<'a>(bindings: Bindings<'a>) -> ::shatter::__abi::RenderShaderBuilder<'a, Shader> {
+        ::shatter::__abi::RenderShaderBuilder::new(bindings)
+    }
+}
@@ -0,0 +1,587 @@
+const _: () = ::std::assert!(
+    ::shatter::ABI_VERSION == 32u32,
+    "shatter-macro 0.0.1 requires shatter ABI 32, update the shatter crate"
+);
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub struct Body {
+    pub position: ::shatter::__abi::PackedVec3<::std::primitive::f32>,
+    pub mass: ::std::primitive::f32,
+    pub transform: ::shatter::__abi::Mat3<::std::primitive::f32>,
+    pub weight: ::std::primitive::f32,
+    _pad0: [::std::primitive::u8; 12],
+    pub scale: ::shatter::__abi::Vec2<::std::primitive::f32>,
+    pub flags: ::std::primitive::u32,
+    pub normal: ::shatter::__abi::Vec3<::std::primitive::f32>,
+}
+impl ::std::fmt::Debug for Body {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        f.debug_struct("Body")
+            .field("position", &self.position)
+            .field("mass", &self.mass)
+            .field("transform", &self.transform)
+            .field("weight", &self.weight)
+            .field("scale", &self.scale)
+            .field("flags", &self.flags)
+            .field("normal", &self.normal)
+            .finish()
+    }
+}
+impl ::std::cmp::PartialEq for Body {
+    #[inline]
+    fn eq(&self, other: &Self) -> ::std::primitive::bool {
+        true && self.position == other.position
+            && self.mass == other.mass
+            && self.transform == other.transform
+            && self.weight == other.weight
+            && self.scale == other.scale
+            && self.flags == other.flags
+            && self.normal == other.normal
+    }
+}
+const _: () = {
+    ::shatter::__abi::assert_layout(
+        "Body::position",
+        "offset",
+        0,
+        ::std::mem::offset_of!(Body, position),
+    );
+    ::shatter::__abi::assert_layout(
+        "Body::mass",
+        "offset",
+        12,
+        ::std::mem::offset_of!(Body, mass),
+    );
+    ::shatter::__abi::assert_layout(
+        "Body::transform",
+        "offset",
+        16,
+        ::std::mem::offset_of!(Body, transform),
+    );
+    ::shatter::__abi::assert_layout(
+        "Body::weight",
+        "offset",
+        64,
+        ::std::mem::offset_of!(Body, weight),
+    );
+    ::shatter::__abi::assert_layout(
+        "Body::scale",
+        "offset",
+        80,
+        ::std::mem::offset_of!(Body, scale),
+    );
+    ::shatter::__abi::assert_layout(
+        "Body::flags",
+        "offset",
+        88,
+        ::std::mem::offset_of!(Body, flags),
+    );
+    ::shatter::__abi::assert_layout(
+        "Body::normal",
+        "offset",
+        96,
+        ::std::mem::offset_of!(Body, normal),
+    );
+    ::shatter::__abi::assert_layout("Body", "size", 112, ::std::mem::size_of::<Body>());
+};
+impl ::std::default::Default for Body {
+    #[inline]
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+unsafe impl ::shatter::__abi::Reflect for Body {
+    const LAYOUT: ::shatter::__abi::StructLayout = ::shatter::__abi::StructLayout {
+        name: ::std::borrow::Cow::Borrowed("Body"),
+        size: ::std::mem::size_of::<Body>() as u64,
+        fields: ::std::borrow::Cow::Borrowed(&[
+            ::shatter::__abi::FieldLayout {
+                name: ::std::borrow::Cow::Borrowed("position"),
+                ty: ::std::borrow::Cow::Borrowed("vec3<f32>"),
+                offset: ::std::mem::offset_of!(Body, position) as u64,
+                size: ::std::mem::size_of::<::shatter::__abi::Vec3<::std::primitive::f32>>() as u64,
+            },
+            ::shatter::__abi::FieldLayout {
+                name: ::std::borrow::Cow::Borrowed("mass"),
+                ty: ::std::borrow::Cow::Borrowed("f32"),
+                offset: ::std::mem::offset_of!(Body, mass) as u64,
+                size: ::std::mem::size_of::<::std::primitive::f32>() as u64,
+            },
+            ::shatter::__abi::FieldLayout {
+                name: ::std::borrow::Cow::Borrowed("transform"),
+                ty: ::std::borrow::Cow::Borrowed("mat3x3<f32>"),
+                offset: ::std::mem::offset_of!(Body, transform) as u64,
+                size: ::std::mem::size_of::<::shatter::__abi::Mat3<::std::primitive::f32>>() as u64,
+            },
+            ::shatter::__abi::FieldLayout {
+                name: ::std::borrow::Cow::Borrowed("weight"),
+                ty: ::std::borrow::Cow::Borrowed("f32"),
+                offset: ::std::mem::offset_of!(Body, weight) as u64,
+                size: ::std::mem::size_of::<::std::primitive::f32>() as u64,
+            },
+            ::shatter::__abi::FieldLayout {
+                name: ::std::borrow::Cow::Borrowed("scale"),
+                ty: ::std::borrow::Cow::Borrowed("vec2<f32>"),
+                offset: ::std::mem::offset_of!(Body, scale) as u64,
+                size: ::std::mem::size_of::<::shatter::__abi::Vec2<::std::primitive::f32>>() as u64,
+            },
+            ::shatter::__abi::FieldLayout {
+                name: ::std::borrow::Cow::Borrowed("flags"),
+                ty: ::std::borrow::Cow::Borrowed("u32"),
+                offset: ::std::mem::offset_of!(Body, flags) as u64,
+                size: ::std::mem::size_of::<::std::primitive::u32>() as u64,
+            },
+            ::shatter::__abi::FieldLayout {
+                name: ::std::borrow::Cow::Borrowed("normal"),
+                ty: ::std::borrow::Cow::Borrowed("vec3<f32>"),
+                offset: ::std::mem::offset_of!(Body, normal) as u64,
+                size: ::std::mem::size_of::<::shatter::__abi::Vec3<::std::primitive::f32>>() as u64,
+            },
+        ]),
+    };
+}
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub struct Bodies {
+    pub bodies: [Body],
+}
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub struct Bodies_Sized {
+    pub bodies: [Body; 0],
+}
+const _: () = {
+    ::shatter::__abi::assert_layout(
+        "Bodies::bodies",
+        "offset",
+        0,
+        ::std::mem::offset_of!(Bodies_Sized, bodies),
+    );
+    ::shatter::__abi::assert_layout(
+        "Bodies::bodies",
+        "stride",
+        112,
+        ::std::mem::size_of::<Body>(),
+    );
+    ::shatter::__abi::assert_layout(
+        "Bodies",
+        "header size",
+        0,
+        ::std::mem::size_of::<Bodies_Sized>(),
+    );
+    ::shatter::__abi::assert_layout(
+        "Bodies::bodies",
+        "element alignment",
+        16,
+        ::std::mem::align_of::<Body>(),
+    );
+};
+impl ::std::default::Default for Bodies_Sized {
+    #[inline]
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+unsafe impl ::shatter::__abi::BufferData for Bodies {
+    type State = (usize, usize);
+    type Contents = ::std::vec::Vec<Body>;
+    const NEEDS_DROP: bool = false;
+    fn init() -> Self::State {
+        let cap = if ::std::mem::size_of::<Body>() == 0 {
+            !0
+        } else {
+            0
+        };
+        (0, cap)
+    }
+    fn size(&(length, _capacity): &Self::State) -> usize {
+        ::std::mem::size_of::<Bodies_Sized>() + length * ::std::mem::size_of::<Body>()
+    }
+    unsafe fn alloc() -> ::std::ptr::NonNull<u8> {
+        let (_, capacity) = Self::init();
+        let layout = ::shatter::__abi::buffer_vec_layout::<Bodies_Sized, Body>(capacity);
+        if layout.size() == 0 {
+            return ::std::ptr::NonNull::<Bodies_Sized>::dangling().cast();
+        }
+        let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+        let ptr = match ::std::ptr::NonNull::new(ptr) {
+            ::std::option::Option::Some(ptr) => ptr,
+            ::std::option::Option::None => ::std::alloc::handle_alloc_error(layout),
+        };
+        ::shatter::__abi::record_alloc::<Bodies>(ptr, layout);
+        ptr
+    }
+    unsafe fn alloc_zeroed(size: usize) -> (::std::ptr::NonNull<u8>, Self::State) {
+        if ::std::mem::size_of::<Body>() == 0 {
+            return (unsafe { Self::alloc() }, Self::init());
+        }
+        let length = size.saturating_sub(::std::mem::size_of::<Bodies_Sized>())
+            / ::std::mem::size_of::<Body>();
+        let layout = ::shatter::__abi::buffer_vec_layout::<Bodies_Sized, Body>(length);
+        if layout.size() == 0 {
+            return (
+                ::std::ptr::NonNull::<Bodies_Sized>::dangling().cast(),
+                (0, 0),
+            );
+        }
+        let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+        let ptr = match ::std::ptr::NonNull::new(ptr) {
+            Some(ptr) => ptr,
+            None => ::std::alloc::handle_alloc_error(layout),
+        };
+        ::shatter::__abi::record_alloc::<Bodies>(ptr, layout);
+        (ptr, (length, length))
+    }
+    unsafe fn dealloc(ptr: ::std::ptr::NonNull<u8>, &(_length, capacity): &Self::State) {
+        let layout = ::shatter::__abi::buffer_vec_layout::<Bodies_Sized, Body>(capacity);
+        if layout.size() == 0 {
+            return;
+        }
+        ::shatter::__abi::record_dealloc::<Bodies>(ptr, layout);
+        unsafe { ::std::alloc::dealloc(ptr.as_ptr(), layout) };
+    }
+    unsafe fn as_ptr(
+        ptr: ::std::ptr::NonNull<u8>,
+        &(length, _capacity): &Self::State,
+    ) -> *mut Self {
+        let slice = unsafe { ::std::slice::from_raw_parts_mut(ptr.as_ptr(), length) };
+        unsafe { ::std::mem::transmute(slice as *mut [u8]) }
+    }
+    unsafe fn contents(
+        ptr: ::std::ptr::NonNull<u8>,
+        &(length, _capacity): &Self::State,
+    ) -> Self::Contents {
+        let layout = ::std::alloc::Layout::new::<Bodies_Sized>();
+        let items = unsafe { ptr.as_ptr().add(layout.size()) as *const Body };
+        (0..length)
+            .map(|i| unsafe { ::std::ptr::read(items.add(i)) })
+            .collect()
+    }
+}
+unsafe impl ::shatter::__abi::BufferVec for Bodies {
+    type Head = ();
+    type Item = Body;
+    const ITEM_STRIDE: usize = 112;
+    const ITEM_ALIGN: usize = 16;
+    fn len(&(length, _): &Self::State) -> usize {
+        length
+    }
+    fn capacity(&(_, capacity): &Self::State) -> usize {
+        capacity
+    }
+    unsafe fn grow(ptr: &mut ::std::ptr::NonNull<u8>, state: &mut Self::State) {
+        let additional = state.1 - state.0 + 1;
+        unsafe { Self::reserve(ptr, state, additional) };
+    }
+    unsafe fn reserve(
+        ptr: &mut ::std::ptr::NonNull<u8>,
+        &mut (length, ref mut capacity): &mut Self::State,
+        additional: usize,
+    ) {
+        let required = length.checked_add(additional).expect("capacity overflow");
+        if required <= *capacity {
+            return;
+        }
+        assert!(
+            ::std::mem::size_of::<Self::Item>() != 0,
+            "capacity overflow"
+        );
+        let new_cap = required.max(capacity.saturating_mul(2));
+        let old_layout = ::shatter::__abi::buffer_vec_layout::<Bodies_Sized, Body>(*capacity);
+        let new_layout = ::shatter::__abi::buffer_vec_layout::<Bodies_Sized, Body>(new_cap);
+        ::shatter::__abi::fault_point(::shatter::__abi::FaultPoint::Grow);
+        let new_ptr = if old_layout.size() == 0 {
+            unsafe { ::std::alloc::alloc(new_layout) }
+        } else {
+            unsafe { ::std::alloc::realloc(ptr.as_ptr(), old_layout, new_layout.size()) }
+        };
+        let new_ptr = match ::std::ptr::NonNull::new(new_ptr) {
+            Some(ptr) => ptr,
+            None => ::std::alloc::handle_alloc_error(new_layout),
+        };
+        let old_ptr = ::std::mem::replace(ptr, new_ptr);
+        *capacity = new_cap;
+        ::shatter::__abi::record_realloc::<Bodies>(old_ptr, old_layout, new_ptr, new_layout);
+    }
+    unsafe fn push(ptr: &mut ::std::ptr::NonNull<u8>, state: &mut Self::State, item: Self::Item) {
+        if state.0 == state.1 {
+            unsafe { Self::grow(ptr, state) };
+        }
+        ::shatter::__abi::fault_point(::shatter::__abi::FaultPoint::Push);
+        unsafe { ::std::ptr::write(Self::items(*ptr, state).add(state.0), item) };
+        state.0 += 1;
+    }
+    unsafe fn pop(
+        ptr: ::std::ptr::NonNull<u8>,
+        state: &mut Self::State,
+    ) -> ::std::option::Option<Self::Item> {
+        if state.0 == 0 {
+            None
+        } else {
+            state.0 -= 1;
+            unsafe { Some(::std::ptr::read(Self::items(ptr, state).add(state.0))) }
+        }
+    }
+    unsafe fn set_len((length, _capacity): &mut Self::State, len: usize) {
+        *length = len;
+    }
+    unsafe fn items(ptr: ::std::ptr::NonNull<u8>, _state: &Self::State) -> *mut Self::Item {
+        unsafe { ptr.as_ptr().add(::std::mem::size_of::<Bodies_Sized>()) as *mut Self::Item }
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod integrate {
+    use super::*;
+    pub const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize =
+        ::shatter::__abi::WorkGroupSize::new(
+            64u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+        );
+    #[doc = r" Invocations per work group, the product of [`WORK_GROUP_SIZE`]."]
+    pub const WORK_GROUP_INVOCATIONS: ::std::primitive::u32 = 64u32;
+    #[doc = r" Dispatch covering `elements` invocations along x, see"]
+    #[doc = r" [`Dispatch::try_linear`](::shatter::__abi::Dispatch::try_linear)."]
+    pub fn checked_dispatch(
+        elements: ::std::primitive::u64,
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_linear(elements, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Dispatch covering `extent` invocations along each axis, see"]
+    #[doc = r" [`Dispatch::try_for_extent`](::shatter::__abi::Dispatch::try_for_extent)."]
+    pub fn checked_dispatch_for_extent(
+        extent: [::std::primitive::u64; 3],
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_for_extent(extent, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Like [`checked_dispatch`], panics if the dispatch is too large."]
+    pub fn dispatch_for(elements: ::std::primitive::u64) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch(elements) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "integrate", err),
+        }
+    }
+    #[doc = r" Like [`checked_dispatch_for_extent`], panics if the dispatch is too large."]
+    pub fn dispatch_for_extent(extent: [::std::primitive::u64; 3]) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch_for_extent(extent) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "integrate", err),
+        }
+    }
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct Bindings<'a> {
+        pub bodies: &'a mut dyn ::shatter::__abi::BindingMut<Bodies>,
+    }
+    impl<'a> Bindings<'a> {
+        #[doc = r" Borrows every binding from `registry` by its WGSL name,"]
+        #[doc = r" reporting every missing or mismatched resource."]
+        pub fn from_registry(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            Self::from_registry_renamed(registry, &[])
+        }
+        #[doc = r" Like [`Self::from_registry`], `rename` maps WGSL names to"]
+        #[doc = r" the names resources are stored under."]
+        pub fn from_registry_renamed(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+            rename: &[(&::std::primitive::str, &::std::primitive::str)],
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            let mut borrows = registry.borrow_bindings(
+                &[::shatter::__abi::RegistryRequest::new::<Bodies>(
+                    "bodies", true, false,
+                )],
+                rename,
+            )?;
+            ::std::result::Result::Ok(Self {
+                bodies: borrows.take_mut::<Bodies>(0usize),
+            })
+        }
+        pub fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            ::std::vec![::shatter::__abi::BindGroupLayoutDescriptor {
+                entries: ::std::vec![::shatter::__abi::BindGroupLayoutEntry {
+                    binding: 0u32,
+                    visibility: ::shatter::__abi::ShaderStages::COMPUTE,
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: ::std::option::Option::None,
+                }],
+            }]
+        }
+        pub fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            let mut layouts = layouts.iter();
+            ::std::vec![::shatter::__abi::BindGroupDescriptor {
+                layout: layouts.next().unwrap().clone(),
+                entries: ::std::vec![::shatter::__abi::BindGroupEntry {
+                    binding: 0u32 as u32,
+                    resource: ::shatter::__abi::Binding::binding_resource(self.bodies),
+                }],
+            }]
+        }
+        pub fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            let mut layouts = layouts.iter();
+            let mut keys = ::shatter::__abi::GroupKeys::new();
+            keys.push({
+                let mut key = ::shatter::__abi::GroupKey::new(layouts.next().unwrap());
+                key.push(
+                    0u32 as u32,
+                    &::shatter::__abi::Binding::binding_resource(self.bodies),
+                );
+                key
+            });
+            keys
+        }
+    }
+    impl<'a> ::shatter::__abi::Bindings for Bindings<'a> {
+        #[inline]
+        fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            self.bind_group_layout_descriptors()
+        }
+        #[inline]
+        fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            self.bind_group_descriptors(layouts)
+        }
+        #[inline]
+        fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            self.bind_group_keys(layouts)
+        }
+        fn validate(&self) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+            let mut bindings = ::std::vec::Vec::new();
+            bindings.push((
+                ::shatter::__abi::BindingExpectation {
+                    name: "bodies",
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    min_size: 112u64,
+                    writable: true,
+                },
+                ::shatter::__abi::Binding::resource_info(self.bodies),
+            ));
+            ::shatter::__abi::validate_bindings(&bindings)
+        }
+        #[inline]
+        fn prepare(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::prepare(self.bodies, report);
+        }
+        #[inline]
+        fn read(&self, report: &mut ::shatter::__abi::TransferReport) {
+            ::shatter::__abi::Binding::read(self.bodies, report);
+        }
+        #[inline]
+        fn write(&mut self) {
+            ::shatter::__abi::BindingMut::write(self.bodies);
+        }
+        #[inline]
+        fn needs_allocation(&self) -> bool {
+            ::shatter::__abi::Binding::needs_allocation(self.bodies)
+        }
+    }
+    #[doc = r" Like [`Bindings`] owning the resources, taken by the `_async`"]
+    #[doc = r" dispatch so its future is `Send` and handed back once it's done."]
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct OwnedBindings<B0> {
+        pub bodies: B0,
+    }
+    impl<B0> OwnedBindings<B0>
+    where
+        B0: ::shatter::__abi::BindingMut<Bodies>,
+    {
+        #[doc = r" Borrows the resources as [`Bindings`]."]
+        pub fn as_bindings(&mut self) -> Bindings<'_> {
+            Bindings {
+                bodies: &mut self.bodies,
+            }
+        }
+        #[doc = r" Downloads what the GPU wrote to the writable resources"]
+        #[doc = r" without blocking, returns the number of bytes downloaded."]
+        pub async fn download_async(
+            &mut self,
+        ) -> ::std::result::Result<::std::primitive::u64, ::shatter::__abi::Error> {
+            ::std::result::Result::Ok(
+                0 + ::shatter::__abi::Binding::<Bodies>::download_async(&mut self.bodies).await?,
+            )
+        }
+    }
+    #[doc = r" Checks that `bindings` are compatible with the shader without dispatching."]
+    pub fn validate(
+        bindings: &Bindings<'_>,
+    ) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+        ::shatter::__abi::Bindings::validate(bindings)
+    }
+    pub struct Shader;
+    impl<'a> ::shatter::__abi::ComputeShader<'a> for Shader {
+        type Bindings = Bindings<'a>;
+        const SOURCE : & 'static :: std :: primitive :: str = "struct Body {position : vec3 < f32 >; mass : f32 ; transform : mat3x3 < f32 >; [[size (16 )]]weight : f32 ; scale : vec2 < f32 >; flags : u32 ; normal : vec3 < f32 >; }; [[block ]]struct Bodies {bodies : array < Body >; }; [[group (0 ), binding (0 )]]var < storage , read_write > bodies : Bodies ; [[stage (compute ), workgroup_size (64 )]]fn integrate ([[builtin (global_invocation_id )]]id : vec3 < u32 > ){if (id . x >= arrayLength (& bodies . bodies )){return ; }let body = bodies . bodies [id . x ]; bodies . bodies [id . x ]. position = body . transform * body . position * body . mass ; bodies . bodies [id . x ]. normal = body . normal * body . weight + vec3 < f32 > (body . scale , 0.0 ); bodies . bodies [id . x ]. flags = body . flags + 1u ; }" ;
+        const ENTRY_POINT: &'static ::std::primitive::str = "integrate";
+        const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = WORK_GROUP_SIZE;
+        type PushConstants = ::shatter::__abi::NoPushConstants;
+    }
+    impl<'a> ::shatter::__abi::DispatchableShader<Bindings<'a>> for Shader {
+        fn entry_point(&self) -> &'static ::std::primitive::str {
+            "integrate"
+        }
+        fn work_group_size(&self) -> ::shatter::__abi::WorkGroupSize {
+            WORK_GROUP_SIZE
+        }
+        fn dispatch(&self, bindings: Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+            ::shatter::__abi::ComputeShaderBuilder::<'a, Shader>::new(bindings)
+                .tag("integrate")
+                .dispatch(dispatch);
+        }
+    }
+    pub fn build<'a>(
+        mut bindings: integrate::Bindings<'a>,
+    ) -> ::shatter::__abi::ComputeShaderBuilder<'a, Shader> {
+        ::shatter::__abi::ComputeShaderBuilder::new(bindings)
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub fn integrate<'a>(mut bindings: integrate::Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+    integrate::build(bindings)
+        .tag("integrate")
+        .dispatch(dispatch);
+}
+#[doc = r" Like the blocking function without waiting on the GPU, resolves once the"]
+#[doc = r" dispatch is done and what it wrote is downloaded. Takes the resources"]
+#[doc = r" by value so the future is `Send`, they are handed back when it resolves."]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub async fn integrate_async<B0>(
+    mut bindings: integrate::OwnedBindings<B0>,
+    dispatch: ::shatter::__abi::Dispatch,
+) -> integrate::OwnedBindings<B0>
+where
+    B0: ::shatter::__abi::BindingMut<Bodies> + ::std::marker::Send,
+{
+    let work = integrate::build(bindings.as_bindings())
+        .tag("integrate")
+        .dispatch_async(dispatch);
+    work.await;
+    if let ::std::result::Result::Err(err) = bindings.download_async().await {
+        ::std::panic!("{}", err);
+    }
+    bindings
+}
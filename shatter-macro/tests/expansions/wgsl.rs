@@ -0,0 +1,651 @@
+const _: () = ::std::assert!(
+    ::shatter::ABI_VERSION == 32u32,
+    "shatter-macro 0.0.1 requires shatter ABI 32, update the shatter crate"
+);
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub struct Particle {
+    pub position: ::shatter::__abi::Vec2<::std::primitive::f32>,
+    pub velocity: ::shatter::__abi::Vec2<::std::primitive::f32>,
+    pub radius: ::std::primitive::f32,
+}
+const _: () = {
+    ::shatter::__abi::assert_layout(
+        "Particle::position",
+        "offset",
+        0,
+        ::std::mem::offset_of!(Particle, position),
+    );
+    ::shatter::__abi::assert_layout(
+        "Particle::velocity",
+        "offset",
+        8,
+        ::std::mem::offset_of!(Particle, velocity),
+    );
+    ::shatter::__abi::assert_layout(
+        "Particle::radius",
+        "offset",
+        16,
+        ::std::mem::offset_of!(Particle, radius),
+    );
+    ::shatter::__abi::assert_layout("Particle", "size", 24, ::std::mem::size_of::<Particle>());
+};
+impl ::std::default::Default for Particle {
+    #[inline]
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+unsafe impl ::shatter::__abi::Reflect for Particle {
+    const LAYOUT: ::shatter::__abi::StructLayout = ::shatter::__abi::StructLayout {
+        name: ::std::borrow::Cow::Borrowed("Particle"),
+        size: ::std::mem::size_of::<Particle>() as u64,
+        fields: ::std::borrow::Cow::Borrowed(&[
+            ::shatter::__abi::FieldLayout {
+                name: ::std::borrow::Cow::Borrowed("position"),
+                ty: ::std::borrow::Cow::Borrowed("vec2<f32>"),
+                offset: ::std::mem::offset_of!(Particle, position) as u64,
+                size: ::std::mem::size_of::<::shatter::__abi::Vec2<::std::primitive::f32>>() as u64,
+            },
+            ::shatter::__abi::FieldLayout {
+                name: ::std::borrow::Cow::Borrowed("velocity"),
+                ty: ::std::borrow::Cow::Borrowed("vec2<f32>"),
+                offset: ::std::mem::offset_of!(Particle, velocity) as u64,
+                size: ::std::mem::size_of::<::shatter::__abi::Vec2<::std::primitive::f32>>() as u64,
+            },
+            ::shatter::__abi::FieldLayout {
+                name: ::std::borrow::Cow::Borrowed("radius"),
+                ty: ::std::borrow::Cow::Borrowed("f32"),
+                offset: ::std::mem::offset_of!(Particle, radius) as u64,
+                size: ::std::mem::size_of::<::std::primitive::f32>() as u64,
+            },
+        ]),
+    };
+}
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub struct Particles {
+    pub particles: [Particle],
+}
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub struct Particles_Sized {
+    pub particles: [Particle; 0],
+}
+const _: () = {
+    ::shatter::__abi::assert_layout(
+        "Particles::particles",
+        "offset",
+        0,
+        ::std::mem::offset_of!(Particles_Sized, particles),
+    );
+    ::shatter::__abi::assert_layout(
+        "Particles::particles",
+        "stride",
+        24,
+        ::std::mem::size_of::<Particle>(),
+    );
+    ::shatter::__abi::assert_layout(
+        "Particles",
+        "header size",
+        0,
+        ::std::mem::size_of::<Particles_Sized>(),
+    );
+    ::shatter::__abi::assert_layout(
+        "Particles::particles",
+        "element alignment",
+        8,
+        ::std::mem::align_of::<Particle>(),
+    );
+};
+impl ::std::default::Default for Particles_Sized {
+    #[inline]
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+unsafe impl ::shatter::__abi::BufferData for Particles {
+    type State = (usize, usize);
+    type Contents = ::std::vec::Vec<Particle>;
+    const NEEDS_DROP: bool = false;
+    fn init() -> Self::State {
+        let cap = if ::std::mem::size_of::<Particle>() == 0 {
+            !0
+        } else {
+            0
+        };
+        (0, cap)
+    }
+    fn size(&(length, _capacity): &Self::State) -> usize {
+        ::std::mem::size_of::<Particles_Sized>() + length * ::std::mem::size_of::<Particle>()
+    }
+    unsafe fn alloc() -> ::std::ptr::NonNull<u8> {
+        let (_, capacity) = Self::init();
+        let layout = ::shatter::__abi::buffer_vec_layout::<Particles_Sized, Particle>(capacity);
+        if layout.size() == 0 {
+            return ::std::ptr::NonNull::<Particles_Sized>::dangling().cast();
+        }
+        let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+        let ptr = match ::std::ptr::NonNull::new(ptr) {
+            ::std::option::Option::Some(ptr) => ptr,
+            ::std::option::Option::None => ::std::alloc::handle_alloc_error(layout),
+        };
+        ::shatter::__abi::record_alloc::<Particles>(ptr, layout);
+        ptr
+    }
+    unsafe fn alloc_zeroed(size: usize) -> (::std::ptr::NonNull<u8>, Self::State) {
+        if ::std::mem::size_of::<Particle>() == 0 {
+            return (unsafe { Self::alloc() }, Self::init());
+        }
+        let length = size.saturating_sub(::std::mem::size_of::<Particles_Sized>())
+            / ::std::mem::size_of::<Particle>();
+        let layout = ::shatter::__abi::buffer_vec_layout::<Particles_Sized, Particle>(length);
+        if layout.size() == 0 {
+            return (
+                ::std::ptr::NonNull::<Particles_Sized>::dangling().cast(),
+                (0, 0),
+            );
+        }
+        let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+        let ptr = match ::std::ptr::NonNull::new(ptr) {
+            Some(ptr) => ptr,
+            None => ::std::alloc::handle_alloc_error(layout),
+        };
+        ::shatter::__abi::record_alloc::<Particles>(ptr, layout);
+        (ptr, (length, length))
+    }
+    unsafe fn dealloc(ptr: ::std::ptr::NonNull<u8>, &(_length, capacity): &Self::State) {
+        let layout = ::shatter::__abi::buffer_vec_layout::<Particles_Sized, Particle>(capacity);
+        if layout.size() == 0 {
+            return;
+        }
+        ::shatter::__abi::record_dealloc::<Particles>(ptr, layout);
+        unsafe { ::std::alloc::dealloc(ptr.as_ptr(), layout) };
+    }
+    unsafe fn as_ptr(
+        ptr: ::std::ptr::NonNull<u8>,
+        &(length, _capacity): &Self::State,
+    ) -> *mut Self {
+        let slice = unsafe { ::std::slice::from_raw_parts_mut(ptr.as_ptr(), length) };
+        unsafe { ::std::mem::transmute(slice as *mut [u8]) }
+    }
+    unsafe fn contents(
+        ptr: ::std::ptr::NonNull<u8>,
+        &(length, _capacity): &Self::State,
+    ) -> Self::Contents {
+        let layout = ::std::alloc::Layout::new::<Particles_Sized>();
+        let items = unsafe { ptr.as_ptr().add(layout.size()) as *const Particle };
+        (0..length)
+            .map(|i| unsafe { ::std::ptr::read(items.add(i)) })
+            .collect()
+    }
+}
+unsafe impl ::shatter::__abi::BufferVec for Particles {
+    type Head = ();
+    type Item = Particle;
+    const ITEM_STRIDE: usize = 24;
+    const ITEM_ALIGN: usize = 8;
+    fn len(&(length, _): &Self::State) -> usize {
+        length
+    }
+    fn capacity(&(_, capacity): &Self::State) -> usize {
+        capacity
+    }
+    unsafe fn grow(ptr: &mut ::std::ptr::NonNull<u8>, state: &mut Self::State) {
+        let additional = state.1 - state.0 + 1;
+        unsafe { Self::reserve(ptr, state, additional) };
+    }
+    unsafe fn reserve(
+        ptr: &mut ::std::ptr::NonNull<u8>,
+        &mut (length, ref mut capacity): &mut Self::State,
+        additional: usize,
+    ) {
+        let required = length.checked_add(additional).expect("capacity overflow");
+        if required <= *capacity {
+            return;
+        }
+        assert!(
+            ::std::mem::size_of::<Self::Item>() != 0,
+            "capacity overflow"
+        );
+        let new_cap = required.max(capacity.saturating_mul(2));
+        let old_layout =
+            ::shatter::__abi::buffer_vec_layout::<Particles_Sized, Particle>(*capacity);
+        let new_layout = ::shatter::__abi::buffer_vec_layout::<Particles_Sized, Particle>(new_cap);
+        ::shatter::__abi::fault_point(::shatter::__abi::FaultPoint::Grow);
+        let new_ptr = if old_layout.size() == 0 {
+            unsafe { ::std::alloc::alloc(new_layout) }
+        } else {
+            unsafe { ::std::alloc::realloc(ptr.as_ptr(), old_layout, new_layout.size()) }
+        };
+        let new_ptr = match ::std::ptr::NonNull::new(new_ptr) {
+            Some(ptr) => ptr,
+            None => ::std::alloc::handle_alloc_error(new_layout),
+        };
+        let old_ptr = ::std::mem::replace(ptr, new_ptr);
+        *capacity = new_cap;
+        ::shatter::__abi::record_realloc::<Particles>(old_ptr, old_layout, new_ptr, new_layout);
+    }
+    unsafe fn push(ptr: &mut ::std::ptr::NonNull<u8>, state: &mut Self::State, item: Self::Item) {
+        if state.0 == state.1 {
+            unsafe { Self::grow(ptr, state) };
+        }
+        ::shatter::__abi::fault_point(::shatter::__abi::FaultPoint::Push);
+        unsafe { ::std::ptr::write(Self::items(*ptr, state).add(state.0), item) };
+        state.0 += 1;
+    }
+    unsafe fn pop(
+        ptr: ::std::ptr::NonNull<u8>,
+        state: &mut Self::State,
+    ) -> ::std::option::Option<Self::Item> {
+        if state.0 == 0 {
+            None
+        } else {
+            state.0 -= 1;
+            unsafe { Some(::std::ptr::read(Self::items(ptr, state).add(state.0))) }
+        }
+    }
+    unsafe fn set_len((length, _capacity): &mut Self::State, len: usize) {
+        *length = len;
+    }
+    unsafe fn items(ptr: ::std::ptr::NonNull<u8>, _state: &Self::State) -> *mut Self::Item {
+        unsafe { ptr.as_ptr().add(::std::mem::size_of::<Particles_Sized>()) as *mut Self::Item }
+    }
+}
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub struct Uniforms {
+    pub simulation_speed: ::std::primitive::f32,
+}
+const _: () = {
+    ::shatter::__abi::assert_layout(
+        "Uniforms::simulation_speed",
+        "offset",
+        0,
+        ::std::mem::offset_of!(Uniforms, simulation_speed),
+    );
+    ::shatter::__abi::assert_layout("Uniforms", "size", 4, ::std::mem::size_of::<Uniforms>());
+};
+impl ::std::default::Default for Uniforms {
+    #[inline]
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+unsafe impl ::shatter::__abi::BufferData for Uniforms {
+    type State = ();
+    type Contents = Self;
+    const NEEDS_DROP: bool = false;
+    fn init() -> Self::State {}
+    fn size(_: &Self::State) -> usize {
+        ::std::mem::size_of::<Uniforms>()
+    }
+    unsafe fn alloc() -> ::std::ptr::NonNull<u8> {
+        if ::std::mem::size_of::<Uniforms>() == 0 {
+            return ::std::ptr::NonNull::<Uniforms>::dangling().cast();
+        }
+        let layout = ::std::alloc::Layout::new::<Uniforms>();
+        let ptr = unsafe { ::std::alloc::alloc_zeroed(layout) };
+        let ptr = match ::std::ptr::NonNull::new(ptr) {
+            ::std::option::Option::Some(ptr) => ptr,
+            ::std::option::Option::None => ::std::alloc::handle_alloc_error(layout),
+        };
+        ::shatter::__abi::record_alloc::<Uniforms>(ptr, layout);
+        ptr
+    }
+    unsafe fn alloc_zeroed(_: usize) -> (::std::ptr::NonNull<u8>, Self::State) {
+        if ::std::mem::size_of::<Uniforms>() == 0 {
+            return (::std::ptr::NonNull::<Uniforms>::dangling().cast(), ());
+        }
+        let layout = ::std::alloc::Layout::new::<Uniforms>();
+        let ptr = ::std::ptr::NonNull::new(unsafe { ::std::alloc::alloc_zeroed(layout) }).unwrap();
+        ::shatter::__abi::record_alloc::<Uniforms>(ptr, layout);
+        (ptr, ())
+    }
+    unsafe fn dealloc(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) {
+        let layout = ::std::alloc::Layout::new::<Uniforms>();
+        if layout.size() == 0 {
+            return;
+        }
+        ::shatter::__abi::record_dealloc::<Uniforms>(ptr, layout);
+        unsafe { ::std::alloc::dealloc(ptr.as_ptr(), layout) };
+    }
+    unsafe fn as_ptr(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) -> *mut Self {
+        ptr.as_ptr() as *mut Self
+    }
+    unsafe fn contents(ptr: ::std::ptr::NonNull<u8>, _: &Self::State) -> Self {
+        unsafe { ::std::ptr::read(ptr.as_ptr() as *const Self) }
+    }
+}
+unsafe impl ::shatter::__abi::Reflect for Uniforms {
+    const LAYOUT: ::shatter::__abi::StructLayout = ::shatter::__abi::StructLayout {
+        name: ::std::borrow::Cow::Borrowed("Uniforms"),
+        size: ::std::mem::size_of::<Uniforms>() as u64,
+        fields: ::std::borrow::Cow::Borrowed(&[::shatter::__abi::FieldLayout {
+            name: ::std::borrow::Cow::Borrowed("simulation_speed"),
+            ty: ::std::borrow::Cow::Borrowed("f32"),
+            offset: ::std::mem::offset_of!(Uniforms, simulation_speed) as u64,
+            size: ::std::mem::size_of::<::std::primitive::f32>() as u64,
+        }]),
+    };
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub mod comp {
+    use super::*;
+    pub const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize =
+        ::shatter::__abi::WorkGroupSize::new(
+            1024u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+            1u32 as ::std::primitive::u32,
+        );
+    #[doc = r" Invocations per work group, the product of [`WORK_GROUP_SIZE`]."]
+    pub const WORK_GROUP_INVOCATIONS: ::std::primitive::u32 = 1024u32;
+    #[doc = r" Dispatch covering `elements` invocations along x, see"]
+    #[doc = r" [`Dispatch::try_linear`](::shatter::__abi::Dispatch::try_linear)."]
+    pub fn checked_dispatch(
+        elements: ::std::primitive::u64,
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_linear(elements, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Dispatch covering `extent` invocations along each axis, see"]
+    #[doc = r" [`Dispatch::try_for_extent`](::shatter::__abi::Dispatch::try_for_extent)."]
+    pub fn checked_dispatch_for_extent(
+        extent: [::std::primitive::u64; 3],
+    ) -> ::std::result::Result<::shatter::__abi::Dispatch, ::shatter::__abi::DispatchTooLarge> {
+        ::shatter::__abi::Dispatch::try_for_extent(extent, WORK_GROUP_SIZE)
+    }
+    #[doc = r" Like [`checked_dispatch`], panics if the dispatch is too large."]
+    pub fn dispatch_for(elements: ::std::primitive::u64) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch(elements) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "comp", err),
+        }
+    }
+    #[doc = r" Like [`checked_dispatch_for_extent`], panics if the dispatch is too large."]
+    pub fn dispatch_for_extent(extent: [::std::primitive::u64; 3]) -> ::shatter::__abi::Dispatch {
+        match checked_dispatch_for_extent(extent) {
+            ::std::result::Result::Ok(dispatch) => dispatch,
+            ::std::result::Result::Err(err) => ::std::panic!("`{}`: {}", "comp", err),
+        }
+    }
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct Bindings<'a> {
+        pub particles: &'a mut dyn ::shatter::__abi::BindingMut<Particles>,
+        pub uniforms: &'a dyn ::shatter::__abi::Binding<Uniforms>,
+    }
+    impl<'a> Bindings<'a> {
+        #[doc = r" Borrows every binding from `registry` by its WGSL name,"]
+        #[doc = r" reporting every missing or mismatched resource."]
+        pub fn from_registry(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            Self::from_registry_renamed(registry, &[])
+        }
+        #[doc = r" Like [`Self::from_registry`], `rename` maps WGSL names to"]
+        #[doc = r" the names resources are stored under."]
+        pub fn from_registry_renamed(
+            registry: &'a mut ::shatter::__abi::ResourceRegistry,
+            rename: &[(&::std::primitive::str, &::std::primitive::str)],
+        ) -> ::std::result::Result<Self, ::shatter::__abi::MissingResource> {
+            let mut borrows = registry.borrow_bindings(
+                &[
+                    ::shatter::__abi::RegistryRequest::new::<Particles>("particles", true, false),
+                    ::shatter::__abi::RegistryRequest::new::<Uniforms>("uniforms", false, false),
+                ],
+                rename,
+            )?;
+            ::std::result::Result::Ok(Self {
+                particles: borrows.take_mut::<Particles>(0usize),
+                uniforms: borrows.take::<Uniforms>(1usize),
+            })
+        }
+        pub fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            ::std::vec![::shatter::__abi::BindGroupLayoutDescriptor {
+                entries: ::std::vec![
+                    ::shatter::__abi::BindGroupLayoutEntry {
+                        binding: 0u32,
+                        visibility: ::shatter::__abi::ShaderStages::COMPUTE,
+                        ty: ::shatter::__abi::BindingType::Buffer {
+                            ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: ::std::option::Option::None,
+                    },
+                    ::shatter::__abi::BindGroupLayoutEntry {
+                        binding: 1u32,
+                        visibility: ::shatter::__abi::ShaderStages::COMPUTE,
+                        ty: ::shatter::__abi::BindingType::Buffer {
+                            ty: ::shatter::__abi::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: ::std::option::Option::None,
+                    }
+                ],
+            }]
+        }
+        pub fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            let mut layouts = layouts.iter();
+            ::std::vec![::shatter::__abi::BindGroupDescriptor {
+                layout: layouts.next().unwrap().clone(),
+                entries: ::std::vec![
+                    ::shatter::__abi::BindGroupEntry {
+                        binding: 0u32 as u32,
+                        resource: ::shatter::__abi::Binding::binding_resource(self.particles),
+                    },
+                    ::shatter::__abi::BindGroupEntry {
+                        binding: 1u32 as u32,
+                        resource: ::shatter::__abi::Binding::binding_resource(self.uniforms),
+                    }
+                ],
+            }]
+        }
+        pub fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            let mut layouts = layouts.iter();
+            let mut keys = ::shatter::__abi::GroupKeys::new();
+            keys.push({
+                let mut key = ::shatter::__abi::GroupKey::new(layouts.next().unwrap());
+                key.push(
+                    0u32 as u32,
+                    &::shatter::__abi::Binding::binding_resource(self.particles),
+                );
+                key.push(
+                    1u32 as u32,
+                    &::shatter::__abi::Binding::binding_resource(self.uniforms),
+                );
+                key
+            });
+            keys
+        }
+    }
+    impl<'a> ::shatter::__abi::Bindings for Bindings<'a> {
+        #[inline]
+        fn bind_group_layout_descriptors(
+            &self,
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupLayoutDescriptor> {
+            self.bind_group_layout_descriptors()
+        }
+        #[inline]
+        fn bind_group_descriptors(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::std::vec::Vec<::shatter::__abi::BindGroupDescriptor> {
+            self.bind_group_descriptors(layouts)
+        }
+        #[inline]
+        fn bind_group_keys(
+            &self,
+            layouts: &[::shatter::__abi::BindGroupLayoutId],
+        ) -> ::shatter::__abi::GroupKeys {
+            self.bind_group_keys(layouts)
+        }
+        fn validate(&self) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+            let mut bindings = ::std::vec::Vec::new();
+            bindings.push((
+                ::shatter::__abi::BindingExpectation {
+                    name: "particles",
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    min_size: 24u64,
+                    writable: true,
+                },
+                ::shatter::__abi::Binding::resource_info(self.particles),
+            ));
+            bindings.push((
+                ::shatter::__abi::BindingExpectation {
+                    name: "uniforms",
+                    ty: ::shatter::__abi::BindingType::Buffer {
+                        ty: ::shatter::__abi::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    min_size: 4u64,
+                    writable: false,
+                },
+                ::shatter::__abi::Binding::resource_info(self.uniforms),
+            ));
+            ::shatter::__abi::validate_bindings(&bindings)
+        }
+        #[inline]
+        fn prepare(&self, report: &mut ::shatter::__abi::TransferReport) {
+            let mut seen = ::shatter::__abi::SeenResources::new();
+            if seen.first_use(::shatter::__abi::Binding::binding_resource(self.particles)) {
+                ::shatter::__abi::Binding::prepare(self.particles, report);
+            }
+            if seen.first_use(::shatter::__abi::Binding::binding_resource(self.uniforms)) {
+                ::shatter::__abi::Binding::prepare(self.uniforms, report);
+            }
+        }
+        #[inline]
+        fn read(&self, report: &mut ::shatter::__abi::TransferReport) {
+            let mut seen = ::shatter::__abi::SeenResources::new();
+            if seen.first_use(::shatter::__abi::Binding::binding_resource(self.particles)) {
+                ::shatter::__abi::Binding::read(self.particles, report);
+            }
+            if seen.first_use(::shatter::__abi::Binding::binding_resource(self.uniforms)) {
+                ::shatter::__abi::Binding::read(self.uniforms, report);
+            }
+        }
+        #[inline]
+        fn write(&mut self) {
+            ::shatter::__abi::BindingMut::write(self.particles);
+        }
+        #[inline]
+        fn needs_allocation(&self) -> bool {
+            ::shatter::__abi::Binding::needs_allocation(self.particles)
+                || ::shatter::__abi::Binding::needs_allocation(self.uniforms)
+        }
+        #[inline]
+        fn guard_count(&self) -> ::std::option::Option<::std::primitive::u32> {
+            ::shatter::__abi::Binding::data_size(self.particles)
+                .map(|size| (size.saturating_sub(0u64) / 24u64) as ::std::primitive::u32)
+        }
+    }
+    #[doc = r" Like [`Bindings`] owning the resources, taken by the `_async`"]
+    #[doc = r" dispatch so its future is `Send` and handed back once it's done."]
+    #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+    pub struct OwnedBindings<B0, B1> {
+        pub particles: B0,
+        pub uniforms: B1,
+    }
+    impl<B0, B1> OwnedBindings<B0, B1>
+    where
+        B0: ::shatter::__abi::BindingMut<Particles>,
+        B1: ::shatter::__abi::Binding<Uniforms>,
+    {
+        #[doc = r" Borrows the resources as [`Bindings`]."]
+        pub fn as_bindings(&mut self) -> Bindings<'_> {
+            Bindings {
+                particles: &mut self.particles,
+                uniforms: &self.uniforms,
+            }
+        }
+        #[doc = r" Downloads what the GPU wrote to the writable resources"]
+        #[doc = r" without blocking, returns the number of bytes downloaded."]
+        pub async fn download_async(
+            &mut self,
+        ) -> ::std::result::Result<::std::primitive::u64, ::shatter::__abi::Error> {
+            ::std::result::Result::Ok(
+                0 + ::shatter::__abi::Binding::<Particles>::download_async(&mut self.particles)
+                    .await?,
+            )
+        }
+    }
+    #[doc = r" Checks that `bindings` are compatible with the shader without dispatching."]
+    pub fn validate(
+        bindings: &Bindings<'_>,
+    ) -> ::std::result::Result<(), ::shatter::__abi::ValidationReport> {
+        ::shatter::__abi::Bindings::validate(bindings)
+    }
+    pub struct Shader;
+    impl<'a> ::shatter::__abi::ComputeShader<'a> for Shader {
+        type Bindings = Bindings<'a>;
+        const SOURCE : & 'static :: std :: primitive :: str = "[[block ]]struct ShatterLaunch {guard_count : u32 ; }; [[group (1 ), binding (0 )]]var < uniform > shatter_launch : ShatterLaunch ; fn shatter_guard_count ()-> u32 {return shatter_launch . guard_count ; }struct Particle {position : vec2 < f32 >; velocity : vec2 < f32 >; radius : f32 ; }; [[block ]]struct Particles {particles : array < Particle >; }; [[group (0 ), binding (0 )]]var < storage , read_write > particles : Particles ; [[block ]]struct Uniforms {simulation_speed : f32 ; }; [[group (0 ), binding (1 )]]var < uniform > uniforms : Uniforms ; [[stage (compute ), workgroup_size (1024 , 1 , 1 )]]fn comp ([[builtin (global_invocation_id )]]param : vec3 < u32 > , [[builtin (num_workgroups )]]shatter_guard_groups : vec3 < u32 > ){if (param . x + (param . y + param . z * shatter_guard_groups . y * 1u )* shatter_guard_groups . x * 1024u >= shatter_guard_count ()){return ; }let particle = & particles . particles [param . x ]; (* particle ). position = (* particle ). position + uniforms . simulation_speed ; }" ;
+        const ENTRY_POINT: &'static ::std::primitive::str = "comp";
+        const WORK_GROUP_SIZE: ::shatter::__abi::WorkGroupSize = WORK_GROUP_SIZE;
+        const LAUNCH_PARAMS: ::shatter::__abi::LaunchParams = ::shatter::__abi::LaunchParams {
+            guard_count: true,
+            dispatch_offset: false,
+        };
+        type PushConstants = ::shatter::__abi::NoPushConstants;
+    }
+    impl<'a> ::shatter::__abi::DispatchableShader<Bindings<'a>> for Shader {
+        fn entry_point(&self) -> &'static ::std::primitive::str {
+            "comp"
+        }
+        fn work_group_size(&self) -> ::shatter::__abi::WorkGroupSize {
+            WORK_GROUP_SIZE
+        }
+        fn dispatch(&self, bindings: Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+            ::shatter::__abi::ComputeShaderBuilder::<'a, Shader>::new(bindings)
+                .tag("comp")
+                .dispatch(dispatch);
+        }
+    }
+    pub fn build<'a>(
+        mut bindings: comp::Bindings<'a>,
+    ) -> ::shatter::__abi::ComputeShaderBuilder<'a, Shader> {
+        ::shatter::__abi::ComputeShaderBuilder::new(bindings)
+    }
+}
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub fn comp<'a>(mut bindings: comp::Bindings<'a>, dispatch: ::shatter::__abi::Dispatch) {
+    comp::build(bindings).tag("comp").dispatch(dispatch);
+}
+#[doc = r" Like the blocking function without waiting on the GPU, resolves once the"]
+#[doc = r" dispatch is done and what it wrote is downloaded. Takes the resources"]
+#[doc = r" by value so the future is `Send`, they are handed back when it resolves."]
+#[allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+pub async fn comp_async<B0, B1>(
+    mut bindings: comp::OwnedBindings<B0, B1>,
+    dispatch: ::shatter::__abi::Dispatch,
+) -> comp::OwnedBindings<B0, B1>
+where
+    B0: ::shatter::__abi::BindingMut<Particles> + ::std::marker::Send,
+    B1: ::shatter::__abi::Binding<Uniforms> + ::std::marker::Send,
+{
+    let work = comp::build(bindings.as_bindings())
+        .tag("comp")
+        .dispatch_async(dispatch);
+    work.await;
+    if let ::std::result::Result::Err(err) = bindings.download_async().await {
+        ::std::panic!("{}", err);
+    }
+    bindings
+}
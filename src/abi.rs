@@ -0,0 +1,63 @@
+//! Items referenced by the code generated by `wgsl!`.
+//!
+//! The macro only ever refers to the runtime through this module, which makes
+//! this list the entire interface between the two crates. Renaming, removing or
+//! changing the signature of anything re-exported here is a breaking change to
+//! the ABI and **must** bump [`ABI_VERSION`](crate::ABI_VERSION) along with the
+//! version expected by `shatter-macro`.
+//!
+//! The ABI consists of:
+//! * the binding traits [`Binding`], [`BindingMut`], [`Bindings`] and the descriptors and
+//!   [`GroupKey`]s they return, and [`EmptyGroup`] composed in place of unused groups,
+//! * [`optional_binding_resource`] used by lenient bindings and [`SeenResources`]
+//!   used to skip resources bound to several fields,
+//! * [`BufferData`] and [`BufferVec`], implemented for generated structs with
+//!   [`buffer_vec_layout`], [`fault_point`] and the allocation hooks
+//!   [`record_alloc`], [`record_realloc`] and [`record_dealloc`], and
+//!   [`BufferDataTransparent`] implemented by its derive,
+//! * [`Reflect`] and the layout tables it exposes, and [`assert_layout`] used to
+//!   check generated and extern types against the WGSL layout,
+//! * [`ComputeShader`], [`ComputeShaderBuilder`] and [`DispatchableShader`] used by
+//!   entry point modules, [`Composed`] built from groups, [`NoPushConstants`] for those without push constants,
+//!   [`LaunchParams`] for those with launch parameters, [`BatchShader`] and
+//!   [`dispatch_shared_batch`] used by shader selects, and [`submit_encoded`]
+//!   used by pipelines,
+//! * [`RenderShader`] and [`RenderShaderBuilder`] used by the modules of
+//!   fragment entry points,
+//! * [`Dispatch`], [`WorkGroupSize`] and [`DispatchTooLarge`] used by the dispatch
+//!   helpers of entry point modules,
+//! * [`Error`] returned by the async downloads of owned bindings,
+//! * [`validate_bindings`] and the types it takes,
+//! * [`ResourceRegistry`], [`RegistryRequest`], [`RegistryBorrows`] and
+//!   [`MissingResource`] used by `from_registry`,
+//! * [`ResourceInfo`] collected for validation by the structs of `wgsl_group!`,
+//! * the math types used for struct members, including [`f16`] with the `f16` feature,
+//!   [`Mat2`], [`Mat3`] and [`Mat4`] used for square matrices,
+//!   [`PackedVec3`] used for `vec3` members packed with the next member, and
+//!   [`Padded`] used for array elements with an explicit stride,
+//! * the texture marker modules and [`SamplerBinding`] used for texture and
+//!   sampler bindings,
+//! * the [`wgpu`] types used in binding layouts.
+
+pub use crate::{
+    assert_layout, buffer_vec_layout,
+    debug::alloc_registry::{record_alloc, record_dealloc, record_realloc},
+    dispatch_shared_batch,
+    fault::{fault_point, FaultPoint},
+    optional_binding_resource, submit_encoded, texel_format, texture_sample_type,
+    texture_view_dimension, validate_bindings, BatchShader, BindGroupDescriptor, BindGroupEntry,
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindGroupLayoutId, Binding,
+    BindingExpectation, BindingMut, BindingType, Bindings, BufferBindingType, BufferData,
+    BufferDataTransparent, BufferVec, Composed, ComputeShader, ComputeShaderBuilder, Dispatch,
+    DispatchTooLarge, DispatchableShader, EmptyGroup, Error, FieldLayout, GroupKey, GroupKeys,
+    LaunchParams, Mat2, Mat3, Mat4, MissingResource, NoPushConstants, PackedVec3, Padded, Reflect,
+    RegistryBorrows, RegistryRequest, RenderShader, RenderShaderBuilder, ResourceInfo,
+    ResourceRegistry, SamplerBinding, SeenResources, ShaderStages, StorageTextureBinding,
+    StructLayout, TextureBinding, TransferReport, ValidationReport, Vec2, Vec3, Vec4,
+    WorkGroupSize,
+};
+
+#[cfg(feature = "f16")]
+pub use crate::f16;
+
+pub use wgpu;
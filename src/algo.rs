@@ -0,0 +1,448 @@
+//! Reductions over textures, e.g. the range or histogram of the luminance of
+//! an HDR frame, driving auto exposure.
+//!
+//! Textures with three or four channels are reduced by the luminance of
+//! their color, `dot(rgb, (0.2126, 0.7152, 0.0722))`, others by their first
+//! channel. NaNs are ignored, infinities are reduced like other values.
+//!
+//! Every reduction has a `_in` variant recording it into an encoder, e.g. of
+//! the frame that rendered the texture, so it adds no submission. It returns
+//! the result in a [`GpuBuffer`] to be bound by later dispatches or read with
+//! [`GpuBuffer::to_cpu`]. Work groups reduce their texels in work group
+//! memory first, so only one atomic per work group reaches the result.
+
+use std::ops::Range;
+
+use crate::{
+    submit_encoded, texture_sample_type, BindGroupDescriptor, BindGroupEntry,
+    BindGroupLayoutDescriptor, Binding, BindingResource, Buffer, ComputePipelineDescriptor,
+    ComputeShader, Dispatch, GpuBuffer, Instance, PipelineLayoutDescriptor, Sampled, Texture2d,
+    TextureFormat,
+};
+
+pub use kernels::{Histogram, MinMax};
+
+/// Most bins of a [`texture_histogram`], one per invocation of a work group.
+pub const MAX_HISTOGRAM_BINS: u32 = 256;
+
+/// Work group size of the reductions in both dimensions.
+const WORK_GROUP_SIZE: u32 = 16;
+
+/// Formats sampled as floats, e.g. `R32Float` and `Rgba16Float`.
+pub trait FloatFormat: TextureFormat + Default {}
+
+impl<F, const FILTERABLE: bool> FloatFormat for F where
+    F: TextureFormat + Default + Sampled<SampleType = texture_sample_type::Float<FILTERABLE>>
+{
+}
+
+#[allow(dead_code)]
+mod kernels {
+    use crate::wgsl;
+
+    wgsl! {
+        [[block]]
+        struct Params {
+            // reduce the luminance of rgb instead of r
+            luminance: u32;
+            // range of the histogram
+            min: f32;
+            max: f32;
+            bins: u32;
+        };
+
+        // zeroed buffers hold the range of no texels, see `decode_minmax`
+        [[block]]
+        struct MinMax {
+            min: f32;
+            max: f32;
+            // inverted key of the minimum, so both keys are reduced with atomicMax
+            min_key: atomic<u32>;
+            max_key: atomic<u32>;
+        };
+
+        [[block]]
+        struct Histogram {
+            // texels before and after the range
+            below: atomic<u32>;
+            above: atomic<u32>;
+            bins: array<atomic<u32>>;
+        };
+
+        [[group(0), binding(0)]]
+        var texture: texture_2d<f32>;
+
+        [[group(0), binding(1)]]
+        var<uniform> params: Params;
+
+        [[group(0), binding(2)]]
+        var<storage, read_write> minmax: MinMax;
+
+        [[group(0), binding(3)]]
+        var<storage, read_write> histogram: Histogram;
+
+        var<workgroup> local_min_key: atomic<u32>;
+        var<workgroup> local_max_key: atomic<u32>;
+        var<workgroup> local_below: atomic<u32>;
+        var<workgroup> local_above: atomic<u32>;
+        var<workgroup> local_bins: array<atomic<u32>, 256>;
+
+        fn is_nan(value: f32) -> bool {
+            return (bitcast<u32>(value) & 0x7fffffffu) > 0x7f800000u;
+        }
+
+        // ordered like the floats, nonzero for every float but negative NaNs
+        fn key(value: f32) -> u32 {
+            let bits = bitcast<u32>(value);
+
+            if ((bits & 0x80000000u) != 0u) {
+                return ~bits;
+            }
+
+            return bits | 0x80000000u;
+        }
+
+        fn from_key(key: u32) -> f32 {
+            if ((key & 0x80000000u) != 0u) {
+                return bitcast<f32>(key & 0x7fffffffu);
+            }
+
+            return bitcast<f32>(~key);
+        }
+
+        fn in_bounds(id: vec3<u32>) -> bool {
+            let size = textureDimensions(texture);
+
+            return i32(id.x) < size.x && i32(id.y) < size.y;
+        }
+
+        fn value_at(id: vec3<u32>) -> f32 {
+            let texel = textureLoad(texture, vec2<i32>(id.xy), 0);
+
+            if (params.luminance != 0u) {
+                return dot(texel.rgb, vec3<f32>(0.2126, 0.7152, 0.0722));
+            }
+
+            return texel.r;
+        }
+
+        [[stage(compute), workgroup_size(16, 16)]]
+        fn reduce_minmax(
+            [[builtin(global_invocation_id)]] id: vec3<u32>,
+            [[builtin(local_invocation_index)]] local_index: u32,
+        ) {
+            if (local_index == 0u) {
+                atomicStore(&local_min_key, 0u);
+                atomicStore(&local_max_key, 0u);
+            }
+
+            workgroupBarrier();
+
+            // out of bounds invocations still reach the barriers
+            if (in_bounds(id)) {
+                let value = value_at(id);
+
+                if (!is_nan(value)) {
+                    let previous_min = atomicMax(&local_min_key, ~key(value));
+                    let previous_max = atomicMax(&local_max_key, key(value));
+                }
+            }
+
+            workgroupBarrier();
+
+            let max_key = atomicLoad(&local_max_key);
+
+            if (local_index == 0u && max_key != 0u) {
+                let previous_min = atomicMax(&minmax.min_key, atomicLoad(&local_min_key));
+                let previous_max = atomicMax(&minmax.max_key, max_key);
+            }
+        }
+
+        [[stage(compute), workgroup_size(1)]]
+        fn decode_minmax() {
+            let max_key = atomicLoad(&minmax.max_key);
+
+            // only NaNs, the range is empty
+            if (max_key == 0u) {
+                minmax.min = bitcast<f32>(0x7f800000u);
+                minmax.max = bitcast<f32>(0xff800000u);
+                return;
+            }
+
+            minmax.min = from_key(~atomicLoad(&minmax.min_key));
+            minmax.max = from_key(max_key);
+        }
+
+        [[stage(compute), workgroup_size(16, 16)]]
+        fn reduce_histogram(
+            [[builtin(global_invocation_id)]] id: vec3<u32>,
+            [[builtin(local_invocation_index)]] local_index: u32,
+        ) {
+            atomicStore(&local_bins[local_index], 0u);
+
+            if (local_index == 0u) {
+                atomicStore(&local_below, 0u);
+                atomicStore(&local_above, 0u);
+            }
+
+            workgroupBarrier();
+
+            if (in_bounds(id)) {
+                let value = value_at(id);
+
+                if (is_nan(value)) {
+                    // ignored
+                } elseif (value < params.min) {
+                    let previous = atomicAdd(&local_below, 1u);
+                } elseif (value >= params.max) {
+                    let previous = atomicAdd(&local_above, 1u);
+                } else {
+                    let scaled = (value - params.min) / (params.max - params.min) * f32(params.bins);
+                    // rounding may reach the end of the range
+                    let bin = min(u32(scaled), params.bins - 1u);
+                    let previous = atomicAdd(&local_bins[bin], 1u);
+                }
+            }
+
+            workgroupBarrier();
+
+            let count = atomicLoad(&local_bins[local_index]);
+
+            if (local_index < params.bins && count != 0u) {
+                let previous = atomicAdd(&histogram.bins[local_index], count);
+            }
+
+            if (local_index == 0u) {
+                let below = atomicLoad(&local_below);
+                let above = atomicLoad(&local_above);
+
+                if (below != 0u) {
+                    let previous = atomicAdd(&histogram.below, below);
+                }
+
+                if (above != 0u) {
+                    let previous = atomicAdd(&histogram.above, above);
+                }
+            }
+        }
+    }
+}
+
+impl MinMax {
+    /// Returns true if the texture had no texel that isn't NaN, in which case
+    /// `min` is infinity and `max` negative infinity.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.min > self.max
+    }
+}
+
+impl Histogram {
+    /// Gets the texels counted by the histogram, including those outside its range.
+    pub fn total(&self) -> u64 {
+        self.bins.iter().map(|&count| count as u64).sum::<u64>()
+            + self.below as u64
+            + self.above as u64
+    }
+}
+
+fn params<F: FloatFormat>(
+    texture: &Texture2d<F>,
+    range: Range<f32>,
+    bins: u32,
+) -> Buffer<kernels::Params> {
+    let mut params = Buffer::<kernels::Params>::new();
+    params.luminance = (texture.wgpu_format().describe().components >= 3) as u32;
+    params.min = range.start;
+    params.max = range.end;
+    params.bins = bins;
+    params.upload();
+
+    params
+}
+
+/// Records the reduction `entry_point` of `texture` into `result`, bound at
+/// `binding`.
+fn record_reduction<F: FloatFormat>(
+    encoder: &mut wgpu::CommandEncoder,
+    entry_point: &'static str,
+    texture: &Texture2d<F>,
+    params: &Buffer<kernels::Params>,
+    (binding, result): (u32, BindingResource),
+) {
+    let instance = Instance::global();
+
+    // float textures are read without filtering, so `R32Float` can be bound
+    let layout = instance.get_bind_group_layout(BindGroupLayoutDescriptor::new(vec![
+        wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        },
+        wgpu::BindGroupLayoutEntry {
+            binding: 1,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        },
+        wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        },
+    ]));
+
+    let bind_group = instance.get_bind_group(BindGroupDescriptor::new(
+        layout.clone(),
+        vec![
+            BindGroupEntry {
+                binding: 0,
+                resource: BindingResource::TextureView(texture.texture_id().clone()),
+            },
+            BindGroupEntry {
+                binding: 1,
+                resource: Binding::<kernels::Params>::binding_resource(params),
+            },
+            BindGroupEntry {
+                binding,
+                resource: result,
+            },
+        ],
+    ));
+
+    let pipeline_layout = instance.get_pipeline_layout(PipelineLayoutDescriptor {
+        bind_group_layouts: vec![layout],
+        push_constant_ranges: Vec::new(),
+    });
+
+    let pipeline = instance.get_compute_pipeline(ComputePipelineDescriptor {
+        layout: Some(pipeline_layout),
+        module: instance
+            .get_shader_module(<kernels::reduce_minmax::Shader as ComputeShader>::SOURCE),
+        entry_point: entry_point.into(),
+    });
+
+    let bind_group = instance.bind_groups.get(&bind_group).unwrap();
+    let pipeline = instance.compute_pipelines.get(&pipeline).unwrap();
+
+    let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+        label: Some(&format!("shatter_compute_pass({})", entry_point)),
+    });
+
+    compute_pass.set_pipeline(&pipeline);
+    compute_pass.set_bind_group(0, &bind_group, &[]);
+    compute_pass.dispatch(
+        (texture.width() as u32).div_ceil(WORK_GROUP_SIZE),
+        (texture.height() as u32).div_ceil(WORK_GROUP_SIZE),
+        1,
+    );
+}
+
+/// Gets the smallest and largest value of `texture`, see [`texture_minmax_in`].
+pub fn texture_minmax<F: FloatFormat>(texture: &Texture2d<F>) -> (f32, f32) {
+    let minmax = submit_encoded("shatter_command_encoder(texture_minmax)", |encoder| {
+        texture_minmax_in(encoder, texture)
+    })
+    .to_cpu();
+
+    (minmax.min, minmax.max)
+}
+
+/// Records reducing `texture` to its smallest and largest value into
+/// `encoder`, see the [module](self) docs. Pending CPU writes to the texture
+/// are uploaded first.
+///
+/// The range of a texture with only NaNs is empty, see [`MinMax::is_empty`].
+pub fn texture_minmax_in<F: FloatFormat>(
+    encoder: &mut wgpu::CommandEncoder,
+    texture: &Texture2d<F>,
+) -> GpuBuffer<MinMax> {
+    texture.upload();
+
+    let mut minmax = GpuBuffer::<MinMax>::new();
+    let params = params(texture, 0.0..0.0, 0);
+
+    record_reduction(
+        encoder,
+        "reduce_minmax",
+        texture,
+        &params,
+        (2, Binding::<MinMax>::binding_resource(&minmax)),
+    );
+
+    kernels::decode_minmax::build(kernels::decode_minmax::Bindings {
+        minmax: &mut minmax,
+    })
+    .dispatch_in(encoder, Dispatch::new(1, 1, 1));
+
+    minmax
+}
+
+/// Counts the values of `texture` in `bins` equal bins of `range`, see
+/// [`texture_histogram_in`].
+pub fn texture_histogram<F: FloatFormat>(
+    texture: &Texture2d<F>,
+    bins: u32,
+    range: Range<f32>,
+) -> Buffer<Histogram> {
+    submit_encoded("shatter_command_encoder(texture_histogram)", |encoder| {
+        texture_histogram_in(encoder, texture, bins, range)
+    })
+    .to_cpu()
+}
+
+/// Records counting the values of `texture` in `bins` equal bins of `range`
+/// into `encoder`, see the [module](self) docs. Values before and after the
+/// range are counted in [`Histogram::below`] and [`Histogram::above`]. Pending
+/// CPU writes to the texture are uploaded first.
+///
+/// # Panics
+/// Panics if `bins` is zero or more than [`MAX_HISTOGRAM_BINS`], or the range
+/// is empty or not finite.
+pub fn texture_histogram_in<F: FloatFormat>(
+    encoder: &mut wgpu::CommandEncoder,
+    texture: &Texture2d<F>,
+    bins: u32,
+    range: Range<f32>,
+) -> GpuBuffer<Histogram> {
+    assert!(
+        (1..=MAX_HISTOGRAM_BINS).contains(&bins),
+        "histograms have 1 to {} bins, not {}",
+        MAX_HISTOGRAM_BINS,
+        bins
+    );
+    assert!(
+        range.start.is_finite() && range.end.is_finite() && range.start < range.end,
+        "histogram range {:?} is empty or not finite",
+        range
+    );
+
+    texture.upload();
+
+    let histogram = GpuBuffer::<Histogram>::with_len(bins as usize);
+    let params = params(texture, range, bins);
+
+    record_reduction(
+        encoder,
+        "reduce_histogram",
+        texture,
+        &params,
+        (3, Binding::<Histogram>::binding_resource(&histogram)),
+    );
+
+    histogram
+}
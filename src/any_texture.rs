@@ -0,0 +1,161 @@
+use std::any::Any;
+
+use crate::{
+    texture_sample_type::SampleType, texture_view_dimension::D2, Binding, BindingResource,
+    ResourceInfo, Texture2d, TextureBinding, TextureFormat, TextureId, TransferReport,
+};
+
+/// Operations on a [`Texture2d`] that don't depend on its format.
+trait ErasedTexture2d: Send {
+    fn upload(&self) -> u64;
+
+    fn mark_needs_download(&mut self);
+
+    fn resource_info(&self) -> ResourceInfo;
+
+    fn texture_id(&self) -> &TextureId;
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<Format> ErasedTexture2d for Texture2d<Format>
+where
+    Format: TextureFormat + 'static,
+    Texture2d<Format>: Send,
+{
+    fn upload(&self) -> u64 {
+        self.upload_binding()
+    }
+
+    fn mark_needs_download(&mut self) {
+        self.mark_needs_download();
+    }
+
+    fn resource_info(&self) -> ResourceInfo {
+        self.resource_info()
+    }
+
+    fn texture_id(&self) -> &TextureId {
+        self.texture_id()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// A [`Texture2d`] whose format is only known at runtime.
+///
+/// Binds to any sampled 2d texture binding, the sample type of the format is
+/// checked when binding and a mismatch panics.
+pub struct AnyTexture2d {
+    texture: Box<dyn ErasedTexture2d>,
+    format: wgpu::TextureFormat,
+    sample_type: wgpu::TextureSampleType,
+}
+
+impl<Format> From<Texture2d<Format>> for AnyTexture2d
+where
+    Format: TextureFormat + 'static,
+    Texture2d<Format>: Send,
+{
+    fn from(texture: Texture2d<Format>) -> Self {
+        let format = texture.wgpu_format();
+
+        Self {
+            texture: Box::new(texture),
+            format,
+            sample_type: format.describe().sample_type,
+        }
+    }
+}
+
+impl AnyTexture2d {
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    pub fn sample_type(&self) -> wgpu::TextureSampleType {
+        self.sample_type
+    }
+
+    pub fn texture_id(&self) -> &TextureId {
+        self.texture.texture_id()
+    }
+
+    /// Returns true if the texture can be bound where `sample_type` is expected.
+    pub fn is_compatible(&self, sample_type: wgpu::TextureSampleType) -> bool {
+        match (self.sample_type, sample_type) {
+            // filterable textures can always be sampled without filtering
+            (
+                wgpu::TextureSampleType::Float { .. },
+                wgpu::TextureSampleType::Float { filterable: false },
+            ) => true,
+            (actual, expected) => actual == expected,
+        }
+    }
+
+    pub fn downcast_ref<Format>(&self) -> Option<&Texture2d<Format>>
+    where
+        Format: TextureFormat + 'static,
+        Texture2d<Format>: Send,
+    {
+        self.texture.as_any().downcast_ref()
+    }
+
+    /// Converts back into a typed texture, returns `self` if the format doesn't match.
+    pub fn downcast<Format>(self) -> Result<Texture2d<Format>, Self>
+    where
+        Format: TextureFormat + 'static,
+        Texture2d<Format>: Send,
+    {
+        if self.downcast_ref::<Format>().is_none() {
+            return Err(self);
+        }
+
+        Ok(*self.texture.into_any().downcast().unwrap())
+    }
+
+    pub(crate) fn mark_needs_download(&mut self) {
+        self.texture.mark_needs_download();
+    }
+
+    fn assert_compatible<S: SampleType>(&self) {
+        assert!(
+            self.is_compatible(S::SAMPLE_TYPE),
+            "texture of format {:?} samples as {:?}, which cannot be bound where {:?} is expected",
+            self.format,
+            self.sample_type,
+            S::SAMPLE_TYPE,
+        );
+    }
+}
+
+impl<S: SampleType> Binding<TextureBinding<S, D2, false>> for AnyTexture2d {
+    fn binding_resource(&self) -> BindingResource {
+        self.assert_compatible::<S>();
+
+        BindingResource::TextureView(self.texture_id().clone())
+    }
+
+    fn resource_info(&self) -> ResourceInfo {
+        self.texture.resource_info()
+    }
+
+    fn prepare(&self, report: &mut TransferReport) {
+        self.assert_compatible::<S>();
+
+        report.textures_touched += 1;
+    }
+
+    fn read(&self, report: &mut TransferReport) {
+        report.textures_touched += 1;
+        report.uploaded_bytes += self.texture.upload();
+    }
+}
@@ -1,32 +1,221 @@
-use std::num::NonZeroU64;
+use std::{future::Future, num::NonZeroU64, pin::Pin};
 
-use crate::{BindGroupLayoutId, Buffer, BufferData, BufferId, SamplerId, TextureId};
+use smallvec::SmallVec;
+
+use crate::{
+    BindGroupId, BindGroupLayoutId, BufferId, Error, Instance, ResourceInfo, SamplerId, TextureId,
+    TextureViewId, TransferReport, ValidationReport,
+};
 
 pub use wgpu::{
     BindGroupLayoutEntry, BindingType, BufferBindingType, ShaderStages, StorageTextureAccess,
-    TextureFormat, TextureSampleType, TextureViewDimension,
+    TextureSampleType, TextureViewDimension,
 };
 
+/// Future of [`Binding::download_async`], resolving to the number of bytes downloaded.
+pub type DownloadFuture<'a> = Pin<Box<dyn Future<Output = Result<u64, Error>> + Send + 'a>>;
+
 pub trait Binding<T: ?Sized> {
     fn binding_resource(&self) -> BindingResource;
 
-    fn prepare(&self);
+    /// Describes the bound resource without touching the GPU.
+    fn resource_info(&self) -> ResourceInfo;
+
+    /// Prepares the binding, transfers are added to `report`.
+    fn prepare(&self, report: &mut TransferReport);
+
+    /// Makes the binding readable by the GPU, transfers are added to `report`.
+    fn read(&self, report: &mut TransferReport);
+
+    /// Returns true if binding the resource would first create it on the GPU,
+    /// e.g. a buffer that has outgrown its allocation.
+    #[inline]
+    fn needs_allocation(&self) -> bool {
+        false
+    }
+
+    /// Size in bytes of the data, without the spare capacity of the GPU
+    /// buffer. Counts the elements of `#![auto_guard]` shaders, `None` if unknown.
+    #[inline]
+    fn data_size(&self) -> Option<u64> {
+        None
+    }
 
-    fn read(&self);
+    /// Downloads pending GPU writes without blocking, used by the `_async`
+    /// dispatch functions. Resources without an async download resolve
+    /// immediately and download on access as usual.
+    #[inline]
+    fn download_async(&mut self) -> DownloadFuture<'_> {
+        Box::pin(std::future::ready(Ok(0)))
+    }
+}
 
+/// A [`Binding`] the GPU can write to, required by `read_write` storage
+/// buffers and storage textures.
+pub trait BindingMut<T: ?Sized>: Binding<T> {
+    /// Marks the resource as written by the dispatch.
     fn write(&mut self);
 }
 
+/// Gets the resource of an optional binding, binding
+/// [`Instance::placeholder_buffer`](crate::Instance::placeholder_buffer) when `None`.
+pub fn optional_binding_resource<T: ?Sized>(
+    binding: Option<&dyn Binding<T>>,
+    min_size: u64,
+) -> BindingResource {
+    match binding {
+        Some(binding) => binding.binding_resource(),
+        None => {
+            let size = min_size.max(4);
+
+            BindingResource::Buffer(BufferBinding {
+                buffer: Instance::global().placeholder_buffer(size),
+                offset: 0,
+                size: NonZeroU64::new(size),
+            })
+        }
+    }
+}
+
+/// Resources already visited by a generated [`Bindings`] method, so a
+/// resource bound to several fields is only prepared, read and written once.
+#[derive(Default)]
+pub struct SeenResources(SmallVec<[BindingResource; 8]>);
+
+impl SeenResources {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true the first time `resource` is passed.
+    #[inline]
+    pub fn first_use(&mut self, resource: BindingResource) -> bool {
+        if self.0.contains(&resource) {
+            return false;
+        }
+
+        self.0.push(resource);
+        true
+    }
+}
+
+/// A set of bindings used by a shader.
+///
+/// # Canonical form
+/// Descriptors returned by [`Bindings`] are canonicalized by [`Instance`](crate::Instance)
+/// before being cached, meaning entries are sorted by binding. Implementors are free
+/// to return entries in any order, two descriptors containing the same entries will
+/// always resolve to the same cached id.
 pub trait Bindings {
     fn bind_group_layout_descriptors(&self) -> Vec<BindGroupLayoutDescriptor>;
 
     fn bind_group_descriptors(&self, layouts: &[BindGroupLayoutId]) -> Vec<BindGroupDescriptor>;
 
-    fn prepare(&self);
+    /// Gets the [`GroupKey`] of every group, used to find cached bind groups
+    /// without building the descriptors.
+    ///
+    /// The default implementation derives the keys from
+    /// [`bind_group_descriptors`](Bindings::bind_group_descriptors), generated
+    /// bindings build them directly without allocating.
+    fn bind_group_keys(&self, layouts: &[BindGroupLayoutId]) -> GroupKeys {
+        self.bind_group_descriptors(layouts)
+            .iter()
+            .map(GroupKey::from_descriptor)
+            .collect()
+    }
+
+    /// Gets the number of groups, the length of
+    /// [`bind_group_layout_descriptors`](Bindings::bind_group_layout_descriptors).
+    ///
+    /// Used by tuples of bindings to split the layouts between their
+    /// elements, groups generated by `wgsl!` return it without building the
+    /// descriptors.
+    #[inline]
+    fn group_count(&self) -> usize {
+        self.bind_group_layout_descriptors().len()
+    }
+
+    /// Checks that the bound resources are compatible with the shader.
+    fn validate(&self) -> Result<(), ValidationReport>;
+
+    fn prepare(&self, report: &mut TransferReport);
 
-    fn read(&self);
+    fn read(&self, report: &mut TransferReport);
 
     fn write(&mut self);
+
+    /// Returns true if any binding would be created on the GPU by the next
+    /// dispatch, see [`Binding::needs_allocation`].
+    #[inline]
+    fn needs_allocation(&self) -> bool {
+        false
+    }
+
+    /// Gets the element count of `#![auto_guard]` shaders, the length of the
+    /// first bound runtime sized array. `None` for other shaders.
+    #[inline]
+    fn guard_count(&self) -> Option<u32> {
+        None
+    }
+
+    /// Creates every resource a dispatch with these bindings needs, without
+    /// dispatching.
+    ///
+    /// Bindings are prepared and read, growing and uploading buffers, and the
+    /// bind group layouts and bind groups are cached. Along with
+    /// [`ComputeShaderBuilder::precompile`](crate::ComputeShaderBuilder::precompile)
+    /// the next dispatch creates nothing, moving the cost out of the first frame.
+    fn warm_up(&mut self) {
+        self.warm_up_in(Instance::global());
+    }
+
+    /// Like [`warm_up`](Bindings::warm_up), caching the bind groups in `instance`.
+    fn warm_up_in(&mut self, instance: &Instance) {
+        let mut report = TransferReport::ZERO;
+        self.prepare(&mut report);
+        self.read(&mut report);
+
+        let layouts = resolve_layouts(instance, self.bind_group_layout_descriptors());
+        resolve_bind_groups(instance, self, &layouts);
+    }
+}
+
+/// Gets the layouts of `layout_descriptors`, creating those not cached.
+pub(crate) fn resolve_layouts(
+    instance: &Instance,
+    layout_descriptors: Vec<BindGroupLayoutDescriptor>,
+) -> Vec<BindGroupLayoutId> {
+    layout_descriptors
+        .into_iter()
+        .map(|desc| instance.get_bind_group_layout(desc))
+        .collect()
+}
+
+/// Gets the bind groups of `bindings` with `layouts`, creating those not cached.
+///
+/// Bind group descriptors are only built if a group isn't cached by key, so
+/// cached groups are resolved without allocating.
+pub(crate) fn resolve_bind_groups<B: Bindings + ?Sized>(
+    instance: &Instance,
+    bindings: &B,
+    layouts: &[BindGroupLayoutId],
+) -> SmallVec<[BindGroupId; 4]> {
+    let mut bind_group_descriptors = None;
+
+    bindings
+        .bind_group_keys(layouts)
+        .into_iter()
+        .enumerate()
+        .map(|(group, key)| {
+            instance.get_keyed_bind_group(key, || {
+                let descriptors = bind_group_descriptors
+                    .get_or_insert_with(|| bindings.bind_group_descriptors(layouts));
+
+                descriptors[group].clone()
+            })
+        })
+        .collect()
 }
 
 impl Bindings for () {
@@ -38,18 +227,222 @@ impl Bindings for () {
         Vec::new()
     }
 
-    fn prepare(&self) {}
+    fn validate(&self) -> Result<(), ValidationReport> {
+        Ok(())
+    }
+
+    fn prepare(&self, _: &mut TransferReport) {}
+
+    fn read(&self, _: &mut TransferReport) {}
+
+    fn write(&mut self) {}
+}
+
+impl<B: Bindings + ?Sized> Bindings for &mut B {
+    #[inline]
+    fn bind_group_layout_descriptors(&self) -> Vec<BindGroupLayoutDescriptor> {
+        (**self).bind_group_layout_descriptors()
+    }
+
+    #[inline]
+    fn bind_group_descriptors(&self, layouts: &[BindGroupLayoutId]) -> Vec<BindGroupDescriptor> {
+        (**self).bind_group_descriptors(layouts)
+    }
+
+    #[inline]
+    fn bind_group_keys(&self, layouts: &[BindGroupLayoutId]) -> GroupKeys {
+        (**self).bind_group_keys(layouts)
+    }
+
+    #[inline]
+    fn group_count(&self) -> usize {
+        (**self).group_count()
+    }
+
+    #[inline]
+    fn validate(&self) -> Result<(), ValidationReport> {
+        (**self).validate()
+    }
+
+    #[inline]
+    fn prepare(&self, report: &mut TransferReport) {
+        (**self).prepare(report)
+    }
+
+    #[inline]
+    fn read(&self, report: &mut TransferReport) {
+        (**self).read(report)
+    }
+
+    #[inline]
+    fn write(&mut self) {
+        (**self).write()
+    }
+
+    #[inline]
+    fn needs_allocation(&self) -> bool {
+        (**self).needs_allocation()
+    }
+
+    #[inline]
+    fn guard_count(&self) -> Option<u32> {
+        (**self).guard_count()
+    }
+}
+
+/// A group without bindings, in place of the unused groups between those of
+/// a shader when composing its bindings from groups.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EmptyGroup;
+
+impl Bindings for EmptyGroup {
+    fn bind_group_layout_descriptors(&self) -> Vec<BindGroupLayoutDescriptor> {
+        vec![BindGroupLayoutDescriptor::new(Vec::new())]
+    }
+
+    fn bind_group_descriptors(&self, layouts: &[BindGroupLayoutId]) -> Vec<BindGroupDescriptor> {
+        vec![BindGroupDescriptor::new(layouts[0].clone(), Vec::new())]
+    }
+
+    fn bind_group_keys(&self, layouts: &[BindGroupLayoutId]) -> GroupKeys {
+        std::iter::once(GroupKey::new(&layouts[0])).collect()
+    }
+
+    #[inline]
+    fn group_count(&self) -> usize {
+        1
+    }
+
+    fn validate(&self) -> Result<(), ValidationReport> {
+        Ok(())
+    }
+
+    fn prepare(&self, _: &mut TransferReport) {}
 
-    fn read(&self) {}
+    fn read(&self, _: &mut TransferReport) {}
 
     fn write(&mut self) {}
 }
 
+/// Bindings composed of the groups of each element in order, e.g. the
+/// `GroupN` structs `wgsl!` generates for entry points binding several groups.
+///
+/// Each element is validated on its own, a resource bound writable by one
+/// element and bound by another isn't reported as aliased.
+macro_rules! impl_tuple_bindings {
+    ($($name:ident $index:tt),+) => {
+        impl<$($name: Bindings),+> Bindings for ($($name,)+) {
+            fn bind_group_layout_descriptors(&self) -> Vec<BindGroupLayoutDescriptor> {
+                let mut descriptors = Vec::new();
+                $(descriptors.extend(self.$index.bind_group_layout_descriptors());)+
+                descriptors
+            }
+
+            // the last element leaves `start` unread
+            #[allow(unused_assignments)]
+            fn bind_group_descriptors(
+                &self,
+                layouts: &[BindGroupLayoutId],
+            ) -> Vec<BindGroupDescriptor> {
+                let mut descriptors = Vec::new();
+                let mut start = 0;
+
+                $(
+                    let end = start + self.$index.group_count();
+                    descriptors.extend(self.$index.bind_group_descriptors(&layouts[start..end]));
+                    start = end;
+                )+
+
+                descriptors
+            }
+
+            #[allow(unused_assignments)]
+            fn bind_group_keys(&self, layouts: &[BindGroupLayoutId]) -> GroupKeys {
+                let mut keys = GroupKeys::new();
+                let mut start = 0;
+
+                $(
+                    let end = start + self.$index.group_count();
+                    keys.extend(self.$index.bind_group_keys(&layouts[start..end]));
+                    start = end;
+                )+
+
+                keys
+            }
+
+            #[inline]
+            fn group_count(&self) -> usize {
+                0 $(+ self.$index.group_count())+
+            }
+
+            fn validate(&self) -> Result<(), ValidationReport> {
+                let mut report = ValidationReport::default();
+
+                $(
+                    if let Err(mut errors) = self.$index.validate() {
+                        report.errors.append(&mut errors.errors);
+                    }
+                )+
+
+                match report.errors.is_empty() {
+                    true => Ok(()),
+                    false => Err(report),
+                }
+            }
+
+            #[inline]
+            fn prepare(&self, report: &mut TransferReport) {
+                $(self.$index.prepare(report);)+
+            }
+
+            #[inline]
+            fn read(&self, report: &mut TransferReport) {
+                $(self.$index.read(report);)+
+            }
+
+            #[inline]
+            fn write(&mut self) {
+                $(self.$index.write();)+
+            }
+
+            #[inline]
+            fn needs_allocation(&self) -> bool {
+                false $(|| self.$index.needs_allocation())+
+            }
+
+            #[inline]
+            fn guard_count(&self) -> Option<u32> {
+                None $(.or_else(|| self.$index.guard_count()))+
+            }
+        }
+    };
+}
+
+impl_tuple_bindings!(A 0, B 1);
+impl_tuple_bindings!(A 0, B 1, C 2);
+impl_tuple_bindings!(A 0, B 1, C 2, D 3);
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct BindGroupLayoutDescriptor {
     pub entries: Vec<wgpu::BindGroupLayoutEntry>,
 }
 
+impl BindGroupLayoutDescriptor {
+    /// Creates a new descriptor in canonical form.
+    #[inline]
+    pub fn new(entries: Vec<wgpu::BindGroupLayoutEntry>) -> Self {
+        let mut desc = Self { entries };
+        desc.canonicalize();
+        desc
+    }
+
+    /// Sorts entries by binding.
+    #[inline]
+    pub fn canonicalize(&mut self) {
+        self.entries.sort_by_key(|entry| entry.binding);
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct BufferBinding {
     pub buffer: BufferId,
@@ -64,6 +457,131 @@ pub enum BindingResource {
     Sampler(SamplerId),
     TextureView(TextureId),
     TextureViewArray(Vec<TextureId>),
+    /// A view without a texture in [`Instance::textures`], e.g. of a surface frame.
+    RawTextureView(TextureViewId),
+}
+
+impl BindingResource {
+    /// Clones the resource without referencing the bound ids.
+    pub fn clone_untracked(&self) -> Self {
+        let buffer = |binding: &BufferBinding| BufferBinding {
+            buffer: binding.buffer.clone_untracked(),
+            ..*binding
+        };
+
+        match *self {
+            Self::Buffer(ref binding) => Self::Buffer(buffer(binding)),
+            Self::BufferArray(ref bindings) => {
+                Self::BufferArray(bindings.iter().map(buffer).collect())
+            }
+            Self::Sampler(ref id) => Self::Sampler(id.clone_untracked()),
+            Self::TextureView(ref id) => Self::TextureView(id.clone_untracked()),
+            Self::TextureViewArray(ref ids) => {
+                Self::TextureViewArray(ids.iter().map(TextureId::clone_untracked).collect())
+            }
+            Self::RawTextureView(ref id) => Self::RawTextureView(id.clone_untracked()),
+        }
+    }
+}
+
+/// Raw id of a bound resource, tagged with the map it belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResourceKey {
+    Buffer(u64),
+    Sampler(u64),
+    Texture(u64),
+    TextureView(u64),
+}
+
+impl ResourceKey {
+    /// Gets the id of the [`Instance`] the resource belongs to, see [`Id::instance`](crate::Id::instance).
+    #[inline]
+    pub fn instance(&self) -> u64 {
+        match *self {
+            Self::Buffer(raw)
+            | Self::Sampler(raw)
+            | Self::Texture(raw)
+            | Self::TextureView(raw) => raw >> crate::INSTANCE_ID_SHIFT,
+        }
+    }
+}
+
+/// Entry of a [`GroupKey`], `(binding, resource, offset, size)`.
+pub type GroupKeyEntry = (u32, ResourceKey, u64, u64);
+
+/// Keys of every group of a [`Bindings`].
+pub type GroupKeys = SmallVec<[GroupKey; 4]>;
+
+/// Compact cache key of a bind group, made of the ids of the layout and the bound resources.
+///
+/// Keys are much cheaper to hash than a [`BindGroupDescriptor`] and don't
+/// allocate for groups of up to 8 bindings. Recreating a resource, e.g. when a
+/// buffer grows, gives it a new id and with that a new key.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GroupKey {
+    pub layout: u64,
+    pub entries: SmallVec<[GroupKeyEntry; 8]>,
+}
+
+impl GroupKey {
+    #[inline]
+    pub fn new(layout: &BindGroupLayoutId) -> Self {
+        Self {
+            layout: layout.raw(),
+            entries: SmallVec::new(),
+        }
+    }
+
+    /// Adds the resource bound at `binding`, entries must be pushed in binding order.
+    ///
+    /// Arrays are keyed by each of their elements.
+    pub fn push(&mut self, binding: u32, resource: &BindingResource) {
+        let buffer_entry = |buffer: &BufferBinding| {
+            (
+                binding,
+                ResourceKey::Buffer(buffer.buffer.raw()),
+                buffer.offset,
+                buffer.size.map_or(0, NonZeroU64::get),
+            )
+        };
+
+        match *resource {
+            BindingResource::Buffer(ref buffer) => self.entries.push(buffer_entry(buffer)),
+            BindingResource::BufferArray(ref buffers) => {
+                self.entries.extend(buffers.iter().map(buffer_entry));
+            }
+            BindingResource::Sampler(ref id) => {
+                self.entries
+                    .push((binding, ResourceKey::Sampler(id.raw()), 0, 0));
+            }
+            BindingResource::TextureView(ref id) => {
+                self.entries
+                    .push((binding, ResourceKey::Texture(id.raw()), 0, 0));
+            }
+            BindingResource::TextureViewArray(ref ids) => {
+                let entries = ids
+                    .iter()
+                    .map(|id| (binding, ResourceKey::Texture(id.raw()), 0, 0));
+
+                self.entries.extend(entries);
+            }
+            BindingResource::RawTextureView(ref id) => {
+                self.entries
+                    .push((binding, ResourceKey::TextureView(id.raw()), 0, 0));
+            }
+        }
+    }
+
+    /// Gets the key of `desc`, which must be in canonical form.
+    pub fn from_descriptor(desc: &BindGroupDescriptor) -> Self {
+        let mut key = Self::new(&desc.layout);
+
+        for entry in &desc.entries {
+            key.push(entry.binding, &entry.resource);
+        }
+
+        key
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -77,3 +595,37 @@ pub struct BindGroupDescriptor {
     pub layout: BindGroupLayoutId,
     pub entries: Vec<BindGroupEntry>,
 }
+
+impl BindGroupDescriptor {
+    /// Creates a new descriptor in canonical form.
+    #[inline]
+    pub fn new(layout: BindGroupLayoutId, entries: Vec<BindGroupEntry>) -> Self {
+        let mut desc = Self { layout, entries };
+        desc.canonicalize();
+        desc
+    }
+
+    /// Sorts entries by binding.
+    #[inline]
+    pub fn canonicalize(&mut self) {
+        self.entries.sort_by_key(|entry| entry.binding);
+    }
+
+    /// Clones the descriptor without referencing the bound resources, so a
+    /// cached descriptor doesn't keep them alive.
+    pub fn clone_untracked(&self) -> Self {
+        let entries = self
+            .entries
+            .iter()
+            .map(|entry| BindGroupEntry {
+                binding: entry.binding,
+                resource: entry.resource.clone_untracked(),
+            })
+            .collect();
+
+        Self {
+            layout: self.layout.clone_untracked(),
+            entries,
+        }
+    }
+}
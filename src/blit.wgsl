@@ -0,0 +1,17 @@
+// Copies a texture to a render target of the same size, converting the format.
+
+[[group(0), binding(0)]]
+var source: texture_2d<f32>;
+
+// a single triangle covering the whole target
+[[stage(vertex)]]
+fn vertex([[builtin(vertex_index)]] index: u32) -> [[builtin(position)]] vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+[[stage(fragment)]]
+fn fragment([[builtin(position)]] position: vec4<f32>) -> [[location(0)]] vec4<f32> {
+    return textureLoad(source, vec2<i32>(position.xy), 0);
+}
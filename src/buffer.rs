@@ -1,5 +1,10 @@
 use std::{
+    alloc::Layout,
+    borrow::Cow,
+    future::Future,
     marker::PhantomData,
+    mem::ManuallyDrop,
+    num::NonZeroU64,
     ops::{Deref, DerefMut},
     ptr::NonNull,
     sync::{
@@ -8,7 +13,27 @@ use std::{
     },
 };
 
-use crate::{Binding, BindingResource, BufferBinding, BufferId, Instance};
+use crate::{
+    Binding, BindingMut, BindingResource, BufferBinding, BufferHandle, BufferId, Dispatch,
+    DownloadFuture, Error, ForcedTransfer, GpuBuffer, Instance, Polled, ResourceInfo, ResourceKind,
+    StreamReport, TransferDirection, TransferReport, WorkGroupSize,
+};
+
+/// Usages of the gpu buffers created by [`Buffer::new`], see [`BufferUsage::Any`].
+pub const BUFFER_USAGES: wgpu::BufferUsages = wgpu::BufferUsages::COPY_DST
+    .union(wgpu::BufferUsages::COPY_SRC)
+    .union(wgpu::BufferUsages::STORAGE)
+    .union(wgpu::BufferUsages::UNIFORM);
+
+/// Default size in bytes of the chunks staged by [`Buffer::stream_items`].
+pub const DEFAULT_STREAM_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+static DOWNLOADED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Gets the number of bytes downloaded by every [`Buffer`] since startup.
+pub fn buffer_downloaded_bytes() -> u64 {
+    DOWNLOADED_BYTES.load(Ordering::Acquire)
+}
 
 /// Allows a struct to reside inside of a [`Buffer`].
 ///
@@ -16,60 +41,386 @@ use crate::{Binding, BindingResource, BufferBinding, BufferId, Instance};
 /// * It **must** be safe to cast the struct to a slice of bytes.
 /// * Alignment must be safe.
 /// * size **must** always return a valid size for ptr.
+/// * `dealloc` **must** free the allocation with the layout it was allocated
+///   or last reallocated with, for every state `alloc`, `alloc_zeroed` and
+///   [`BufferVec`] may leave behind.
+///
+/// ## Panic safety
+/// [`Buffer`] drops and deallocates its data with the pointer and state it
+/// holds, including while unwinding. Whenever a method may panic, the pointer
+/// and state **must** describe a live allocation, which `alloc` and
+/// `alloc_zeroed` satisfy by not panicking after allocating.
+///
+/// Generated implementations report every allocation to the
+/// [`alloc_registry`](crate::debug::alloc_registry), which checks them with the
+/// `leak-check` feature.
 pub unsafe trait BufferData {
     type State;
 
+    /// Owned snapshot of the data, returned by [`Buffer::into_contents`].
+    type Contents;
+
+    /// Whether the data has to be dropped, [`Buffer`] skips dropping it when false.
+    const NEEDS_DROP: bool = true;
+
     fn init() -> Self::State;
 
     fn size(state: &Self::State) -> usize;
 
     /// Allocates self and returns a pointer.
+    ///
+    /// # Safety
+    /// * The returned pointer **must** be deallocated with [`BufferData::dealloc`].
     unsafe fn alloc() -> NonNull<u8>;
+    /// Allocates zeroed data spanning `size` bytes and returns a pointer along with its state.
+    ///
+    /// # Safety
+    /// * The returned pointer **must** be deallocated with [`BufferData::dealloc`].
+    unsafe fn alloc_zeroed(size: usize) -> (NonNull<u8>, Self::State);
     /// Deallocates self from a NonNull pointer.
+    ///
+    /// # Safety
+    /// * `ptr` **must** have been allocated by [`BufferData::alloc`].
+    /// * `state` **must** be the state associated with `ptr`.
     unsafe fn dealloc(ptr: NonNull<u8>, state: &Self::State);
 
+    /// # Safety
+    /// * `ptr` **must** have been allocated by [`BufferData::alloc`].
+    /// * `state` **must** be the state associated with `ptr`.
     unsafe fn as_ptr(ptr: NonNull<u8>, state: &Self::State) -> *mut Self;
+
+    /// Moves the data out into an owned snapshot.
+    ///
+    /// # Safety
+    /// * `ptr` **must** have been allocated by [`BufferData::alloc`].
+    /// * `state` **must** be the state associated with `ptr`.
+    /// * The data **must not** be dropped afterwards.
+    unsafe fn contents(ptr: NonNull<u8>, state: &Self::State) -> Self::Contents;
 }
 
-/// Allows a struct to
+/// Allows a struct ending in a runtime sized array to grow and shrink.
+///
+/// # Safety
+/// * `len` **must** always return the number of initialized items.
+/// * `capacity` **must** always return the number of items the allocation
+///   has room for.
+/// * `grow`, `reserve`, `push`, `pop` and `set_len` **must** keep `ptr` and
+///   `state` consistent with [`BufferData::size`].
+/// * `items` **must** return a pointer to the first item, valid for
+///   `capacity` items.
+/// * The allocation **must** start with a [`Self::Head`], aligned for it,
+///   for every capacity.
+///
+/// ## Panic safety
+/// `grow`, `reserve`, `push` and `pop` **must** leave `ptr` and `state`
+/// consistent if they unwind, as the [`Buffer`] is dropped with them:
+/// * Every fallible step, e.g. computing layouts, comes before the first
+///   write to `ptr` or `state`.
+/// * A reallocation updates `ptr` and the capacity together, with nothing
+///   that may panic in between. A failed allocation leaves the old one valid.
+/// * The length is only increased once the item is written, and decreased
+///   before it's read out.
+///
+/// Generated implementations lay the data out with [`buffer_vec_layout`] and
+/// call [`fault_point`](crate::fault::fault_point) where they may panic, so the
+/// `fault-injection` feature can test the above.
 pub unsafe trait BufferVec: BufferData {
+    /// The members before the runtime sized array, the `_Sized` struct
+    /// generated by `wgsl!`, or `()` if the struct is only the array.
+    type Head;
     type Item;
 
+    /// Bytes from one item to the next in WGSL, the stride of the runtime
+    /// sized array. Generated implementations assert at compile time that it's
+    /// the size of [`Self::Item`].
+    const ITEM_STRIDE: usize;
+    /// Alignment of the items in WGSL, e.g. 16 for items containing a `vec3`.
+    const ITEM_ALIGN: usize;
+
     fn len(state: &Self::State) -> usize;
 
+    fn capacity(state: &Self::State) -> usize;
+
+    /// # Safety
+    /// * `ptr` and `state` **must** belong to the same allocation.
     unsafe fn grow(ptr: &mut NonNull<u8>, state: &mut Self::State);
+    /// Makes room for at least `additional` more items with at most a single
+    /// reallocation.
+    ///
+    /// # Safety
+    /// * `ptr` and `state` **must** belong to the same allocation.
+    unsafe fn reserve(ptr: &mut NonNull<u8>, state: &mut Self::State, additional: usize);
+    /// # Safety
+    /// * `ptr` and `state` **must** belong to the same allocation.
     unsafe fn push(ptr: &mut NonNull<u8>, state: &mut Self::State, item: Self::Item);
+    /// # Safety
+    /// * `ptr` and `state` **must** belong to the same allocation.
     unsafe fn pop(ptr: NonNull<u8>, state: &mut Self::State) -> Option<Self::Item>;
+    /// Sets the number of items, items past it are forgotten without being
+    /// dropped.
+    ///
+    /// # Safety
+    /// * `len` **must not** exceed the capacity.
+    /// * The first `len` items **must** be initialized.
+    unsafe fn set_len(state: &mut Self::State, len: usize);
+    /// # Safety
+    /// * `ptr` and `state` **must** belong to the same allocation.
+    unsafe fn items(ptr: NonNull<u8>, state: &Self::State) -> *mut Self::Item;
+}
+
+/// Layout of a header `H` followed by `capacity` items `I`, padded to its
+/// alignment, the allocation of a [`BufferVec`] of that capacity.
+///
+/// # Panics
+/// If the size overflows `isize`.
+#[inline]
+pub fn buffer_vec_layout<H, I>(capacity: usize) -> Layout {
+    let items = Layout::array::<I>(capacity).expect("capacity overflow");
+
+    Layout::new::<H>()
+        .extend(items)
+        .expect("capacity overflow")
+        .0
+        .pad_to_align()
 }
 
+/// A `#[repr(transparent)]` newtype over [`BufferData`], implemented by
+/// `#[derive(BufferDataTransparent)]`.
+///
+/// # Safety
+/// * `Self` **must** be `#[repr(transparent)]` over `Inner`.
+/// * The [`BufferData`] implementation **must** forward to that of `Inner`.
+pub unsafe trait BufferDataTransparent: BufferData {
+    type Inner: BufferData<State = Self::State> + ?Sized;
+}
+
+/// A buffer of a [`BufferDataTransparent`] newtype bound as its inner type.
+#[repr(transparent)]
+struct InnerBinding<T: BufferData + ?Sized>(Buffer<T>);
+
+impl<T: BufferDataTransparent + ?Sized> Binding<T::Inner> for InnerBinding<T> {
+    fn binding_resource(&self) -> BindingResource {
+        Binding::<T>::binding_resource(&self.0)
+    }
+
+    fn resource_info(&self) -> ResourceInfo {
+        Binding::<T>::resource_info(&self.0)
+    }
+
+    fn prepare(&self, report: &mut TransferReport) {
+        Binding::<T>::prepare(&self.0, report)
+    }
+
+    fn read(&self, report: &mut TransferReport) {
+        Binding::<T>::read(&self.0, report)
+    }
+
+    fn needs_allocation(&self) -> bool {
+        Binding::<T>::needs_allocation(&self.0)
+    }
+
+    fn data_size(&self) -> Option<u64> {
+        Binding::<T>::data_size(&self.0)
+    }
+
+    fn download_async(&mut self) -> DownloadFuture<'_> {
+        Binding::<T>::download_async(&mut self.0)
+    }
+}
+
+impl<T: BufferDataTransparent + ?Sized> BindingMut<T::Inner> for InnerBinding<T> {
+    fn write(&mut self) {
+        BindingMut::<T>::write(&mut self.0)
+    }
+}
+
+/// How a [`Buffer`] may be bound, see [`Buffer::with_usage`].
+///
+/// Shaders check the usage against how each binding is declared, binding a
+/// buffer as `var<uniform>` requires [`Uniform`](Self::Uniform) or
+/// [`Any`](Self::Any), `var<storage>` requires [`Storage`](Self::Storage) or
+/// [`Any`](Self::Any).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BufferUsage {
+    /// Bound as `var<storage>`, the gpu buffer doesn't request `UNIFORM`.
+    Storage,
+    /// Bound as `var<uniform>`, the data can't exceed
+    /// `max_uniform_buffer_binding_size`.
+    Uniform,
+    /// Bound as either, [`BUFFER_USAGES`].
+    #[default]
+    Any,
+}
+
+impl BufferUsage {
+    /// Gets the usages of gpu buffers bound this way.
+    pub const fn buffer_usages(self) -> wgpu::BufferUsages {
+        match self {
+            Self::Storage => wgpu::BufferUsages::COPY_DST
+                .union(wgpu::BufferUsages::COPY_SRC)
+                .union(wgpu::BufferUsages::STORAGE),
+            Self::Uniform => wgpu::BufferUsages::COPY_DST
+                .union(wgpu::BufferUsages::COPY_SRC)
+                .union(wgpu::BufferUsages::UNIFORM),
+            Self::Any => BUFFER_USAGES,
+        }
+    }
+
+    /// Gets the largest size in bytes a buffer can be bound with.
+    fn max_binding_size(self, limits: &wgpu::Limits) -> u64 {
+        match self {
+            Self::Uniform => limits.max_uniform_buffer_binding_size as u64,
+            Self::Storage | Self::Any => limits.max_storage_buffer_binding_size as u64,
+        }
+    }
+}
+
+impl From<BufferUsage> for wgpu::BufferUsages {
+    #[inline]
+    fn from(usage: BufferUsage) -> Self {
+        usage.buffer_usages()
+    }
+}
+
+/// How a [`Buffer`] sizes its gpu buffer when the data outgrows it, see
+/// [`Buffer::with_growth_policy`].
+///
+/// Growing recreates the gpu buffer, giving it a new [`BufferId`] which
+/// invalidates the bind groups using it, and uploads all of the data again.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GrowthPolicy {
+    /// Exactly the size of the data, for memory constrained cases. Growing a
+    /// vec one item at a time recreates the gpu buffer every time.
+    Exact,
+    /// The next power of two above the size of the data, so the gpu buffer
+    /// is recreated about as often as the CPU side is reallocated.
+    #[default]
+    Double,
+    /// The size of the data rounded up to a multiple of the given number of
+    /// bytes, `Chunked(0)` is the same as [`Exact`](Self::Exact).
+    Chunked(u64),
+}
+
+impl GrowthPolicy {
+    /// Gets the size of a gpu buffer holding `size` bytes.
+    pub fn allocation_size(self, size: u64) -> u64 {
+        let size = match self {
+            Self::Exact | Self::Chunked(0) => size,
+            Self::Double => size.next_power_of_two(),
+            Self::Chunked(bytes) => size.next_multiple_of(bytes),
+        };
+
+        // copies are made in multiples of 4 bytes
+        size.max(4).next_multiple_of(4)
+    }
+}
+
+/// A buffer with a CPU side mirroring a gpu buffer.
+///
+/// The gpu buffer grows according to a [`GrowthPolicy`], but is always bound
+/// with the size of the data.
+///
+/// # Drop
+/// Dropping a buffer discards any pending GPU writes, the data is never
+/// downloaded just to be dropped. Use [`Buffer::into_contents`] to keep it.
 pub struct Buffer<T: BufferData + ?Sized> {
     value: NonNull<u8>,
     state: T::State,
     id: Mutex<BufferId>,
     buffer_size: AtomicU64,
+    growth: GrowthPolicy,
+    usage: BufferUsage,
+    needs_upload: AtomicBool,
     needs_download: AtomicBool,
+    instance: &'static Instance,
     marker: PhantomData<T>,
 }
 
+// SAFETY: Buffer owns its allocation.
+unsafe impl<T: BufferData + Send + ?Sized> Send for Buffer<T> where T::State: Send {}
+
 impl<T: BufferData + ?Sized> Binding<T> for Buffer<T> {
     fn binding_resource(&self) -> BindingResource {
+        // bound with the size of the data, so the slack left by the growth
+        // policy doesn't show up in `arrayLength`
         BindingResource::Buffer(BufferBinding {
             buffer: self.id(),
             offset: 0,
-            size: None,
+            size: NonZeroU64::new(self.data_size_bytes()),
         })
     }
 
-    fn prepare(&self) {
+    fn resource_info(&self) -> ResourceInfo {
+        // the gpu buffer is grown to fit the data before binding
+        ResourceInfo::Buffer {
+            id: self.id(),
+            size: self.data_size_bytes(),
+            usage: self.usage.buffer_usages(),
+        }
+    }
+
+    fn prepare(&self, report: &mut TransferReport) {
+        if self.usage == BufferUsage::Uniform {
+            let size = self.data_size_bytes();
+
+            if let Err(err) = self.instance.check_uniform_buffer_size(size) {
+                panic!("binding a uniform buffer of {} bytes failed: {}", size, err);
+            }
+        }
+
+        report.buffers_touched += 1;
         self.resize_buffer();
     }
 
-    fn read(&self) {
-        self.upload();
+    fn read(&self, report: &mut TransferReport) {
+        report.buffers_touched += 1;
+
+        let instance = self.instance;
+
+        // an upload dirty tracking would skip, the gpu buffer is up to date
+        if instance.force_upload() && (self.needs_download() || !self.needs_upload()) {
+            report.downloaded_bytes += self.download();
+            self.mark_needs_upload();
+
+            let bytes = self.upload();
+            report.uploaded_bytes += bytes;
+
+            instance.force_transfers.emit(ForcedTransfer {
+                direction: TransferDirection::Upload,
+                label: self.label(),
+                bytes,
+            });
+        } else {
+            report.uploaded_bytes += self.upload();
+        }
+    }
+
+    fn needs_allocation(&self) -> bool {
+        self.buffer_size.load(Ordering::Acquire) < self.data_size_bytes()
+    }
+
+    fn data_size(&self) -> Option<u64> {
+        Some(T::size(&self.state) as u64)
     }
 
+    fn download_async(&mut self) -> DownloadFuture<'_> {
+        Box::pin(Buffer::download_async(self))
+    }
+}
+
+impl<T: BufferData + ?Sized> BindingMut<T> for Buffer<T> {
     fn write(&mut self) {
         self.mark_needs_download();
+
+        let instance = self.instance;
+
+        if instance.force_download() {
+            instance.force_transfers.emit(ForcedTransfer {
+                direction: TransferDirection::Download,
+                label: self.label(),
+                bytes: self.download(),
+            });
+        }
     }
 }
 
@@ -80,89 +431,308 @@ impl<T: BufferData + ?Sized> Default for Buffer<T> {
     }
 }
 
+/// Implicitly downloads pending GPU writes, prefer [`Buffer::read`] to make
+/// the synchronization visible.
 impl<T: BufferData + ?Sized> Deref for Buffer<T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
-        self.download();
+        #[cfg(feature = "warn-implicit-sync")]
+        warn_implicit_sync(self.needs_download());
 
-        let ptr = unsafe { T::as_ptr(self.value, &self.state) };
+        self.download();
 
-        unsafe { &*ptr }
+        unsafe { self.data() }
     }
 }
 
+/// Implicitly downloads pending GPU writes, prefer [`Buffer::write`] to make
+/// the synchronization visible.
 impl<T: BufferData + ?Sized> DerefMut for Buffer<T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        #[cfg(feature = "warn-implicit-sync")]
+        warn_implicit_sync(self.needs_download());
+
         self.download();
+        self.mark_needs_upload();
+
+        unsafe { self.data_mut() }
+    }
+}
+
+#[cfg(feature = "warn-implicit-sync")]
+fn warn_implicit_sync(needs_download: bool) {
+    static WARNED: AtomicBool = AtomicBool::new(false);
+
+    if needs_download && !WARNED.swap(true, Ordering::Relaxed) {
+        eprintln!(
+            "warning: Buffer was downloaded through Deref, \
+            use .read()/.write() to make GPU synchronization explicit"
+        );
+    }
+}
+
+/// Read access to the CPU side of a [`Buffer`], returned by [`Buffer::read`].
+///
+/// The data is downloaded once when the guard is created, accesses through the
+/// guard never synchronize. The guard borrows the buffer, so it can't be
+/// dispatched or mutated while the guard is alive.
+pub struct ReadGuard<'a, T: BufferData + ?Sized> {
+    data: &'a T,
+}
+
+impl<T: BufferData + ?Sized> Deref for ReadGuard<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.data
+    }
+}
+
+/// Write access to the CPU side of a [`Buffer`], returned by [`Buffer::write`].
+///
+/// The data is downloaded once when the guard is created and marked as
+/// needing upload when it's dropped.
+pub struct WriteGuard<'a, T: BufferData + ?Sized> {
+    buffer: &'a mut Buffer<T>,
+}
 
-        let ptr = unsafe { T::as_ptr(self.value, &self.state) };
+impl<T: BufferData + ?Sized> Deref for WriteGuard<'_, T> {
+    type Target = T;
 
-        unsafe { &mut *ptr }
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.buffer.data() }
+    }
+}
+
+impl<T: BufferData + ?Sized> DerefMut for WriteGuard<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.buffer.data_mut() }
+    }
+}
+
+impl<T: BufferData + ?Sized> Drop for WriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.buffer.mark_needs_upload();
     }
 }
 
 impl<T: BufferData + ?Sized> Buffer<T> {
     #[inline]
     pub fn new() -> Self {
-        let value = unsafe { T::alloc() };
+        Self::with_growth_policy(GrowthPolicy::default())
+    }
+
+    /// Creates a buffer whose gpu buffer grows according to `growth`.
+    #[inline]
+    pub fn with_growth_policy(growth: GrowthPolicy) -> Self {
+        Self::create(growth, BufferUsage::default())
+    }
+
+    /// Creates a buffer that can only be bound as `usage`, e.g. a storage
+    /// buffer larger than uniform bindings allow.
+    #[inline]
+    pub fn with_usage(usage: BufferUsage) -> Self {
+        Self::create(GrowthPolicy::default(), usage)
+    }
+
+    /// Creates a buffer in `instance` rather than the global instance, it
+    /// can only be bound to shaders dispatched in `instance`, see
+    /// [`ComputeShaderBuilder::instance`](crate::ComputeShaderBuilder::instance).
+    #[inline]
+    pub fn new_in(instance: &'static Instance) -> Self {
+        Self::create_in(instance, GrowthPolicy::default(), BufferUsage::default())
+    }
+
+    /// Like [`Self::new`], failing with
+    /// [`DeviceError::OutOfMemory`](crate::DeviceError::OutOfMemory) if the
+    /// [OOM handler](Instance::set_oom_handler) gives up instead of panicking.
+    #[inline]
+    pub fn try_new() -> Result<Self, Error> {
+        Self::try_create_in(
+            Instance::global(),
+            GrowthPolicy::default(),
+            BufferUsage::default(),
+        )
+    }
+
+    fn create(growth: GrowthPolicy, usage: BufferUsage) -> Self {
+        Self::create_in(Instance::global(), growth, usage)
+    }
+
+    fn create_in(instance: &'static Instance, growth: GrowthPolicy, usage: BufferUsage) -> Self {
+        Self::try_create_in(instance, growth, usage).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_create_in(
+        instance: &'static Instance,
+        growth: GrowthPolicy,
+        usage: BufferUsage,
+    ) -> Result<Self, Error> {
         let state = T::init();
 
         let size = T::size(&state).max(4) as u64;
+        let id = try_create_buffer(instance, size, usage)?;
 
-        let device = &Instance::global().device;
+        let value = unsafe { T::alloc() };
 
-        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("shatter_buffer"),
-            size,
-            usage: wgpu::BufferUsages::COPY_DST
-                | wgpu::BufferUsages::COPY_SRC
-                | wgpu::BufferUsages::STORAGE
-                | wgpu::BufferUsages::UNIFORM,
-            mapped_at_creation: false,
-        });
+        Ok(Self {
+            value,
+            state,
+            id: Mutex::new(id),
+            buffer_size: AtomicU64::new(size),
+            growth,
+            usage,
+            needs_upload: AtomicBool::new(true),
+            needs_download: AtomicBool::new(false),
+            instance,
+            marker: PhantomData,
+        })
+    }
 
-        let id = Instance::global().buffers.next_id();
-        Instance::global().buffers.insert(id.clone(), buffer);
+    /// Creates a buffer mirroring the first `size` bytes of an existing gpu buffer,
+    /// the data is downloaded on first access.
+    pub(crate) fn from_gpu(
+        instance: &'static Instance,
+        id: BufferId,
+        size: u64,
+        buffer_size: u64,
+    ) -> Self {
+        let (value, state) = unsafe { T::alloc_zeroed(size as usize) };
 
         Self {
             value,
             state,
             id: Mutex::new(id),
-            buffer_size: AtomicU64::new(size),
-            needs_download: AtomicBool::new(false),
+            buffer_size: AtomicU64::new(buffer_size),
+            growth: GrowthPolicy::default(),
+            usage: BufferUsage::default(),
+            needs_upload: AtomicBool::new(false),
+            needs_download: AtomicBool::new(true),
+            instance,
             marker: PhantomData,
         }
     }
 
+    /// Downloads any pending GPU writes and returns an owned snapshot of the data.
+    #[inline]
+    pub fn into_contents(self) -> T::Contents {
+        self.download();
+
+        let mut buffer = ManuallyDrop::new(self);
+
+        // SAFETY: the data is moved into contents, so only the allocation is freed
+        unsafe {
+            let contents = T::contents(buffer.value, &buffer.state);
+            T::dealloc(buffer.value, &buffer.state);
+
+            std::ptr::drop_in_place(&mut buffer.state);
+            std::ptr::drop_in_place(&mut buffer.id);
+
+            contents
+        }
+    }
+
+    /// Drops the CPU side of the buffer, uploading any pending changes first.
+    #[inline]
+    pub fn into_gpu_only(self) -> GpuBuffer<T> {
+        self.upload();
+
+        GpuBuffer::from_raw(
+            self.instance,
+            self.id(),
+            T::size(&self.state) as u64,
+            self.buffer_size.load(Ordering::Acquire),
+        )
+    }
+
+    #[inline]
+    pub fn growth_policy(&self) -> GrowthPolicy {
+        self.growth
+    }
+
+    #[inline]
+    pub fn usage(&self) -> BufferUsage {
+        self.usage
+    }
+
+    /// Size in bytes the buffer is bound with, the size of the data.
+    #[inline]
+    fn data_size_bytes(&self) -> u64 {
+        T::size(&self.state).max(4) as u64
+    }
+
+    /// Grows the gpu buffer to fit the data according to the
+    /// [`GrowthPolicy`].
+    ///
+    /// The contents of the old gpu buffer are copied into the new one on the
+    /// GPU, so what a dispatch wrote survives without being downloaded.
     #[inline]
     pub fn resize_buffer(&self) {
-        if self.needs_download() {
-            self.download();
+        self.try_resize_buffer()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Like [`Self::resize_buffer`], failing with
+    /// [`DeviceError::OutOfMemory`](crate::DeviceError::OutOfMemory) if the
+    /// [OOM handler](Instance::set_oom_handler) gives up instead of panicking.
+    /// The buffer is left as it was.
+    pub fn try_resize_buffer(&self) -> Result<(), Error> {
+        let size = self.data_size_bytes();
+        let old_size = self.buffer_size.load(Ordering::Acquire);
+
+        if old_size >= size {
+            return Ok(());
         }
 
-        let size = T::size(&self.state).max(4) as u64;
+        let instance = self.instance;
 
-        if self.buffer_size.load(Ordering::Acquire) < size {
-            let device = &Instance::global().device;
+        // the slack is capped at what can be bound, the data itself isn't
+        let max = self.usage.max_binding_size(&instance.device.limits());
+        let size = self.growth.allocation_size(size).min(max).max(size);
 
-            let buffer = device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("shatter_buffer"),
-                size,
-                usage: wgpu::BufferUsages::COPY_DST
-                    | wgpu::BufferUsages::COPY_SRC
-                    | wgpu::BufferUsages::STORAGE
-                    | wgpu::BufferUsages::UNIFORM,
-                mapped_at_creation: false,
-            });
+        let new_id = try_create_buffer(instance, size, self.usage)?;
+        instance.stats.buffer_resized();
 
-            let id = Instance::global().buffers.next_id();
-            Instance::global().buffers.insert(id.clone(), buffer);
-            Instance::global().buffers.clean();
+        let mut id = self.id.lock().unwrap();
 
-            *self.id.lock().unwrap() = id;
-            self.buffer_size.store(size, Ordering::Release);
+        // a pending upload replaces the data anyway
+        if self.needs_download() || !self.needs_upload() {
+            let mut encoder = instance.device.create_command_encoder(&Default::default());
+            encoder.copy_buffer_to_buffer(
+                &instance.buffers.get(&id).unwrap(),
+                0,
+                &instance.buffers.get(&new_id).unwrap(),
+                0,
+                // copies are made in multiples of 4 bytes
+                old_size & !3,
+            );
+            instance.queue.submit(std::iter::once(encoder.finish()));
+            instance.submitted(Some(Cow::Borrowed("shatter_buffer_resize")), &[]);
         }
+
+        *id = new_id;
+        self.buffer_size.store(size, Ordering::Release);
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn needs_upload(&self) -> bool {
+        self.needs_upload.load(Ordering::Acquire)
+    }
+
+    /// Names the buffer in [`ForcedTransfer`]s.
+    fn label(&self) -> String {
+        format!("Buffer<{}>", std::any::type_name::<T>())
+    }
+
+    #[inline]
+    pub fn mark_needs_upload(&self) {
+        self.needs_upload.store(true, Ordering::Release);
     }
 
     #[inline]
@@ -175,125 +745,822 @@ impl<T: BufferData + ?Sized> Buffer<T> {
         self.needs_download.store(true, Ordering::Release);
     }
 
+    /// Uploads the data if it has changed, returns the number of bytes uploaded.
     #[inline]
-    pub fn upload(&self) {
+    pub fn upload(&self) -> u64 {
         // if we haven't downloaded, there is no need to upload
         // we know that the data hasn't changed since both reading
         // and writing requires downloading
         if self.needs_download() {
-            return;
+            return 0;
         }
 
         self.resize_buffer();
 
+        // the data hasn't been written to since the last upload
+        if !self.needs_upload.swap(false, Ordering::AcqRel) {
+            return 0;
+        }
+
         let size = T::size(&self.state);
 
         if size == 0 {
-            return;
+            return 0;
         }
 
         // SAFETY:
         // * BufferData ensures that size is valid.
         let slice = unsafe { std::slice::from_raw_parts(self.value.as_ptr(), size) };
 
+        let instance = self.instance;
+
         let id = self.id.lock().unwrap();
-        let buffer = Instance::global().buffers.get(&id).unwrap();
-        Instance::global().queue.write_buffer(&buffer, 0, slice);
+        let buffer = instance.buffers.get(&id).unwrap();
+        instance.queue.write_buffer(&buffer, 0, slice);
+
+        instance.stats.uploaded(size as u64);
+
+        size as u64
     }
 
+    /// Downloads pending GPU writes and returns a guard reading the data.
     #[inline]
-    pub fn download(&self) {
-        // if we don't need to download then don't
-        if !self.needs_download.swap(false, Ordering::AcqRel) {
-            return;
+    pub fn read(&self) -> ReadGuard<'_, T> {
+        self.download();
+
+        ReadGuard {
+            data: unsafe { self.data() },
         }
+    }
 
-        let device = &Instance::global().device;
+    /// Downloads pending GPU writes and returns a guard writing the data, the
+    /// data is uploaded on the next dispatch after the guard is dropped.
+    #[inline]
+    pub fn write(&mut self) -> WriteGuard<'_, T> {
+        self.download();
+
+        WriteGuard { buffer: self }
+    }
+
+    /// # Safety
+    /// * Pending GPU writes **must** have been downloaded.
+    #[inline]
+    unsafe fn data(&self) -> &T {
+        unsafe { &*T::as_ptr(self.value, &self.state) }
+    }
+
+    /// # Safety
+    /// * Pending GPU writes **must** have been downloaded.
+    #[inline]
+    unsafe fn data_mut(&mut self) -> &mut T {
+        unsafe { &mut *T::as_ptr(self.value, &self.state) }
+    }
+
+    /// Downloads the data if the GPU has written to it, returns the number of bytes downloaded.
+    ///
+    /// # Panics
+    /// Panics if the download fails, see [`Self::try_download`].
+    #[inline]
+    pub fn download(&self) -> u64 {
+        self.try_download().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Like [`Self::download`], returning [`DeviceError::Lost`](crate::DeviceError::Lost)
+    /// if the data can't be read back.
+    #[inline]
+    pub fn try_download(&self) -> Result<u64, Error> {
+        // the load keeps the common case free of read-modify-writes
+        if !self.needs_download() || !self.needs_download.swap(false, Ordering::AcqRel) {
+            return Ok(0);
+        }
+
+        let instance = self.instance;
 
         let size = T::size(&self.state);
 
         if size == 0 {
-            return;
+            return Ok(0);
         } else if size < 4 {
             panic!("wtf");
         }
 
         let size = size.max(4) as u64;
 
-        // TODO: cache the staging buffer
-        let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("shatter_buffer"),
-            size,
-            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        let staging_buffer = instance.acquire_staging(size);
 
         let id = self.id.lock().unwrap();
-        let buffer = Instance::global().buffers.get(&id).unwrap();
+        let buffer = instance.buffers.get(&id).unwrap();
 
         // copy data into the staging buffer
-        let mut encoder = device.create_command_encoder(&Default::default());
+        let mut encoder = instance.device.create_command_encoder(&Default::default());
         encoder.copy_buffer_to_buffer(&buffer, 0, &staging_buffer, 0, size);
-        Instance::global()
-            .queue
-            .submit(std::iter::once(encoder.finish()));
+        instance.queue.submit(std::iter::once(encoder.finish()));
+        instance.submitted(Some(Cow::Borrowed("shatter_download")), &[]);
+
+        // map the staging buffer, a failed map drops it rather than pooling it
+        let future = staging_buffer.slice(..size).map_async(wgpu::MapMode::Read);
+        instance.device.poll(wgpu::Maintain::Wait);
+        pollster::block_on(future)?;
+
+        {
+            // get a mutable slice of the data
+            let slice: &[u8] = &staging_buffer.slice(..size).get_mapped_range();
+
+            assert_eq!(slice.len(), size as usize);
+
+            // SAFETY:
+            // * BufferData ensures that size is valid.
+            // * a mutable reference is needed to mark needs_download.
+            //   any read or write to self.value requires a download.
+            //   download marks itself as not needing download.
+            //   therefore it's impossible to get here while a reference
+            //   to self.value is held.
+            // * self.value doesn't overlap with slice
+            // * align of u8 is 1 so pointers will always be properly aligned.
+            // * we have just asserted that the length if slice is equal to size.
+            unsafe {
+                std::ptr::copy_nonoverlapping(
+                    slice as *const [u8] as *const u8,
+                    self.value.as_ptr(),
+                    size as usize,
+                )
+            };
+        }
+
+        staging_buffer.unmap();
+        instance.release_staging(staging_buffer);
+
+        DOWNLOADED_BYTES.fetch_add(size, Ordering::AcqRel);
+        self.instance.stats.downloaded(size);
+
+        Ok(size)
+    }
+
+    /// Like [`Self::try_download`] without blocking, the device is polled by
+    /// [`Instance::poll`] until the data has been copied back.
+    pub fn download_async(&mut self) -> impl Future<Output = Result<u64, Error>> + Send + '_ {
+        let size = T::size(&self.state);
+
+        // only the copy is submitted here, the future borrows nothing but the mirror
+        let staging = (self.needs_download.swap(false, Ordering::AcqRel) && size > 0).then(|| {
+            let instance = self.instance;
+            let size = size.max(4) as u64;
+
+            let staging_buffer = instance.acquire_staging(size);
 
-        // map the staging buffer
-        let future = staging_buffer.slice(..).map_async(wgpu::MapMode::Read);
-        Instance::global().device.poll(wgpu::Maintain::Wait);
-        pollster::block_on(future).unwrap();
+            let id = self.id.get_mut().unwrap();
+            let buffer = instance.buffers.get(id).unwrap();
 
-        // get a mutable slice of the data
-        let slice: &[u8] = &staging_buffer.slice(..).get_mapped_range();
+            let mut encoder = instance.device.create_command_encoder(&Default::default());
+            encoder.copy_buffer_to_buffer(&buffer, 0, &staging_buffer, 0, size);
+            instance.queue.submit(std::iter::once(encoder.finish()));
+            instance.submitted(Some(Cow::Borrowed("shatter_download")), &[]);
 
-        assert_eq!(slice.len(), size as usize);
+            let map = Polled::new(staging_buffer.slice(..size).map_async(wgpu::MapMode::Read));
+
+            (staging_buffer, size, map)
+        });
 
         // SAFETY:
         // * BufferData ensures that size is valid.
-        // * a mutable reference is needed to mark needs_download.
-        //   any read or write to self.value requires a download.
-        //   download marks itself as not needing download.
-        //   therefore it's impossible to get here while a reference
-        //   to self.value is held.
-        // * self.value doesn't overlap with slice
-        // * align of u8 is 1 so pointers will always be properly aligned.
-        // * we have just asserted that the length if slice is equal to size.
-        unsafe {
-            std::ptr::copy_nonoverlapping(
-                slice as *const [u8] as *const u8,
-                self.value.as_ptr(),
-                size as usize,
-            )
-        };
+        // * the mirror is exclusively borrowed for the lifetime of the future.
+        let mirror = unsafe { std::slice::from_raw_parts_mut(self.value.as_ptr(), size) };
+        let restore = RestoreNeedsDownload(Some(&self.needs_download));
+        let instance = self.instance;
+
+        async move {
+            let (staging_buffer, size, map) = match staging {
+                Some(staging) => staging,
+                None => {
+                    restore.disarm();
+                    return Ok(0);
+                }
+            };
+
+            map.await?;
+
+            {
+                let slice = staging_buffer.slice(..size).get_mapped_range();
+                mirror.copy_from_slice(&slice[..mirror.len()]);
+            }
+
+            staging_buffer.unmap();
+            instance.release_staging(staging_buffer);
+            restore.disarm();
+
+            let size = mirror.len() as u64;
+
+            DOWNLOADED_BYTES.fetch_add(size, Ordering::AcqRel);
+            instance.stats.downloaded(size);
+
+            Ok(size)
+        }
     }
 
     #[inline]
     pub fn id(&self) -> BufferId {
         self.id.lock().unwrap().clone()
     }
+
+    /// Gets the instance the buffer was created in, see [`Self::new_in`].
+    #[inline]
+    pub fn instance(&self) -> &'static Instance {
+        self.instance
+    }
+
+    /// Registers the buffer in the global instance and returns a [`BufferHandle`] to it.
+    #[inline]
+    pub fn into_handle(self) -> BufferHandle<T>
+    where
+        T: Send + 'static,
+        T::State: Send,
+    {
+        Instance::global().handles.register(self)
+    }
+}
+
+impl<T: BufferDataTransparent + ?Sized> Buffer<T> {
+    /// Binds the buffer as the type `T` wraps, for bindings expecting the
+    /// generated struct rather than the newtype.
+    #[inline]
+    pub fn as_inner_binding(&self) -> &dyn Binding<T::Inner> {
+        // SAFETY: InnerBinding is repr(transparent) over Buffer<T>
+        unsafe { &*(self as *const Self as *const InnerBinding<T>) }
+    }
+
+    /// Mutably binds the buffer as the type `T` wraps, see [`Self::as_inner_binding`].
+    #[inline]
+    pub fn as_inner_binding_mut(&mut self) -> &mut dyn BindingMut<T::Inner> {
+        // SAFETY: InnerBinding is repr(transparent) over Buffer<T>
+        unsafe { &mut *(self as *mut Self as *mut InnerBinding<T>) }
+    }
 }
 
 impl<T: BufferVec + ?Sized> Buffer<T> {
+    /// Creates a buffer of `head` followed by `items`, allocating once for
+    /// exactly `items.len()` and copying them once. Uploaded on the next
+    /// dispatch.
+    pub fn from_vec(head: T::Head, mut items: Vec<T::Item>) -> Self {
+        let mut buffer = Self::new();
+        buffer.reserve(items.len());
+
+        // SAFETY: the allocation starts with the head and `reserve` made room
+        // for the items, which are moved out of `items`
+        unsafe {
+            std::ptr::write(buffer.value.as_ptr() as *mut T::Head, head);
+
+            let dst = T::items(buffer.value, &buffer.state);
+            std::ptr::copy_nonoverlapping(items.as_ptr(), dst, items.len());
+            T::set_len(&mut buffer.state, items.len());
+
+            items.set_len(0);
+        }
+
+        buffer
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         T::len(&self.state)
     }
 
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the dispatch covering every item along x with `work_group_size`,
+    /// see [`Dispatch::for_size`].
+    ///
+    /// # Panics
+    /// Panics if the dispatch is too large, see [`Dispatch::try_linear`].
+    pub fn dispatch_for(&self, work_group_size: WorkGroupSize) -> Dispatch {
+        Dispatch::try_linear(self.len() as u64, work_group_size)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
     #[inline]
     pub fn push(&mut self, item: T::Item) {
+        self.download();
+        self.mark_needs_upload();
+
         unsafe { T::push(&mut self.value, &mut self.state, item) };
     }
 
+    /// Like [`Self::push`], failing with
+    /// [`ResourceError::LimitExceeded`](crate::ResourceError::LimitExceeded) instead
+    /// of growing the buffer past what the device can bind.
+    pub fn try_push(&mut self, item: T::Item) -> Result<(), Error> {
+        let size = T::size(&self.state) as u64 + std::mem::size_of::<T::Item>() as u64;
+        self.instance.check_buffer_size(size)?;
+
+        self.try_download()?;
+        self.push(item);
+
+        Ok(())
+    }
+
     #[inline]
     pub fn pop(&mut self) -> Option<T::Item> {
+        self.download();
+        self.mark_needs_upload();
+
         unsafe { T::pop(self.value, &mut self.state) }
     }
+
+    /// Offset in bytes of the first item, the size of the header.
+    #[inline]
+    pub(crate) fn items_offset(&self) -> u64 {
+        let items = unsafe { T::items(self.value, &self.state) };
+
+        (items as usize - self.value.as_ptr() as usize) as u64
+    }
+
+    /// Number of items the buffer holds without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        T::capacity(&self.state)
+    }
+
+    /// Makes room for at least `additional` more items, reallocating at most once.
+    ///
+    /// Only the CPU side is reallocated, the gpu buffer grows on the next upload.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        unsafe { T::reserve(&mut self.value, &mut self.state, additional) };
+    }
+
+    /// Appends every item of `items`, reserving the lower bound of its size
+    /// hint up front.
+    pub fn extend<I: IntoIterator<Item = T::Item>>(&mut self, items: I) {
+        self.download();
+        self.mark_needs_upload();
+
+        let items = items.into_iter();
+        self.reserve(items.size_hint().0);
+
+        for item in items {
+            unsafe { T::push(&mut self.value, &mut self.state, item) };
+        }
+    }
+
+    /// Appends `items` with a single copy.
+    pub fn extend_from_slice(&mut self, items: &[T::Item])
+    where
+        T::Item: Copy,
+    {
+        self.download();
+        self.mark_needs_upload();
+        self.reserve(items.len());
+
+        let len = self.len();
+
+        // SAFETY: `reserve` made room for the items after `len`
+        unsafe {
+            let dst = T::items(self.value, &self.state).add(len);
+            std::ptr::copy_nonoverlapping(items.as_ptr(), dst, items.len());
+            T::set_len(&mut self.state, len + items.len());
+        }
+    }
+
+    /// Shortens the buffer to `len` items, keeping its capacity. Does nothing
+    /// if it's already shorter.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len() {
+            return;
+        }
+
+        self.download();
+        self.mark_needs_upload();
+
+        unsafe { T::set_len(&mut self.state, len) };
+    }
+
+    /// Removes every item, keeping the capacity.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Resizes the buffer to `len` items, calling `f` for every new item,
+    /// and grows the gpu buffer to fit right away.
+    pub fn resize_with(&mut self, len: usize, mut f: impl FnMut() -> T::Item) {
+        let current = self.len();
+
+        if len > current {
+            self.extend((current..len).map(|_| f()));
+        } else {
+            self.truncate(len);
+        }
+
+        self.resize_buffer();
+    }
+
+    /// Sets the number of items without touching them, e.g. to the count a
+    /// shader appended through an atomic counter, and grows the gpu buffer to
+    /// fit right away, keeping its contents.
+    ///
+    /// Doesn't download, if the GPU wrote to the buffer the items are
+    /// downloaded on the next CPU access as usual, otherwise the CPU side
+    /// items are uploaded on the next dispatch. Items past the end of the old
+    /// gpu buffer are zeroed on the GPU.
+    ///
+    /// # Safety
+    /// * `len` **must not** exceed the [capacity](Self::capacity), see [`Self::reserve`].
+    /// * The first `len` items **must** be initialized, on the GPU if it wrote
+    ///   to the buffer since the last download, otherwise on the CPU.
+    pub unsafe fn set_len(&mut self, len: usize) {
+        debug_assert!(len <= self.capacity(), "`len` exceeds the capacity");
+
+        unsafe { T::set_len(&mut self.state, len) };
+
+        if !self.needs_download() {
+            self.mark_needs_upload();
+        }
+
+        self.resize_buffer();
+    }
+
+    /// Gets the items, downloading pending GPU writes like [`Deref`].
+    #[inline]
+    pub fn as_slice(&self) -> &[T::Item] {
+        self.download();
+
+        unsafe { std::slice::from_raw_parts(T::items(self.value, &self.state), self.len()) }
+    }
+
+    /// Gets the items mutably, downloading pending GPU writes and uploading
+    /// on the next dispatch like [`DerefMut`].
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T::Item] {
+        self.download();
+        self.mark_needs_upload();
+
+        unsafe { std::slice::from_raw_parts_mut(T::items(self.value, &self.state), self.len()) }
+    }
+
+    /// Splits the buffer into its head and items, downloading pending GPU
+    /// writes first. The items are copied out once.
+    pub fn into_vec(self) -> (T::Head, Vec<T::Item>) {
+        self.download();
+
+        let len = self.len();
+        let mut items = Vec::with_capacity(len);
+        let mut buffer = ManuallyDrop::new(self);
+
+        // SAFETY: the head and items are moved out, so only the allocation is freed
+        unsafe {
+            let head = std::ptr::read(buffer.value.as_ptr() as *const T::Head);
+
+            let src = T::items(buffer.value, &buffer.state);
+            std::ptr::copy_nonoverlapping(src, items.as_mut_ptr(), len);
+            items.set_len(len);
+
+            T::dealloc(buffer.value, &buffer.state);
+
+            std::ptr::drop_in_place(&mut buffer.state);
+            std::ptr::drop_in_place(&mut buffer.id);
+
+            (head, items)
+        }
+    }
+
+    /// Copies the items out, downloading pending GPU writes first.
+    #[inline]
+    pub fn to_vec(&self) -> Vec<T::Item>
+    where
+        T::Item: Clone,
+    {
+        self.as_slice().to_vec()
+    }
+
+    /// Gets the offset and size in bytes of item `index` in the gpu buffer.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    fn item_range(&self, index: usize) -> (u64, u64) {
+        let len = self.len();
+
+        assert!(
+            index < len,
+            "index {} is out of bounds of {} of length {}",
+            index,
+            self.label(),
+            len
+        );
+
+        // strides and offsets of WGSL items are multiples of 4, as copies need
+        let offset = self.items_offset() + (index * T::ITEM_STRIDE) as u64;
+
+        (offset, T::ITEM_STRIDE as u64)
+    }
+
+    /// Reads item `index`, downloading only its bytes if the GPU wrote to
+    /// the buffer, without downloading the rest.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds or the download fails.
+    pub fn read_element(&self, index: usize) -> T::Item
+    where
+        T::Item: Copy,
+    {
+        let (offset, size) = self.item_range(index);
+
+        if !self.needs_download() {
+            return unsafe { *T::items(self.value, &self.state).add(index) };
+        }
+
+        let instance = self.instance;
+        let staging_buffer = instance.acquire_staging(size);
+
+        {
+            let id = self.id.lock().unwrap();
+            let buffer = instance.buffers.get(&id).unwrap();
+
+            let mut encoder = instance.device.create_command_encoder(&Default::default());
+            encoder.copy_buffer_to_buffer(&buffer, offset, &staging_buffer, 0, size);
+            instance.queue.submit(std::iter::once(encoder.finish()));
+            instance.submitted(Some(Cow::Borrowed("shatter_read_element")), &[]);
+        }
+
+        let future = staging_buffer.slice(..size).map_async(wgpu::MapMode::Read);
+        instance.device.poll(wgpu::Maintain::Wait);
+
+        if let Err(err) = pollster::block_on(future) {
+            panic!("{}", Error::from(err));
+        }
+
+        let item = {
+            let slice = staging_buffer.slice(..size).get_mapped_range();
+
+            // SAFETY: BufferVec ensures the bytes of an item are a valid item
+            unsafe { std::ptr::read_unaligned(slice.as_ptr() as *const T::Item) }
+        };
+
+        staging_buffer.unmap();
+        instance.release_staging(staging_buffer);
+
+        DOWNLOADED_BYTES.fetch_add(size, Ordering::AcqRel);
+        instance.stats.downloaded(size);
+
+        item
+    }
+
+    /// Writes item `index`, uploading only its bytes unless the whole buffer
+    /// is uploaded on the next dispatch anyway.
+    ///
+    /// What the GPU wrote to the rest of the buffer stays pending, the item
+    /// is written to the gpu buffer as well, so downloading it later doesn't
+    /// overwrite the item.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn write_element(&mut self, index: usize, item: T::Item) {
+        let (offset, size) = self.item_range(index);
+
+        let ptr = unsafe { T::items(self.value, &self.state).add(index) };
+        unsafe { *ptr = item };
+
+        if self.needs_upload() && !self.needs_download() {
+            return;
+        }
+
+        // SAFETY: BufferData ensures the item can be cast to bytes
+        let bytes = unsafe { std::slice::from_raw_parts(ptr as *const u8, size as usize) };
+
+        let instance = self.instance;
+        let id = self.id.get_mut().unwrap();
+        let buffer = instance.buffers.get(id).unwrap();
+        instance.queue.write_buffer(&buffer, offset, bytes);
+
+        instance.stats.uploaded(size);
+    }
+
+    /// Replaces the items of the buffer with `items`, uploading them in chunks of
+    /// [`DEFAULT_STREAM_CHUNK_SIZE`] bytes, see [`Buffer::stream_items_with`].
+    #[inline]
+    pub fn stream_items<I>(&mut self, count_hint: usize, items: I) -> StreamReport
+    where
+        I: IntoIterator<Item = T::Item>,
+    {
+        self.stream_items_with(count_hint, items, DEFAULT_STREAM_CHUNK_SIZE, |_| {})
+    }
+
+    /// Replaces the items of the buffer with `items`, writing them straight into
+    /// staging chunks of at most `chunk_size` bytes which are uploaded as they fill up.
+    ///
+    /// The full CPU mirror is never materialized, it is allocated zeroed and only
+    /// filled when the buffer is read. `count_hint` sizes the gpu buffer up front,
+    /// more items grow it. `progress` is called with the number of items written
+    /// after each chunk.
+    ///
+    /// # Panics
+    /// * If the buffer would exceed the maximum storage buffer binding size.
+    /// * If `T::Item` is zero sized.
+    ///
+    /// The buffer is left unchanged if this panics, including when `items` or
+    /// `progress` does.
+    pub fn stream_items_with<I, F>(
+        &mut self,
+        count_hint: usize,
+        items: I,
+        chunk_size: usize,
+        mut progress: F,
+    ) -> StreamReport
+    where
+        I: IntoIterator<Item = T::Item>,
+        F: FnMut(usize),
+    {
+        let instance = self.instance;
+
+        let item_size = std::mem::size_of::<T::Item>();
+        assert!(item_size > 0, "cannot stream zero sized items");
+
+        let header_size = T::size(&T::init());
+        let check_size = |size: u64| {
+            if let Err(err) = instance.check_buffer_size(size) {
+                panic!("streaming into a buffer of {} bytes failed: {}", size, err);
+            }
+        };
+
+        let mut buffer_size = (header_size as u64 + count_hint as u64 * item_size as u64).max(4);
+        check_size(buffer_size);
+
+        // keep the header, everything after it is replaced
+        self.download();
+        let header =
+            unsafe { std::slice::from_raw_parts(self.value.as_ptr(), header_size) }.to_vec();
+
+        let mut id = create_buffer(self.instance, buffer_size, self.usage);
+
+        if !header.is_empty() {
+            let buffer = instance.buffers.get(&id).unwrap();
+            instance.queue.write_buffer(&buffer, 0, &header);
+        }
+
+        let items_per_chunk = (chunk_size / item_size).max(1);
+        let mut chunk = Vec::with_capacity(items_per_chunk * item_size);
+        let mut items = items.into_iter();
+
+        let mut report = StreamReport {
+            uploaded_bytes: header.len() as u64,
+            ..Default::default()
+        };
+
+        loop {
+            chunk.clear();
+
+            for item in items.by_ref().take(items_per_chunk) {
+                // SAFETY:
+                // * BufferData ensures that items can be cast to bytes.
+                let bytes = unsafe {
+                    std::slice::from_raw_parts(&item as *const T::Item as *const u8, item_size)
+                };
+
+                chunk.extend_from_slice(bytes);
+            }
+
+            if chunk.is_empty() {
+                break;
+            }
+
+            let offset = (header_size + report.items * item_size) as u64;
+            let end = offset + chunk.len() as u64;
+
+            // more items than hinted, move what's been written to a larger buffer
+            if end > buffer_size {
+                let new_size = (buffer_size * 2).max(end);
+                check_size(new_size);
+
+                let new_id = create_buffer(instance, new_size, self.usage);
+
+                let mut encoder = instance.device.create_command_encoder(&Default::default());
+                encoder.copy_buffer_to_buffer(
+                    &instance.buffers.get(&id).unwrap(),
+                    0,
+                    &instance.buffers.get(&new_id).unwrap(),
+                    0,
+                    offset,
+                );
+                instance.queue.submit(std::iter::once(encoder.finish()));
+                instance.submitted(Some(Cow::Borrowed("shatter_buffer_resize")), &[]);
+
+                id = new_id;
+                buffer_size = new_size;
+            }
+
+            let buffer = instance.buffers.get(&id).unwrap();
+            instance.queue.write_buffer(&buffer, offset, &chunk);
+            drop(buffer);
+
+            // submit and wait for the write so only a single chunk is ever staged
+            instance.queue.submit(std::iter::empty());
+            instance.submitted(Some(Cow::Borrowed("shatter_stream")), &[]);
+            instance.device.poll(wgpu::Maintain::Wait);
+
+            report.items += chunk.len() / item_size;
+            report.uploaded_bytes += chunk.len() as u64;
+            report.peak_staging_bytes = report.peak_staging_bytes.max(chunk.len() as u64);
+
+            progress(report.items);
+        }
+
+        // replace the mirror with a zeroed allocation, which isn't backed by
+        // memory until it's written to when downloading
+        let (value, state) = unsafe { T::alloc_zeroed(header_size + report.items * item_size) };
+        unsafe { std::ptr::copy_nonoverlapping(header.as_ptr(), value.as_ptr(), header.len()) };
+
+        // the buffer holds the new data before the old is dropped, which may panic
+        let old_value = std::mem::replace(&mut self.value, value);
+        let old_state = std::mem::replace(&mut self.state, state);
+
+        unsafe { drop_data::<T>(old_value, &old_state) };
+
+        *self.id.get_mut().unwrap() = id;
+        *self.buffer_size.get_mut() = buffer_size;
+        *self.needs_upload.get_mut() = false;
+        *self.needs_download.get_mut() = true;
+
+        instance.stats.uploaded(report.uploaded_bytes);
+
+        report
+    }
+}
+
+/// Marks a buffer as needing download again unless disarmed, for async
+/// downloads dropped or failing before the data was copied.
+struct RestoreNeedsDownload<'a>(Option<&'a AtomicBool>);
+
+impl RestoreNeedsDownload<'_> {
+    #[inline]
+    fn disarm(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for RestoreNeedsDownload<'_> {
+    fn drop(&mut self) {
+        if let Some(needs_download) = self.0 {
+            needs_download.store(true, Ordering::Release);
+        }
+    }
+}
+
+fn create_buffer(instance: &Instance, size: u64, usage: BufferUsage) -> BufferId {
+    try_create_buffer(instance, size, usage).unwrap_or_else(|err| panic!("{}", err))
+}
+
+fn try_create_buffer(
+    instance: &Instance,
+    size: u64,
+    usage: BufferUsage,
+) -> Result<BufferId, Error> {
+    let buffer = instance.create_resource(ResourceKind::Buffer, "shatter_buffer", size, || {
+        instance.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("shatter_buffer"),
+            size,
+            usage: usage.buffer_usages(),
+            mapped_at_creation: false,
+        })
+    })?;
+
+    let id = instance.buffers.stable_id("shatter_buffer");
+    instance.buffers.insert_sized(id.clone(), buffer, size);
+
+    Ok(id)
+}
+
+/// Deallocates data when dropped, so it's freed even if dropping it panics.
+struct DeallocOnDrop<'a, T: BufferData + ?Sized> {
+    value: NonNull<u8>,
+    state: &'a T::State,
+}
+
+impl<T: BufferData + ?Sized> Drop for DeallocOnDrop<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { T::dealloc(self.value, self.state) };
+    }
+}
+
+/// Drops and deallocates data of `T`.
+///
+/// # Safety
+/// * `value` and `state` **must** describe a live allocation of `T`, which
+///   **must not** be used afterwards.
+unsafe fn drop_data<T: BufferData + ?Sized>(value: NonNull<u8>, state: &T::State) {
+    let _dealloc = DeallocOnDrop::<T> { value, state };
+
+    if T::NEEDS_DROP {
+        unsafe { std::ptr::drop_in_place(T::as_ptr(value, state)) };
+    }
 }
 
 impl<T: BufferData + ?Sized> Drop for Buffer<T> {
     #[inline]
     fn drop(&mut self) {
-        unsafe { std::ptr::drop_in_place(T::as_ptr(self.value, &self.state)) };
-        unsafe { T::dealloc(self.value, &self.state) };
+        // pending GPU writes are discarded
+        unsafe { drop_data::<T>(self.value, &self.state) };
     }
 }
@@ -0,0 +1,333 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Number of characters of the newest source included in a [`CacheChurn`].
+const PREVIEW_CHARS: usize = 100;
+
+/// Number of characters of either key shown by a diff hint.
+const DIFF_CHARS: usize = 40;
+
+/// A cache of [`Instance`](crate::Instance) watched for unbounded growth.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CacheKind {
+    ShaderModule,
+    ComputePipeline,
+}
+
+impl fmt::Display for CacheKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ShaderModule => write!(f, "shader module"),
+            Self::ComputePipeline => write!(f, "compute pipeline"),
+        }
+    }
+}
+
+/// Limits on how fast and how far the shader module and pipeline caches may grow.
+///
+/// Caches are keyed by content, so a source that changes every frame, e.g. by
+/// a timestamp comment injected by a generator, creates a new module and
+/// pipeline every frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CacheLimits {
+    /// More insertions into a cache than this within
+    /// [`churn_window`](Self::churn_window) emit a [`CacheChurn`].
+    pub churn_insertions: usize,
+    pub churn_window: Duration,
+    /// Shader modules after which inserting fails with [`CacheFull`].
+    pub max_shader_modules: Option<usize>,
+    /// Compute pipelines after which inserting fails with [`CacheFull`].
+    pub max_compute_pipelines: Option<usize>,
+    /// Distinct values a constant may be specialized for before emitting a
+    /// [`ManySpecializations`], see
+    /// [`ComputeShaderBuilder::specialize_extent`](crate::ComputeShaderBuilder::specialize_extent).
+    pub max_specializations: usize,
+}
+
+impl Default for CacheLimits {
+    fn default() -> Self {
+        Self {
+            churn_insertions: 32,
+            churn_window: Duration::from_secs(1),
+            max_shader_modules: None,
+            max_compute_pipelines: None,
+            max_specializations: 8,
+        }
+    }
+}
+
+impl CacheLimits {
+    #[inline]
+    pub fn max_entries(&self, kind: CacheKind) -> Option<usize> {
+        match kind {
+            CacheKind::ShaderModule => self.max_shader_modules,
+            CacheKind::ComputePipeline => self.max_compute_pipelines,
+        }
+    }
+}
+
+/// Emitted when a cache grows faster than [`CacheLimits`] allow, see
+/// [`Instance::set_cache_churn_hook`](crate::Instance::set_cache_churn_hook).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CacheChurn {
+    pub kind: CacheKind,
+    /// Insertions within [`window`](Self::window).
+    pub insertions: usize,
+    pub window: Duration,
+    /// Start of the newest key, the source of shader modules.
+    pub preview: String,
+    /// Where the newest key first differs from the previous one.
+    pub diff_hint: Option<String>,
+}
+
+impl fmt::Display for CacheChurn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} cache grew by {} entries within {:?}, are keys changing every frame?",
+            self.kind, self.insertions, self.window
+        )?;
+        write!(f, "  newest: {:?}", self.preview)?;
+
+        if let Some(ref diff_hint) = self.diff_hint {
+            write!(f, "\n  {}", diff_hint)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Emitted when a constant is specialized for more distinct values than
+/// [`CacheLimits::max_specializations`], see
+/// [`Instance::set_specialization_hook`](crate::Instance::set_specialization_hook).
+///
+/// Each value creates a pipeline, a constant taking many values is better
+/// passed as a uniform.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManySpecializations {
+    pub entry_point: String,
+    pub constant: String,
+    /// Distinct values specialized for.
+    pub values: usize,
+}
+
+impl fmt::Display for ManySpecializations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` of `{}` was specialized for {} distinct values, consider a uniform instead",
+            self.constant, self.entry_point, self.values
+        )
+    }
+}
+
+/// Inserting into a cache would exceed [`CacheLimits`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CacheFull {
+    pub kind: CacheKind,
+    pub max_entries: usize,
+}
+
+impl fmt::Display for CacheFull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} cache is full ({} entries), see `CacheLimits`",
+            self.kind, self.max_entries
+        )
+    }
+}
+
+impl std::error::Error for CacheFull {}
+
+pub(crate) type ChurnHook = Arc<dyn Fn(&CacheChurn) + Send + Sync>;
+pub(crate) type SpecializationHook = Arc<dyn Fn(&ManySpecializations) + Send + Sync>;
+
+#[derive(Default)]
+struct Tracker {
+    insertions: VecDeque<Instant>,
+    previous: Option<String>,
+    warned_at: Option<Instant>,
+}
+
+/// Distinct values of a specialized constant, `None` once warned.
+type Specializations = Option<HashSet<u32>>;
+
+/// Tracks insertions into the watched caches of an instance.
+pub(crate) struct CacheWatch {
+    limits: Mutex<CacheLimits>,
+    hook: Mutex<Option<ChurnHook>>,
+    shader_modules: Mutex<Tracker>,
+    compute_pipelines: Mutex<Tracker>,
+    specialization_hook: Mutex<Option<SpecializationHook>>,
+    specializations: Mutex<HashMap<(String, String), Specializations>>,
+}
+
+impl CacheWatch {
+    pub fn new(limits: CacheLimits) -> Self {
+        Self {
+            limits: Mutex::new(limits),
+            hook: Mutex::new(None),
+            shader_modules: Mutex::default(),
+            compute_pipelines: Mutex::default(),
+            specialization_hook: Mutex::new(None),
+            specializations: Mutex::default(),
+        }
+    }
+
+    pub fn limits(&self) -> CacheLimits {
+        *self.limits.lock().unwrap()
+    }
+
+    pub fn set_limits(&self, limits: CacheLimits) {
+        *self.limits.lock().unwrap() = limits;
+    }
+
+    pub fn set_hook(&self, hook: Option<ChurnHook>) {
+        *self.hook.lock().unwrap() = hook;
+    }
+
+    pub fn set_specialization_hook(&self, hook: Option<SpecializationHook>) {
+        *self.specialization_hook.lock().unwrap() = hook;
+    }
+
+    /// Records a specialization of `constant` of `entry_point` for `value`,
+    /// emitting a [`ManySpecializations`] once too many distinct values are seen.
+    pub fn record_specialization(&self, entry_point: &str, constant: &str, value: u32) {
+        let max_specializations = self.limits().max_specializations;
+
+        let warning = {
+            let mut specializations = self.specializations.lock().unwrap();
+
+            let values = specializations
+                .entry((entry_point.into(), constant.into()))
+                .or_insert_with(|| Some(HashSet::new()));
+
+            let distinct = match values {
+                Some(set) => {
+                    set.insert(value);
+                    set.len()
+                }
+                None => 0,
+            };
+
+            if distinct > max_specializations {
+                *values = None;
+
+                Some(ManySpecializations {
+                    entry_point: entry_point.into(),
+                    constant: constant.into(),
+                    values: distinct,
+                })
+            } else {
+                None
+            }
+        };
+
+        if let Some(warning) = warning {
+            let hook = self.specialization_hook.lock().unwrap().clone();
+
+            match hook {
+                Some(hook) => hook(&warning),
+                None => eprintln!("shatter: {}", warning),
+            }
+        }
+    }
+
+    /// Checks that a cache holding `len` entries may grow.
+    pub fn check_capacity(&self, kind: CacheKind, len: usize) -> Result<(), CacheFull> {
+        match self.limits().max_entries(kind) {
+            Some(max_entries) if len >= max_entries => Err(CacheFull { kind, max_entries }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Records an insertion of `key`, emitting a [`CacheChurn`] if the cache
+    /// grows too fast. Warnings are emitted at most once per window.
+    pub fn record_insertion(&self, kind: CacheKind, key: &str) {
+        let limits = self.limits();
+        let now = Instant::now();
+
+        let churn = {
+            let mut tracker = match kind {
+                CacheKind::ShaderModule => self.shader_modules.lock().unwrap(),
+                CacheKind::ComputePipeline => self.compute_pipelines.lock().unwrap(),
+            };
+
+            while let Some(&first) = tracker.insertions.front() {
+                if now.duration_since(first) <= limits.churn_window {
+                    break;
+                }
+
+                tracker.insertions.pop_front();
+            }
+
+            tracker.insertions.push_back(now);
+
+            let recently_warned = tracker
+                .warned_at
+                .is_some_and(|warned_at| now.duration_since(warned_at) < limits.churn_window);
+
+            let churn = if tracker.insertions.len() > limits.churn_insertions && !recently_warned {
+                tracker.warned_at = Some(now);
+
+                Some(CacheChurn {
+                    kind,
+                    insertions: tracker.insertions.len(),
+                    window: limits.churn_window,
+                    preview: key.chars().take(PREVIEW_CHARS).collect(),
+                    diff_hint: tracker
+                        .previous
+                        .as_deref()
+                        .and_then(|previous| diff_hint(previous, key)),
+                })
+            } else {
+                None
+            };
+
+            tracker.previous = Some(key.into());
+
+            churn
+        };
+
+        if let Some(churn) = churn {
+            let hook = self.hook.lock().unwrap().clone();
+
+            match hook {
+                Some(hook) => hook(&churn),
+                None => eprintln!("shatter: {}", churn),
+            }
+        }
+    }
+}
+
+/// Describes where `new` first differs from `previous`.
+fn diff_hint(previous: &str, new: &str) -> Option<String> {
+    let start = previous
+        .char_indices()
+        .zip(new.chars())
+        .find(|((_, a), b)| a != b)
+        .map(|((i, _), _)| i)
+        .unwrap_or_else(|| previous.len().min(new.len()));
+
+    if start == previous.len() && start == new.len() {
+        return None;
+    }
+
+    let line = new[..start].matches('\n').count() + 1;
+    let column = start - new[..start].rfind('\n').map_or(0, |i| i + 1) + 1;
+
+    let snippet = |source: &str| source[start..].chars().take(DIFF_CHARS).collect::<String>();
+
+    Some(format!(
+        "differs from the previous at line {} column {}: {:?} was {:?}",
+        line,
+        column,
+        snippet(new),
+        snippet(previous)
+    ))
+}
@@ -1,5 +1,25 @@
 use crate::TextureData;
 
+/// A 3 channel color, e.g. [`Rgb8U`].
+///
+/// No texture format has 3 channels, so these colors can't be stored in a
+/// texture. They're packed into their 4 channel counterpart with
+/// [`pack_rgb_to_rgba`](crate::convert::pack_rgb_to_rgba) or
+/// [`Texture2d::from_rgb_data`](crate::Texture2d::from_rgb_data) instead.
+pub trait RgbColor: TextureData + Default {
+    type Rgba: RgbaColor<Rgb = Self>;
+    type Channel: Copy;
+
+    fn with_alpha(self, a: Self::Channel) -> Self::Rgba;
+}
+
+/// A 4 channel color with a 3 channel counterpart, see [`RgbColor`].
+pub trait RgbaColor: TextureData + Default {
+    type Rgb: RgbColor<Rgba = Self>;
+
+    fn to_rgb(self) -> Self::Rgb;
+}
+
 macro_rules! color {
     {
 		colors: [$d1:ident, $d2:ident, $d3: ident, $d4:ident],
@@ -114,11 +134,41 @@ macro_rules! color {
 			pub const fn rgba(r: $data, g: $data, b: $data, a: $data) -> Self {
 				Self { r, g, b, a }
 			}
+
+			#[inline]
+			pub const fn from_rgb(color: $d3, a: $data) -> Self {
+				Self { r: color.r, g: color.g, b: color.b, a }
+			}
+
+			/// Drops the alpha channel.
+			#[inline]
+			pub const fn to_rgb(self) -> $d3 {
+				$d3 { r: self.r, g: self.g, b: self.b }
+			}
+		}
+
+		impl RgbColor for $d3 {
+			type Rgba = $d4;
+			type Channel = $data;
+
+			#[inline]
+			fn with_alpha(self, a: $data) -> $d4 {
+				$d4::from_rgb(self, a)
+			}
+		}
+
+		impl RgbaColor for $d4 {
+			type Rgb = $d3;
+
+			#[inline]
+			fn to_rgb(self) -> $d3 {
+				$d4::to_rgb(self)
+			}
 		}
 
-		impl Into<[$data; 4]> for $d4 {
-			fn into(self) -> [$data; 4] {
-				[self.r, self.g, self.b, self.a]
+		impl From<$d4> for [$data; 4] {
+			fn from(color: $d4) -> Self {
+				[color.r, color.g, color.b, color.a]
 			}
 		}
 	};
@@ -172,3 +222,104 @@ color! {
     zero: 0.0,
     one: 1.0,
 }
+
+#[cfg(feature = "f16")]
+pub use half::f16;
+
+#[cfg(feature = "f16")]
+color! {
+    colors: [R16F, Rg16F, Rgb16F, Rgba16F],
+    data: half::f16,
+    zero: half::f16::ZERO,
+    one: half::f16::ONE,
+}
+
+#[cfg(feature = "f16")]
+macro_rules! f16_conversions {
+    ($($half:ident <-> $full:ident { $($c:ident),* }),* $(,)?) => {
+        $(
+            impl From<$full> for $half {
+                #[inline]
+                fn from(color: $full) -> Self {
+                    Self {
+                        $($c: half::f16::from_f32(color.$c)),*
+                    }
+                }
+            }
+
+            impl From<$half> for $full {
+                #[inline]
+                fn from(color: $half) -> Self {
+                    Self {
+                        $($c: color.$c.to_f32()),*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "f16")]
+f16_conversions! {
+    R16F <-> R32 { r },
+    Rg16F <-> Rg32 { r, g },
+    Rgb16F <-> Rgb32 { r, g, b },
+    Rgba16F <-> Rgba32 { r, g, b, a },
+}
+
+impl From<Rgba32> for wgpu::Color {
+    #[inline]
+    fn from(color: Rgba32) -> Self {
+        Self {
+            r: color.r as f64,
+            g: color.g as f64,
+            b: color.b as f64,
+            a: color.a as f64,
+        }
+    }
+}
+
+impl From<wgpu::Color> for Rgba32 {
+    #[inline]
+    fn from(color: wgpu::Color) -> Self {
+        Self::rgba(
+            color.r as f32,
+            color.g as f32,
+            color.b as f32,
+            color.a as f32,
+        )
+    }
+}
+
+/// An 8 bit color stored in blue, green, red, alpha order, as used by most swapchains.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Bgra8U {
+    pub b: u8,
+    pub g: u8,
+    pub r: u8,
+    pub a: u8,
+}
+
+unsafe impl TextureData for Bgra8U {}
+
+impl Bgra8U {
+    #[inline]
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { b, g, r, a }
+    }
+}
+
+impl From<Rgba8U> for Bgra8U {
+    #[inline]
+    fn from(color: Rgba8U) -> Self {
+        Self::rgba(color.r, color.g, color.b, color.a)
+    }
+}
+
+impl From<Bgra8U> for Rgba8U {
+    #[inline]
+    fn from(color: Bgra8U) -> Self {
+        Self::rgba(color.r, color.g, color.b, color.a)
+    }
+}
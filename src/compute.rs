@@ -1,4 +1,21 @@
-use crate::{Bindings, ComputePipelineDescriptor, Instance, PipelineLayoutDescriptor};
+use std::{
+    borrow::Cow,
+    fmt,
+    marker::PhantomData,
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+
+use smallvec::SmallVec;
+
+use crate::{
+    record_dispatch_args, reflect_entry_point, resolve_bind_groups, resolve_layouts,
+    BindGroupLayoutDescriptor, BindGroupLayoutId, Bindings, BufferData, BufferId,
+    ComputePipelineDescriptor, ComputePipelineId, DispatchTiming, DynamicBindingReport,
+    DynamicBindings, GpuBuffer, Instance, LaunchParams, PassStatistics, PipelineLayoutDescriptor,
+    Reflect, ReflectedBinding, SourceError, StatisticsQuery, StructLayout, SubmittedWork,
+    TimestampQuery, TransferReport,
+};
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct Dispatch {
@@ -11,6 +28,195 @@ impl Dispatch {
     pub const fn new(x: u32, y: u32, z: u32) -> Self {
         Self { x, y, z }
     }
+
+    /// Returns true if the dispatch has no work groups.
+    pub const fn is_empty(&self) -> bool {
+        self.x == 0 || self.y == 0 || self.z == 0
+    }
+
+    /// Number of invocations launched with `work_group_size`, including
+    /// those outside the data a dispatch was rounded up to cover.
+    pub const fn invocations(&self, work_group_size: WorkGroupSize) -> u64 {
+        (self.x as u64 * self.y as u64 * self.z as u64)
+            * (work_group_size.x as u64 * work_group_size.y as u64 * work_group_size.z as u64)
+    }
+
+    /// Gets the dispatch covering `elements` invocations along x, rounded up
+    /// to whole work groups.
+    ///
+    /// Fails if the invocations can't be counted or indexed with u32, or if more than [`MAX_WORK_GROUPS_PER_AXIS`] work groups are needed, in
+    /// which case the error holds a dispatch spreading them over the axes.
+    pub fn try_linear(
+        elements: u64,
+        work_group_size: WorkGroupSize,
+    ) -> Result<Dispatch, DispatchTooLarge> {
+        Self::try_for_extent([elements, 1, 1], work_group_size)
+    }
+
+    /// Gets the dispatch covering `extent` invocations along each axis,
+    /// rounded up to whole work groups, e.g. the size of a texture.
+    ///
+    /// Fails like [`Dispatch::try_linear`], checking the axes in order.
+    pub fn try_for_extent(
+        extent: [u64; 3],
+        work_group_size: WorkGroupSize,
+    ) -> Result<Dispatch, DispatchTooLarge> {
+        let work_group_size = [work_group_size.x, work_group_size.y, work_group_size.z];
+        let mut work_groups = [0; 3];
+
+        for axis in 0..3 {
+            // raw shaders have no work group size
+            let size = work_group_size[axis].max(1) as u64;
+            let groups = extent[axis].div_ceil(size);
+
+            // the count must fit the u32 lengths and guards, and the last
+            // invocation of the rounded up dispatch must still be indexable,
+            // or the padding wraps onto the first elements
+            if extent[axis] > u32::MAX as u64 || groups * size > 1 << 32 {
+                return Err(DispatchTooLarge::Overflow {
+                    axis: AXES[axis],
+                    elements: extent[axis],
+                });
+            }
+
+            work_groups[axis] = groups;
+        }
+
+        for axis in 0..3 {
+            if work_groups[axis] > MAX_WORK_GROUPS_PER_AXIS as u64 {
+                return Err(DispatchTooLarge::AxisLimit {
+                    axis: AXES[axis],
+                    work_groups: work_groups[axis],
+                    chunked: Self::chunked(work_groups.iter().product()),
+                });
+            }
+        }
+
+        let [x, y, z] = work_groups;
+        Ok(Dispatch::new(x as u32, y as u32, z as u32))
+    }
+
+    /// Gets the dispatch covering `extent` invocations along each axis,
+    /// rounded up to whole work groups, so sizes that aren't a multiple of
+    /// `work_group_size` aren't cut short.
+    ///
+    /// # Panics
+    /// Panics if the dispatch is too large, see [`Dispatch::try_for_extent`].
+    pub fn for_size(work_group_size: WorkGroupSize, extent: (u32, u32, u32)) -> Dispatch {
+        let (x, y, z) = extent;
+
+        Self::try_for_extent([x as u64, y as u64, z as u64], work_group_size)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Spreads `work_groups` over the axes, `None` if they don't fit.
+    fn chunked(work_groups: u64) -> Option<Dispatch> {
+        let max = MAX_WORK_GROUPS_PER_AXIS as u64;
+
+        let x = work_groups.min(max);
+        let y = work_groups.div_ceil(x).min(max);
+        let z = work_groups.div_ceil(x * y);
+
+        (z <= max).then(|| Dispatch::new(x as u32, y as u32, z as u32))
+    }
+}
+
+/// Maximum number of work groups along each axis of a dispatch, the default
+/// `max_compute_workgroups_per_dimension` of WebGPU.
+pub const MAX_WORK_GROUPS_PER_AXIS: u32 = 65535;
+
+const AXES: [char; 3] = ['x', 'y', 'z'];
+
+/// A dispatch can't cover the requested invocations, see [`Dispatch::try_linear`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DispatchTooLarge {
+    /// The invocations along `axis` can't be counted or indexed with u32,
+    /// as `global_invocation_id` and array lengths are.
+    Overflow { axis: char, elements: u64 },
+    /// `axis` needs more than [`MAX_WORK_GROUPS_PER_AXIS`] work groups.
+    ///
+    /// `chunked` launches the same number of work groups spread over the
+    /// axes, the kernel then has to flatten its invocation index with
+    /// `num_workgroups` the way `#![auto_guard]` does. It's `None` if even
+    /// that exceeds the limit.
+    AxisLimit {
+        axis: char,
+        work_groups: u64,
+        chunked: Option<Dispatch>,
+    },
+}
+
+impl fmt::Display for DispatchTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overflow { axis, elements } => write!(
+                f,
+                "{} invocations along {} overflow the u32 invocation id",
+                elements, axis
+            ),
+            Self::AxisLimit {
+                axis,
+                work_groups,
+                chunked,
+            } => {
+                write!(
+                    f,
+                    "{} work groups along {} exceed the limit of {} per axis",
+                    work_groups, axis, MAX_WORK_GROUPS_PER_AXIS
+                )?;
+
+                if let Some(chunked) = chunked {
+                    write!(
+                        f,
+                        ", dispatch {}x{}x{} and flatten the invocation id instead",
+                        chunked.x, chunked.y, chunked.z
+                    )?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for DispatchTooLarge {}
+
+/// Dispatch commands recorded by a [`ComputeShaderBuilder`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DispatchReport {
+    pub recorded: u32,
+    /// Empty dispatches that were skipped.
+    pub skipped: u32,
+    /// Statistics of the pass if queried, see [`ComputeShaderBuilder::with_statistics`].
+    pub statistics: Option<PassStatistics>,
+}
+
+impl DispatchReport {
+    /// Returns true if more than half of the dispatches were empty, in which
+    /// case the dispatch list should be compacted, or an indirect dispatch
+    /// used instead, see [`ComputeShaderBuilder::dispatch_if_nonzero`].
+    pub fn is_mostly_empty(&self) -> bool {
+        self.skipped > self.recorded
+    }
+}
+
+/// Calls `record` for every non-empty dispatch in `dispatches`.
+pub fn record_dispatches(
+    dispatches: &[Dispatch],
+    mut record: impl FnMut(&Dispatch),
+) -> DispatchReport {
+    let mut report = DispatchReport::default();
+
+    for dispatch in dispatches {
+        if dispatch.is_empty() {
+            report.skipped += 1;
+        } else {
+            record(dispatch);
+            report.recorded += 1;
+        }
+    }
+
+    report
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
@@ -31,11 +237,130 @@ pub trait ComputeShader<'a> {
 
     const SOURCE: &'static str;
     const ENTRY_POINT: &'static str;
+    const WORK_GROUP_SIZE: WorkGroupSize;
+    /// Members of the launch parameters the shader reads from a group
+    /// following those of its bindings, set by `wgsl!` options like
+    /// `#![auto_guard]`.
+    const LAUNCH_PARAMS: LaunchParams = LaunchParams::NONE;
+
+    /// Type of the `var<push_constant>` global of the entry point,
+    /// [`NoPushConstants`] without one, see
+    /// [`ComputeShaderBuilder::push_constants`].
+    type PushConstants: Reflect;
+}
+
+/// The [`ComputeShader::PushConstants`] of shaders without push constants.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NoPushConstants;
+
+// SAFETY: zero sized
+unsafe impl Reflect for NoPushConstants {
+    const LAYOUT: StructLayout = StructLayout {
+        name: Cow::Borrowed("NoPushConstants"),
+        size: 0,
+        fields: Cow::Borrowed(&[]),
+    };
+}
+
+/// Work recorded by [`ComputeShaderBuilder`].
+enum Work<'d> {
+    Direct(&'d [Dispatch]),
+    Indirect {
+        counter: BufferId,
+        field_offset: u64,
+        work_group_size: WorkGroupSize,
+        max_dispatch: Dispatch,
+    },
 }
 
+/// Counts the dispatches of a [`ComputeShaderBuilder`] in progress.
+///
+/// Every dispatch holds a [`DispatchLease`] for as long as it runs, dropping
+/// it even if the dispatch panics, so none is outstanding once the builder is
+/// dropped.
+#[derive(Debug, Default)]
+struct DispatchLeases(AtomicU32);
+
+impl DispatchLeases {
+    #[inline]
+    fn acquire(&self) -> DispatchLease<'_> {
+        self.0.fetch_add(1, Ordering::AcqRel);
+        DispatchLease(&self.0)
+    }
+
+    #[inline]
+    fn outstanding(&self) -> u32 {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+impl Drop for DispatchLeases {
+    fn drop(&mut self) {
+        debug_assert_eq!(
+            self.outstanding(),
+            0,
+            "ComputeShaderBuilder dropped with a dispatch lease outstanding"
+        );
+    }
+}
+
+struct DispatchLease<'l>(&'l AtomicU32);
+
+impl Drop for DispatchLease<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Configures and dispatches a compute shader.
+///
+/// # Side effects
+/// Creating and configuring a builder does no GPU work, bindings aren't
+/// uploaded and no resources are created, so a builder can be dropped
+/// without dispatching at any point. Everything happens in the dispatch
+/// methods, [`precompile`](Self::precompile) and [`warm_up`](Self::warm_up).
 pub struct ComputeShaderBuilder<'a, S: ComputeShader<'a>> {
     bindings: S::Bindings,
     encoder: Option<&'a mut wgpu::CommandEncoder>,
+    // source of the shader and its override
+    base_source: Cow<'static, str>,
+    source: Option<Cow<'static, str>>,
+    entry_point: Cow<'static, str>,
+    // unknown for raw shaders without a work group size
+    work_group_size: Option<WorkGroupSize>,
+    #[cfg(all(feature = "validate-source", debug_assertions))]
+    source_checked: bool,
+    last_transfer_report: TransferReport,
+    last_dispatch_report: DispatchReport,
+    // created by the first dispatch querying them
+    wants_statistics: bool,
+    statistics: Option<StatisticsQuery>,
+    // the timestamp queries are pooled by the instance
+    timed: bool,
+    last_timing: Option<DispatchTiming>,
+    last_dispatch_timings: Option<Vec<DispatchTiming>>,
+    tag: Option<Cow<'static, str>>,
+    guard_count: Option<u32>,
+    dispatch_offset: u32,
+    // zeroed until set
+    push_constants: Vec<u8>,
+    // the global instance unless set
+    instance: Option<&'static Instance>,
+    leases: DispatchLeases,
+    // resolved by the first dispatch, cleared when the source or instance changes
+    resolved: Option<ResolvedPipeline>,
+}
+
+/// The layouts and pipeline of a builder, which only depend on the types of
+/// its bindings, its source and instance. Resolved once, so later dispatches
+/// neither build nor hash their descriptors.
+struct ResolvedPipeline {
+    /// Layouts of the bindings, without that of the launch parameters.
+    layouts: Vec<BindGroupLayoutId>,
+    pipeline: ComputePipelineId,
+    pass_label: String,
+    encoder_label: String,
 }
 
 impl<'a, S: ComputeShader<'a>> ComputeShaderBuilder<'a, S> {
@@ -44,6 +369,26 @@ impl<'a, S: ComputeShader<'a>> ComputeShaderBuilder<'a, S> {
         Self {
             bindings,
             encoder: None,
+            base_source: Cow::Borrowed(S::SOURCE),
+            source: None,
+            entry_point: Cow::Borrowed(S::ENTRY_POINT),
+            work_group_size: Some(S::WORK_GROUP_SIZE),
+            #[cfg(all(feature = "validate-source", debug_assertions))]
+            source_checked: false,
+            last_transfer_report: TransferReport::ZERO,
+            last_dispatch_report: DispatchReport::default(),
+            wants_statistics: false,
+            statistics: None,
+            timed: false,
+            last_timing: None,
+            last_dispatch_timings: None,
+            tag: None,
+            guard_count: None,
+            dispatch_offset: 0,
+            push_constants: vec![0; std::mem::size_of::<S::PushConstants>()],
+            instance: None,
+            leases: DispatchLeases::default(),
+            resolved: None,
         }
     }
 
@@ -62,6 +407,25 @@ impl<'a, S: ComputeShader<'a>> ComputeShaderBuilder<'a, S> {
         self
     }
 
+    /// Dispatches in `instance` rather than the global instance, e.g. one
+    /// created with [`Instance::create`].
+    ///
+    /// # Panics
+    /// Dispatches panic if a binding was created in another instance, see
+    /// [`Buffer::new_in`](crate::Buffer::new_in).
+    #[inline]
+    pub fn instance(&mut self, instance: &'static Instance) -> &mut Self {
+        self.instance = Some(instance);
+        self.resolved = None;
+        self
+    }
+
+    /// Gets the instance dispatches run in.
+    #[inline]
+    fn get_instance(&self) -> &'static Instance {
+        self.instance.unwrap_or_else(Instance::global)
+    }
+
     /// Unsets the command encoder.
     ///
     /// This means that a command encoder will automatically be created
@@ -72,6 +436,270 @@ impl<'a, S: ComputeShader<'a>> ComputeShaderBuilder<'a, S> {
         self
     }
 
+    /// Overrides the source of the shader, [`ComputeShader::SOURCE`] or that of
+    /// the [`RawComputeShader`], for this builder.
+    ///
+    /// The source must declare the entry point with bindings matching the
+    /// generated layout. With the `validate-source` feature this is checked on
+    /// the next dispatch in debug builds, panicking with the differences, see
+    /// [`check_source_interface`](crate::check_source_interface). Otherwise
+    /// mismatches are caught by wgpu's validation. Pipelines are cached by
+    /// source, so overridden and original pipelines coexist.
+    #[inline]
+    pub fn with_source(&mut self, source: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.source = Some(source.into());
+        self.resolved = None;
+
+        #[cfg(all(feature = "validate-source", debug_assertions))]
+        {
+            self.source_checked = false;
+        }
+
+        self
+    }
+
+    /// Sets the `var<push_constant>` global of subsequent dispatches, zeroed
+    /// until set.
+    ///
+    /// Push constants are recorded with each dispatch, so small parameters
+    /// changing every frame don't go through a buffer upload.
+    ///
+    /// # Panics
+    /// Dispatches panic if the instance wasn't created with
+    /// [`wgpu::Features::PUSH_CONSTANTS`] and a
+    /// [`max_push_constant_size`](wgpu::Limits::max_push_constant_size) fitting
+    /// the push constants.
+    #[inline]
+    pub fn push_constants(&mut self, constants: &S::PushConstants) -> &mut Self {
+        self.push_constants = crate::items_as_bytes(std::slice::from_ref(constants)).to_vec();
+        self
+    }
+
+    /// Gets the push constant range of the pipeline layout, none without
+    /// push constants.
+    fn push_constant_ranges(&self) -> Vec<wgpu::PushConstantRange> {
+        if self.push_constants.is_empty() {
+            return Vec::new();
+        }
+
+        vec![wgpu::PushConstantRange {
+            stages: wgpu::ShaderStages::COMPUTE,
+            range: 0..self.push_constants.len() as u32,
+        }]
+    }
+
+    /// Specializes the shader for an extent by replacing the value of its
+    /// module scope constant `name`, e.g. `let HEIGHT: u32 = 1u;`, with
+    /// `extent`, on top of any source override.
+    ///
+    /// Constant loop bounds generate better code than uniform ones on some
+    /// drivers. Pipelines are cached by source, so each distinct extent
+    /// creates a pipeline once and reuses it after. Specializing a constant
+    /// for more than [`CacheLimits::max_specializations`](crate::CacheLimits::max_specializations)
+    /// values emits a [`ManySpecializations`](crate::ManySpecializations),
+    /// the extent should then be a uniform.
+    ///
+    /// # Panics
+    /// - If the shader has no `u32`, `i32` or `f32` constant `name`.
+    pub fn specialize_extent(&mut self, name: &str, extent: u32) -> &mut Self {
+        let source = self.source.as_deref().unwrap_or(&self.base_source);
+
+        let specialized = match crate::specialize::specialize_constant(source, name, extent) {
+            Some(specialized) => specialized,
+            None => panic!(
+                "`{}` has no module scope `u32`, `i32` or `f32` constant `{}` to specialize",
+                self.entry_point, name
+            ),
+        };
+
+        self.get_instance()
+            .cache_watch
+            .record_specialization(&self.entry_point, name, extent);
+
+        self.with_source(specialized)
+    }
+
+    /// Removes the source override, using the source of the shader again.
+    #[inline]
+    pub fn reset_source(&mut self) -> &mut Self {
+        self.source = None;
+        self.resolved = None;
+        self
+    }
+
+    /// Sets the element count `#![auto_guard]` shaders compare the invocation
+    /// index against, overriding [`Bindings::guard_count`]. Ignored by other shaders.
+    #[inline]
+    pub fn with_guard_count(&mut self, count: u32) -> &mut Self {
+        self.guard_count = Some(count);
+        self
+    }
+
+    /// Gets the element count of `#![auto_guard]` shaders, `None` for other
+    /// shaders or if there's nothing to count.
+    #[inline]
+    fn guard_count(&self) -> Option<u32> {
+        if !S::LAUNCH_PARAMS.guard_count {
+            return None;
+        }
+
+        self.guard_count.or_else(|| self.bindings.guard_count())
+    }
+
+    /// Sets the base element `#![dispatch_offset]` shaders read with
+    /// `shatter_dispatch_offset()`, e.g. the [`Segment::offset`](crate::util::Segment::offset)
+    /// of a [`SegmentedDispatch`](crate::util::SegmentedDispatch). Zero by default,
+    /// ignored by other shaders.
+    #[inline]
+    pub fn with_dispatch_offset(&mut self, offset: u32) -> &mut Self {
+        self.dispatch_offset = offset;
+        self
+    }
+
+    /// Writes the launch parameters of the next dispatch, returning their
+    /// dynamic offset. `None` for shaders without launch parameters.
+    fn push_launch_params(&self, instance: &Instance) -> Option<u32> {
+        if S::LAUNCH_PARAMS.is_empty() {
+            return None;
+        }
+
+        let guard_count = match self.guard_count() {
+            Some(count) => count,
+            None if S::LAUNCH_PARAMS.guard_count => panic!(
+                "`{}` uses `auto_guard` but binds no runtime sized array, set the count with `with_guard_count`",
+                self.entry_point
+            ),
+            None => 0,
+        };
+
+        let params = S::LAUNCH_PARAMS.encode(guard_count, self.dispatch_offset);
+        Some(instance.launch_ring().push(instance, &params))
+    }
+
+    /// Gets the transfers performed by the last dispatch.
+    #[inline]
+    pub fn last_transfer_report(&self) -> TransferReport {
+        self.last_transfer_report
+    }
+
+    /// Gets the dispatch commands recorded by the last dispatch.
+    #[inline]
+    pub fn last_dispatch_report(&self) -> DispatchReport {
+        self.last_dispatch_report
+    }
+
+    /// Queries pipeline statistics of subsequent dispatches, if the instance
+    /// was created with [`wgpu::Features::PIPELINE_STATISTICS_QUERY`], see
+    /// [`InstanceDescriptor::optional_features`](crate::InstanceDescriptor::optional_features).
+    /// Otherwise this does nothing and [`Self::last_statistics`] returns `None`.
+    ///
+    /// Reading the statistics waits for the dispatch to finish, so this is
+    /// meant for debugging. Dispatches recorded into an encoder aren't queried.
+    ///
+    /// # Launched and effective invocations
+    /// [`PassStatistics::invocations`] counts every launched invocation, which
+    /// is the dispatch size rounded up to whole work groups, see
+    /// [`Dispatch::invocations`]. To count invocations passing a bounds guard,
+    /// count them in the shader:
+    ///
+    /// ```wgsl
+    /// [[block]]
+    /// struct Counter {
+    ///     effective: atomic<u32>;
+    /// };
+    ///
+    /// [[group(0), binding(1)]]
+    /// var<storage, read_write> counter: Counter;
+    ///
+    /// [[stage(compute), workgroup_size(8, 8, 1)]]
+    /// fn main([[builtin(global_invocation_id)]] id: vec3<u32>) {
+    ///     if (id.x >= WIDTH || id.y >= HEIGHT) {
+    ///         return;
+    ///     }
+    ///
+    ///     let previous = atomicAdd(&counter.effective, 1u);
+    ///     // ...
+    /// }
+    /// ```
+    ///
+    /// The difference between the two is the work spent on padding.
+    #[inline]
+    pub fn with_statistics(&mut self) -> &mut Self {
+        self.wants_statistics = true;
+        self
+    }
+
+    /// Stops querying pipeline statistics.
+    #[inline]
+    pub fn without_statistics(&mut self) -> &mut Self {
+        self.wants_statistics = false;
+        self.statistics = None;
+        self
+    }
+
+    /// Gets the statistics of the last dispatch, see [`Self::with_statistics`].
+    #[inline]
+    pub fn last_statistics(&self) -> Option<PassStatistics> {
+        self.last_dispatch_report.statistics
+    }
+
+    /// Times subsequent dispatches on the GPU, if the instance was created
+    /// with [`wgpu::Features::TIMESTAMP_QUERY`], see
+    /// [`InstanceDescriptor::optional_features`](crate::InstanceDescriptor::optional_features).
+    /// Otherwise this does nothing and [`Self::last_timing`] returns `None`.
+    ///
+    /// Timestamps are written around every dispatch of the pass, so unlike
+    /// timing [`Self::dispatch`] on the CPU this excludes transfers, creating
+    /// pipelines and bind groups, and waiting for the submission. Reading them
+    /// waits for the dispatch to finish. Dispatches recorded into an encoder
+    /// and [`Self::dispatch_async`] aren't timed.
+    #[inline]
+    pub fn timed(&mut self) -> &mut Self {
+        self.timed = true;
+        self
+    }
+
+    /// Stops timing dispatches.
+    #[inline]
+    pub fn untimed(&mut self) -> &mut Self {
+        self.timed = false;
+        self
+    }
+
+    /// Tags the submissions of subsequent dispatches, listed by
+    /// [`Instance::recent_submissions`] and by uncaptured errors to trace them
+    /// back to what submitted them. Generated functions tag with their entry point.
+    #[inline]
+    pub fn tag(&mut self, tag: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Gets the GPU time of the last dispatch, of every dispatch together for
+    /// [`Self::dispatch_multiple`], see [`Self::timed`].
+    #[inline]
+    pub fn last_timing(&self) -> Option<DispatchTiming> {
+        self.last_timing
+    }
+
+    /// Gets the GPU time of every dispatch of the last
+    /// [`Self::dispatch_multiple`] in order, see [`Self::timed`]. Skipped empty
+    /// dispatches take no time.
+    ///
+    /// Passes of [`wgpu::QUERY_SET_MAX_QUERIES`] or more non-empty dispatches
+    /// are only timed as a whole, returning `None`.
+    #[inline]
+    pub fn last_dispatch_timings(&self) -> Option<&[DispatchTiming]> {
+        self.last_dispatch_timings.as_deref()
+    }
+
+    /// Gets the number of dispatches of this builder in progress, always 0
+    /// outside of them, including after a dispatch panicked.
+    #[inline]
+    pub fn outstanding_leases(&self) -> u32 {
+        self.leases.outstanding()
+    }
+
     #[inline]
     pub fn dispatch(&mut self, dispatch: Dispatch) -> &mut Self {
         self.dispatch_multiple(&[dispatch]);
@@ -80,82 +708,934 @@ impl<'a, S: ComputeShader<'a>> ComputeShaderBuilder<'a, S> {
 
     #[inline]
     pub fn dispatch_multiple(&mut self, dispatches: &[Dispatch]) -> &mut Self {
-        self.bindings.read();
+        self.record(Work::Direct(dispatches), None, true)
+    }
 
-        let instance = Instance::global();
+    /// Submits a dispatch without waiting on the GPU, the returned future
+    /// resolves once the GPU has finished it.
+    ///
+    /// The future borrows nothing, so it is `Send` and `'static`. Written
+    /// bindings are still downloaded on access, the generated `_async`
+    /// functions take owned bindings to download them without blocking.
+    /// Statistics and timings aren't collected since reading them waits on
+    /// the GPU.
+    pub fn dispatch_async(&mut self, dispatch: Dispatch) -> SubmittedWork {
+        self.record(Work::Direct(&[dispatch]), None, false);
 
-        let layout_descriptors = self.bindings.bind_group_layout_descriptors();
-        let layouts = layout_descriptors
-            .into_iter()
-            .map(|desc| instance.get_bind_group_layout(desc))
-            .collect::<Vec<_>>();
+        SubmittedWork::new()
+    }
 
-        let bind_group_descriptors = self.bindings.bind_group_descriptors(&layouts);
-        let bind_group_ids = bind_group_descriptors
-            .into_iter()
-            .map(|desc| instance.get_bind_group(desc))
-            .collect::<Vec<_>>();
+    /// Records a dispatch into `encoder` without submitting it.
+    ///
+    /// Unlike [`ComputeShaderBuilder::encoder`] the encoder is only borrowed for
+    /// the call, so several builders can record into it one after another. As
+    /// with a set encoder, bindings must be *downloaded* manually.
+    #[inline]
+    pub fn dispatch_in(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        dispatch: Dispatch,
+    ) -> &mut Self {
+        self.record(Work::Direct(&[dispatch]), Some(encoder), true)
+    }
 
-        let bind_groups = bind_group_ids
+    /// Dispatches enough work groups to cover the `u32` counter at `field_offset`
+    /// bytes into `counter`, clamped to `max_dispatch`.
+    ///
+    /// The counter is converted into an indirect dispatch on the GPU, so it is
+    /// never read back. When the counter is zero the dispatch is empty.
+    ///
+    /// # Panics
+    /// Panics if `field_offset` isn't 4 byte aligned or out of bounds, or the
+    /// work group size is unknown, see [`Self::dispatch_if_nonzero_sized`].
+    #[inline]
+    pub fn dispatch_if_nonzero<C: BufferData + ?Sized>(
+        &mut self,
+        counter: &GpuBuffer<C>,
+        field_offset: usize,
+        max_dispatch: Dispatch,
+    ) -> &mut Self {
+        let work_group_size = self
+            .work_group_size
+            .expect("work group size of raw shader is unknown, use `dispatch_if_nonzero_sized`");
+
+        self.dispatch_if_nonzero_sized(counter, field_offset, work_group_size, max_dispatch)
+    }
+
+    /// Like [`Self::dispatch_if_nonzero`] with the work group size of the
+    /// shader given, for raw shaders created without one.
+    ///
+    /// # Panics
+    /// Panics if `field_offset` isn't 4 byte aligned or out of bounds.
+    #[inline]
+    pub fn dispatch_if_nonzero_sized<C: BufferData + ?Sized>(
+        &mut self,
+        counter: &GpuBuffer<C>,
+        field_offset: usize,
+        work_group_size: WorkGroupSize,
+        max_dispatch: Dispatch,
+    ) -> &mut Self {
+        let field_offset = field_offset as u64;
+
+        assert!(
+            field_offset.is_multiple_of(4),
+            "counter offset {} is not 4 byte aligned",
+            field_offset
+        );
+        assert!(
+            field_offset + 4 <= counter.size(),
+            "counter offset {} is out of bounds of a {} byte buffer",
+            field_offset,
+            counter.size()
+        );
+
+        self.record(
+            Work::Indirect {
+                counter: counter.id(),
+                field_offset,
+                work_group_size,
+                max_dispatch,
+            },
+            None,
+            true,
+        )
+    }
+
+    /// Creates the pipeline of the shader without dispatching.
+    ///
+    /// Pipelines are otherwise compiled by the first dispatch, see also
+    /// [`Self::warm_up`].
+    pub fn precompile(&mut self) -> &mut Self {
+        self.resolve();
+        self
+    }
+
+    /// Creates the pipeline and every resource of the bindings without
+    /// dispatching, so the next dispatch only records commands, see
+    /// [`Bindings::warm_up`].
+    pub fn warm_up(&mut self) -> &mut Self {
+        self.bindings.warm_up_in(self.get_instance());
+        self.precompile()
+    }
+
+    /// Returns true if the next dispatch would create nothing, neither
+    /// resources, bind groups nor pipelines.
+    ///
+    /// Only the caches are checked, nothing is created.
+    pub fn is_warm(&self) -> bool {
+        if self.bindings.needs_allocation() {
+            return false;
+        }
+
+        let instance = self.get_instance();
+
+        let mut layouts = Vec::new();
+        for mut desc in self.bindings.bind_group_layout_descriptors() {
+            desc.canonicalize();
+
+            match instance.bind_group_layout_descriptors.get(&desc) {
+                Some(id) => layouts.push(id.clone()),
+                None => return false,
+            }
+        }
+
+        let bind_groups_cached = self
+            .bindings
+            .bind_group_keys(&layouts)
             .iter()
-            .map(|id| instance.bind_groups.get(id).unwrap())
-            .collect::<Vec<_>>();
+            .all(|key| instance.group_keys.contains_key(key));
+
+        if !bind_groups_cached {
+            return false;
+        }
+
+        if !S::LAUNCH_PARAMS.is_empty() {
+            match instance.launch_ring.get() {
+                Some(ring) => layouts.push(ring.layout.clone()),
+                None => return false,
+            }
+        }
+
+        let pipeline_layout_descriptor = PipelineLayoutDescriptor {
+            bind_group_layouts: layouts,
+            push_constant_ranges: self.push_constant_ranges(),
+        };
+
+        let layout = match instance
+            .pipeline_layout_descriptors
+            .get(&pipeline_layout_descriptor)
+        {
+            Some(id) => id.clone(),
+            None => return false,
+        };
+
+        let source = self.source.as_ref().unwrap_or(&self.base_source);
+        let module = match instance.shader_module_sources.get(source) {
+            Some(id) => id.clone(),
+            None => return false,
+        };
+
+        instance
+            .compute_pipeline_descriptors
+            .contains_key(&ComputePipelineDescriptor {
+                layout: Some(layout),
+                module,
+                entry_point: self.entry_point.clone(),
+            })
+    }
+
+    /// Resolves the layouts and pipeline of the shader unless they already
+    /// are. The bindings are validated along with them in debug builds, as
+    /// they can't change while the builder holds them.
+    fn resolve(&mut self) {
+        if self.resolved.is_some() {
+            return;
+        }
+
+        #[cfg(debug_assertions)]
+        if let Err(report) = self.bindings.validate() {
+            panic!("{}", report);
+        }
+
+        let instance = self.get_instance();
+        let layouts = resolve_layouts(instance, self.bindings.bind_group_layout_descriptors());
+
+        let mut pipeline_layouts = layouts.clone();
+
+        if !S::LAUNCH_PARAMS.is_empty() {
+            pipeline_layouts.push(instance.launch_ring().layout.clone());
+        }
+
+        self.resolved = Some(ResolvedPipeline {
+            pipeline: self.compute_pipeline(pipeline_layouts),
+            layouts,
+            pass_label: format!("shatter_compute_pass({})", self.entry_point),
+            encoder_label: format!("shatter_command_encoder({})", self.entry_point),
+        });
+    }
+
+    /// Gets the pipeline of the shader with bind group `layouts`.
+    fn compute_pipeline(&self, layouts: Vec<BindGroupLayoutId>) -> ComputePipelineId {
+        let instance = self.get_instance();
+
+        if !self.push_constants.is_empty() {
+            check_push_constants(
+                instance,
+                &self.entry_point,
+                self.push_constants.len() as u32,
+            );
+        }
 
         let pipeline_layout_descriptor = PipelineLayoutDescriptor {
             bind_group_layouts: layouts,
-            push_constant_ranges: Vec::new(),
+            push_constant_ranges: self.push_constant_ranges(),
         };
 
         let pipeline_layout = instance.get_pipeline_layout(pipeline_layout_descriptor);
 
-        let shader_module = instance.get_shader_module(S::SOURCE);
+        let source = self.source.as_ref().unwrap_or(&self.base_source);
+        let shader_module = instance.get_shader_module(source.clone());
 
         let compute_pipeline_descriptor = ComputePipelineDescriptor {
             layout: Some(pipeline_layout),
             module: shader_module,
-            entry_point: S::ENTRY_POINT.into(),
+            entry_point: self.entry_point.clone(),
+        };
+
+        instance.get_compute_pipeline(compute_pipeline_descriptor)
+    }
+
+    /// Records `work`, submitting it unless there's an encoder. Statistics and
+    /// timestamps are only queried if `query` is true.
+    fn record(
+        &mut self,
+        work: Work,
+        encoder: Option<&mut wgpu::CommandEncoder>,
+        query: bool,
+    ) -> &mut Self {
+        self.resolve();
+
+        // released even if the dispatch panics
+        let lease = self.leases.acquire();
+
+        let instance = self.get_instance();
+        instance.clean_if_due();
+
+        let mut report = TransferReport::ZERO;
+        self.bindings.read(&mut report);
+        self.last_transfer_report = report;
+
+        #[cfg(all(feature = "validate-source", debug_assertions))]
+        if let (Some(source), false) = (&self.source, self.source_checked) {
+            let mut layout_descriptors = self.bindings.bind_group_layout_descriptors();
+
+            if !S::LAUNCH_PARAMS.is_empty() {
+                layout_descriptors.push(crate::launch_layout_descriptor());
+            }
+
+            if let Err(err) =
+                crate::check_source_interface(source, &self.entry_point, &layout_descriptors)
+            {
+                panic!("{}", err);
+            }
+
+            self.source_checked = true;
+        }
+
+        #[cfg(feature = "debug-recorder")]
+        let mut recording = crate::debug::Recording::start(
+            &self.entry_point,
+            &self.bindings.bind_group_layout_descriptors(),
+        );
+
+        let resolved = self.resolved.as_ref().unwrap();
+        let mut bind_group_ids = resolve_bind_groups(instance, &self.bindings, &resolved.layouts);
+
+        #[cfg(feature = "debug-recorder")]
+        if let Some(ref mut recording) = recording {
+            recording.bind(&self.bindings.bind_group_descriptors(&resolved.layouts));
+        }
+
+        // bound last, at the offset of this dispatch's slot
+        let launch_offset = self.push_launch_params(instance);
+
+        if launch_offset.is_some() {
+            bind_group_ids.push(instance.launch_ring().bind_group.clone());
+        }
+
+        let bind_groups = bind_group_ids
+            .iter()
+            .map(|id| instance.bind_groups.get(id).unwrap())
+            .collect::<SmallVec<[_; 4]>>();
+
+        let compute_pipeline = instance.compute_pipelines.get(&resolved.pipeline).unwrap();
+
+        // a timestamp before the first and after every recorded dispatch,
+        // unless there are more than a query set holds
+        let recorded = match work {
+            Work::Direct(dispatches) => dispatches
+                .iter()
+                .filter(|dispatch| !dispatch.is_empty())
+                .count(),
+            Work::Indirect { .. } => 1,
+        };
+        let time_dispatches = recorded < wgpu::QUERY_SET_MAX_QUERIES as usize;
+        let timestamp_count = match time_dispatches {
+            true => recorded as u32 + 1,
+            false => 2,
         };
 
-        let compute_pipeline_id = instance.get_compute_pipeline(compute_pipeline_descriptor);
+        #[cfg(feature = "debug-recorder")]
+        let entry_point = &self.entry_point;
+        let pass_label = resolved.pass_label.as_str();
+        let push_constants = &self.push_constants;
+        let dispatch = |encoder: &mut wgpu::CommandEncoder,
+                        statistics: Option<&StatisticsQuery>,
+                        timestamps: Option<&TimestampQuery>| {
+            let args = match work {
+                Work::Indirect {
+                    ref counter,
+                    field_offset,
+                    work_group_size,
+                    max_dispatch,
+                } => {
+                    let args = record_dispatch_args(
+                        instance,
+                        encoder,
+                        counter.clone(),
+                        field_offset,
+                        work_group_size.x,
+                        max_dispatch,
+                    );
 
-        let compute_pipeline = instance
-            .compute_pipelines
-            .get(&compute_pipeline_id)
-            .unwrap();
+                    Some(instance.buffers.get(&args).unwrap())
+                }
+                Work::Direct(_) => None,
+            };
 
-        let dispatch = |encoder: &mut wgpu::CommandEncoder| {
             let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: Some(&format!("shatter_compute_pass({})", S::ENTRY_POINT)),
+                label: Some(pass_label),
             });
 
             compute_pass.set_pipeline(&compute_pipeline);
 
             for (i, bind_group) in bind_groups.iter().enumerate() {
-                compute_pass.set_bind_group(i as u32, bind_group, &[]);
+                let offsets = match launch_offset {
+                    Some(ref offset) if i == bind_groups.len() - 1 => std::slice::from_ref(offset),
+                    _ => &[],
+                };
+
+                compute_pass.set_bind_group(i as u32, bind_group, offsets);
+            }
+
+            if !push_constants.is_empty() {
+                compute_pass.set_push_constants(0, push_constants);
+            }
+
+            if let Some(statistics) = statistics {
+                statistics.begin(&mut compute_pass);
             }
 
-            for dispatch in dispatches {
-                compute_pass.dispatch(dispatch.x, dispatch.y, dispatch.z);
+            if let Some(timestamps) = timestamps {
+                timestamps.write(&mut compute_pass, 0);
             }
+
+            let mut timestamp = 1;
+            let mut dispatched = |compute_pass: &mut wgpu::ComputePass| {
+                if let (Some(timestamps), true) = (timestamps, time_dispatches) {
+                    timestamps.write(compute_pass, timestamp);
+                    timestamp += 1;
+                }
+            };
+
+            let report = match work {
+                // empty dispatches still cost encoding, so they're skipped
+                Work::Direct(dispatches) => record_dispatches(dispatches, |dispatch| {
+                    compute_pass.dispatch(dispatch.x, dispatch.y, dispatch.z);
+                    dispatched(&mut compute_pass);
+                }),
+                Work::Indirect { .. } => {
+                    compute_pass.dispatch_indirect(args.as_ref().unwrap(), 0);
+                    dispatched(&mut compute_pass);
+
+                    DispatchReport {
+                        recorded: 1,
+                        skipped: 0,
+                        statistics: None,
+                    }
+                }
+            };
+
+            if let (Some(timestamps), false) = (timestamps, time_dispatches) {
+                timestamps.write(&mut compute_pass, 1);
+            }
+
+            if let Some(statistics) = statistics {
+                statistics.end(&mut compute_pass);
+            }
+
+            drop(compute_pass);
+
+            if let Some(statistics) = statistics {
+                statistics.resolve(encoder);
+            }
+
+            if let Some(timestamps) = timestamps {
+                timestamps.resolve(encoder, timestamp_count);
+            }
+
+            #[cfg(feature = "debug-recorder")]
+            if let Some(ref recording) = recording {
+                let dispatches = match work {
+                    Work::Direct(dispatches) => dispatches,
+                    Work::Indirect { .. } => &[],
+                };
+
+                recording.record(encoder, entry_point, dispatches);
+            }
+
+            instance.stats.dispatched(report.recorded as u64);
+
+            report
         };
 
-        if let Some(encoder) = &mut self.encoder {
-            dispatch(encoder);
+        self.last_timing = None;
+        self.last_dispatch_timings = None;
+
+        let dispatch_report = if let Some(encoder) = encoder {
+            dispatch(encoder, None, None)
+        } else if let Some(encoder) = &mut self.encoder {
+            dispatch(encoder, None, None)
         } else {
             let mut encoder =
                 instance
                     .device
                     .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                        label: Some(&format!("shatter_command_encoder({})", S::ENTRY_POINT)),
+                        label: Some(&resolved.encoder_label),
                     });
 
-            dispatch(&mut encoder);
+            let statistics = match query && self.wants_statistics {
+                true if self.statistics.is_none() => {
+                    self.statistics = StatisticsQuery::new(instance);
+                    self.statistics.as_ref()
+                }
+                true => self.statistics.as_ref(),
+                false => None,
+            };
+
+            let timestamps = match query && self.timed {
+                true => instance.timestamp_queries.take(instance, timestamp_count),
+                false => None,
+            };
+
+            let mut dispatch_report = dispatch(&mut encoder, statistics, timestamps.as_ref());
 
             instance.queue.submit(std::iter::once(encoder.finish()));
+            instance.submitted(self.tag.clone(), std::slice::from_ref(&self.entry_point));
 
             self.bindings.write();
+
+            dispatch_report.statistics = statistics.map(|statistics| statistics.read(instance));
+
+            if let Some(timestamps) = timestamps {
+                let durations = timestamps.read(instance, timestamp_count);
+                instance.timestamp_queries.give_back(timestamps);
+
+                let (timing, dispatch_timings) = timings(&work, time_dispatches, durations);
+                self.last_timing = Some(timing);
+                self.last_dispatch_timings = dispatch_timings;
+            }
+
+            dispatch_report
         };
 
+        self.last_dispatch_report = dispatch_report;
+        drop(lease);
+
         self
     }
 }
+
+/// Panics unless the device supports `size` bytes of push constants.
+fn check_push_constants(instance: &Instance, entry_point: &str, size: u32) {
+    assert!(
+        instance
+            .device
+            .features()
+            .contains(wgpu::Features::PUSH_CONSTANTS),
+        "`{}` uses `var<push_constant>`, create the instance with `wgpu::Features::PUSH_CONSTANTS` in `InstanceDescriptor::features`",
+        entry_point
+    );
+
+    let max = instance.device.limits().max_push_constant_size;
+
+    assert!(
+        size <= max,
+        "`{}` pushes {} bytes of constants but `max_push_constant_size` is {}, raise it in `InstanceDescriptor::limits`",
+        entry_point,
+        size,
+        max
+    );
+}
+
+/// Gets the timing of `work` and of each of its dispatches from the `durations`
+/// between its timestamps, one per recorded dispatch if `time_dispatches`,
+/// otherwise one for all.
+fn timings(
+    work: &Work,
+    time_dispatches: bool,
+    durations: Vec<Duration>,
+) -> (DispatchTiming, Option<Vec<DispatchTiming>>) {
+    let timing = DispatchTiming {
+        gpu_duration: durations.iter().sum(),
+    };
+
+    if !time_dispatches {
+        return (timing, None);
+    }
+
+    let mut durations = durations.into_iter();
+
+    let dispatch_timings = match work {
+        Work::Direct(dispatches) => dispatches
+            .iter()
+            .map(|dispatch| match dispatch.is_empty() {
+                true => Duration::ZERO,
+                false => durations.next().unwrap_or_default(),
+            })
+            .map(|gpu_duration| DispatchTiming { gpu_duration })
+            .collect(),
+        Work::Indirect { .. } => vec![timing],
+    };
+
+    (timing, Some(dispatch_timings))
+}
+
+/// A compute shader given as a WGSL string at runtime, for shaders `wgsl!`
+/// can't consume, e.g. generated by external tools.
+///
+/// Raw shaders go through the same caches as generated ones, bindings are
+/// either [`DynamicBindings`](crate::DynamicBindings) or implemented by hand.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RawComputeShader {
+    pub source: Cow<'static, str>,
+    pub entry_point: Cow<'static, str>,
+    /// Needed by dispatches sized on the GPU, see
+    /// [`ComputeShaderBuilder::dispatch_if_nonzero`].
+    pub work_group_size: Option<WorkGroupSize>,
+}
+
+impl RawComputeShader {
+    pub fn new(
+        source: impl Into<Cow<'static, str>>,
+        entry_point: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            source: source.into(),
+            entry_point: entry_point.into(),
+            work_group_size: None,
+        }
+    }
+
+    pub fn with_work_group_size(mut self, work_group_size: WorkGroupSize) -> Self {
+        self.work_group_size = Some(work_group_size);
+        self
+    }
+
+    pub fn build<'a, B: Bindings>(&self, bindings: B) -> ComputeShaderBuilder<'a, Raw<B>> {
+        let mut builder = ComputeShaderBuilder::new(bindings);
+        builder.base_source = self.source.clone();
+        builder.entry_point = self.entry_point.clone();
+        builder.work_group_size = self.work_group_size;
+        builder
+    }
+
+    pub fn dispatch<B: Bindings>(&self, bindings: B, dispatch: Dispatch) {
+        self.build(bindings).dispatch(dispatch);
+    }
+}
+
+/// A compute shader loaded from WGSL at runtime, e.g. by a plugin, with the
+/// bindings of its entry point reflected.
+///
+/// Unlike a [`RawComputeShader`], the [`DynamicBindings`] are checked against
+/// the reflected bindings before dispatching, returning the problems instead of
+/// failing in wgpu's validation. Dispatches go through the same caches as
+/// generated and raw shaders.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DynamicComputeShader {
+    raw: RawComputeShader,
+    bindings: Vec<ReflectedBinding>,
+}
+
+impl DynamicComputeShader {
+    /// Parses and validates `source`, reflecting the work group size and the
+    /// bindings of the compute entry point `entry_point`.
+    pub fn new(
+        source: impl Into<Cow<'static, str>>,
+        entry_point: impl Into<Cow<'static, str>>,
+    ) -> Result<Self, SourceError> {
+        let raw = RawComputeShader::new(source, entry_point);
+        let reflection = reflect_entry_point(&raw.source, &raw.entry_point)?;
+
+        Ok(Self {
+            raw: raw.with_work_group_size(reflection.work_group_size),
+            bindings: reflection.bindings,
+        })
+    }
+
+    #[inline]
+    pub fn source(&self) -> &str {
+        &self.raw.source
+    }
+
+    #[inline]
+    pub fn entry_point(&self) -> &str {
+        &self.raw.entry_point
+    }
+
+    #[inline]
+    pub fn work_group_size(&self) -> WorkGroupSize {
+        self.raw.work_group_size.unwrap()
+    }
+
+    /// The bindings used by the entry point, sorted by group and binding.
+    #[inline]
+    pub fn bindings(&self) -> &[ReflectedBinding] {
+        &self.bindings
+    }
+
+    /// Layouts of the groups up to the last one the entry point uses, groups
+    /// in between are empty.
+    pub fn bind_group_layout_descriptors(&self) -> Vec<BindGroupLayoutDescriptor> {
+        let groups = self
+            .bindings
+            .iter()
+            .map(|binding| binding.group + 1)
+            .max()
+            .unwrap_or(0);
+
+        (0..groups)
+            .map(|group| {
+                let entries = self
+                    .bindings
+                    .iter()
+                    .filter(|binding| binding.group == group)
+                    .map(|binding| wgpu::BindGroupLayoutEntry {
+                        binding: binding.binding,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: binding.ty,
+                        count: None,
+                    })
+                    .collect();
+
+                BindGroupLayoutDescriptor::new(entries)
+            })
+            .collect()
+    }
+
+    /// Checks `bindings` against the entry point and builds a dispatch of it.
+    /// Resources the entry point doesn't use are left unbound.
+    pub fn build<'a>(
+        &self,
+        bindings: DynamicBindings<'a>,
+    ) -> Result<ComputeShaderBuilder<'a, Raw<DynamicBindings<'a>>>, DynamicBindingReport> {
+        Ok(self.raw.build(bindings.check(&self.bindings)?))
+    }
+
+    pub fn dispatch(
+        &self,
+        bindings: DynamicBindings<'_>,
+        dispatch: Dispatch,
+    ) -> Result<(), DynamicBindingReport> {
+        self.build(bindings)?.dispatch(dispatch);
+
+        Ok(())
+    }
+}
+
+/// The [`ComputeShader`] of builders created by [`RawComputeShader::build`]
+/// and [`DynamicComputeShader::build`].
+///
+/// The source, entry point and work group size are taken from the
+/// [`RawComputeShader`], the associated consts are never used.
+pub struct Raw<B>(PhantomData<B>);
+
+impl<'a, B: Bindings> ComputeShader<'a> for Raw<B> {
+    type Bindings = B;
+
+    const SOURCE: &'static str = "";
+    const ENTRY_POINT: &'static str = "";
+    const WORK_GROUP_SIZE: WorkGroupSize = WorkGroupSize::new(0, 0, 0);
+
+    type PushConstants = NoPushConstants;
+}
+
+/// The [`ComputeShader`] `S` with its bindings composed of the groups `B`,
+/// built by the `build_groups` function `wgsl!` generates for entry points
+/// binding several groups.
+pub struct Composed<S, B>(PhantomData<(S, B)>);
+
+impl<'a, S: ComputeShader<'a>, B: Bindings> ComputeShader<'a> for Composed<S, B> {
+    type Bindings = B;
+
+    const SOURCE: &'static str = S::SOURCE;
+    const ENTRY_POINT: &'static str = S::ENTRY_POINT;
+    const WORK_GROUP_SIZE: WorkGroupSize = S::WORK_GROUP_SIZE;
+    const LAUNCH_PARAMS: LaunchParams = S::LAUNCH_PARAMS;
+
+    type PushConstants = S::PushConstants;
+}
+
+/// Records commands into a new command encoder and submits it.
+pub fn submit_encoded<R>(label: &str, record: impl FnOnce(&mut wgpu::CommandEncoder) -> R) -> R {
+    submit_encoded_in(Instance::global(), label, record)
+}
+
+/// Like [`submit_encoded`], submitting to `instance` rather than the global instance.
+pub fn submit_encoded_in<R>(
+    instance: &Instance,
+    label: &str,
+    record: impl FnOnce(&mut wgpu::CommandEncoder) -> R,
+) -> R {
+    let mut encoder = instance
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some(label) });
+
+    let result = record(&mut encoder);
+
+    instance.queue.submit(std::iter::once(encoder.finish()));
+    instance.submitted(Some(Cow::Owned(label.to_owned())), &[]);
+
+    result
+}
+
+/// An entry point of a [`dispatch_shared_batch`], the parts of its
+/// [`ComputeShader`] its pipeline depends on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BatchShader {
+    pub source: &'static str,
+    pub entry_point: &'static str,
+    pub launch_params: LaunchParams,
+    /// Size of the push constants, dispatched zeroed.
+    pub push_constant_size: u32,
+}
+
+impl BatchShader {
+    #[inline]
+    pub fn of<'a, S: ComputeShader<'a>>() -> Self {
+        Self {
+            source: S::SOURCE,
+            entry_point: S::ENTRY_POINT,
+            launch_params: S::LAUNCH_PARAMS,
+            push_constant_size: std::mem::size_of::<S::PushConstants>() as u32,
+        }
+    }
+}
+
+/// Dispatches entry points binding the same `bindings` in order, recording
+/// them into a single compute pass of a single submission.
+///
+/// The bind groups are set once and only the pipeline changes between entry
+/// points. Used by the `dispatch_selected_batch` functions `wgsl!` generates
+/// for entry points sharing their bindings, which also share their launch
+/// parameters.
+///
+/// # Panics
+/// Panics if the shaders use `#![auto_guard]` and `bindings` bind no runtime
+/// sized array.
+pub fn dispatch_shared_batch<B: Bindings>(
+    name: &str,
+    mut bindings: B,
+    batch: &[(BatchShader, Dispatch)],
+) {
+    let instance = Instance::global();
+
+    #[cfg(debug_assertions)]
+    if let Err(report) = bindings.validate() {
+        panic!("{}", report);
+    }
+
+    instance.clean_if_due();
+
+    let mut report = TransferReport::ZERO;
+    bindings.read(&mut report);
+
+    let mut layouts = resolve_layouts(instance, bindings.bind_group_layout_descriptors());
+    let mut bind_group_ids = resolve_bind_groups(instance, &bindings, &layouts);
+
+    let launch_params = batch
+        .first()
+        .map_or(LaunchParams::NONE, |(shader, _)| shader.launch_params);
+
+    // bound last, like the launch parameters of single dispatches
+    let launch_offset = (!launch_params.is_empty()).then(|| {
+        let guard_count = match bindings.guard_count() {
+            Some(count) => count,
+            None if launch_params.guard_count => panic!(
+                "`{}` uses `auto_guard` but binds no runtime sized array, dispatch it with a builder setting `with_guard_count`",
+                batch[0].0.entry_point
+            ),
+            None => 0,
+        };
+
+        let ring = instance.launch_ring();
+        layouts.push(ring.layout.clone());
+        bind_group_ids.push(ring.bind_group.clone());
+
+        ring.push(instance, &launch_params.encode(guard_count, 0))
+    });
+
+    let pipeline_ids = batch
+        .iter()
+        .map(|(shader, _)| {
+            let mut push_constant_ranges = Vec::new();
+
+            if shader.push_constant_size > 0 {
+                check_push_constants(instance, shader.entry_point, shader.push_constant_size);
+
+                push_constant_ranges.push(wgpu::PushConstantRange {
+                    stages: wgpu::ShaderStages::COMPUTE,
+                    range: 0..shader.push_constant_size,
+                });
+            }
+
+            let layout = instance.get_pipeline_layout(PipelineLayoutDescriptor {
+                bind_group_layouts: layouts.clone(),
+                push_constant_ranges,
+            });
+
+            instance.get_compute_pipeline(ComputePipelineDescriptor {
+                layout: Some(layout),
+                module: instance.get_shader_module(shader.source),
+                entry_point: Cow::Borrowed(shader.entry_point),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let bind_groups = bind_group_ids
+        .iter()
+        .map(|id| instance.bind_groups.get(id).unwrap())
+        .collect::<SmallVec<[_; 4]>>();
+    let pipelines = pipeline_ids
+        .iter()
+        .map(|id| instance.compute_pipelines.get(id).unwrap())
+        .collect::<Vec<_>>();
+
+    let push_constants = vec![
+        0;
+        batch
+            .iter()
+            .map(|(shader, _)| shader.push_constant_size as usize)
+            .max()
+            .unwrap_or(0)
+    ];
+
+    let mut encoder = instance
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some(&format!("shatter_command_encoder({})", name)),
+        });
+
+    let mut recorded = 0;
+    let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+        label: Some(&format!("shatter_compute_pass({})", name)),
+    });
+
+    for (i, bind_group) in bind_groups.iter().enumerate() {
+        let offsets = match launch_offset {
+            Some(ref offset) if i == bind_groups.len() - 1 => std::slice::from_ref(offset),
+            _ => &[],
+        };
+
+        compute_pass.set_bind_group(i as u32, bind_group, offsets);
+    }
+
+    let mut current = None;
+
+    // empty dispatches still cost encoding, so they're skipped
+    for (i, (shader, dispatch)) in batch.iter().enumerate() {
+        if dispatch.is_empty() {
+            continue;
+        }
+
+        if current != Some(&pipeline_ids[i]) {
+            compute_pass.set_pipeline(&pipelines[i]);
+
+            if shader.push_constant_size > 0 {
+                let size = shader.push_constant_size as usize;
+                compute_pass.set_push_constants(0, &push_constants[..size]);
+            }
+
+            current = Some(&pipeline_ids[i]);
+        }
+
+        compute_pass.dispatch(dispatch.x, dispatch.y, dispatch.z);
+        recorded += 1;
+    }
+
+    drop(compute_pass);
+
+    let entry_points = batch
+        .iter()
+        .map(|(shader, _)| Cow::Borrowed(shader.entry_point))
+        .collect::<Vec<_>>();
+
+    instance.queue.submit(std::iter::once(encoder.finish()));
+    instance.submitted(Some(Cow::Owned(name.to_owned())), &entry_points);
+    instance.stats.dispatched(recorded);
+
+    bindings.write();
+}
+
+/// A compute shader that can be selected at runtime.
+///
+/// Implemented by every shader generated by `wgsl!`, shader families return
+/// their variants as `&dyn DispatchableShader`.
+pub trait DispatchableShader<B> {
+    fn entry_point(&self) -> &'static str;
+
+    fn work_group_size(&self) -> WorkGroupSize;
+
+    fn dispatch(&self, bindings: B, dispatch: Dispatch);
+}
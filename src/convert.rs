@@ -0,0 +1,236 @@
+//! Row conversion kernels shared by the CPU side texture helpers, along with
+//! packing 3 channel colors into the 4 channel colors textures store.
+//!
+//! Every kernel processes slices in fixed size chunks, which keeps the inner
+//! loops free of bounds checks so they are auto-vectorized, followed by a
+//! scalar loop over the remainder.
+
+use once_cell::sync::Lazy;
+
+use crate::{RgbColor, RgbaColor};
+
+/// Number of elements processed per chunk.
+const CHUNK: usize = 16;
+
+/// Entries of the table used by [`linear_to_srgb8`], a power of two.
+const LINEAR_TO_SRGB_ENTRIES: usize = 4096;
+
+/// Adding 2^23 to a float in `0.0..2^23` rounds it to an integer in the low
+/// mantissa bits, which unlike a saturating float to int cast vectorizes.
+const ROUND_MAGIC: f32 = 8388608.0;
+
+static SRGB_TO_LINEAR: Lazy<[f32; 256]> = Lazy::new(|| {
+    let mut table = [0.0; 256];
+
+    for (value, entry) in table.iter_mut().enumerate() {
+        *entry = srgb_to_linear_f32(value as f32 / 255.0);
+    }
+
+    table
+});
+
+static LINEAR_TO_SRGB: Lazy<Box<[u8; LINEAR_TO_SRGB_ENTRIES]>> = Lazy::new(|| {
+    let mut table = Box::new([0; LINEAR_TO_SRGB_ENTRIES]);
+
+    for (i, entry) in table.iter_mut().enumerate() {
+        let linear = i as f32 / (LINEAR_TO_SRGB_ENTRIES - 1) as f32;
+
+        *entry = f32_to_unorm8_scalar(linear_to_srgb_f32(linear));
+    }
+
+    table
+});
+
+/// Converts an sRGB encoded value in `0.0..=1.0` to linear.
+#[inline]
+pub fn srgb_to_linear_f32(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a linear value in `0.0..=1.0` to sRGB encoded.
+#[inline]
+pub fn linear_to_srgb_f32(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Rounds `value` in `0.0..2^23` to nearest, ties to even.
+#[inline(always)]
+fn round_to_bits(value: f32) -> u32 {
+    (value + ROUND_MAGIC).to_bits()
+}
+
+/// Clamps `value` to `0.0..=1.0`, NaN to `0.0`, which `clamp` doesn't.
+#[allow(clippy::manual_clamp)]
+#[inline(always)]
+fn saturate(value: f32) -> f32 {
+    value.max(0.0).min(1.0)
+}
+
+#[inline(always)]
+fn f32_to_unorm8_scalar(value: f32) -> u8 {
+    round_to_bits(saturate(value) * 255.0) as u8
+}
+
+/// Applies `f` to every element of `src`, writing the results to `dst`.
+///
+/// # Panics
+/// Panics if `src` and `dst` differ in length.
+#[inline(always)]
+fn map_chunked<S: Copy, D>(src: &[S], dst: &mut [D], f: impl Fn(S) -> D) {
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination lengths differ"
+    );
+
+    let mut src_chunks = src.chunks_exact(CHUNK);
+    let mut dst_chunks = dst.chunks_exact_mut(CHUNK);
+
+    for (src, dst) in (&mut src_chunks).zip(&mut dst_chunks) {
+        let src: &[S; CHUNK] = src.try_into().unwrap();
+        let dst: &mut [D; CHUNK] = dst.try_into().unwrap();
+
+        for i in 0..CHUNK {
+            dst[i] = f(src[i]);
+        }
+    }
+
+    for (src, dst) in src_chunks
+        .remainder()
+        .iter()
+        .zip(dst_chunks.into_remainder())
+    {
+        *dst = f(*src);
+    }
+}
+
+/// Normalizes unorm8 values to `0.0..=1.0`.
+///
+/// # Panics
+/// Panics if `src` and `dst` differ in length.
+pub fn unorm8_to_f32(src: &[u8], dst: &mut [f32]) {
+    map_chunked(src, dst, |value| value as f32 / 255.0);
+}
+
+/// Quantizes values to unorm8, clamping to `0.0..=1.0` and rounding to
+/// nearest, ties to even. NaN is converted to zero.
+///
+/// # Panics
+/// Panics if `src` and `dst` differ in length.
+pub fn f32_to_unorm8(src: &[f32], dst: &mut [u8]) {
+    map_chunked(src, dst, f32_to_unorm8_scalar);
+}
+
+/// Decodes sRGB encoded unorm8 values to linear `0.0..=1.0`.
+///
+/// Alpha isn't sRGB encoded, use [`unorm8_to_f32`] for alpha channels.
+///
+/// # Panics
+/// Panics if `src` and `dst` differ in length.
+pub fn srgb8_to_linear(src: &[u8], dst: &mut [f32]) {
+    let table = &*SRGB_TO_LINEAR;
+
+    map_chunked(src, dst, |value| table[value as usize]);
+}
+
+/// Encodes linear values to sRGB unorm8, clamping to `0.0..=1.0`. NaN is
+/// converted to zero.
+///
+/// Uses a lookup table, results are within one of the exact encoding.
+///
+/// # Panics
+/// Panics if `src` and `dst` differ in length.
+pub fn linear_to_srgb8(src: &[f32], dst: &mut [u8]) {
+    let table = &**LINEAR_TO_SRGB;
+    let scale = (LINEAR_TO_SRGB_ENTRIES - 1) as f32;
+
+    map_chunked(src, dst, |value| {
+        let index = round_to_bits(saturate(value) * scale) as usize;
+
+        // the mask is a no-op after saturating, but lets the bounds check be elided
+        table[index & (LINEAR_TO_SRGB_ENTRIES - 1)]
+    });
+}
+
+/// Copies `rows` rows of `row_bytes` bytes between buffers with different
+/// row strides, e.g. stripping the row padding required by texture copies.
+///
+/// # Panics
+/// Panics if `row_bytes` exceeds either stride or either buffer is too small.
+pub fn copy_rows(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+    row_bytes: usize,
+    rows: usize,
+) {
+    assert!(
+        row_bytes <= src_stride && row_bytes <= dst_stride,
+        "row of {} bytes exceeds stride",
+        row_bytes
+    );
+
+    if rows == 0 {
+        return;
+    }
+
+    let src_len = (rows - 1) * src_stride + row_bytes;
+    let dst_len = (rows - 1) * dst_stride + row_bytes;
+    assert!(src.len() >= src_len, "source is too small");
+    assert!(dst.len() >= dst_len, "destination is too small");
+
+    // tightly packed rows are copied at once
+    if src_stride == row_bytes && dst_stride == row_bytes {
+        dst[..src_len].copy_from_slice(&src[..src_len]);
+        return;
+    }
+
+    for (src, dst) in src[..src_len]
+        .chunks(src_stride)
+        .zip(dst[..dst_len].chunks_mut(dst_stride))
+    {
+        dst[..row_bytes].copy_from_slice(&src[..row_bytes]);
+    }
+}
+
+/// Adds `alpha` to every color of `src`, writing the 4 channel colors to `dst`.
+///
+/// # Panics
+/// Panics if `src` and `dst` differ in length.
+pub fn pack_rgb_into<C: RgbColor>(src: &[C], alpha: C::Channel, dst: &mut [C::Rgba]) {
+    map_chunked(src, dst, |color| color.with_alpha(alpha));
+}
+
+/// Adds `alpha` to every color of `src`, e.g. to upload RGB images to a 4
+/// channel texture, see also [`Texture2d::from_rgb_data`](crate::Texture2d::from_rgb_data).
+pub fn pack_rgb_to_rgba<C: RgbColor>(src: &[C], alpha: C::Channel) -> Vec<C::Rgba> {
+    let mut dst = vec![C::Rgba::default(); src.len()];
+    pack_rgb_into(src, alpha, &mut dst);
+    dst
+}
+
+/// Drops the alpha channel of every color of `src`, writing the 3 channel
+/// colors to `dst`.
+///
+/// # Panics
+/// Panics if `src` and `dst` differ in length.
+pub fn strip_alpha_into<C: RgbaColor>(src: &[C], dst: &mut [C::Rgb]) {
+    map_chunked(src, dst, RgbaColor::to_rgb);
+}
+
+/// Drops the alpha channel of every color of `src`, the inverse of
+/// [`pack_rgb_to_rgba`].
+pub fn strip_alpha<C: RgbaColor>(src: &[C]) -> Vec<C::Rgb> {
+    let mut dst = vec![C::Rgb::default(); src.len()];
+    strip_alpha_into(src, &mut dst);
+    dst
+}
@@ -0,0 +1,240 @@
+//! Tracking of the allocations of the [`BufferData`](crate::BufferData)
+//! implementations generated by `wgsl!`, catching mismatched layouts, double
+//! frees and leaks in the generated unsafe code.
+//!
+//! Generated implementations call [`record_alloc`], [`record_realloc`] and
+//! [`record_dealloc`] around every allocation, which are no-ops unless the
+//! `leak-check` feature is enabled. With it, every live allocation is recorded
+//! with its layout and type, and every misuse is printed to stderr and kept as
+//! an [`AllocReport`], see [`reports`]. Leaks are reported by [`LeakCheck`].
+
+use std::{alloc::Layout, fmt, ptr::NonNull};
+
+#[cfg(feature = "leak-check")]
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+#[cfg(feature = "leak-check")]
+use once_cell::sync::Lazy;
+
+/// What went wrong with an allocation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportKind {
+    /// Deallocated or reallocated without being allocated, or after being
+    /// deallocated already.
+    UnknownPointer,
+    /// Deallocated or reallocated with a layout other than it was allocated
+    /// with, `layout` of the report.
+    LayoutMismatch { allocated: Layout },
+    /// Still allocated when a [`LeakCheck`] was dropped.
+    Leak,
+}
+
+/// A misused allocation, see the [module docs](self).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AllocReport {
+    pub kind: ReportKind,
+    /// Name of the type the allocation holds.
+    pub type_name: &'static str,
+    /// Address of the allocation.
+    pub ptr: usize,
+    /// Layout the allocation was deallocated with, or allocated with for leaks.
+    pub layout: Layout,
+}
+
+impl fmt::Display for AllocReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ReportKind::UnknownPointer => write!(
+                f,
+                "`{}` deallocated {:#x}, which isn't allocated, e.g. a double free",
+                self.type_name, self.ptr
+            ),
+            ReportKind::LayoutMismatch { allocated } => write!(
+                f,
+                "`{}` deallocated {:#x} with {:?}, it was allocated with {:?}",
+                self.type_name, self.ptr, self.layout, allocated
+            ),
+            ReportKind::Leak => write!(
+                f,
+                "`{}` leaked {:#x} with {:?}",
+                self.type_name, self.ptr, self.layout
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "leak-check")]
+struct Allocation {
+    layout: Layout,
+    type_name: &'static str,
+    /// Allocations made before a [`LeakCheck`] aren't its leaks.
+    sequence: u64,
+}
+
+#[cfg(feature = "leak-check")]
+#[derive(Default)]
+struct Registry {
+    live: HashMap<usize, Allocation>,
+    next_sequence: u64,
+    reports: Vec<AllocReport>,
+}
+
+#[cfg(feature = "leak-check")]
+static REGISTRY: Lazy<Mutex<Registry>> = Lazy::new(Default::default);
+
+/// Locks the registry, a panic while reporting doesn't stop the tracking.
+#[cfg(feature = "leak-check")]
+fn registry() -> MutexGuard<'static, Registry> {
+    REGISTRY.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(feature = "leak-check")]
+impl Registry {
+    fn report(&mut self, report: AllocReport) {
+        eprintln!("shatter leak-check: {}", report);
+        self.reports.push(report);
+    }
+
+    fn insert(&mut self, ptr: NonNull<u8>, layout: Layout, type_name: &'static str) {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+
+        self.live.insert(
+            ptr.as_ptr() as usize,
+            Allocation {
+                layout,
+                type_name,
+                sequence,
+            },
+        );
+    }
+
+    fn remove(&mut self, ptr: NonNull<u8>, layout: Layout, type_name: &'static str) {
+        let address = ptr.as_ptr() as usize;
+
+        let kind = match self.live.remove(&address) {
+            Some(allocation) if allocation.layout == layout => return,
+            Some(allocation) => ReportKind::LayoutMismatch {
+                allocated: allocation.layout,
+            },
+            None => ReportKind::UnknownPointer,
+        };
+
+        self.report(AllocReport {
+            kind,
+            type_name,
+            ptr: address,
+            layout,
+        });
+    }
+}
+
+/// Records that `ptr` was allocated with `layout` to hold a `T`.
+#[inline(always)]
+pub fn record_alloc<T: ?Sized>(ptr: NonNull<u8>, layout: Layout) {
+    #[cfg(feature = "leak-check")]
+    registry().insert(ptr, layout, std::any::type_name::<T>());
+
+    #[cfg(not(feature = "leak-check"))]
+    let _ = (ptr, layout);
+}
+
+/// Records that `old_ptr`, allocated with `old_layout`, was reallocated to
+/// `new_ptr` with `new_layout`. An `old_layout` of size 0 had no allocation.
+#[inline(always)]
+pub fn record_realloc<T: ?Sized>(
+    old_ptr: NonNull<u8>,
+    old_layout: Layout,
+    new_ptr: NonNull<u8>,
+    new_layout: Layout,
+) {
+    #[cfg(feature = "leak-check")]
+    {
+        let type_name = std::any::type_name::<T>();
+        let mut registry = registry();
+
+        if old_layout.size() != 0 {
+            registry.remove(old_ptr, old_layout, type_name);
+        }
+
+        registry.insert(new_ptr, new_layout, type_name);
+    }
+
+    #[cfg(not(feature = "leak-check"))]
+    let _ = (old_ptr, old_layout, new_ptr, new_layout);
+}
+
+/// Records that `ptr` holding a `T` is deallocated with `layout`, reporting
+/// if it isn't allocated or was allocated with another layout.
+#[inline(always)]
+pub fn record_dealloc<T: ?Sized>(ptr: NonNull<u8>, layout: Layout) {
+    #[cfg(feature = "leak-check")]
+    registry().remove(ptr, layout, std::any::type_name::<T>());
+
+    #[cfg(not(feature = "leak-check"))]
+    let _ = (ptr, layout);
+}
+
+/// Every report so far, oldest first.
+#[cfg(feature = "leak-check")]
+pub fn reports() -> Vec<AllocReport> {
+    registry().reports.clone()
+}
+
+/// Reports the allocations made after it was created that are still live when
+/// it's dropped, e.g. around a test or the main function.
+#[cfg(feature = "leak-check")]
+pub struct LeakCheck {
+    since: u64,
+}
+
+#[cfg(feature = "leak-check")]
+impl LeakCheck {
+    pub fn new() -> Self {
+        Self {
+            since: registry().next_sequence,
+        }
+    }
+
+    /// The allocations made since creation that are still live.
+    pub fn leaks(&self) -> Vec<AllocReport> {
+        let registry = registry();
+
+        let mut leaks = registry
+            .live
+            .iter()
+            .filter(|(_, allocation)| allocation.sequence >= self.since)
+            .map(|(&ptr, allocation)| AllocReport {
+                kind: ReportKind::Leak,
+                type_name: allocation.type_name,
+                ptr,
+                layout: allocation.layout,
+            })
+            .collect::<Vec<_>>();
+
+        leaks.sort_by_key(|leak| leak.ptr);
+        leaks
+    }
+}
+
+#[cfg(feature = "leak-check")]
+impl Default for LeakCheck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "leak-check")]
+impl Drop for LeakCheck {
+    fn drop(&mut self) {
+        let leaks = self.leaks();
+        let mut registry = registry();
+
+        for leak in leaks {
+            registry.report(leak);
+        }
+    }
+}
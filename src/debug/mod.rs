@@ -0,0 +1,15 @@
+//! Debugging aids, each behind a feature.
+//!
+//! With `debug-recorder`, a [`Recorder`] set with
+//! [`Instance::set_recorder`](crate::Instance::set_recorder) records buffers at
+//! dispatch boundaries, to find where a long running simulation first diverges.
+//!
+//! With `leak-check`, the [`alloc_registry`] tracks every allocation of the
+//! `BufferData` implementations generated by `wgsl!`.
+
+pub mod alloc_registry;
+#[cfg(feature = "debug-recorder")]
+mod recorder;
+
+#[cfg(feature = "debug-recorder")]
+pub use recorder::*;
@@ -0,0 +1,460 @@
+//! Recording buffers at dispatch boundaries, to find where a long running
+//! simulation first diverges.
+//!
+//! A [`Recorder`] set with [`Instance::set_recorder`] copies the buffers
+//! written by every matching dispatch into buffers of its own, in the command
+//! encoder of the dispatch. Nothing is downloaded until a [`BufferSnapshot`]
+//! is inspected.
+
+use std::{
+    collections::VecDeque,
+    fs, io,
+    num::NonZeroU64,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use once_cell::sync::OnceCell;
+
+use crate::{
+    items_from_bytes, BindGroupDescriptor, BindGroupLayoutDescriptor, BindingResource, BufferId,
+    Dispatch, Instance, Reflect,
+};
+
+/// Default byte budget of a [`Recorder`].
+pub const DEFAULT_MAX_RECORDED_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Which dispatches a [`Recorder`] records.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordPolicy {
+    /// Entry points to record, every entry point if empty.
+    pub entry_points: Vec<String>,
+    /// Frames to record, see [`Recorder::next_frame`].
+    pub frames: Range<u64>,
+    /// Raw ids of the buffers to record, see [`Id::raw`](crate::Id::raw).
+    /// Every written buffer if empty.
+    pub buffers: Vec<u64>,
+    /// Bytes of snapshots kept, the oldest are evicted to make room.
+    pub max_bytes: u64,
+}
+
+impl Default for RecordPolicy {
+    fn default() -> Self {
+        Self {
+            entry_points: Vec::new(),
+            frames: 0..u64::MAX,
+            buffers: Vec::new(),
+            max_bytes: DEFAULT_MAX_RECORDED_BYTES,
+        }
+    }
+}
+
+impl RecordPolicy {
+    fn records(&self, entry_point: &str, frame: u64) -> bool {
+        self.frames.contains(&frame)
+            && (self.entry_points.is_empty()
+                || self.entry_points.iter().any(|name| name == entry_point))
+    }
+
+    fn records_buffer(&self, buffer: u64) -> bool {
+        self.buffers.is_empty() || self.buffers.contains(&buffer)
+    }
+}
+
+/// A buffer as written by a dispatch, see [`Recorder`].
+pub struct BufferSnapshot {
+    /// Position among every snapshot taken by the recorder, including evicted ones.
+    pub sequence: u64,
+    pub frame: u64,
+    pub entry_point: String,
+    /// Dispatches recorded in the pass, empty for indirect dispatches.
+    pub dispatches: Vec<Dispatch>,
+    pub group: u32,
+    pub binding: u32,
+    /// Raw id of the buffer, see [`Id::raw`](crate::Id::raw).
+    pub buffer: u64,
+    /// Offset of the binding into the buffer.
+    pub offset: u64,
+    pub size: u64,
+    // released once downloaded
+    gpu: Mutex<Option<wgpu::Buffer>>,
+    bytes: OnceCell<Vec<u8>>,
+}
+
+impl BufferSnapshot {
+    /// Gets the bound bytes of the buffer, downloading them on first access.
+    ///
+    /// Snapshots of dispatches recorded into an encoder passed to
+    /// [`ComputeShaderBuilder::encoder`](crate::ComputeShaderBuilder::encoder)
+    /// can only be read once it's submitted.
+    pub fn bytes(&self) -> &[u8] {
+        self.bytes.get_or_init(|| {
+            let instance = Instance::global();
+            let buffer = self.gpu.lock().unwrap().take().unwrap();
+
+            let slice = buffer.slice(..);
+            let future = slice.map_async(wgpu::MapMode::Read);
+            instance.device.poll(wgpu::Maintain::Wait);
+            pollster::block_on(future).unwrap();
+
+            let bytes = slice.get_mapped_range().to_vec();
+            buffer.unmap();
+
+            instance.stats.downloaded(bytes.len() as u64);
+
+            bytes
+        })
+    }
+
+    /// Returns true if the bytes have been downloaded.
+    pub fn is_downloaded(&self) -> bool {
+        self.bytes.get().is_some()
+    }
+
+    /// Reads the items `T` starting at byte `offset`, e.g. the size of the
+    /// fields preceding a runtime sized array. A trailing partial item is ignored.
+    pub fn items<T: Reflect>(&self, offset: u64) -> Vec<T> {
+        let bytes = self.bytes().get(offset as usize..).unwrap_or_default();
+        let len = bytes.len() - bytes.len() % std::mem::size_of::<T>().max(1);
+
+        items_from_bytes(&bytes[..len])
+    }
+
+    /// Compares the items `T` starting at byte `offset` with those of
+    /// `other`, usually the same buffer in another frame or another run.
+    ///
+    /// Returns `None` if they're equal. Items only one of the snapshots has
+    /// count as differing.
+    pub fn diff<T: Reflect + PartialEq>(
+        &self,
+        other: &BufferSnapshot,
+        offset: u64,
+    ) -> Option<BufferDiff<T>> {
+        let items = self.items::<T>(offset);
+        let other_items = other.items::<T>(offset);
+
+        let mut diff: Option<BufferDiff<T>> = None;
+
+        for i in 0..items.len().max(other_items.len()) {
+            let (item, other_item) = (items.get(i), other_items.get(i));
+
+            if item == other_item {
+                continue;
+            }
+
+            match diff {
+                Some(ref mut diff) => diff.differing += 1,
+                None => {
+                    diff = Some(BufferDiff {
+                        first_index: i,
+                        first: item.copied(),
+                        second: other_item.copied(),
+                        differing: 1,
+                    })
+                }
+            }
+        }
+
+        diff
+    }
+
+    /// Writes the bytes to `path`.
+    pub fn export(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.bytes())
+    }
+
+    fn file_name(&self) -> String {
+        format!(
+            "{:06}_frame{}_{}_group{}_binding{}.bin",
+            self.sequence, self.frame, self.entry_point, self.group, self.binding
+        )
+    }
+}
+
+/// Items differing between two snapshots, see [`BufferSnapshot::diff`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BufferDiff<T> {
+    /// Index of the first differing item.
+    pub first_index: usize,
+    /// The first differing item of the snapshot compared, `None` past its end.
+    pub first: Option<T>,
+    /// The first differing item of the other snapshot, `None` past its end.
+    pub second: Option<T>,
+    /// Number of differing items.
+    pub differing: usize,
+}
+
+#[derive(Default)]
+struct Snapshots {
+    snapshots: VecDeque<Arc<BufferSnapshot>>,
+    bytes: u64,
+    sequence: u64,
+    evicted: u64,
+    skipped: u64,
+}
+
+/// Records the buffers written by dispatches, see the [module](self) docs.
+pub struct Recorder {
+    policy: RecordPolicy,
+    frame: AtomicU64,
+    snapshots: Mutex<Snapshots>,
+}
+
+impl Recorder {
+    pub fn new(policy: RecordPolicy) -> Self {
+        Self {
+            policy,
+            frame: AtomicU64::new(0),
+            snapshots: Mutex::default(),
+        }
+    }
+
+    #[inline]
+    pub fn policy(&self) -> &RecordPolicy {
+        &self.policy
+    }
+
+    /// Frame snapshots are currently tagged with, starting at 0.
+    #[inline]
+    pub fn frame(&self) -> u64 {
+        self.frame.load(Ordering::Acquire)
+    }
+
+    /// Advances to the next frame, returns its index.
+    #[inline]
+    pub fn next_frame(&self) -> u64 {
+        self.frame.fetch_add(1, Ordering::AcqRel) + 1
+    }
+
+    /// Gets the snapshots kept, oldest first.
+    pub fn snapshots(&self) -> Vec<Arc<BufferSnapshot>> {
+        let snapshots = self.snapshots.lock().unwrap();
+
+        snapshots.snapshots.iter().cloned().collect()
+    }
+
+    /// Gets the snapshots of the buffer with raw id `buffer`, oldest first.
+    pub fn snapshots_of(&self, buffer: u64) -> Vec<Arc<BufferSnapshot>> {
+        let snapshots = self.snapshots.lock().unwrap();
+
+        snapshots
+            .snapshots
+            .iter()
+            .filter(|snapshot| snapshot.buffer == buffer)
+            .cloned()
+            .collect()
+    }
+
+    /// Bytes of the snapshots kept.
+    pub fn recorded_bytes(&self) -> u64 {
+        self.snapshots.lock().unwrap().bytes
+    }
+
+    /// Number of snapshots evicted to stay within [`RecordPolicy::max_bytes`].
+    pub fn evicted(&self) -> u64 {
+        self.snapshots.lock().unwrap().evicted
+    }
+
+    /// Number of snapshots not taken as they alone exceed [`RecordPolicy::max_bytes`].
+    pub fn skipped(&self) -> u64 {
+        self.snapshots.lock().unwrap().skipped
+    }
+
+    /// Drops every snapshot.
+    pub fn clear(&self) {
+        let mut snapshots = self.snapshots.lock().unwrap();
+
+        snapshots.snapshots.clear();
+        snapshots.bytes = 0;
+    }
+
+    /// Writes every snapshot kept into `dir`, one file each named after its
+    /// sequence, frame, entry point and binding. Returns the paths written.
+    pub fn export(&self, dir: impl AsRef<Path>) -> io::Result<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        self.snapshots()
+            .iter()
+            .map(|snapshot| {
+                let path = dir.join(snapshot.file_name());
+                snapshot.export(&path)?;
+
+                Ok(path)
+            })
+            .collect()
+    }
+
+    /// Makes room for `size` bytes, returns the sequence of the snapshot or
+    /// `None` if it doesn't fit the budget at all.
+    fn reserve(&self, snapshots: &mut Snapshots, size: u64) -> Option<u64> {
+        if size > self.policy.max_bytes {
+            snapshots.skipped += 1;
+            return None;
+        }
+
+        while snapshots.bytes + size > self.policy.max_bytes {
+            let evicted = snapshots.snapshots.pop_front().unwrap();
+
+            snapshots.bytes -= evicted.size;
+            snapshots.evicted += 1;
+        }
+
+        snapshots.sequence += 1;
+
+        Some(snapshots.sequence - 1)
+    }
+}
+
+/// A written buffer binding of a dispatch.
+struct WrittenBuffer {
+    group: u32,
+    binding: u32,
+    buffer: BufferId,
+    offset: u64,
+    size: u64,
+}
+
+/// The buffers a dispatch will write, collected before the dispatch is
+/// recorded when a [`Recorder`] records it.
+pub(crate) struct Recording {
+    recorder: Arc<Recorder>,
+    frame: u64,
+    // (group, binding) of the read_write storage buffers
+    written: Vec<(u32, u32)>,
+    buffers: Vec<WrittenBuffer>,
+}
+
+impl Recording {
+    /// Starts recording a dispatch of `entry_point` if the recorder of the
+    /// instance, if any, records it.
+    pub fn start(entry_point: &str, layouts: &[BindGroupLayoutDescriptor]) -> Option<Self> {
+        let recorder = Instance::global().recorder()?;
+        let frame = recorder.frame();
+
+        if !recorder.policy.records(entry_point, frame) {
+            return None;
+        }
+
+        let written = layouts
+            .iter()
+            .enumerate()
+            .flat_map(|(group, layout)| {
+                layout
+                    .entries
+                    .iter()
+                    .filter_map(move |entry| match entry.ty {
+                        wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: false },
+                            ..
+                        } => Some((group as u32, entry.binding)),
+                        _ => None,
+                    })
+            })
+            .collect();
+
+        Some(Self {
+            recorder,
+            frame,
+            written,
+            buffers: Vec::new(),
+        })
+    }
+
+    /// Resolves the written buffers from the bind groups of the dispatch.
+    pub fn bind(&mut self, groups: &[BindGroupDescriptor]) {
+        let instance = Instance::global();
+
+        for &(group, binding) in &self.written {
+            let resource = groups.get(group as usize).and_then(|desc| {
+                desc.entries
+                    .iter()
+                    .find(|entry| entry.binding == binding)
+                    .map(|entry| &entry.resource)
+            });
+
+            let buffer = match resource {
+                Some(BindingResource::Buffer(buffer)) => buffer,
+                _ => continue,
+            };
+
+            if !self.recorder.policy.records_buffer(buffer.buffer.raw()) {
+                continue;
+            }
+
+            let size = match buffer.size.map(NonZeroU64::get) {
+                Some(size) => size,
+                None => match instance.buffers.size_of(&buffer.buffer) {
+                    Some(size) => size.saturating_sub(buffer.offset),
+                    None => continue,
+                },
+            };
+
+            // copies are in whole words
+            let size = size & !(wgpu::COPY_BUFFER_ALIGNMENT - 1);
+
+            if size > 0 {
+                self.buffers.push(WrittenBuffer {
+                    group,
+                    binding,
+                    buffer: buffer.buffer.clone(),
+                    offset: buffer.offset,
+                    size,
+                });
+            }
+        }
+    }
+
+    /// Copies the written buffers into snapshots, after the compute pass of
+    /// `dispatches` in `encoder`.
+    pub fn record(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        entry_point: &str,
+        dispatches: &[Dispatch],
+    ) {
+        let instance = Instance::global();
+
+        for written in &self.buffers {
+            let source = match instance.buffers.get(&written.buffer) {
+                Some(source) => source,
+                None => continue,
+            };
+
+            let mut snapshots = self.recorder.snapshots.lock().unwrap();
+
+            let sequence = match self.recorder.reserve(&mut snapshots, written.size) {
+                Some(sequence) => sequence,
+                None => continue,
+            };
+
+            let gpu = instance.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("shatter_recorded_buffer"),
+                size: written.size,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+
+            encoder.copy_buffer_to_buffer(&source, written.offset, &gpu, 0, written.size);
+
+            snapshots.bytes += written.size;
+            snapshots.snapshots.push_back(Arc::new(BufferSnapshot {
+                sequence,
+                frame: self.frame,
+                entry_point: entry_point.into(),
+                dispatches: dispatches.to_vec(),
+                group: written.group,
+                binding: written.binding,
+                buffer: written.buffer.raw(),
+                offset: written.offset,
+                size: written.size,
+                gpu: Mutex::new(Some(gpu)),
+                bytes: OnceCell::new(),
+            }));
+        }
+    }
+}
@@ -0,0 +1,48 @@
+// Converts a counter into the arguments of an indirect dispatch.
+
+[[block]]
+struct Counter {
+    values: array<u32>;
+};
+
+[[block]]
+struct Params {
+    // index of the counter in `counter.values`
+    index: u32;
+    work_group_size: u32;
+    max_x: u32;
+    max_y: u32;
+    max_z: u32;
+};
+
+[[block]]
+struct DispatchArgs {
+    x: u32;
+    y: u32;
+    z: u32;
+};
+
+[[group(0), binding(0)]]
+var<storage, read> counter: Counter;
+
+[[group(0), binding(1)]]
+var<uniform> params: Params;
+
+[[group(0), binding(2)]]
+var<storage, read_write> args: DispatchArgs;
+
+[[stage(compute), workgroup_size(1)]]
+fn main() {
+    let count = counter.values[params.index];
+
+    // rounded up without overflowing
+    var groups: u32 = count / params.work_group_size;
+
+    if (count % params.work_group_size != 0u) {
+        groups = groups + 1u;
+    }
+
+    args.x = min(groups, params.max_x);
+    args.y = params.max_y;
+    args.z = params.max_z;
+}
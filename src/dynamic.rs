@@ -0,0 +1,412 @@
+use std::fmt;
+
+use crate::{
+    same_binding_type, validate_binding, BindGroupDescriptor, BindGroupEntry,
+    BindGroupLayoutDescriptor, BindGroupLayoutId, Binding, BindingErrorKind, BindingExpectation,
+    BindingMut, BindingResource, Bindings, ReflectedBinding, ResourceInfo, StorageTextureBinding,
+    TransferReport, ValidationReport,
+};
+
+/// A [`Binding`] with its binding type erased.
+trait ErasedBinding {
+    fn binding_resource(&self) -> BindingResource;
+
+    fn prepare(&self, report: &mut TransferReport);
+
+    fn read(&self, report: &mut TransferReport);
+
+    fn write(&mut self);
+
+    fn needs_allocation(&self) -> bool;
+
+    fn resource_info(&self) -> ResourceInfo;
+}
+
+struct Shared<'a, T: ?Sized>(&'a dyn Binding<T>);
+
+impl<T: ?Sized> ErasedBinding for Shared<'_, T> {
+    fn binding_resource(&self) -> BindingResource {
+        self.0.binding_resource()
+    }
+
+    fn prepare(&self, report: &mut TransferReport) {
+        self.0.prepare(report)
+    }
+
+    fn read(&self, report: &mut TransferReport) {
+        self.0.read(report)
+    }
+
+    fn write(&mut self) {}
+
+    fn needs_allocation(&self) -> bool {
+        self.0.needs_allocation()
+    }
+
+    fn resource_info(&self) -> ResourceInfo {
+        self.0.resource_info()
+    }
+}
+
+struct Exclusive<'a, T: ?Sized>(&'a mut dyn BindingMut<T>);
+
+impl<T: ?Sized> ErasedBinding for Exclusive<'_, T> {
+    fn binding_resource(&self) -> BindingResource {
+        self.0.binding_resource()
+    }
+
+    fn prepare(&self, report: &mut TransferReport) {
+        self.0.prepare(report)
+    }
+
+    fn read(&self, report: &mut TransferReport) {
+        self.0.read(report)
+    }
+
+    fn write(&mut self) {
+        self.0.write()
+    }
+
+    fn needs_allocation(&self) -> bool {
+        self.0.needs_allocation()
+    }
+
+    fn resource_info(&self) -> ResourceInfo {
+        self.0.resource_info()
+    }
+}
+
+struct DynamicEntry<'a> {
+    group: u32,
+    binding: u32,
+    ty: wgpu::BindingType,
+    resource: Box<dyn ErasedBinding + 'a>,
+}
+
+/// Bindings assembled at runtime, for shaders without generated bindings,
+/// e.g. a [`RawComputeShader`](crate::RawComputeShader).
+///
+/// Binding types are derived from how each resource is added, texture formats
+/// and dimensions from the resources themselves. A
+/// [`DynamicComputeShader`](crate::DynamicComputeShader) checks them against
+/// the bindings of its entry point before dispatching.
+#[derive(Default)]
+pub struct DynamicBindings<'a> {
+    entries: Vec<DynamicEntry<'a>>,
+}
+
+impl<'a> DynamicBindings<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a `var<storage, read_write>` buffer.
+    pub fn storage<T: ?Sized + 'a>(
+        self,
+        group: u32,
+        binding: u32,
+        buffer: &'a mut dyn BindingMut<T>,
+    ) -> Self {
+        let ty = buffer_type(wgpu::BufferBindingType::Storage { read_only: false });
+
+        self.push(group, binding, ty, Box::new(Exclusive(buffer)))
+    }
+
+    /// Adds a `var<storage, read>` buffer.
+    pub fn storage_read<T: ?Sized + 'a>(
+        self,
+        group: u32,
+        binding: u32,
+        buffer: &'a dyn Binding<T>,
+    ) -> Self {
+        let ty = buffer_type(wgpu::BufferBindingType::Storage { read_only: true });
+
+        self.push(group, binding, ty, Box::new(Shared(buffer)))
+    }
+
+    /// Adds a `var<uniform>` buffer.
+    pub fn uniform<T: ?Sized + 'a>(
+        self,
+        group: u32,
+        binding: u32,
+        buffer: &'a dyn Binding<T>,
+    ) -> Self {
+        let ty = buffer_type(wgpu::BufferBindingType::Uniform);
+
+        self.push(group, binding, ty, Box::new(Shared(buffer)))
+    }
+
+    /// Adds a storage texture accessed with `access`.
+    pub fn storage_texture<F: 'a, D: 'a>(
+        self,
+        group: u32,
+        binding: u32,
+        texture: &'a mut dyn BindingMut<StorageTextureBinding<F, D>>,
+        access: wgpu::StorageTextureAccess,
+    ) -> Self {
+        let ty = match texture.resource_info() {
+            ResourceInfo::Texture {
+                format,
+                view_dimension,
+                ..
+            } => wgpu::BindingType::StorageTexture {
+                access,
+                format,
+                view_dimension,
+            },
+            ResourceInfo::Buffer { .. } => panic!("storage texture binding is a buffer"),
+            ResourceInfo::Sampler { .. } => panic!("storage texture binding is a sampler"),
+        };
+
+        self.push(group, binding, ty, Box::new(Exclusive(texture)))
+    }
+
+    /// # Panics
+    /// Panics if `group` and `binding` are already bound.
+    fn push(
+        mut self,
+        group: u32,
+        binding: u32,
+        ty: wgpu::BindingType,
+        resource: Box<dyn ErasedBinding + 'a>,
+    ) -> Self {
+        assert!(
+            !self
+                .entries
+                .iter()
+                .any(|entry| entry.group == group && entry.binding == binding),
+            "group {} binding {} is already bound",
+            group,
+            binding
+        );
+
+        self.entries.push(DynamicEntry {
+            group,
+            binding,
+            ty,
+            resource,
+        });
+
+        self
+    }
+
+    /// Checks the resources against the `reflected` bindings of an entry point,
+    /// dropping those the entry point doesn't use.
+    pub(crate) fn check(
+        mut self,
+        reflected: &[ReflectedBinding],
+    ) -> Result<Self, DynamicBindingReport> {
+        let mut report = DynamicBindingReport::default();
+
+        for expected in reflected {
+            let entry = self
+                .entries
+                .iter()
+                .find(|entry| entry.group == expected.group && entry.binding == expected.binding);
+
+            let kinds = match entry {
+                None => vec![DynamicBindingErrorKind::Missing],
+                Some(entry) if !same_binding_type(&expected.ty, &entry.ty) => {
+                    vec![DynamicBindingErrorKind::TypeMismatch {
+                        expected: expected.ty,
+                        found: entry.ty,
+                    }]
+                }
+                Some(entry) => {
+                    let expectation = BindingExpectation {
+                        name: "",
+                        ty: expected.ty,
+                        min_size: expected.min_size,
+                        writable: false,
+                    };
+
+                    validate_binding(&expectation, &entry.resource.resource_info())
+                        .into_iter()
+                        .map(DynamicBindingErrorKind::Resource)
+                        .collect()
+                }
+            };
+
+            report
+                .errors
+                .extend(kinds.into_iter().map(|kind| DynamicBindingError {
+                    group: expected.group,
+                    binding: expected.binding,
+                    name: expected.name.clone(),
+                    kind,
+                }));
+        }
+
+        if !report.errors.is_empty() {
+            return Err(report);
+        }
+
+        self.entries.retain(|entry| {
+            reflected
+                .iter()
+                .any(|expected| expected.group == entry.group && expected.binding == entry.binding)
+        });
+
+        Ok(self)
+    }
+
+    fn groups(&self) -> u32 {
+        self.entries
+            .iter()
+            .map(|entry| entry.group + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn group(&self, group: u32) -> impl Iterator<Item = &DynamicEntry<'a>> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.group == group)
+    }
+}
+
+/// Why a resource of [`DynamicBindings`] can't be bound to a binding.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DynamicBindingErrorKind {
+    /// Nothing was added for a binding the entry point uses.
+    Missing,
+    /// The resource was added as another type of binding, e.g. a uniform
+    /// buffer for a `var<storage>`.
+    TypeMismatch {
+        expected: wgpu::BindingType,
+        found: wgpu::BindingType,
+    },
+    /// The resource doesn't fit the binding, e.g. a buffer too small.
+    Resource(BindingErrorKind),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DynamicBindingError {
+    pub group: u32,
+    pub binding: u32,
+    /// Name of the binding in WGSL.
+    pub name: String,
+    pub kind: DynamicBindingErrorKind,
+}
+
+impl fmt::Display for DynamicBindingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "group {} binding {} `{}`: ",
+            self.group, self.binding, self.name
+        )?;
+
+        match self.kind {
+            DynamicBindingErrorKind::Missing => write!(f, "no resource was added"),
+            DynamicBindingErrorKind::TypeMismatch { expected, found } => {
+                write!(f, "expected {:?}, added as {:?}", expected, found)
+            }
+            DynamicBindingErrorKind::Resource(ref kind) => kind.fmt(f),
+        }
+    }
+}
+
+/// Every problem found checking [`DynamicBindings`] against a
+/// [`DynamicComputeShader`](crate::DynamicComputeShader).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DynamicBindingReport {
+    pub errors: Vec<DynamicBindingError>,
+}
+
+impl DynamicBindingReport {
+    /// Returns true if the binding at `group` and `binding` has an error of `kind`.
+    pub fn contains(&self, group: u32, binding: u32, kind: &DynamicBindingErrorKind) -> bool {
+        self.errors
+            .iter()
+            .any(|error| error.group == group && error.binding == binding && error.kind == *kind)
+    }
+}
+
+impl fmt::Display for DynamicBindingReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "invalid dynamic bindings:")?;
+
+        for error in &self.errors {
+            writeln!(f, "  {}", error)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for DynamicBindingReport {}
+
+fn buffer_type(ty: wgpu::BufferBindingType) -> wgpu::BindingType {
+    wgpu::BindingType::Buffer {
+        ty,
+        has_dynamic_offset: false,
+        min_binding_size: None,
+    }
+}
+
+impl Bindings for DynamicBindings<'_> {
+    fn bind_group_layout_descriptors(&self) -> Vec<BindGroupLayoutDescriptor> {
+        (0..self.groups())
+            .map(|group| {
+                let entries = self
+                    .group(group)
+                    .map(|entry| wgpu::BindGroupLayoutEntry {
+                        binding: entry.binding,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: entry.ty,
+                        count: None,
+                    })
+                    .collect();
+
+                BindGroupLayoutDescriptor::new(entries)
+            })
+            .collect()
+    }
+
+    fn bind_group_descriptors(&self, layouts: &[BindGroupLayoutId]) -> Vec<BindGroupDescriptor> {
+        (0..self.groups())
+            .map(|group| {
+                let entries = self
+                    .group(group)
+                    .map(|entry| BindGroupEntry {
+                        binding: entry.binding,
+                        resource: entry.resource.binding_resource(),
+                    })
+                    .collect();
+
+                BindGroupDescriptor::new(layouts[group as usize].clone(), entries)
+            })
+            .collect()
+    }
+
+    /// Dynamic bindings carry no expectations of the shader, they are only
+    /// validated by wgpu.
+    fn validate(&self) -> Result<(), ValidationReport> {
+        Ok(())
+    }
+
+    fn prepare(&self, report: &mut TransferReport) {
+        for entry in &self.entries {
+            entry.resource.prepare(report);
+        }
+    }
+
+    fn read(&self, report: &mut TransferReport) {
+        for entry in &self.entries {
+            entry.resource.read(report);
+        }
+    }
+
+    fn write(&mut self) {
+        for entry in &mut self.entries {
+            entry.resource.write();
+        }
+    }
+
+    fn needs_allocation(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.resource.needs_allocation())
+    }
+}
@@ -0,0 +1,312 @@
+use std::fmt;
+
+use crate::{
+    CacheFull, CacheKind, ConvertError, DispatchTooLarge, MissingResource, RegistryErrorKind,
+    ValidationReport, MAX_WORK_GROUPS_PER_AXIS,
+};
+
+/// Every error returned by shatter.
+///
+/// Implements [`std::error::Error`], so `?` converts it into `anyhow::Error`
+/// and other boxed errors.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    Init(InitError),
+    Device(DeviceError),
+    Resource(ResourceError),
+    Shader(ShaderError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Init(err) => err.fmt(f),
+            Self::Device(err) => err.fmt(f),
+            Self::Resource(err) => err.fmt(f),
+            Self::Shader(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Init(err) => Some(err),
+            Self::Device(err) => Some(err),
+            Self::Resource(err) => Some(err),
+            Self::Shader(err) => Some(err),
+        }
+    }
+}
+
+/// Creating an [`Instance`](crate::Instance) failed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum InitError {
+    NoAdapter,
+    /// Adapters exist, but none matches the
+    /// [`InstanceDescriptor`](crate::InstanceDescriptor).
+    NoMatchingAdapter {
+        filter: Option<String>,
+        force_fallback_adapter: bool,
+        available: Vec<wgpu::AdapterInfo>,
+    },
+    /// The adapter doesn't support the requested features or limits.
+    RequestDevice(wgpu::RequestDeviceError),
+}
+
+impl fmt::Display for InitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoAdapter => write!(f, "no suitable adapter found"),
+            Self::NoMatchingAdapter {
+                filter,
+                force_fallback_adapter,
+                available,
+            } => {
+                if *force_fallback_adapter {
+                    write!(f, "no fallback adapter")?;
+                } else {
+                    write!(f, "no adapter")?;
+                }
+
+                if let Some(filter) = filter {
+                    write!(f, " named like `{}`", filter)?;
+                }
+
+                write!(f, " found, available adapters:")?;
+
+                for info in available {
+                    write!(
+                        f,
+                        "\n  {} ({:?}, {:?})",
+                        info.name, info.backend, info.device_type
+                    )?;
+                }
+
+                Ok(())
+            }
+            Self::RequestDevice(err) => write!(f, "failed to request device: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for InitError {}
+
+/// The device failed, see [`Instance::error_scope`](crate::Instance::error_scope).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DeviceError {
+    /// The device was lost. wgpu reports this only by failing to map buffers,
+    /// so downloads fail with it.
+    Lost,
+    OutOfMemory,
+    /// wgpu rejected a call, contains its description.
+    Validation(String),
+}
+
+impl fmt::Display for DeviceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Lost => write!(f, "device lost"),
+            Self::OutOfMemory => write!(f, "device is out of memory"),
+            Self::Validation(description) => write!(f, "validation error: {}", description),
+        }
+    }
+}
+
+impl std::error::Error for DeviceError {}
+
+/// A resource can't be created or bound.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ResourceError {
+    /// `requested` exceeds the device or cache limit `limit` of `max`.
+    LimitExceeded {
+        limit: &'static str,
+        requested: u64,
+        max: u64,
+    },
+    /// No resource is registered as `name` for the binding `binding`.
+    MissingBinding {
+        binding: &'static str,
+        name: String,
+    },
+    /// The same resource is bound to `a` and `b` and written through one of them.
+    Aliasing {
+        a: &'static str,
+        b: &'static str,
+    },
+    /// Several problems building bindings from a registry.
+    Registry(MissingResource),
+    Convert(ConvertError),
+}
+
+impl fmt::Display for ResourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LimitExceeded {
+                limit,
+                requested,
+                max,
+            } => write!(f, "requested {} exceeds `{}` of {}", requested, limit, max),
+            Self::MissingBinding { binding, name } => {
+                write!(
+                    f,
+                    "`{}` (\"{}\"): no resource with this name",
+                    binding, name
+                )
+            }
+            Self::Aliasing { a, b } => {
+                write!(
+                    f,
+                    "`{}`: resource is also bound to `{}` and written to",
+                    a, b
+                )
+            }
+            Self::Registry(err) => err.fmt(f),
+            Self::Convert(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ResourceError {}
+
+/// A shader can't be used with its bindings or source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ShaderError {
+    /// The bound resources don't match what the shader expects.
+    Bindings(ValidationReport),
+    /// The source isn't valid or doesn't declare what the bindings expect.
+    Source(crate::SourceError),
+}
+
+impl fmt::Display for ShaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bindings(report) => report.fmt(f),
+            Self::Source(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ShaderError {}
+
+impl From<InitError> for Error {
+    fn from(err: InitError) -> Self {
+        Self::Init(err)
+    }
+}
+
+impl From<DeviceError> for Error {
+    fn from(err: DeviceError) -> Self {
+        Self::Device(err)
+    }
+}
+
+impl From<ResourceError> for Error {
+    fn from(err: ResourceError) -> Self {
+        Self::Resource(err)
+    }
+}
+
+impl From<ShaderError> for Error {
+    fn from(err: ShaderError) -> Self {
+        Self::Shader(err)
+    }
+}
+
+impl From<wgpu::Error> for Error {
+    fn from(err: wgpu::Error) -> Self {
+        Self::Device(match err {
+            wgpu::Error::OutOfMemoryError { .. } => DeviceError::OutOfMemory,
+            wgpu::Error::ValidationError { description, .. } => {
+                DeviceError::Validation(description)
+            }
+        })
+    }
+}
+
+impl From<wgpu::BufferAsyncError> for Error {
+    fn from(_: wgpu::BufferAsyncError) -> Self {
+        Self::Device(DeviceError::Lost)
+    }
+}
+
+impl From<CacheFull> for Error {
+    fn from(err: CacheFull) -> Self {
+        let limit = match err.kind {
+            CacheKind::ShaderModule => "max_shader_modules",
+            CacheKind::ComputePipeline => "max_compute_pipelines",
+        };
+
+        Self::Resource(ResourceError::LimitExceeded {
+            limit,
+            requested: err.max_entries as u64 + 1,
+            max: err.max_entries as u64,
+        })
+    }
+}
+
+impl From<DispatchTooLarge> for Error {
+    fn from(err: DispatchTooLarge) -> Self {
+        Self::Resource(match err {
+            DispatchTooLarge::Overflow { elements, .. } => ResourceError::LimitExceeded {
+                limit: "u32 invocation id",
+                requested: elements,
+                max: u32::MAX as u64,
+            },
+            DispatchTooLarge::AxisLimit { work_groups, .. } => ResourceError::LimitExceeded {
+                limit: "max_compute_workgroups_per_dimension",
+                requested: work_groups,
+                max: MAX_WORK_GROUPS_PER_AXIS as u64,
+            },
+        })
+    }
+}
+
+/// A single missing or aliased resource gets its own variant, anything else
+/// the full report.
+impl From<MissingResource> for Error {
+    fn from(err: MissingResource) -> Self {
+        if let [error] = err.errors.as_slice() {
+            match error.kind {
+                RegistryErrorKind::Missing => {
+                    return Self::Resource(ResourceError::MissingBinding {
+                        binding: error.field,
+                        name: error.name.clone(),
+                    });
+                }
+                RegistryErrorKind::Conflict { other } => {
+                    return Self::Resource(ResourceError::Aliasing {
+                        a: error.field,
+                        b: other,
+                    });
+                }
+                RegistryErrorKind::WrongType { .. } => {}
+            }
+        }
+
+        Self::Resource(ResourceError::Registry(err))
+    }
+}
+
+impl From<ConvertError> for Error {
+    fn from(err: ConvertError) -> Self {
+        Self::Resource(ResourceError::Convert(err))
+    }
+}
+
+impl From<ValidationReport> for Error {
+    fn from(report: ValidationReport) -> Self {
+        Self::Shader(ShaderError::Bindings(report))
+    }
+}
+
+impl From<crate::SourceError> for Error {
+    fn from(err: crate::SourceError) -> Self {
+        Self::Shader(ShaderError::Source(err))
+    }
+}
@@ -0,0 +1,43 @@
+//! Panics injected at the points where [`BufferVec`](crate::BufferVec)
+//! implementations may unwind, for testing panic safety.
+//!
+//! Generated implementations call [`fault_point`] at each [`FaultPoint`], which
+//! is a no-op unless the `fault-injection` feature is enabled and the point was
+//! armed with [`inject_panic`] on the current thread.
+
+#[cfg(feature = "fault-injection")]
+use std::cell::Cell;
+
+/// A point where a [`BufferVec`](crate::BufferVec) operation may panic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FaultPoint {
+    /// In `grow`, after the new layout was computed and before reallocating.
+    Grow,
+    /// In `push`, after growing and before the item is written.
+    Push,
+}
+
+#[cfg(feature = "fault-injection")]
+thread_local! {
+    static ARMED: Cell<Option<FaultPoint>> = const { Cell::new(None) };
+}
+
+/// Makes the next [`fault_point`] at `point` on the current thread panic.
+#[cfg(feature = "fault-injection")]
+#[inline]
+pub fn inject_panic(point: FaultPoint) {
+    ARMED.with(|armed| armed.set(Some(point)));
+}
+
+/// Panics if `point` was armed with [`inject_panic`], disarming it.
+#[inline(always)]
+pub fn fault_point(point: FaultPoint) {
+    #[cfg(feature = "fault-injection")]
+    if ARMED.with(|armed| armed.get() == Some(point)) {
+        ARMED.with(|armed| armed.set(None));
+        panic!("injected panic at {:?}", point);
+    }
+
+    #[cfg(not(feature = "fault-injection"))]
+    let _ = point;
+}
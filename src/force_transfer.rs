@@ -0,0 +1,93 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// Direction of a [`ForcedTransfer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TransferDirection {
+    Upload,
+    Download,
+}
+
+/// A transfer dirty tracking would have skipped, made because
+/// [`Instance::set_force_upload`](crate::Instance::set_force_upload) or
+/// [`Instance::set_force_download`](crate::Instance::set_force_download) is on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForcedTransfer {
+    pub direction: TransferDirection,
+    /// The resource, named after its type, e.g. `Buffer<app::Particles>`.
+    pub label: String,
+    pub bytes: u64,
+}
+
+impl fmt::Display for ForcedTransfer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = match self.direction {
+            TransferDirection::Upload => "upload",
+            TransferDirection::Download => "download",
+        };
+
+        write!(
+            f,
+            "forced {} of {} bytes of {}",
+            direction, self.bytes, self.label
+        )
+    }
+}
+
+/// Called with every [`ForcedTransfer`], see
+/// [`Instance::set_forced_transfer_hook`](crate::Instance::set_forced_transfer_hook).
+pub type ForcedTransferHook = Arc<dyn Fn(&ForcedTransfer) + Send + Sync>;
+
+/// Debug toggles transferring every binding regardless of dirty state.
+pub(crate) struct ForceTransfers {
+    upload: AtomicBool,
+    download: AtomicBool,
+    hook: Mutex<Option<ForcedTransferHook>>,
+}
+
+impl ForceTransfers {
+    pub fn new(upload: bool, download: bool) -> Self {
+        Self {
+            upload: AtomicBool::new(upload),
+            download: AtomicBool::new(download),
+            hook: Mutex::new(None),
+        }
+    }
+
+    #[inline]
+    pub fn upload(&self) -> bool {
+        self.upload.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub fn download(&self) -> bool {
+        self.download.load(Ordering::Relaxed)
+    }
+
+    pub fn set_upload(&self, upload: bool) {
+        self.upload.store(upload, Ordering::Relaxed);
+    }
+
+    pub fn set_download(&self, download: bool) {
+        self.download.store(download, Ordering::Relaxed);
+    }
+
+    pub fn set_hook(&self, hook: Option<ForcedTransferHook>) {
+        *self.hook.lock().unwrap() = hook;
+    }
+
+    /// Passes `transfer` to the hook, or prints it to stderr without one.
+    pub fn emit(&self, transfer: ForcedTransfer) {
+        let hook = self.hook.lock().unwrap().clone();
+
+        match hook {
+            Some(hook) => hook(&transfer),
+            None => eprintln!("shatter: {}", transfer),
+        }
+    }
+}
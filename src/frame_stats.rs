@@ -0,0 +1,140 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::Instance;
+
+/// Counters of an [`Instance`], see [`Instance::stats_frame`].
+///
+/// Fields documented as "since the last frame" count activity between two
+/// calls to [`Instance::stats_frame`], every other field is the current value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StatsFrame {
+    pub live_buffers: u64,
+    pub live_textures: u64,
+    pub live_bind_groups: u64,
+    /// Compute and render pipelines.
+    pub live_pipelines: u64,
+    /// Device memory of the buffers and textures created by shatter.
+    pub resident_bytes: u64,
+    /// Bytes uploaded since the last frame.
+    pub uploaded_bytes: u64,
+    /// Bytes downloaded since the last frame.
+    pub downloaded_bytes: u64,
+    /// Dispatches recorded since the last frame.
+    pub dispatches: u64,
+    /// Command buffers submitted to the queue since the last frame.
+    pub submissions: u64,
+    /// Gpu buffers of [`Buffer`](crate::Buffer)s recreated to fit their data
+    /// since the last frame, see [`GrowthPolicy`](crate::GrowthPolicy).
+    pub buffer_resizes: u64,
+    /// Lookups of bind groups, layouts, shader modules and pipelines served
+    /// from the caches since the last frame.
+    pub cache_hits: u64,
+    /// Bind groups, layouts, shader modules and pipelines created since the last frame.
+    pub cache_misses: u64,
+    /// Frames submitted to a [`FrameReadbackQueue`](crate::util::FrameReadbackQueue)
+    /// and not yet received.
+    pub frames_in_flight: u64,
+}
+
+impl StatsFrame {
+    /// Fraction of cache lookups that were hits, `None` without lookups.
+    #[inline]
+    pub fn cache_hit_rate(&self) -> Option<f64> {
+        let lookups = self.cache_hits + self.cache_misses;
+
+        if lookups == 0 {
+            return None;
+        }
+
+        Some(self.cache_hits as f64 / lookups as f64)
+    }
+}
+
+/// Counters maintained on the hot paths, relaxed since they're only ever
+/// read for display.
+#[derive(Default)]
+pub(crate) struct StatsCounters {
+    uploaded_bytes: AtomicU64,
+    downloaded_bytes: AtomicU64,
+    dispatches: AtomicU64,
+    submissions: AtomicU64,
+    buffer_resizes: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    frames_in_flight: AtomicU64,
+}
+
+impl StatsCounters {
+    #[inline]
+    pub fn uploaded(&self, bytes: u64) {
+        self.uploaded_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn downloaded(&self, bytes: u64) {
+        self.downloaded_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn dispatched(&self, dispatches: u64) {
+        self.dispatches.fetch_add(dispatches, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn submitted(&self) {
+        self.submissions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn buffer_resized(&self) {
+        self.buffer_resizes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn cache_miss(&self) {
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn frame_submitted(&self) {
+        self.frames_in_flight.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn frames_received(&self, frames: u64) {
+        self.frames_in_flight.fetch_sub(frames, Ordering::Relaxed);
+    }
+}
+
+impl Instance {
+    /// Collects the current [`StatsFrame`], e.g. to draw a debug overlay
+    /// every frame, see `overlay::StatsOverlay` with the `overlay` feature.
+    ///
+    /// Only reads and resets counters, so it's cheap and never blocks. The
+    /// counters since the last frame are shared, calling this from several
+    /// places splits them between the callers.
+    pub fn stats_frame(&self) -> StatsFrame {
+        let counters = &self.stats;
+
+        StatsFrame {
+            live_buffers: self.buffers.live() as u64,
+            live_textures: self.textures.live() as u64,
+            live_bind_groups: self.bind_groups.live() as u64,
+            live_pipelines: (self.compute_pipelines.live() + self.render_pipelines.live()) as u64,
+            resident_bytes: self.buffers.resident_bytes() + self.textures.resident_bytes(),
+            uploaded_bytes: counters.uploaded_bytes.swap(0, Ordering::Relaxed),
+            downloaded_bytes: counters.downloaded_bytes.swap(0, Ordering::Relaxed),
+            dispatches: counters.dispatches.swap(0, Ordering::Relaxed),
+            submissions: counters.submissions.swap(0, Ordering::Relaxed),
+            buffer_resizes: counters.buffer_resizes.swap(0, Ordering::Relaxed),
+            cache_hits: counters.cache_hits.swap(0, Ordering::Relaxed),
+            cache_misses: counters.cache_misses.swap(0, Ordering::Relaxed),
+            frames_in_flight: counters.frames_in_flight.load(Ordering::Relaxed),
+        }
+    }
+}
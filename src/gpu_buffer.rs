@@ -0,0 +1,170 @@
+use std::{
+    marker::PhantomData,
+    num::NonZeroU64,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    Binding, BindingMut, BindingResource, Buffer, BufferBinding, BufferData, BufferId, BufferVec,
+    Error, Instance, ResourceInfo, ResourceKind, TransferReport, BUFFER_USAGES,
+};
+
+/// A buffer that only exists on the GPU.
+///
+/// Useful for intermediate results passed between dispatches, since it has no
+/// CPU allocation and is never uploaded or downloaded. Use [`GpuBuffer::to_cpu`]
+/// to read the data on the CPU.
+///
+/// # Panics
+/// Dereferencing a [`GpuBuffer`] always panics.
+pub struct GpuBuffer<T: BufferData + ?Sized> {
+    id: BufferId,
+    size: u64,
+    buffer_size: u64,
+    instance: &'static Instance,
+    marker: PhantomData<T>,
+}
+
+impl<T: BufferData + ?Sized> Binding<T> for GpuBuffer<T> {
+    fn binding_resource(&self) -> BindingResource {
+        // bound with the size of the data, the gpu buffer may be larger
+        BindingResource::Buffer(BufferBinding {
+            buffer: self.id(),
+            offset: 0,
+            size: NonZeroU64::new(self.size.max(4)),
+        })
+    }
+
+    fn resource_info(&self) -> ResourceInfo {
+        ResourceInfo::Buffer {
+            id: self.id(),
+            size: self.size.max(4),
+            usage: BUFFER_USAGES,
+        }
+    }
+
+    fn prepare(&self, report: &mut TransferReport) {
+        report.buffers_touched += 1;
+    }
+
+    fn read(&self, report: &mut TransferReport) {
+        report.buffers_touched += 1;
+    }
+
+    fn data_size(&self) -> Option<u64> {
+        Some(self.size)
+    }
+}
+
+impl<T: BufferData + ?Sized> BindingMut<T> for GpuBuffer<T> {
+    fn write(&mut self) {}
+}
+
+impl<T: BufferData + ?Sized> Default for GpuBuffer<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: BufferData + ?Sized> Deref for GpuBuffer<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        panic!("GpuBuffer has no CPU side, use `GpuBuffer::to_cpu` to read it");
+    }
+}
+
+impl<T: BufferData + ?Sized> DerefMut for GpuBuffer<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        panic!("GpuBuffer has no CPU side, use `GpuBuffer::to_cpu` to write to it");
+    }
+}
+
+impl<T: BufferData + ?Sized> GpuBuffer<T> {
+    /// Creates a buffer the size of `T` in its initial state.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_size(T::size(&T::init()) as u64)
+    }
+
+    /// Creates a zeroed buffer of `size` bytes.
+    #[inline]
+    pub fn with_size(size: u64) -> Self {
+        Self::try_with_size(size).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Like [`Self::with_size`], failing with
+    /// [`DeviceError::OutOfMemory`](crate::DeviceError::OutOfMemory) if the
+    /// [OOM handler](Instance::set_oom_handler) gives up instead of panicking.
+    pub fn try_with_size(size: u64) -> Result<Self, Error> {
+        let instance = Instance::global();
+        let buffer_size = size.max(4);
+
+        let buffer = instance.create_resource(
+            ResourceKind::Buffer,
+            "shatter_gpu_buffer",
+            buffer_size,
+            || {
+                instance.device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("shatter_gpu_buffer"),
+                    size: buffer_size,
+                    usage: BUFFER_USAGES,
+                    mapped_at_creation: false,
+                })
+            },
+        )?;
+
+        let id = instance.buffers.stable_id("shatter_gpu_buffer");
+        instance
+            .buffers
+            .insert_sized(id.clone(), buffer, buffer_size);
+
+        Ok(Self::from_raw(instance, id, size, buffer_size))
+    }
+
+    #[inline]
+    pub(crate) fn from_raw(
+        instance: &'static Instance,
+        id: BufferId,
+        size: u64,
+        buffer_size: u64,
+    ) -> Self {
+        Self {
+            id,
+            size,
+            buffer_size,
+            instance,
+            marker: PhantomData,
+        }
+    }
+
+    /// Size of the data in bytes.
+    #[inline]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    #[inline]
+    pub fn id(&self) -> BufferId {
+        self.id.clone()
+    }
+
+    /// Allocates the CPU side of the buffer and downloads the data.
+    #[inline]
+    pub fn to_cpu(self) -> Buffer<T> {
+        let buffer = Buffer::<T>::from_gpu(self.instance, self.id, self.size, self.buffer_size);
+        buffer.download();
+        buffer
+    }
+}
+
+impl<T: BufferVec + ?Sized> GpuBuffer<T> {
+    /// Creates a zeroed buffer holding `len` items.
+    #[inline]
+    pub fn with_len(len: usize) -> Self {
+        let size = T::size(&T::init()) + len * std::mem::size_of::<T::Item>();
+
+        Self::with_size(size as u64)
+    }
+}
@@ -0,0 +1,194 @@
+use std::{
+    any::Any,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
+
+use crate::{Buffer, Instance, Texture};
+
+pub type BufferHandle<T> = Handle<Buffer<T>>;
+pub type TextureHandle<Format, Dimension, const MULTISAMPLED: bool> =
+    Handle<Texture<Format, Dimension, MULTISAMPLED>>;
+
+/// A cheap, clonable handle to a resource registered in the [`Instance`].
+///
+/// The resource is kept alive until every handle has been dropped or it is
+/// explicitly destroyed with [`Instance::destroy`], after which resolving
+/// any remaining handle returns `None`.
+pub struct Handle<T> {
+    index: usize,
+    generation: u64,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Send + 'static> Handle<T> {
+    /// Resolves the handle through the global instance.
+    #[inline]
+    pub fn resolve(&self) -> Option<Arc<Mutex<T>>> {
+        Instance::global().resolve(self)
+    }
+}
+
+impl<T> Handle<T> {
+    #[inline]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Instance::global()
+            .handles
+            .retain(self.index, self.generation);
+
+        Self {
+            index: self.index,
+            generation: self.generation,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Drop for Handle<T> {
+    fn drop(&mut self) {
+        Instance::global()
+            .handles
+            .release(self.index, self.generation);
+    }
+}
+
+impl<T> std::fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Handle({}v{})", self.index, self.generation)
+    }
+}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+struct Slot {
+    generation: u64,
+    handles: usize,
+    resource: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+#[derive(Default)]
+struct Slots {
+    slots: Vec<Slot>,
+    free: Vec<usize>,
+}
+
+impl Slots {
+    /// Frees the slot at `index`, bumping its generation so old handles no longer match.
+    fn free(&mut self, index: usize) -> Option<Arc<dyn Any + Send + Sync>> {
+        let slot = &mut self.slots[index];
+
+        slot.generation += 1;
+        slot.handles = 0;
+        self.free.push(index);
+
+        slot.resource.take()
+    }
+}
+
+/// Registry of resources shared through [`Handle`]s.
+#[derive(Default)]
+pub struct HandleRegistry {
+    slots: Mutex<Slots>,
+}
+
+impl HandleRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<T: Send + 'static>(&self, resource: T) -> Handle<T> {
+        let resource: Arc<dyn Any + Send + Sync> = Arc::new(Mutex::new(resource));
+
+        let mut slots = self.slots.lock().unwrap();
+
+        let index = match slots.free.pop() {
+            Some(index) => {
+                let slot = &mut slots.slots[index];
+                slot.handles = 1;
+                slot.resource = Some(resource);
+                index
+            }
+            None => {
+                slots.slots.push(Slot {
+                    generation: 0,
+                    handles: 1,
+                    resource: Some(resource),
+                });
+                slots.slots.len() - 1
+            }
+        };
+
+        Handle {
+            index,
+            generation: slots.slots[index].generation,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn resolve<T: Send + 'static>(&self, handle: &Handle<T>) -> Option<Arc<Mutex<T>>> {
+        let slots = self.slots.lock().unwrap();
+        let slot = slots.slots.get(handle.index)?;
+
+        if slot.generation != handle.generation {
+            return None;
+        }
+
+        slot.resource.clone()?.downcast().ok()
+    }
+
+    /// Destroys the resource `handle` points to, returns `false` if it was already destroyed.
+    pub fn destroy<T>(&self, handle: &Handle<T>) -> bool {
+        let mut slots = self.slots.lock().unwrap();
+
+        if slots.slots[handle.index].generation != handle.generation {
+            return false;
+        }
+
+        let resource = slots.free(handle.index);
+
+        // drop the resource after releasing the lock
+        drop(slots);
+        drop(resource);
+
+        true
+    }
+
+    fn retain(&self, index: usize, generation: u64) {
+        let mut slots = self.slots.lock().unwrap();
+        let slot = &mut slots.slots[index];
+
+        if slot.generation == generation {
+            slot.handles += 1;
+        }
+    }
+
+    fn release(&self, index: usize, generation: u64) {
+        let mut slots = self.slots.lock().unwrap();
+        let slot = &mut slots.slots[index];
+
+        if slot.generation != generation {
+            return;
+        }
+
+        slot.handles -= 1;
+
+        if slot.handles == 0 {
+            let resource = slots.free(index);
+
+            drop(slots);
+            drop(resource);
+        }
+    }
+}
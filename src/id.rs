@@ -1,14 +1,15 @@
 use std::{
-    hash::Hash,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
     marker::PhantomData,
     ops::{Deref, DerefMut},
     sync::{
-        atomic::{AtomicU32, AtomicU64, Ordering},
-        Arc,
+        atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
     },
 };
 
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
 
 pub type ShaderModuleId = Id<wgpu::ShaderModule>;
 pub type BindGroupLayoutDescriptorId = Id<crate::BindGroupLayoutDescriptor>;
@@ -18,13 +19,29 @@ pub type BindGroupId = Id<wgpu::BindGroup>;
 pub type BufferId = Id<wgpu::Buffer>;
 pub type SamplerId = Id<wgpu::Sampler>;
 pub type TextureId = Id<wgpu::Texture>;
+pub type TextureViewId = Id<wgpu::TextureView>;
 pub type PipelineLayoutDescriptorId = Id<crate::PipelineLayoutDescriptor>;
 pub type PipelineLayoutId = Id<wgpu::PipelineLayout>;
 pub type ComputePipelineDescriptorId = Id<crate::ComputePipelineDescriptor>;
 pub type ComputePipelineId = Id<wgpu::ComputePipeline>;
 pub type RenderPipelineId = Id<wgpu::RenderPipeline>;
 
-pub struct Id<T>(u64, Arc<AtomicU32>, PhantomData<fn() -> T>);
+/// Bits of a raw id below the id of the [`Instance`](crate::Instance) it
+/// was handed out by, see [`Id::instance`].
+pub const INSTANCE_ID_SHIFT: u32 = 48;
+
+/// Raw ids whose reference count dropped to zero, drained by [`IdMap::clean`].
+type DropQueue = Arc<Mutex<Vec<u64>>>;
+
+/// Shared by an id and its clones.
+#[derive(Default)]
+struct RefCount {
+    count: AtomicU32,
+    // the queue of the map the id was handed out by, if it queues drops
+    dropped: Option<DropQueue>,
+}
+
+pub struct Id<T>(u64, Arc<RefCount>, PhantomData<fn() -> T>);
 
 impl<T> Id<T> {
     #[allow(dead_code)]
@@ -32,24 +49,51 @@ impl<T> Id<T> {
         Self(0, Arc::new(Default::default()), PhantomData)
     }
 
+    fn with_queue(id: u64, dropped: Option<DropQueue>) -> Self {
+        let ref_count = RefCount {
+            count: AtomicU32::new(0),
+            dropped,
+        };
+
+        Self(id, Arc::new(ref_count), PhantomData)
+    }
+
     pub(crate) fn ref_count(&self) -> u32 {
-        self.1.load(Ordering::Acquire)
+        self.1.count.load(Ordering::Acquire)
+    }
+
+    /// Gets the raw id, unique among ids of the same map.
+    #[inline]
+    pub fn raw(&self) -> u64 {
+        self.0
+    }
+
+    /// Gets the [`Instance::id`](crate::Instance::id) of the instance whose
+    /// map handed out the id.
+    #[inline]
+    pub fn instance(&self) -> u64 {
+        self.0 >> INSTANCE_ID_SHIFT
     }
 
     pub fn clone_untracked(&self) -> Self {
-        Self(self.0, Arc::new(AtomicU32::new(0)), PhantomData)
+        Self::with_queue(self.0, None)
     }
 }
 
 impl<T> Drop for Id<T> {
     fn drop(&mut self) {
-        self.1.fetch_sub(1, Ordering::AcqRel);
+        // the last id left is the key of the map, which is cleaned later
+        if self.1.count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            if let Some(ref dropped) = self.1.dropped {
+                dropped.lock().unwrap().push(self.0);
+            }
+        }
     }
 }
 
 impl<T> Clone for Id<T> {
     fn clone(&self) -> Self {
-        self.1.fetch_add(1, Ordering::AcqRel);
+        self.1.count.fetch_add(1, Ordering::AcqRel);
 
         Self(self.0, self.1.clone(), PhantomData)
     }
@@ -71,7 +115,7 @@ impl<T> Eq for Id<T> {}
 
 impl<T> PartialOrd for Id<T> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.0.partial_cmp(&other.0)
+        Some(self.cmp(other))
     }
 }
 
@@ -87,27 +131,216 @@ impl<T> Hash for Id<T> {
     }
 }
 
+/// Resources by id.
+///
+/// [`IdMap::insert`], [`IdMap::remove`] and [`IdMap::clean`] maintain the
+/// counters read by [`IdMap::live`] and [`IdMap::resident_bytes`], inserting
+/// or removing through the [`DashMap`] directly bypasses them.
 pub struct IdMap<T> {
     map: DashMap<Id<T>, T>,
+    // only when created `with_drop_queue`
+    dropped: Option<DropQueue>,
     next_id: AtomicU64,
+    // set in the high bits of every id, see `with_instance`
+    instance: u64,
+    // every id handed out by `stable_id`, only when ids are deterministic
+    stable_ids: Option<DashSet<u64>>,
+    live: AtomicUsize,
+    // sizes of the entries inserted by `insert_sized`
+    sizes: DashMap<u64, u64>,
+    resident_bytes: AtomicU64,
 }
 
 impl<T> IdMap<T> {
     pub fn new() -> Self {
         Self {
             map: DashMap::new(),
+            dropped: None,
             next_id: AtomicU64::new(0),
+            instance: 0,
+            stable_ids: None,
+            live: AtomicUsize::new(0),
+            sizes: DashMap::new(),
+            resident_bytes: AtomicU64::new(0),
+        }
+    }
+
+    /// Creates a map where [`IdMap::stable_id`] derives ids from its key.
+    pub fn deterministic() -> Self {
+        Self {
+            stable_ids: Some(DashSet::new()),
+            ..Self::new()
         }
     }
 
+    /// Queues the ids handed out by the map once only the map references
+    /// them, so [`IdMap::clean`] removes their entries.
+    ///
+    /// Maps whose entries live as long as a cache entry, e.g. bind groups,
+    /// are never cleaned and don't queue drops.
+    pub fn with_drop_queue(mut self) -> Self {
+        self.dropped = Some(Default::default());
+        self
+    }
+
+    /// Hands out ids tagged with the id of an [`Instance`](crate::Instance),
+    /// so ids of different instances never collide, see [`Id::instance`].
+    pub fn with_instance(mut self, instance: u64) -> Self {
+        self.instance = instance;
+        *self.next_id.get_mut() = instance << INSTANCE_ID_SHIFT;
+        self
+    }
+
+    #[inline]
+    pub fn is_deterministic(&self) -> bool {
+        self.stable_ids.is_some()
+    }
+
     pub fn next_id(&self) -> Id<T> {
         let id = self.next_id.fetch_add(1, Ordering::AcqRel);
 
-        Id(id, Arc::new(AtomicU32::new(0)), PhantomData)
+        Id::with_queue(id, self.dropped.clone())
+    }
+
+    /// Gets a new id for a resource described by `key`, e.g. a label or a descriptor.
+    ///
+    /// In a [deterministic](IdMap::deterministic) map the id is a hash of `key`,
+    /// so the same program gets the same ids run to run. Keys hashing to an id
+    /// already handed out, including repeated keys, get a sequence number
+    /// appended until the id is unique. Ids are never reused. Otherwise this
+    /// is [`IdMap::next_id`].
+    pub fn stable_id<K: Hash + ?Sized>(&self, key: &K) -> Id<T> {
+        let stable_ids = match self.stable_ids {
+            Some(ref stable_ids) => stable_ids,
+            None => return self.next_id(),
+        };
+
+        // DefaultHasher::new uses fixed keys, unlike RandomState
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let base = hasher.finish();
+
+        let mut id = self.tag(base);
+        let mut sequence = 0u64;
+
+        while !stable_ids.insert(id) {
+            sequence += 1;
+
+            let mut hasher = DefaultHasher::new();
+            (base, sequence).hash(&mut hasher);
+            id = self.tag(hasher.finish());
+        }
+
+        Id::with_queue(id, self.dropped.clone())
+    }
+
+    /// Replaces the high bits of a hashed id with the instance.
+    #[inline]
+    fn tag(&self, hash: u64) -> u64 {
+        let mask = (1 << INSTANCE_ID_SHIFT) - 1;
+
+        (hash & mask) | (self.instance << INSTANCE_ID_SHIFT)
     }
 
-    pub fn clean(&self) {
-        self.map.retain(|id, _| id.ref_count() > 0)
+    /// Inserts `value`, returning the value previously inserted as `id`.
+    pub fn insert(&self, id: Id<T>, value: T) -> Option<T> {
+        let previous = self.map.insert(id, value);
+
+        if previous.is_none() {
+            self.live.fetch_add(1, Ordering::Relaxed);
+        }
+
+        previous
+    }
+
+    /// Inserts `value` occupying `bytes` of device memory, counted by
+    /// [`IdMap::resident_bytes`] until it's removed.
+    pub fn insert_sized(&self, id: Id<T>, value: T, bytes: u64) -> Option<T> {
+        self.resident_bytes.fetch_add(bytes, Ordering::Relaxed);
+
+        if let Some(previous) = self.sizes.insert(id.raw(), bytes) {
+            self.resident_bytes.fetch_sub(previous, Ordering::Relaxed);
+        }
+
+        self.insert(id, value)
+    }
+
+    /// Gets the size `id` was inserted with by [`IdMap::insert_sized`].
+    #[inline]
+    pub fn size_of(&self, id: &Id<T>) -> Option<u64> {
+        self.sizes.get(&id.raw()).map(|size| *size)
+    }
+
+    pub fn remove(&self, id: &Id<T>) -> Option<(Id<T>, T)> {
+        let removed = self.map.remove(id);
+
+        if removed.is_some() {
+            self.forget(id.raw());
+        }
+
+        removed
+    }
+
+    /// Removes the entries no longer referenced, returning their raw ids.
+    ///
+    /// Only the ids dropped since the last clean are visited, so the cost is
+    /// proportional to the drops rather than the entries. Each entry is
+    /// checked again before removal, as the map's keys can be cloned through
+    /// the [`DashMap`] after the drop was queued. Does nothing unless the map
+    /// was created [`with_drop_queue`](IdMap::with_drop_queue).
+    pub fn clean(&self) -> Vec<u64> {
+        let dropped = match self.dropped {
+            Some(ref dropped) => std::mem::take(&mut *dropped.lock().unwrap()),
+            None => return Vec::new(),
+        };
+
+        dropped
+            .into_iter()
+            .filter(|&raw| {
+                let key = Id::with_queue(raw, None);
+                let removed = self.map.remove_if(&key, |id, _| id.ref_count() == 0);
+
+                if removed.is_some() {
+                    self.forget(raw);
+                }
+
+                removed.is_some()
+            })
+            .collect()
+    }
+
+    /// Number of dropped ids waiting for [`IdMap::clean`].
+    #[inline]
+    pub fn pending_drops(&self) -> usize {
+        self.dropped
+            .as_ref()
+            .map_or(0, |dropped| dropped.lock().unwrap().len())
+    }
+
+    fn forget(&self, id: u64) {
+        self.live.fetch_sub(1, Ordering::Relaxed);
+
+        if let Some((_, bytes)) = self.sizes.remove(&id) {
+            self.resident_bytes.fetch_sub(bytes, Ordering::Relaxed);
+        }
+    }
+
+    /// Number of entries, without locking the map unlike [`DashMap::len`].
+    #[inline]
+    pub fn live(&self) -> usize {
+        self.live.load(Ordering::Relaxed)
+    }
+
+    /// Device memory occupied by the entries inserted with [`IdMap::insert_sized`].
+    #[inline]
+    pub fn resident_bytes(&self) -> u64 {
+        self.resident_bytes.load(Ordering::Relaxed)
+    }
+}
+
+impl<T> Default for IdMap<T> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
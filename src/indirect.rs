@@ -0,0 +1,125 @@
+use std::num::NonZeroU64;
+
+use crate::{
+    BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor, BindingResource, BufferBinding,
+    BufferId, ComputePipelineDescriptor, Dispatch, Instance, PipelineLayoutDescriptor,
+};
+
+/// Kernel converting a counter into [`DispatchIndirectArgs`].
+pub const DISPATCH_ARGS_SOURCE: &str = include_str!("dispatch_args.wgsl");
+
+/// Usages of the buffer holding [`DispatchIndirectArgs`].
+pub const DISPATCH_ARGS_USAGES: wgpu::BufferUsages =
+    wgpu::BufferUsages::STORAGE.union(wgpu::BufferUsages::INDIRECT);
+
+/// Arguments of an indirect dispatch, as laid out on the GPU.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DispatchIndirectArgs {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+fn storage_entry(binding: u32, read_only: bool) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Storage { read_only },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+fn buffer_entry(binding: u32, buffer: BufferId, size: Option<u64>) -> BindGroupEntry {
+    BindGroupEntry {
+        binding,
+        resource: BindingResource::Buffer(BufferBinding {
+            buffer,
+            offset: 0,
+            size: size.and_then(NonZeroU64::new),
+        }),
+    }
+}
+
+/// Records the dispatch args kernel into `encoder`, converting the `u32` at
+/// `field_offset` in `counter` into a dispatch of `work_group_size` sized groups,
+/// clamped to `max_dispatch`.
+///
+/// Returns the buffer holding the [`DispatchIndirectArgs`].
+pub(crate) fn record_dispatch_args(
+    instance: &Instance,
+    encoder: &mut wgpu::CommandEncoder,
+    counter: BufferId,
+    field_offset: u64,
+    work_group_size: u32,
+    max_dispatch: Dispatch,
+) -> BufferId {
+    let params = [
+        (field_offset / 4) as u32,
+        work_group_size.max(1),
+        max_dispatch.x,
+        max_dispatch.y,
+        max_dispatch.z,
+    ];
+
+    let args = instance.dispatch_args_buffer();
+    let params = instance.dispatch_args_params(params);
+
+    let layout = instance.get_bind_group_layout(BindGroupLayoutDescriptor::new(vec![
+        storage_entry(0, true),
+        wgpu::BindGroupLayoutEntry {
+            binding: 1,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        },
+        storage_entry(2, false),
+    ]));
+
+    let bind_group = instance.get_bind_group(BindGroupDescriptor::new(
+        layout.clone(),
+        vec![
+            buffer_entry(0, counter, None),
+            buffer_entry(1, params, None),
+            buffer_entry(
+                2,
+                args.clone(),
+                Some(std::mem::size_of::<DispatchIndirectArgs>() as u64),
+            ),
+        ],
+    ));
+
+    let pipeline_layout = instance.get_pipeline_layout(PipelineLayoutDescriptor {
+        bind_group_layouts: vec![layout],
+        push_constant_ranges: Vec::new(),
+    });
+
+    let pipeline = instance.get_compute_pipeline(ComputePipelineDescriptor {
+        layout: Some(pipeline_layout),
+        module: instance.get_shader_module(DISPATCH_ARGS_SOURCE),
+        entry_point: "main".into(),
+    });
+
+    let bind_group = instance.bind_groups.get(&bind_group).unwrap();
+    let pipeline = instance.compute_pipelines.get(&pipeline).unwrap();
+
+    let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+        label: Some("shatter_compute_pass(dispatch_args)"),
+    });
+
+    compute_pass.set_pipeline(&pipeline);
+    compute_pass.set_bind_group(0, &bind_group, &[]);
+    compute_pass.dispatch(1, 1, 1);
+
+    drop(compute_pass);
+
+    args
+}
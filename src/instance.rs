@@ -1,105 +1,664 @@
-use std::borrow::Cow;
+use std::{
+    any::Any,
+    borrow::Cow,
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU32, AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
+};
 
 use dashmap::{mapref::one::Ref, DashMap};
 use once_cell::sync::OnceCell;
-use wgpu::Backends;
+use smallvec::SmallVec;
+use wgpu::{util::DeviceExt, Backends};
 
 use crate::{
-    BindGroupId, BindGroupLayoutId, BufferId, ComputePipelineId, IdMap, PipelineLayoutId,
-    SamplerId, ShaderModuleId,
+    BindGroupId, BindGroupLayoutId, BufferId, CacheChurn, CacheFull, CacheKind, CacheLimits,
+    CacheWatch, ComputePipelineId, DeviceError, Error, ForceTransfers, ForcedTransfer, Handle,
+    HandleRegistry, IdMap, InitError, LaunchRing, ManySpecializations, OomRecovery,
+    PipelineLayoutId, RenderPipelineId, ResourceError, SamplerId, ShaderModuleId, StagingBuffer,
+    StagingPool, StatsCounters, SubmissionLog, SubmissionRecord, TextureId, TextureViewId,
+    TimestampPool, WakerRegistry,
 };
 
 pub static GLOBAL_INSTANCE: OnceCell<Instance> = OnceCell::new();
 
-#[derive(Default)]
+/// Id of the next instance created, see [`Instance::id`].
+static NEXT_INSTANCE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Submissions between automatic runs of [`Instance::clean`].
+pub const CLEAN_INTERVAL: u32 = 16;
+
 pub struct InstanceDescriptor {
+    /// Backends searched for an adapter.
+    pub backends: Backends,
+    /// Picks between a low power and a high performance adapter, e.g. an
+    /// integrated and a discrete GPU. Ignored with an
+    /// [`adapter_name_filter`](Self::adapter_name_filter).
+    pub power_preference: wgpu::PowerPreference,
+    /// Only considers the fallback adapter, e.g. a software renderer on CI.
+    ///
+    /// Defaults to true if the `SHATTER_FORCE_FALLBACK_ADAPTER` environment variable is set.
+    pub force_fallback_adapter: bool,
+    /// Picks the first adapter of [`backends`](Self::backends) whose name
+    /// contains the filter, see [`Instance::adapters`].
+    ///
+    /// Defaults to the `SHATTER_ADAPTER_NAME` environment variable.
+    pub adapter_name_filter: Option<String>,
     pub features: wgpu::Features,
+    /// Features requested in addition to [`features`](Self::features) if the
+    /// adapter supports them, e.g. [`wgpu::Features::PIPELINE_STATISTICS_QUERY`]
+    /// for [`ComputeShaderBuilder::with_statistics`](crate::ComputeShaderBuilder::with_statistics).
+    pub optional_features: wgpu::Features,
     pub limits: wgpu::Limits,
+    /// Derives resource ids from labels, sources and descriptors instead of
+    /// allocation order, so the same program gets the same ids run to run,
+    /// see [`IdMap::stable_id`].
+    ///
+    /// Defaults to true if the `SHATTER_DETERMINISTIC_IDS` environment variable is set.
+    pub deterministic_ids: bool,
+    /// See [`Instance::set_cache_limits`].
+    pub cache_limits: CacheLimits,
+    /// See [`Instance::set_force_upload`].
+    ///
+    /// Defaults to true if the `SHATTER_FORCE_UPLOAD` environment variable is set.
+    pub force_upload: bool,
+    /// See [`Instance::set_force_download`].
+    ///
+    /// Defaults to true if the `SHATTER_FORCE_DOWNLOAD` environment variable is set.
+    pub force_download: bool,
+    /// See [`Instance::set_memory_budget`].
+    pub memory_budget: Option<u64>,
+}
+
+impl Default for InstanceDescriptor {
+    fn default() -> Self {
+        Self {
+            backends: Backends::all(),
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            force_fallback_adapter: std::env::var_os("SHATTER_FORCE_FALLBACK_ADAPTER").is_some(),
+            adapter_name_filter: std::env::var("SHATTER_ADAPTER_NAME").ok(),
+            features: Default::default(),
+            optional_features: Default::default(),
+            limits: Default::default(),
+            deterministic_ids: std::env::var_os("SHATTER_DETERMINISTIC_IDS").is_some(),
+            cache_limits: CacheLimits::default(),
+            force_upload: std::env::var_os("SHATTER_FORCE_UPLOAD").is_some(),
+            force_download: std::env::var_os("SHATTER_FORCE_DOWNLOAD").is_some(),
+            memory_budget: None,
+        }
+    }
+}
+
+fn id_map<T>(instance: u64, deterministic: bool) -> IdMap<T> {
+    let map = if deterministic {
+        IdMap::deterministic()
+    } else {
+        IdMap::new()
+    };
+
+    map.with_instance(instance)
 }
 
 pub struct Instance {
+    id: u64,
     pub instance: wgpu::Instance,
+    pub adapter: wgpu::Adapter,
     pub device: wgpu::Device,
     pub queue: wgpu::Queue,
     pub buffers: IdMap<wgpu::Buffer>,
     pub textures: IdMap<wgpu::Texture>,
+    pub texture_views: IdMap<wgpu::TextureView>,
     pub samplers: IdMap<wgpu::Sampler>,
     pub shader_module_sources: DashMap<Cow<'static, str>, ShaderModuleId>,
     pub shader_modules: IdMap<wgpu::ShaderModule>,
     pub bind_group_layout_descriptors: DashMap<crate::BindGroupLayoutDescriptor, BindGroupLayoutId>,
     pub bind_group_layouts: IdMap<wgpu::BindGroupLayout>,
     pub bind_group_descriptors: DashMap<crate::BindGroupDescriptor, BindGroupId>,
+    /// Bind groups by [`GroupKey`](crate::GroupKey), consulted before building descriptors.
+    pub group_keys: DashMap<crate::GroupKey, BindGroupId>,
+    /// Cached bind group descriptors by bound resource, so the groups of a
+    /// dropped resource are removed without scanning the cache.
+    pub(crate) bind_groups_by_resource:
+        DashMap<crate::ResourceKey, HashMap<crate::GroupKey, crate::BindGroupDescriptor>>,
     pub bind_groups: IdMap<wgpu::BindGroup>,
     pub pipeline_layout_descriptors: DashMap<crate::PipelineLayoutDescriptor, PipelineLayoutId>,
     pub pipeline_layouts: IdMap<wgpu::PipelineLayout>,
     pub compute_pipeline_descriptors: DashMap<crate::ComputePipelineDescriptor, ComputePipelineId>,
+    pub render_pipeline_descriptors: DashMap<crate::RenderPipelineDescriptor, RenderPipelineId>,
     pub render_pipelines: IdMap<wgpu::RenderPipeline>,
     pub compute_pipelines: IdMap<wgpu::ComputePipeline>,
+    pub handles: HandleRegistry,
+    placeholder_buffer: Mutex<Option<(BufferId, u64)>>,
+    dispatch_args_buffer: Mutex<Option<BufferId>>,
+    pub(crate) dispatch_args_params: DashMap<[u32; 5], BufferId>,
+    pub(crate) convert_intermediates: DashMap<(u32, u32), TextureId>,
+    pub(crate) launch_ring: OnceCell<LaunchRing>,
+    /// Pool of [`SharedBuffer`](crate::SharedBuffer)s by content hash.
+    pub(crate) shared_buffers: DashMap<u64, Weak<dyn Any + Send + Sync>>,
+    pub(crate) wakers: WakerRegistry,
+    pub(crate) cache_watch: CacheWatch,
+    pub(crate) force_transfers: ForceTransfers,
+    pub(crate) oom: OomRecovery,
+    staging_buffers: StagingPool,
+    pub(crate) timestamp_queries: TimestampPool,
+    #[cfg(feature = "debug-recorder")]
+    recorder: Mutex<Option<Arc<crate::debug::Recorder>>>,
+    captured_error: Arc<Mutex<Option<CapturedError>>>,
+    submissions: Arc<SubmissionLog>,
+    pub(crate) stats: StatsCounters,
+    submissions_since_clean: AtomicU32,
+}
+
+/// State of [`Instance::error_scope`], shared with the uncaptured error handler.
+enum CapturedError {
+    Capturing,
+    Captured(Error),
+}
+
+/// Captures `err` in the running [`Instance::error_scope`], or panics outside
+/// of one. Validation errors and panics list the recent submissions.
+fn handle_uncaptured_error(
+    captured: &Mutex<Option<CapturedError>>,
+    submissions: &SubmissionLog,
+    err: wgpu::Error,
+) {
+    let recent = submissions.describe_recent();
+    let mut captured = captured.lock().unwrap();
+
+    match *captured {
+        Some(CapturedError::Capturing) => {
+            let err = match Error::from(err) {
+                Error::Device(DeviceError::Validation(description)) if !recent.is_empty() => {
+                    DeviceError::Validation(format!("{}\n{}", description, recent)).into()
+                }
+                err => err,
+            };
+
+            *captured = Some(CapturedError::Captured(err));
+            return;
+        }
+        Some(CapturedError::Captured(_)) => return,
+        None => {}
+    }
+
+    // unlocked first, so the mutex isn't poisoned if the panic is caught
+    drop(captured);
+
+    if recent.is_empty() {
+        panic!("wgpu error: {}", err);
+    }
+
+    panic!("wgpu error: {}\n{}", err, recent);
 }
 
 impl Instance {
     pub fn global<'a>() -> &'a Self {
         GLOBAL_INSTANCE.get_or_init(|| {
-            pollster::block_on(Self::initialize(&InstanceDescriptor::default())).unwrap()
+            pollster::block_on(Self::initialize(&InstanceDescriptor::default()))
+                .unwrap_or_else(|err| panic!("{}", err))
         })
     }
 
+    /// Gets the global instance, returns `None` if no adapter is available.
+    pub fn try_global<'a>() -> Option<&'a Self> {
+        GLOBAL_INSTANCE
+            .get_or_try_init(|| {
+                pollster::block_on(Self::initialize(&InstanceDescriptor::default()))
+            })
+            .ok()
+    }
+
     pub fn init(desc: &InstanceDescriptor) {
-        GLOBAL_INSTANCE.get_or_init(|| pollster::block_on(Self::initialize(desc)).unwrap());
+        GLOBAL_INSTANCE.get_or_init(|| {
+            pollster::block_on(Self::initialize(desc)).unwrap_or_else(|err| panic!("{}", err))
+        });
     }
 
-    pub async fn initialize(desc: &InstanceDescriptor) -> anyhow::Result<Self> {
-        let instance = wgpu::Instance::new(Backends::all());
+    /// Like [`Self::init`], returning the error instead of panicking.
+    ///
+    /// If the global instance already exists it is returned as is.
+    pub fn try_init<'a>(desc: &InstanceDescriptor) -> Result<&'a Self, Error> {
+        GLOBAL_INSTANCE.get_or_try_init(|| pollster::block_on(Self::initialize(desc)))
+    }
 
-        let adapter = instance
-            .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::HighPerformance,
-                force_fallback_adapter: false,
-                compatible_surface: None,
-            })
-            .await
-            .unwrap();
+    /// Makes `instance` the global instance, e.g. one created with
+    /// [`Self::from_device`]. Returns `instance` back if the global instance
+    /// already exists.
+    pub fn set_global<'a>(instance: Self) -> Result<&'a Self, Box<Self>> {
+        GLOBAL_INSTANCE.set(instance).map_err(Box::new)?;
+
+        Ok(GLOBAL_INSTANCE.get().unwrap())
+    }
+
+    /// Creates an instance besides the global one, e.g. to run on a second
+    /// adapter. Resources are created in it with `new_in`, e.g.
+    /// [`Buffer::new_in`](crate::Buffer::new_in), and shaders dispatched
+    /// with [`ComputeShaderBuilder::instance`](crate::ComputeShaderBuilder::instance).
+    ///
+    /// Like the global instance, it lives for the rest of the program.
+    pub fn create(desc: &InstanceDescriptor) -> Result<&'static Self, Error> {
+        Ok(pollster::block_on(Self::initialize(desc))?.leak())
+    }
+
+    /// Leaks `self` so resources can refer to it, e.g. an instance created
+    /// with [`Self::from_device`], see [`Self::create`].
+    pub fn leak(self) -> &'static Self {
+        Box::leak(Box::new(self))
+    }
+
+    /// Gets the id of the instance, unique in the process. Every resource id
+    /// handed out by the instance carries it, see [`Id::instance`](crate::Id::instance).
+    #[inline]
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns true if `self` is the global instance.
+    #[inline]
+    pub fn is_global(&self) -> bool {
+        GLOBAL_INSTANCE
+            .get()
+            .is_some_and(|global| std::ptr::eq(global, self))
+    }
+
+    pub async fn initialize(desc: &InstanceDescriptor) -> Result<Self, Error> {
+        let instance = wgpu::Instance::new(desc.backends);
+
+        let adapter = match desc.adapter_name_filter {
+            Some(ref filter) => instance.enumerate_adapters(desc.backends).find(|adapter| {
+                let info = adapter.get_info();
+
+                info.name.contains(filter.as_str())
+                    && (!desc.force_fallback_adapter || info.device_type == wgpu::DeviceType::Cpu)
+            }),
+            None => {
+                instance
+                    .request_adapter(&wgpu::RequestAdapterOptions {
+                        power_preference: desc.power_preference,
+                        force_fallback_adapter: desc.force_fallback_adapter,
+                        compatible_surface: None,
+                    })
+                    .await
+            }
+        };
+
+        let adapter = match adapter {
+            Some(adapter) => adapter,
+            None => {
+                let available = Self::adapters(desc.backends);
+
+                if available.is_empty() {
+                    return Err(InitError::NoAdapter.into());
+                }
+
+                return Err(InitError::NoMatchingAdapter {
+                    filter: desc.adapter_name_filter.clone(),
+                    force_fallback_adapter: desc.force_fallback_adapter,
+                    available,
+                }
+                .into());
+            }
+        };
 
         let (device, queue) = adapter
             .request_device(
                 &wgpu::DeviceDescriptor {
                     label: Some("shatter_default_device"),
-                    features: desc.features,
+                    features: desc.features | (desc.optional_features & adapter.features()),
                     limits: desc.limits.clone(),
                 },
                 None,
             )
-            .await?;
+            .await
+            .map_err(InitError::RequestDevice)?;
 
-        Ok(Self {
+        Ok(Self::from_device(instance, adapter, device, queue, desc))
+    }
+
+    /// Lists the adapters of `backends`, e.g. to pick an
+    /// [`InstanceDescriptor::adapter_name_filter`].
+    pub fn adapters(backends: Backends) -> Vec<wgpu::AdapterInfo> {
+        wgpu::Instance::new(backends)
+            .enumerate_adapters(backends)
+            .map(|adapter| adapter.get_info())
+            .collect()
+    }
+
+    /// Wraps a device created elsewhere, e.g. to share it with code using wgpu
+    /// directly. [`InstanceDescriptor::features`] and
+    /// [`InstanceDescriptor::limits`] are ignored, the device already has its own.
+    ///
+    /// Installs an uncaptured error handler on `device`, see [`Self::error_scope`].
+    pub fn from_device(
+        instance: wgpu::Instance,
+        adapter: wgpu::Adapter,
+        device: wgpu::Device,
+        queue: wgpu::Queue,
+        desc: &InstanceDescriptor,
+    ) -> Self {
+        // wgpu 0.11 has no error scopes, errors are captured by the handler instead
+        let captured_error = Arc::new(Mutex::new(None));
+        let submissions = Arc::new(SubmissionLog::default());
+        let handler_error = captured_error.clone();
+        let handler_submissions = submissions.clone();
+        device.on_uncaptured_error(move |err: wgpu::Error| {
+            handle_uncaptured_error(&handler_error, &handler_submissions, err)
+        });
+
+        let id = NEXT_INSTANCE_ID.fetch_add(1, Ordering::Relaxed);
+
+        Self {
+            id,
             instance,
+            adapter,
             device,
             queue,
-            buffers: IdMap::new(),
-            textures: IdMap::new(),
-            samplers: IdMap::new(),
+            buffers: id_map(id, desc.deterministic_ids).with_drop_queue(),
+            textures: id_map(id, desc.deterministic_ids).with_drop_queue(),
+            texture_views: id_map(id, desc.deterministic_ids),
+            samplers: id_map(id, desc.deterministic_ids).with_drop_queue(),
             shader_module_sources: DashMap::new(),
-            shader_modules: IdMap::new(),
+            shader_modules: id_map(id, desc.deterministic_ids),
             bind_group_layout_descriptors: DashMap::new(),
-            bind_group_layouts: IdMap::new(),
+            bind_group_layouts: id_map(id, desc.deterministic_ids),
             bind_group_descriptors: DashMap::new(),
-            bind_groups: IdMap::new(),
+            group_keys: DashMap::new(),
+            bind_groups_by_resource: DashMap::new(),
+            bind_groups: id_map(id, desc.deterministic_ids),
             pipeline_layout_descriptors: DashMap::new(),
-            pipeline_layouts: IdMap::new(),
+            pipeline_layouts: id_map(id, desc.deterministic_ids),
             compute_pipeline_descriptors: DashMap::new(),
-            compute_pipelines: IdMap::new(),
-            render_pipelines: IdMap::new(),
-        })
+            compute_pipelines: id_map(id, desc.deterministic_ids),
+            render_pipeline_descriptors: DashMap::new(),
+            render_pipelines: id_map(id, desc.deterministic_ids),
+            handles: HandleRegistry::new(),
+            placeholder_buffer: Mutex::new(None),
+            dispatch_args_buffer: Mutex::new(None),
+            dispatch_args_params: DashMap::new(),
+            convert_intermediates: DashMap::new(),
+            launch_ring: OnceCell::new(),
+            shared_buffers: DashMap::new(),
+            wakers: WakerRegistry::default(),
+            cache_watch: CacheWatch::new(desc.cache_limits),
+            force_transfers: ForceTransfers::new(desc.force_upload, desc.force_download),
+            oom: OomRecovery::new(desc.memory_budget),
+            staging_buffers: StagingPool::new(),
+            timestamp_queries: TimestampPool::default(),
+            #[cfg(feature = "debug-recorder")]
+            recorder: Mutex::new(None),
+            captured_error,
+            submissions,
+            stats: StatsCounters::default(),
+            submissions_since_clean: AtomicU32::new(0),
+        }
+    }
+
+    /// Resolves a [`Handle`], returns `None` if the resource has been destroyed.
+    #[inline]
+    pub fn resolve<T: Send + 'static>(&self, handle: &Handle<T>) -> Option<Arc<Mutex<T>>> {
+        self.handles.resolve(handle)
+    }
+
+    /// Destroys the resource behind `handle`, invalidating every handle to it.
+    #[inline]
+    pub fn destroy<T>(&self, handle: &Handle<T>) -> bool {
+        self.handles.destroy(handle)
+    }
+
+    /// Gets a zeroed buffer of at least `min_size` bytes, bound in place of missing
+    /// optional bindings. The buffer is shared, so it must never be written to.
+    pub fn placeholder_buffer(&self, min_size: u64) -> BufferId {
+        let mut placeholder = self.placeholder_buffer.lock().unwrap();
+
+        if let Some((id, size)) = &*placeholder {
+            if *size >= min_size {
+                return id.clone();
+            }
+        }
+
+        let size = min_size.max(4);
+
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("shatter_placeholder_buffer"),
+            size,
+            usage: crate::BUFFER_USAGES,
+            mapped_at_creation: false,
+        });
+
+        let id = self.buffers.stable_id("shatter_placeholder_buffer");
+        self.buffers.insert_sized(id.clone(), buffer, size);
+
+        *placeholder = Some((id.clone(), size));
+
+        id
+    }
+
+    /// Gets the buffer the dispatch args kernel writes to, shared by every
+    /// conditional dispatch since they execute in submission order.
+    pub(crate) fn dispatch_args_buffer(&self) -> BufferId {
+        let mut args = self.dispatch_args_buffer.lock().unwrap();
+
+        if let Some(id) = &*args {
+            return id.clone();
+        }
+
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("shatter_dispatch_args_buffer"),
+            size: std::mem::size_of::<crate::DispatchIndirectArgs>() as u64,
+            usage: crate::DISPATCH_ARGS_USAGES,
+            mapped_at_creation: false,
+        });
+
+        let id = self.buffers.stable_id("shatter_dispatch_args_buffer");
+        let size = std::mem::size_of::<crate::DispatchIndirectArgs>() as u64;
+        self.buffers.insert_sized(id.clone(), buffer, size);
+
+        *args = Some(id.clone());
+
+        id
+    }
+
+    /// Gets the ring launch parameters are written to, see [`LaunchParams`](crate::LaunchParams).
+    pub(crate) fn launch_ring(&self) -> &LaunchRing {
+        self.launch_ring.get_or_init(|| LaunchRing::new(self))
+    }
+
+    /// Number of buffers pooled by [`SharedBuffer`](crate::SharedBuffer).
+    #[inline]
+    pub fn shared_buffer_count(&self) -> usize {
+        self.shared_buffers.len()
+    }
+
+    /// Gets a uniform buffer holding the parameters of the dispatch args kernel.
+    pub(crate) fn dispatch_args_params(&self, params: [u32; 5]) -> BufferId {
+        if let Some(id) = self.dispatch_args_params.get(&params) {
+            return id.clone();
+        }
+
+        // uniform structs are padded to 16 bytes
+        let mut contents = [0u32; 8];
+        contents[..5].copy_from_slice(&params);
+
+        let contents = contents
+            .iter()
+            .flat_map(|value| value.to_ne_bytes())
+            .collect::<Vec<_>>();
+
+        let buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("shatter_dispatch_args_params"),
+                contents: &contents,
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+
+        let id = self.buffers.stable_id(&params);
+        self.buffers
+            .insert_sized(id.clone(), buffer, contents.len() as u64);
+
+        self.dispatch_args_params.insert(params, id.clone());
+
+        id
+    }
+
+    /// Gets the `Rgba8Unorm` texture conversions write to before copying to
+    /// destinations a kernel can't write, shared by conversions of the same size.
+    pub(crate) fn convert_intermediate(&self, width: u32, height: u32) -> TextureId {
+        if let Some(id) = self.convert_intermediates.get(&(width, height)) {
+            return id.clone();
+        }
+
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("shatter_convert_intermediate"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::COPY_SRC,
+        });
+
+        let id = self
+            .textures
+            .stable_id(&("shatter_convert_intermediate", width, height));
+        // Rgba8Unorm
+        let size = width as u64 * height as u64 * 4;
+        self.textures.insert_sized(id.clone(), texture, size);
+
+        self.convert_intermediates
+            .insert((width, height), id.clone());
+
+        id
+    }
+
+    /// Removes a view from [`Instance::texture_views`] along with every cached
+    /// bind group using it, so the view is freed immediately.
+    pub fn remove_texture_view(&self, id: &TextureViewId) {
+        self.remove_bind_groups_using(crate::ResourceKey::TextureView(id.raw()));
+        self.texture_views.remove(id);
+    }
+
+    /// Removes the buffers, textures and samplers no longer referenced, along
+    /// with the cached bind groups using them.
+    ///
+    /// Only resources dropped since the last clean are visited, see
+    /// [`IdMap::clean`]. Runs automatically every [`CLEAN_INTERVAL`]
+    /// submissions, before recording the next dispatch or draw.
+    ///
+    /// The parameter buffers of conditional dispatches are released too, one
+    /// per distinct parameters, and recreated by the next dispatch using them.
+    pub fn clean(&self) {
+        self.submissions_since_clean.store(0, Ordering::Relaxed);
+
+        // dropped before the buffers are cleaned, so they're freed right away
+        self.dispatch_args_params.clear();
+
+        let buffers = self
+            .buffers
+            .clean()
+            .into_iter()
+            .map(crate::ResourceKey::Buffer);
+        let textures = self
+            .textures
+            .clean()
+            .into_iter()
+            .map(crate::ResourceKey::Texture);
+        let samplers = self
+            .samplers
+            .clean()
+            .into_iter()
+            .map(crate::ResourceKey::Sampler);
+
+        for resource in buffers.chain(textures).chain(samplers) {
+            self.remove_bind_groups_using(resource);
+        }
+    }
+
+    /// Records a submission in [`Self::recent_submissions`] and counts it
+    /// towards the next automatic [`Self::clean`].
+    #[inline]
+    pub(crate) fn submitted(
+        &self,
+        tag: Option<Cow<'static, str>>,
+        entry_points: &[Cow<'static, str>],
+    ) {
+        self.submissions.record(tag, entry_points);
+        self.stats.submitted();
+        self.submissions_since_clean.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Gets the last [`RECENT_SUBMISSIONS`](crate::RECENT_SUBMISSIONS)
+    /// submissions to the queue, oldest first, e.g. to log what ran before an
+    /// error. Uncaptured errors list the most recent ones.
+    pub fn recent_submissions(&self) -> Vec<SubmissionRecord> {
+        self.submissions.recent()
+    }
+
+    /// Handles `err` like the uncaptured error handler installed on the
+    /// device, capturing it in the running [`Self::error_scope`] and panicking
+    /// outside of one, e.g. to report errors of wgpu calls made elsewhere.
+    pub fn deliver_uncaptured_error(&self, err: wgpu::Error) {
+        handle_uncaptured_error(&self.captured_error, &self.submissions, err);
+    }
+
+    /// Runs [`Self::clean`] if [`CLEAN_INTERVAL`] submissions passed since the
+    /// last one. Must not be called while holding entries of the maps.
+    #[inline]
+    pub(crate) fn clean_if_due(&self) {
+        if self.submissions_since_clean.load(Ordering::Relaxed) >= CLEAN_INTERVAL {
+            self.clean();
+        }
+    }
+
+    /// Removes every cached bind group using `resource`.
+    fn remove_bind_groups_using(&self, resource: crate::ResourceKey) {
+        let descriptors = match self.bind_groups_by_resource.remove(&resource) {
+            Some((_, descriptors)) => descriptors,
+            None => return,
+        };
+
+        for (key, desc) in descriptors {
+            // the other resources of the group stop listing it
+            for &(_, other, _, _) in &key.entries {
+                if other == resource {
+                    continue;
+                }
+
+                if let Some(mut listed) = self.bind_groups_by_resource.get_mut(&other) {
+                    listed.remove(&key);
+                }
+
+                self.bind_groups_by_resource
+                    .remove_if(&other, |_, listed| listed.is_empty());
+            }
+
+            self.group_keys.remove(&key);
+
+            if let Some((_, bind_group)) = self.bind_group_descriptors.remove(&desc) {
+                self.bind_groups.remove(&bind_group);
+            }
+        }
     }
 
     pub fn get_bind_group_layout(
         &self,
-        desc: crate::BindGroupLayoutDescriptor,
+        mut desc: crate::BindGroupLayoutDescriptor,
     ) -> BindGroupLayoutId {
+        desc.canonicalize();
+
         if let Some(id) = self.bind_group_layout_descriptors.get(&desc) {
+            self.stats.cache_hit();
             return id.clone();
         }
 
+        self.stats.cache_miss();
+
         let wgpu_desc = wgpu::BindGroupLayoutDescriptor {
             label: Some("shatter_bind_group_layout"),
             entries: &desc.entries,
@@ -107,7 +666,7 @@ impl Instance {
 
         let bind_group = self.device.create_bind_group_layout(&wgpu_desc);
 
-        let id = self.bind_group_layouts.next_id();
+        let id = self.bind_group_layouts.stable_id(&desc);
 
         self.bind_group_layout_descriptors
             .insert(desc, id.clone_untracked());
@@ -116,34 +675,75 @@ impl Instance {
         id
     }
 
-    pub fn get_bind_group(&self, desc: crate::BindGroupDescriptor) -> BindGroupId {
+    pub fn get_bind_group(&self, mut desc: crate::BindGroupDescriptor) -> BindGroupId {
+        desc.canonicalize();
+
         if let Some(id) = self.bind_group_descriptors.get(&desc) {
+            self.stats.cache_hit();
             return id.clone();
         }
 
+        self.stats.cache_miss();
+
+        let key = crate::GroupKey::from_descriptor(&desc);
+
+        for &(binding, resource, _, _) in &key.entries {
+            if resource.instance() != self.id {
+                panic!(
+                    "binding {} is a {:?} of instance {}, but the bind group is created in instance {}, \
+                     resources can't be shared between instances",
+                    binding,
+                    resource,
+                    resource.instance(),
+                    self.id,
+                );
+            }
+        }
+
         let layout = self.bind_group_layouts.get(&desc.layout).unwrap();
 
         #[allow(unused)]
         enum RefResource<'a> {
-            Buffer(Ref<'a, BufferId, wgpu::Buffer>, &'a crate::BufferBinding),
+            /// Index into `buffers`.
+            Buffer(usize, &'a crate::BufferBinding),
             BufferArray(Vec<(Ref<'a, BufferId, wgpu::Buffer>, &'a crate::BufferBinding)>),
             Sampler(Ref<'a, SamplerId, wgpu::Sampler>),
             TextureView(wgpu::TextureView),
             TextureViewArray(Vec<wgpu::TextureView>),
+            RawTextureView(Ref<'a, TextureViewId, wgpu::TextureView>),
         }
 
+        // a buffer may be bound to several entries, borrowing it once avoids
+        // holding two guards of the same shard
+        let mut buffers: SmallVec<[(BufferId, Ref<BufferId, wgpu::Buffer>); 4]> = SmallVec::new();
+
         let resources = desc
             .entries
             .iter()
             .map(|entry| match entry.resource {
                 crate::BindingResource::Buffer(ref binding) => {
-                    RefResource::Buffer(self.buffers.get(&binding.buffer).unwrap(), binding)
+                    let index = match buffers.iter().position(|(id, _)| *id == binding.buffer) {
+                        Some(index) => index,
+                        None => {
+                            let buffer = self.buffers.get(&binding.buffer).unwrap();
+                            buffers.push((binding.buffer.clone(), buffer));
+                            buffers.len() - 1
+                        }
+                    };
+
+                    RefResource::Buffer(index, binding)
                 }
                 crate::BindingResource::TextureView(ref id) => {
                     let texture = self.textures.get(id).unwrap();
 
                     RefResource::TextureView(texture.create_view(&Default::default()))
                 }
+                crate::BindingResource::RawTextureView(ref id) => {
+                    RefResource::RawTextureView(self.texture_views.get(id).unwrap())
+                }
+                crate::BindingResource::Sampler(ref id) => {
+                    RefResource::Sampler(self.samplers.get(id).unwrap())
+                }
                 _ => unimplemented!(),
             })
             .collect::<Vec<_>>();
@@ -154,14 +754,16 @@ impl Instance {
             .zip(&resources)
             .map(|(entry, resource)| {
                 let resource = match resource {
-                    RefResource::Buffer(buffer, binding) => {
+                    RefResource::Buffer(index, binding) => {
                         wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                            buffer: &buffer,
+                            buffer: &buffers[*index].1,
                             offset: binding.offset,
                             size: binding.size,
                         })
                     }
                     RefResource::TextureView(view) => wgpu::BindingResource::TextureView(view),
+                    RefResource::RawTextureView(view) => wgpu::BindingResource::TextureView(view),
+                    RefResource::Sampler(sampler) => wgpu::BindingResource::Sampler(sampler),
                     _ => unimplemented!(),
                 };
 
@@ -180,10 +782,20 @@ impl Instance {
 
         let bind_group = self.device.create_bind_group(&wgpu_desc);
 
-        drop(wgpu_desc);
         drop(resources);
 
-        let id = self.bind_groups.next_id();
+        let id = self.bind_groups.stable_id(&desc);
+
+        // the cache doesn't keep the resources alive, dropping one removes
+        // the group in `clean`
+        let desc = desc.clone_untracked();
+
+        for &(_, resource, _, _) in &key.entries {
+            self.bind_groups_by_resource
+                .entry(resource)
+                .or_default()
+                .insert(key.clone(), desc.clone());
+        }
 
         self.bind_group_descriptors
             .insert(desc, id.clone_untracked());
@@ -192,34 +804,81 @@ impl Instance {
         id
     }
 
+    /// Gets the bind group cached for `key`, building it from `desc` on a miss.
+    ///
+    /// A hit neither calls `desc` nor hashes a full descriptor.
+    pub fn get_keyed_bind_group(
+        &self,
+        key: crate::GroupKey,
+        desc: impl FnOnce() -> crate::BindGroupDescriptor,
+    ) -> BindGroupId {
+        if let Some(id) = self.group_keys.get(&key) {
+            self.stats.cache_hit();
+            return id.clone();
+        }
+
+        // the descriptor lookup counts the hit or miss
+        let id = self.get_bind_group(desc());
+
+        self.group_keys.insert(key, id.clone_untracked());
+
+        id
+    }
+
+    /// Gets the shader module compiled from `source`.
+    ///
+    /// # Panics
+    /// Panics if the cache is full, see [`Self::try_get_shader_module`].
+    #[track_caller]
     pub fn get_shader_module(&self, source: impl Into<Cow<'static, str>>) -> ShaderModuleId {
+        self.try_get_shader_module(source)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Gets the shader module compiled from `source`, failing instead of
+    /// compiling a new module once [`CacheLimits::max_shader_modules`] is reached.
+    pub fn try_get_shader_module(
+        &self,
+        source: impl Into<Cow<'static, str>>,
+    ) -> Result<ShaderModuleId, CacheFull> {
         let source = source.into();
 
         if let Some(id) = self.shader_module_sources.get(&source) {
-            return id.clone();
+            self.stats.cache_hit();
+            return Ok(id.clone());
         }
 
+        self.cache_watch
+            .check_capacity(CacheKind::ShaderModule, self.shader_module_sources.len())?;
+
         let wgpu_desc = wgpu::ShaderModuleDescriptor {
             label: Some("shatter_shader_module"),
             source: wgpu::ShaderSource::Wgsl(source.clone()),
         };
 
         let shader_module = self.device.create_shader_module(&wgpu_desc);
+        self.stats.cache_miss();
 
-        let id = self.shader_modules.next_id();
+        let id = self.shader_modules.stable_id(&source);
+
+        self.cache_watch
+            .record_insertion(CacheKind::ShaderModule, &source);
 
         self.shader_module_sources
             .insert(source, id.clone_untracked());
         self.shader_modules.insert(id.clone(), shader_module);
 
-        id
+        Ok(id)
     }
 
     pub fn get_pipeline_layout(&self, desc: crate::PipelineLayoutDescriptor) -> PipelineLayoutId {
         if let Some(id) = self.pipeline_layout_descriptors.get(&desc) {
+            self.stats.cache_hit();
             return id.clone();
         }
 
+        self.stats.cache_miss();
+
         let refs = desc
             .bind_group_layouts
             .iter()
@@ -236,7 +895,7 @@ impl Instance {
 
         let pipeline_layout = self.device.create_pipeline_layout(&wgpu_desc);
 
-        let id = self.pipeline_layouts.next_id();
+        let id = self.pipeline_layouts.stable_id(&desc);
 
         self.pipeline_layout_descriptors
             .insert(desc, id.clone_untracked());
@@ -245,14 +904,35 @@ impl Instance {
         id
     }
 
+    /// Gets the compute pipeline described by `desc`.
+    ///
+    /// # Panics
+    /// Panics if the cache is full, see [`Self::try_get_compute_pipeline`].
+    #[track_caller]
     pub fn get_compute_pipeline(
         &self,
         desc: crate::ComputePipelineDescriptor,
     ) -> ComputePipelineId {
+        self.try_get_compute_pipeline(desc)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Gets the compute pipeline described by `desc`, failing instead of
+    /// creating a new pipeline once [`CacheLimits::max_compute_pipelines`] is reached.
+    pub fn try_get_compute_pipeline(
+        &self,
+        desc: crate::ComputePipelineDescriptor,
+    ) -> Result<ComputePipelineId, CacheFull> {
         if let Some(id) = self.compute_pipeline_descriptors.get(&desc) {
-            return id.clone();
+            self.stats.cache_hit();
+            return Ok(id.clone());
         }
 
+        self.cache_watch.check_capacity(
+            CacheKind::ComputePipeline,
+            self.compute_pipeline_descriptors.len(),
+        )?;
+
         let layout = desc
             .layout
             .as_ref()
@@ -261,19 +941,295 @@ impl Instance {
 
         let wgpu_desc = wgpu::ComputePipelineDescriptor {
             label: Some("shatter_compute_pipeline_layout"),
-            layout: layout.as_ref().map(|layout| &**layout),
+            layout: layout.as_deref(),
             module,
             entry_point: desc.entry_point.as_ref(),
         };
 
         let compute_pipeline = self.device.create_compute_pipeline(&wgpu_desc);
+        self.stats.cache_miss();
 
-        let id = self.compute_pipelines.next_id();
+        let id = self.compute_pipelines.stable_id(&desc);
+
+        self.cache_watch
+            .record_insertion(CacheKind::ComputePipeline, &format!("{:?}", desc));
 
         self.compute_pipeline_descriptors
             .insert(desc, id.clone_untracked());
         self.compute_pipelines.insert(id.clone(), compute_pipeline);
 
+        Ok(id)
+    }
+
+    /// Gets the render pipeline described by `desc`.
+    pub fn get_render_pipeline(&self, desc: crate::RenderPipelineDescriptor) -> RenderPipelineId {
+        if let Some(id) = self.render_pipeline_descriptors.get(&desc) {
+            self.stats.cache_hit();
+            return id.clone();
+        }
+
+        let layout = desc
+            .layout
+            .as_ref()
+            .map(|id| self.pipeline_layouts.get(id).unwrap());
+        let module = &*self.shader_modules.get(&desc.module).unwrap();
+
+        let wgpu_desc = wgpu::RenderPipelineDescriptor {
+            label: Some("shatter_render_pipeline"),
+            layout: layout.as_deref(),
+            vertex: wgpu::VertexState {
+                module,
+                entry_point: desc.vertex_entry_point.as_ref(),
+                buffers: &[],
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module,
+                entry_point: desc.fragment_entry_point.as_ref(),
+                targets: &[desc.format.into()],
+            }),
+        };
+
+        let render_pipeline = self.device.create_render_pipeline(&wgpu_desc);
+        self.stats.cache_miss();
+
+        let id = self.render_pipelines.stable_id(&desc);
+
+        self.render_pipeline_descriptors
+            .insert(desc, id.clone_untracked());
+        self.render_pipelines.insert(id.clone(), render_pipeline);
+
         id
     }
+
+    /// Runs `f`, returning the first error wgpu reports while it runs instead
+    /// of panicking.
+    ///
+    /// Errors are captured device wide, so errors caused by other threads in
+    /// the meantime are returned as well. Waits for the device to process the calls.
+    ///
+    /// # Panics
+    /// Panics if called within another error scope.
+    pub fn error_scope<T>(&self, f: impl FnOnce() -> T) -> Result<T, Error> {
+        {
+            let mut captured = self.captured_error.lock().unwrap();
+            assert!(captured.is_none(), "error scopes can't be nested");
+            *captured = Some(CapturedError::Capturing);
+        }
+
+        // ends the scope even if `f` panics
+        struct EndScope<'a>(&'a Mutex<Option<CapturedError>>);
+
+        impl Drop for EndScope<'_> {
+            fn drop(&mut self) {
+                *self.0.lock().unwrap() = None;
+            }
+        }
+
+        let scope = EndScope(&self.captured_error);
+
+        let value = f();
+
+        self.device.poll(wgpu::Maintain::Wait);
+
+        let captured = scope.0.lock().unwrap().take();
+
+        match captured {
+            Some(CapturedError::Captured(err)) => Err(err),
+            _ => Ok(value),
+        }
+    }
+
+    /// Returns true while [`Self::error_scope`] runs.
+    pub(crate) fn in_error_scope(&self) -> bool {
+        self.captured_error.lock().unwrap().is_some()
+    }
+
+    /// Checks that a storage buffer of `size` bytes can be bound.
+    pub fn check_buffer_size(&self, size: u64) -> Result<(), Error> {
+        let max = self.device.limits().max_storage_buffer_binding_size as u64;
+
+        if size > max {
+            return Err(ResourceError::LimitExceeded {
+                limit: "max_storage_buffer_binding_size",
+                requested: size,
+                max,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a uniform buffer of `size` bytes can be bound.
+    pub fn check_uniform_buffer_size(&self, size: u64) -> Result<(), Error> {
+        let max = self.device.limits().max_uniform_buffer_binding_size as u64;
+
+        if size > max {
+            return Err(ResourceError::LimitExceeded {
+                limit: "max_uniform_buffer_binding_size",
+                requested: size,
+                max,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn shader_module_count(&self) -> usize {
+        self.shader_module_sources.len()
+    }
+
+    #[inline]
+    pub fn compute_pipeline_count(&self) -> usize {
+        self.compute_pipeline_descriptors.len()
+    }
+
+    #[inline]
+    pub fn pipeline_layout_count(&self) -> usize {
+        self.pipeline_layout_descriptors.len()
+    }
+
+    #[inline]
+    pub fn bind_group_layout_count(&self) -> usize {
+        self.bind_group_layout_descriptors.len()
+    }
+
+    /// Number of cached bind groups, including those using resources dropped
+    /// since the last [`Self::clean`].
+    #[inline]
+    pub fn bind_group_count(&self) -> usize {
+        self.bind_group_descriptors.len()
+    }
+
+    #[inline]
+    pub fn cache_limits(&self) -> CacheLimits {
+        self.cache_watch.limits()
+    }
+
+    /// Sets how fast and how far the shader module and pipeline caches may
+    /// grow. Caches already over a new maximum aren't shrunk.
+    #[inline]
+    pub fn set_cache_limits(&self, limits: CacheLimits) {
+        self.cache_watch.set_limits(limits);
+    }
+
+    /// Calls `hook` instead of printing to stderr when a cache grows faster
+    /// than [`CacheLimits`] allow.
+    pub fn set_cache_churn_hook(&self, hook: impl Fn(&CacheChurn) + Send + Sync + 'static) {
+        self.cache_watch.set_hook(Some(Arc::new(hook)));
+    }
+
+    /// Prints cache churn to stderr again.
+    pub fn clear_cache_churn_hook(&self) {
+        self.cache_watch.set_hook(None);
+    }
+
+    /// Calls `hook` instead of printing to stderr when a constant is
+    /// specialized for more values than [`CacheLimits::max_specializations`].
+    pub fn set_specialization_hook(
+        &self,
+        hook: impl Fn(&ManySpecializations) + Send + Sync + 'static,
+    ) {
+        self.cache_watch
+            .set_specialization_hook(Some(Arc::new(hook)));
+    }
+
+    /// Prints specialization warnings to stderr again.
+    pub fn clear_specialization_hook(&self) {
+        self.cache_watch.set_specialization_hook(None);
+    }
+
+    /// Makes every dispatch upload the data of the bindings it reads, even if
+    /// unchanged since the last upload, to rule out dirty tracking when results
+    /// look stale. Each upload dirty tracking would have skipped is passed to
+    /// the [forced transfer hook](Self::set_forced_transfer_hook).
+    #[inline]
+    pub fn set_force_upload(&self, force_upload: bool) {
+        self.force_transfers.set_upload(force_upload);
+    }
+
+    #[inline]
+    pub fn force_upload(&self) -> bool {
+        self.force_transfers.upload()
+    }
+
+    /// Makes every dispatch download what it wrote right after submitting,
+    /// instead of on the next CPU access, see [`Self::set_force_upload`].
+    #[inline]
+    pub fn set_force_download(&self, force_download: bool) {
+        self.force_transfers.set_download(force_download);
+    }
+
+    #[inline]
+    pub fn force_download(&self) -> bool {
+        self.force_transfers.download()
+    }
+
+    /// Calls `hook` instead of printing to stderr for every [`ForcedTransfer`].
+    pub fn set_forced_transfer_hook(&self, hook: impl Fn(&ForcedTransfer) + Send + Sync + 'static) {
+        self.force_transfers.set_hook(Some(Arc::new(hook)));
+    }
+
+    /// Prints forced transfers to stderr again.
+    pub fn clear_forced_transfer_hook(&self) {
+        self.force_transfers.set_hook(None);
+    }
+
+    /// Takes a pooled staging buffer of at least `size` bytes, or creates one.
+    /// Hand it back with [`Self::release_staging`] once unmapped.
+    #[inline]
+    pub fn acquire_staging(&self, size: u64) -> StagingBuffer {
+        self.staging_buffers.acquire(&self.device, size)
+    }
+
+    /// Returns a staging buffer to the pool, unless the pool already retains
+    /// [`Self::max_staging_bytes`].
+    ///
+    /// The buffer **must** be unmapped, mapping it again is a validation error.
+    #[inline]
+    pub fn release_staging(&self, buffer: StagingBuffer) {
+        self.staging_buffers.release(buffer);
+    }
+
+    /// Bytes of staging buffers held by the pool for reuse.
+    #[inline]
+    pub fn staging_bytes(&self) -> u64 {
+        self.staging_buffers.retained_bytes()
+    }
+
+    #[inline]
+    pub fn max_staging_bytes(&self) -> u64 {
+        self.staging_buffers.max_bytes()
+    }
+
+    /// Sets how many bytes of staging buffers the pool retains, defaults to
+    /// [`DEFAULT_MAX_STAGING_BYTES`](crate::DEFAULT_MAX_STAGING_BYTES).
+    #[inline]
+    pub fn set_max_staging_bytes(&self, max_bytes: u64) {
+        self.staging_buffers.set_max_bytes(max_bytes);
+    }
+
+    /// Snapshots the buffers written by subsequent dispatches into `recorder`,
+    /// replacing the previous recorder.
+    #[cfg(feature = "debug-recorder")]
+    pub fn set_recorder(&self, recorder: Arc<crate::debug::Recorder>) {
+        *self.recorder.lock().unwrap() = Some(recorder);
+    }
+
+    /// Stops recording, returns the recorder.
+    #[cfg(feature = "debug-recorder")]
+    pub fn clear_recorder(&self) -> Option<Arc<crate::debug::Recorder>> {
+        self.recorder.lock().unwrap().take()
+    }
+
+    #[cfg(feature = "debug-recorder")]
+    #[inline]
+    pub fn recorder(&self) -> Option<Arc<crate::debug::Recorder>> {
+        self.recorder.lock().unwrap().clone()
+    }
 }
@@ -0,0 +1,148 @@
+use std::{
+    num::NonZeroU64,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::{
+    BindGroupDescriptor, BindGroupEntry, BindGroupId, BindGroupLayoutDescriptor, BindGroupLayoutId,
+    BindingResource, BufferBinding, BufferId, Instance,
+};
+
+/// Dispatches per submission the launch parameter ring holds distinct values
+/// for, see [`LaunchParams`].
+pub const LAUNCH_RING_SLOTS: u32 = 256;
+
+/// Size of the launch parameters, uniform structs are padded to 16 bytes.
+const LAUNCH_PARAMS_SIZE: u64 = 16;
+
+/// Members of the launch parameters of a shader, a uniform the builder fills
+/// before every dispatch.
+///
+/// `wgsl!` options needing a value from the host opt a member in, so they
+/// share a single binding in the group after the shader's own. Shaders
+/// enabling none of them have [`LaunchParams::NONE`] and bind nothing extra.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LaunchParams {
+    /// Set by `#![auto_guard]`, see
+    /// [`ComputeShaderBuilder::with_guard_count`](crate::ComputeShaderBuilder::with_guard_count).
+    pub guard_count: bool,
+    /// Set by `#![dispatch_offset]`, see
+    /// [`ComputeShaderBuilder::with_dispatch_offset`](crate::ComputeShaderBuilder::with_dispatch_offset).
+    pub dispatch_offset: bool,
+}
+
+impl LaunchParams {
+    pub const NONE: Self = Self {
+        guard_count: false,
+        dispatch_offset: false,
+    };
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        !self.guard_count && !self.dispatch_offset
+    }
+
+    /// Encodes the values of the declared members in declaration order.
+    pub fn encode(&self, guard_count: u32, dispatch_offset: u32) -> [u8; 16] {
+        let members = [
+            (self.guard_count, guard_count),
+            (self.dispatch_offset, dispatch_offset),
+        ];
+
+        let mut bytes = [0; 16];
+
+        let values = members
+            .iter()
+            .filter(|(declared, _)| *declared)
+            .map(|&(_, value)| value);
+
+        for (chunk, value) in bytes.chunks_exact_mut(4).zip(values) {
+            chunk.copy_from_slice(&value.to_ne_bytes());
+        }
+
+        bytes
+    }
+}
+
+/// Layout of the group holding the launch parameters, bound at a dynamic
+/// offset into the ring.
+pub(crate) fn launch_layout_descriptor() -> BindGroupLayoutDescriptor {
+    BindGroupLayoutDescriptor::new(vec![wgpu::BindGroupLayoutEntry {
+        binding: 0,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Uniform,
+            has_dynamic_offset: true,
+            min_binding_size: NonZeroU64::new(LAUNCH_PARAMS_SIZE),
+        },
+        count: None,
+    }])
+}
+
+/// A uniform buffer of [`LAUNCH_RING_SLOTS`] slots launch parameters are
+/// written to, so dispatches don't create a buffer each.
+///
+/// Writes land before the next submission, so every dispatch recorded into
+/// one submission takes its own slot. Slots are reused after wrapping around.
+pub(crate) struct LaunchRing {
+    // keeps the buffer out of `Instance::clean`
+    buffer: BufferId,
+    pub layout: BindGroupLayoutId,
+    pub bind_group: BindGroupId,
+    stride: u32,
+    next: AtomicU32,
+}
+
+impl LaunchRing {
+    pub fn new(instance: &Instance) -> Self {
+        let stride = instance
+            .device
+            .limits()
+            .min_uniform_buffer_offset_alignment
+            .max(LAUNCH_PARAMS_SIZE as u32);
+        let size = stride as u64 * LAUNCH_RING_SLOTS as u64;
+
+        let buffer = instance.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("shatter_launch_ring"),
+            size,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let id = instance.buffers.stable_id("shatter_launch_ring");
+        instance.buffers.insert_sized(id.clone(), buffer, size);
+
+        let layout = instance.get_bind_group_layout(launch_layout_descriptor());
+
+        let bind_group = instance.get_bind_group(BindGroupDescriptor::new(
+            layout.clone(),
+            vec![BindGroupEntry {
+                binding: 0,
+                resource: BindingResource::Buffer(BufferBinding {
+                    buffer: id.clone(),
+                    offset: 0,
+                    size: NonZeroU64::new(LAUNCH_PARAMS_SIZE),
+                }),
+            }],
+        ));
+
+        Self {
+            buffer: id,
+            layout,
+            bind_group,
+            stride,
+            next: AtomicU32::new(0),
+        }
+    }
+
+    /// Writes `params` to the next slot, returning its dynamic offset.
+    pub fn push(&self, instance: &Instance, params: &[u8; 16]) -> u32 {
+        let slot = self.next.fetch_add(1, Ordering::Relaxed) % LAUNCH_RING_SLOTS;
+        let offset = slot * self.stride;
+
+        let buffer = instance.buffers.get(&self.buffer).unwrap();
+        instance.queue.write_buffer(&buffer, offset as u64, params);
+
+        offset
+    }
+}
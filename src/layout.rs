@@ -0,0 +1,87 @@
+/// Longest message of [`assert_layout`], longer ones are cut off.
+const MESSAGE_LEN: usize = 256;
+
+/// Fails the build if the `what` of `item`, e.g. the offset of `Particle::mass`,
+/// is `rust` bytes in Rust but `wgsl` bytes in WGSL.
+///
+/// `wgsl!` calls this in constants for every member of the structs it
+/// generates or takes with `#![extern_type]`, so a layout diverging from
+/// naga's, e.g. after a naga update or for a hand written type, fails to
+/// compile rather than corrupting the data of every element after it.
+pub const fn assert_layout(item: &str, what: &str, wgsl: usize, rust: usize) {
+    if wgsl == rust {
+        return;
+    }
+
+    let mut message = Message::new();
+    message.push("the ");
+    message.push(what);
+    message.push(" of `");
+    message.push(item);
+    message.push("` is ");
+    message.push_number(rust);
+    message.push(" bytes in Rust, but ");
+    message.push_number(wgsl);
+    message.push(" bytes in WGSL");
+
+    match std::str::from_utf8(message.as_bytes()) {
+        Ok(message) => panic!("{}", message),
+        Err(_) => panic!("a generated type doesn't match the WGSL layout"),
+    }
+}
+
+/// A message formatted at compile time, `format!` isn't const.
+struct Message {
+    bytes: [u8; MESSAGE_LEN],
+    len: usize,
+}
+
+impl Message {
+    const fn new() -> Self {
+        Self {
+            bytes: [0; MESSAGE_LEN],
+            len: 0,
+        }
+    }
+
+    const fn push(&mut self, s: &str) {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+
+        // stops at a char boundary, so the message stays valid UTF-8
+        while i < bytes.len() && self.len < MESSAGE_LEN {
+            if self.len + 4 > MESSAGE_LEN && bytes[i] >= 0x80 {
+                break;
+            }
+
+            self.bytes[self.len] = bytes[i];
+            self.len += 1;
+            i += 1;
+        }
+    }
+
+    const fn push_number(&mut self, mut n: usize) {
+        let mut digits = [0; 20];
+        let mut count = 0;
+
+        loop {
+            digits[count] = b'0' + (n % 10) as u8;
+            count += 1;
+            n /= 10;
+
+            if n == 0 {
+                break;
+            }
+        }
+
+        while count > 0 && self.len < MESSAGE_LEN {
+            count -= 1;
+            self.bytes[self.len] = digits[count];
+            self.len += 1;
+        }
+    }
+
+    const fn as_bytes(&self) -> &[u8] {
+        self.bytes.split_at(self.len).0
+    }
+}
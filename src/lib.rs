@@ -1,31 +1,129 @@
 #![deny(unsafe_op_in_unsafe_fn)]
 
+// `wgsl!` names the crate `::shatter`, see `algo`
+extern crate self as shatter;
+
+pub mod algo;
+mod any_texture;
 mod bind_group;
 mod buffer;
+mod cache_watch;
 pub mod color;
 mod compute;
+pub mod convert;
+pub mod debug;
+mod dynamic;
+mod error;
+pub mod fault;
+mod force_transfer;
+mod frame_stats;
+mod gpu_buffer;
+mod handle;
 mod id;
+mod indirect;
 mod instance;
+mod launch;
+mod layout;
+mod maintenance;
 mod math;
+mod migrate;
+mod oom;
+#[cfg(feature = "overlay")]
+pub mod overlay;
 mod pipeline;
+mod registry;
 mod render;
+mod sampler;
+mod shared_buffer;
+mod snapshot;
+mod source;
+mod specialize;
+mod staging;
+mod statistics;
+mod submission;
+mod surface;
+pub mod test_util;
 mod texture;
+mod texture_convert;
+mod texture_copy;
+mod timing;
+mod transfer;
+pub mod tune;
+pub mod util;
+mod validate;
 
+pub use any_texture::*;
 pub use bind_group::*;
 pub use buffer::*;
+pub use cache_watch::*;
 #[doc(hidden)]
 pub use color::*;
 pub use compute::*;
+pub use dynamic::*;
+pub use error::*;
+pub use force_transfer::*;
+pub use frame_stats::*;
+pub use gpu_buffer::*;
+pub use handle::*;
 pub use id::*;
+pub use indirect::*;
 pub use instance::*;
+pub use launch::*;
+pub use layout::*;
+pub use maintenance::*;
 #[doc(hidden)]
 pub use math::*;
+pub use migrate::*;
+pub use oom::*;
 pub use pipeline::*;
+pub use registry::*;
 pub use render::*;
+pub use sampler::*;
+pub use shared_buffer::*;
 pub use shatter_macro::*;
+pub use snapshot::*;
+pub use source::*;
+pub use staging::*;
+pub use statistics::*;
+pub use submission::*;
+pub use surface::*;
 pub use texture::*;
+pub use texture_convert::*;
+pub use texture_copy::*;
 #[doc(hidden)]
 pub use texture_format::*;
+pub use timing::*;
+pub use transfer::*;
+pub use validate::*;
 
-#[doc(hidden)]
+/// The wgpu shatter is built against.
+///
+/// shatter's API uses wgpu types, e.g. [`InstanceDescriptor::features`] and
+/// [`BindGroupLayoutEntry`], so a crate using another version of wgpu gets two
+/// copies of them and errors like "expected `wgpu::Features`, found
+/// `wgpu::Features`". Name them through this re-export instead of depending on
+/// wgpu directly. Crates that need their own dependency can check it is the
+/// same version at compile time:
+///
+/// ```
+/// # use shatter::wgpu;
+/// // fails with "expected `shatter::wgpu::Features`, found `wgpu::Features`"
+/// // and a note on multiple versions of the crate if `wgpu` isn't the version
+/// // shatter re-exports
+/// const _: fn(wgpu::Features) -> shatter::wgpu::Features = |features| features;
+/// ```
 pub use wgpu;
+
+/// Gets the wgpu release shatter is built against, e.g. `"0.11"`, see [`wgpu`].
+#[inline]
+pub const fn wgpu_version() -> &'static str {
+    "0.11"
+}
+
+/// Version of the interface between `shatter` and the code generated by
+/// `wgsl!`, checked at compile time by every expansion.
+pub const ABI_VERSION: u32 = 32;
+
+#[doc(hidden)]
+#[path = "abi.rs"]
+pub mod __abi;
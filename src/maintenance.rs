@@ -0,0 +1,142 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll, Waker},
+    thread::{self, Thread},
+    time::Duration,
+};
+
+use once_cell::sync::OnceCell;
+
+use crate::Instance;
+
+/// Interval at which the maintenance thread polls the device while futures
+/// are waiting on it.
+const MAINTENANCE_INTERVAL: Duration = Duration::from_micros(500);
+
+/// Wakers of futures waiting on the GPU, woken by [`Instance::poll`].
+///
+/// wgpu futures only resolve when the device is polled, which the first
+/// registration hands to a maintenance thread polling while any waker is
+/// registered, so executors never block on the device.
+#[derive(Default)]
+pub(crate) struct WakerRegistry {
+    wakers: Mutex<Vec<Waker>>,
+    maintenance: OnceCell<Thread>,
+}
+
+impl WakerRegistry {
+    fn register(&self, waker: &Waker) {
+        let mut wakers = self.wakers.lock().unwrap();
+
+        if !wakers.iter().any(|registered| registered.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+
+        drop(wakers);
+
+        self.maintenance.get_or_init(spawn_maintenance).unpark();
+    }
+
+    fn is_empty(&self) -> bool {
+        self.wakers.lock().unwrap().is_empty()
+    }
+
+    fn wake_all(&self) {
+        let wakers = std::mem::take(&mut *self.wakers.lock().unwrap());
+
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+fn spawn_maintenance() -> Thread {
+    thread::Builder::new()
+        .name(String::from("shatter_maintenance"))
+        .spawn(|| {
+            let instance = Instance::global();
+
+            loop {
+                // registering unparks, so a waker registered since the check isn't missed
+                if instance.wakers.is_empty() {
+                    thread::park();
+                    continue;
+                }
+
+                instance.poll();
+                thread::sleep(MAINTENANCE_INTERVAL);
+            }
+        })
+        .expect("failed to spawn the maintenance thread")
+        .thread()
+        .clone()
+}
+
+impl Instance {
+    /// Polls the device without blocking, resolving the futures of async
+    /// dispatches and downloads that are done.
+    ///
+    /// A maintenance thread calls this while any future is waiting, so it
+    /// never has to be called, applications already polling the device every
+    /// frame may call it to resolve futures sooner.
+    pub fn poll(&self) {
+        self.device.poll(wgpu::Maintain::Poll);
+        self.wakers.wake_all();
+    }
+}
+
+/// A wgpu future, resolved by polling the device through [`Instance::poll`].
+pub(crate) struct Polled<F>(Pin<Box<F>>);
+
+impl<F: Future> Polled<F> {
+    #[inline]
+    pub(crate) fn new(future: F) -> Self {
+        Self(Box::pin(future))
+    }
+}
+
+impl<F: Future> Future for Polled<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // registered first, so a completion while polling below wakes the task
+        let instance = Instance::global();
+        instance.wakers.register(cx.waker());
+
+        instance.device.poll(wgpu::Maintain::Poll);
+
+        self.0.as_mut().poll(cx)
+    }
+}
+
+/// Future resolving once the GPU has finished the work submitted before it
+/// was created, returned by
+/// [`ComputeShaderBuilder::dispatch_async`](crate::ComputeShaderBuilder::dispatch_async).
+pub struct SubmittedWork(Polled<Pin<Box<dyn Future<Output = ()> + Send>>>);
+
+impl SubmittedWork {
+    #[inline]
+    pub fn new() -> Self {
+        let done = Instance::global().queue.on_submitted_work_done();
+
+        Self(Polled::new(Box::pin(done)))
+    }
+}
+
+impl Default for SubmittedWork {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Future for SubmittedWork {
+    type Output = ();
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
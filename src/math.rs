@@ -1,3 +1,8 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
 #[repr(C, align(8))]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Vec2<T> {
@@ -25,6 +30,40 @@ impl<T> Vec3<T> {
     }
 }
 
+/// A [`Vec3`] without its trailing padding, generated for `vec3` struct
+/// members followed by a member in their last 4 bytes, e.g. `vec3<f32>`
+/// followed by `f32`.
+///
+/// Structs with such a member are aligned to 16 bytes themselves, so the
+/// vector keeps its alignment within the struct.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PackedVec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> PackedVec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Vec3<T>> for PackedVec3<T> {
+    #[inline]
+    fn from(vec: Vec3<T>) -> Self {
+        Self::new(vec.x, vec.y, vec.z)
+    }
+}
+
+impl<T> From<PackedVec3<T>> for Vec3<T> {
+    #[inline]
+    fn from(vec: PackedVec3<T>) -> Self {
+        Self::new(vec.x, vec.y, vec.z)
+    }
+}
+
 #[repr(C, align(16))]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Vec4<T> {
@@ -40,8 +79,153 @@ impl<T> Vec4<T> {
     }
 }
 
+macro_rules! impl_vec_op {
+    ($vec:ident { $($field:ident),+ }, $op:ident::$method:ident, $assign:ident::$assign_method:ident) => {
+        impl<T: $op<Output = T>> $op for $vec<T> {
+            type Output = Self;
+
+            #[inline]
+            fn $method(self, rhs: Self) -> Self {
+                Self { $($field: self.$field.$method(rhs.$field)),+ }
+            }
+        }
+
+        impl<T: Copy + $op<Output = T>> $op<T> for $vec<T> {
+            type Output = Self;
+
+            #[inline]
+            fn $method(self, rhs: T) -> Self {
+                Self { $($field: self.$field.$method(rhs)),+ }
+            }
+        }
+
+        impl<T: $assign> $assign for $vec<T> {
+            #[inline]
+            fn $assign_method(&mut self, rhs: Self) {
+                $(self.$field.$assign_method(rhs.$field);)+
+            }
+        }
+
+        impl<T: Copy + $assign> $assign<T> for $vec<T> {
+            #[inline]
+            fn $assign_method(&mut self, rhs: T) {
+                $(self.$field.$assign_method(rhs);)+
+            }
+        }
+    };
+}
+
+// component wise, with a vector or a scalar applied to every component
+macro_rules! impl_vec_ops {
+    ($vec:ident { $($field:ident),+ }, $len:literal) => {
+        impl<T: Copy> $vec<T> {
+            /// Creates a vector with every component set to `value`.
+            #[inline]
+            pub const fn splat(value: T) -> Self {
+                Self { $($field: value),+ }
+            }
+
+            #[inline]
+            pub const fn to_array(self) -> [T; $len] {
+                [$(self.$field),+]
+            }
+        }
+
+        impl_vec_op!($vec { $($field),+ }, Add::add, AddAssign::add_assign);
+        impl_vec_op!($vec { $($field),+ }, Sub::sub, SubAssign::sub_assign);
+        impl_vec_op!($vec { $($field),+ }, Mul::mul, MulAssign::mul_assign);
+        impl_vec_op!($vec { $($field),+ }, Div::div, DivAssign::div_assign);
+
+        impl<T: Neg<Output = T>> Neg for $vec<T> {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T> From<[T; $len]> for $vec<T> {
+            #[inline]
+            fn from([$($field),+]: [T; $len]) -> Self {
+                Self { $($field),+ }
+            }
+        }
+
+        impl<T> From<$vec<T>> for [T; $len] {
+            #[inline]
+            fn from(vec: $vec<T>) -> Self {
+                [$(vec.$field),+]
+            }
+        }
+    };
+}
+
+impl_vec_ops!(Vec2 { x, y }, 2);
+impl_vec_ops!(Vec3 { x, y, z }, 3);
+impl_vec_ops!(Vec4 { x, y, z, w }, 4);
+impl_vec_ops!(PackedVec3 { x, y, z }, 3);
+
+macro_rules! impl_vec_methods {
+    ($vec:ident { $first:ident $(, $field:ident)* }, $ty:ty) => {
+        impl $vec<$ty> {
+            #[inline]
+            pub const fn dot(self, other: Self) -> $ty {
+                self.$first * other.$first $(+ self.$field * other.$field)*
+            }
+
+            /// Component wise minimum.
+            #[inline]
+            pub fn min(self, other: Self) -> Self {
+                Self {
+                    $first: self.$first.min(other.$first),
+                    $($field: self.$field.min(other.$field),)*
+                }
+            }
+
+            /// Component wise maximum.
+            #[inline]
+            pub fn max(self, other: Self) -> Self {
+                Self {
+                    $first: self.$first.max(other.$first),
+                    $($field: self.$field.max(other.$field),)*
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_float_vec_methods {
+    ($vec:ident { $($field:ident),+ }, $ty:ty) => {
+        impl $vec<$ty> {
+            #[inline]
+            pub fn length(self) -> $ty {
+                self.dot(self).sqrt()
+            }
+
+            /// Scales the vector to a length of 1, the components are NaN if
+            /// the length is 0.
+            #[inline]
+            pub fn normalize(self) -> Self {
+                self / self.length()
+            }
+
+            /// Interpolates linearly from `self` at `t = 0` to `other` at `t = 1`.
+            #[inline]
+            pub const fn lerp(self, other: Self, t: $ty) -> Self {
+                Self { $($field: self.$field + (other.$field - self.$field) * t),+ }
+            }
+        }
+    };
+}
+
 macro_rules! impl_vec {
     ($ty:ty, zero: $zero:expr) => {
+        impl_vec_methods!(Vec2 { x, y }, $ty);
+        impl_vec_methods!(Vec3 { x, y, z }, $ty);
+        impl_vec_methods!(Vec4 { x, y, z, w }, $ty);
+        impl_vec_methods!(PackedVec3 { x, y, z }, $ty);
+
         impl Vec2<$ty> {
             pub const ZERO: Self = Self::new($zero, $zero);
         }
@@ -53,9 +237,229 @@ macro_rules! impl_vec {
         impl Vec4<$ty> {
             pub const ZERO: Self = Self::new($zero, $zero, $zero, $zero);
         }
+
+        impl PackedVec3<$ty> {
+            pub const ZERO: Self = Self::new($zero, $zero, $zero);
+        }
+    };
+}
+
+macro_rules! impl_float_vec {
+    ($ty:ty) => {
+        impl_float_vec_methods!(Vec2 { x, y }, $ty);
+        impl_float_vec_methods!(Vec3 { x, y, z }, $ty);
+        impl_float_vec_methods!(Vec4 { x, y, z, w }, $ty);
+        impl_float_vec_methods!(PackedVec3 { x, y, z }, $ty);
     };
 }
 
 impl_vec!(f32, zero: 0.0);
+impl_vec!(f64, zero: 0.0);
 impl_vec!(i32, zero: 0);
 impl_vec!(u32, zero: 0);
+
+impl_float_vec!(f32);
+impl_float_vec!(f64);
+
+/// A `mat2x2` of two [`Vec2`] columns.
+#[repr(C, align(8))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mat2<T> {
+    pub cols: [Vec2<T>; 2],
+}
+
+impl<T> Mat2<T> {
+    pub const fn from_cols(x: Vec2<T>, y: Vec2<T>) -> Self {
+        Self { cols: [x, y] }
+    }
+}
+
+/// A `mat3x3` of three [`Vec3`] columns, each padded to 16 bytes like in WGSL.
+#[repr(C, align(16))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mat3<T> {
+    pub cols: [Vec3<T>; 3],
+}
+
+impl<T> Mat3<T> {
+    pub const fn from_cols(x: Vec3<T>, y: Vec3<T>, z: Vec3<T>) -> Self {
+        Self { cols: [x, y, z] }
+    }
+}
+
+/// A `mat4x4` of four [`Vec4`] columns.
+#[repr(C, align(16))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mat4<T> {
+    pub cols: [Vec4<T>; 4],
+}
+
+impl<T> Mat4<T> {
+    pub const fn from_cols(x: Vec4<T>, y: Vec4<T>, z: Vec4<T>, w: Vec4<T>) -> Self {
+        Self { cols: [x, y, z, w] }
+    }
+}
+
+macro_rules! impl_mat {
+    ($ty:ty, zero: $zero:expr, one: $one:expr) => {
+        impl Mat2<$ty> {
+            pub const ZERO: Self =
+                Self::from_cols(Vec2::new($zero, $zero), Vec2::new($zero, $zero));
+
+            pub const fn identity() -> Self {
+                Self::from_cols(Vec2::new($one, $zero), Vec2::new($zero, $one))
+            }
+        }
+
+        impl Mat3<$ty> {
+            pub const ZERO: Self = Self::from_cols(
+                Vec3::new($zero, $zero, $zero),
+                Vec3::new($zero, $zero, $zero),
+                Vec3::new($zero, $zero, $zero),
+            );
+
+            pub const fn identity() -> Self {
+                Self::from_cols(
+                    Vec3::new($one, $zero, $zero),
+                    Vec3::new($zero, $one, $zero),
+                    Vec3::new($zero, $zero, $one),
+                )
+            }
+        }
+
+        impl Mat4<$ty> {
+            pub const ZERO: Self = Self::from_cols(
+                Vec4::new($zero, $zero, $zero, $zero),
+                Vec4::new($zero, $zero, $zero, $zero),
+                Vec4::new($zero, $zero, $zero, $zero),
+                Vec4::new($zero, $zero, $zero, $zero),
+            );
+
+            pub const fn identity() -> Self {
+                Self::from_cols(
+                    Vec4::new($one, $zero, $zero, $zero),
+                    Vec4::new($zero, $one, $zero, $zero),
+                    Vec4::new($zero, $zero, $one, $zero),
+                    Vec4::new($zero, $zero, $zero, $one),
+                )
+            }
+        }
+    };
+}
+
+impl_mat!(f32, zero: 0.0, one: 1.0);
+impl_mat!(f64, zero: 0.0, one: 1.0);
+
+// transforms column vectors, like `m * v` in WGSL
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Mul<Vec2<T>> for Mat2<T> {
+    type Output = Vec2<T>;
+
+    #[inline]
+    fn mul(self, v: Vec2<T>) -> Vec2<T> {
+        let [x, y] = self.cols;
+
+        Vec2::new(x.x * v.x + y.x * v.y, x.y * v.x + y.y * v.y)
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Mul<Vec3<T>> for Mat3<T> {
+    type Output = Vec3<T>;
+
+    #[inline]
+    fn mul(self, v: Vec3<T>) -> Vec3<T> {
+        let [x, y, z] = self.cols;
+
+        Vec3::new(
+            x.x * v.x + y.x * v.y + z.x * v.z,
+            x.y * v.x + y.y * v.y + z.y * v.z,
+            x.z * v.x + y.z * v.y + z.z * v.z,
+        )
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Mul<Vec4<T>> for Mat4<T> {
+    type Output = Vec4<T>;
+
+    #[inline]
+    fn mul(self, v: Vec4<T>) -> Vec4<T> {
+        let [x, y, z, w] = self.cols;
+
+        Vec4::new(
+            x.x * v.x + y.x * v.y + z.x * v.z + w.x * v.w,
+            x.y * v.x + y.y * v.y + z.y * v.z + w.y * v.w,
+            x.z * v.x + y.z * v.y + z.z * v.z + w.z * v.w,
+            x.w * v.x + y.w * v.y + z.w * v.z + w.w * v.w,
+        )
+    }
+}
+
+/// An array element followed by `PADDING` bytes, generated for arrays declared
+/// with a `[[stride(N)]]` larger than their element, e.g. arrays of small
+/// structs in uniform buffers, whose stride has to be a multiple of 16.
+///
+/// Converts from `T` and derefs to it, the padding is always zeroed. Generated
+/// structs also convert back from their padded form.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Padded<T, const PADDING: usize> {
+    pub value: T,
+    padding: [u8; PADDING],
+}
+
+impl<T, const PADDING: usize> Padded<T, PADDING> {
+    #[inline]
+    pub const fn new(value: T) -> Self {
+        Self {
+            value,
+            padding: [0; PADDING],
+        }
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Default, const PADDING: usize> Default for Padded<T, PADDING> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug, const PADDING: usize> fmt::Debug for Padded<T, PADDING> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<T: PartialEq, const PADDING: usize> PartialEq for Padded<T, PADDING> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T, const PADDING: usize> From<T> for Padded<T, PADDING> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T, const PADDING: usize> Deref for Padded<T, PADDING> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T, const PADDING: usize> DerefMut for Padded<T, PADDING> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
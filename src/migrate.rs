@@ -0,0 +1,172 @@
+use std::{borrow::Cow, mem::size_of};
+
+/// Layout of a single struct member.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FieldLayout {
+    pub name: Cow<'static, str>,
+    /// WGSL type of the member, e.g. `vec3<f32>`.
+    pub ty: Cow<'static, str>,
+    /// Offset in bytes from the start of the struct.
+    pub offset: u64,
+    /// Size in bytes.
+    pub size: u64,
+}
+
+/// Layout of a struct generated by `wgsl!`.
+///
+/// Generated structs expose theirs through [`Reflect::LAYOUT`], layouts can
+/// also be built at runtime, e.g. from a manifest saved by a previous version
+/// of an application.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StructLayout {
+    pub name: Cow<'static, str>,
+    /// Size in bytes, including trailing padding.
+    pub size: u64,
+    pub fields: Cow<'static, [FieldLayout]>,
+}
+
+impl StructLayout {
+    #[inline]
+    pub fn field(&self, name: &str) -> Option<&FieldLayout> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// Structs with a known memory layout.
+///
+/// # Safety
+/// Implementors must be plain old data, valid for any bit pattern, and
+/// [`LAYOUT`](Reflect::LAYOUT) must describe their actual memory layout.
+pub unsafe trait Reflect: Copy + 'static {
+    const LAYOUT: StructLayout;
+}
+
+/// Fields changed by [`migrate_layout`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MigrationReport {
+    /// Number of items migrated.
+    pub items: usize,
+    /// Fields present in both layouts with the same type, copied over.
+    pub copied: Vec<String>,
+    /// Fields of the old layout that weren't carried over.
+    pub dropped: Vec<String>,
+    /// Fields of the new layout that were zero filled.
+    pub added: Vec<String>,
+}
+
+/// Migrates an array of `Old` items to `New` items by calling `fill` on every item.
+///
+/// # Panics
+/// Panics if the length of `old_bytes` isn't a multiple of the size of `Old`.
+pub fn migrate<Old: Reflect, New: Reflect>(
+    old_bytes: &[u8],
+    fill: impl Fn(&Old) -> New,
+) -> Vec<u8> {
+    let new = items_from_bytes::<Old>(old_bytes)
+        .iter()
+        .map(fill)
+        .collect::<Vec<_>>();
+
+    items_as_bytes(&new).to_vec()
+}
+
+/// Views `items` as bytes, e.g. to save a buffer dump.
+#[inline]
+pub fn items_as_bytes<T: Reflect>(items: &[T]) -> &[u8] {
+    // SAFETY: T is plain old data
+    unsafe { std::slice::from_raw_parts(items.as_ptr() as *const u8, std::mem::size_of_val(items)) }
+}
+
+/// Reads items from `bytes`, which don't have to be aligned.
+///
+/// # Panics
+/// Panics if the length of `bytes` isn't a multiple of the size of `T`.
+pub fn items_from_bytes<T: Reflect>(bytes: &[u8]) -> Vec<T> {
+    let items = item_count(bytes.len(), size_of::<T>());
+
+    (0..items)
+        .map(|i| {
+            let item = &bytes[i * size_of::<T>()..];
+
+            // SAFETY: T is valid for any bit pattern and the slice is at least size_of::<T>() long
+            unsafe { std::ptr::read_unaligned(item.as_ptr() as *const T) }
+        })
+        .collect()
+}
+
+/// Migrates an array of `Old` items to `New` items, see [`migrate_layout`].
+#[inline]
+pub fn migrate_auto<Old: Reflect, New: Reflect>(old_bytes: &[u8]) -> (Vec<u8>, MigrationReport) {
+    migrate_layout(&Old::LAYOUT, &New::LAYOUT, old_bytes)
+}
+
+/// Migrates an array of items from the `old` layout to the `new` layout.
+///
+/// Fields with the same name and type are copied, every other field of `new`
+/// is zero filled, which matches the [`Default`] of generated structs.
+///
+/// # Panics
+/// Panics if the length of `old_bytes` isn't a multiple of the size of `old`.
+pub fn migrate_layout(
+    old: &StructLayout,
+    new: &StructLayout,
+    old_bytes: &[u8],
+) -> (Vec<u8>, MigrationReport) {
+    let items = item_count(old_bytes.len(), old.size as usize);
+
+    let mut report = MigrationReport {
+        items,
+        ..Default::default()
+    };
+
+    let mut copies = Vec::new();
+
+    for field in new.fields.iter() {
+        match old.field(&field.name) {
+            Some(old_field) if old_field.ty == field.ty && old_field.size == field.size => {
+                copies.push((
+                    old_field.offset as usize,
+                    field.offset as usize,
+                    field.size as usize,
+                ));
+                report.copied.push(field.name.to_string());
+            }
+            _ => report.added.push(field.name.to_string()),
+        }
+    }
+
+    for field in old.fields.iter() {
+        if !report.copied.iter().any(|name| *name == field.name) {
+            report.dropped.push(field.name.to_string());
+        }
+    }
+
+    let mut new_bytes = vec![0; items * new.size as usize];
+
+    let old_items = old_bytes.chunks_exact(old.size as usize);
+    let new_items = new_bytes.chunks_exact_mut(new.size as usize);
+
+    for (old_item, new_item) in old_items.zip(new_items) {
+        for &(old_offset, new_offset, size) in &copies {
+            new_item[new_offset..new_offset + size]
+                .copy_from_slice(&old_item[old_offset..old_offset + size]);
+        }
+    }
+
+    (new_bytes, report)
+}
+
+fn item_count(len: usize, item_size: usize) -> usize {
+    if item_size == 0 {
+        return 0;
+    }
+
+    assert!(
+        len.is_multiple_of(item_size),
+        "{} bytes is not a whole number of {} byte items",
+        len,
+        item_size
+    );
+
+    len / item_size
+}
@@ -0,0 +1,197 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use crate::{DeviceError, Error, Instance};
+
+/// Times an [`OomHandler`] may ask to retry a single creation before it fails.
+pub const MAX_OOM_RETRIES: u32 = 3;
+
+/// Kind of resource whose creation ran out of memory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResourceKind {
+    Buffer,
+    Texture,
+}
+
+/// A resource creation that ran out of device memory, passed to the
+/// [`OomHandler`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OomInfo {
+    pub kind: ResourceKind,
+    /// Label of the wgpu resource, e.g. `shatter_buffer`.
+    pub label: &'static str,
+    /// Size in bytes of the resource.
+    pub size: u64,
+    /// Bytes occupied by the buffers and textures of the instance.
+    pub resident_bytes: u64,
+    /// Retries of this creation so far, up to [`MAX_OOM_RETRIES`].
+    pub attempt: u32,
+}
+
+impl fmt::Display for OomInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            ResourceKind::Buffer => "buffer",
+            ResourceKind::Texture => "texture",
+        };
+
+        write!(
+            f,
+            "out of memory creating {} `{}` of {} bytes with {} bytes resident",
+            kind, self.label, self.size, self.resident_bytes
+        )
+    }
+}
+
+/// What to do about a creation that ran out of memory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OomAction {
+    /// Create the resource again, after the handler freed memory, e.g. by
+    /// dropping caches and calling [`Instance::clean`](crate::Instance::clean).
+    Retry,
+    /// Return [`DeviceError::OutOfMemory`](crate::DeviceError::OutOfMemory)
+    /// from fallible creations, e.g. [`Buffer::try_new`](crate::Buffer::try_new),
+    /// others panic.
+    Fail,
+    /// Panic, what happens without a handler.
+    Panic,
+}
+
+/// Decides what to do about a creation that ran out of memory, see
+/// [`Instance::set_oom_handler`](crate::Instance::set_oom_handler).
+pub type OomHandler = Arc<dyn Fn(&OomInfo) -> OomAction + Send + Sync>;
+
+/// Out of memory handler and artificial memory budget of an instance.
+pub(crate) struct OomRecovery {
+    handler: Mutex<Option<OomHandler>>,
+    // 0 if unlimited
+    budget: AtomicU64,
+}
+
+impl OomRecovery {
+    pub fn new(budget: Option<u64>) -> Self {
+        Self {
+            handler: Mutex::new(None),
+            budget: AtomicU64::new(budget.unwrap_or(0)),
+        }
+    }
+
+    pub fn set_handler(&self, handler: Option<OomHandler>) {
+        *self.handler.lock().unwrap() = handler;
+    }
+
+    #[inline]
+    pub fn has_handler(&self) -> bool {
+        self.handler.lock().unwrap().is_some()
+    }
+
+    #[inline]
+    pub fn budget(&self) -> Option<u64> {
+        match self.budget.load(Ordering::Relaxed) {
+            0 => None,
+            budget => Some(budget),
+        }
+    }
+
+    pub fn set_budget(&self, budget: Option<u64>) {
+        self.budget.store(budget.unwrap_or(0), Ordering::Relaxed);
+    }
+
+    /// Asks the handler what to do about `info`, failing once the retries
+    /// are used up and panicking without a handler.
+    pub fn action(&self, info: &OomInfo) -> OomAction {
+        let handler = self.handler.lock().unwrap().clone();
+
+        match handler {
+            Some(_) if info.attempt >= MAX_OOM_RETRIES => OomAction::Fail,
+            Some(handler) => handler(info),
+            None => OomAction::Panic,
+        }
+    }
+}
+
+impl Instance {
+    /// Calls `handler` when creating a buffer or texture runs out of device
+    /// memory, instead of panicking. It may free memory and ask to retry, at
+    /// most [`MAX_OOM_RETRIES`] times per creation.
+    ///
+    /// Creations run in an [error scope](Self::error_scope) while a handler
+    /// is installed, which waits for the device, unless already in one.
+    pub fn set_oom_handler(&self, handler: impl Fn(&OomInfo) -> OomAction + Send + Sync + 'static) {
+        self.oom.set_handler(Some(Arc::new(handler)));
+    }
+
+    /// Panics when out of memory again.
+    pub fn clear_oom_handler(&self) {
+        self.oom.set_handler(None);
+    }
+
+    #[inline]
+    pub fn memory_budget(&self) -> Option<u64> {
+        self.oom.budget()
+    }
+
+    /// Treats creating a buffer or texture as out of memory if it would
+    /// take the bytes occupied by the buffers and textures of the instance
+    /// over `budget`, e.g. to leave room for other applications or to test
+    /// an [OOM handler](Self::set_oom_handler). `None` leaves it to the device.
+    ///
+    /// Dropped resources occupy memory until [`Self::clean`].
+    #[inline]
+    pub fn set_memory_budget(&self, budget: Option<u64>) {
+        self.oom.set_budget(budget);
+    }
+
+    /// Runs `create`, creating a resource of `size` bytes, consulting the
+    /// OOM handler when it runs out of memory.
+    pub(crate) fn create_resource<T>(
+        &self,
+        kind: ResourceKind,
+        label: &'static str,
+        size: u64,
+        create: impl Fn() -> T,
+    ) -> Result<T, Error> {
+        let mut attempt = 0;
+
+        loop {
+            let resident_bytes = self.buffers.resident_bytes() + self.textures.resident_bytes();
+            let over_budget = self
+                .oom
+                .budget()
+                .is_some_and(|budget| resident_bytes.saturating_add(size) > budget);
+
+            let result = if over_budget {
+                Err(DeviceError::OutOfMemory.into())
+            } else if self.oom.has_handler() && !self.in_error_scope() {
+                self.error_scope(&create)
+            } else {
+                // without a handler the device panics when out of memory
+                return Ok(create());
+            };
+
+            match result {
+                Err(Error::Device(DeviceError::OutOfMemory)) => {}
+                result => return result,
+            }
+
+            let info = OomInfo {
+                kind,
+                label,
+                size,
+                resident_bytes,
+                attempt,
+            };
+
+            match self.oom.action(&info) {
+                OomAction::Retry => attempt += 1,
+                OomAction::Fail => return Err(DeviceError::OutOfMemory.into()),
+                OomAction::Panic => panic!("{}", info),
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! An [`egui`] widget drawing [`StatsFrame`]s, enabled by the `overlay` feature.
+
+use crate::StatsFrame;
+
+/// Draws a [`StatsFrame`] as a two column grid, e.g. in a debug window:
+///
+/// ```ignore
+/// egui::Window::new("shatter").show(ctx, |ui| {
+///     ui.add(StatsOverlay::new(&Instance::global().stats_frame()));
+/// });
+/// ```
+pub struct StatsOverlay<'a> {
+    frame: &'a StatsFrame,
+}
+
+impl<'a> StatsOverlay<'a> {
+    #[inline]
+    pub fn new(frame: &'a StatsFrame) -> Self {
+        Self { frame }
+    }
+}
+
+impl egui::Widget for StatsOverlay<'_> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let frame = self.frame;
+
+        let hit_rate = match frame.cache_hit_rate() {
+            Some(rate) => format!("{:.1}%", rate * 100.0),
+            None => String::from("-"),
+        };
+
+        let rows = [
+            ("buffers", frame.live_buffers.to_string()),
+            ("textures", frame.live_textures.to_string()),
+            ("bind groups", frame.live_bind_groups.to_string()),
+            ("pipelines", frame.live_pipelines.to_string()),
+            ("resident", format_bytes(frame.resident_bytes)),
+            ("uploaded", format_bytes(frame.uploaded_bytes)),
+            ("downloaded", format_bytes(frame.downloaded_bytes)),
+            ("dispatches", frame.dispatches.to_string()),
+            ("submissions", frame.submissions.to_string()),
+            ("buffer resizes", frame.buffer_resizes.to_string()),
+            (
+                "cache hits",
+                format!(
+                    "{}/{} ({})",
+                    frame.cache_hits,
+                    frame.cache_hits + frame.cache_misses,
+                    hit_rate
+                ),
+            ),
+            ("frames in flight", frame.frames_in_flight.to_string()),
+        ];
+
+        egui::Grid::new("shatter_stats_overlay")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                for (name, value) in rows {
+                    ui.label(name);
+                    ui.monospace(value);
+                    ui.end_row();
+                }
+            })
+            .response
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
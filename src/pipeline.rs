@@ -14,3 +14,14 @@ pub struct ComputePipelineDescriptor {
     pub module: ShaderModuleId,
     pub entry_point: Cow<'static, str>,
 }
+
+/// A render pipeline drawing with a vertex and a fragment entry point of
+/// `module` into a single color target of `format`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RenderPipelineDescriptor {
+    pub layout: Option<PipelineLayoutId>,
+    pub module: ShaderModuleId,
+    pub vertex_entry_point: Cow<'static, str>,
+    pub fragment_entry_point: Cow<'static, str>,
+    pub format: wgpu::TextureFormat,
+}
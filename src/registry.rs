@@ -0,0 +1,349 @@
+use std::{
+    any::{type_name, Any, TypeId},
+    collections::HashMap,
+    fmt,
+};
+
+use crate::{
+    Binding, BindingMut, Buffer, BufferData, GpuBuffer, StorageTextureBinding, Stored, Texture,
+    TextureDimension, TextureFormat,
+};
+
+type Cast<B> = for<'r> fn(&'r (dyn Any + Send)) -> &'r dyn Binding<B>;
+type CastMut<B> = for<'r> fn(&'r mut (dyn Any + Send)) -> &'r mut dyn BindingMut<B>;
+
+/// A resource that can be stored in a [`ResourceRegistry`].
+pub trait RegistryResource: BindingMut<Self::Binding> + Send + Sized + 'static {
+    /// What the resource is bound as by generated bindings.
+    type Binding: ?Sized + 'static;
+}
+
+impl<T: BufferData + ?Sized + 'static> RegistryResource for Buffer<T>
+where
+    Buffer<T>: Send,
+{
+    type Binding = T;
+}
+
+impl<T: BufferData + ?Sized + 'static> RegistryResource for GpuBuffer<T>
+where
+    GpuBuffer<T>: Send,
+{
+    type Binding = T;
+}
+
+impl<Format, Dimension> RegistryResource for Texture<Format, Dimension, false>
+where
+    Format: TextureFormat + Stored + 'static,
+    Format::TexelFormat: 'static,
+    Dimension: TextureDimension<Format> + 'static,
+    Texture<Format, Dimension, false>: Send,
+{
+    type Binding = StorageTextureBinding<Format::TexelFormat, Dimension>;
+}
+
+fn cast<R: RegistryResource>(resource: &(dyn Any + Send)) -> &dyn Binding<R::Binding> {
+    resource.downcast_ref::<R>().unwrap()
+}
+
+fn cast_mut<R: RegistryResource>(
+    resource: &mut (dyn Any + Send),
+) -> &mut dyn BindingMut<R::Binding> {
+    resource.downcast_mut::<R>().unwrap()
+}
+
+struct Entry {
+    resource: Box<dyn Any + Send>,
+    type_name: &'static str,
+    binding: TypeId,
+    /// `(Cast<B>, CastMut<B>)` where `B` is the binding of the resource.
+    casts: Box<dyn Any + Send + Sync>,
+}
+
+/// Resources stored by name, from which generated bindings can be built with
+/// `Bindings::from_registry`.
+///
+/// Useful when the resources a kernel reads and writes are described by data,
+/// e.g. a frame graph loaded from an asset, but the kernel itself is known at
+/// compile time.
+#[derive(Default)]
+pub struct ResourceRegistry {
+    entries: HashMap<String, Entry>,
+}
+
+impl ResourceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts `resource` under `name`, replacing any resource already stored there.
+    pub fn insert<R: RegistryResource>(&mut self, name: impl Into<String>, resource: R) {
+        let casts: (Cast<R::Binding>, CastMut<R::Binding>) = (cast::<R>, cast_mut::<R>);
+
+        let entry = Entry {
+            resource: Box::new(resource),
+            type_name: type_name::<R>(),
+            binding: TypeId::of::<R::Binding>(),
+            casts: Box::new(casts),
+        };
+
+        self.entries.insert(name.into(), entry);
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    /// Gets the resource stored under `name`, if it's an `R`.
+    pub fn get<R: RegistryResource>(&self, name: &str) -> Option<&R> {
+        self.entries.get(name)?.resource.downcast_ref()
+    }
+
+    /// Gets the resource stored under `name`, if it's an `R`.
+    pub fn get_mut<R: RegistryResource>(&mut self, name: &str) -> Option<&mut R> {
+        self.entries.get_mut(name)?.resource.downcast_mut()
+    }
+
+    /// Removes the resource stored under `name`, if it's an `R`.
+    pub fn remove<R: RegistryResource>(&mut self, name: &str) -> Option<R> {
+        if !self.entries.get(name)?.resource.is::<R>() {
+            return None;
+        }
+
+        let entry = self.entries.remove(name)?;
+
+        entry.resource.downcast().ok().map(|resource| *resource)
+    }
+
+    /// Borrows the resources of every request at once, used by generated bindings.
+    ///
+    /// `rename` maps field names to the names the resources are stored under.
+    /// Every problem is reported, not only the first.
+    pub fn borrow_bindings<'a>(
+        &'a mut self,
+        requests: &[RegistryRequest],
+        rename: &[(&str, &str)],
+    ) -> Result<RegistryBorrows<'a>, MissingResource> {
+        let names = requests
+            .iter()
+            .map(|request| {
+                rename
+                    .iter()
+                    .find(|(field, _)| *field == request.field)
+                    .map_or(request.field, |(_, name)| *name)
+            })
+            .collect::<Vec<_>>();
+
+        let mut errors = Vec::new();
+
+        for (request, &name) in requests.iter().zip(&names) {
+            let kind = match self.entries.get(name) {
+                None if request.optional => continue,
+                None => RegistryErrorKind::Missing,
+                Some(entry) if entry.binding != request.binding => RegistryErrorKind::WrongType {
+                    expected: request.binding_name,
+                    found: entry.type_name,
+                },
+                Some(_) => continue,
+            };
+
+            errors.push(RegistryError {
+                field: request.field,
+                name: name.into(),
+                kind,
+            });
+        }
+
+        for (i, a) in requests.iter().enumerate() {
+            for (j, b) in requests.iter().enumerate().skip(i + 1) {
+                if names[i] == names[j] && (a.writable || b.writable) {
+                    errors.push(RegistryError {
+                        field: a.field,
+                        name: names[i].into(),
+                        kind: RegistryErrorKind::Conflict { other: b.field },
+                    });
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(MissingResource { errors });
+        }
+
+        let mut borrows = Vec::new();
+        borrows.resize_with(requests.len(), || None);
+
+        // every entry is borrowed once, shared borrows are handed to every reader
+        for (name, entry) in self.entries.iter_mut() {
+            let Entry {
+                resource, casts, ..
+            } = entry;
+            let casts = &**casts;
+
+            let mut users = names.iter().enumerate().filter(|(_, n)| **n == name);
+
+            let (first, _) = match users.next() {
+                Some(user) => user,
+                None => continue,
+            };
+
+            if requests[first].writable {
+                borrows[first] = Some(Borrow::Mut(&mut **resource, casts));
+            } else {
+                let resource = &**resource;
+
+                borrows[first] = Some(Borrow::Shared(resource, casts));
+
+                for (i, _) in users {
+                    borrows[i] = Some(Borrow::Shared(resource, casts));
+                }
+            }
+        }
+
+        Ok(RegistryBorrows { borrows })
+    }
+}
+
+/// A binding requested from a [`ResourceRegistry`] by generated bindings.
+#[derive(Clone, Copy, Debug)]
+pub struct RegistryRequest {
+    /// Name of the binding in WGSL.
+    pub field: &'static str,
+    pub binding: TypeId,
+    pub binding_name: &'static str,
+    pub writable: bool,
+    /// Whether the binding may be missing from the registry.
+    pub optional: bool,
+}
+
+impl RegistryRequest {
+    #[inline]
+    pub fn new<B: ?Sized + 'static>(field: &'static str, writable: bool, optional: bool) -> Self {
+        Self {
+            field,
+            binding: TypeId::of::<B>(),
+            binding_name: type_name::<B>(),
+            writable,
+            optional,
+        }
+    }
+}
+
+enum Borrow<'a> {
+    Shared(&'a (dyn Any + Send), &'a (dyn Any + Send + Sync)),
+    Mut(&'a mut (dyn Any + Send), &'a (dyn Any + Send + Sync)),
+}
+
+/// Resources borrowed by [`ResourceRegistry::borrow_bindings`], indexed like the requests.
+pub struct RegistryBorrows<'a> {
+    borrows: Vec<Option<Borrow<'a>>>,
+}
+
+impl<'a> RegistryBorrows<'a> {
+    /// Takes the binding of request `index`, `None` if it was optional and missing.
+    ///
+    /// # Panics
+    /// Panics if `B` isn't the type of the request or it was already taken.
+    pub fn take_optional<B: ?Sized + 'static>(
+        &mut self,
+        index: usize,
+    ) -> Option<&'a dyn Binding<B>> {
+        Some(match self.borrows[index].take()? {
+            Borrow::Shared(resource, casts) => casts_of::<B>(casts).0(resource),
+            Borrow::Mut(resource, casts) => casts_of::<B>(casts).0(resource),
+        })
+    }
+
+    /// Takes the binding of request `index`.
+    ///
+    /// # Panics
+    /// Panics if `B` isn't the type of the request or it was already taken.
+    pub fn take<B: ?Sized + 'static>(&mut self, index: usize) -> &'a dyn Binding<B> {
+        self.take_optional(index).expect("binding already taken")
+    }
+
+    /// Takes the binding of writable request `index`.
+    ///
+    /// # Panics
+    /// Panics if `B` isn't the type of the request, it wasn't writable or was already taken.
+    pub fn take_mut<B: ?Sized + 'static>(&mut self, index: usize) -> &'a mut dyn BindingMut<B> {
+        match self.borrows[index].take() {
+            Some(Borrow::Mut(resource, casts)) => casts_of::<B>(casts).1(resource),
+            _ => panic!("binding {} isn't writable or already taken", index),
+        }
+    }
+}
+
+fn casts_of<B: ?Sized + 'static>(casts: &(dyn Any + Send + Sync)) -> (Cast<B>, CastMut<B>) {
+    *casts
+        .downcast_ref::<(Cast<B>, CastMut<B>)>()
+        .expect("binding type doesn't match the request")
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegistryErrorKind {
+    /// No resource is stored under the name.
+    Missing,
+    /// The resource can't be bound as the binding type.
+    WrongType {
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// The resource is also requested by `other` and at least one of them is written to.
+    Conflict { other: &'static str },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegistryError {
+    /// Name of the binding in WGSL.
+    pub field: &'static str,
+    /// Name the resource was looked up by.
+    pub name: String,
+    pub kind: RegistryErrorKind,
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` (\"{}\"): ", self.field, self.name)?;
+
+        match self.kind {
+            RegistryErrorKind::Missing => write!(f, "no resource with this name"),
+            RegistryErrorKind::WrongType { expected, found } => {
+                write!(f, "expected a binding of {}, found {}", expected, found)
+            }
+            RegistryErrorKind::Conflict { other } => {
+                write!(f, "resource is also bound to `{}` and written to", other)
+            }
+        }
+    }
+}
+
+/// Every problem found when building bindings from a [`ResourceRegistry`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MissingResource {
+    pub errors: Vec<RegistryError>,
+}
+
+impl MissingResource {
+    /// Gets the errors of `field`.
+    pub fn field_errors<'a>(
+        &'a self,
+        field: &'a str,
+    ) -> impl Iterator<Item = &'a RegistryError> + 'a {
+        self.errors.iter().filter(move |error| error.field == field)
+    }
+}
+
+impl fmt::Display for MissingResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "failed to build bindings from registry:")?;
+
+        for error in &self.errors {
+            writeln!(f, "  {}", error)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for MissingResource {}
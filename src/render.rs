@@ -1 +1,226 @@
+use std::{borrow::Cow, ops::Range};
 
+use smallvec::SmallVec;
+
+use crate::{
+    resolve_bind_groups, resolve_layouts, texture_view_dimension::D2, Bindings, Instance,
+    PipelineLayoutDescriptor, RenderPipelineDescriptor, Texture, TextureFormat, TextureId,
+    TransferReport,
+};
+
+/// A vertex and fragment entry point drawn together, implemented by `wgsl!`
+/// for every fragment entry point.
+pub trait RenderShader<'a> {
+    type Bindings: Bindings;
+
+    const SOURCE: &'static str;
+    const VERTEX_ENTRY_POINT: &'static str;
+    const FRAGMENT_ENTRY_POINT: &'static str;
+}
+
+/// A texture a [`RenderShaderBuilder`] draws into.
+pub trait ColorTarget {
+    fn texture_id(&self) -> &TextureId;
+
+    fn wgpu_format(&self) -> wgpu::TextureFormat;
+
+    /// Uploads pending CPU changes before drawing, returns the number of bytes uploaded.
+    fn upload(&self) -> u64;
+
+    /// Called after drawing, the CPU side is then downloaded on access.
+    fn mark_drawn(&mut self);
+}
+
+impl<Format: TextureFormat> ColorTarget for Texture<Format, D2, false> {
+    #[inline]
+    fn texture_id(&self) -> &TextureId {
+        Texture::texture_id(self)
+    }
+
+    #[inline]
+    fn wgpu_format(&self) -> wgpu::TextureFormat {
+        Texture::wgpu_format(self)
+    }
+
+    #[inline]
+    fn upload(&self) -> u64 {
+        Texture::upload(self)
+    }
+
+    #[inline]
+    fn mark_drawn(&mut self) {
+        self.mark_needs_download();
+    }
+}
+
+pub struct RenderShaderBuilder<'a, S: RenderShader<'a>> {
+    bindings: S::Bindings,
+    target: Option<&'a mut dyn ColorTarget>,
+    clear: Option<wgpu::Color>,
+    source: Cow<'static, str>,
+    last_transfer_report: TransferReport,
+}
+
+impl<'a, S: RenderShader<'a>> RenderShaderBuilder<'a, S> {
+    #[inline]
+    pub fn new(bindings: S::Bindings) -> Self {
+        Self {
+            bindings,
+            target: None,
+            clear: None,
+            source: Cow::Borrowed(S::SOURCE),
+            last_transfer_report: TransferReport::ZERO,
+        }
+    }
+
+    #[inline]
+    pub fn take_binding(self) -> S::Bindings {
+        self.bindings
+    }
+
+    /// Sets the texture subsequent draws render into, e.g. a
+    /// [`Texture2d`](crate::Texture2d) whose format matches the output of the
+    /// fragment entry point.
+    #[inline]
+    pub fn with_color_target(&mut self, target: &'a mut dyn ColorTarget) -> &mut Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Clears the color target to `color` before each draw, by default the
+    /// draws blend over its contents.
+    #[inline]
+    pub fn with_clear(&mut self, color: impl Into<wgpu::Color>) -> &mut Self {
+        self.clear = Some(color.into());
+        self
+    }
+
+    /// Overrides the source of the shader for this builder, see
+    /// [`ComputeShaderBuilder::with_source`](crate::ComputeShaderBuilder::with_source).
+    #[inline]
+    pub fn with_source(&mut self, source: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.source = source.into();
+        self
+    }
+
+    /// Gets the transfers performed by the last draw.
+    #[inline]
+    pub fn last_transfer_report(&self) -> TransferReport {
+        self.last_transfer_report
+    }
+
+    /// Draws a single triangle covering the target, for vertex entry points
+    /// deriving a fullscreen triangle from `vertex_index`.
+    #[inline]
+    pub fn draw_fullscreen_triangle(&mut self) -> &mut Self {
+        self.draw(0..3, 0..1)
+    }
+
+    /// Draws `vertices` for each of `instances`, the vertex entry point gets
+    /// their indices through `vertex_index` and `instance_index`.
+    ///
+    /// # Panics
+    /// Panics if no color target is set.
+    pub fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>) -> &mut Self {
+        #[cfg(debug_assertions)]
+        if let Err(report) = self.bindings.validate() {
+            panic!("{}", report);
+        }
+
+        let target = self.target.as_mut().unwrap_or_else(|| {
+            panic!(
+                "`{}` has no color target, set one with `with_color_target`",
+                S::FRAGMENT_ENTRY_POINT
+            )
+        });
+
+        let instance = Instance::global();
+        instance.clean_if_due();
+
+        let mut report = TransferReport::ZERO;
+        self.bindings.read(&mut report);
+        report.textures_touched += 1;
+        report.uploaded_bytes += target.upload();
+        self.last_transfer_report = report;
+
+        let layouts = resolve_layouts(instance, self.bindings.bind_group_layout_descriptors());
+        let bind_group_ids = resolve_bind_groups(instance, &self.bindings, &layouts);
+
+        let bind_groups = bind_group_ids
+            .iter()
+            .map(|id| instance.bind_groups.get(id).unwrap())
+            .collect::<SmallVec<[_; 4]>>();
+
+        let pipeline_layout = instance.get_pipeline_layout(PipelineLayoutDescriptor {
+            bind_group_layouts: layouts,
+            push_constant_ranges: Vec::new(),
+        });
+
+        let render_pipeline_id = instance.get_render_pipeline(RenderPipelineDescriptor {
+            layout: Some(pipeline_layout),
+            module: instance.get_shader_module(self.source.clone()),
+            vertex_entry_point: Cow::Borrowed(S::VERTEX_ENTRY_POINT),
+            fragment_entry_point: Cow::Borrowed(S::FRAGMENT_ENTRY_POINT),
+            format: target.wgpu_format(),
+        });
+
+        let render_pipeline = instance.render_pipelines.get(&render_pipeline_id).unwrap();
+
+        let view = instance
+            .textures
+            .get(target.texture_id())
+            .unwrap()
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = instance
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some(&format!(
+                    "shatter_command_encoder({})",
+                    S::FRAGMENT_ENTRY_POINT
+                )),
+            });
+
+        {
+            let label = format!("shatter_render_pass({})", S::FRAGMENT_ENTRY_POINT);
+
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(&label),
+                color_attachments: &[wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: match self.clear {
+                            Some(color) => wgpu::LoadOp::Clear(color),
+                            None => wgpu::LoadOp::Load,
+                        },
+                        store: true,
+                    },
+                }],
+                depth_stencil_attachment: None,
+            });
+
+            render_pass.set_pipeline(&render_pipeline);
+
+            for (i, bind_group) in bind_groups.iter().enumerate() {
+                render_pass.set_bind_group(i as u32, bind_group, &[]);
+            }
+
+            render_pass.draw(vertices, instances);
+        }
+
+        instance.queue.submit(std::iter::once(encoder.finish()));
+        instance.submitted(
+            None,
+            &[
+                Cow::Borrowed(S::VERTEX_ENTRY_POINT),
+                Cow::Borrowed(S::FRAGMENT_ENTRY_POINT),
+            ],
+        );
+
+        target.mark_drawn();
+        self.bindings.write();
+
+        self
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{Binding, BindingResource, Instance, ResourceInfo, SamplerId, TransferReport};
+
+/// What `sampler` variables are bound as by generated bindings.
+pub struct SamplerBinding;
+
+/// How a shader samples textures, bound to `sampler` variables.
+///
+/// Samplers live on the GPU only, so binding one never transfers anything.
+pub struct Sampler {
+    id: SamplerId,
+}
+
+impl Sampler {
+    /// Creates a sampler clamping to the edge and filtering linearly.
+    pub fn linear() -> Self {
+        Self::new(&wgpu::SamplerDescriptor {
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        })
+    }
+
+    /// Creates a sampler clamping to the edge and picking the nearest texel.
+    pub fn nearest() -> Self {
+        Self::new(&Default::default())
+    }
+
+    /// Creates a sampler from `desc`.
+    ///
+    /// # Panics
+    /// Panics if `desc` has a `compare` function, comparison samplers aren't
+    /// supported.
+    pub fn new(desc: &wgpu::SamplerDescriptor) -> Self {
+        assert!(
+            desc.compare.is_none(),
+            "comparison samplers are not supported"
+        );
+
+        let instance = Instance::global();
+
+        let sampler = instance.device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("shatter_sampler"),
+            ..desc.clone()
+        });
+
+        let id = instance.samplers.stable_id("shatter_sampler");
+        instance.samplers.insert(id.clone(), sampler);
+
+        Self { id }
+    }
+
+    #[inline]
+    pub fn sampler_id(&self) -> &SamplerId {
+        &self.id
+    }
+}
+
+impl Binding<SamplerBinding> for Sampler {
+    fn binding_resource(&self) -> BindingResource {
+        BindingResource::Sampler(self.id.clone())
+    }
+
+    fn resource_info(&self) -> ResourceInfo {
+        ResourceInfo::Sampler {
+            id: self.id.clone(),
+        }
+    }
+
+    fn prepare(&self, _report: &mut TransferReport) {}
+
+    fn read(&self, _report: &mut TransferReport) {}
+}
@@ -0,0 +1,174 @@
+use std::{
+    any::Any,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    sync::Arc,
+};
+
+use wgpu::util::DeviceExt;
+
+use crate::{
+    items_as_bytes, Binding, BindingResource, Buffer, BufferBinding, BufferData, BufferId,
+    Instance, Reflect, ResourceInfo, TransferReport, BUFFER_USAGES,
+};
+
+/// An immutable buffer shared by every handle created with the same contents,
+/// see [`Buffer::immutable_shared`].
+///
+/// Handles of the same type and bytes point to a single gpu buffer, uploaded
+/// when the first handle is created. The buffer leaves the pool when the last
+/// handle is dropped and is freed by the next [`Instance::clean`].
+///
+/// Shared buffers can't be mutated, they have no `DerefMut` and only implement
+/// [`Binding`], not [`BindingMut`](crate::BindingMut), so they can't be bound
+/// as `read_write` storage.
+pub struct SharedBuffer<T: Reflect + Send + Sync> {
+    inner: Arc<SharedData<T>>,
+}
+
+struct SharedData<T> {
+    data: T,
+    id: BufferId,
+    key: u64,
+}
+
+impl<T: Reflect> SharedData<T> {
+    #[inline]
+    fn bytes(&self) -> &[u8] {
+        items_as_bytes(std::slice::from_ref(&self.data))
+    }
+}
+
+impl<T> Drop for SharedData<T> {
+    fn drop(&mut self) {
+        // another handle may have replaced the entry since
+        Instance::global()
+            .shared_buffers
+            .remove_if(&self.key, |_, shared| shared.strong_count() == 0);
+    }
+}
+
+/// Hashes `bytes` along with the layout of `T`, so identical bytes of
+/// different types don't share a buffer.
+fn shared_key<T: Reflect>(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    T::LAYOUT.hash(&mut hasher);
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<T: Reflect + Send + Sync> SharedBuffer<T> {
+    /// Gets the pooled buffer holding `data`, uploading it if there is none.
+    pub fn new(data: &T) -> Self {
+        let instance = Instance::global();
+
+        let bytes = items_as_bytes(std::slice::from_ref(data));
+        let key = shared_key::<T>(bytes);
+
+        let pooled = instance
+            .shared_buffers
+            .get(&key)
+            .and_then(|shared| shared.upgrade())
+            .and_then(|shared| shared.downcast().ok())
+            .filter(|shared: &Arc<SharedData<T>>| shared.bytes() == bytes);
+
+        if let Some(inner) = pooled {
+            return Self { inner };
+        }
+
+        let buffer = instance
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("shatter_shared_buffer"),
+                contents: bytes,
+                usage: BUFFER_USAGES,
+            });
+
+        let size = (bytes.len() as u64).max(4);
+
+        let id = instance.buffers.stable_id(&("shatter_shared_buffer", key));
+        instance.buffers.insert_sized(id.clone(), buffer, size);
+        instance.stats.uploaded(bytes.len() as u64);
+
+        let inner = Arc::new(SharedData {
+            data: *data,
+            id,
+            key,
+        });
+
+        let shared: Arc<dyn Any + Send + Sync> = inner.clone();
+        instance.shared_buffers.insert(key, Arc::downgrade(&shared));
+
+        Self { inner }
+    }
+
+    #[inline]
+    pub fn id(&self) -> BufferId {
+        self.inner.id.clone()
+    }
+
+    /// Returns true if both handles share a gpu buffer.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<T: Reflect + Send + Sync> Clone for SharedBuffer<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Reflect + Send + Sync> Deref for SharedBuffer<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner.data
+    }
+}
+
+impl<T: Reflect + Send + Sync> Binding<T> for SharedBuffer<T> {
+    fn binding_resource(&self) -> BindingResource {
+        BindingResource::Buffer(BufferBinding {
+            buffer: self.id(),
+            offset: 0,
+            size: None,
+        })
+    }
+
+    fn resource_info(&self) -> ResourceInfo {
+        ResourceInfo::Buffer {
+            id: self.id(),
+            size: (std::mem::size_of::<T>() as u64).max(4),
+            usage: BUFFER_USAGES,
+        }
+    }
+
+    // the contents were uploaded when the buffer was pooled
+    fn prepare(&self, report: &mut TransferReport) {
+        report.buffers_touched += 1;
+    }
+
+    fn read(&self, report: &mut TransferReport) {
+        report.buffers_touched += 1;
+    }
+
+    fn data_size(&self) -> Option<u64> {
+        Some(std::mem::size_of::<T>() as u64)
+    }
+}
+
+impl<T: BufferData + Reflect + Send + Sync> Buffer<T> {
+    /// Gets a [`SharedBuffer`] holding `data`, sharing the gpu buffer of any
+    /// live handle with the same contents.
+    #[inline]
+    pub fn immutable_shared(data: &T) -> SharedBuffer<T> {
+        SharedBuffer::new(data)
+    }
+}
@@ -0,0 +1,309 @@
+use std::{collections::BTreeMap, fmt, hash::Hash};
+
+use dashmap::DashMap;
+
+use crate::{Id, IdMap, Instance};
+
+/// A resource or cache entry in an [`InstanceSnapshot`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotEntry {
+    /// The raw id, see [`Id::raw`].
+    pub id: u64,
+    /// The key of a cache entry, e.g. the `Debug` output of a canonical
+    /// descriptor or a shader source. `None` for resources.
+    pub key: Option<String>,
+}
+
+impl SnapshotEntry {
+    /// Entries are matched by key if they have one, by id otherwise.
+    fn identity(&self) -> (Option<&str>, u64) {
+        match self.key {
+            Some(ref key) => (Some(key), 0),
+            None => (None, self.id),
+        }
+    }
+}
+
+impl fmt::Display for SnapshotEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Id({})", self.id)?;
+
+        if let Some(ref key) = self.key {
+            write!(f, " {}", key)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The resources and cache entries of an [`Instance`] at one point in time,
+/// see [`Instance::snapshot`].
+///
+/// Categories are named after the [`Instance`] fields they're taken from and
+/// entries are sorted by id, so with
+/// [deterministic ids](crate::InstanceDescriptor::deterministic_ids) two runs
+/// of the same program produce identical snapshots.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InstanceSnapshot {
+    pub categories: BTreeMap<String, Vec<SnapshotEntry>>,
+}
+
+impl InstanceSnapshot {
+    /// Gets the entries of `category`, empty if there is no such category.
+    pub fn entries(&self, category: &str) -> &[SnapshotEntry] {
+        self.categories
+            .get(category)
+            .map_or(&[], |entries| entries.as_slice())
+    }
+
+    /// Total number of entries in every category.
+    pub fn len(&self) -> usize {
+        self.categories.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Lists what changed from `self` to `other`.
+    ///
+    /// A cache entry whose key maps to a different id in `other` is changed,
+    /// i.e. the cached object was recreated.
+    pub fn diff(&self, other: &Self) -> SnapshotDiff {
+        let empty = Vec::new();
+        let mut categories = BTreeMap::new();
+
+        let names = self.categories.keys().chain(other.categories.keys());
+
+        for name in names {
+            if categories.contains_key(name) {
+                continue;
+            }
+
+            let before = self.categories.get(name).unwrap_or(&empty);
+            let after = other.categories.get(name).unwrap_or(&empty);
+
+            let diff = CategoryDiff::new(before, after);
+
+            if !diff.is_empty() {
+                categories.insert(name.clone(), diff);
+            }
+        }
+
+        SnapshotDiff { categories }
+    }
+}
+
+/// The difference between two [`InstanceSnapshot`]s, see [`InstanceSnapshot::diff`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotDiff {
+    /// Only categories with differences are present.
+    pub categories: BTreeMap<String, CategoryDiff>,
+}
+
+impl SnapshotDiff {
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+    }
+
+    /// Gets the added entries of `category`.
+    pub fn added(&self, category: &str) -> &[SnapshotEntry] {
+        self.categories
+            .get(category)
+            .map_or(&[], |diff| diff.added.as_slice())
+    }
+
+    /// Gets the removed entries of `category`.
+    pub fn removed(&self, category: &str) -> &[SnapshotEntry] {
+        self.categories
+            .get(category)
+            .map_or(&[], |diff| diff.removed.as_slice())
+    }
+}
+
+/// Prints an entry per line, prefixed with `+` if added, `-` if removed and
+/// `~` if changed.
+impl fmt::Display for SnapshotDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, diff) in &self.categories {
+            for entry in &diff.added {
+                writeln!(f, "+ {}: {}", name, entry)?;
+            }
+
+            for entry in &diff.removed {
+                writeln!(f, "- {}: {}", name, entry)?;
+            }
+
+            for change in &diff.changed {
+                writeln!(
+                    f,
+                    "~ {}: {} -> Id({})",
+                    name, change.before, change.after.id
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The differences in one category of an [`InstanceSnapshot`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CategoryDiff {
+    pub added: Vec<SnapshotEntry>,
+    pub removed: Vec<SnapshotEntry>,
+    pub changed: Vec<SnapshotChange>,
+}
+
+impl CategoryDiff {
+    fn new(before: &[SnapshotEntry], after: &[SnapshotEntry]) -> Self {
+        let before_by_identity = before
+            .iter()
+            .map(|entry| (entry.identity(), entry))
+            .collect::<BTreeMap<_, _>>();
+        let after_by_identity = after
+            .iter()
+            .map(|entry| (entry.identity(), entry))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut diff = Self::default();
+
+        for entry in after {
+            match before_by_identity.get(&entry.identity()) {
+                Some(before) if *before != entry => diff.changed.push(SnapshotChange {
+                    before: (*before).clone(),
+                    after: entry.clone(),
+                }),
+                Some(_) => {}
+                None => diff.added.push(entry.clone()),
+            }
+        }
+
+        for entry in before {
+            if !after_by_identity.contains_key(&entry.identity()) {
+                diff.removed.push(entry.clone());
+            }
+        }
+
+        diff
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A cache entry whose key maps to a different id.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotChange {
+    pub before: SnapshotEntry,
+    pub after: SnapshotEntry,
+}
+
+fn resources<T>(map: &IdMap<T>) -> Vec<SnapshotEntry> {
+    let mut entries = map
+        .iter()
+        .map(|entry| SnapshotEntry {
+            id: entry.key().raw(),
+            key: None,
+        })
+        .collect::<Vec<_>>();
+
+    entries.sort();
+    entries
+}
+
+fn cache<K: Eq + Hash, T>(
+    map: &DashMap<K, Id<T>>,
+    key: impl Fn(&K) -> String,
+) -> Vec<SnapshotEntry> {
+    let mut entries = map
+        .iter()
+        .map(|entry| SnapshotEntry {
+            id: entry.value().raw(),
+            key: Some(key(entry.key())),
+        })
+        .collect::<Vec<_>>();
+
+    entries.sort();
+    entries
+}
+
+impl Instance {
+    /// Takes an [`InstanceSnapshot`] of every resource and cache entry, e.g.
+    /// to find out what leaks between two frames with [`InstanceSnapshot::diff`].
+    ///
+    /// Other threads creating resources meanwhile may or may not be captured.
+    pub fn snapshot(&self) -> InstanceSnapshot {
+        let mut categories = BTreeMap::new();
+
+        let mut insert = |name: &str, entries| {
+            categories.insert(String::from(name), entries);
+        };
+
+        insert("buffers", resources(&self.buffers));
+        insert("textures", resources(&self.textures));
+        insert("texture_views", resources(&self.texture_views));
+        insert("samplers", resources(&self.samplers));
+        insert("shader_modules", resources(&self.shader_modules));
+        insert("bind_group_layouts", resources(&self.bind_group_layouts));
+        insert("bind_groups", resources(&self.bind_groups));
+        insert("pipeline_layouts", resources(&self.pipeline_layouts));
+        insert("render_pipelines", resources(&self.render_pipelines));
+        insert("compute_pipelines", resources(&self.compute_pipelines));
+
+        insert(
+            "shader_module_sources",
+            cache(&self.shader_module_sources, |source| source.to_string()),
+        );
+        insert(
+            "bind_group_layout_descriptors",
+            cache(&self.bind_group_layout_descriptors, |desc| {
+                format!("{:?}", desc)
+            }),
+        );
+        insert(
+            "bind_group_descriptors",
+            cache(&self.bind_group_descriptors, |desc| format!("{:?}", desc)),
+        );
+        insert(
+            "group_keys",
+            cache(&self.group_keys, |key| format!("{:?}", key)),
+        );
+        insert(
+            "pipeline_layout_descriptors",
+            cache(&self.pipeline_layout_descriptors, |desc| {
+                format!("{:?}", desc)
+            }),
+        );
+        insert(
+            "compute_pipeline_descriptors",
+            cache(&self.compute_pipeline_descriptors, |desc| {
+                format!("{:?}", desc)
+            }),
+        );
+        insert(
+            "render_pipeline_descriptors",
+            cache(&self.render_pipeline_descriptors, |desc| {
+                format!("{:?}", desc)
+            }),
+        );
+        insert(
+            "dispatch_args_params",
+            cache(&self.dispatch_args_params, |params| format!("{:?}", params)),
+        );
+        insert(
+            "convert_intermediates",
+            cache(&self.convert_intermediates, |size| format!("{:?}", size)),
+        );
+
+        InstanceSnapshot { categories }
+    }
+}
@@ -0,0 +1,344 @@
+use std::fmt;
+
+use naga::{
+    valid::{Capabilities, ModuleInfo, ValidationFlags, Validator},
+    ImageClass, ImageDimension, Module, ScalarKind, StorageAccess, StorageClass, StorageFormat,
+    TypeInner,
+};
+
+use crate::{BindGroupLayoutDescriptor, WorkGroupSize};
+
+/// A binding declared differently by an override source than by the generated layout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterfaceDiff {
+    pub group: u32,
+    pub binding: u32,
+    /// Name of the binding in the override source.
+    pub name: String,
+    /// Type in the generated layout, `None` if the layout has no such binding.
+    pub expected: Option<wgpu::BindingType>,
+    /// Type in the override source, `None` if it can't be bound by shatter.
+    pub found: Option<wgpu::BindingType>,
+}
+
+impl fmt::Display for InterfaceDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "group {} binding {} `{}`: ",
+            self.group, self.binding, self.name
+        )?;
+
+        match (&self.expected, &self.found) {
+            (None, _) => write!(f, "not in the generated layout"),
+            (Some(expected), None) => {
+                write!(f, "expected {:?}, found an unsupported type", expected)
+            }
+            (Some(expected), Some(found)) => {
+                write!(f, "expected {:?}, found {:?}", expected, found)
+            }
+        }
+    }
+}
+
+/// Why a source can't be used, an override source with the generated bindings,
+/// see [`ComputeShaderBuilder::with_source`](crate::ComputeShaderBuilder::with_source),
+/// or the source of a [`DynamicComputeShader`](crate::DynamicComputeShader).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SourceError {
+    /// The source isn't valid WGSL, contains the formatted parse error.
+    Parse(String),
+    /// The source failed validation, contains the formatted validation error.
+    Validation(String),
+    /// The source has no compute entry point with the name.
+    MissingEntryPoint(String),
+    /// The interface of the entry point differs from the generated layout.
+    Interface(Vec<InterfaceDiff>),
+    /// The entry point uses a binding shatter can't bind, e.g. a storage
+    /// texture of an unsupported format.
+    UnsupportedBinding {
+        group: u32,
+        binding: u32,
+        name: String,
+    },
+}
+
+impl fmt::Display for SourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(error) => write!(f, "failed to parse shader source:\n{}", error),
+            Self::Validation(error) => write!(f, "invalid shader source: {}", error),
+            Self::MissingEntryPoint(name) => {
+                write!(f, "shader source has no compute entry point `{}`", name)
+            }
+            Self::UnsupportedBinding {
+                group,
+                binding,
+                name,
+            } => write!(
+                f,
+                "group {} binding {} `{}` has a type that can't be bound",
+                group, binding, name
+            ),
+            Self::Interface(diffs) => {
+                writeln!(f, "override source doesn't match the generated layout:")?;
+
+                for diff in diffs {
+                    writeln!(f, "  {}", diff)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SourceError {}
+
+/// Checks that the bindings used by `entry_point` in `source` match `layouts`.
+///
+/// Bindings of the layout the source doesn't use are allowed. Whether samplers
+/// filter and textures are filterable isn't declared in WGSL, so it's ignored.
+pub fn check_source_interface(
+    source: &str,
+    entry_point: &str,
+    layouts: &[BindGroupLayoutDescriptor],
+) -> Result<(), SourceError> {
+    let (module, info, index) = parse_entry_point(source, entry_point)?;
+    let function_info = info.get_entry_point(index);
+
+    let mut diffs = Vec::new();
+
+    for (handle, variable) in module.global_variables.iter() {
+        let binding = match variable.binding {
+            Some(ref binding) if !function_info[handle].is_empty() => binding,
+            _ => continue,
+        };
+
+        let expected = layouts
+            .get(binding.group as usize)
+            .and_then(|layout| {
+                layout
+                    .entries
+                    .iter()
+                    .find(|entry| entry.binding == binding.binding)
+            })
+            .map(|entry| entry.ty);
+        let found = binding_type(&module.types[variable.ty].inner, variable.class);
+
+        let matches = match (&expected, &found) {
+            (Some(expected), Some(found)) => same_binding_type(expected, found),
+            _ => false,
+        };
+
+        if !matches {
+            diffs.push(InterfaceDiff {
+                group: binding.group,
+                binding: binding.binding,
+                name: variable.name.clone().unwrap_or_default(),
+                expected,
+                found,
+            });
+        }
+    }
+
+    if diffs.is_empty() {
+        Ok(())
+    } else {
+        Err(SourceError::Interface(diffs))
+    }
+}
+
+/// A binding used by the entry point of a
+/// [`DynamicComputeShader`](crate::DynamicComputeShader).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReflectedBinding {
+    pub group: u32,
+    pub binding: u32,
+    /// Name of the binding in WGSL.
+    pub name: String,
+    pub ty: wgpu::BindingType,
+    /// Minimum size in bytes of buffer bindings.
+    pub min_size: u64,
+}
+
+/// What a compute entry point declares, see [`reflect_entry_point`].
+pub(crate) struct Reflection {
+    pub work_group_size: WorkGroupSize,
+    /// Sorted by group and binding.
+    pub bindings: Vec<ReflectedBinding>,
+}
+
+/// Reflects the work group size and the bindings used by `entry_point` in `source`.
+pub(crate) fn reflect_entry_point(
+    source: &str,
+    entry_point: &str,
+) -> Result<Reflection, SourceError> {
+    let (module, info, index) = parse_entry_point(source, entry_point)?;
+    let function_info = info.get_entry_point(index);
+
+    let mut bindings = Vec::new();
+
+    for (handle, variable) in module.global_variables.iter() {
+        let binding = match variable.binding {
+            Some(ref binding) if !function_info[handle].is_empty() => binding,
+            _ => continue,
+        };
+
+        let name = variable.name.clone().unwrap_or_default();
+        let inner = &module.types[variable.ty].inner;
+
+        let ty = binding_type(inner, variable.class).ok_or(SourceError::UnsupportedBinding {
+            group: binding.group,
+            binding: binding.binding,
+            name: name.clone(),
+        })?;
+
+        let min_size = match ty {
+            wgpu::BindingType::Buffer { .. } => inner.span(&module.constants) as u64,
+            _ => 0,
+        };
+
+        bindings.push(ReflectedBinding {
+            group: binding.group,
+            binding: binding.binding,
+            name,
+            ty,
+            min_size,
+        });
+    }
+
+    bindings.sort_by_key(|binding| (binding.group, binding.binding));
+
+    let [x, y, z] = module.entry_points[index].workgroup_size;
+
+    Ok(Reflection {
+        work_group_size: WorkGroupSize::new(x, y, z),
+        bindings,
+    })
+}
+
+/// Parses and validates `source`, returning the index of the compute entry
+/// point `entry_point`.
+fn parse_entry_point(
+    source: &str,
+    entry_point: &str,
+) -> Result<(Module, ModuleInfo, usize), SourceError> {
+    let module = naga::front::wgsl::parse_str(source)
+        .map_err(|err| SourceError::Parse(err.emit_to_string(source)))?;
+
+    let info = Validator::new(ValidationFlags::all(), Capabilities::all())
+        .validate(&module)
+        .map_err(|err| SourceError::Validation(err.to_string()))?;
+
+    let index = module
+        .entry_points
+        .iter()
+        .position(|ep| ep.name == entry_point && ep.stage == naga::ShaderStage::Compute)
+        .ok_or_else(|| SourceError::MissingEntryPoint(entry_point.into()))?;
+
+    Ok((module, info, index))
+}
+
+fn binding_type(ty: &TypeInner, class: StorageClass) -> Option<wgpu::BindingType> {
+    Some(match *ty {
+        TypeInner::Sampler { comparison } => wgpu::BindingType::Sampler {
+            filtering: true,
+            comparison,
+        },
+        TypeInner::Image {
+            dim,
+            arrayed,
+            class,
+        } => {
+            let view_dimension = view_dimension(dim, arrayed);
+
+            match class {
+                ImageClass::Sampled { kind, multi } => wgpu::BindingType::Texture {
+                    sample_type: match kind {
+                        ScalarKind::Float => wgpu::TextureSampleType::Float { filterable: true },
+                        ScalarKind::Sint => wgpu::TextureSampleType::Sint,
+                        ScalarKind::Uint => wgpu::TextureSampleType::Uint,
+                        ScalarKind::Bool => return None,
+                    },
+                    view_dimension,
+                    multisampled: multi,
+                },
+                ImageClass::Depth { multi } => wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Depth,
+                    view_dimension,
+                    multisampled: multi,
+                },
+                ImageClass::Storage { format, access } => wgpu::BindingType::StorageTexture {
+                    access: match access {
+                        _ if access.contains(StorageAccess::LOAD | StorageAccess::STORE) => {
+                            wgpu::StorageTextureAccess::ReadWrite
+                        }
+                        _ if access.contains(StorageAccess::LOAD) => {
+                            wgpu::StorageTextureAccess::ReadOnly
+                        }
+                        _ => wgpu::StorageTextureAccess::WriteOnly,
+                    },
+                    format: texture_format(format)?,
+                    view_dimension,
+                },
+            }
+        }
+        _ => wgpu::BindingType::Buffer {
+            ty: match class {
+                StorageClass::Uniform => wgpu::BufferBindingType::Uniform,
+                StorageClass::Storage { access } => wgpu::BufferBindingType::Storage {
+                    read_only: !access.contains(StorageAccess::STORE),
+                },
+                _ => return None,
+            },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+    })
+}
+
+/// Compares binding types, ignoring what WGSL doesn't declare.
+pub(crate) fn same_binding_type(expected: &wgpu::BindingType, found: &wgpu::BindingType) -> bool {
+    match (*expected, *found) {
+        (
+            wgpu::BindingType::Sampler { comparison, .. },
+            wgpu::BindingType::Sampler {
+                comparison: found, ..
+            },
+        ) => comparison == found,
+        (
+            wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { .. },
+                view_dimension,
+                multisampled,
+            },
+            wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { .. },
+                view_dimension: found_dimension,
+                multisampled: found_multisampled,
+            },
+        ) => view_dimension == found_dimension && multisampled == found_multisampled,
+        (expected, found) => expected == found,
+    }
+}
+
+fn view_dimension(dim: ImageDimension, arrayed: bool) -> wgpu::TextureViewDimension {
+    match (dim, arrayed) {
+        (ImageDimension::D1, _) => wgpu::TextureViewDimension::D1,
+        (ImageDimension::D2, false) => wgpu::TextureViewDimension::D2,
+        (ImageDimension::D2, true) => wgpu::TextureViewDimension::D2Array,
+        (ImageDimension::D3, _) => wgpu::TextureViewDimension::D3,
+        (ImageDimension::Cube, false) => wgpu::TextureViewDimension::Cube,
+        (ImageDimension::Cube, true) => wgpu::TextureViewDimension::CubeArray,
+    }
+}
+
+/// Storage texture formats supported by `wgsl!`.
+fn texture_format(format: StorageFormat) -> Option<wgpu::TextureFormat> {
+    match format {
+        StorageFormat::Rgba8Unorm => Some(wgpu::TextureFormat::Rgba8Unorm),
+        StorageFormat::Rgba16Float => Some(wgpu::TextureFormat::Rgba16Float),
+        _ => None,
+    }
+}
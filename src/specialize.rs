@@ -0,0 +1,96 @@
+/// Replaces the value of the module scope constant `name` of `source` with
+/// `value`, returns `None` if there's no such scalar constant.
+///
+/// Works on both the spaced sources generated by [`wgsl!`](crate::wgsl), e.g.
+/// `let HEIGHT : u32 = 256u ;`, and hand-written ones. The literal is suffixed
+/// for the type of the constant, taken from its annotation or its literal.
+pub(crate) fn specialize_constant(source: &str, name: &str, value: u32) -> Option<String> {
+    let (start, end, literal) = find_constant(source, name)?;
+
+    let suffix = match literal {
+        Scalar::U32 => "u",
+        Scalar::I32 => "",
+        Scalar::F32 => ".0",
+    };
+
+    Some(format!(
+        "{} {}{}{}",
+        &source[..start],
+        value,
+        suffix,
+        &source[end..]
+    ))
+}
+
+#[derive(Clone, Copy)]
+enum Scalar {
+    U32,
+    I32,
+    F32,
+}
+
+/// Finds the initializer of constant `name`, between `=` and `;`.
+fn find_constant(source: &str, name: &str) -> Option<(usize, usize, Scalar)> {
+    let bytes = source.as_bytes();
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = source[i..].find('\n').map_or(bytes.len(), |end| i + end);
+                continue;
+            }
+            b'{' => depth += 1,
+            b'}' => depth = depth.saturating_sub(1),
+            b'l' if depth == 0 && is_word_at(source, i, "let") => {
+                let rest = source[i + 3..].trim_start();
+                let name_start = source.len() - rest.len();
+
+                if is_word_at(source, name_start, name) {
+                    let equals = name_start + source[name_start..].find('=')?;
+                    let semicolon = equals + source[equals..].find(';')?;
+
+                    let ty = source[name_start + name.len()..equals].trim();
+                    let init = source[equals + 1..semicolon].trim();
+
+                    return Some((equals + 1, semicolon, scalar(ty, init)?));
+                }
+            }
+            _ => {}
+        }
+
+        i += 1;
+    }
+
+    None
+}
+
+/// Gets the scalar type of a constant from its annotation, e.g. `: u32`, or
+/// else its literal.
+fn scalar(ty: &str, init: &str) -> Option<Scalar> {
+    match ty.strip_prefix(':').map(str::trim) {
+        Some("u32") => Some(Scalar::U32),
+        Some("i32") => Some(Scalar::I32),
+        Some("f32") => Some(Scalar::F32),
+        Some(_) => None,
+        None if init.parse::<i64>().is_ok() => Some(Scalar::I32),
+        None if init.ends_with('u') && init[..init.len() - 1].parse::<u32>().is_ok() => {
+            Some(Scalar::U32)
+        }
+        None if init.parse::<f32>().is_ok() => Some(Scalar::F32),
+        None => None,
+    }
+}
+
+/// Checks that `word` starts at `i` of `source`, not within a longer identifier.
+fn is_word_at(source: &str, i: usize, word: &str) -> bool {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+    source[i..].starts_with(word)
+        && !source[..i].chars().next_back().is_some_and(is_ident)
+        && !source[i + word.len()..]
+            .chars()
+            .next()
+            .is_some_and(is_ident)
+}
@@ -0,0 +1,111 @@
+use std::{ops::Deref, sync::Mutex};
+
+/// Default number of bytes of staging buffers an [`Instance`](crate::Instance)
+/// keeps for reuse, see [`Instance::set_max_staging_bytes`](crate::Instance::set_max_staging_bytes).
+pub const DEFAULT_MAX_STAGING_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Smallest staging buffer, smaller downloads share its bucket.
+const MIN_STAGING_SIZE: u64 = 256;
+
+/// A `MAP_READ | COPY_DST` buffer downloads are copied through, see
+/// [`Instance::acquire_staging`](crate::Instance::acquire_staging).
+///
+/// The buffer may be larger than requested, sizes are rounded up to the next
+/// power of two so similar downloads share buffers.
+pub struct StagingBuffer {
+    buffer: wgpu::Buffer,
+    size: u64,
+}
+
+impl StagingBuffer {
+    /// Size of the buffer in bytes.
+    #[inline]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+impl Deref for StagingBuffer {
+    type Target = wgpu::Buffer;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.buffer
+    }
+}
+
+/// Staging buffers released for reuse, bucketed by size.
+pub(crate) struct StagingPool {
+    buffers: Mutex<Vec<StagingBuffer>>,
+    max_bytes: Mutex<u64>,
+}
+
+impl StagingPool {
+    pub fn new() -> Self {
+        Self {
+            buffers: Mutex::new(Vec::new()),
+            max_bytes: Mutex::new(DEFAULT_MAX_STAGING_BYTES),
+        }
+    }
+
+    /// Takes a pooled buffer of at least `size` bytes or creates one.
+    pub fn acquire(&self, device: &wgpu::Device, size: u64) -> StagingBuffer {
+        let size = bucket(size);
+
+        let mut buffers = self.buffers.lock().unwrap();
+
+        if let Some(i) = buffers.iter().position(|buffer| buffer.size == size) {
+            return buffers.swap_remove(i);
+        }
+
+        drop(buffers);
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("shatter_staging_buffer"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        StagingBuffer { buffer, size }
+    }
+
+    /// Returns `buffer` to the pool, dropping it if the pool would retain
+    /// more than the maximum.
+    pub fn release(&self, buffer: StagingBuffer) {
+        let max_bytes = self.max_bytes();
+        let mut buffers = self.buffers.lock().unwrap();
+
+        if retained(&buffers) + buffer.size <= max_bytes {
+            buffers.push(buffer);
+        }
+    }
+
+    pub fn retained_bytes(&self) -> u64 {
+        retained(&self.buffers.lock().unwrap())
+    }
+
+    pub fn max_bytes(&self) -> u64 {
+        *self.max_bytes.lock().unwrap()
+    }
+
+    /// Sets the maximum retained bytes, dropping the largest buffers over it.
+    pub fn set_max_bytes(&self, max_bytes: u64) {
+        *self.max_bytes.lock().unwrap() = max_bytes;
+
+        let mut buffers = self.buffers.lock().unwrap();
+        buffers.sort_by_key(|buffer| buffer.size);
+
+        while retained(&buffers) > max_bytes {
+            buffers.pop();
+        }
+    }
+}
+
+fn bucket(size: u64) -> u64 {
+    size.max(MIN_STAGING_SIZE).next_power_of_two()
+}
+
+fn retained(buffers: &[StagingBuffer]) -> u64 {
+    buffers.iter().map(|buffer| buffer.size).sum()
+}
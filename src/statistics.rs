@@ -0,0 +1,86 @@
+use crate::Instance;
+
+/// Pipeline statistics of a compute pass, see
+/// [`ComputeShaderBuilder::with_statistics`](crate::ComputeShaderBuilder::with_statistics).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PassStatistics {
+    /// Compute shader invocations launched by every dispatch of the pass.
+    ///
+    /// Every invocation of every work group is counted, including those
+    /// returning early from a bounds guard, see [`Dispatch::invocations`](crate::Dispatch::invocations).
+    pub invocations: u64,
+}
+
+/// Size of the single statistic queried.
+const RESULT_SIZE: u64 = std::mem::size_of::<u64>() as u64;
+
+/// A pipeline statistics query counting compute shader invocations.
+pub(crate) struct StatisticsQuery {
+    query_set: wgpu::QuerySet,
+    readback: wgpu::Buffer,
+}
+
+impl StatisticsQuery {
+    /// Creates the query, returns `None` if the device doesn't support
+    /// [`wgpu::Features::PIPELINE_STATISTICS_QUERY`].
+    pub fn new(instance: &Instance) -> Option<Self> {
+        let device = &instance.device;
+
+        if !device
+            .features()
+            .contains(wgpu::Features::PIPELINE_STATISTICS_QUERY)
+        {
+            return None;
+        }
+
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("shatter_statistics_query"),
+            ty: wgpu::QueryType::PipelineStatistics(
+                wgpu::PipelineStatisticsTypes::COMPUTE_SHADER_INVOCATIONS,
+            ),
+            count: 1,
+        });
+
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("shatter_statistics_readback"),
+            size: RESULT_SIZE,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Some(Self {
+            query_set,
+            readback,
+        })
+    }
+
+    pub fn begin(&self, compute_pass: &mut wgpu::ComputePass) {
+        compute_pass.begin_pipeline_statistics_query(&self.query_set, 0);
+    }
+
+    pub fn end(&self, compute_pass: &mut wgpu::ComputePass) {
+        compute_pass.end_pipeline_statistics_query();
+    }
+
+    /// Records resolving the result into the readback buffer.
+    pub fn resolve(&self, encoder: &mut wgpu::CommandEncoder) {
+        encoder.resolve_query_set(&self.query_set, 0..1, &self.readback, 0);
+    }
+
+    /// Waits for the resolved result.
+    pub fn read(&self, instance: &Instance) -> PassStatistics {
+        let slice = self.readback.slice(..);
+        let future = slice.map_async(wgpu::MapMode::Read);
+        instance.device.poll(wgpu::Maintain::Wait);
+        pollster::block_on(future).unwrap();
+
+        let invocations = {
+            let bytes = slice.get_mapped_range();
+            u64::from_ne_bytes(bytes[..RESULT_SIZE as usize].try_into().unwrap())
+        };
+
+        self.readback.unmap();
+
+        PassStatistics { invocations }
+    }
+}
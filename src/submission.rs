@@ -0,0 +1,123 @@
+use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    fmt::{self, Write},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Submissions kept by an instance, see
+/// [`Instance::recent_submissions`](crate::Instance::recent_submissions).
+pub const RECENT_SUBMISSIONS: usize = 64;
+
+/// Submissions listed by errors, the most recent ones.
+const SUBMISSIONS_IN_ERRORS: usize = 4;
+
+/// A submission to the queue of an [`Instance`](crate::Instance), see
+/// [`Instance::recent_submissions`](crate::Instance::recent_submissions).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubmissionRecord {
+    /// Position of the submission among those of the instance.
+    pub index: u64,
+    /// What submitted it, the entry point for generated functions, set with
+    /// [`ComputeShaderBuilder::tag`](crate::ComputeShaderBuilder::tag) and
+    /// [`SurfaceFrame::set_tag`](crate::SurfaceFrame::set_tag), or the
+    /// `shatter_*` name of an internal transfer.
+    pub tag: Option<Cow<'static, str>>,
+    pub submitted_at: Instant,
+    /// Entry points dispatched by the submission.
+    pub entry_points: Vec<Cow<'static, str>>,
+}
+
+impl SubmissionRecord {
+    fn write_at(&self, f: &mut impl Write, now: Instant) -> fmt::Result {
+        write!(f, "#{}", self.index)?;
+
+        if let Some(ref tag) = self.tag {
+            write!(f, " `{}`", tag)?;
+        }
+
+        if !self.entry_points.is_empty() {
+            write!(f, " dispatching {}", self.entry_points.join(", "))?;
+        }
+
+        let age = now.saturating_duration_since(self.submitted_at);
+        write!(
+            f,
+            ", {:?} ago",
+            Duration::from_micros(age.as_micros() as u64)
+        )
+    }
+}
+
+impl fmt::Display for SubmissionRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_at(f, Instant::now())
+    }
+}
+
+/// The last [`RECENT_SUBMISSIONS`] submissions of an instance, listed by
+/// errors so they can be traced back to what submitted them.
+#[derive(Default)]
+pub(crate) struct SubmissionLog {
+    state: Mutex<LogState>,
+}
+
+#[derive(Default)]
+struct LogState {
+    next_index: u64,
+    records: VecDeque<SubmissionRecord>,
+}
+
+impl SubmissionLog {
+    /// Records a submission, returning its index.
+    pub fn record(
+        &self,
+        tag: Option<Cow<'static, str>>,
+        entry_points: &[Cow<'static, str>],
+    ) -> u64 {
+        let submitted_at = Instant::now();
+
+        let mut state = self.state.lock().unwrap();
+        let index = state.next_index;
+        state.next_index += 1;
+
+        if state.records.len() == RECENT_SUBMISSIONS {
+            state.records.pop_front();
+        }
+
+        state.records.push_back(SubmissionRecord {
+            index,
+            tag,
+            submitted_at,
+            entry_points: entry_points.to_vec(),
+        });
+
+        index
+    }
+
+    /// The recorded submissions, oldest first.
+    pub fn recent(&self) -> Vec<SubmissionRecord> {
+        self.state.lock().unwrap().records.iter().cloned().collect()
+    }
+
+    /// Describes the most recent submissions for an error message, empty
+    /// without submissions.
+    pub fn describe_recent(&self) -> String {
+        let state = self.state.lock().unwrap();
+
+        if state.records.is_empty() {
+            return String::new();
+        }
+
+        let now = Instant::now();
+        let mut description = String::from("recent submissions, most recent first:");
+
+        for record in state.records.iter().rev().take(SUBMISSIONS_IN_ERRORS) {
+            description.push_str("\n  ");
+            record.write_at(&mut description, now).unwrap();
+        }
+
+        description
+    }
+}
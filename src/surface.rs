@@ -0,0 +1,474 @@
+use std::borrow::Cow;
+
+use raw_window_handle::HasRawWindowHandle;
+
+use crate::{
+    texel_format,
+    texture_format::{Bgra8Unorm, Rgba8Unorm},
+    texture_usages,
+    texture_view_dimension::D2,
+    AnyTexture2d, Binding, BindingMut, BindingResource, Instance, ResourceInfo,
+    StorageTextureBinding, Texture2d, TextureId, TextureViewId, TransferReport,
+};
+
+/// Shader copying the intermediate texture of a fallback frame to the target.
+pub const BLIT_SOURCE: &str = include_str!("blit.wgsl");
+
+/// Usages of surface frames written directly by compute shaders.
+pub const DIRECT_SURFACE_USAGES: wgpu::TextureUsages =
+    wgpu::TextureUsages::RENDER_ATTACHMENT.union(wgpu::TextureUsages::STORAGE_BINDING);
+
+enum SurfaceTarget {
+    Window(wgpu::Surface),
+    Headless(AnyTexture2d),
+}
+
+struct Blit {
+    layout: wgpu::BindGroupLayout,
+    pipeline: wgpu::RenderPipeline,
+}
+
+/// A surface presenting frames written by compute shaders.
+///
+/// Frames are bound as `texture_storage_2d<rgba8unorm, write>`. When the
+/// surface format can't be bound as such, e.g. the common Bgra8Unorm, frames
+/// are written to an intermediate texture which is blitted to the surface on
+/// [`SurfaceFrame::present`].
+pub struct Surface {
+    target: SurfaceTarget,
+    format: wgpu::TextureFormat,
+    width: u32,
+    height: u32,
+    direct: bool,
+    // identifies frames of a window surface, which aren't in `Instance::textures`
+    id: TextureId,
+    intermediate: Option<Texture2d<Rgba8Unorm>>,
+    blit: Option<Blit>,
+}
+
+impl Surface {
+    /// Creates a surface presenting to `window`.
+    ///
+    /// # Safety
+    /// * `window` **must** outlive the surface.
+    pub unsafe fn new<W: HasRawWindowHandle>(window: &W, width: u32, height: u32) -> Self {
+        let instance = Instance::global();
+
+        let surface = unsafe { instance.instance.create_surface(window) };
+        let format = surface
+            .get_preferred_format(&instance.adapter)
+            .unwrap_or(wgpu::TextureFormat::Bgra8Unorm);
+
+        let mut surface = Self {
+            target: SurfaceTarget::Window(surface),
+            format,
+            width,
+            height,
+            direct: supports_direct(format),
+            id: instance.textures.stable_id("shatter_surface"),
+            intermediate: None,
+            blit: None,
+        };
+
+        surface.configure();
+        surface
+    }
+
+    /// Creates a surface presenting to a texture instead of a window, see
+    /// [`Surface::headless_target`].
+    ///
+    /// # Panics
+    /// Panics if `format` isn't Rgba8Unorm or Bgra8Unorm.
+    pub fn headless(width: u32, height: u32, format: wgpu::TextureFormat) -> Self {
+        Self {
+            target: SurfaceTarget::Headless(headless_texture(width, height, format)),
+            format,
+            width,
+            height,
+            direct: supports_direct(format),
+            id: Instance::global().textures.stable_id("shatter_surface"),
+            intermediate: None,
+            blit: None,
+        }
+    }
+
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns true if frames are written directly, without an intermediate texture.
+    pub fn is_direct(&self) -> bool {
+        self.direct
+    }
+
+    /// Always writes frames to an intermediate texture, even if the surface
+    /// supports being written directly.
+    pub fn force_fallback(&mut self) {
+        self.direct = false;
+        self.configure();
+    }
+
+    /// Gets the texture presented to by a [headless](Surface::headless) surface.
+    pub fn headless_target(&self) -> Option<&AnyTexture2d> {
+        match self.target {
+            SurfaceTarget::Headless(ref texture) => Some(texture),
+            SurfaceTarget::Window(_) => None,
+        }
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if self.width == width && self.height == height {
+            return;
+        }
+
+        self.width = width;
+        self.height = height;
+        self.intermediate = None;
+
+        if let SurfaceTarget::Headless(ref mut texture) = self.target {
+            *texture = headless_texture(width, height, self.format);
+        }
+
+        self.configure();
+    }
+
+    fn configure(&mut self) {
+        if let SurfaceTarget::Window(ref surface) = self.target {
+            let usage = if self.direct {
+                DIRECT_SURFACE_USAGES
+            } else {
+                wgpu::TextureUsages::RENDER_ATTACHMENT
+            };
+
+            surface.configure(
+                &Instance::global().device,
+                &wgpu::SurfaceConfiguration {
+                    usage,
+                    format: self.format,
+                    width: self.width,
+                    height: self.height,
+                    present_mode: wgpu::PresentMode::Fifo,
+                },
+            );
+        }
+    }
+
+    /// Acquires the next frame, reconfiguring the surface once if it's outdated or lost.
+    pub fn frame(&mut self) -> Result<SurfaceFrame<'_>, wgpu::SurfaceError> {
+        let instance = Instance::global();
+
+        if !self.direct && self.intermediate.is_none() {
+            self.intermediate = Some(Texture2d::gpu_only(
+                self.width as usize,
+                self.height as usize,
+            ));
+        }
+
+        let (surface_texture, view) = match self.target {
+            SurfaceTarget::Window(ref surface) => {
+                let surface_texture = match surface.get_current_texture() {
+                    Ok(surface_texture) => surface_texture,
+                    Err(wgpu::SurfaceError::Outdated | wgpu::SurfaceError::Lost) => {
+                        self.configure();
+
+                        match self.target {
+                            SurfaceTarget::Window(ref surface) => surface.get_current_texture()?,
+                            SurfaceTarget::Headless(_) => unreachable!(),
+                        }
+                    }
+                    Err(err) => return Err(err),
+                };
+
+                let view = surface_texture.texture.create_view(&Default::default());
+
+                let id = instance.texture_views.stable_id("shatter_surface_frame");
+                instance.texture_views.insert(id.clone(), view);
+
+                (Some(surface_texture), Some(id))
+            }
+            SurfaceTarget::Headless(_) => (None, None),
+        };
+
+        Ok(SurfaceFrame {
+            surface: self,
+            surface_texture,
+            view,
+            tag: Cow::Borrowed("shatter_present"),
+        })
+    }
+
+    fn blit(&mut self, target: &wgpu::TextureView, tag: Cow<'static, str>) {
+        let instance = Instance::global();
+
+        let format = self.format;
+        let blit = self.blit.get_or_insert_with(|| create_blit(format));
+
+        let intermediate = self.intermediate.as_ref().unwrap();
+        let source = instance.textures.get(intermediate.texture_id()).unwrap();
+        let source = source.create_view(&Default::default());
+
+        let bind_group = instance
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("shatter_blit_bind_group"),
+                layout: &blit.layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&source),
+                }],
+            });
+
+        let mut encoder = instance
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("shatter_command_encoder(blit)"),
+            });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("shatter_render_pass(blit)"),
+                color_attachments: &[wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: true,
+                    },
+                }],
+                depth_stencil_attachment: None,
+            });
+
+            render_pass.set_pipeline(&blit.pipeline);
+            render_pass.set_bind_group(0, &bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        instance.queue.submit(std::iter::once(encoder.finish()));
+        instance.submitted(Some(tag), &[]);
+    }
+}
+
+/// A frame acquired from a [`Surface`], bound as a write only rgba8unorm storage texture.
+///
+/// Frames have no CPU side. Dispatches writing the frame are submitted before
+/// [`SurfaceFrame::present`], so they complete before the frame is shown.
+/// Dropping a frame without presenting it discards it.
+pub struct SurfaceFrame<'a> {
+    surface: &'a mut Surface,
+    surface_texture: Option<wgpu::SurfaceTexture>,
+    view: Option<TextureViewId>,
+    tag: Cow<'static, str>,
+}
+
+impl SurfaceFrame<'_> {
+    /// Returns true if the frame is written directly, see [`Surface::is_direct`].
+    pub fn is_direct(&self) -> bool {
+        self.surface.direct
+    }
+
+    pub fn width(&self) -> u32 {
+        self.surface.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.surface.height
+    }
+
+    /// Tags the submission blitting the frame when it's presented,
+    /// `shatter_present` by default, see [`Instance::recent_submissions`].
+    /// Direct frames are presented without a submission.
+    pub fn set_tag(&mut self, tag: impl Into<Cow<'static, str>>) {
+        self.tag = tag.into();
+    }
+
+    /// Presents the frame, blitting the intermediate texture first if needed.
+    pub fn present(mut self) {
+        let instance = Instance::global();
+
+        if !self.surface.direct {
+            match self.view {
+                Some(ref view) => {
+                    let view = instance.texture_views.get(view).unwrap();
+
+                    self.surface.blit(&view, self.tag.clone());
+                }
+                None => {
+                    let target = match self.surface.target {
+                        SurfaceTarget::Headless(ref texture) => {
+                            let texture = instance.textures.get(texture.texture_id()).unwrap();
+
+                            texture.create_view(&Default::default())
+                        }
+                        SurfaceTarget::Window(_) => unreachable!(),
+                    };
+
+                    self.surface.blit(&target, self.tag.clone());
+                }
+            }
+        }
+
+        if let SurfaceTarget::Headless(ref mut texture) = self.surface.target {
+            texture.mark_needs_download();
+        }
+
+        if let Some(view) = self.view.take() {
+            instance.remove_texture_view(&view);
+        }
+
+        if let Some(surface_texture) = self.surface_texture.take() {
+            surface_texture.present();
+        }
+    }
+}
+
+impl Drop for SurfaceFrame<'_> {
+    fn drop(&mut self) {
+        // the surface texture is discarded when dropped without presenting
+        if let Some(view) = self.view.take() {
+            Instance::global().remove_texture_view(&view);
+        }
+    }
+}
+
+impl Binding<StorageTextureBinding<texel_format::Rgba8Unorm, D2>> for SurfaceFrame<'_> {
+    fn binding_resource(&self) -> BindingResource {
+        if let Some(ref intermediate) = self.surface.intermediate {
+            if !self.surface.direct {
+                return BindingResource::TextureView(intermediate.texture_id().clone());
+            }
+        }
+
+        match (&self.view, &self.surface.target) {
+            (Some(view), _) => BindingResource::RawTextureView(view.clone()),
+            (None, SurfaceTarget::Headless(texture)) => {
+                BindingResource::TextureView(texture.texture_id().clone())
+            }
+            (None, SurfaceTarget::Window(_)) => unreachable!(),
+        }
+    }
+
+    fn resource_info(&self) -> ResourceInfo {
+        if let (false, Some(intermediate)) = (self.surface.direct, &self.surface.intermediate) {
+            return intermediate.resource_info();
+        }
+
+        let id = match self.surface.target {
+            SurfaceTarget::Headless(ref texture) => texture.texture_id().clone(),
+            SurfaceTarget::Window(_) => self.surface.id.clone(),
+        };
+
+        ResourceInfo::Texture {
+            id,
+            format: self.surface.format,
+            view_dimension: wgpu::TextureViewDimension::D2,
+            usage: match self.surface.target {
+                SurfaceTarget::Headless(_) => texture_usages(self.surface.format),
+                SurfaceTarget::Window(_) => DIRECT_SURFACE_USAGES,
+            },
+        }
+    }
+
+    fn prepare(&self, report: &mut TransferReport) {
+        report.textures_touched += 1;
+    }
+
+    fn read(&self, report: &mut TransferReport) {
+        report.textures_touched += 1;
+    }
+}
+
+impl BindingMut<StorageTextureBinding<texel_format::Rgba8Unorm, D2>> for SurfaceFrame<'_> {
+    fn write(&mut self) {}
+}
+
+/// Returns true if textures of `format` can be bound as rgba8unorm storage textures.
+fn supports_direct(format: wgpu::TextureFormat) -> bool {
+    if format != wgpu::TextureFormat::Rgba8Unorm {
+        return false;
+    }
+
+    let instance = Instance::global();
+
+    let features = if instance
+        .device
+        .features()
+        .contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES)
+    {
+        instance.adapter.get_texture_format_features(format)
+    } else {
+        format.describe().guaranteed_format_features
+    };
+
+    features
+        .allowed_usages
+        .contains(wgpu::TextureUsages::STORAGE_BINDING)
+}
+
+fn headless_texture(width: u32, height: u32, format: wgpu::TextureFormat) -> AnyTexture2d {
+    match format {
+        wgpu::TextureFormat::Rgba8Unorm => {
+            Texture2d::<Rgba8Unorm>::new(width as usize, height as usize).into()
+        }
+        wgpu::TextureFormat::Bgra8Unorm => {
+            Texture2d::<Bgra8Unorm>::new(width as usize, height as usize).into()
+        }
+        format => panic!("headless surfaces don't support {:?}", format),
+    }
+}
+
+fn create_blit(format: wgpu::TextureFormat) -> Blit {
+    let device = &Instance::global().device;
+
+    let module = device.create_shader_module(&wgpu::ShaderModuleDescriptor {
+        label: Some("shatter_shader_module(blit)"),
+        source: wgpu::ShaderSource::Wgsl(BLIT_SOURCE.into()),
+    });
+
+    let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("shatter_bind_group_layout(blit)"),
+        entries: &[wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        }],
+    });
+
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("shatter_pipeline_layout(blit)"),
+        bind_group_layouts: &[&layout],
+        push_constant_ranges: &[],
+    });
+
+    let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("shatter_render_pipeline(blit)"),
+        layout: Some(&pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: &module,
+            entry_point: "vertex",
+            buffers: &[],
+        },
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module: &module,
+            entry_point: "fragment",
+            targets: &[format.into()],
+        }),
+    });
+
+    Blit { layout, pipeline }
+}
@@ -0,0 +1,531 @@
+//! Helpers for testing kernels, comparing rendered images against goldens.
+//!
+//! Goldens are PNGs stored with uncompressed deflate blocks, which keeps the
+//! encoder dependency free. Goldens are only written with `SHATTER_BLESS=1`,
+//! a missing golden fails the comparison like a mismatching one.
+
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{convert, Texture2d, TextureFormat};
+
+/// How much an image may differ from its golden.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ImageTolerance {
+    /// Largest difference of a channel for a pixel to match.
+    pub channel_epsilon: u8,
+    /// Fraction of pixels in `0.0..=1.0` allowed to differ by more than
+    /// [`channel_epsilon`](Self::channel_epsilon).
+    pub max_differing_fraction: f32,
+}
+
+impl Default for ImageTolerance {
+    /// Absorbs small float differences between backends.
+    fn default() -> Self {
+        Self {
+            channel_epsilon: 2,
+            max_differing_fraction: 0.001,
+        }
+    }
+}
+
+impl ImageTolerance {
+    pub const EXACT: Self = Self {
+        channel_epsilon: 0,
+        max_differing_fraction: 0.0,
+    };
+}
+
+/// Result of comparing an image against a golden of the same size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImageDiff {
+    pub pixels: usize,
+    /// Pixels with a channel differing by more than the epsilon.
+    pub differing: usize,
+    /// Largest difference of any channel.
+    pub max_channel_diff: u8,
+}
+
+impl ImageDiff {
+    #[inline]
+    pub fn differing_fraction(&self) -> f32 {
+        if self.pixels == 0 {
+            0.0
+        } else {
+            self.differing as f32 / self.pixels as f32
+        }
+    }
+}
+
+/// Compares RGBA8 images of the same size, returning the diff and a heatmap
+/// of the largest channel difference per pixel.
+///
+/// Pixels within the epsilon are blue in the heatmap, pixels outside it red,
+/// brighter the larger the difference.
+///
+/// # Panics
+/// Panics if the images differ in length.
+pub fn diff_rgba8(actual: &[u8], golden: &[u8], channel_epsilon: u8) -> (ImageDiff, Vec<u8>) {
+    assert_eq!(actual.len(), golden.len(), "images differ in size");
+
+    let mut diff = ImageDiff {
+        pixels: actual.len() / 4,
+        differing: 0,
+        max_channel_diff: 0,
+    };
+
+    let mut heatmap = Vec::with_capacity(actual.len());
+
+    for (actual, golden) in actual.chunks_exact(4).zip(golden.chunks_exact(4)) {
+        let pixel_diff = actual
+            .iter()
+            .zip(golden)
+            .map(|(a, g)| a.abs_diff(*g))
+            .max()
+            .unwrap_or(0);
+
+        diff.max_channel_diff = diff.max_channel_diff.max(pixel_diff);
+
+        // scale small differences up so they are visible
+        let heat = if pixel_diff == 0 {
+            0
+        } else {
+            64 + (pixel_diff as u32 * 191 / 255) as u8
+        };
+
+        if pixel_diff > channel_epsilon {
+            diff.differing += 1;
+            heatmap.extend_from_slice(&[heat, 0, 0, 255]);
+        } else {
+            heatmap.extend_from_slice(&[0, 0, heat, 255]);
+        }
+    }
+
+    (diff, heatmap)
+}
+
+/// Why an image doesn't match its golden.
+#[derive(Debug)]
+pub enum ImageMismatch {
+    /// The golden is missing or couldn't be read.
+    Golden { path: PathBuf, error: String },
+    Size {
+        expected: (u32, u32),
+        found: (u32, u32),
+    },
+    /// More pixels differ than the tolerance allows, the actual image and a
+    /// heatmap were written next to the golden.
+    Pixels {
+        diff: ImageDiff,
+        tolerance: ImageTolerance,
+        actual: PathBuf,
+        heatmap: PathBuf,
+    },
+}
+
+impl fmt::Display for ImageMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Golden { path, error } => {
+                write!(f, "golden {}: {}", path.display(), error)?;
+                write!(f, "\nrun with SHATTER_BLESS=1 to regenerate goldens")
+            }
+            Self::Size { expected, found } => write!(
+                f,
+                "image is {}x{}, golden is {}x{}",
+                found.0, found.1, expected.0, expected.1
+            ),
+            Self::Pixels {
+                diff,
+                tolerance,
+                actual,
+                heatmap,
+            } => {
+                write!(
+                    f,
+                    "{} of {} pixels ({:.3}%) differ by more than {}, {:.3}% allowed, \
+                    largest difference is {}",
+                    diff.differing,
+                    diff.pixels,
+                    diff.differing_fraction() * 100.0,
+                    tolerance.channel_epsilon,
+                    tolerance.max_differing_fraction * 100.0,
+                    diff.max_channel_diff,
+                )?;
+                write!(
+                    f,
+                    "\nactual: {}\nheatmap: {}",
+                    actual.display(),
+                    heatmap.display()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImageMismatch {}
+
+fn bless() -> bool {
+    env::var_os("SHATTER_BLESS").is_some_and(|value| value != "0")
+}
+
+/// Gets `golden` with `.png` replaced by `suffix`, e.g. `mandelbrot.actual.png`.
+fn sibling(golden: &Path, suffix: &str) -> PathBuf {
+    let stem = golden.file_stem().unwrap_or_default().to_string_lossy();
+
+    golden.with_file_name(format!("{}.{}", stem, suffix))
+}
+
+/// Writes an RGBA8 image as a PNG in the format used for goldens, creating
+/// missing parent directories.
+pub fn write_png(path: impl AsRef<Path>, width: u32, height: u32, rgba: &[u8]) -> io::Result<()> {
+    let path = path.as_ref();
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, encode_png(width, height, rgba))
+}
+
+/// Reads a PNG written by [`write_png`], returning the size and RGBA8 pixels.
+pub fn read_png(path: impl AsRef<Path>) -> io::Result<(u32, u32, Vec<u8>)> {
+    let bytes = fs::read(path)?;
+
+    decode_png(&bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Compares a tightly packed RGBA8 image against the PNG at `golden`.
+///
+/// With `SHATTER_BLESS=1` the golden is written instead, otherwise a missing
+/// golden is an [`ImageMismatch::Golden`]. On a pixel
+/// mismatch the actual image and a heatmap, see [`diff_rgba8`], are written
+/// next to the golden as `<name>.actual.png` and `<name>.diff.png`.
+///
+/// # Panics
+/// Panics if `rgba` isn't `width * height` pixels.
+pub fn compare_image(
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+    golden: impl AsRef<Path>,
+    tolerance: ImageTolerance,
+) -> Result<(), ImageMismatch> {
+    assert_eq!(
+        rgba.len(),
+        width as usize * height as usize * 4,
+        "image isn't {}x{} RGBA8",
+        width,
+        height
+    );
+
+    let golden = golden.as_ref();
+
+    let golden_error = |error: String| ImageMismatch::Golden {
+        path: golden.into(),
+        error,
+    };
+
+    if bless() {
+        write_png(golden, width, height, rgba).map_err(|err| golden_error(err.to_string()))?;
+
+        eprintln!("wrote golden {}", golden.display());
+
+        return Ok(());
+    }
+
+    let (golden_width, golden_height, golden_rgba) =
+        read_png(golden).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => golden_error(String::from("missing")),
+            _ => golden_error(err.to_string()),
+        })?;
+
+    if (golden_width, golden_height) != (width, height) {
+        return Err(ImageMismatch::Size {
+            expected: (golden_width, golden_height),
+            found: (width, height),
+        });
+    }
+
+    let (diff, heatmap) = diff_rgba8(rgba, &golden_rgba, tolerance.channel_epsilon);
+
+    if diff.differing_fraction() <= tolerance.max_differing_fraction {
+        return Ok(());
+    }
+
+    let actual_path = sibling(golden, "actual.png");
+    let heatmap_path = sibling(golden, "diff.png");
+
+    write_png(&actual_path, width, height, rgba)
+        .and_then(|_| write_png(&heatmap_path, width, height, &heatmap))
+        .map_err(|err| golden_error(err.to_string()))?;
+
+    Err(ImageMismatch::Pixels {
+        diff,
+        tolerance,
+        actual: actual_path,
+        heatmap: heatmap_path,
+    })
+}
+
+/// Downloads `texture` as tightly packed RGBA8.
+///
+/// # Panics
+/// Panics if the format isn't 8 bit RGBA or BGRA, or `Rgba32Float`.
+pub fn texture_rgba8<Format: TextureFormat + Default>(texture: &Texture2d<Format>) -> Vec<u8> {
+    let format = texture.wgpu_format();
+    let bytes = texture.bytes();
+
+    let width = texture.width();
+    let height = texture.height();
+    let stride = bytes.len() / height.max(1);
+
+    let texel_size = match format {
+        wgpu::TextureFormat::Rgba32Float => 16,
+        _ => 4,
+    };
+
+    let mut packed = vec![0; width * height * texel_size];
+    convert::copy_rows(
+        bytes,
+        stride,
+        &mut packed,
+        width * texel_size,
+        width * texel_size,
+        height,
+    );
+
+    match format {
+        wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => packed,
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => {
+            for texel in packed.chunks_exact_mut(4) {
+                texel.swap(0, 2);
+            }
+
+            packed
+        }
+        wgpu::TextureFormat::Rgba32Float => {
+            let floats = packed
+                .chunks_exact(4)
+                .map(|bytes| f32::from_ne_bytes(bytes.try_into().unwrap()))
+                .collect::<Vec<_>>();
+
+            let mut rgba = vec![0; floats.len()];
+            convert::f32_to_unorm8(&floats, &mut rgba);
+
+            rgba
+        }
+        format => panic!("can't compare images of format {:?}", format),
+    }
+}
+
+/// Asserts that `texture` matches the PNG at `golden`, see [`compare_image`].
+///
+/// # Panics
+/// Panics with the mismatch if the texture doesn't match.
+#[track_caller]
+pub fn assert_image_matches<Format: TextureFormat + Default>(
+    texture: &Texture2d<Format>,
+    golden: impl AsRef<Path>,
+    tolerance: ImageTolerance,
+) {
+    let rgba = texture_rgba8(texture);
+
+    if let Err(err) = compare_image(
+        texture.width() as u32,
+        texture.height() as u32,
+        &rgba,
+        golden,
+        tolerance,
+    ) {
+        panic!("image doesn't match golden: {}", err);
+    }
+}
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// Largest length of an uncompressed deflate block.
+const STORED_BLOCK: usize = 0xffff;
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+
+    for &byte in bytes {
+        crc ^= byte as u32;
+
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+
+    for chunk in bytes.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+
+        a %= 65521;
+        b %= 65521;
+    }
+
+    (b << 16) | a
+}
+
+fn push_chunk(png: &mut Vec<u8>, ty: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+
+    let start = png.len();
+    png.extend_from_slice(ty);
+    png.extend_from_slice(data);
+
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Encodes an RGBA8 image as a PNG with uncompressed deflate blocks.
+fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+    let row = width as usize * 4;
+
+    // every row starts with filter type 0, none
+    let mut raw = Vec::with_capacity((row + 1) * height as usize);
+    for row in rgba.chunks_exact(row.max(1)).take(height as usize) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut zlib = vec![0x78, 0x01];
+    let mut blocks = raw.chunks(STORED_BLOCK).peekable();
+
+    if blocks.peek().is_none() {
+        zlib.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none() as u8;
+        let len = block.len() as u16;
+
+        zlib.push(last);
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // 8 bit RGBA, default compression and filtering, not interlaced
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut png = PNG_SIGNATURE.to_vec();
+    push_chunk(&mut png, b"IHDR", &header);
+    push_chunk(&mut png, b"IDAT", &zlib);
+    push_chunk(&mut png, b"IEND", &[]);
+
+    png
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, String> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|bytes| u32::from_be_bytes(bytes.try_into().unwrap()))
+        .ok_or_else(|| String::from("unexpected end of file"))
+}
+
+/// Decodes a PNG written by [`encode_png`], returning the size and RGBA8 pixels.
+///
+/// Compressed or filtered PNGs, e.g. goldens re-saved by an image editor,
+/// aren't supported.
+fn decode_png(bytes: &[u8]) -> Result<(u32, u32, Vec<u8>), String> {
+    if !bytes.starts_with(&PNG_SIGNATURE) {
+        return Err("not a PNG".into());
+    }
+
+    let mut offset = PNG_SIGNATURE.len();
+    let mut size = None;
+    let mut zlib = Vec::new();
+
+    loop {
+        let len = read_u32(bytes, offset)? as usize;
+        let ty = bytes
+            .get(offset + 4..offset + 8)
+            .ok_or("unexpected end of file")?;
+        let data = bytes
+            .get(offset + 8..offset + 8 + len)
+            .ok_or("unexpected end of file")?;
+
+        match ty {
+            b"IHDR" => {
+                if data.get(8..13) != Some(&[8, 6, 0, 0, 0]) {
+                    return Err("only non-interlaced 8 bit RGBA PNGs are supported".into());
+                }
+
+                size = Some((read_u32(data, 0)?, read_u32(data, 4)?));
+            }
+            b"IDAT" => zlib.extend_from_slice(data),
+            b"IEND" => break,
+            _ => {}
+        }
+
+        offset += len + 12;
+    }
+
+    let (width, height) = size.ok_or("missing header")?;
+
+    let mut raw = Vec::new();
+    let mut offset = 2;
+
+    loop {
+        let header = *zlib.get(offset).ok_or("unexpected end of image data")?;
+
+        if header & 0b110 != 0 {
+            return Err("compressed PNGs aren't supported, regenerate the golden".into());
+        }
+
+        let len = zlib
+            .get(offset + 1..offset + 3)
+            .map(|len| u16::from_le_bytes(len.try_into().unwrap()) as usize)
+            .ok_or("unexpected end of image data")?;
+        let block = zlib
+            .get(offset + 5..offset + 5 + len)
+            .ok_or("unexpected end of image data")?;
+
+        raw.extend_from_slice(block);
+        offset += 5 + len;
+
+        if header & 1 != 0 {
+            break;
+        }
+    }
+
+    let row = width as usize * 4;
+
+    if raw.len() != (row + 1) * height as usize {
+        return Err("image data doesn't match the size".into());
+    }
+
+    let mut rgba = Vec::with_capacity(row * height as usize);
+
+    for row in raw.chunks_exact(row + 1) {
+        if row[0] != 0 {
+            return Err("filtered PNGs aren't supported, regenerate the golden".into());
+        }
+
+        rgba.extend_from_slice(&row[1..]);
+    }
+
+    Ok((width, height, rgba))
+}
@@ -1,17 +1,70 @@
 use std::{
+    borrow::Cow,
     marker::PhantomData,
+    mem,
     num::NonZeroU32,
     ops::{Index, IndexMut},
-    sync::atomic::{AtomicBool, Ordering},
+    slice,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex,
+    },
 };
 
-use crate::{Binding, BindingResource, Instance, TextureId};
+use crate::{
+    convert, Binding, BindingMut, BindingResource, Dispatch, Error, ForcedTransfer, Instance,
+    ResourceError, ResourceInfo, ResourceKind, RgbColor, RgbaColor, TextureHandle, TextureId,
+    TransferDirection, TransferReport, WorkGroupSize,
+};
+
+static MIRROR_BYTES: AtomicU64 = AtomicU64::new(0);
+static MIRROR_ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// Host memory used by the CPU mirrors of textures.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TextureMemoryStats {
+    /// Bytes currently allocated by CPU mirrors.
+    pub mirror_bytes: u64,
+    /// Number of CPU mirrors allocated since startup, including freed ones.
+    pub mirror_allocations: u64,
+}
+
+/// Gets the host memory used by the CPU mirrors of every texture.
+pub fn texture_memory_stats() -> TextureMemoryStats {
+    TextureMemoryStats {
+        mirror_bytes: MIRROR_BYTES.load(Ordering::Acquire),
+        mirror_allocations: MIRROR_ALLOCATIONS.load(Ordering::Acquire),
+    }
+}
 
 pub mod texture_sample_type {
+    /// Reflects a sample type marker into its [`wgpu::TextureSampleType`].
+    pub trait SampleType {
+        const SAMPLE_TYPE: wgpu::TextureSampleType;
+    }
+
     pub struct Float<const FILTERABLE: bool>;
     pub struct Depth;
     pub struct Sint;
     pub struct Uint;
+
+    impl<const FILTERABLE: bool> SampleType for Float<FILTERABLE> {
+        const SAMPLE_TYPE: wgpu::TextureSampleType = wgpu::TextureSampleType::Float {
+            filterable: FILTERABLE,
+        };
+    }
+
+    impl SampleType for Depth {
+        const SAMPLE_TYPE: wgpu::TextureSampleType = wgpu::TextureSampleType::Depth;
+    }
+
+    impl SampleType for Sint {
+        const SAMPLE_TYPE: wgpu::TextureSampleType = wgpu::TextureSampleType::Sint;
+    }
+
+    impl SampleType for Uint {
+        const SAMPLE_TYPE: wgpu::TextureSampleType = wgpu::TextureSampleType::Uint;
+    }
 }
 
 pub mod texture_view_dimension {
@@ -32,6 +85,9 @@ pub mod texture_view_dimension {
     }
 
     impl<Data> TextureStorageData<Data> {
+        /// # Safety
+        /// * `layout` **must** describe an allocation that is valid to
+        ///   interpret as a sequence of `Data`.
         pub const unsafe fn new(layout: Layout) -> Self {
             Self {
                 data: AtomicPtr::new(ptr::null_mut()),
@@ -40,21 +96,51 @@ pub mod texture_view_dimension {
             }
         }
 
+        /// Allocates the zeroed CPU mirror if it hasn't been already.
         pub fn allocate(&self) {
-            if !self.data.load(Ordering::Acquire).is_null() {
+            if self.has_cpu_mirror() {
                 return;
             }
 
-            let ptr = unsafe { alloc::alloc(self.layout) };
+            if self.layout.size() == 0 {
+                let dangling = NonNull::<Data>::dangling().as_ptr();
+                let _ = self.data.compare_exchange(
+                    ptr::null_mut(),
+                    dangling,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                );
 
-            unsafe { ptr::write_bytes(ptr, 0, self.layout.size()) };
+                return;
+            }
+
+            let ptr = unsafe { alloc::alloc_zeroed(self.layout) };
 
             let ptr = match NonNull::new(ptr) {
-                Some(ptr) => ptr,
+                Some(ptr) => ptr.cast::<Data>(),
                 None => alloc::handle_alloc_error(self.layout),
             };
 
-            self.data.store(ptr.cast().as_ptr(), Ordering::Release);
+            // another thread may have allocated in the meantime
+            let swapped = self.data.compare_exchange(
+                ptr::null_mut(),
+                ptr.as_ptr(),
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            );
+
+            match swapped {
+                Ok(_) => {
+                    MIRROR_BYTES.fetch_add(self.layout.size() as u64, Ordering::AcqRel);
+                    MIRROR_ALLOCATIONS.fetch_add(1, Ordering::AcqRel);
+                }
+                Err(_) => unsafe { alloc::dealloc(ptr.as_ptr() as *mut u8, self.layout) },
+            }
+        }
+
+        /// Returns true if the CPU mirror has been allocated.
+        pub fn has_cpu_mirror(&self) -> bool {
+            !self.data.load(Ordering::Acquire).is_null()
         }
 
         pub fn size(&self) -> usize {
@@ -83,14 +169,10 @@ pub mod texture_view_dimension {
 
             let image_ptr = unsafe { (self.ptr() as *mut u8).add(z * bytes_per_image) };
             let row_ptr = unsafe { image_ptr.add(y * bytes_per_row) as *mut Data };
-            let ptr = unsafe { row_ptr.add(x) };
-
-            ptr
+            unsafe { row_ptr.add(x) }
         }
 
         pub fn bytes(&self) -> &[u8] {
-            self.allocate();
-
             unsafe { slice::from_raw_parts(self.ptr() as *const u8, self.layout.size()) }
         }
     }
@@ -99,9 +181,12 @@ pub mod texture_view_dimension {
         fn drop(&mut self) {
             assert!(!mem::needs_drop::<Data>());
 
-            let ptr = self.ptr();
+            // never allocate just to free
+            let ptr = *self.data.get_mut();
             if self.layout.size() > 0 && !ptr.is_null() {
                 unsafe { alloc::dealloc(ptr as *mut u8, self.layout) };
+
+                MIRROR_BYTES.fetch_sub(self.layout.size() as u64, Ordering::AcqRel);
             }
         }
     }
@@ -146,12 +231,20 @@ pub mod texture_view_dimension {
         fn bytes(&self) -> &[u8] {
             self.data.bytes()
         }
+
+        fn has_cpu_mirror(&self) -> bool {
+            self.data.has_cpu_mirror()
+        }
     }
 
     pub struct D1;
 
     impl<Format: TextureFormat> TextureDimension<Format> for D1 {
         type Storage = TextureStorageD1<Format::Data>;
+
+        fn view_dimension() -> wgpu::TextureViewDimension {
+            wgpu::TextureViewDimension::D1
+        }
     }
 
     fn bytes_per_row<Data: TextureData>(width: usize) -> usize {
@@ -212,12 +305,20 @@ pub mod texture_view_dimension {
         fn bytes(&self) -> &[u8] {
             self.data.bytes()
         }
+
+        fn has_cpu_mirror(&self) -> bool {
+            self.data.has_cpu_mirror()
+        }
     }
 
     pub struct D2;
 
     impl<Format: TextureFormat> TextureDimension<Format> for D2 {
         type Storage = TextureStorageD2<Format::Data>;
+
+        fn view_dimension() -> wgpu::TextureViewDimension {
+            wgpu::TextureViewDimension::D2
+        }
     }
 
     pub struct D2Array;
@@ -280,12 +381,27 @@ pub mod texture_format {
     }
 
     texture_format!(Rgba8UnormSrgb, Float<true>, Rgba8U);
+    // naga can't parse bgra8unorm storage textures, so these are sampled only
+    texture_format!(Bgra8Unorm, Float<true>, Bgra8U);
+    texture_format!(Bgra8UnormSrgb, Float<true>, Bgra8U);
     texture_format!(Rgba8Unorm, Float<true>, Rgba8U, Rgba8Unorm);
     texture_format!(Rgba8Snorm, Float<true>, Rgba8I, Rgba8Snorm);
     texture_format!(Rgba8Uint, Uint, Rgba8U, Rgba8Uint);
     texture_format!(Rgba8Sint, Sint, Rgba8I, Rgba8Sint);
     texture_format!(Rgba16Uint, Uint, Rgba16U, Rgba16Uint);
     texture_format!(Rgba16Sint, Sint, Rgba16I, Rgba16Sint);
+    // without the `f16` feature half precision texels are raw bits
+    #[cfg(feature = "f16")]
+    texture_format!(R16Float, Float<false>, R16F);
+    #[cfg(not(feature = "f16"))]
+    texture_format!(R16Float, Float<false>, R16U);
+    #[cfg(feature = "f16")]
+    texture_format!(Rg16Float, Float<false>, Rg16F);
+    #[cfg(not(feature = "f16"))]
+    texture_format!(Rg16Float, Float<false>, Rg16U);
+    #[cfg(feature = "f16")]
+    texture_format!(Rgba16Float, Float<false>, Rgba16F, Rgba16Float);
+    #[cfg(not(feature = "f16"))]
     texture_format!(Rgba16Float, Float<false>, Rgba16U, Rgba16Float);
     texture_format!(R32Uint, Uint, R32U, R32Uint);
     texture_format!(R32Sint, Sint, R32I, R32Sint);
@@ -306,6 +422,12 @@ pub trait Stored {
     type TexelFormat;
 }
 
+/// CPU side storage of a [`Texture`].
+///
+/// # Safety
+/// * `ptr` **must** point to an allocation at least `size` bytes long.
+/// * `bytes` **must** return a slice of exactly `size` bytes.
+/// * The data **must** be laid out according to `extent` and `bytes_per_row`.
 pub unsafe trait TextureStorage {
     fn extent(&self) -> wgpu::Extent3d;
 
@@ -313,23 +435,100 @@ pub unsafe trait TextureStorage {
 
     fn size(&self) -> usize;
 
+    /// Gets the CPU mirror, allocating it if needed.
     fn ptr(&self) -> *mut u8;
 
+    /// Gets the CPU mirror, allocating it if needed.
     fn bytes(&self) -> &[u8];
+
+    /// Returns true if the CPU mirror has been allocated.
+    fn has_cpu_mirror(&self) -> bool;
 }
 
 pub trait TextureDimension<Format: TextureFormat> {
     type Storage: TextureStorage;
+
+    fn view_dimension() -> wgpu::TextureViewDimension;
 }
 
+/// A single texel of a [`Texture`].
+///
+/// # Safety
+/// * It **must** be safe to cast the type to a slice of bytes.
+/// * The memory layout **must** match the texel layout on the GPU.
 pub unsafe trait TextureData: Copy {}
 
+/// The format of a [`Texture`], see the markers like [`Rgba8Unorm`].
+///
+/// There are no 3 channel formats, as the GPU has none that can be stored to.
+/// 3 channel colors like [`Rgb8U`](crate::Rgb8U) are packed into their 4
+/// channel counterpart with [`Texture2d::from_rgb_data`] or
+/// [`convert::pack_rgb_to_rgba`] and unpacked with [`Texture2d::rgb_data`].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a texture format",
+    note = "3 channel colors like `Rgb8U` have no texture format, use a 4 channel format like `Rgba8Unorm` and pack the colors with `Texture2d::from_rgb_data` or `convert::pack_rgb_to_rgba`"
+)]
 pub trait TextureFormat {
     type Data: TextureData;
 
     fn format(&self) -> wgpu::TextureFormat;
 }
 
+/// Usages of the gpu textures created by [`Texture`].
+pub const TEXTURE_USAGES: wgpu::TextureUsages = wgpu::TextureUsages::COPY_DST
+    .union(wgpu::TextureUsages::COPY_SRC)
+    .union(wgpu::TextureUsages::TEXTURE_BINDING)
+    .union(wgpu::TextureUsages::STORAGE_BINDING)
+    .union(wgpu::TextureUsages::RENDER_ATTACHMENT);
+
+/// Gets the usages of a texture of `format`, [`TEXTURE_USAGES`] without
+/// storage binding for formats that don't support it.
+pub fn texture_usages(format: wgpu::TextureFormat) -> wgpu::TextureUsages {
+    let allowed = format.describe().guaranteed_format_features.allowed_usages;
+
+    if allowed.contains(wgpu::TextureUsages::STORAGE_BINDING) {
+        TEXTURE_USAGES
+    } else {
+        TEXTURE_USAGES - wgpu::TextureUsages::STORAGE_BINDING
+    }
+}
+
+/// Default maximum number of bytes transferred per texture copy, see
+/// [`Texture::set_max_transfer_size`].
+pub const DEFAULT_MAX_TRANSFER_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Rows of a [`Texture2d`] downloaded together by indexing, about this many bytes.
+const INDEX_DOWNLOAD_SIZE: usize = 64 * 1024;
+
+/// Returns true if `row` is set in the bitset `rows`.
+fn is_fresh(rows: &[u64], row: u32) -> bool {
+    rows.get(row as usize / 64)
+        .is_some_and(|bits| bits & (1 << (row % 64)) != 0)
+}
+
+/// Sets `row` in the bitset `rows`.
+fn set_fresh(rows: &mut Vec<u64>, row: u32) {
+    let word = row as usize / 64;
+
+    if rows.len() <= word {
+        rows.resize(word + 1, 0);
+    }
+
+    rows[word] |= 1 << (row % 64);
+}
+
+/// A copy between a texture and its CPU mirror, see [`Texture::set_max_transfer_size`].
+struct TransferRegion {
+    origin: wgpu::Origin3d,
+    extent: wgpu::Extent3d,
+    /// Offset into the mirror of the first texel.
+    start: usize,
+    /// Bytes copied to or from the mirror.
+    len: usize,
+    /// Bytes per row of the copied data, a multiple of `COPY_BYTES_PER_ROW_ALIGNMENT`.
+    bytes_per_row: u32,
+}
+
 pub struct TextureBinding<SampleType, ViewDimension, const MULTISAMPLED: bool>(
     PhantomData<(SampleType, ViewDimension)>,
 );
@@ -348,6 +547,12 @@ where
     id: TextureId,
     needs_upload: AtomicBool,
     needs_download: AtomicBool,
+    /// Bitset of the mirror rows downloaded by indexing since the GPU last
+    /// wrote, see [`Texture2d::read_region`].
+    fresh_rows: Mutex<Vec<u64>>,
+    max_transfer_size: u64,
+    gpu_only: bool,
+    instance: &'static Instance,
 }
 
 impl<Format, Dimension, const MULTISAMPLED: bool> Texture<Format, Dimension, MULTISAMPLED>
@@ -369,16 +574,58 @@ where
 
     pub fn mark_needs_download(&mut self) {
         self.needs_download.store(true, Ordering::Release);
+        self.fresh_rows.get_mut().unwrap().clear();
     }
 
     pub fn wgpu_format(&self) -> wgpu::TextureFormat {
         self.format.format()
     }
 
+    pub fn resource_info(&self) -> ResourceInfo {
+        ResourceInfo::Texture {
+            id: self.id.clone(),
+            format: self.wgpu_format(),
+            view_dimension: Dimension::view_dimension(),
+            usage: texture_usages(self.wgpu_format()),
+        }
+    }
+
     pub fn texture_id(&self) -> &TextureId {
         &self.id
     }
 
+    /// Gets the instance the texture was created in, see [`Texture2d::new_in`].
+    pub fn instance(&self) -> &'static Instance {
+        self.instance
+    }
+
+    /// Returns true if the CPU mirror has been allocated.
+    ///
+    /// The mirror is allocated the first time the texture is accessed on the CPU.
+    pub fn has_cpu_mirror(&self) -> bool {
+        self.storage.has_cpu_mirror()
+    }
+
+    /// Returns true if the texture has no CPU side, see [`Texture2d::gpu_only`].
+    pub fn is_gpu_only(&self) -> bool {
+        self.gpu_only
+    }
+
+    fn assert_cpu_access(&self) {
+        assert!(
+            !self.gpu_only,
+            "texture is GPU only and cannot be accessed on the CPU"
+        );
+    }
+
+    /// Registers the texture in the global instance and returns a [`TextureHandle`] to it.
+    pub fn into_handle(self) -> TextureHandle<Format, Dimension, MULTISAMPLED>
+    where
+        Self: Send + 'static,
+    {
+        Instance::global().handles.register(self)
+    }
+
     pub fn bytes(&self) -> &[u8] {
         self.download();
 
@@ -397,97 +644,295 @@ where
         self.storage.extent().depth_or_array_layers as usize
     }
 
-    pub fn upload(&self) {
+    /// Sets the maximum number of bytes transferred per copy.
+    ///
+    /// Larger transfers are split into horizontal bands of whole rows, rows
+    /// longer than `size` are split into tiles of at least
+    /// `COPY_BYTES_PER_ROW_ALIGNMENT` bytes.
+    pub fn set_max_transfer_size(&mut self, size: u64) {
+        self.max_transfer_size = size;
+    }
+
+    pub fn max_transfer_size(&self) -> u64 {
+        self.max_transfer_size
+    }
+
+    fn rows_per_band(&self) -> u32 {
+        let extent = self.storage.extent();
+        let bytes_per_row = self.storage.bytes_per_row().map_or(1, |b| b.get() as u64);
+
+        (self.max_transfer_size / bytes_per_row).clamp(1, extent.height.max(1) as u64) as u32
+    }
+
+    /// Splits the texture into bands of at most [`Self::max_transfer_size`] bytes.
+    ///
+    /// Yields `(layer, first row, rows)` for each band.
+    fn bands(&self) -> impl Iterator<Item = (u32, u32, u32)> {
+        let extent = self.storage.extent();
+        let rows_per_band = self.rows_per_band();
+
+        (0..extent.depth_or_array_layers).flat_map(move |z| {
+            (0..extent.height)
+                .step_by(rows_per_band as usize)
+                .map(move |y| (z, y, rows_per_band.min(extent.height - y)))
+        })
+    }
+
+    /// Gets the offset into storage of `row` in `layer`.
+    fn band_offset(&self, layer: u32, row: u32) -> usize {
+        let extent = self.storage.extent();
+        let bytes_per_row = self.storage.bytes_per_row().map_or(0, |b| b.get() as usize);
+
+        (layer as usize * extent.height as usize + row as usize) * bytes_per_row
+    }
+
+    /// Splits the texture into the copies of a transfer, the [bands](Self::bands)
+    /// if a row fits in [`Self::max_transfer_size`], otherwise tiles of each row.
+    fn regions(&self) -> Vec<TransferRegion> {
+        let extent = self.storage.extent();
+        let bytes_per_row = self.storage.bytes_per_row().map_or(0, |b| b.get());
+        let size = self.storage.size();
+
+        if bytes_per_row as u64 <= self.max_transfer_size {
+            return self
+                .bands()
+                .map(|(z, y, rows)| {
+                    let start = self.band_offset(z, y);
+
+                    TransferRegion {
+                        origin: wgpu::Origin3d { x: 0, y, z },
+                        extent: wgpu::Extent3d {
+                            width: extent.width,
+                            height: rows,
+                            depth_or_array_layers: 1,
+                        },
+                        start,
+                        len: self.band_offset(z, y + rows).min(size) - start,
+                        bytes_per_row,
+                    }
+                })
+                .collect();
+        }
+
+        // tiles are whole multiples of the row alignment, so every tile but
+        // the last of a row is copied without padding
+        let texel = mem::size_of::<Format::Data>() as u64;
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64;
+        let texels_per_tile = ((self.max_transfer_size / align).max(1) * align / texel) as u32;
+
+        let mut regions = Vec::new();
+
+        for z in 0..extent.depth_or_array_layers {
+            for y in 0..extent.height {
+                for x in (0..extent.width).step_by(texels_per_tile as usize) {
+                    let width = texels_per_tile.min(extent.width - x);
+                    let len = width as usize * texel as usize;
+
+                    regions.push(TransferRegion {
+                        origin: wgpu::Origin3d { x, y, z },
+                        extent: wgpu::Extent3d {
+                            width,
+                            height: 1,
+                            depth_or_array_layers: 1,
+                        },
+                        start: self.band_offset(z, y) + x as usize * texel as usize,
+                        len,
+                        bytes_per_row: (len as u64).next_multiple_of(align) as u32,
+                    });
+                }
+            }
+        }
+
+        regions
+    }
+
+    /// Uploads the data if it has changed, returns the number of bytes uploaded.
+    pub fn upload(&self) -> u64 {
         if !self.needs_upload.swap(false, Ordering::AcqRel) {
-            return;
+            return 0;
         }
 
-        let instance = Instance::global();
+        let instance = self.instance;
 
         let size = self.storage.size();
 
         if size == 0 {
-            return;
+            return 0;
         }
 
         let texture = instance.textures.get(&self.id).unwrap();
 
-        instance.queue.write_texture(
-            wgpu::ImageCopyTexture {
-                texture: &texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
-                aspect: wgpu::TextureAspect::All,
-            },
-            self.storage.bytes(),
-            wgpu::ImageDataLayout {
-                offset: 0,
-                bytes_per_row: self.storage.bytes_per_row(),
-                rows_per_image: None,
-            },
-            self.storage.extent(),
-        );
+        let bytes = self.storage.bytes();
+
+        for region in self.regions() {
+            instance.queue.write_texture(
+                wgpu::ImageCopyTexture {
+                    texture: &texture,
+                    mip_level: 0,
+                    origin: region.origin,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                &bytes[region.start..region.start + region.len],
+                wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: NonZeroU32::new(region.bytes_per_row),
+                    rows_per_image: None,
+                },
+                region.extent,
+            );
+        }
+
+        instance.stats.uploaded(size as u64);
+
+        size as u64
     }
 
-    pub fn download(&self) {
+    /// Uploads the data for a dispatch reading the texture, re-uploading
+    /// unchanged data if [`Instance::force_upload`] is on.
+    pub(crate) fn upload_binding(&self) -> u64 {
+        let instance = self.instance;
+
+        if !instance.force_upload() || self.gpu_only || self.needs_upload() {
+            return self.upload();
+        }
+
+        self.download();
+        self.mark_needs_upload();
+
+        let bytes = self.upload();
+
+        instance.force_transfers.emit(ForcedTransfer {
+            direction: TransferDirection::Upload,
+            label: self.label(),
+            bytes,
+        });
+
+        bytes
+    }
+
+    /// Marks the texture as written by a dispatch, downloading it right away
+    /// if [`Instance::force_download`] is on.
+    fn write_binding(&mut self) {
+        self.mark_needs_download();
+
+        let instance = self.instance;
+
+        if instance.force_download() && !self.gpu_only {
+            instance.force_transfers.emit(ForcedTransfer {
+                direction: TransferDirection::Download,
+                label: self.label(),
+                bytes: self.download(),
+            });
+        }
+    }
+
+    /// Names the texture in [`ForcedTransfer`]s.
+    fn label(&self) -> String {
+        format!("Texture<{}>", std::any::type_name::<Format>())
+    }
+
+    /// Downloads the data if the GPU has written to it, returns the number of bytes downloaded.
+    ///
+    /// # Panics
+    /// Panics if the texture is [GPU only](Texture2d::gpu_only).
+    pub fn download(&self) -> u64 {
+        self.assert_cpu_access();
+
+        // held until the mirror is written, rows indexed meanwhile wait for it
+        let mut fresh_rows = self.fresh_rows.lock().unwrap();
+
         if !self.needs_download.swap(false, Ordering::AcqRel) {
-            return;
+            return 0;
         }
 
-        let instance = Instance::global();
+        let instance = self.instance;
 
         let size = self.storage.size();
 
         if size == 0 {
-            return;
+            return 0;
         }
 
-        let size = size.max(4) as u64;
+        let extent = self.storage.extent();
+        let regions = self.regions();
+        let staging_size = regions
+            .iter()
+            .map(|region| (region.len as u64).next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT))
+            .max()
+            .unwrap_or(0);
 
-        let staging_buffer = instance.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("shatter_staging_buffer"),
-            size,
-            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        // the staging buffer is reused for every band or tile
+        let staging_buffer = instance.acquire_staging(staging_size.max(4));
 
         let texture = instance.textures.get(&self.id).unwrap();
 
-        let mut encoder = instance.device.create_command_encoder(&Default::default());
-        encoder.copy_texture_to_buffer(
-            wgpu::ImageCopyTexture {
-                texture: &texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
-                aspect: wgpu::TextureAspect::All,
-            },
-            wgpu::ImageCopyBuffer {
-                buffer: &staging_buffer,
-                layout: wgpu::ImageDataLayout {
-                    offset: 0,
-                    bytes_per_row: self.storage.bytes_per_row(),
-                    rows_per_image: None,
+        for region in regions {
+            let mut encoder = instance.device.create_command_encoder(&Default::default());
+            encoder.copy_texture_to_buffer(
+                wgpu::ImageCopyTexture {
+                    texture: &texture,
+                    mip_level: 0,
+                    origin: region.origin,
+                    aspect: wgpu::TextureAspect::All,
                 },
-            },
-            self.storage.extent(),
-        );
-        instance.queue.submit(std::iter::once(encoder.finish()));
+                wgpu::ImageCopyBuffer {
+                    buffer: &staging_buffer,
+                    layout: wgpu::ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: NonZeroU32::new(region.bytes_per_row),
+                        rows_per_image: None,
+                    },
+                },
+                region.extent,
+            );
+            instance.queue.submit(std::iter::once(encoder.finish()));
+            instance.submitted(Some(Cow::Borrowed("shatter_texture_download")), &[]);
+
+            let (start, end) = (region.start, region.start + region.len);
+            let band = staging_buffer
+                .slice(..(region.len as u64).next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT));
+
+            let future = band.map_async(wgpu::MapMode::Read);
+            instance.device.poll(wgpu::Maintain::Wait);
+            pollster::block_on(future).unwrap();
+
+            {
+                // the mapped range of a tile may be padded
+                let mapped = band.get_mapped_range();
+                let slice: &[u8] = &mapped[..end - start];
+
+                let bytes_per_row = self.storage.bytes_per_row().map_or(0, |b| b.get() as usize);
+                let first_row = region.origin.z * extent.height + region.origin.y;
+
+                // rows downloaded by indexing may be borrowed, they're skipped
+                // rather than overwritten with the same texels
+                for (i, row) in slice.chunks(bytes_per_row.max(1)).enumerate() {
+                    if is_fresh(&fresh_rows, first_row + i as u32) {
+                        continue;
+                    }
+
+                    // SAFETY:
+                    // * end is clamped to the size of storage.
+                    // * row doesn't overlap with storage.
+                    unsafe {
+                        std::ptr::copy_nonoverlapping(
+                            row.as_ptr(),
+                            self.storage.ptr().add(start + i * bytes_per_row),
+                            row.len(),
+                        )
+                    };
+                }
+            }
 
-        let future = staging_buffer.slice(..).map_async(wgpu::MapMode::Read);
-        instance.device.poll(wgpu::Maintain::Wait);
-        pollster::block_on(future).unwrap();
+            staging_buffer.unmap();
+        }
 
-        let slice: &[u8] = &staging_buffer.slice(..).get_mapped_range();
+        fresh_rows.clear();
+        instance.release_staging(staging_buffer);
 
-        assert_eq!(slice.len(), size as usize);
+        instance.stats.downloaded(size as u64);
 
-        unsafe {
-            std::ptr::copy_nonoverlapping(
-                slice as *const [u8] as *const u8,
-                self.storage.ptr(),
-                size as usize,
-            )
-        };
+        size as u64
     }
 }
 
@@ -502,14 +947,17 @@ where
         BindingResource::TextureView(self.id.clone())
     }
 
-    fn prepare(&self) {}
+    fn resource_info(&self) -> ResourceInfo {
+        self.resource_info()
+    }
 
-    fn read(&self) {
-        self.upload();
+    fn prepare(&self, report: &mut TransferReport) {
+        report.textures_touched += 1;
     }
 
-    fn write(&mut self) {
-        self.mark_needs_download();
+    fn read(&self, report: &mut TransferReport) {
+        report.textures_touched += 1;
+        report.uploaded_bytes += self.upload_binding();
     }
 }
 
@@ -524,67 +972,460 @@ where
         BindingResource::TextureView(self.id.clone())
     }
 
-    fn prepare(&self) {}
+    fn resource_info(&self) -> ResourceInfo {
+        self.resource_info()
+    }
 
-    fn read(&self) {
-        self.upload();
+    fn prepare(&self, report: &mut TransferReport) {
+        report.textures_touched += 1;
     }
 
+    fn read(&self, report: &mut TransferReport) {
+        report.textures_touched += 1;
+        report.uploaded_bytes += self.upload_binding();
+    }
+}
+
+impl<Format, Dimension, const MULTISAMPLED: bool>
+    BindingMut<StorageTextureBinding<Format::TexelFormat, Dimension>>
+    for Texture<Format, Dimension, MULTISAMPLED>
+where
+    Format: TextureFormat + Stored,
+    Dimension: TextureDimension<Format>,
+{
     fn write(&mut self) {
-        self.mark_needs_download();
+        self.write_binding();
     }
 }
 
 pub type Texture2d<Format> = Texture<Format, texture_view_dimension::D2, false>;
 
 impl<Format: TextureFormat + Default> Texture2d<Format> {
+    /// Creates a texture, the CPU mirror is allocated on first CPU access.
     pub fn new(width: usize, height: usize) -> Self {
-        let format = Format::default();
+        Self::create(Instance::global(), width, height, false)
+    }
 
-        let instance = Instance::global();
+    /// Creates a texture in `instance` rather than the global instance, it
+    /// can only be bound to shaders dispatched in `instance`, see
+    /// [`ComputeShaderBuilder::instance`](crate::ComputeShaderBuilder::instance).
+    pub fn new_in(instance: &'static Instance, width: usize, height: usize) -> Self {
+        Self::create(instance, width, height, false)
+    }
 
-        let texture = instance.device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("shatter_texture"),
-            size: wgpu::Extent3d {
-                width: width as u32,
-                height: height as u32,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: format.format(),
-            usage: wgpu::TextureUsages::COPY_DST
-                | wgpu::TextureUsages::COPY_SRC
-                | wgpu::TextureUsages::TEXTURE_BINDING
-                | wgpu::TextureUsages::STORAGE_BINDING
-                | wgpu::TextureUsages::RENDER_ATTACHMENT,
-        });
+    /// Creates a texture without a CPU side, for intermediates that never
+    /// leave the GPU.
+    ///
+    /// # Panics
+    /// Indexing, [`Texture::bytes`] and [`Texture::download`] panic.
+    pub fn gpu_only(width: usize, height: usize) -> Self {
+        Self::create(Instance::global(), width, height, true)
+    }
 
-        let id = instance.textures.next_id();
-        instance.textures.insert(id.clone(), texture);
+    /// Like [`Self::new`], failing with
+    /// [`DeviceError::OutOfMemory`](crate::DeviceError::OutOfMemory) if the
+    /// [OOM handler](Instance::set_oom_handler) gives up instead of panicking.
+    pub fn try_new(width: usize, height: usize) -> Result<Self, Error> {
+        Self::try_create(Instance::global(), width, height, false)
+    }
+
+    /// Gets the dispatch covering every texel with `work_group_size`, see
+    /// [`Dispatch::for_size`].
+    pub fn dispatch_for(&self, work_group_size: WorkGroupSize) -> Dispatch {
+        Dispatch::for_size(
+            work_group_size,
+            (self.width() as u32, self.height() as u32, 1),
+        )
+    }
+
+    fn create(instance: &'static Instance, width: usize, height: usize, gpu_only: bool) -> Self {
+        Self::try_create(instance, width, height, gpu_only).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_create(
+        instance: &'static Instance,
+        width: usize,
+        height: usize,
+        gpu_only: bool,
+    ) -> Result<Self, Error> {
+        let format = Format::default();
+        let size = width as u64 * height as u64 * format.format().describe().block_size as u64;
 
-        Self {
+        // copies describe rows with a u32, checked before allocating anything
+        let bytes_per_row = (width as u64 * mem::size_of::<Format::Data>() as u64)
+            .next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64);
+
+        if bytes_per_row > u32::MAX as u64 {
+            return Err(ResourceError::LimitExceeded {
+                limit: "bytes_per_row",
+                requested: bytes_per_row,
+                max: u32::MAX as u64,
+            }
+            .into());
+        }
+
+        let texture =
+            instance.create_resource(ResourceKind::Texture, "shatter_texture", size, || {
+                instance.device.create_texture(&wgpu::TextureDescriptor {
+                    label: Some("shatter_texture"),
+                    size: wgpu::Extent3d {
+                        width: width as u32,
+                        height: height as u32,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: format.format(),
+                    usage: texture_usages(format.format()),
+                })
+            })?;
+
+        let id = instance.textures.stable_id("shatter_texture");
+        instance.textures.insert_sized(id.clone(), texture, size);
+
+        Ok(Self {
             format,
             storage: texture_view_dimension::TextureStorageD2::new(width, height),
             id,
             needs_upload: AtomicBool::new(false),
             needs_download: AtomicBool::new(false),
+            fresh_rows: Mutex::new(Vec::new()),
+            max_transfer_size: DEFAULT_MAX_TRANSFER_SIZE,
+            gpu_only,
+            instance,
+        })
+    }
+}
+
+impl<Format: TextureFormat + Default> Texture2d<Format> {
+    /// Creates a texture from tightly packed rows of texels.
+    ///
+    /// # Panics
+    /// Panics if `data` doesn't hold `width * height` texels.
+    pub fn from_data(width: usize, height: usize, data: &[Format::Data]) -> Self {
+        let mut texture = Self::new(width, height);
+        texture.write_data(data);
+
+        texture
+    }
+
+    /// Overwrites every texel with tightly packed rows of texels, the rows
+    /// are padded to `COPY_BYTES_PER_ROW_ALIGNMENT` in the CPU mirror.
+    ///
+    /// # Panics
+    /// Panics if `data` doesn't hold `width * height` texels, or if the texture
+    /// is [`gpu_only`](Self::gpu_only).
+    pub fn write_data(&mut self, data: &[Format::Data]) {
+        self.assert_cpu_access();
+
+        let (width, height) = (self.width(), self.height());
+        assert_eq!(
+            data.len(),
+            width * height,
+            "expected {}x{} texels",
+            width,
+            height
+        );
+
+        // every texel is overwritten, so what the GPU wrote is never read
+        self.needs_download.store(false, Ordering::Release);
+
+        for (y, row) in data.chunks(width.max(1)).enumerate() {
+            self.row_mut(y).copy_from_slice(row);
         }
+
+        self.mark_needs_upload();
+    }
+
+    /// Reads the `size` texels at `origin` as tightly packed rows, copying only
+    /// them if the GPU wrote to the texture, without downloading the rest.
+    ///
+    /// Unlike [`Self::to_vec`] this works on [GPU only](Self::gpu_only) textures.
+    ///
+    /// # Panics
+    /// Panics if the region is out of bounds or the download fails.
+    pub fn read_region(
+        &self,
+        (x, y): (usize, usize),
+        (width, height): (usize, usize),
+    ) -> Vec<Format::Data> {
+        assert!(
+            x + width <= self.width() && y + height <= self.height(),
+            "region of {}x{} at ({}, {}) is out of bounds of a {}x{} texture",
+            width,
+            height,
+            x,
+            y,
+            self.width(),
+            self.height(),
+        );
+
+        if width == 0 || height == 0 {
+            return Vec::new();
+        }
+
+        // the mirror holds the texture, or changes not uploaded yet
+        if !self.gpu_only && !self.needs_download() {
+            return (y..y + height)
+                .flat_map(|y| &self.row(y)[x..x + width])
+                .copied()
+                .collect();
+        }
+
+        let texel = mem::size_of::<Format::Data>();
+        let mut texels = Vec::<Format::Data>::with_capacity(width * height);
+
+        self.read_texels((x, y), (width, height), |mapped, bytes_per_row| {
+            for (i, row) in mapped.chunks(bytes_per_row).take(height).enumerate() {
+                // SAFETY: TextureData can be cast from the bytes of a texel
+                unsafe {
+                    std::ptr::copy_nonoverlapping(
+                        row.as_ptr(),
+                        (texels.as_mut_ptr() as *mut u8).add(i * width * texel),
+                        width * texel,
+                    )
+                };
+            }
+
+            // SAFETY: every texel was written above
+            unsafe { texels.set_len(width * height) };
+        });
+
+        texels
+    }
+
+    /// Copies the `size` texels at `origin` from the GPU, calling `read` with
+    /// the mapped rows and their stride.
+    fn read_texels(
+        &self,
+        (x, y): (usize, usize),
+        (width, height): (usize, usize),
+        read: impl FnOnce(&[u8], usize),
+    ) {
+        let instance = self.instance;
+
+        let bytes_per_row = (width * mem::size_of::<Format::Data>())
+            .next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize);
+        let size = (bytes_per_row * height) as u64;
+
+        let staging_buffer = instance.acquire_staging(size);
+
+        {
+            let texture = instance.textures.get(&self.id).unwrap();
+
+            let mut encoder = instance.device.create_command_encoder(&Default::default());
+            encoder.copy_texture_to_buffer(
+                wgpu::ImageCopyTexture {
+                    texture: &texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d {
+                        x: x as u32,
+                        y: y as u32,
+                        z: 0,
+                    },
+                    aspect: wgpu::TextureAspect::All,
+                },
+                wgpu::ImageCopyBuffer {
+                    buffer: &staging_buffer,
+                    layout: wgpu::ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: NonZeroU32::new(bytes_per_row as u32),
+                        rows_per_image: None,
+                    },
+                },
+                wgpu::Extent3d {
+                    width: width as u32,
+                    height: height as u32,
+                    depth_or_array_layers: 1,
+                },
+            );
+            instance.queue.submit(std::iter::once(encoder.finish()));
+            instance.submitted(Some(Cow::Borrowed("shatter_texture_read_region")), &[]);
+        }
+
+        let slice = staging_buffer.slice(..size);
+        let future = slice.map_async(wgpu::MapMode::Read);
+        instance.device.poll(wgpu::Maintain::Wait);
+
+        if let Err(err) = pollster::block_on(future) {
+            panic!("{}", Error::from(err));
+        }
+
+        read(&slice.get_mapped_range(), bytes_per_row);
+
+        staging_buffer.unmap();
+        instance.release_staging(staging_buffer);
+
+        instance.stats.downloaded(size);
+    }
+
+    /// Downloads the rows around `y` if the GPU wrote to the texture, so
+    /// indexing a texel doesn't download the whole texture.
+    fn download_rows_around(&self, y: usize) {
+        self.assert_cpu_access();
+
+        let mut fresh_rows = self.fresh_rows.lock().unwrap();
+
+        if !self.needs_download() || is_fresh(&fresh_rows, y as u32) {
+            return;
+        }
+
+        let (width, height) = (self.width(), self.height());
+        let bytes_per_row = self.storage.bytes_per_row().map_or(0, |b| b.get() as usize);
+
+        let rows = (INDEX_DOWNLOAD_SIZE / bytes_per_row.max(1)).clamp(1, height);
+        let first = y / rows * rows;
+        let rows = rows.min(height - first);
+
+        self.read_texels((0, first), (width, rows), |mapped, stride| {
+            // the mirror has the same stride
+            debug_assert_eq!(stride, bytes_per_row);
+
+            for (i, row) in mapped.chunks(stride).take(rows).enumerate() {
+                let y = (first + i) as u32;
+
+                // rows already downloaded may be borrowed
+                if is_fresh(&fresh_rows, y) {
+                    continue;
+                }
+
+                let start = self.band_offset(0, y);
+
+                // SAFETY: the row is within the mirror and not borrowed
+                unsafe {
+                    std::ptr::copy_nonoverlapping(
+                        row.as_ptr(),
+                        self.storage.ptr().add(start),
+                        bytes_per_row,
+                    )
+                };
+
+                set_fresh(&mut fresh_rows, y);
+            }
+        });
+
+        // every row is downloaded
+        if fresh_rows.iter().map(|bits| bits.count_ones()).sum::<u32>() == height as u32 {
+            self.needs_download.store(false, Ordering::Release);
+            fresh_rows.clear();
+        }
+    }
+
+    /// Downloads the texture as tightly packed rows of texels.
+    ///
+    /// # Panics
+    /// Panics if the texture is [`gpu_only`](Self::gpu_only).
+    pub fn to_vec(&self) -> Vec<Format::Data> {
+        self.rows().flatten().copied().collect()
+    }
+
+    /// Downloads the texture and iterates over its rows of texels, without
+    /// the padding of [`Texture::bytes`].
+    ///
+    /// # Panics
+    /// Panics if the texture is [`gpu_only`](Self::gpu_only).
+    pub fn rows(&self) -> impl Iterator<Item = &[Format::Data]> {
+        self.download();
+
+        (0..self.height()).map(move |y| self.row(y))
+    }
+
+    /// Gets row `y` of the CPU mirror without transferring.
+    fn row(&self, y: usize) -> &[Format::Data] {
+        let width = self.width();
+
+        if width == 0 {
+            return &[];
+        }
+
+        let extent = self.storage.extent();
+        unsafe { slice::from_raw_parts(self.storage.data.index(extent, 0, y, 0), width) }
+    }
+
+    /// Gets row `y` of the CPU mirror without transferring or marking it.
+    fn row_mut(&mut self, y: usize) -> &mut [Format::Data] {
+        let width = self.width();
+
+        if width == 0 {
+            return &mut [];
+        }
+
+        let extent = self.storage.extent();
+        unsafe { slice::from_raw_parts_mut(self.storage.data.index(extent, 0, y, 0), width) }
+    }
+}
+
+impl<Format: TextureFormat + Default> Texture2d<Format>
+where
+    Format::Data: RgbaColor,
+{
+    /// Creates a texture from tightly packed rows of 3 channel colors, adding
+    /// `alpha` to each of them.
+    ///
+    /// # Panics
+    /// Panics if `data` doesn't hold `width * height` colors.
+    pub fn from_rgb_data(
+        width: usize,
+        height: usize,
+        data: &[<Format::Data as RgbaColor>::Rgb],
+        alpha: <<Format::Data as RgbaColor>::Rgb as RgbColor>::Channel,
+    ) -> Self {
+        assert_eq!(
+            data.len(),
+            width * height,
+            "expected {}x{} colors",
+            width,
+            height
+        );
+
+        let mut texture = Self::new(width, height);
+
+        // packed straight into the padded rows of the mirror
+        for (y, row) in data.chunks(width.max(1)).enumerate() {
+            convert::pack_rgb_into(row, alpha, texture.row_mut(y));
+        }
+
+        texture.mark_needs_upload();
+
+        texture
+    }
+
+    /// Downloads the texture as tightly packed rows of 3 channel colors,
+    /// dropping the alpha channel.
+    ///
+    /// # Panics
+    /// Panics if the texture is [`gpu_only`](Self::gpu_only).
+    pub fn rgb_data(&self) -> Vec<<Format::Data as RgbaColor>::Rgb> {
+        let width = self.width();
+        let mut data = vec![Default::default(); width * self.height()];
+
+        for (row, texels) in data.chunks_mut(width.max(1)).zip(self.rows()) {
+            convert::strip_alpha_into(texels, row);
+        }
+
+        data
     }
 }
 
 impl<Format: TextureFormat + Default> Index<(usize, usize)> for Texture2d<Format> {
     type Output = Format::Data;
 
+    /// Downloads only the rows around the texel if the GPU wrote to the
+    /// texture, use [`Texture2d::read_region`] to read a region at once.
     fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
-        self.download();
+        self.assert_cpu_access();
+
+        let texel = self.storage.data.index(self.storage.extent(), x, y, 0);
+        self.download_rows_around(y);
 
-        unsafe { &*self.storage.data.index(self.storage.extent(), x, y, 0) }
+        unsafe { &*texel }
     }
 }
 
 impl<Format: TextureFormat + Default> IndexMut<(usize, usize)> for Texture2d<Format> {
+    /// Downloads the whole texture if the GPU wrote to it, as the whole
+    /// texture is uploaded before the next dispatch.
     fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
         self.download();
 
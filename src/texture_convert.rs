@@ -0,0 +1,438 @@
+use std::fmt;
+
+use crate::{
+    submit_encoded, texture_usages, BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor,
+    BindingResource, ComputePipelineDescriptor, Instance, PipelineLayoutDescriptor, Texture2d,
+    TextureFormat, TextureId,
+};
+
+/// Work group size of the conversion kernels in both dimensions.
+const WORK_GROUP_SIZE: u32 = 8;
+
+/// Why [`convert`] can't convert between two textures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConvertError {
+    /// The textures differ in size, `(width, height)`.
+    Extent { src: (u32, u32), dst: (u32, u32) },
+    /// No kernel converts between the formats, e.g. depth formats or
+    /// destinations that can't be written by a compute shader.
+    Unsupported {
+        src: wgpu::TextureFormat,
+        dst: wgpu::TextureFormat,
+    },
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Extent { src, dst } => write!(
+                f,
+                "can't convert a {}x{} texture to {}x{}",
+                src.0, src.1, dst.0, dst.1
+            ),
+            Self::Unsupported { src, dst } => {
+                write!(f, "can't convert from {:?} to {:?}", src, dst)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConvertError {}
+
+/// How [`convert_in`] converted a texture.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConvertMethod {
+    /// The formats share a layout, the texels were copied as is.
+    Copy,
+    /// A kernel converted the texels.
+    Kernel,
+    /// A kernel converted the texels into an intermediate texture, which was
+    /// copied to the destination, for destinations that can't be written by a
+    /// compute shader, e.g. sRGB formats.
+    KernelAndCopy,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ScalarKind {
+    Float,
+    Uint,
+    Sint,
+}
+
+impl ScalarKind {
+    fn of(format: wgpu::TextureFormat) -> Option<Self> {
+        match format.describe().sample_type {
+            wgpu::TextureSampleType::Float { .. } => Some(Self::Float),
+            wgpu::TextureSampleType::Uint => Some(Self::Uint),
+            wgpu::TextureSampleType::Sint => Some(Self::Sint),
+            wgpu::TextureSampleType::Depth => None,
+        }
+    }
+
+    fn wgsl(self) -> &'static str {
+        match self {
+            Self::Float => "f32",
+            Self::Uint => "u32",
+            Self::Sint => "i32",
+        }
+    }
+
+    fn one(self) -> &'static str {
+        match self {
+            Self::Float => "1.0",
+            Self::Uint => "1u",
+            Self::Sint => "1",
+        }
+    }
+}
+
+/// Gets the WGSL name of a storage texture format.
+fn storage_format_name(format: wgpu::TextureFormat) -> Option<&'static str> {
+    use wgpu::TextureFormat::*;
+
+    if !texture_usages(format).contains(wgpu::TextureUsages::STORAGE_BINDING) {
+        return None;
+    }
+
+    Some(match format {
+        Rgba8Unorm => "rgba8unorm",
+        Rgba8Snorm => "rgba8snorm",
+        Rgba8Uint => "rgba8uint",
+        Rgba8Sint => "rgba8sint",
+        Rgba16Uint => "rgba16uint",
+        Rgba16Sint => "rgba16sint",
+        Rgba16Float => "rgba16float",
+        R32Uint => "r32uint",
+        R32Sint => "r32sint",
+        R32Float => "r32float",
+        Rg32Uint => "rg32uint",
+        Rg32Sint => "rg32sint",
+        Rg32Float => "rg32float",
+        Rgba32Uint => "rgba32uint",
+        Rgba32Sint => "rgba32sint",
+        Rgba32Float => "rgba32float",
+        _ => return None,
+    })
+}
+
+/// Gets the linear format with the same layout as `format`.
+fn linear_format(format: wgpu::TextureFormat) -> wgpu::TextureFormat {
+    match format {
+        wgpu::TextureFormat::Rgba8UnormSrgb => wgpu::TextureFormat::Rgba8Unorm,
+        wgpu::TextureFormat::Bgra8UnormSrgb => wgpu::TextureFormat::Bgra8Unorm,
+        format => format,
+    }
+}
+
+/// The kernel writing a destination format.
+struct Target {
+    /// Format written by the kernel, an intermediate if it differs from the destination.
+    format: wgpu::TextureFormat,
+    srgb_encode: bool,
+    /// Whether the kernel writes BGRA texels into an RGBA intermediate.
+    swizzle: bool,
+}
+
+impl Target {
+    fn of(dst: wgpu::TextureFormat) -> Option<Self> {
+        let linear = linear_format(dst);
+        let srgb_encode = dst.describe().srgb;
+
+        if storage_format_name(linear).is_some() {
+            return Some(Self {
+                format: linear,
+                srgb_encode,
+                swizzle: false,
+            });
+        }
+
+        // BGRA isn't a storage format, its bytes are written as RGBA
+        if linear == wgpu::TextureFormat::Bgra8Unorm {
+            return Some(Self {
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                srgb_encode,
+                swizzle: true,
+            });
+        }
+
+        None
+    }
+}
+
+/// Generates the kernel converting `src` texels into `target`.
+fn kernel_source(src: wgpu::TextureFormat, target: &Target) -> Option<String> {
+    let src_kind = ScalarKind::of(src)?;
+    let dst_kind = ScalarKind::of(target.format)?;
+    let dst_name = storage_format_name(target.format)?;
+
+    let src_scalar = src_kind.wgsl();
+
+    // single channel sources are broadcast to every color channel
+    let texel = if src.describe().components == 1 {
+        format!(
+            "vec4<{}>(texel.r, texel.r, texel.r, {})",
+            src_scalar,
+            src_kind.one()
+        )
+    } else {
+        String::from("texel")
+    };
+
+    let value = match (src_kind, dst_kind) {
+        (src, dst) if src == dst => texel,
+        // negative floats have no unsigned value
+        (ScalarKind::Float, ScalarKind::Uint) => {
+            format!("vec4<u32>(max({}, vec4<f32>(0.0)))", texel)
+        }
+        (_, dst) => format!("vec4<{}>({})", dst.wgsl(), texel),
+    };
+
+    let mut body = format!("    var value = {};\n", value);
+
+    if dst_name.ends_with("unorm") {
+        body += "    value = clamp(value, vec4<f32>(0.0), vec4<f32>(1.0));\n";
+    }
+
+    if target.srgb_encode {
+        body += "    value = vec4<f32>(encode_srgb(value.r), encode_srgb(value.g), \
+            encode_srgb(value.b), value.a);\n";
+    }
+
+    if target.swizzle {
+        body += "    value = value.bgra;\n";
+    }
+
+    Some(format!(
+        "\
+[[group(0), binding(0)]]
+var source: texture_2d<{src_scalar}>;
+
+[[group(0), binding(1)]]
+var destination: texture_storage_2d<{dst_name}, write>;
+
+fn encode_srgb(value: f32) -> f32 {{
+    if (value <= 0.0031308) {{
+        return value * 12.92;
+    }}
+
+    return 1.055 * pow(value, 1.0 / 2.4) - 0.055;
+}}
+
+[[stage(compute), workgroup_size({size}, {size})]]
+fn main([[builtin(global_invocation_id)]] id: vec3<u32>) {{
+    let size = textureDimensions(destination);
+
+    if (i32(id.x) >= size.x || i32(id.y) >= size.y) {{
+        return;
+    }}
+
+    let texel = textureLoad(source, vec2<i32>(id.xy), 0);
+{body}
+    textureStore(destination, vec2<i32>(id.xy), value);
+}}
+",
+        src_scalar = src_scalar,
+        dst_name = dst_name,
+        size = WORK_GROUP_SIZE,
+        body = body,
+    ))
+}
+
+/// Records the conversion kernel from `src` into `target`.
+fn record_kernel(
+    encoder: &mut wgpu::CommandEncoder,
+    source: &str,
+    src: (&TextureId, wgpu::TextureFormat),
+    target: (&TextureId, wgpu::TextureFormat),
+    (width, height): (u32, u32),
+) {
+    let instance = Instance::global();
+
+    let layout = instance.get_bind_group_layout(BindGroupLayoutDescriptor::new(vec![
+        wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Texture {
+                sample_type: match src.1.describe().sample_type {
+                    wgpu::TextureSampleType::Float { .. } => {
+                        wgpu::TextureSampleType::Float { filterable: false }
+                    }
+                    sample_type => sample_type,
+                },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        },
+        wgpu::BindGroupLayoutEntry {
+            binding: 1,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::StorageTexture {
+                access: wgpu::StorageTextureAccess::WriteOnly,
+                format: target.1,
+                view_dimension: wgpu::TextureViewDimension::D2,
+            },
+            count: None,
+        },
+    ]));
+
+    let bind_group = instance.get_bind_group(BindGroupDescriptor::new(
+        layout.clone(),
+        vec![
+            BindGroupEntry {
+                binding: 0,
+                resource: BindingResource::TextureView(src.0.clone()),
+            },
+            BindGroupEntry {
+                binding: 1,
+                resource: BindingResource::TextureView(target.0.clone()),
+            },
+        ],
+    ));
+
+    let pipeline_layout = instance.get_pipeline_layout(PipelineLayoutDescriptor {
+        bind_group_layouts: vec![layout],
+        push_constant_ranges: Vec::new(),
+    });
+
+    let pipeline = instance.get_compute_pipeline(ComputePipelineDescriptor {
+        layout: Some(pipeline_layout),
+        module: instance.get_shader_module(source.to_owned()),
+        entry_point: "main".into(),
+    });
+
+    let bind_group = instance.bind_groups.get(&bind_group).unwrap();
+    let pipeline = instance.compute_pipelines.get(&pipeline).unwrap();
+
+    let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+        label: Some("shatter_compute_pass(convert)"),
+    });
+
+    compute_pass.set_pipeline(&pipeline);
+    compute_pass.set_bind_group(0, &bind_group, &[]);
+    compute_pass.dispatch(
+        width.div_ceil(WORK_GROUP_SIZE),
+        height.div_ceil(WORK_GROUP_SIZE),
+        1,
+    );
+}
+
+fn record_copy(
+    encoder: &mut wgpu::CommandEncoder,
+    src: &TextureId,
+    dst: &TextureId,
+    (width, height): (u32, u32),
+) {
+    let instance = Instance::global();
+
+    let src = instance.textures.get(src).unwrap();
+    let dst = instance.textures.get(dst).unwrap();
+
+    encoder.copy_texture_to_texture(
+        src.as_image_copy(),
+        dst.as_image_copy(),
+        wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+    );
+}
+
+/// Converts `src` into `dst` on the GPU, see [`convert_in`].
+pub fn convert<A, B>(
+    src: &Texture2d<A>,
+    dst: &mut Texture2d<B>,
+) -> Result<ConvertMethod, ConvertError>
+where
+    A: TextureFormat + Default,
+    B: TextureFormat + Default,
+{
+    submit_encoded("shatter_command_encoder(convert)", |encoder| {
+        convert_in(encoder, src, dst)
+    })
+}
+
+/// Records converting `src` into `dst` into `encoder`, without downloading
+/// `src` to the CPU.
+///
+/// Float sources are clamped to `0.0..=1.0` for unorm destinations and sRGB
+/// encoded for sRGB destinations, sRGB sources are decoded. Integers are cast,
+/// negative floats become zero when cast to unsigned. Single channel sources
+/// are broadcast to the color channels, with an alpha of one.
+///
+/// Formats sharing a layout, e.g. `Rgba8Unorm` and `Rgba8UnormSrgb`, are
+/// copied without a kernel, so the texels are reinterpreted rather than
+/// encoded.
+pub fn convert_in<A, B>(
+    encoder: &mut wgpu::CommandEncoder,
+    src: &Texture2d<A>,
+    dst: &mut Texture2d<B>,
+) -> Result<ConvertMethod, ConvertError>
+where
+    A: TextureFormat + Default,
+    B: TextureFormat + Default,
+{
+    let extent = (src.width() as u32, src.height() as u32);
+    let dst_extent = (dst.width() as u32, dst.height() as u32);
+
+    if extent != dst_extent {
+        return Err(ConvertError::Extent {
+            src: extent,
+            dst: dst_extent,
+        });
+    }
+
+    let src_format = src.wgpu_format();
+    let dst_format = dst.wgpu_format();
+    let unsupported = ConvertError::Unsupported {
+        src: src_format,
+        dst: dst_format,
+    };
+
+    let target = Target::of(dst_format).ok_or(unsupported)?;
+    let source = kernel_source(src_format, &target);
+
+    // pending CPU writes are uploaded first, so they can't overwrite the result later
+    src.upload();
+    dst.upload();
+
+    let method = if linear_format(src_format) == linear_format(dst_format) {
+        record_copy(encoder, src.texture_id(), dst.texture_id(), extent);
+
+        ConvertMethod::Copy
+    } else if target.format == dst_format {
+        let source = source.ok_or(unsupported)?;
+
+        record_kernel(
+            encoder,
+            &source,
+            (src.texture_id(), src_format),
+            (dst.texture_id(), dst_format),
+            extent,
+        );
+
+        ConvertMethod::Kernel
+    } else {
+        let source = source.ok_or(unsupported)?;
+
+        // the only intermediate format, see `Target::of`
+        debug_assert_eq!(target.format, wgpu::TextureFormat::Rgba8Unorm);
+        let intermediate = Instance::global().convert_intermediate(extent.0, extent.1);
+
+        record_kernel(
+            encoder,
+            &source,
+            (src.texture_id(), src_format),
+            (&intermediate, target.format),
+            extent,
+        );
+        record_copy(encoder, &intermediate, dst.texture_id(), extent);
+
+        ConvertMethod::KernelAndCopy
+    };
+
+    dst.mark_needs_download();
+
+    Ok(method)
+}
@@ -0,0 +1,176 @@
+use crate::{
+    submit_encoded_in, Bgra8U, Buffer, BufferVec, Rg16I, Rg16U, Rg32, Rg32I, Rg32U, Rgba16U,
+    Rgba32, Rgba32I, Rgba32U, Rgba8I, Rgba8U, Texture2d, TextureData, TextureFormat, Vec2, Vec4,
+    R32, R32I, R32U,
+};
+
+/// A buffer item laid out like the texel `Data`, copied between buffers and
+/// textures as is, see [`Texture2d::copy_to_typed_buffer`].
+///
+/// Every texel is laid out like itself. WGSL buffers can't hold the color
+/// types, so scalars and vectors are laid out like the texels of the same
+/// size, e.g. `u32` like [`Rgba8U`] for colors packed with
+/// `pack4x8unorm`.
+///
+/// # Safety
+/// * `Self` **must** have the size of `Data`.
+/// * Any bytes of `Data` **must** be a valid `Self` and vice versa.
+pub unsafe trait TexelLayout<Data: TextureData> {}
+
+unsafe impl<Data: TextureData> TexelLayout<Data> for Data {}
+
+macro_rules! texel_layout {
+    ($item:ty => $($data:ty),*) => {
+        $(unsafe impl TexelLayout<$data> for $item {})*
+    };
+}
+
+texel_layout!(u32 => Rgba8U, Rgba8I, Bgra8U, Rg16U, Rg16I, R32U, R32I, R32);
+texel_layout!(i32 => R32I);
+texel_layout!(f32 => R32);
+texel_layout!(Vec2<u32> => Rg32U, Rgba16U);
+texel_layout!(Vec2<i32> => Rg32I);
+texel_layout!(Vec2<f32> => Rg32);
+texel_layout!(Vec4<u32> => Rgba32U);
+texel_layout!(Vec4<i32> => Rgba32I);
+texel_layout!(Vec4<f32> => Rgba32);
+
+/// Direction of a copy between a texture and a buffer.
+enum Direction {
+    ToBuffer,
+    ToTexture,
+}
+
+impl<Format: TextureFormat + Default> Texture2d<Format> {
+    /// Copies the texels into the items of `buffer` on the GPU, row by row,
+    /// the items being tightly packed rows of [`width`](Self::width) texels.
+    ///
+    /// Pending CPU writes to the texture are uploaded first, the CPU side of
+    /// `buffer` is downloaded when next accessed.
+    ///
+    /// # Panics
+    /// If `buffer` doesn't hold `width * height` items.
+    pub fn copy_to_typed_buffer<T>(&self, buffer: &mut Buffer<T>)
+    where
+        T: BufferVec + ?Sized,
+        T::Item: TexelLayout<Format::Data>,
+    {
+        self.upload();
+        buffer.upload();
+
+        self.copy_typed(buffer, Direction::ToBuffer);
+
+        buffer.mark_needs_download();
+    }
+
+    /// Copies the items of `buffer`, tightly packed rows of
+    /// [`width`](Self::width) texels, into the texture on the GPU.
+    ///
+    /// Pending CPU writes to the texture are overwritten, its CPU side is
+    /// downloaded when next accessed.
+    ///
+    /// # Panics
+    /// If `buffer` doesn't hold `width * height` items.
+    pub fn copy_from_typed_buffer<T>(&mut self, buffer: &Buffer<T>)
+    where
+        T: BufferVec + ?Sized,
+        T::Item: TexelLayout<Format::Data>,
+    {
+        self.upload();
+        buffer.upload();
+
+        self.copy_typed(buffer, Direction::ToTexture);
+
+        self.mark_needs_download();
+    }
+
+    fn copy_typed<T>(&self, buffer: &Buffer<T>, direction: Direction)
+    where
+        T: BufferVec + ?Sized,
+    {
+        let width = self.width() as u32;
+        let height = self.height() as u32;
+
+        assert_eq!(
+            buffer.len(),
+            width as usize * height as usize,
+            "buffer holds {} items, expected {}x{} texels",
+            buffer.len(),
+            width,
+            height
+        );
+
+        if buffer.is_empty() {
+            return;
+        }
+
+        let block_size = self.wgpu_format().describe().block_size as u64;
+        let offset = buffer.items_offset();
+
+        assert!(
+            offset.is_multiple_of(block_size),
+            "items start at byte {}, which isn't a multiple of the texel size {}",
+            offset,
+            block_size
+        );
+
+        let instance = self.instance();
+
+        assert!(
+            std::ptr::eq(instance, buffer.instance()),
+            "the buffer and the texture belong to different instances"
+        );
+
+        let texture = instance.textures.get(self.texture_id()).unwrap();
+        let gpu_buffer = instance.buffers.get(&buffer.id()).unwrap();
+
+        let row_size = width as u64 * block_size;
+
+        // rows of the buffer are tightly packed, texture copies need rows
+        // aligned to 256 bytes, so unaligned rows are copied one at a time,
+        // a single row only spans its own bytes of the padded stride
+        let bytes_per_row =
+            row_size.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64) as u32;
+
+        let rows = if bytes_per_row as u64 == row_size {
+            vec![(0, height)]
+        } else {
+            (0..height).map(|y| (y, 1)).collect()
+        };
+
+        submit_encoded_in(instance, "shatter_typed_copy", |encoder| {
+            for (y, rows) in rows {
+                let image_texture = wgpu::ImageCopyTexture {
+                    texture: &texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d { x: 0, y, z: 0 },
+                    aspect: wgpu::TextureAspect::All,
+                };
+
+                let image_buffer = wgpu::ImageCopyBuffer {
+                    buffer: &gpu_buffer,
+                    layout: wgpu::ImageDataLayout {
+                        offset: offset + y as u64 * row_size,
+                        bytes_per_row: std::num::NonZeroU32::new(bytes_per_row),
+                        rows_per_image: None,
+                    },
+                };
+
+                let size = wgpu::Extent3d {
+                    width,
+                    height: rows,
+                    depth_or_array_layers: 1,
+                };
+
+                match direction {
+                    Direction::ToBuffer => {
+                        encoder.copy_texture_to_buffer(image_texture, image_buffer, size)
+                    }
+                    Direction::ToTexture => {
+                        encoder.copy_buffer_to_texture(image_buffer, image_texture, size)
+                    }
+                }
+            }
+        });
+    }
+}
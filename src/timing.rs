@@ -0,0 +1,143 @@
+use std::{sync::Mutex, time::Duration};
+
+use crate::Instance;
+
+/// GPU time taken by a dispatch, see
+/// [`ComputeShaderBuilder::timed`](crate::ComputeShaderBuilder::timed).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DispatchTiming {
+    /// Time between the timestamps written around the dispatch, excluding
+    /// transfers, pipeline creation and waiting for the submission.
+    pub gpu_duration: Duration,
+}
+
+/// Timestamp query sets kept by a [`TimestampPool`].
+const MAX_POOLED_QUERIES: usize = 4;
+
+/// Size of a resolved timestamp.
+const TIMESTAMP_SIZE: u64 = std::mem::size_of::<u64>() as u64;
+
+/// A timestamp query set and the buffer its timestamps are read back from.
+pub(crate) struct TimestampQuery {
+    query_set: wgpu::QuerySet,
+    readback: wgpu::Buffer,
+    capacity: u32,
+}
+
+impl TimestampQuery {
+    fn new(device: &wgpu::Device, capacity: u32) -> Self {
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("shatter_timestamp_query"),
+            ty: wgpu::QueryType::Timestamp,
+            count: capacity,
+        });
+
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("shatter_timestamp_readback"),
+            size: capacity as u64 * TIMESTAMP_SIZE,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            query_set,
+            readback,
+            capacity,
+        }
+    }
+
+    pub fn write(&self, compute_pass: &mut wgpu::ComputePass, index: u32) {
+        compute_pass.write_timestamp(&self.query_set, index);
+    }
+
+    /// Records resolving the first `count` timestamps into the readback buffer.
+    pub fn resolve(&self, encoder: &mut wgpu::CommandEncoder, count: u32) {
+        encoder.resolve_query_set(&self.query_set, 0..count, &self.readback, 0);
+    }
+
+    /// Waits for the first `count` resolved timestamps, returning the time
+    /// between consecutive ones.
+    pub fn read(&self, instance: &Instance, count: u32) -> Vec<Duration> {
+        let slice = self.readback.slice(..count as u64 * TIMESTAMP_SIZE);
+        let future = slice.map_async(wgpu::MapMode::Read);
+        instance.device.poll(wgpu::Maintain::Wait);
+        pollster::block_on(future).unwrap();
+
+        let ticks = slice
+            .get_mapped_range()
+            .chunks_exact(TIMESTAMP_SIZE as usize)
+            .map(|bytes| u64::from_ne_bytes(bytes.try_into().unwrap()))
+            .collect::<Vec<_>>();
+
+        self.readback.unmap();
+
+        let period = instance.queue.get_timestamp_period() as f64;
+
+        ticks
+            .windows(2)
+            // timestamps may go backwards when the GPU changes clocks
+            .map(|ticks| ticks[1].saturating_sub(ticks[0]))
+            .map(|ticks| Duration::from_nanos((ticks as f64 * period) as u64))
+            .collect()
+    }
+}
+
+/// Timestamp query sets of an instance reused across dispatches, since
+/// timed builders are usually short lived.
+#[derive(Default)]
+pub(crate) struct TimestampPool {
+    queries: Mutex<Vec<TimestampQuery>>,
+}
+
+impl TimestampPool {
+    /// Takes a query of at least `count` timestamps, creating one if none is
+    /// pooled. Returns `None` if the device doesn't support
+    /// [`wgpu::Features::TIMESTAMP_QUERY`].
+    ///
+    /// # Panics
+    /// Panics if `count` exceeds [`wgpu::QUERY_SET_MAX_QUERIES`].
+    pub fn take(&self, instance: &Instance, count: u32) -> Option<TimestampQuery> {
+        assert!(count <= wgpu::QUERY_SET_MAX_QUERIES);
+
+        let device = &instance.device;
+
+        if !device.features().contains(wgpu::Features::TIMESTAMP_QUERY) {
+            return None;
+        }
+
+        let mut queries = self.queries.lock().unwrap();
+
+        // the smallest large enough
+        let pooled = queries
+            .iter()
+            .enumerate()
+            .filter(|(_, query)| query.capacity >= count)
+            .min_by_key(|(_, query)| query.capacity)
+            .map(|(i, _)| i);
+
+        if let Some(i) = pooled {
+            return Some(queries.swap_remove(i));
+        }
+
+        let capacity = count
+            .next_power_of_two()
+            .clamp(2, wgpu::QUERY_SET_MAX_QUERIES);
+
+        Some(TimestampQuery::new(device, capacity))
+    }
+
+    /// Returns a query taken with [`Self::take`] once its timestamps are read,
+    /// dropping the smallest if the pool is full.
+    pub fn give_back(&self, query: TimestampQuery) {
+        let mut queries = self.queries.lock().unwrap();
+        queries.push(query);
+
+        if queries.len() > MAX_POOLED_QUERIES {
+            let smallest = (0..queries.len())
+                .min_by_key(|&i| queries[i].capacity)
+                .unwrap();
+
+            queries.swap_remove(smallest);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use std::ops::{Add, AddAssign};
+
+/// Bytes transferred between the CPU and the GPU.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TransferReport {
+    pub uploaded_bytes: u64,
+    pub downloaded_bytes: u64,
+    pub buffers_touched: u32,
+    pub textures_touched: u32,
+}
+
+impl TransferReport {
+    pub const ZERO: Self = Self {
+        uploaded_bytes: 0,
+        downloaded_bytes: 0,
+        buffers_touched: 0,
+        textures_touched: 0,
+    };
+
+    #[inline]
+    pub fn transferred_bytes(&self) -> u64 {
+        self.uploaded_bytes + self.downloaded_bytes
+    }
+}
+
+impl Add for TransferReport {
+    type Output = Self;
+
+    #[inline]
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl AddAssign for TransferReport {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.uploaded_bytes += rhs.uploaded_bytes;
+        self.downloaded_bytes += rhs.downloaded_bytes;
+        self.buffers_touched += rhs.buffers_touched;
+        self.textures_touched += rhs.textures_touched;
+    }
+}
+
+/// Outcome of streaming items into a buffer with [`Buffer::stream_items`](crate::Buffer::stream_items).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StreamReport {
+    /// Number of items written.
+    pub items: usize,
+    pub uploaded_bytes: u64,
+    /// Largest number of bytes staged for upload at once.
+    pub peak_staging_bytes: u64,
+}
+
+/// Asserts properties of a [`TransferReport`].
+///
+/// ```ignore
+/// assert_transfers!(builder.last_transfer_report(), uploaded_bytes == 0, downloaded_bytes <= 256);
+/// ```
+#[macro_export]
+macro_rules! assert_transfers {
+    ($report:expr, $($field:ident $op:tt $value:expr),+ $(,)?) => {{
+        let report: $crate::TransferReport = $report;
+
+        $(
+            assert!(
+                report.$field $op $value,
+                "expected `{} {} {}`, found {} in {:?}",
+                stringify!($field),
+                stringify!($op),
+                stringify!($value),
+                report.$field,
+                report,
+            );
+        )+
+    }};
+}
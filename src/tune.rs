@@ -0,0 +1,500 @@
+//! Picking the fastest work group size of a kernel on the current adapter.
+//!
+//! The variants are usually the members of a shader family, e.g.
+//! `for SIZE in [64, 128, 256];` with `workgroup_size(SIZE)`. The first pick
+//! times every variant with timestamp queries, later picks read the winner
+//! from a cache file keyed by adapter, entry point and variants.
+//!
+//! ```no_run
+//! # use shatter::*;
+//! wgsl! {
+//!     for SIZE in [64, 128, 256];
+//!
+//!     [[block]]
+//!     struct Values {
+//!         values: array<f32>;
+//!     };
+//!
+//!     [[group(0), binding(0)]]
+//!     var<storage, read_write> values: Values;
+//!
+//!     [[stage(compute), workgroup_size(SIZE)]]
+//!     fn double([[builtin(global_invocation_id)]] id: vec3<u32>) {
+//!         values.values[id.x] = values.values[id.x] * 2.0;
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let mut values = Buffer::<Values>::new();
+//! values.extend_from_slice(&[1.0; 1 << 20]);
+//!
+//! let variants = double::SIZE
+//!     .iter()
+//!     .map(|&size| double::with_size(size).unwrap())
+//!     .collect::<Vec<_>>();
+//!
+//! let best = tune::pick_work_group_size(&variants, [1 << 20, 1, 1], |i, dispatch| {
+//!     let shader = double::with_size(double::SIZE[i]).unwrap();
+//!     shader.dispatch(double::Bindings { values: &mut values }, dispatch);
+//! });
+//!
+//! let shader = double::with_size(best.x).unwrap();
+//! # }
+//! ```
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fmt,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+
+use crate::{submit_encoded, Dispatch, DispatchableShader, Instance, WorkGroupSize};
+
+/// Untimed dispatches of every variant before timing it.
+pub const DEFAULT_WARM_UP: u32 = 2;
+
+/// Timed dispatches of every variant, the fastest counts.
+pub const DEFAULT_ITERATIONS: u32 = 5;
+
+/// Measures the variants for a [`Tuner`].
+///
+/// [`GpuTimer`] times the GPU with timestamp queries, tests can replace it
+/// to exercise the cache without depending on GPU timings.
+pub trait Timer {
+    /// Identifies the adapter, winners are cached per adapter.
+    fn adapter(&self) -> String;
+
+    /// Times the GPU work submitted by `run`, `None` if it can't be timed.
+    fn time(&mut self, run: &mut dyn FnMut()) -> Option<Duration>;
+}
+
+/// Times GPU work with [`wgpu::Features::TIMESTAMP_QUERY`], which has to be
+/// requested with
+/// [`InstanceDescriptor::optional_features`](crate::InstanceDescriptor::optional_features).
+pub struct GpuTimer {
+    /// `None` without timestamp queries.
+    queries: Option<(wgpu::QuerySet, wgpu::Buffer)>,
+}
+
+/// Size of the two timestamps bracketing the work.
+const TIMESTAMPS_SIZE: u64 = 2 * std::mem::size_of::<u64>() as u64;
+
+impl GpuTimer {
+    pub fn new() -> Self {
+        let device = &Instance::global().device;
+
+        if !device.features().contains(wgpu::Features::TIMESTAMP_QUERY) {
+            return Self { queries: None };
+        }
+
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("shatter_tune_timestamps"),
+            ty: wgpu::QueryType::Timestamp,
+            count: 2,
+        });
+
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("shatter_tune_readback"),
+            size: TIMESTAMPS_SIZE,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            queries: Some((query_set, readback)),
+        }
+    }
+}
+
+impl Default for GpuTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Timer for GpuTimer {
+    fn adapter(&self) -> String {
+        let info = Instance::global().adapter.get_info();
+
+        format!(
+            "{} ({:?}, {:#06x}:{:#06x})",
+            info.name, info.backend, info.vendor, info.device
+        )
+    }
+
+    fn time(&mut self, run: &mut dyn FnMut()) -> Option<Duration> {
+        let (query_set, readback) = self.queries.as_ref()?;
+        let instance = Instance::global();
+
+        // the dispatches submit themselves, the timestamps are written by
+        // submissions around them
+        submit_encoded("shatter_tune_start", |encoder| {
+            encoder.write_timestamp(query_set, 0);
+        });
+
+        run();
+
+        submit_encoded("shatter_tune_end", |encoder| {
+            encoder.write_timestamp(query_set, 1);
+            encoder.resolve_query_set(query_set, 0..2, readback, 0);
+        });
+
+        let slice = readback.slice(..);
+        let future = slice.map_async(wgpu::MapMode::Read);
+        instance.device.poll(wgpu::Maintain::Wait);
+        pollster::block_on(future).ok()?;
+
+        let ticks = {
+            let bytes = slice.get_mapped_range();
+            let start = u64::from_ne_bytes(bytes[..8].try_into().unwrap());
+            let end = u64::from_ne_bytes(bytes[8..16].try_into().unwrap());
+            end.saturating_sub(start)
+        };
+
+        readback.unmap();
+
+        let period = instance.queue.get_timestamp_period() as f64;
+        Some(Duration::from_nanos((ticks as f64 * period) as u64))
+    }
+}
+
+/// What a [`Tuner`] did to pick a work group size, see [`Tuner::with_hook`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TuneEvent {
+    /// The winner was read from the cache.
+    Cached {
+        entry_point: String,
+        work_group_size: WorkGroupSize,
+    },
+    /// Every variant was timed.
+    Measured {
+        entry_point: String,
+        /// The fastest dispatch of every variant, in order.
+        timings: Vec<(WorkGroupSize, Duration)>,
+        work_group_size: WorkGroupSize,
+    },
+    /// The variants couldn't be timed, e.g. without
+    /// [`wgpu::Features::TIMESTAMP_QUERY`], the first one is used and
+    /// nothing is cached.
+    Untimed {
+        entry_point: String,
+        work_group_size: WorkGroupSize,
+    },
+    /// The cache file couldn't be written, the winner is timed again next run.
+    CacheNotWritten { path: PathBuf, error: String },
+}
+
+impl fmt::Display for TuneEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cached {
+                entry_point,
+                work_group_size,
+            } => write!(
+                f,
+                "`{}` uses the cached work group size {:?}",
+                entry_point, work_group_size
+            ),
+            Self::Measured {
+                entry_point,
+                timings,
+                work_group_size,
+            } => {
+                write!(f, "`{}` picked {:?} of", entry_point, work_group_size)?;
+
+                for (size, time) in timings {
+                    write!(f, " {:?} in {:?},", size, time)?;
+                }
+
+                Ok(())
+            }
+            Self::Untimed {
+                entry_point,
+                work_group_size,
+            } => write!(
+                f,
+                "`{}` can't be timed, is TIMESTAMP_QUERY supported? falling back to {:?}",
+                entry_point, work_group_size
+            ),
+            Self::CacheNotWritten { path, error } => {
+                write!(f, "failed to write `{}`: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+/// Called with every [`TuneEvent`], see [`Tuner::with_hook`].
+pub type TuneHook = Arc<dyn Fn(&TuneEvent) + Send + Sync>;
+
+/// A cached winner, by adapter, entry point and hash of the variants.
+type CacheKey = (String, String, u64);
+
+/// Picks the fastest of several variants of a kernel, caching the winner in
+/// a file.
+pub struct Tuner<T: Timer = GpuTimer> {
+    timer: T,
+    cache_file: Option<PathBuf>,
+    warm_up: u32,
+    iterations: u32,
+    hook: Option<TuneHook>,
+}
+
+impl Tuner {
+    /// Creates a tuner timing with [`GpuTimer`], see [`Tuner::with_timer`].
+    pub fn new() -> Self {
+        Self::with_timer(GpuTimer::new())
+    }
+}
+
+impl Default for Tuner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Timer> Tuner<T> {
+    /// Creates a tuner timing with `timer`.
+    ///
+    /// Winners are cached in the file named by the `SHATTER_TUNE_CACHE`
+    /// environment variable, by default `shatter_tune_cache` in the
+    /// temporary directory.
+    pub fn with_timer(timer: T) -> Self {
+        let cache_file = match std::env::var_os("SHATTER_TUNE_CACHE") {
+            Some(path) => PathBuf::from(path),
+            None => std::env::temp_dir().join("shatter_tune_cache"),
+        };
+
+        Self {
+            timer,
+            cache_file: Some(cache_file),
+            warm_up: DEFAULT_WARM_UP,
+            iterations: DEFAULT_ITERATIONS,
+            hook: None,
+        }
+    }
+
+    /// Sets the file winners are cached in, `None` times the variants on
+    /// every pick.
+    pub fn with_cache_file(mut self, cache_file: Option<PathBuf>) -> Self {
+        self.cache_file = cache_file;
+        self
+    }
+
+    /// Sets the untimed and timed dispatches of every variant, see
+    /// [`DEFAULT_WARM_UP`] and [`DEFAULT_ITERATIONS`].
+    pub fn with_iterations(mut self, warm_up: u32, iterations: u32) -> Self {
+        self.warm_up = warm_up;
+        self.iterations = iterations.max(1);
+        self
+    }
+
+    /// Calls `hook` with every [`TuneEvent`], by default only
+    /// [`TuneEvent::Untimed`] and [`TuneEvent::CacheNotWritten`] are printed
+    /// to stderr.
+    pub fn with_hook(mut self, hook: impl Fn(&TuneEvent) + Send + Sync + 'static) -> Self {
+        self.hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Gets the work group size of the fastest of `variants`, timing them if
+    /// the winner isn't cached.
+    ///
+    /// `dispatch` dispatches the variant at an index with the representative
+    /// bindings, covering `extent` invocations with the variant's work group
+    /// size.
+    ///
+    /// # Panics
+    /// Panics if `variants` is empty, the variants have different entry
+    /// points, or `extent` can't be dispatched.
+    pub fn pick<B>(
+        &mut self,
+        variants: &[&dyn DispatchableShader<B>],
+        extent: [u64; 3],
+        dispatch: impl FnMut(usize, Dispatch),
+    ) -> WorkGroupSize {
+        let key = self.key(variants, extent);
+
+        if let Some(&work_group_size) = self.load().get(&key) {
+            self.emit(TuneEvent::Cached {
+                entry_point: key.1,
+                work_group_size,
+            });
+
+            return work_group_size;
+        }
+
+        self.measure(key, variants, extent, dispatch)
+    }
+
+    /// Times `variants` even if the winner is cached, replacing it, see
+    /// [`Self::pick`].
+    pub fn retune<B>(
+        &mut self,
+        variants: &[&dyn DispatchableShader<B>],
+        extent: [u64; 3],
+        dispatch: impl FnMut(usize, Dispatch),
+    ) -> WorkGroupSize {
+        let key = self.key(variants, extent);
+        self.measure(key, variants, extent, dispatch)
+    }
+
+    fn key<B>(&self, variants: &[&dyn DispatchableShader<B>], extent: [u64; 3]) -> CacheKey {
+        let first = variants.first().expect("no variants to pick from");
+        let entry_point = first.entry_point();
+
+        assert!(
+            variants
+                .iter()
+                .all(|variant| variant.entry_point() == entry_point),
+            "the variants of `{}` have different entry points",
+            entry_point
+        );
+
+        // DefaultHasher::new uses fixed keys, so the hash is the same run to run
+        let mut hasher = DefaultHasher::new();
+        extent.hash(&mut hasher);
+
+        for variant in variants {
+            variant.work_group_size().hash(&mut hasher);
+        }
+
+        (
+            self.timer.adapter(),
+            entry_point.to_string(),
+            hasher.finish(),
+        )
+    }
+
+    fn measure<B>(
+        &mut self,
+        key: CacheKey,
+        variants: &[&dyn DispatchableShader<B>],
+        extent: [u64; 3],
+        mut dispatch: impl FnMut(usize, Dispatch),
+    ) -> WorkGroupSize {
+        let mut timings = Vec::with_capacity(variants.len());
+
+        for (i, variant) in variants.iter().enumerate() {
+            let work_group_size = variant.work_group_size();
+            let work_groups = Dispatch::try_for_extent(extent, work_group_size)
+                .unwrap_or_else(|err| panic!("{}", err));
+
+            let mut run = || dispatch(i, work_groups);
+
+            for _ in 0..self.warm_up {
+                self.timer.time(&mut run);
+            }
+
+            let fastest = (0..self.iterations)
+                .map(|_| self.timer.time(&mut run))
+                .try_fold(Duration::MAX, |fastest, time| Some(fastest.min(time?)));
+
+            match fastest {
+                Some(fastest) => timings.push((work_group_size, fastest)),
+                None => {
+                    let work_group_size = variants[0].work_group_size();
+
+                    self.emit(TuneEvent::Untimed {
+                        entry_point: key.1,
+                        work_group_size,
+                    });
+
+                    return work_group_size;
+                }
+            }
+        }
+
+        // the first of equally fast variants wins
+        let work_group_size = timings
+            .iter()
+            .min_by_key(|(_, time)| *time)
+            .map(|&(size, _)| size)
+            .unwrap();
+
+        let entry_point = key.1.clone();
+        self.store(key, work_group_size);
+
+        self.emit(TuneEvent::Measured {
+            entry_point,
+            timings,
+            work_group_size,
+        });
+
+        work_group_size
+    }
+
+    /// Reads the cache file, a missing or malformed file caches nothing.
+    fn load(&self) -> HashMap<CacheKey, WorkGroupSize> {
+        let contents = match self.cache_file {
+            Some(ref path) => std::fs::read_to_string(path).unwrap_or_default(),
+            None => return HashMap::new(),
+        };
+
+        contents.lines().filter_map(parse_line).collect()
+    }
+
+    fn store(&self, key: CacheKey, work_group_size: WorkGroupSize) {
+        let path = match self.cache_file {
+            Some(ref path) => path,
+            None => return,
+        };
+
+        let mut cache = self.load().into_iter().collect::<Vec<_>>();
+        cache.retain(|(cached, _)| *cached != key);
+        cache.push((key, work_group_size));
+        cache.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let contents = cache
+            .iter()
+            .map(|((adapter, entry_point, hash), size)| {
+                format!(
+                    "{}\t{}\t{:016x}\t{} {} {}\n",
+                    adapter, entry_point, hash, size.x, size.y, size.z
+                )
+            })
+            .collect::<String>();
+
+        if let Err(err) = std::fs::write(path, contents) {
+            self.emit(TuneEvent::CacheNotWritten {
+                path: path.clone(),
+                error: err.to_string(),
+            });
+        }
+    }
+
+    fn emit(&self, event: TuneEvent) {
+        match (&self.hook, &event) {
+            (Some(hook), _) => hook(&event),
+            (None, TuneEvent::Untimed { .. } | TuneEvent::CacheNotWritten { .. }) => {
+                eprintln!("shatter: {}", event)
+            }
+            (None, _) => {}
+        }
+    }
+}
+
+/// Parses `adapter\tentry point\thash\tx y z`.
+fn parse_line(line: &str) -> Option<(CacheKey, WorkGroupSize)> {
+    let mut fields = line.split('\t');
+
+    let adapter = fields.next()?.to_string();
+    let entry_point = fields.next()?.to_string();
+    let hash = u64::from_str_radix(fields.next()?, 16).ok()?;
+
+    let mut size = fields.next()?.split(' ').map(str::parse::<u32>);
+    let (x, y, z) = (size.next()?.ok()?, size.next()?.ok()?, size.next()?.ok()?);
+
+    Some(((adapter, entry_point, hash), WorkGroupSize::new(x, y, z)))
+}
+
+/// Picks the fastest of `variants` with a default [`Tuner`], see [`Tuner::pick`].
+pub fn pick_work_group_size<B>(
+    variants: &[&dyn DispatchableShader<B>],
+    extent: [u64; 3],
+    dispatch: impl FnMut(usize, Dispatch),
+) -> WorkGroupSize {
+    Tuner::new().pick(variants, extent, dispatch)
+}
@@ -0,0 +1,475 @@
+//! Building blocks for applications driving shatter every frame.
+
+use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    future::Future,
+    ops::Range,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Waker},
+    time::{Duration, Instant},
+};
+
+use crate::{convert, Instance, Texture2d, TextureFormat};
+
+type MapFuture = Pin<Box<dyn Future<Output = Result<(), wgpu::BufferAsyncError>> + Send>>;
+
+/// A frame whose copy into `staging` has been submitted.
+struct InFlight {
+    staging: wgpu::Buffer,
+    staging_size: u64,
+    map: MapFuture,
+    mapped: bool,
+    width: usize,
+    height: usize,
+    stride: usize,
+    bgra: bool,
+}
+
+/// Reads frames back from the GPU without waiting for each one, delivering
+/// them in submission order as packed RGBA8 rows.
+///
+/// At most [`capacity`](Self::capacity) frames are in flight. Submitting
+/// another frame first waits for the oldest one and hands it back, so a
+/// consumer slower than the GPU throttles the producer instead of queueing
+/// frames without bound.
+///
+/// Staging buffers are reused once their frame has been delivered.
+pub struct FrameReadbackQueue {
+    capacity: usize,
+    in_flight: VecDeque<InFlight>,
+    staging: Vec<(wgpu::Buffer, u64)>,
+}
+
+impl FrameReadbackQueue {
+    /// Creates a queue keeping at most `capacity` frames in flight.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "readback queue needs room for a frame");
+
+        Self {
+            capacity,
+            in_flight: VecDeque::with_capacity(capacity),
+            staging: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of submitted frames not yet delivered.
+    #[inline]
+    pub fn outstanding(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.in_flight.is_empty()
+    }
+
+    /// Copies the GPU side of `texture` into a staging buffer and starts
+    /// mapping it, without waiting. Pending CPU writes are uploaded first.
+    ///
+    /// If the queue is full the oldest frame is waited for and returned.
+    ///
+    /// # Panics
+    /// Panics if the texture isn't 8 bit RGBA or BGRA.
+    pub fn submit<Format: TextureFormat>(
+        &mut self,
+        texture: &Texture2d<Format>,
+    ) -> Option<Vec<u8>> {
+        let bgra = match texture.wgpu_format() {
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => false,
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+            format => panic!("can't read back frames of format {:?}", format),
+        };
+
+        let oldest = if self.in_flight.len() >= self.capacity {
+            self.recv()
+        } else {
+            None
+        };
+
+        if !texture.is_gpu_only() {
+            texture.upload();
+        }
+
+        let instance = Instance::global();
+
+        let width = texture.width();
+        let height = texture.height();
+        let stride = (width * 4).next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize);
+        let staging_size = (stride * height).max(4) as u64;
+
+        let staging = match self
+            .staging
+            .iter()
+            .position(|&(_, size)| size == staging_size)
+        {
+            Some(i) => self.staging.swap_remove(i).0,
+            None => instance.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("shatter_readback_buffer"),
+                size: staging_size,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+        };
+
+        let gpu_texture = instance.textures.get(texture.texture_id()).unwrap();
+
+        let mut encoder = instance.device.create_command_encoder(&Default::default());
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: &gpu_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &staging,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: std::num::NonZeroU32::new(stride as u32),
+                    rows_per_image: None,
+                },
+            },
+            wgpu::Extent3d {
+                width: width as u32,
+                height: height as u32,
+                depth_or_array_layers: 1,
+            },
+        );
+        instance.queue.submit(std::iter::once(encoder.finish()));
+        instance.submitted(Some(Cow::Borrowed("shatter_frame_readback")), &[]);
+
+        let map = Box::pin(staging.slice(..).map_async(wgpu::MapMode::Read));
+
+        self.in_flight.push_back(InFlight {
+            staging,
+            staging_size,
+            map,
+            mapped: false,
+            width,
+            height,
+            stride,
+            bgra,
+        });
+
+        instance.stats.frame_submitted();
+
+        oldest
+    }
+
+    /// Returns the oldest frame if it has been read back, without waiting.
+    pub fn try_recv(&mut self) -> Option<Vec<u8>> {
+        Instance::global().device.poll(wgpu::Maintain::Poll);
+
+        let oldest = self.in_flight.front_mut()?;
+
+        if !oldest.mapped {
+            let mut context = Context::from_waker(Waker::noop());
+
+            match oldest.map.as_mut().poll(&mut context) {
+                Poll::Ready(result) => {
+                    result.unwrap();
+                    oldest.mapped = true;
+                }
+                Poll::Pending => return None,
+            }
+        }
+
+        Some(self.deliver())
+    }
+
+    /// Waits for the oldest frame, returns `None` if none is in flight.
+    pub fn recv(&mut self) -> Option<Vec<u8>> {
+        let oldest = self.in_flight.front_mut()?;
+
+        if !oldest.mapped {
+            Instance::global().device.poll(wgpu::Maintain::Wait);
+            pollster::block_on(oldest.map.as_mut()).unwrap();
+            oldest.mapped = true;
+        }
+
+        Some(self.deliver())
+    }
+
+    /// Waits for every frame in flight, yielding them in order.
+    pub fn drain(&mut self) -> impl Iterator<Item = Vec<u8>> + '_ {
+        std::iter::from_fn(move || self.recv())
+    }
+
+    /// Packs the mapped oldest frame and recycles its staging buffer.
+    fn deliver(&mut self) -> Vec<u8> {
+        let frame = self.in_flight.pop_front().unwrap();
+
+        let stats = &Instance::global().stats;
+        stats.frames_received(1);
+        stats.downloaded(frame.staging_size);
+
+        let row_bytes = frame.width * 4;
+        let mut rgba = vec![0; row_bytes * frame.height];
+
+        {
+            let mapped = frame.staging.slice(..).get_mapped_range();
+            convert::copy_rows(
+                &mapped,
+                frame.stride,
+                &mut rgba,
+                row_bytes,
+                row_bytes,
+                frame.height,
+            );
+        }
+
+        frame.staging.unmap();
+
+        // frames of a new size leave buffers of the old one behind
+        if self.staging.len() >= self.capacity {
+            self.staging.remove(0);
+        }
+
+        self.staging.push((frame.staging, frame.staging_size));
+
+        if frame.bgra {
+            for texel in rgba.chunks_exact_mut(4) {
+                texel.swap(0, 2);
+            }
+        }
+
+        rgba
+    }
+}
+
+impl Drop for FrameReadbackQueue {
+    fn drop(&mut self) {
+        if !self.in_flight.is_empty() {
+            let stats = &Instance::global().stats;
+            stats.frames_received(self.in_flight.len() as u64);
+        }
+    }
+}
+
+/// A part of the range of a [`SegmentedDispatch`], dispatched by a single
+/// [`SegmentedDispatch::step`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Segment {
+    /// Number of segments before this one.
+    pub index: u64,
+    /// First element of the segment, the base offset the kernel adds to its
+    /// invocation ids.
+    pub offset: u64,
+    /// Number of elements, the segment size except for a shorter last segment.
+    pub len: u64,
+}
+
+impl Segment {
+    /// The elements of the segment within the whole range.
+    #[inline]
+    pub fn range(&self) -> Range<u64> {
+        self.offset..self.offset + self.len
+    }
+}
+
+/// A segment issued by [`SegmentedDispatch::step`] and how long it took.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SegmentReport {
+    pub segment: Segment,
+    /// Time from issuing the segment until the queue drained.
+    pub elapsed: Duration,
+    /// Whether `elapsed` exceeded the budget, see [`SegmentedDispatch::with_budget`].
+    pub over_budget: bool,
+}
+
+/// Cancels a [`SegmentedDispatch`] from elsewhere, e.g. another thread, see
+/// [`SegmentedDispatch::cancel_handle`].
+#[derive(Clone, Debug, Default)]
+pub struct CancelHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelHandle {
+    /// Stops the dispatch from issuing further segments, the segment in
+    /// flight still finishes.
+    #[inline]
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Splits a long running workload into segments, issuing one per
+/// [`step`](Self::step) so it can be cancelled between them.
+///
+/// Every segment is its own submission and the queue drains before `step`
+/// returns, so other work gets the GPU in between and nothing is left in
+/// flight once cancelled. The kernel covers `len` elements starting at the
+/// segment's `offset`, which the caller passes along with the dispatch, e.g.
+/// written into a uniform:
+///
+/// ```ignore
+/// let mut segments = SegmentedDispatch::new(samples, 1);
+///
+/// while segments
+///     .step(|segment| {
+///         uniforms.first_sample = segment.offset as u32;
+///         uniforms.samples = segment.len as u32;
+///
+///         accumulate(bindings(&mut accum, &uniforms), dispatch);
+///     })
+///     .is_some()
+/// {
+///     progress_bar.set(segments.progress());
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SegmentedDispatch {
+    total: u64,
+    segment_size: u64,
+    issued: u64,
+    cancel: CancelHandle,
+    budget: Option<Duration>,
+    over_budget: u64,
+}
+
+impl SegmentedDispatch {
+    /// Creates a dispatch over `total` elements, `segment_size` per segment.
+    ///
+    /// # Panics
+    /// Panics if `segment_size` is zero.
+    pub fn new(total: u64, segment_size: u64) -> Self {
+        assert!(segment_size > 0, "segments must cover at least one element");
+
+        Self {
+            total,
+            segment_size,
+            issued: 0,
+            cancel: CancelHandle::default(),
+            budget: None,
+            over_budget: 0,
+        }
+    }
+
+    /// Flags segments taking longer than `budget` in their [`SegmentReport`],
+    /// e.g. to shrink the segments before the driver's watchdog resets the
+    /// device.
+    #[inline]
+    pub fn with_budget(mut self, budget: Duration) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    #[inline]
+    pub fn segment_size(&self) -> u64 {
+        self.segment_size
+    }
+
+    /// Number of segments covering the whole range.
+    #[inline]
+    pub fn segments(&self) -> u64 {
+        self.total.div_ceil(self.segment_size)
+    }
+
+    /// Gets the segment the next [`step`](Self::step) issues, `None` once
+    /// done or cancelled.
+    pub fn next_segment(&self) -> Option<Segment> {
+        if self.issued >= self.total || self.is_cancelled() {
+            return None;
+        }
+
+        Some(Segment {
+            index: self.issued / self.segment_size,
+            offset: self.issued,
+            len: self.segment_size.min(self.total - self.issued),
+        })
+    }
+
+    /// Issues the next segment by calling `dispatch` with it, then waits for
+    /// the queue to drain.
+    ///
+    /// Returns `None` without calling `dispatch` once done or cancelled.
+    pub fn step(&mut self, dispatch: impl FnOnce(Segment)) -> Option<SegmentReport> {
+        let segment = self.next_segment()?;
+
+        let start = Instant::now();
+
+        dispatch(segment);
+        Instance::global().device.poll(wgpu::Maintain::Wait);
+
+        let elapsed = start.elapsed();
+        let over_budget = self.budget.is_some_and(|budget| elapsed > budget);
+
+        self.issued += segment.len;
+        self.over_budget += over_budget as u64;
+
+        Some(SegmentReport {
+            segment,
+            elapsed,
+            over_budget,
+        })
+    }
+
+    /// Stops issuing segments, what the issued ones wrote stays readable.
+    #[inline]
+    pub fn cancel(&mut self) {
+        self.cancel.cancel();
+    }
+
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Gets a handle cancelling this dispatch, taking effect at the next
+    /// [`step`](Self::step).
+    #[inline]
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel.clone()
+    }
+
+    /// Returns true once every segment has been issued.
+    #[inline]
+    pub fn is_done(&self) -> bool {
+        self.issued >= self.total
+    }
+
+    /// Number of elements covered by the issued segments.
+    #[inline]
+    pub fn completed(&self) -> u64 {
+        self.issued
+    }
+
+    /// Fraction of the range covered by the issued segments, 1 for an empty
+    /// range.
+    pub fn progress(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+
+        (self.issued as f64 / self.total as f64) as f32
+    }
+
+    /// Number of segments that exceeded the budget, see [`Self::with_budget`].
+    #[inline]
+    pub fn over_budget(&self) -> u64 {
+        self.over_budget
+    }
+}
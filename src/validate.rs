@@ -0,0 +1,295 @@
+use std::fmt;
+
+use crate::{BufferId, SamplerId, TextureId, GLOBAL_INSTANCE};
+
+/// Describes a bound resource using only CPU side metadata.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ResourceInfo {
+    Buffer {
+        id: BufferId,
+        size: u64,
+        usage: wgpu::BufferUsages,
+    },
+    Texture {
+        id: TextureId,
+        format: wgpu::TextureFormat,
+        view_dimension: wgpu::TextureViewDimension,
+        usage: wgpu::TextureUsages,
+    },
+    Sampler {
+        id: SamplerId,
+    },
+}
+
+impl ResourceInfo {
+    fn is_same_resource(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Buffer { id: a, .. }, Self::Buffer { id: b, .. }) => a == b,
+            (Self::Texture { id: a, .. }, Self::Texture { id: b, .. }) => a == b,
+            (Self::Sampler { id: a }, Self::Sampler { id: b }) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// What a shader expects of a single binding.
+#[derive(Clone, Debug)]
+pub struct BindingExpectation {
+    /// Name of the binding in WGSL.
+    pub name: &'static str,
+    pub ty: wgpu::BindingType,
+    /// Minimum size in bytes of buffer bindings.
+    pub min_size: u64,
+    pub writable: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BindingErrorKind {
+    BufferTooSmall {
+        size: u64,
+        min_size: u64,
+    },
+    FormatMismatch {
+        format: wgpu::TextureFormat,
+        expected: wgpu::TextureFormat,
+    },
+    DimensionMismatch {
+        view_dimension: wgpu::TextureViewDimension,
+        expected: wgpu::TextureViewDimension,
+    },
+    MissingBufferUsage(wgpu::BufferUsages),
+    /// A uniform binding exceeds `max_uniform_buffer_binding_size`.
+    UniformTooLarge {
+        size: u64,
+        max: u64,
+    },
+    MissingTextureUsage(wgpu::TextureUsages),
+    /// A buffer was bound where a texture was expected or vice versa.
+    ResourceMismatch,
+    /// The resource is also bound to `other` and at least one of them is written to.
+    Aliased {
+        other: &'static str,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BindingError {
+    /// Name of the binding in WGSL.
+    pub binding: &'static str,
+    pub kind: BindingErrorKind,
+}
+
+impl fmt::Display for BindingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.binding, self.kind)
+    }
+}
+
+impl fmt::Display for BindingErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            BindingErrorKind::BufferTooSmall { size, min_size } => write!(
+                f,
+                "buffer is {} bytes but the shader requires at least {}",
+                size, min_size
+            ),
+            BindingErrorKind::FormatMismatch { format, expected } => {
+                write!(f, "texture format is {:?}, expected {:?}", format, expected)
+            }
+            BindingErrorKind::DimensionMismatch {
+                view_dimension,
+                expected,
+            } => write!(
+                f,
+                "texture dimension is {:?}, expected {:?}",
+                view_dimension, expected
+            ),
+            BindingErrorKind::MissingBufferUsage(usage) => {
+                write!(f, "buffer is missing usage {:?}", usage)
+            }
+            BindingErrorKind::UniformTooLarge { size, max } => write!(
+                f,
+                "uniform buffer is {} bytes but uniform bindings are limited to {}, \
+                declare it as `var<storage>` instead",
+                size, max
+            ),
+            BindingErrorKind::MissingTextureUsage(usage) => {
+                write!(f, "texture is missing usage {:?}", usage)
+            }
+            BindingErrorKind::ResourceMismatch => write!(f, "wrong kind of resource"),
+            BindingErrorKind::Aliased { other } => {
+                write!(f, "resource is also bound to `{}` and written to", other)
+            }
+        }
+    }
+}
+
+/// Every problem found when validating a set of bindings.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub errors: Vec<BindingError>,
+}
+
+impl ValidationReport {
+    /// Returns true if `binding` has an error of `kind`.
+    pub fn contains(&self, binding: &str, kind: &BindingErrorKind) -> bool {
+        self.errors
+            .iter()
+            .any(|error| error.binding == binding && error.kind == *kind)
+    }
+
+    /// Gets the errors of `binding`.
+    pub fn binding_errors<'a>(
+        &'a self,
+        binding: &'a str,
+    ) -> impl Iterator<Item = &'a BindingError> + 'a {
+        self.errors
+            .iter()
+            .filter(move |error| error.binding == binding)
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "invalid bindings:")?;
+
+        for error in &self.errors {
+            writeln!(f, "  {}", error)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationReport {}
+
+/// Validates resources against what a shader expects, reporting every problem found.
+pub fn validate_bindings(
+    bindings: &[(BindingExpectation, ResourceInfo)],
+) -> Result<(), ValidationReport> {
+    let mut report = ValidationReport::default();
+
+    for (expectation, info) in bindings {
+        for kind in validate_binding(expectation, info) {
+            report.errors.push(BindingError {
+                binding: expectation.name,
+                kind,
+            });
+        }
+    }
+
+    for (i, (a, a_info)) in bindings.iter().enumerate() {
+        for (b, b_info) in &bindings[i + 1..] {
+            if (a.writable || b.writable) && a_info.is_same_resource(b_info) {
+                report.errors.push(BindingError {
+                    binding: a.name,
+                    kind: BindingErrorKind::Aliased { other: b.name },
+                });
+            }
+        }
+    }
+
+    if report.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(report)
+    }
+}
+
+pub(crate) fn validate_binding(
+    expectation: &BindingExpectation,
+    info: &ResourceInfo,
+) -> Vec<BindingErrorKind> {
+    let mut errors = Vec::new();
+
+    match (&expectation.ty, info) {
+        (wgpu::BindingType::Buffer { ty, .. }, &ResourceInfo::Buffer { size, usage, .. }) => {
+            if size < expectation.min_size {
+                errors.push(BindingErrorKind::BufferTooSmall {
+                    size,
+                    min_size: expectation.min_size,
+                });
+            }
+
+            let required = match ty {
+                wgpu::BufferBindingType::Uniform => wgpu::BufferUsages::UNIFORM,
+                wgpu::BufferBindingType::Storage { .. } => wgpu::BufferUsages::STORAGE,
+            };
+
+            if !usage.contains(required) {
+                errors.push(BindingErrorKind::MissingBufferUsage(required));
+            }
+
+            // the limit is only known once there's a device
+            if let (wgpu::BufferBindingType::Uniform, Some(instance)) = (ty, GLOBAL_INSTANCE.get())
+            {
+                let max = instance.device.limits().max_uniform_buffer_binding_size as u64;
+
+                if size > max {
+                    errors.push(BindingErrorKind::UniformTooLarge { size, max });
+                }
+            }
+        }
+        (
+            wgpu::BindingType::StorageTexture {
+                format: expected_format,
+                view_dimension: expected_dimension,
+                ..
+            },
+            &ResourceInfo::Texture {
+                format,
+                view_dimension,
+                usage,
+                ..
+            },
+        ) => {
+            if format != *expected_format {
+                errors.push(BindingErrorKind::FormatMismatch {
+                    format,
+                    expected: *expected_format,
+                });
+            }
+
+            if view_dimension != *expected_dimension {
+                errors.push(BindingErrorKind::DimensionMismatch {
+                    view_dimension,
+                    expected: *expected_dimension,
+                });
+            }
+
+            if !usage.contains(wgpu::TextureUsages::STORAGE_BINDING) {
+                errors.push(BindingErrorKind::MissingTextureUsage(
+                    wgpu::TextureUsages::STORAGE_BINDING,
+                ));
+            }
+        }
+        (
+            wgpu::BindingType::Texture {
+                view_dimension: expected_dimension,
+                ..
+            },
+            &ResourceInfo::Texture {
+                view_dimension,
+                usage,
+                ..
+            },
+        ) => {
+            if view_dimension != *expected_dimension {
+                errors.push(BindingErrorKind::DimensionMismatch {
+                    view_dimension,
+                    expected: *expected_dimension,
+                });
+            }
+
+            if !usage.contains(wgpu::TextureUsages::TEXTURE_BINDING) {
+                errors.push(BindingErrorKind::MissingTextureUsage(
+                    wgpu::TextureUsages::TEXTURE_BINDING,
+                ));
+            }
+        }
+        (wgpu::BindingType::Sampler { .. }, ResourceInfo::Sampler { .. }) => {}
+        _ => errors.push(BindingErrorKind::ResourceMismatch),
+    }
+
+    errors
+}
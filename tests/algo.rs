@@ -0,0 +1,158 @@
+use shatter::{algo::*, *};
+
+/// A `width * height` texture of `value(x, y)`.
+fn texture(
+    width: usize,
+    height: usize,
+    value: impl Fn(usize, usize) -> f32,
+) -> Texture2d<R32Float> {
+    let mut texture = Texture2d::<R32Float>::new(width, height);
+
+    for y in 0..height {
+        for x in 0..width {
+            texture[(x, y)] = R32::r(value(x, y));
+        }
+    }
+
+    texture
+}
+
+fn cpu_minmax(values: impl Iterator<Item = f32>) -> (f32, f32) {
+    values
+        .filter(|value| !value.is_nan())
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), value| {
+            (min.min(value), max.max(value))
+        })
+}
+
+#[test]
+fn minmax_of_gradient() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    // neither side a multiple of the work group size
+    let value = |x: usize, y: usize| (x as f32 - 20.0) * 0.5 - y as f32 * 3.0;
+    let gradient = texture(37, 19, value);
+
+    let expected = cpu_minmax((0..19).flat_map(|y| (0..37).map(move |x| value(x, y))));
+    assert_eq!(expected, (-64.0, 8.0));
+    assert_eq!(texture_minmax(&gradient), expected);
+
+    let constant = texture(16, 16, |_, _| 3.5);
+    assert_eq!(texture_minmax(&constant), (3.5, 3.5));
+}
+
+#[test]
+fn minmax_of_large_texture() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let (width, height) = (1500, 1300);
+    let value = |x: usize, y: usize| ((x * 7 + y * 13) % 1009) as f32 - 500.0;
+    let large = texture(width, height, value);
+
+    let expected = cpu_minmax((0..height).flat_map(|y| (0..width).map(move |x| value(x, y))));
+    assert_eq!(texture_minmax(&large), expected);
+}
+
+#[test]
+fn minmax_ignores_nans() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let value = |x: usize, y: usize| match (x + y) % 3 {
+        0 => f32::NAN,
+        _ => x as f32 - y as f32,
+    };
+    let with_nans = texture(20, 20, value);
+
+    let expected = cpu_minmax((0..20).flat_map(|y| (0..20).map(move |x| value(x, y))));
+    assert_eq!(texture_minmax(&with_nans), expected);
+
+    // infinities are values
+    let infinite = texture(4, 4, |x, _| [f32::NAN, f32::INFINITY, 1.0, 2.0][x]);
+    assert_eq!(texture_minmax(&infinite), (1.0, f32::INFINITY));
+
+    let only_nans = texture(4, 4, |_, _| f32::NAN);
+    let minmax = submit_encoded("only_nans", |encoder| {
+        texture_minmax_in(encoder, &only_nans)
+    })
+    .to_cpu();
+
+    assert!(minmax.is_empty());
+    assert_eq!((minmax.min, minmax.max), (f32::INFINITY, f32::NEG_INFINITY));
+}
+
+#[test]
+fn minmax_of_rgba_is_luminance() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut hdr = Texture2d::<Rgba32Float>::new(3, 1);
+    hdr[(0, 0)] = Rgba32::rgba(1.0, 1.0, 1.0, 0.0);
+    hdr[(1, 0)] = Rgba32::rgba(8.0, 0.0, 0.0, 1.0);
+    hdr[(2, 0)] = Rgba32::rgba(0.0, 0.0, 2.0, 1.0);
+
+    let (min, max) = texture_minmax(&hdr);
+    assert!((min - 0.1444).abs() < 1e-5, "{}", min);
+    assert!((max - 1.7008).abs() < 1e-5, "{}", max);
+}
+
+#[test]
+fn histogram_of_gradient() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    // values in the middle of the bins, so rounding can't move them
+    let value = |x: usize, y: usize| match (x + y * 45) % 70 {
+        i @ 0..=63 => i as f32 + 0.5,
+        64 => f32::NAN,
+        65..=66 => -1.0,
+        _ => 100.0,
+    };
+    let gradient = texture(45, 23, value);
+
+    let mut expected = [0u32; 32];
+    let (mut below, mut above) = (0, 0);
+
+    for y in 0..23 {
+        for x in 0..45 {
+            match value(x, y) {
+                value if value.is_nan() => {}
+                value if value < 0.0 => below += 1,
+                value if value >= 64.0 => above += 1,
+                value => expected[(value / 2.0) as usize] += 1,
+            }
+        }
+    }
+
+    let histogram = texture_histogram(&gradient, 32, 0.0..64.0);
+
+    assert_eq!(histogram.bins, expected[..]);
+    assert_eq!((histogram.below, histogram.above), (below, above));
+
+    let nans = (0..23)
+        .flat_map(|y| (0..45).map(move |x| value(x, y)))
+        .filter(|value| value.is_nan())
+        .count();
+    assert_eq!(histogram.total(), (45 * 23 - nans) as u64);
+}
+
+#[test]
+fn histogram_of_constant() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let constant = texture(33, 17, |_, _| 0.3);
+    let histogram = texture_histogram(&constant, MAX_HISTOGRAM_BINS, 0.0..1.0);
+
+    assert_eq!(histogram.bins.len(), MAX_HISTOGRAM_BINS as usize);
+    assert_eq!(histogram.bins[76], 33 * 17);
+    assert_eq!(histogram.total(), 33 * 17);
+}
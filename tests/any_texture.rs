@@ -0,0 +1,61 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use shatter::{
+    texture_format::{Rgba16Float, Rgba8Uint},
+    texture_sample_type::{Float, Uint},
+    texture_view_dimension::D2,
+    *,
+};
+
+type FloatBinding = TextureBinding<Float<true>, D2, false>;
+type UintBinding = TextureBinding<Uint, D2, false>;
+
+#[test]
+fn float_sampled() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let texture = AnyTexture2d::from(Texture2d::<Rgba16Float>::new(4, 4));
+
+    let mut report = TransferReport::ZERO;
+    Binding::<FloatBinding>::prepare(&texture, &mut report);
+    Binding::<FloatBinding>::binding_resource(&texture);
+
+    assert_eq!(report.textures_touched, 1);
+}
+
+#[test]
+fn uint_sampled_panics() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let texture = AnyTexture2d::from(Texture2d::<Rgba16Float>::new(4, 4));
+
+    let error = panic::catch_unwind(AssertUnwindSafe(|| {
+        Binding::<UintBinding>::binding_resource(&texture);
+    }))
+    .unwrap_err();
+
+    let message = error.downcast_ref::<String>().unwrap();
+    assert!(message.contains("Rgba16Float"), "{}", message);
+    assert!(message.contains("Uint"), "{}", message);
+}
+
+#[test]
+fn downcast() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let texture = AnyTexture2d::from(Texture2d::<Rgba16Float>::new(4, 4));
+
+    let texture = match texture.downcast::<Rgba8Uint>() {
+        Ok(_) => panic!("downcast to the wrong format succeeded"),
+        Err(texture) => texture,
+    };
+
+    assert!(texture.downcast_ref::<Rgba16Float>().is_some());
+    assert!(texture.downcast::<Rgba16Float>().is_ok());
+}
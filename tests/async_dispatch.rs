@@ -0,0 +1,134 @@
+use std::time::Instant;
+
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<f32>;
+    };
+
+    [[block]]
+    struct Params {
+        iterations: u32;
+    };
+
+    [[group(0), binding(0)]]
+    var<uniform> params: Params;
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> values: Values;
+
+    // deliberately slow, every invocation loops `params.iterations` times
+    [[stage(compute), workgroup_size(64)]]
+    fn spin([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        var value = values.values[id.x];
+
+        for (var i = 0u; i < params.iterations; i = i + 1u) {
+            value = value * 0.5 + 1.0;
+        }
+
+        values.values[id.x] = value;
+    }
+
+    [[stage(compute), workgroup_size(64)]]
+    fn increment([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] + 1.0;
+    }
+}
+
+const LEN: usize = 1024;
+
+fn values() -> Buffer<Values> {
+    let mut values = Buffer::<Values>::new();
+
+    for _ in 0..LEN {
+        values.push(0.0);
+    }
+
+    values
+}
+
+fn params(iterations: u32) -> Buffer<Params> {
+    let mut params = Buffer::<Params>::new();
+    params.iterations = iterations;
+    params
+}
+
+fn assert_send<T: Send>(value: T) -> T {
+    value
+}
+
+// a single worker, so a dispatch blocking it would run the tasks one after the other
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn tasks_overlap_while_waiting_on_the_gpu() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let dispatch = Dispatch::new(LEN as u32 / 64, 1, 1);
+    let start = Instant::now();
+
+    let slow = tokio::spawn(assert_send(async move {
+        let bindings = spin_async(
+            spin::OwnedBindings {
+                params: params(1 << 20),
+                values: values(),
+            },
+            dispatch,
+        )
+        .await;
+
+        (start.elapsed(), bindings)
+    }));
+
+    let fast = tokio::spawn(assert_send(async move {
+        let began = start.elapsed();
+
+        let bindings =
+            increment_async(increment::OwnedBindings { values: values() }, dispatch).await;
+
+        (began, start.elapsed(), bindings)
+    }));
+
+    let (slow_end, slow) = slow.await.unwrap();
+    let (fast_start, fast_end, fast) = fast.await.unwrap();
+
+    // the fast task started before the slow kernel was done
+    assert!(
+        fast_start < slow_end,
+        "fast task started at {:?}, after the slow one finished at {:?}",
+        fast_start,
+        slow_end,
+    );
+    assert!(fast_end > fast_start);
+
+    // the results were downloaded by the futures
+    assert!(!slow.values.needs_download());
+    assert!(!fast.values.needs_download());
+
+    assert!(slow.values.values.iter().all(|value| *value == 2.0));
+    assert!(fast.values.values.iter().all(|value| *value == 1.0));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn builder_dispatch_resolves_when_done() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut values = values();
+
+    let work = increment::build(increment::Bindings {
+        values: &mut values,
+    })
+    .dispatch_async(Dispatch::new(LEN as u32 / 64, 1, 1));
+
+    assert_send(work).await;
+
+    // the GPU is done, downloading doesn't wait on it
+    assert!(values.needs_download());
+    values.download_async().await.unwrap();
+
+    assert!(values.values.iter().all(|value| *value == 1.0));
+}
@@ -0,0 +1,434 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    num::NonZeroU64,
+};
+
+use shatter::*;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Counts the allocations made by `f` on the current thread.
+fn allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let value = f();
+    (value, ALLOCATIONS.with(Cell::get) - before)
+}
+
+wgsl! {
+    struct Particle {
+        position: vec2<f32>;
+        velocity: vec2<f32>;
+    };
+
+    [[block]]
+    struct Particles {
+        particles: array<Particle>;
+    };
+
+    [[block]]
+    struct Uniforms {
+        delta_time: f32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> particles: Particles;
+
+    [[group(0), binding(1)]]
+    var<uniform> uniforms: Uniforms;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn step([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&particles.particles)) {
+            return;
+        }
+
+        let particle = particles.particles[id.x];
+        particles.particles[id.x].position = particle.position + particle.velocity * uniforms.delta_time;
+    }
+}
+
+fn buffer_entry(binding: u32, buffer: &BufferId, offset: u64) -> BindGroupEntry {
+    BindGroupEntry {
+        binding,
+        resource: BindingResource::Buffer(BufferBinding {
+            buffer: buffer.clone(),
+            offset,
+            size: NonZeroU64::new(16),
+        }),
+    }
+}
+
+#[test]
+fn key_identifies_resources() {
+    let layouts = IdMap::<wgpu::BindGroupLayout>::new();
+    let buffers = IdMap::<wgpu::Buffer>::new();
+
+    let layout = layouts.next_id();
+    let a = buffers.next_id();
+    let b = buffers.next_id();
+
+    let desc = |a: &BufferId, offset| {
+        BindGroupDescriptor::new(
+            layout.clone(),
+            vec![buffer_entry(1, &b, 0), buffer_entry(0, a, offset)],
+        )
+    };
+
+    let key = GroupKey::from_descriptor(&desc(&a, 0));
+
+    // rebuilding the descriptor from the same resources gives the same key
+    assert_eq!(GroupKey::from_descriptor(&desc(&a, 0)), key);
+    assert_eq!(key.entries.len(), 2);
+    assert_eq!(key.entries[0].0, 0);
+
+    // a recreated resource gets a new id
+    let grown = buffers.next_id();
+    assert_ne!(GroupKey::from_descriptor(&desc(&grown, 0)), key);
+    assert_ne!(GroupKey::from_descriptor(&desc(&a, 256)), key);
+}
+
+#[test]
+fn building_keys_does_not_allocate() {
+    let layouts = IdMap::<wgpu::BindGroupLayout>::new();
+    let buffers = IdMap::<wgpu::Buffer>::new();
+
+    let layout = layouts.next_id();
+    let ids = (0..8).map(|_| buffers.next_id()).collect::<Vec<_>>();
+
+    let desc = BindGroupDescriptor::new(
+        layout,
+        ids.iter()
+            .enumerate()
+            .map(|(i, id)| buffer_entry(i as u32, id, 0))
+            .collect(),
+    );
+
+    let (key, count) = allocations(|| GroupKey::from_descriptor(&desc));
+
+    assert_eq!(key.entries.len(), 8);
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn steady_state_bind_groups() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let mut particles = Buffer::<Particles>::new();
+    let mut uniforms = Buffer::<Uniforms>::new();
+
+    for _ in 0..256 {
+        particles.push(Particle::default());
+    }
+
+    uniforms.delta_time = 0.016;
+
+    let dispatch = Dispatch::new(4, 1, 1);
+
+    step(
+        step::Bindings {
+            particles: &mut particles,
+            uniforms: &uniforms,
+        },
+        dispatch,
+    );
+
+    let layouts = step::Bindings {
+        particles: &mut particles,
+        uniforms: &uniforms,
+    }
+    .bind_group_layout_descriptors()
+    .into_iter()
+    .map(|desc| instance.get_bind_group_layout(desc))
+    .collect::<Vec<_>>();
+
+    let cached = |particles: &mut Buffer<Particles>| {
+        // the bindings are reconstructed every frame
+        let bindings = step::Bindings {
+            particles,
+            uniforms: &uniforms,
+        };
+
+        let keys = bindings.bind_group_keys(&layouts);
+
+        instance
+            .group_keys
+            .get(&keys[0])
+            .map(|id| id.clone_untracked())
+    };
+
+    let (first, _) = allocations(|| cached(&mut particles));
+    let first = first.expect("bind group is cached by key after a dispatch");
+
+    for _ in 0..16 {
+        let (id, count) = allocations(|| cached(&mut particles));
+
+        assert_eq!(id, Some(first.clone_untracked()));
+        assert_eq!(count, 0);
+    }
+
+    // growing the buffer recreates it, which changes the key
+    for _ in 0..4096 {
+        particles.push(Particle::default());
+    }
+
+    step(
+        step::Bindings {
+            particles: &mut particles,
+            uniforms: &uniforms,
+        },
+        Dispatch::new(68, 1, 1),
+    );
+
+    let grown = cached(&mut particles).unwrap();
+    assert_ne!(grown, first);
+}
+
+#[test]
+fn steady_state_dispatch() {
+    // an instance of its own, so its counters only see this test
+    let instance = match Instance::create(&InstanceDescriptor::default()) {
+        Ok(instance) => instance,
+        Err(_) => return,
+    };
+
+    let mut particles = Buffer::<Particles>::new_in(instance);
+    let uniforms = Buffer::<Uniforms>::new_in(instance);
+
+    for _ in 0..256 {
+        particles.push(Particle::default());
+    }
+
+    let dispatch = Dispatch::new(4, 1, 1);
+
+    step::build(step::Bindings {
+        particles: &mut particles,
+        uniforms: &uniforms,
+    })
+    .instance(instance)
+    .dispatch(dispatch);
+
+    // the cached ids, to record the same pass without shatter
+    let bindings = step::Bindings {
+        particles: &mut particles,
+        uniforms: &uniforms,
+    };
+
+    let layouts = layouts(instance, &bindings, false);
+    let bind_group = instance
+        .group_keys
+        .get(&bindings.bind_group_keys(&layouts)[0])
+        .map(|id| id.clone_untracked())
+        .unwrap();
+
+    let pipeline_layout = instance.get_pipeline_layout(PipelineLayoutDescriptor {
+        bind_group_layouts: layouts,
+        push_constant_ranges: Vec::new(),
+    });
+    let pipeline = instance.get_compute_pipeline(ComputePipelineDescriptor {
+        layout: Some(pipeline_layout),
+        module: instance.get_shader_module(<step::Shader as ComputeShader>::SOURCE),
+        entry_point: <step::Shader as ComputeShader>::ENTRY_POINT.into(),
+    });
+
+    let record_raw = |encoder: &mut wgpu::CommandEncoder| {
+        let pipeline = instance.compute_pipelines.get(&pipeline).unwrap();
+        let bind_group = instance.bind_groups.get(&bind_group).unwrap();
+
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("shatter_compute_pass(step)"),
+        });
+
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch(dispatch.x, dispatch.y, dispatch.z);
+    };
+
+    let mut shader = step::build(step::Bindings {
+        particles: &mut particles,
+        uniforms: &uniforms,
+    });
+    shader.instance(instance).dispatch(dispatch);
+
+    let new_encoder = || {
+        instance
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default())
+    };
+
+    // wgpu allocates while recording the pass, shatter adds nothing to it
+    for _ in 0..4 {
+        let mut encoder = new_encoder();
+        let (_, recorded) = allocations(|| {
+            shader.dispatch_in(&mut encoder, dispatch);
+        });
+
+        let mut encoder = new_encoder();
+        let (_, raw) = allocations(|| record_raw(&mut encoder));
+
+        assert!(
+            recorded <= raw,
+            "{} allocations dispatching, {} recording the pass with wgpu",
+            recorded,
+            raw
+        );
+    }
+
+    instance.stats_frame();
+
+    for _ in 0..16 {
+        shader.dispatch(dispatch);
+    }
+
+    // only the bind group is looked up, by key, no descriptor is hashed
+    let stats = instance.stats_frame();
+    assert_eq!(stats.cache_misses, 0);
+    assert_eq!(stats.cache_hits, 16);
+}
+
+#[test]
+fn canonical_descriptors() {
+    let layouts = IdMap::<wgpu::BindGroupLayout>::new();
+    let buffers = IdMap::<wgpu::Buffer>::new();
+
+    let layout = layouts.next_id();
+    let (a, b) = (buffers.next_id(), buffers.next_id());
+
+    let sorted = vec![buffer_entry(0, &a, 0), buffer_entry(1, &b, 0)];
+    let mut shuffled = BindGroupDescriptor {
+        layout: layout.clone(),
+        entries: sorted.iter().rev().cloned().collect(),
+    };
+
+    shuffled.canonicalize();
+    assert_eq!(shuffled, BindGroupDescriptor::new(layout, sorted));
+}
+
+/// Layouts of `bindings`, each with its entries reversed if `reversed`.
+fn layouts(
+    instance: &Instance,
+    bindings: &step::Bindings,
+    reversed: bool,
+) -> Vec<BindGroupLayoutId> {
+    bindings
+        .bind_group_layout_descriptors()
+        .into_iter()
+        .map(|mut desc| {
+            if reversed {
+                desc.entries.reverse();
+            }
+
+            instance.get_bind_group_layout(desc)
+        })
+        .collect()
+}
+
+#[test]
+fn reordered_layout_entries() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let mut particles = Buffer::<Particles>::new();
+    let uniforms = Buffer::<Uniforms>::new();
+
+    let bindings = step::Bindings {
+        particles: &mut particles,
+        uniforms: &uniforms,
+    };
+
+    let desc = &bindings.bind_group_layout_descriptors()[0];
+    assert!(desc.entries.len() > 1, "reversing must reorder the entries");
+
+    assert_eq!(
+        layouts(instance, &bindings, true),
+        layouts(instance, &bindings, false)
+    );
+}
+
+#[test]
+fn reordered_bind_group_entries() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let mut particles = Buffer::<Particles>::new();
+    let uniforms = Buffer::<Uniforms>::new();
+
+    let bindings = step::Bindings {
+        particles: &mut particles,
+        uniforms: &uniforms,
+    };
+
+    let layouts = layouts(instance, &bindings, false);
+
+    let bind_groups = |reversed: bool| {
+        bindings
+            .bind_group_descriptors(&layouts)
+            .into_iter()
+            .map(|mut desc| {
+                if reversed {
+                    desc.entries.reverse();
+                }
+
+                instance.get_bind_group(desc)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(bind_groups(true), bind_groups(false));
+}
+
+#[test]
+fn reordered_layout_entries_share_pipeline() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let mut particles = Buffer::<Particles>::new();
+    let uniforms = Buffer::<Uniforms>::new();
+
+    let bindings = step::Bindings {
+        particles: &mut particles,
+        uniforms: &uniforms,
+    };
+
+    let pipeline = |reversed: bool| {
+        let layout = instance.get_pipeline_layout(PipelineLayoutDescriptor {
+            bind_group_layouts: layouts(instance, &bindings, reversed),
+            push_constant_ranges: Vec::new(),
+        });
+
+        instance.get_compute_pipeline(ComputePipelineDescriptor {
+            layout: Some(layout),
+            module: instance.get_shader_module(<step::Shader as ComputeShader>::SOURCE),
+            entry_point: <step::Shader as ComputeShader>::ENTRY_POINT.into(),
+        })
+    };
+
+    assert_eq!(pipeline(true), pipeline(false));
+}
@@ -0,0 +1,179 @@
+use shatter::*;
+
+wgsl_group! {
+    name: Scene,
+    group: 0,
+
+    [[block]]
+    struct Frame {
+        time: f32;
+    };
+
+    [[binding(0)]]
+    var<uniform> frame: Frame;
+}
+
+wgsl! {
+    use_group!(Scene);
+
+    [[block]]
+    struct Positions {
+        positions: array<f32>;
+    };
+
+    [[group(1), binding(0)]]
+    var<storage, read_write> positions: Positions;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn advance([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&positions.positions)) {
+            return;
+        }
+
+        positions.positions[id.x] = positions.positions[id.x] + frame.time;
+    }
+}
+
+wgsl! {
+    use_group!(Scene);
+
+    [[block]]
+    struct Weights {
+        weights: array<f32>;
+    };
+
+    [[group(1), binding(0)]]
+    var<storage, read_write> weights: Weights;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn decay([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&weights.weights)) {
+            return;
+        }
+
+        weights.weights[id.x] = weights.weights[id.x] * frame.time;
+    }
+}
+
+wgsl!(mod sparse {
+    [[block]]
+    struct Params {
+        offset: u32;
+    };
+
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<uniform> params: Params;
+
+    [[group(2), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn offset([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&values.values)) {
+            return;
+        }
+
+        values.values[id.x] = values.values[id.x] + params.offset;
+    }
+});
+
+#[test]
+fn shared_group_is_created_once() {
+    // an instance of its own, so other tests don't create bind groups in between
+    let instance = match Instance::create(&InstanceDescriptor::default()) {
+        Ok(instance) => instance,
+        Err(_) => return,
+    };
+
+    let mut frame = Buffer::<Frame>::new_in(instance);
+    frame.time = 2.0;
+
+    let mut positions = Buffer::<Positions>::new_in(instance);
+    positions.extend([1.0, 2.0, 3.0]);
+
+    let mut weights = Buffer::<Weights>::new_in(instance);
+    weights.extend([1.0, 2.0, 3.0]);
+
+    let live_bind_groups = instance.stats_frame().live_bind_groups;
+
+    let mut scene = Scene { frame: &frame };
+
+    for _ in 0..2 {
+        advance::build_groups((
+            &mut scene,
+            advance::Group1 {
+                positions: &mut positions,
+            },
+        ))
+        .instance(instance)
+        .dispatch(advance::dispatch_for(3));
+
+        decay::build_groups((
+            &mut scene,
+            decay::Group1 {
+                weights: &mut weights,
+            },
+        ))
+        .instance(instance)
+        .dispatch(decay::dispatch_for(3));
+    }
+
+    // the scene group and a group per kernel, the second round only hits the cache
+    let stats = instance.stats_frame();
+    assert_eq!(stats.live_bind_groups - live_bind_groups, 3);
+
+    assert_eq!(positions.as_slice(), [5.0, 6.0, 7.0]);
+    assert_eq!(weights.as_slice(), [4.0, 8.0, 12.0]);
+}
+
+#[test]
+fn groups_convert_from_and_to_bindings() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut params = Buffer::<sparse::Params>::new();
+    params.offset = 5;
+
+    let mut values = Buffer::<sparse::Values>::new();
+    values.extend(0..8);
+
+    let bindings = sparse::offset::Bindings {
+        params: &params,
+        values: &mut values,
+    };
+
+    // the unused group 1 is composed as an empty group
+    let (params_group, empty, values_group) = bindings.into_groups();
+    assert_eq!(empty, EmptyGroup);
+
+    let groups = (params_group, EmptyGroup, values_group);
+    assert_eq!(Bindings::group_count(&groups), 3);
+    assert_eq!(
+        Bindings::bind_group_layout_descriptors(&groups)
+            .iter()
+            .map(|desc| desc.entries.len())
+            .collect::<Vec<_>>(),
+        [1, 0, 1]
+    );
+
+    sparse::offset::build_groups(groups).dispatch(sparse::offset::dispatch_for(8));
+    assert!(values.as_slice().iter().copied().eq(5..13));
+
+    sparse::offset(
+        sparse::offset::Bindings::from_groups((
+            sparse::offset::Group0 { params: &params },
+            EmptyGroup,
+            sparse::offset::Group2 {
+                values: &mut values,
+            },
+        )),
+        sparse::offset::dispatch_for(8),
+    );
+    assert!(values.as_slice().iter().copied().eq(10..18));
+}
@@ -0,0 +1,67 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn increment([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&values.values)) {
+            return;
+        }
+
+        values.values[id.x] = values.values[id.x] + 1u;
+    }
+}
+
+// the only test touching the instance, so the count only sees its groups
+#[test]
+fn resized_buffers_evict_their_bind_groups() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let mut values = Buffer::<Values>::new();
+    let mut first = 0;
+    let mut most = 0;
+
+    for i in 0..100 {
+        // every growth recreates the wgpu buffer under a new id
+        values.extend(std::iter::repeat_n(0, 64 * (i + 1)));
+        let len = values.values.len() as u64;
+
+        increment(
+            increment::Bindings {
+                values: &mut values,
+            },
+            increment::dispatch_for(len),
+        );
+
+        if i == 0 {
+            first = instance.bind_group_count();
+        }
+
+        most = most.max(instance.bind_group_count());
+    }
+
+    // every dispatch submits at least once and creates `first` groups, so a
+    // clean runs before at least every `CLEAN_INTERVAL`th dispatch, leaving
+    // the groups of at most that many dispatches alive
+    let bound = first * CLEAN_INTERVAL as usize;
+    assert!(
+        most <= bound,
+        "{} bind groups, at most {} expected",
+        most,
+        bound
+    );
+
+    instance.clean();
+    assert_eq!(instance.bind_group_count(), first);
+    assert!(instance.group_keys.len() <= first);
+}
@@ -0,0 +1,58 @@
+use shatter::{
+    test_util::{diff_rgba8, texture_rgba8, ImageTolerance},
+    *,
+};
+
+#[path = "../examples/blur/kernels.rs"]
+mod kernels;
+
+use kernels::Blur;
+
+// not a multiple of the work group size, so the bounds checks are exercised
+const WIDTH: usize = 37;
+const HEIGHT: usize = 21;
+
+// the bind group counter is global, so everything runs in a single test
+#[test]
+fn separable_blur_matches_reference() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let mut image = kernels::test_image(WIDTH, HEIGHT, 4);
+    let mut scratch = Texture2d::<Rgba8Unorm>::new(WIDTH, HEIGHT);
+    let input = texture_rgba8(&image);
+
+    let mut params = Buffer::<Blur>::new();
+    params.radius = 3;
+    params.sigma = 1.5;
+
+    let bind_groups = instance.bind_group_count();
+
+    kernels::blur(&mut image, &mut scratch, &params);
+
+    // the parameter group is shared, each pass only adds its textures
+    assert_eq!(instance.bind_group_count(), bind_groups + 3);
+
+    let reference = kernels::reference(&input, WIDTH, HEIGHT, &params);
+    let tolerance = ImageTolerance::default();
+    let (diff, _) = diff_rgba8(
+        &texture_rgba8(&image),
+        &reference,
+        tolerance.channel_epsilon,
+    );
+
+    assert!(
+        diff.differing_fraction() <= tolerance.max_differing_fraction,
+        "{:?}",
+        diff
+    );
+
+    // ping-ponging again reuses every bind group
+    let bind_groups = instance.bind_group_count();
+
+    kernels::blur(&mut image, &mut scratch, &params);
+
+    assert_eq!(instance.bind_group_count(), bind_groups);
+}
@@ -0,0 +1,97 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[block]]
+    struct Sum {
+        total: u32;
+        count: u32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> sum: Sum;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn double([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] * 2u;
+    }
+
+    [[stage(compute), workgroup_size(1)]]
+    fn total() {
+        sum.total = 0u;
+
+        for (var i: u32 = 0u; i < arrayLength(&values.values); i = i + 1u) {
+            sum.total = sum.total + values.values[i];
+        }
+
+        sum.count = arrayLength(&values.values);
+    }
+}
+
+fn values(len: u32) -> Buffer<Values> {
+    let mut values = Buffer::<Values>::new();
+
+    for i in 0..len {
+        values.push(i);
+    }
+
+    values
+}
+
+// the download counter is global, so everything runs in a single test
+#[test]
+fn drop_and_into_contents() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    // dropping a buffer the GPU just wrote doesn't download it
+    let mut dirty = values(1024);
+
+    double(
+        double::Bindings { values: &mut dirty },
+        Dispatch::new(1024, 1, 1),
+    );
+
+    assert!(dirty.needs_download());
+
+    let downloaded = buffer_downloaded_bytes();
+    drop(dirty);
+    assert_eq!(buffer_downloaded_bytes(), downloaded);
+
+    // into_contents forces the download
+    let mut values = values(4);
+    let mut sum = Buffer::<Sum>::new();
+
+    double(
+        double::Bindings {
+            values: &mut values,
+        },
+        Dispatch::new(4, 1, 1),
+    );
+
+    total(
+        total::Bindings {
+            values: &mut values,
+            sum: &mut sum,
+        },
+        Dispatch::new(1, 1, 1),
+    );
+
+    assert_eq!(values.into_contents(), vec![0, 2, 4, 6]);
+    assert_eq!(
+        sum.into_contents(),
+        Sum {
+            total: 12,
+            count: 4
+        }
+    );
+    assert!(buffer_downloaded_bytes() > downloaded);
+}
@@ -0,0 +1,80 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        scale: u32;
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn scale([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&values.values)) {
+            return;
+        }
+
+        values.values[id.x] = values.values[id.x] * values.scale;
+    }
+}
+
+fn dispatch_scale(values: &mut Buffer<Values>) {
+    scale(scale::Bindings { values }, scale::dispatch_for(1000));
+}
+
+// the only test touching the instance, so the counters only see its transfers
+#[test]
+fn single_elements_transfer_only_their_bytes() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let mut values = Buffer::<Values>::new();
+    values.scale = 2;
+    values.extend(0..1000);
+
+    dispatch_scale(&mut values);
+    assert!(values.needs_download());
+
+    instance.stats_frame();
+
+    assert_eq!(values.read_element(123), 246);
+
+    let stats = instance.stats_frame();
+    assert_eq!(stats.downloaded_bytes, 4);
+    assert_eq!(stats.uploaded_bytes, 0);
+    assert!(values.needs_download());
+
+    values.write_element(7, 1);
+
+    let stats = instance.stats_frame();
+    assert_eq!(stats.uploaded_bytes, 4);
+    assert_eq!(stats.downloaded_bytes, 0);
+    assert!(values.needs_download());
+
+    // the kernel sees the written element next to what it wrote before
+    dispatch_scale(&mut values);
+
+    assert_eq!(values.read_element(7), 2);
+    assert_eq!(values.read_element(123), 492);
+
+    // a full download agrees with the single elements
+    let full = values.as_slice().to_vec();
+    assert_eq!(full[7], 2);
+    assert_eq!(full[123], 492);
+    assert_eq!(full[999], 999 * 4);
+
+    // in sync, read from the CPU side without transferring
+    instance.stats_frame();
+    assert_eq!(values.read_element(999), 999 * 4);
+    assert_eq!(instance.stats_frame().downloaded_bytes, 0);
+
+    let error = panic::catch_unwind(AssertUnwindSafe(|| values.read_element(1000))).unwrap_err();
+    let message = error.downcast_ref::<String>().unwrap();
+    assert!(message.contains("out of bounds"), "{}", message);
+}
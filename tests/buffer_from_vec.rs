@@ -0,0 +1,57 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn increment([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x < arrayLength(&values.values)) {
+            values.values[id.x] = values.values[id.x] + 1u;
+        }
+    }
+}
+
+// the only test in its process, so nothing else adds to the downloaded bytes
+#[test]
+fn round_trips_with_one_copy_each_way() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    const LEN: usize = 1 << 20;
+    let size = (LEN * std::mem::size_of::<u32>()) as u64;
+
+    let mut values = Buffer::<Values>::from_vec((), (0..LEN as u32).collect());
+
+    // allocated once for exactly the items
+    assert_eq!(values.len(), LEN);
+    assert_eq!(values.capacity(), LEN);
+    assert!(values.needs_upload());
+    assert!(!values.needs_download());
+
+    let mut builder = increment::build(increment::Bindings {
+        values: &mut values,
+    });
+    builder.dispatch(increment::dispatch_for(LEN as u64));
+
+    let report = builder.last_transfer_report();
+    assert_eq!(report.uploaded_bytes, size);
+    assert_eq!(report.downloaded_bytes, 0);
+    drop(builder);
+
+    assert!(!values.needs_upload());
+    assert!(values.needs_download());
+
+    let downloaded = buffer_downloaded_bytes();
+    let ((), items) = values.into_vec();
+    assert_eq!(buffer_downloaded_bytes() - downloaded, size);
+
+    assert_eq!(items.len(), LEN);
+    assert!(items.iter().copied().eq(1..=LEN as u32));
+}
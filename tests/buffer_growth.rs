@@ -0,0 +1,66 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn count([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = arrayLength(&values.values);
+    }
+}
+
+#[test]
+fn allocation_sizes() {
+    assert_eq!(GrowthPolicy::Exact.allocation_size(0), 4);
+    assert_eq!(GrowthPolicy::Exact.allocation_size(100), 100);
+    assert_eq!(GrowthPolicy::Double.allocation_size(100), 128);
+    assert_eq!(GrowthPolicy::Double.allocation_size(128), 128);
+    assert_eq!(GrowthPolicy::Chunked(256).allocation_size(100), 256);
+    assert_eq!(GrowthPolicy::Chunked(256).allocation_size(257), 512);
+    assert_eq!(GrowthPolicy::Chunked(0).allocation_size(100), 100);
+    assert_eq!(GrowthPolicy::Chunked(6).allocation_size(7), 12);
+}
+
+/// Pushes one item before each of 100 dispatches, returns the number of gpu
+/// buffer recreations.
+fn grow_one_at_a_time(growth: GrowthPolicy) -> u64 {
+    let instance = Instance::global();
+
+    let mut values = Buffer::<Values>::with_growth_policy(growth);
+    instance.stats_frame();
+
+    for len in 1..=100 {
+        values.push(0);
+
+        count(
+            count::Bindings {
+                values: &mut values,
+            },
+            count::dispatch_for(len),
+        );
+    }
+
+    // the slack of the gpu buffer isn't visible to the shader
+    assert!(values.values.iter().all(|&len| len == 100));
+
+    instance.stats_frame().buffer_resizes
+}
+
+// the only test touching the instance, so the counters only see its buffers
+#[test]
+fn gpu_buffer_grows_with_the_data() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    // 8, 16, .., 512 bytes
+    assert!(grow_one_at_a_time(GrowthPolicy::Double) <= 7);
+    assert_eq!(grow_one_at_a_time(GrowthPolicy::Exact), 99);
+    assert_eq!(grow_one_at_a_time(GrowthPolicy::Chunked(64)), 7);
+}
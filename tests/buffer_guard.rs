@@ -0,0 +1,64 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Stats {
+        min: u32;
+        max: u32;
+        sum: u32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> stats: Stats;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn fill() {
+        stats.min = 1u;
+        stats.max = 8u;
+        stats.sum = 36u;
+    }
+}
+
+fn fill_stats(stats: &mut Buffer<Stats>) {
+    fill(fill::Bindings { stats }, Dispatch::new(1, 1, 1));
+}
+
+#[test]
+fn guards_download_once() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut stats = Buffer::<Stats>::new();
+    let size = std::mem::size_of::<Stats>() as u64;
+
+    fill_stats(&mut stats);
+    assert!(stats.needs_download());
+
+    let before = buffer_downloaded_bytes();
+
+    {
+        let read = stats.read();
+        assert_eq!(read.min, 1);
+        assert_eq!(read.max, 8);
+        assert_eq!(read.sum, 36);
+    }
+
+    assert_eq!(buffer_downloaded_bytes() - before, size);
+    assert!(!stats.needs_upload());
+
+    fill_stats(&mut stats);
+
+    let before = buffer_downloaded_bytes();
+
+    {
+        let mut write = stats.write();
+        write.min = 0;
+        write.sum -= 1;
+        write.max += write.sum;
+    }
+
+    assert_eq!(buffer_downloaded_bytes() - before, size);
+    assert!(stats.needs_upload());
+    assert_eq!((stats.read().min, stats.read().max), (0, 43));
+}
@@ -0,0 +1,222 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        scale: u32;
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn scale([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&values.values)) {
+            return;
+        }
+
+        values.values[id.x] = values.values[id.x] * values.scale;
+    }
+}
+
+wgsl!(mod append {
+    [[block]]
+    struct Counter {
+        count: atomic<u32>;
+    };
+
+    [[block]]
+    struct Items {
+        items: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> counter: Counter;
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> items: Items;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn append_even([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x % 2u == 0u) {
+            items.items[atomicAdd(&counter.count, 1u)] = id.x;
+        }
+    }
+});
+
+#[test]
+fn reserve_reallocates_once() {
+    let mut state = Values::init();
+    let mut ptr = unsafe { Values::alloc() };
+
+    unsafe { Values::reserve(&mut ptr, &mut state, 1000) };
+    assert_eq!(Values::capacity(&state), 1000);
+
+    // already has room
+    unsafe { Values::reserve(&mut ptr, &mut state, 10) };
+    assert_eq!(Values::capacity(&state), 1000);
+
+    for i in 0..1000 {
+        unsafe { Values::push(&mut ptr, &mut state, i) };
+    }
+
+    assert_eq!(Values::capacity(&state), 1000);
+
+    // doubles when growing past it
+    unsafe { Values::reserve(&mut ptr, &mut state, 1) };
+    assert_eq!(Values::capacity(&state), 2000);
+
+    let items = unsafe { std::slice::from_raw_parts(Values::items(ptr, &state), 1000) };
+    assert!(items.iter().copied().eq(0..1000));
+
+    unsafe { Values::dealloc(ptr, &state) };
+}
+
+#[test]
+fn bulk_operations() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut buffer = Buffer::<Values>::new();
+    buffer.scale = 3;
+
+    buffer.extend(0..100);
+    assert_eq!(buffer.capacity(), 100);
+
+    buffer.extend_from_slice(&[100, 101, 102]);
+    assert_eq!(buffer.len(), 103);
+    assert!(buffer.as_slice().iter().copied().eq(0..103));
+
+    buffer.truncate(200);
+    assert_eq!(buffer.len(), 103);
+
+    buffer.truncate(10);
+    assert_eq!(buffer.as_slice(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    assert!(buffer.capacity() >= 103);
+
+    scale(
+        scale::Bindings {
+            values: &mut buffer,
+        },
+        scale::dispatch_for(10),
+    );
+
+    // downloads what the dispatch wrote
+    assert_eq!(buffer.as_slice()[9], 27);
+
+    buffer.as_mut_slice()[0] = 5;
+    assert!(buffer.needs_upload());
+
+    scale(
+        scale::Bindings {
+            values: &mut buffer,
+        },
+        scale::dispatch_for(10),
+    );
+
+    assert_eq!(buffer.as_slice()[..3], [15, 9, 18]);
+
+    buffer.clear();
+    assert!(buffer.is_empty());
+    assert!(buffer.values.is_empty());
+}
+
+#[test]
+fn gpu_appends_survive_growth() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut counter = Buffer::<append::Counter>::new();
+    let mut items = Buffer::<append::Items>::new();
+
+    // room for every invocation to append
+    items.resize_with(64, || u32::MAX);
+    assert_eq!(items.len(), 64);
+
+    append::append_even(
+        append::append_even::Bindings {
+            counter: &mut counter,
+            items: &mut items,
+        },
+        append::append_even::dispatch_for(64),
+    );
+
+    // the CPU learns the length from the counter
+    unsafe { items.set_len(counter.count as usize) };
+    assert!(items.needs_download());
+
+    let mut appended = items.as_slice().to_vec();
+    appended.sort_unstable();
+    assert!(appended.into_iter().eq((0..64).step_by(2)));
+
+    counter.count = 0;
+
+    append::append_even(
+        append::append_even::Bindings {
+            counter: &mut counter,
+            items: &mut items,
+        },
+        append::append_even::dispatch_for(64),
+    );
+
+    // growing copies what the GPU wrote into the larger buffer
+    items.reserve(68);
+    unsafe { items.set_len(100) };
+    assert!(items.needs_download());
+
+    let mut appended = items.as_slice()[..32].to_vec();
+    appended.sort_unstable();
+    assert!(appended.into_iter().eq((0..64).step_by(2)));
+
+    assert!(items.as_slice()[32..64]
+        .iter()
+        .all(|&item| item == u32::MAX));
+    assert!(items.as_slice()[64..].iter().all(|&item| item == 0));
+
+    items.resize_with(10, || unreachable!());
+    assert_eq!(items.len(), 10);
+}
+
+#[test]
+fn from_vec_keeps_the_head() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut buffer = Buffer::<Values>::from_vec(
+        Values_Sized {
+            scale: 3,
+            ..Default::default()
+        },
+        vec![1, 2, 3],
+    );
+    assert_eq!(buffer.scale, 3);
+    assert_eq!(buffer.to_vec(), [1, 2, 3]);
+
+    scale(
+        scale::Bindings {
+            values: &mut buffer,
+        },
+        scale::dispatch_for(3),
+    );
+
+    assert_eq!(buffer.to_vec(), [3, 6, 9]);
+
+    let (head, items) = buffer.into_vec();
+    assert_eq!(head.scale, 3);
+    assert_eq!(items, [3, 6, 9]);
+
+    // an empty vec still carries the head
+    let empty = Buffer::<Values>::from_vec(
+        Values_Sized {
+            scale: 7,
+            ..Default::default()
+        },
+        Vec::new(),
+    );
+    assert!(empty.is_empty());
+    assert_eq!(empty.into_vec().0.scale, 7);
+}
@@ -0,0 +1,138 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn double([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&values.values)) {
+            return;
+        }
+
+        values.values[id.x] = values.values[id.x] * 2u;
+    }
+}
+
+mod guarded {
+    use shatter::*;
+
+    wgsl! {
+        #![auto_guard]
+
+        [[block]]
+        struct Params {
+            scale: f32;
+        };
+
+        [[group(0), binding(0)]]
+        var<storage, read_write> params: Params;
+
+        // binds no runtime sized array, so dispatches need a guard count
+        [[stage(compute), workgroup_size(64)]]
+        fn scale() {
+            params.scale = params.scale * 2.0;
+        }
+    }
+}
+
+fn values() -> Buffer<Values> {
+    let mut values = Buffer::<Values>::new();
+    values.extend_from_slice(&[1; 100]);
+    values
+}
+
+/// Everything the builders could have done.
+#[derive(Debug, PartialEq)]
+struct Work {
+    compute_pipelines: usize,
+    bind_groups: usize,
+    bind_group_layouts: usize,
+    uploaded_bytes: u64,
+    dispatches: u64,
+    submissions: u64,
+}
+
+fn work(instance: &Instance) -> Work {
+    let frame = instance.stats_frame();
+
+    Work {
+        compute_pipelines: instance.compute_pipeline_count(),
+        bind_groups: instance.bind_group_count(),
+        bind_group_layouts: instance.bind_group_layout_count(),
+        uploaded_bytes: frame.uploaded_bytes,
+        dispatches: frame.dispatches,
+        submissions: frame.submissions,
+    }
+}
+
+// the counters are global, so everything runs in a single test
+#[test]
+fn builders_only_work_when_dispatching() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let mut values = values();
+    work(instance);
+
+    // configured and dropped without dispatching
+    {
+        let mut builder = double::build(double::Bindings {
+            values: &mut values,
+        });
+
+        builder
+            .with_statistics()
+            .with_source(double::Shader::SOURCE)
+            .with_guard_count(100);
+
+        assert_eq!(builder.last_transfer_report(), TransferReport::ZERO);
+        assert_eq!(builder.outstanding_leases(), 0);
+    }
+
+    let idle = work(instance);
+    assert_eq!(idle.uploaded_bytes, 0);
+    assert_eq!(idle.dispatches, 0);
+    assert_eq!(idle.submissions, 0);
+    assert_eq!(work(instance), idle);
+    assert!(values.needs_upload());
+
+    // a panic mid dispatch releases its lease, the builder stays usable
+    let mut params = Buffer::<guarded::Params>::new();
+    let mut builder = guarded::scale::build(guarded::scale::Bindings {
+        params: &mut params,
+    });
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        builder.dispatch(Dispatch::new(1, 1, 1));
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(builder.outstanding_leases(), 0);
+    assert_eq!(work(instance).submissions, 0);
+
+    builder.with_guard_count(1).dispatch(Dispatch::new(1, 1, 1));
+    assert_eq!(builder.outstanding_leases(), 0);
+    assert_eq!(work(instance).submissions, 1);
+    drop(builder);
+
+    // only the dispatch uploads the dirty binding
+    double(
+        double::Bindings {
+            values: &mut values,
+        },
+        Dispatch::new(2, 1, 1),
+    );
+
+    assert!(!values.needs_upload());
+    assert_eq!(values.as_slice(), &[2; 100]);
+}
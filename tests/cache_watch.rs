@@ -0,0 +1,74 @@
+use std::sync::{Arc, Mutex};
+
+use shatter::*;
+
+fn source(frame: usize) -> String {
+    format!(
+        "// generated at frame {}
+[[stage(compute), workgroup_size(1)]]
+fn main() {{}}
+",
+        frame
+    )
+}
+
+#[test]
+fn churn_warns_and_cap_stops_growth() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let churn = Arc::new(Mutex::new(Vec::new()));
+    let hook_churn = churn.clone();
+    instance.set_cache_churn_hook(move |event| hook_churn.lock().unwrap().push(event.clone()));
+
+    for frame in 0..100 {
+        instance.get_shader_module(source(frame));
+    }
+
+    {
+        let churn = churn.lock().unwrap();
+        let event = churn
+            .iter()
+            .find(|event| event.kind == CacheKind::ShaderModule)
+            .expect("no churn warning");
+
+        assert!(event.insertions > CacheLimits::default().churn_insertions);
+        assert!(event.preview.starts_with("// generated at frame"));
+        assert!(event.preview.chars().count() <= 100);
+
+        let diff_hint = event.diff_hint.as_ref().unwrap();
+        assert!(diff_hint.contains("line 1"), "{}", diff_hint);
+    }
+
+    let count = instance.shader_module_count();
+
+    instance.set_cache_limits(CacheLimits {
+        max_shader_modules: Some(count + 10),
+        ..Default::default()
+    });
+
+    let mut inserted = 0;
+    let mut full = 0;
+
+    for frame in 100..200 {
+        match instance.try_get_shader_module(source(frame)) {
+            Ok(_) => inserted += 1,
+            Err(err) => {
+                assert_eq!(err.kind, CacheKind::ShaderModule);
+                full += 1;
+            }
+        }
+    }
+
+    assert_eq!(inserted, 10);
+    assert_eq!(full, 90);
+    assert_eq!(instance.shader_module_count(), count + 10);
+
+    // cached sources are still available once the cache is full
+    assert!(instance.try_get_shader_module(source(0)).is_ok());
+
+    instance.set_cache_limits(CacheLimits::default());
+    instance.clear_cache_churn_hook();
+}
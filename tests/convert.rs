@@ -0,0 +1,146 @@
+use shatter::convert;
+
+/// Lengths covering empty input, partial chunks and several chunks with a remainder.
+const LENGTHS: &[usize] = &[0, 1, 15, 16, 17, 64, 1000];
+
+fn bytes(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 37 % 256) as u8).collect()
+}
+
+fn floats(len: usize) -> Vec<f32> {
+    // includes values outside of 0..=1 and NaN
+    (0..len)
+        .map(|i| match i % 97 {
+            0 => f32::NAN,
+            i => i as f32 / 80.0 - 0.1,
+        })
+        .collect()
+}
+
+fn saturate(value: f32) -> f32 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(0.0, 1.0)
+    }
+}
+
+fn reference_unorm8(value: f32) -> u8 {
+    (saturate(value) * 255.0).round_ties_even() as u8
+}
+
+#[test]
+fn unorm8_matches_scalar() {
+    for &len in LENGTHS {
+        let src = bytes(len);
+        let mut dst = vec![0.0; len];
+        convert::unorm8_to_f32(&src, &mut dst);
+
+        for (src, dst) in src.iter().zip(&dst) {
+            assert_eq!(*dst, *src as f32 / 255.0);
+        }
+
+        let src = floats(len);
+        let mut dst = vec![0; len];
+        convert::f32_to_unorm8(&src, &mut dst);
+
+        for (src, dst) in src.iter().zip(&dst) {
+            assert_eq!(*dst, reference_unorm8(*src));
+        }
+    }
+}
+
+#[test]
+fn unorm8_round_trips() {
+    let src = (0..=255).collect::<Vec<u8>>();
+    let mut floats = vec![0.0; 256];
+    let mut dst = vec![0; 256];
+
+    convert::unorm8_to_f32(&src, &mut floats);
+    convert::f32_to_unorm8(&floats, &mut dst);
+
+    assert_eq!(src, dst);
+}
+
+#[test]
+fn srgb_matches_scalar() {
+    for &len in LENGTHS {
+        let src = bytes(len);
+        let mut dst = vec![0.0; len];
+        convert::srgb8_to_linear(&src, &mut dst);
+
+        for (src, dst) in src.iter().zip(&dst) {
+            assert_eq!(*dst, convert::srgb_to_linear_f32(*src as f32 / 255.0));
+        }
+
+        let src = floats(len);
+        let mut dst = vec![0; len];
+        convert::linear_to_srgb8(&src, &mut dst);
+
+        for (src, dst) in src.iter().zip(&dst) {
+            let exact = reference_unorm8(convert::linear_to_srgb_f32(saturate(*src)));
+
+            assert!(
+                (*dst as i32 - exact as i32).abs() <= 1,
+                "{} encoded to {}, expected {}",
+                src,
+                dst,
+                exact
+            );
+        }
+    }
+}
+
+#[test]
+fn srgb_round_trips() {
+    let src = (0..=255).collect::<Vec<u8>>();
+    let mut linear = vec![0.0; 256];
+    let mut dst = vec![0; 256];
+
+    convert::srgb8_to_linear(&src, &mut linear);
+    convert::linear_to_srgb8(&linear, &mut dst);
+
+    assert_eq!(src, dst);
+}
+
+#[test]
+fn copy_rows_strips_padding() {
+    let (width, height, stride) = (5, 3, 8);
+    let padded = (0..stride * height).map(|i| i as u8).collect::<Vec<_>>();
+
+    let mut packed = vec![0; width * height];
+    convert::copy_rows(&padded, stride, &mut packed, width, width, height);
+    assert_eq!(
+        packed,
+        [0, 1, 2, 3, 4, 8, 9, 10, 11, 12, 16, 17, 18, 19, 20]
+    );
+
+    // padding the rows again leaves the padding bytes untouched
+    let mut repadded = vec![255; stride * height];
+    convert::copy_rows(&packed, width, &mut repadded, stride, width, height);
+
+    for row in 0..height {
+        assert_eq!(
+            repadded[row * stride..row * stride + width],
+            padded[row * stride..row * stride + width]
+        );
+        assert!(repadded[row * stride + width..(row + 1) * stride]
+            .iter()
+            .all(|&byte| byte == 255));
+    }
+}
+
+#[test]
+fn copy_rows_packed() {
+    let src = bytes(64);
+    let mut dst = vec![0; 64];
+
+    convert::copy_rows(&src, 16, &mut dst, 16, 16, 4);
+    assert_eq!(src, dst);
+}
+
+#[test]
+#[should_panic(expected = "source and destination lengths differ")]
+fn mismatched_lengths() {
+    convert::unorm8_to_f32(&[0; 4], &mut [0.0; 3]);
+}
@@ -0,0 +1,159 @@
+use std::sync::Arc;
+
+use shatter::{
+    debug::{RecordPolicy, Recorder},
+    *,
+};
+
+wgsl! {
+    struct Particle {
+        position: vec2<f32>;
+        velocity: vec2<f32>;
+    };
+
+    [[block]]
+    struct Particles {
+        particles: array<Particle>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> particles: Particles;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn step([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&particles.particles)) {
+            return;
+        }
+
+        let particle = particles.particles[id.x];
+        particles.particles[id.x].position = particle.position + particle.velocity;
+    }
+
+    [[stage(compute), workgroup_size(64)]]
+    fn corrupt([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x != 37u) {
+            return;
+        }
+
+        particles.particles[id.x].velocity = -particles.particles[id.x].velocity;
+    }
+}
+
+const PARTICLES: usize = 100;
+const FRAMES: u64 = 10;
+
+fn particles() -> Buffer<Particles> {
+    let mut particles = Buffer::<Particles>::new();
+
+    for i in 0..PARTICLES {
+        particles.push(Particle {
+            position: Vec2::new(i as f32, 0.0),
+            velocity: Vec2::new(0.5, i as f32 * 0.25),
+        });
+    }
+
+    particles
+}
+
+// the recorder is global, so everything runs in a single test
+#[test]
+fn finds_first_divergent_frame() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let recorder = Arc::new(Recorder::new(RecordPolicy {
+        entry_points: vec!["step".into()],
+        ..Default::default()
+    }));
+    instance.set_recorder(recorder.clone());
+
+    let mut reference = particles();
+    let mut corrupted = particles();
+    let dispatch = Dispatch::new(2, 1, 1);
+
+    for frame in 0..FRAMES {
+        step(
+            step::Bindings {
+                particles: &mut reference,
+            },
+            dispatch,
+        );
+
+        if frame == 6 {
+            corrupt(
+                corrupt::Bindings {
+                    particles: &mut corrupted,
+                },
+                dispatch,
+            );
+        }
+
+        step(
+            step::Bindings {
+                particles: &mut corrupted,
+            },
+            dispatch,
+        );
+
+        recorder.next_frame();
+    }
+
+    // `corrupt` isn't recorded
+    assert_eq!(recorder.snapshots().len(), 2 * FRAMES as usize);
+    assert!(recorder.snapshots().iter().all(|s| s.entry_point == "step"));
+
+    let reference_snapshots = recorder.snapshots_of(reference.id().raw());
+    let corrupted_snapshots = recorder.snapshots_of(corrupted.id().raw());
+    assert_eq!(reference_snapshots.len(), FRAMES as usize);
+    assert!(!reference_snapshots[0].is_downloaded());
+
+    let (frame, diff) = reference_snapshots
+        .iter()
+        .zip(&corrupted_snapshots)
+        .find_map(|(a, b)| Some((a.frame, a.diff::<Particle>(b, 0)?)))
+        .unwrap();
+
+    assert_eq!(frame, 6);
+    assert_eq!(diff.first_index, 37);
+    assert_eq!(diff.differing, 1);
+    assert_eq!(reference_snapshots[6].dispatches, vec![dispatch]);
+
+    let last = reference_snapshots.last().unwrap().items::<Particle>(0);
+    reference.download();
+    assert_eq!(last, reference.particles);
+
+    // exports one file per snapshot
+    let dir = std::env::temp_dir().join("shatter_debug_recorder");
+    let paths = recorder.export(&dir).unwrap();
+    assert_eq!(paths.len(), 2 * FRAMES as usize);
+    assert_eq!(
+        std::fs::read(&paths[0]).unwrap(),
+        recorder.snapshots()[0].bytes()
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    // the oldest snapshots are evicted past the budget
+    let size = reference_snapshots[0].size;
+    let small = Arc::new(Recorder::new(RecordPolicy {
+        max_bytes: size * 3,
+        ..Default::default()
+    }));
+    instance.set_recorder(small.clone());
+
+    for _ in 0..5 {
+        step(
+            step::Bindings {
+                particles: &mut reference,
+            },
+            dispatch,
+        );
+    }
+
+    assert!(instance.clear_recorder().is_some());
+    assert_eq!(small.snapshots().len(), 3);
+    assert_eq!(small.evicted(), 2);
+    assert_eq!(small.recorded_bytes(), size * 3);
+    assert_eq!(small.snapshots()[0].sequence, 2);
+}
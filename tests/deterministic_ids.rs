@@ -0,0 +1,54 @@
+use shatter::*;
+
+fn ids(map: &IdMap<wgpu::Buffer>) -> Vec<u64> {
+    ["particles", "uniforms", "particles", "particles"]
+        .iter()
+        .map(|label| map.stable_id(*label).raw())
+        .collect()
+}
+
+#[test]
+fn same_keys_same_ids() {
+    let a = IdMap::deterministic();
+    let b = IdMap::deterministic();
+
+    // unrelated allocations don't shift stable ids
+    b.next_id();
+
+    assert_eq!(ids(&a), ids(&b));
+}
+
+#[test]
+fn repeated_keys_get_sequence_ids() {
+    let map = IdMap::deterministic();
+    let ids = ids(&map);
+
+    for (i, id) in ids.iter().enumerate() {
+        assert!(!ids[..i].contains(id), "duplicate id {}", id);
+    }
+
+    // a key hashing to a taken id falls back to the next sequence number
+    let next = map.stable_id("uniforms").raw();
+    assert!(!ids.contains(&next));
+}
+
+#[test]
+fn stable_ids_carry_the_instance() {
+    let map = IdMap::<wgpu::Buffer>::deterministic().with_instance(3);
+
+    // the second id of a key collides with the first and takes a sequence id
+    let first = map.stable_id("particles");
+    let second = map.stable_id("particles");
+
+    assert_ne!(first.raw(), second.raw());
+    assert_eq!(first.instance(), 3);
+    assert_eq!(second.instance(), 3);
+}
+
+#[test]
+fn sequential_by_default() {
+    let map = IdMap::<wgpu::Buffer>::new();
+    assert!(!map.is_deterministic());
+
+    assert_eq!(ids(&map), vec![0, 1, 2, 3]);
+}
@@ -0,0 +1,240 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn increment([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] + 1u;
+    }
+}
+
+fn tiles() -> Vec<Dispatch> {
+    (0..1000)
+        .map(|i| {
+            if i % 100 == 0 {
+                Dispatch::new(1, 1, 1)
+            } else {
+                Dispatch::new(0, 1, 1)
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn skips_empty_dispatches() {
+    let mut recorded = 0;
+
+    let report = record_dispatches(&tiles(), |dispatch| {
+        assert!(!dispatch.is_empty());
+        recorded += 1;
+    });
+
+    assert_eq!(recorded, 10);
+    assert_eq!(
+        report,
+        DispatchReport {
+            recorded: 10,
+            skipped: 990,
+            statistics: None,
+        }
+    );
+    assert!(report.is_mostly_empty());
+}
+
+#[test]
+fn builder_reports_skipped_dispatches() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut values = Buffer::<Values>::new();
+    values.push(0);
+
+    let mut builder = increment::build(increment::Bindings {
+        values: &mut values,
+    });
+
+    builder.dispatch_multiple(&tiles());
+
+    assert_eq!(builder.last_dispatch_report().recorded, 10);
+    assert_eq!(builder.last_dispatch_report().skipped, 990);
+
+    assert_eq!(values.values[0], 10);
+}
+
+const WG_64: WorkGroupSize = WorkGroupSize::new(64, 1, 1);
+
+#[test]
+fn linear_dispatch_rounds_up() {
+    assert_eq!(Dispatch::try_linear(0, WG_64), Ok(Dispatch::new(0, 1, 1)));
+    assert_eq!(Dispatch::try_linear(64, WG_64), Ok(Dispatch::new(1, 1, 1)));
+    assert_eq!(Dispatch::try_linear(65, WG_64), Ok(Dispatch::new(2, 1, 1)));
+}
+
+#[test]
+fn linear_dispatch_fits_the_axis_limit_exactly() {
+    assert_eq!(
+        Dispatch::try_linear(65535 * 64, WG_64),
+        Ok(Dispatch::new(65535, 1, 1))
+    );
+    assert_eq!(
+        Dispatch::try_linear(65535 * 64 + 1, WG_64),
+        Err(DispatchTooLarge::AxisLimit {
+            axis: 'x',
+            work_groups: 65536,
+            chunked: Some(Dispatch::new(65535, 2, 1)),
+        })
+    );
+}
+
+#[test]
+fn linear_dispatch_detects_u32_overflow() {
+    // the largest count, 2^26 work groups of 64
+    assert_eq!(
+        Dispatch::try_linear(u32::MAX as u64, WG_64),
+        Err(DispatchTooLarge::AxisLimit {
+            axis: 'x',
+            work_groups: 1 << 26,
+            chunked: Some(Dispatch::new(65535, 1025, 1)),
+        })
+    );
+    assert_eq!(
+        Dispatch::try_linear(1 << 32, WG_64),
+        Err(DispatchTooLarge::Overflow {
+            axis: 'x',
+            elements: 1 << 32,
+        })
+    );
+
+    // rounded up to work groups of 7 the last invocation id is 2^32 + 2
+    assert_eq!(
+        Dispatch::try_linear(u32::MAX as u64, WorkGroupSize::new(7, 1, 1)),
+        Err(DispatchTooLarge::Overflow {
+            axis: 'x',
+            elements: u32::MAX as u64,
+        })
+    );
+}
+
+#[test]
+fn flattened_image_dispatch_doesnt_wrap() {
+    // 65536 * 65536 wraps to 0 as u32
+    let elements = 65536u64 * 65536;
+
+    assert_eq!(
+        Dispatch::try_linear(elements, WG_64),
+        Err(DispatchTooLarge::Overflow {
+            axis: 'x',
+            elements
+        })
+    );
+}
+
+#[test]
+fn extent_dispatch_checks_every_axis() {
+    let size = WorkGroupSize::new(8, 8, 1);
+
+    assert_eq!(
+        Dispatch::try_for_extent([1920, 1080, 1], size),
+        Ok(Dispatch::new(240, 135, 1))
+    );
+    assert_eq!(
+        Dispatch::try_for_extent([8, 65535 * 8, 1], size),
+        Ok(Dispatch::new(1, 65535, 1))
+    );
+    assert_eq!(
+        Dispatch::try_for_extent([8, 65535 * 8 + 1, 1], size),
+        Err(DispatchTooLarge::AxisLimit {
+            axis: 'y',
+            work_groups: 65536,
+            chunked: Some(Dispatch::new(65535, 2, 1)),
+        })
+    );
+    assert_eq!(
+        Dispatch::try_for_extent([8, 8, 1 << 32], size),
+        Err(DispatchTooLarge::Overflow {
+            axis: 'z',
+            elements: 1 << 32,
+        })
+    );
+}
+
+#[test]
+fn dispatch_too_large_converts_into_error() {
+    let err = Error::from(Dispatch::try_linear(1 << 32, WG_64).unwrap_err());
+
+    assert!(matches!(
+        err,
+        Error::Resource(ResourceError::LimitExceeded {
+            limit: "u32 invocation id",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn generated_helpers_use_the_work_group_size() {
+    assert_eq!(increment::dispatch_for(1000), Dispatch::new(1000, 1, 1));
+    assert_eq!(
+        increment::checked_dispatch(65536),
+        Err(DispatchTooLarge::AxisLimit {
+            axis: 'x',
+            work_groups: 65536,
+            chunked: Some(Dispatch::new(65535, 2, 1)),
+        })
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "`increment`: 65536 work groups along x exceed the limit of 65535 per axis, dispatch 65535x2x1"
+)]
+fn generated_helper_panics_naming_the_entry_point() {
+    increment::dispatch_for(65536);
+}
+
+#[test]
+fn dispatch_for_size_rounds_up() {
+    let size = WorkGroupSize::new(8, 8, 1);
+
+    // a 250 wide texture needs a 32nd column of work groups for its last 2 texels
+    assert_eq!(
+        Dispatch::for_size(size, (250, 250, 1)),
+        Dispatch::new(32, 32, 1)
+    );
+    assert_eq!(
+        Dispatch::for_size(size, (256, 1, 1)),
+        Dispatch::new(32, 1, 1)
+    );
+    assert_eq!(Dispatch::for_size(size, (0, 8, 1)), Dispatch::new(0, 1, 1));
+}
+
+#[test]
+#[should_panic(expected = "65536 work groups along x exceed the limit of 65535 per axis")]
+fn dispatch_for_size_panics_if_too_large() {
+    Dispatch::for_size(WorkGroupSize::new(1, 1, 1), (65536, 1, 1));
+}
+
+#[test]
+fn texture_and_buffer_dispatch_for_their_size() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let texture = Texture2d::<Rgba8Unorm>::new(250, 17);
+    assert_eq!(
+        texture.dispatch_for(WorkGroupSize::new(8, 8, 1)),
+        Dispatch::new(32, 3, 1)
+    );
+
+    let mut values = Buffer::<Values>::new();
+    values.extend(0..1000);
+    assert_eq!(values.dispatch_for(WG_64), Dispatch::new(16, 1, 1));
+}
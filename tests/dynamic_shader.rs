@@ -0,0 +1,185 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[block]]
+    struct Params {
+        factor: u32;
+    };
+
+    [[group(0), binding(0)]]
+    var<uniform> params: Params;
+}
+
+/// A filter as a plugin would provide it.
+const SOURCE: &str = "
+[[block]]
+struct Values {
+    values: array<u32>;
+};
+
+[[block]]
+struct Params {
+    factor: u32;
+};
+
+[[group(0), binding(0)]]
+var<storage, read> input: Values;
+
+[[group(0), binding(1)]]
+var<uniform> params: Params;
+
+[[group(0), binding(2)]]
+var<storage, read> unused: Values;
+
+[[group(1), binding(0)]]
+var<storage, read_write> output: Values;
+
+[[stage(compute), workgroup_size(4)]]
+fn scale([[builtin(global_invocation_id)]] id: vec3<u32>) {
+    output.values[id.x] = input.values[id.x] * params.factor;
+}
+";
+
+const LEN: u32 = 16;
+
+fn storage(read_only: bool) -> wgpu::BindingType {
+    wgpu::BindingType::Buffer {
+        ty: wgpu::BufferBindingType::Storage { read_only },
+        has_dynamic_offset: false,
+        min_binding_size: None,
+    }
+}
+
+#[test]
+fn reflects_the_entry_point() {
+    let shader = DynamicComputeShader::new(SOURCE, "scale").unwrap();
+
+    assert_eq!(shader.work_group_size(), WorkGroupSize::new(4, 1, 1));
+
+    let bindings = shader
+        .bindings()
+        .iter()
+        .map(|binding| (binding.group, binding.binding, &*binding.name, binding.ty))
+        .collect::<Vec<_>>();
+
+    // `unused` isn't used by the entry point
+    assert_eq!(
+        bindings,
+        [
+            (0, 0, "input", storage(true)),
+            (
+                0,
+                1,
+                "params",
+                wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                }
+            ),
+            (1, 0, "output", storage(false)),
+        ]
+    );
+    assert_eq!(shader.bindings()[1].min_size, 4);
+
+    let layouts = shader.bind_group_layout_descriptors();
+    assert_eq!(layouts.len(), 2);
+    assert_eq!(layouts[0].entries.len(), 2);
+    assert_eq!(layouts[1].entries.len(), 1);
+}
+
+#[test]
+fn invalid_sources_are_errors() {
+    assert!(matches!(
+        DynamicComputeShader::new("fn (", "scale"),
+        Err(SourceError::Parse(_))
+    ));
+    assert_eq!(
+        DynamicComputeShader::new(SOURCE, "blur"),
+        Err(SourceError::MissingEntryPoint(String::from("blur")))
+    );
+}
+
+#[test]
+fn dispatch_checks_bindings() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let shader = DynamicComputeShader::new(SOURCE, "scale").unwrap();
+
+    let mut input = Buffer::<Values>::new();
+    let mut output = Buffer::<Values>::new();
+
+    for i in 0..LEN {
+        input.push(i);
+        output.push(0);
+    }
+
+    let mut params = Buffer::<Params>::new();
+    params.factor = 3;
+
+    let dispatch = Dispatch::new(LEN / 4, 1, 1);
+
+    // the uniform is missing and the input added as writable
+    let report = shader
+        .dispatch(
+            DynamicBindings::new()
+                .storage(0, 0, &mut input)
+                .storage(1, 0, &mut output),
+            dispatch,
+        )
+        .unwrap_err();
+
+    assert_eq!(report.errors.len(), 2);
+    assert!(report.contains(
+        0,
+        0,
+        &DynamicBindingErrorKind::TypeMismatch {
+            expected: storage(true),
+            found: storage(false),
+        }
+    ));
+    assert!(report.contains(0, 1, &DynamicBindingErrorKind::Missing));
+    assert!(report.to_string().contains("`params`"), "{}", report);
+
+    // nothing was dispatched
+    assert!(output.values.iter().all(|&value| value == 0));
+
+    let pipelines = instance.compute_pipeline_descriptors.len();
+
+    for _ in 0..3 {
+        shader
+            .dispatch(
+                DynamicBindings::new()
+                    .storage_read(0, 0, &input)
+                    .uniform(0, 1, &params)
+                    .storage_read(0, 2, &input)
+                    .storage(1, 0, &mut output),
+                dispatch,
+            )
+            .unwrap();
+    }
+
+    // the unused resource isn't part of the layout, so a raw shader of the
+    // same source shares the pipeline
+    RawComputeShader::new(SOURCE, "scale").dispatch(
+        DynamicBindings::new()
+            .storage_read(0, 0, &input)
+            .uniform(0, 1, &params)
+            .storage(1, 0, &mut output),
+        dispatch,
+    );
+
+    assert_eq!(instance.compute_pipeline_descriptors.len(), pipelines + 1);
+
+    for i in 0..LEN {
+        assert_eq!(output.values[i as usize], i * 3);
+    }
+}
@@ -0,0 +1,144 @@
+use shatter::*;
+
+fn boxed(err: Error) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    Err(err)?
+}
+
+#[test]
+fn init_errors_are_matchable() {
+    // no adapter supports every feature, so this fails either way
+    let result = pollster::block_on(Instance::initialize(&InstanceDescriptor {
+        features: wgpu::Features::all(),
+        ..Default::default()
+    }));
+
+    match result {
+        Err(Error::Init(InitError::NoAdapter)) => {}
+        Err(Error::Init(InitError::RequestDevice(_))) => {}
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("an adapter supports every feature"),
+    }
+}
+
+#[test]
+fn unmatched_adapter_filters_list_adapters() {
+    let result = pollster::block_on(Instance::initialize(&InstanceDescriptor {
+        adapter_name_filter: Some(String::from("no such adapter")),
+        ..Default::default()
+    }));
+
+    match result {
+        Err(Error::Init(InitError::NoAdapter)) => {}
+        Err(Error::Init(ref err @ InitError::NoMatchingAdapter { ref available, .. })) => {
+            let message = err.to_string();
+
+            assert!(message.contains("`no such adapter`"), "{}", message);
+
+            for info in available {
+                assert!(message.contains(&info.name), "{}", message);
+            }
+        }
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("an adapter matches the filter"),
+    }
+}
+
+#[test]
+fn failed_downloads_report_device_lost() {
+    let err = Error::from(wgpu::BufferAsyncError);
+
+    assert!(matches!(err, Error::Device(DeviceError::Lost)));
+    assert_eq!(err.to_string(), "device lost");
+    assert!(boxed(err).is_err());
+}
+
+#[test]
+fn single_registry_errors_are_flattened() {
+    let missing = MissingResource {
+        errors: vec![RegistryError {
+            field: "particles",
+            name: "enemies".into(),
+            kind: RegistryErrorKind::Missing,
+        }],
+    };
+
+    match Error::from(missing) {
+        Error::Resource(ResourceError::MissingBinding { binding, name }) => {
+            assert_eq!(binding, "particles");
+            assert_eq!(name, "enemies");
+        }
+        err => panic!("unexpected error: {}", err),
+    }
+
+    let conflict = MissingResource {
+        errors: vec![RegistryError {
+            field: "a",
+            name: "shared".into(),
+            kind: RegistryErrorKind::Conflict { other: "b" },
+        }],
+    };
+
+    assert!(matches!(
+        Error::from(conflict),
+        Error::Resource(ResourceError::Aliasing { a: "a", b: "b" })
+    ));
+}
+
+#[test]
+fn limits_are_matchable() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    match instance.check_buffer_size(u64::MAX) {
+        Err(Error::Resource(ResourceError::LimitExceeded {
+            limit, requested, ..
+        })) => {
+            assert_eq!(limit, "max_storage_buffer_binding_size");
+            assert_eq!(requested, u64::MAX);
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    let limits = instance.cache_limits();
+    instance.set_cache_limits(CacheLimits {
+        max_shader_modules: Some(instance.shader_module_count()),
+        ..limits
+    });
+
+    let result = instance.try_get_shader_module("// never cached\n");
+    instance.set_cache_limits(limits);
+
+    match result.map_err(Error::from) {
+        Err(Error::Resource(ResourceError::LimitExceeded { limit, .. })) => {
+            assert_eq!(limit, "max_shader_modules");
+        }
+        result => panic!("unexpected result: {:?}", result.map(|_| ())),
+    }
+}
+
+#[test]
+fn device_errors_are_captured_by_scopes() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let result = instance.error_scope(|| {
+        instance.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            // mappable buffers can't be storage buffers
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        })
+    });
+
+    assert!(matches!(
+        result,
+        Err(Error::Device(DeviceError::Validation(_)))
+    ));
+
+    assert!(instance.error_scope(|| ()).is_ok());
+}
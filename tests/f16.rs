@@ -0,0 +1,84 @@
+#![cfg(feature = "f16")]
+
+use shatter::{texture_format::Rgba16Float, texture_view_dimension::TextureStorageD2, *};
+
+wgsl! {
+    [[group(0), binding(0)]]
+    var input: texture_storage_2d<rgba16float, read>;
+
+    [[group(0), binding(1)]]
+    var output: texture_storage_2d<rgba16float, write>;
+
+    [[stage(compute), workgroup_size(8, 8)]]
+    fn pass_through([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        let coords = vec2<i32>(id.xy);
+        textureStore(output, coords, textureLoad(input, coords));
+    }
+}
+
+fn pixel(x: usize, y: usize) -> Rgba32 {
+    Rgba32::rgba(x as f32 * 0.1, y as f32 * -0.25, 1.0 / 3.0, 1.0)
+}
+
+fn assert_close(a: Rgba32, b: Rgba32) {
+    let a: [f32; 4] = a.into();
+    let b: [f32; 4] = b.into();
+
+    for (a, b) in a.iter().zip(&b) {
+        // f16 has 11 bits of precision
+        assert!((a - b).abs() <= a.abs().max(1.0) / 1024.0, "{} != {}", a, b);
+    }
+}
+
+#[test]
+fn conversions() {
+    let color = Rgba16F::from(pixel(3, 5));
+
+    assert_eq!(color.g, f16::from_f32(-1.25));
+    assert_close(Rgba32::from(color), pixel(3, 5));
+
+    assert_eq!(R32::from(R16F::r(f16::ONE)), R32::r(1.0));
+    assert_eq!(
+        Rg16F::from(Rg32::rg(0.5, 2.0)),
+        Rg16F::rg(f16::from_f32(0.5), f16::from_f32(2.0))
+    );
+}
+
+#[test]
+fn texels_are_half_precision() {
+    // 32 texels fill a 256 byte row, so there's no row padding
+    let storage = TextureStorageD2::<Rgba16F>::new(32, 4);
+    assert_eq!(storage.size(), 32 * 4 * 8);
+
+    let _: <Rgba16Float as TextureFormat>::Data = Rgba16F::WHITE;
+}
+
+#[test]
+fn round_trip() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut input = Texture2d::<Rgba16Float>::new(16, 16);
+    let mut output = Texture2d::<Rgba16Float>::new(16, 16);
+
+    for y in 0..16 {
+        for x in 0..16 {
+            input[(x, y)] = pixel(x, y).into();
+        }
+    }
+
+    pass_through(
+        pass_through::Bindings {
+            input: &input,
+            output: &mut output,
+        },
+        Dispatch::new(2, 2, 1),
+    );
+
+    for y in 0..16 {
+        for x in 0..16 {
+            assert_close(output[(x, y)].into(), pixel(x, y));
+        }
+    }
+}
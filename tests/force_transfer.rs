@@ -0,0 +1,108 @@
+use std::sync::{Arc, Mutex};
+
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Weights {
+        weights: array<f32>;
+    };
+
+    [[block]]
+    struct Values {
+        values: array<f32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read> weights: Weights;
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn accumulate([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] + weights.weights[id.x];
+    }
+}
+
+/// Accumulates for a few frames, touching the values on the CPU in between.
+fn run() -> Vec<f32> {
+    let mut weights = Buffer::<Weights>::new();
+    weights.extend((0..64).map(|i| i as f32));
+
+    let mut values = Buffer::<Values>::new();
+    values.extend_from_slice(&[1.0; 64]);
+
+    for frame in 0..4 {
+        accumulate(
+            accumulate::Bindings {
+                weights: &weights,
+                values: &mut values,
+            },
+            Dispatch::new(1, 1, 1),
+        );
+
+        if frame == 1 {
+            values.write().values[0] = 0.0;
+        }
+    }
+
+    values.read().values.to_vec()
+}
+
+// the toggles are global, so everything runs in a single test
+#[test]
+fn forcing_transfers_keeps_results() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let transfers = Arc::new(Mutex::new(Vec::new()));
+    let hook_transfers = transfers.clone();
+    instance.set_forced_transfer_hook(move |transfer| {
+        hook_transfers.lock().unwrap().push(transfer.clone())
+    });
+
+    let expected = run();
+    assert!(transfers.lock().unwrap().is_empty());
+
+    instance.set_force_upload(true);
+    instance.set_force_download(true);
+
+    assert_eq!(run(), expected);
+
+    instance.set_force_upload(false);
+    instance.set_force_download(false);
+    instance.clear_forced_transfer_hook();
+
+    let transfers = transfers.lock().unwrap();
+
+    // the weights never change, so every upload after the first is forced
+    let weight_uploads = transfers
+        .iter()
+        .filter(|transfer| {
+            transfer.direction == TransferDirection::Upload && transfer.label.contains("Weights")
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(weight_uploads.len(), 3);
+    assert!(weight_uploads
+        .iter()
+        .all(|transfer| transfer.bytes == 64 * 4));
+
+    let value_downloads = transfers
+        .iter()
+        .filter(|transfer| {
+            transfer.direction == TransferDirection::Download && transfer.label.contains("Values")
+        })
+        .count();
+    assert_eq!(value_downloads, 4);
+
+    assert_eq!(
+        weight_uploads[0].to_string(),
+        format!(
+            "forced upload of 256 bytes of Buffer<{}>",
+            std::any::type_name::<Weights>()
+        )
+    );
+}
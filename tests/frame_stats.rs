@@ -0,0 +1,114 @@
+use shatter::{util::FrameReadbackQueue, *};
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn double([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&values.values)) {
+            return;
+        }
+
+        values.values[id.x] = values.values[id.x] * 2u;
+    }
+}
+
+#[test]
+fn cache_hit_rate() {
+    let frame = StatsFrame {
+        cache_hits: 3,
+        cache_misses: 1,
+        ..Default::default()
+    };
+
+    assert_eq!(frame.cache_hit_rate(), Some(0.75));
+    assert_eq!(StatsFrame::default().cache_hit_rate(), None);
+}
+
+// the only test touching the instance, so the counters only see these frames
+#[test]
+fn deltas_cover_activity_since_the_last_frame() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let start = instance.stats_frame();
+
+    // frame 1: a new buffer, uploaded and dispatched over
+    let mut values = Buffer::<Values>::new();
+
+    for i in 0..256 {
+        values.push(i);
+    }
+
+    double(
+        double::Bindings {
+            values: &mut values,
+        },
+        Dispatch::new(4, 1, 1),
+    );
+
+    let frame = instance.stats_frame();
+
+    // growing the buffer may leave the initial one around until the next clean
+    assert!(frame.live_buffers > start.live_buffers);
+    assert!(frame.resident_bytes >= start.resident_bytes + 256 * 4);
+    assert_eq!(frame.uploaded_bytes, 256 * 4);
+    assert_eq!(frame.downloaded_bytes, 0);
+    assert_eq!(frame.dispatches, 1);
+    assert!(frame.submissions > 0);
+    assert!(frame.cache_misses > 0);
+    assert!(frame.live_pipelines > start.live_pipelines);
+
+    // frame 2: nothing happens, only the live counts carry over
+    let idle = instance.stats_frame();
+
+    assert_eq!(
+        idle,
+        StatsFrame {
+            live_buffers: frame.live_buffers,
+            live_textures: frame.live_textures,
+            live_bind_groups: frame.live_bind_groups,
+            live_pipelines: frame.live_pipelines,
+            resident_bytes: frame.resident_bytes,
+            frames_in_flight: frame.frames_in_flight,
+            ..Default::default()
+        }
+    );
+
+    // frame 3: the same dispatch hits every cache, the result is read and a
+    // frame is left in flight
+    double(
+        double::Bindings {
+            values: &mut values,
+        },
+        Dispatch::new(4, 1, 1),
+    );
+
+    assert_eq!(values.values[255], 255 * 4);
+
+    let texture = Texture2d::<Rgba8Unorm>::new(4, 4);
+    let mut queue = FrameReadbackQueue::new(2);
+    assert!(queue.submit(&texture).is_none());
+
+    let frame = instance.stats_frame();
+
+    assert_eq!(frame.dispatches, 1);
+    assert_eq!(frame.cache_misses, 0);
+    assert!(frame.cache_hits > 0);
+    // once before dispatching over the GPU writes of frame 1, once to read
+    assert_eq!(frame.downloaded_bytes, 2 * 256 * 4);
+    assert_eq!(frame.live_textures, idle.live_textures + 1);
+    assert_eq!(frame.frames_in_flight, 1);
+
+    drop(queue);
+
+    assert_eq!(instance.stats_frame().frames_in_flight, 0);
+}
@@ -0,0 +1,45 @@
+use shatter::*;
+
+#[test]
+fn wraps_existing_devices() {
+    let global = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let wgpu_instance = wgpu::Instance::new(wgpu::Backends::all());
+    let adapter = pollster::block_on(wgpu_instance.request_adapter(&Default::default())).unwrap();
+    let (device, queue) =
+        pollster::block_on(adapter.request_device(&Default::default(), None)).unwrap();
+
+    let instance = Instance::from_device(
+        wgpu_instance,
+        adapter,
+        device,
+        queue,
+        &InstanceDescriptor::default(),
+    );
+
+    // errors of the wrapped device are captured like those of created ones
+    let result = instance.error_scope(|| {
+        instance.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        })
+    });
+
+    assert!(matches!(
+        result,
+        Err(Error::Device(DeviceError::Validation(_)))
+    ));
+
+    // the global instance already exists, so it's kept
+    match Instance::set_global(instance) {
+        Ok(_) => panic!("replaced the global instance"),
+        Err(rejected) => assert!(!std::ptr::eq(&*rejected, global)),
+    }
+
+    assert!(std::ptr::eq(Instance::global(), global));
+}
@@ -0,0 +1,106 @@
+use shatter::{test_util::*, *};
+
+// the kernel of the mandelbrot example
+wgsl! {
+    [[group(0), binding(0)]]
+    var texture: texture_storage_2d<rgba8unorm, write>;
+
+    [[block]]
+    struct Uniforms {
+        position: vec2<f32>;
+        zoom: f32;
+    };
+
+    [[group(0), binding(1)]]
+    var<uniform> uniforms: Uniforms;
+
+    let SCALE = 4.0;
+    let AA = 3;
+
+    [[stage(compute), workgroup_size(8, 8, 1)]]
+    fn mandelbrot([[builtin(global_invocation_id)]] param: vec3<u32>) {
+        var color = vec3<f32>(0.0);
+
+        for (var m = 0; m < AA; m = m + 1) {
+            let x_offset = f32(m) / f32(AA) - 0.5;
+
+            for (var n = 0; n < AA; n = n + 1) {
+                let y_offset = f32(n) / f32(AA) - 0.5;
+
+                let size = textureDimensions(texture);
+
+                var x = (f32(param.x) + x_offset) / f32(size.x) * SCALE - SCALE / 2.0;
+                var y = (f32(param.y) + y_offset) / f32(size.y) * SCALE - SCALE / 2.0;
+
+                x = x / uniforms.zoom - uniforms.position.x;
+                y = y / uniforms.zoom - uniforms.position.y;
+
+                var l = 0.0;
+                var z = vec2<f32>(0.0);
+                for (var i = 0; i < 512; i = i + 1) {
+                    z = vec2<f32>(
+                        z.x * z.x - z.y * z.y + x,
+                        z.y * z.x + z.x * z.y + y,
+                    );
+
+                    if (dot(z, z) > pow(256.0, 2.0)) {
+                        break;
+                    }
+
+                    l = l + 1.0;
+                }
+
+                if (l > 511.0) {
+                    l = 0.0;
+                }
+
+                let smooth = l - log2(log2(dot(z, z))) + 4.0;
+
+                let sub_color = 0.5 + 0.5 * cos(3.0 + smooth * 0.15 + vec3<f32>(0.0, 0.6, 1.0));
+                color = color + sub_color;
+            }
+        }
+
+        color = color / f32(AA * AA);
+
+        let out_color = vec4<f32>(color, 1.0);
+
+        textureStore(texture, vec2<i32>(param.xy), out_color);
+    }
+}
+
+const SIZE: usize = 128;
+
+#[test]
+fn mandelbrot_frame() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut texture = Texture2d::<Rgba8Unorm>::new(SIZE, SIZE);
+    let mut uniforms = Buffer::<Uniforms>::new();
+
+    uniforms.position = Vec2::new(0.745, 0.186);
+    uniforms.zoom = 1.0;
+
+    let dispatch = texture.dispatch_for(mandelbrot::WORK_GROUP_SIZE);
+    mandelbrot(
+        mandelbrot::Bindings {
+            texture: &mut texture,
+            uniforms: &uniforms,
+        },
+        dispatch,
+    );
+
+    // escape times near the set boundary are sensitive to float precision
+    let tolerance = ImageTolerance {
+        channel_epsilon: 4,
+        max_differing_fraction: 0.01,
+    };
+
+    assert_image_matches(
+        &texture,
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/mandelbrot.png"),
+        tolerance,
+    );
+}
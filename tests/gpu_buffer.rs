@@ -0,0 +1,100 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<f32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read> input: Values;
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> scratch: Values;
+
+    [[group(0), binding(2)]]
+    var<storage, read_write> output: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn double([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        scratch.values[id.x] = input.values[id.x] * 2.0;
+    }
+
+    [[stage(compute), workgroup_size(64)]]
+    fn increment([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        output.values[id.x] = scratch.values[id.x] + 1.0;
+    }
+}
+
+const LEN: usize = 256;
+
+#[test]
+fn chain_through_gpu_buffer() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut input = Buffer::<Values>::new();
+    let mut scratch = GpuBuffer::<Values>::with_len(LEN);
+    let mut output = Buffer::<Values>::new();
+
+    for i in 0..LEN {
+        input.push(i as f32);
+        output.push(0.0);
+    }
+
+    let dispatch = input.dispatch_for(double::WORK_GROUP_SIZE);
+
+    let mut builder = double::build(double::Bindings {
+        input: &input,
+        scratch: &mut scratch,
+    });
+    builder.dispatch(dispatch);
+
+    // only the input crossed the bus
+    assert_transfers!(
+        builder.last_transfer_report(),
+        uploaded_bytes == (LEN * 4) as u64,
+        downloaded_bytes == 0,
+    );
+
+    let mut builder = increment::build(increment::Bindings {
+        scratch: &scratch,
+        output: &mut output,
+    });
+    builder.dispatch(dispatch);
+
+    assert_transfers!(
+        builder.last_transfer_report(),
+        uploaded_bytes == 0,
+        downloaded_bytes == 0,
+    );
+
+    for i in 0..LEN {
+        assert_eq!(output.values[i], i as f32 * 2.0 + 1.0);
+    }
+
+    let scratch = scratch.to_cpu();
+    assert_eq!(scratch.len(), LEN);
+    assert_eq!(scratch.values[3], 6.0);
+}
+
+#[test]
+fn round_trip() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut buffer = Buffer::<Values>::new();
+
+    for i in 0..LEN {
+        buffer.push(i as f32);
+    }
+
+    let gpu_buffer = buffer.into_gpu_only();
+    assert_eq!(gpu_buffer.size(), (LEN * 4) as u64);
+
+    let buffer = gpu_buffer.to_cpu();
+    assert_eq!(buffer.len(), LEN);
+    assert_eq!(buffer.values[LEN - 1], (LEN - 1) as f32);
+}
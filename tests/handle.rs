@@ -0,0 +1,73 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Counter {
+        value: i32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> counter: Counter;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn increment() {
+        counter.value = counter.value + 1;
+    }
+}
+
+#[test]
+fn handle_outlives_buffer() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut buffer = Buffer::<Counter>::new();
+    buffer.value = 1;
+
+    let handle = buffer.into_handle();
+    let other = handle.clone();
+    drop(handle);
+
+    let resource = other.resolve().unwrap();
+    let mut counter = resource.lock().unwrap();
+
+    increment(
+        increment::Bindings {
+            counter: &mut *counter,
+        },
+        Dispatch::new(1, 1, 1),
+    );
+
+    assert_eq!(counter.value, 2);
+}
+
+#[test]
+fn destroy_invalidates_handles() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let handle = Buffer::<Counter>::new().into_handle();
+    let other = handle.clone();
+
+    assert!(Instance::global().destroy(&handle));
+    assert!(handle.resolve().is_none());
+    assert!(other.resolve().is_none());
+    assert!(!Instance::global().destroy(&other));
+}
+
+#[test]
+fn reused_slots_do_not_alias() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let old = Buffer::<Counter>::new().into_handle();
+    Instance::global().destroy(&old);
+
+    let new = Buffer::<Counter>::new().into_handle();
+
+    assert_ne!(old, new);
+    assert!(old.resolve().is_none());
+    assert!(new.resolve().is_some());
+}
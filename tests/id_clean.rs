@@ -0,0 +1,121 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn increment([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] + 1u;
+    }
+}
+
+#[test]
+fn clean_visits_only_dropped_ids() {
+    let map = IdMap::<u32>::new().with_drop_queue();
+
+    let ids = (0..100_000)
+        .map(|i| {
+            let id = map.next_id();
+            map.insert(id.clone(), i);
+            id
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(map.pending_drops(), 0);
+    assert!(map.clean().is_empty());
+
+    let (dropped, kept): (Vec<_>, Vec<_>) = ids.into_iter().partition(|id| id.raw() % 10_000 == 0);
+    let mut expected = dropped.iter().map(Id::raw).collect::<Vec<_>>();
+    drop(dropped);
+
+    assert_eq!(map.pending_drops(), 10);
+
+    let mut removed = map.clean();
+    removed.sort_unstable();
+    expected.sort_unstable();
+
+    assert_eq!(removed, expected);
+    assert_eq!(map.pending_drops(), 0);
+    assert_eq!(map.live(), kept.len());
+}
+
+#[test]
+fn clean_keeps_ids_referenced_again() {
+    let map = IdMap::<u32>::new().with_drop_queue();
+
+    let id = map.next_id();
+    map.insert(id.clone(), 0);
+    drop(id);
+
+    // the key is cloned out of the map after the drop was queued
+    let key = map.iter().next().unwrap().key().clone();
+
+    assert!(map.clean().is_empty());
+    assert_eq!(map.live(), 1);
+
+    drop(key);
+
+    assert_eq!(map.clean().len(), 1);
+    assert_eq!(map.live(), 0);
+}
+
+#[test]
+fn maps_without_drop_queue_are_not_cleaned() {
+    let map = IdMap::<u32>::new();
+
+    let id = map.next_id();
+    map.insert(id.clone(), 0);
+    drop(id);
+
+    assert_eq!(map.pending_drops(), 0);
+    assert!(map.clean().is_empty());
+    assert_eq!(map.live(), 1);
+}
+
+// the maps are global, so everything runs in a single test
+#[test]
+fn dropped_buffers_are_cleaned_across_frames() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let frame = |i: u32| {
+        let mut values = Buffer::<Values>::new();
+        values.extend_from_slice(&[i; 64]);
+
+        increment(
+            increment::Bindings {
+                values: &mut values,
+            },
+            Dispatch::new(1, 1, 1),
+        );
+
+        assert_eq!(values.as_slice(), &[i + 1; 64]);
+    };
+
+    // settle the buffers and bind groups created by the first frames
+    for i in 0..CLEAN_INTERVAL * 2 {
+        frame(i);
+    }
+
+    instance.clean();
+    let buffers = instance.buffers.live();
+    let bind_groups = instance.bind_groups.len();
+
+    for i in 0..100_000 {
+        frame(i);
+    }
+
+    instance.clean();
+
+    assert_eq!(instance.buffers.live(), buffers);
+    assert_eq!(instance.bind_groups.len(), bind_groups);
+    assert_eq!(instance.buffers.pending_drops(), 0);
+}
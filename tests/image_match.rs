@@ -0,0 +1,155 @@
+use std::{env, fs, path::PathBuf};
+
+use shatter::test_util::*;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("shatter_image_match_{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn gradient(width: u32, height: u32) -> Vec<u8> {
+    (0..height)
+        .flat_map(|y| (0..width).flat_map(move |x| [x as u8, y as u8, (x ^ y) as u8, 255]))
+        .collect()
+}
+
+#[test]
+fn png_round_trip() {
+    let dir = temp_dir("round_trip");
+
+    // larger than a single uncompressed deflate block
+    let (width, height) = (200, 150);
+    let rgba = gradient(width, height);
+
+    write_png(dir.join("gradient.png"), width, height, &rgba).unwrap();
+
+    let (read_width, read_height, read) = read_png(dir.join("gradient.png")).unwrap();
+    assert_eq!((read_width, read_height), (width, height));
+    assert_eq!(read, rgba);
+}
+
+#[test]
+fn within_tolerance() {
+    let dir = temp_dir("within_tolerance");
+    let golden = dir.join("golden.png");
+
+    let (width, height) = (64, 64);
+    let rgba = gradient(width, height);
+    write_png(&golden, width, height, &rgba).unwrap();
+
+    // every pixel slightly off, and two pixels far off
+    let mut actual = rgba.iter().map(|c| c.saturating_add(1)).collect::<Vec<_>>();
+    actual[0] = 200;
+    actual[4 * 100] = 200;
+
+    let tolerance = ImageTolerance {
+        channel_epsilon: 1,
+        max_differing_fraction: 2.0 / (64.0 * 64.0),
+    };
+
+    compare_image(width, height, &actual, &golden, tolerance).unwrap();
+
+    assert!(compare_image(width, height, &actual, &golden, ImageTolerance::EXACT).is_err());
+}
+
+#[test]
+fn mismatch_writes_actual_and_heatmap() {
+    let dir = temp_dir("mismatch");
+    let golden = dir.join("golden.png");
+
+    let (width, height) = (16, 16);
+    let rgba = gradient(width, height);
+    write_png(&golden, width, height, &rgba).unwrap();
+
+    let mut actual = rgba.clone();
+    for texel in actual.chunks_exact_mut(4).take(32) {
+        texel[1] = texel[1].wrapping_add(100);
+    }
+
+    let err =
+        compare_image(width, height, &actual, &golden, ImageTolerance::default()).unwrap_err();
+
+    match err {
+        ImageMismatch::Pixels {
+            diff,
+            actual: actual_path,
+            heatmap,
+            ..
+        } => {
+            assert_eq!(diff.pixels, 256);
+            assert_eq!(diff.differing, 32);
+            assert_eq!(diff.max_channel_diff, 100);
+
+            assert_eq!(actual_path, dir.join("golden.actual.png"));
+            assert_eq!(read_png(&actual_path).unwrap().2, actual);
+
+            let (_, _, heatmap) = read_png(&heatmap).unwrap();
+            assert!(
+                heatmap[0] > 0 && heatmap[2] == 0,
+                "differing pixels are red"
+            );
+            assert_eq!(heatmap[4 * 32..4 * 33], [0, 0, 0, 255]);
+        }
+        err => panic!("unexpected mismatch: {}", err),
+    }
+}
+
+#[test]
+fn missing_golden() {
+    // blessing writes the golden instead
+    if env::var_os("SHATTER_BLESS").is_some() {
+        return;
+    }
+
+    let dir = temp_dir("missing");
+    let golden = dir.join("golden.png");
+
+    let err = compare_image(4, 4, &gradient(4, 4), &golden, ImageTolerance::default()).unwrap_err();
+
+    assert!(matches!(err, ImageMismatch::Golden { .. }));
+    assert!(
+        !golden.exists(),
+        "missing goldens are only written when blessing"
+    );
+}
+
+#[test]
+fn size_mismatch() {
+    let dir = temp_dir("size");
+    let golden = dir.join("golden.png");
+
+    write_png(&golden, 8, 8, &gradient(8, 8)).unwrap();
+
+    let err = compare_image(4, 4, &gradient(4, 4), &golden, ImageTolerance::default()).unwrap_err();
+
+    assert!(matches!(
+        err,
+        ImageMismatch::Size {
+            expected: (8, 8),
+            found: (4, 4),
+        }
+    ));
+}
+
+#[test]
+fn diff_heatmap() {
+    let golden = [10, 10, 10, 255, 10, 10, 10, 255];
+    let actual = [11, 10, 10, 255, 10, 60, 10, 255];
+
+    let (diff, heatmap) = diff_rgba8(&actual, &golden, 2);
+
+    assert_eq!(
+        diff,
+        ImageDiff {
+            pixels: 2,
+            differing: 1,
+            max_channel_diff: 50,
+        }
+    );
+
+    // within the epsilon is blue, outside red
+    assert!(heatmap[2] > 0 && heatmap[0] == 0);
+    assert!(heatmap[4] > 0 && heatmap[6] == 0);
+}
@@ -0,0 +1,131 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<f32>;
+    };
+
+    [[block]]
+    struct Counters {
+        survivors: u32;
+    };
+
+    [[block]]
+    struct Params {
+        threshold: f32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read> input: Values;
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> survivors: Values;
+
+    [[group(0), binding(2)]]
+    var<storage, read_write> counters: Counters;
+
+    [[group(0), binding(3)]]
+    var<uniform> params: Params;
+
+    // compacts the values above the threshold
+    [[stage(compute), workgroup_size(1)]]
+    fn cull() {
+        let len = arrayLength(&input.values);
+        var count: u32 = 0u;
+
+        for (var i: u32 = 0u; i < len; i = i + 1u) {
+            let value = input.values[i];
+
+            if (value > params.threshold) {
+                survivors.values[count] = value;
+                count = count + 1u;
+            }
+        }
+
+        counters.survivors = count;
+    }
+
+    [[stage(compute), workgroup_size(64)]]
+    fn process([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&survivors.values)) {
+            return;
+        }
+
+        survivors.values[id.x] = survivors.values[id.x] * 2.0;
+    }
+}
+
+const COUNT: usize = 200;
+
+fn cull_then_process(threshold: f32) -> Vec<f32> {
+    let mut input = Buffer::<Values>::new();
+    let mut survivors = Buffer::<Values>::new();
+    let mut counters = GpuBuffer::<Counters>::new();
+    let mut params = Buffer::<Params>::new();
+
+    for i in 0..COUNT {
+        input.push(i as f32);
+        survivors.push(0.0);
+    }
+
+    params.threshold = threshold;
+
+    cull(
+        cull::Bindings {
+            input: &input,
+            survivors: &mut survivors,
+            counters: &mut counters,
+            params: &params,
+        },
+        Dispatch::new(1, 1, 1),
+    );
+
+    let max_dispatch = Dispatch::new(COUNT.div_ceil(64) as u32, 1, 1);
+    let offset = Counters::LAYOUT.field("survivors").unwrap().offset as usize;
+
+    let mut builder = process::build(process::Bindings {
+        survivors: &mut survivors,
+    });
+
+    builder.dispatch_if_nonzero(&counters, offset, max_dispatch);
+
+    // the counter never leaves the GPU
+    assert_eq!(builder.last_transfer_report().downloaded_bytes, 0);
+
+    survivors.values.to_vec()
+}
+
+#[test]
+fn dispatch_if_nonzero() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let values = cull_then_process(149.5);
+
+    for (i, value) in values.iter().enumerate() {
+        if i < 50 {
+            assert_eq!(*value, (150 + i) as f32 * 2.0);
+        } else {
+            assert_eq!(*value, 0.0);
+        }
+    }
+
+    // nothing survives, the dispatch is empty
+    let values = cull_then_process(1000.0);
+    assert!(values.iter().all(|value| *value == 0.0));
+
+    // the parameter buffers don't outlive a clean
+    let instance = Instance::global();
+    assert!(!instance
+        .snapshot()
+        .entries("dispatch_args_params")
+        .is_empty());
+
+    instance.clean();
+    assert!(instance
+        .snapshot()
+        .entries("dispatch_args_params")
+        .is_empty());
+}
@@ -0,0 +1,82 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn double([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] * 2u;
+    }
+}
+
+fn values_in(instance: &'static Instance, len: u32) -> Buffer<Values> {
+    let mut values = Buffer::<Values>::new_in(instance);
+
+    for i in 0..len {
+        values.push(i);
+    }
+
+    values
+}
+
+#[test]
+fn explicit_instances() {
+    let global = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let instance = Instance::create(&InstanceDescriptor::default()).unwrap();
+
+    assert!(global.is_global());
+    assert!(!instance.is_global());
+    assert_ne!(instance.id(), global.id());
+
+    // resources remember the instance they were created in
+    let mut values = values_in(instance, 4);
+    assert!(std::ptr::eq(values.instance(), instance));
+    assert_eq!(values.id().instance(), instance.id());
+
+    let texture = Texture2d::<Rgba8Unorm>::new_in(instance, 4, 4);
+    assert!(std::ptr::eq(texture.instance(), instance));
+    assert_eq!(texture.texture_id().instance(), instance.id());
+
+    let global_bind_groups = global.bind_group_count();
+
+    double::build(double::Bindings {
+        values: &mut values,
+    })
+    .instance(instance)
+    .dispatch(Dispatch::new(4, 1, 1));
+
+    assert_eq!(values.into_contents(), vec![0, 2, 4, 6]);
+    assert_eq!(global.bind_group_count(), global_bind_groups);
+
+    // binding a resource of another instance panics instead of reading garbage
+    let mut foreign = values_in(instance, 4);
+
+    let error = panic::catch_unwind(AssertUnwindSafe(|| {
+        double(
+            double::Bindings {
+                values: &mut foreign,
+            },
+            Dispatch::new(4, 1, 1),
+        );
+    }))
+    .unwrap_err();
+
+    let message = error.downcast_ref::<String>().unwrap();
+    assert!(
+        message.contains("can't be shared between instances"),
+        "{}",
+        message
+    );
+}
@@ -0,0 +1,62 @@
+use std::thread;
+
+use shatter::*;
+
+wgsl! {
+    #![max_type_size = 134217728]
+
+    // 64 MiB, far larger than any thread's stack
+    [[block]]
+    struct Field {
+        values: array<vec4<f32>, 4194304>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> field: Field;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn clear([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        field.values[id.x] = vec4<f32>(0.0);
+    }
+}
+
+const SMALL_STACK: usize = 256 * 1024;
+
+#[test]
+fn alloc_in_place() {
+    thread::Builder::new()
+        .stack_size(SMALL_STACK)
+        .spawn(|| {
+            assert_eq!(<Field as BufferData>::size(&()), 64 * 1024 * 1024);
+
+            let ptr = unsafe { <Field as BufferData>::alloc() };
+
+            // the default is zeroed
+            let field = unsafe { &*<Field as BufferData>::as_ptr(ptr, &()) };
+            assert_eq!(field.values[4194303], Vec4::default());
+
+            unsafe { <Field as BufferData>::dealloc(ptr, &()) };
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn large_buffer() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    thread::Builder::new()
+        .stack_size(SMALL_STACK)
+        .spawn(|| {
+            let mut buffer = Buffer::<Field>::new();
+
+            buffer.values[7] = Vec4::new(1.0, 2.0, 3.0, 4.0);
+            assert_eq!(buffer.values[7], Vec4::new(1.0, 2.0, 3.0, 4.0));
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
@@ -0,0 +1,165 @@
+use std::{alloc::Layout, any, ptr::NonNull, sync::Mutex};
+
+use shatter::{
+    debug::alloc_registry::{self, LeakCheck, ReportKind},
+    *,
+};
+
+wgsl! {
+    [[block]]
+    struct Particles {
+        count: u32;
+        positions: array<vec2<f32>>;
+    };
+
+    [[block]]
+    struct Settings {
+        gravity: vec2<f32>;
+        drag: f32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> particles: Particles;
+
+    [[group(0), binding(1)]]
+    var<uniform> settings: Settings;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn fall([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x < particles.count) {
+            particles.positions[id.x] = particles.positions[id.x] + settings.gravity * settings.drag;
+        }
+    }
+}
+
+/// The lifecycles allocate the same types, so one's live allocations aren't
+/// the other's leaks.
+static LIFECYCLES: Mutex<()> = Mutex::new(());
+
+/// Reports of the generated types of this file.
+fn generated_reports(check: &LeakCheck) -> Vec<alloc_registry::AllocReport> {
+    let generated =
+        |name: &str| name == any::type_name::<Particles>() || name == any::type_name::<Settings>();
+
+    alloc_registry::reports()
+        .into_iter()
+        .chain(check.leaks())
+        .filter(|report| generated(report.type_name))
+        .collect()
+}
+
+#[test]
+fn generated_impls_report_nothing() {
+    let _lock = LIFECYCLES.lock().unwrap_or_else(|err| err.into_inner());
+    let check = LeakCheck::new();
+
+    unsafe {
+        let mut ptr = Particles::alloc();
+        let mut state = Particles::init();
+
+        for i in 0..100 {
+            Particles::push(&mut ptr, &mut state, Vec2::new(i as f32, 0.0));
+        }
+
+        Particles::reserve(&mut ptr, &mut state, 1000);
+
+        // tracked through the reallocations
+        let live = check.leaks();
+        assert!(live.iter().any(|leak| leak.ptr == ptr.as_ptr() as usize));
+        assert_eq!(Particles::pop(ptr, &mut state), Some(Vec2::new(99.0, 0.0)));
+        Particles::dealloc(ptr, &state);
+
+        let (ptr, state) = Particles::alloc_zeroed(4096);
+        Particles::dealloc(ptr, &state);
+
+        let settings = Settings::alloc();
+        Settings::dealloc(settings, &Settings::init());
+    }
+
+    assert_eq!(generated_reports(&check), []);
+}
+
+#[test]
+fn buffer_lifecycles_report_nothing() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let _lock = LIFECYCLES.lock().unwrap_or_else(|err| err.into_inner());
+    let check = LeakCheck::new();
+
+    {
+        let mut particles = Buffer::<Particles>::new();
+        particles.extend((0..1000).map(|i| Vec2::new(i as f32, 0.0)));
+        particles.count = 1000;
+
+        let mut settings = Buffer::<Settings>::new();
+        settings.gravity = Vec2::new(0.0, -9.8);
+        settings.drag = 0.5;
+
+        fall(
+            fall::Bindings {
+                particles: &mut particles,
+                settings: &settings,
+            },
+            fall::dispatch_for(1000),
+        );
+
+        let (head, positions) = particles.into_vec();
+        let particles = Buffer::<Particles>::from_vec(head, positions);
+        assert_eq!(particles.positions.len(), 1000);
+    }
+
+    assert_eq!(generated_reports(&check), []);
+}
+
+struct Misused;
+
+#[test]
+fn misuse_is_classified() {
+    let type_name = any::type_name::<Misused>();
+    let reports = || {
+        alloc_registry::reports()
+            .into_iter()
+            .filter(|report| report.type_name == type_name)
+            .map(|report| report.kind)
+            .collect::<Vec<_>>()
+    };
+
+    // real allocations, so no other test records the same addresses
+    let mut memory = [0u64; 16];
+    let first = NonNull::from(&mut memory[0]).cast::<u8>();
+    let second = NonNull::from(&mut memory[8]).cast::<u8>();
+
+    let layout = Layout::from_size_align(64, 8).unwrap();
+    let smaller = Layout::from_size_align(32, 8).unwrap();
+
+    alloc_registry::record_alloc::<Misused>(first, layout);
+    alloc_registry::record_dealloc::<Misused>(first, smaller);
+
+    assert_eq!(
+        reports(),
+        [ReportKind::LayoutMismatch { allocated: layout }]
+    );
+
+    // freed by the mismatched deallocation
+    alloc_registry::record_dealloc::<Misused>(first, layout);
+
+    assert_eq!(reports()[1..], [ReportKind::UnknownPointer]);
+
+    {
+        let check = LeakCheck::new();
+
+        alloc_registry::record_alloc::<Misused>(first, layout);
+        alloc_registry::record_realloc::<Misused>(first, layout, second, smaller);
+
+        assert_eq!(check.leaks().len(), 1);
+        assert_eq!(check.leaks()[0].ptr, second.as_ptr() as usize);
+    }
+
+    assert_eq!(reports()[2..], [ReportKind::Leak]);
+
+    let leak = alloc_registry::reports().pop().unwrap();
+    assert_eq!(leak.layout, smaller);
+    assert!(leak.to_string().contains("Misused"), "{}", leak);
+}
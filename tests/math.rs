@@ -0,0 +1,103 @@
+use std::mem;
+
+use shatter::*;
+
+#[test]
+fn vec3_ops_keep_layout() {
+    assert_eq!(mem::size_of::<Vec3<f32>>(), 16);
+    assert_eq!(mem::align_of::<Vec3<f32>>(), 16);
+    assert_eq!(mem::size_of::<PackedVec3<f32>>(), 12);
+
+    // elements are 16 bytes apart, in-place ops must only touch the components
+    let mut points = [Vec3::new(1.0f32, 2.0, 3.0); 4];
+    let offset = Vec3::new(0.5, -1.0, 2.0);
+
+    for (i, point) in points.iter_mut().enumerate() {
+        *point += offset * i as f32;
+        *point *= 2.0;
+    }
+
+    for (i, point) in points.iter().enumerate() {
+        let i = i as f32;
+
+        assert_eq!(
+            *point,
+            Vec3::new(2.0 + i, 4.0 - 2.0 * i, 6.0 + 4.0 * i),
+            "point {}",
+            i
+        );
+    }
+
+    assert_eq!(mem::size_of_val(&points), 64);
+    assert_eq!(
+        &points[1].x as *const f32 as usize - points.as_ptr() as usize,
+        16
+    );
+
+    let packed = PackedVec3::from(points[1]) - PackedVec3::splat(1.0);
+    assert_eq!(packed, PackedVec3::new(2.0, 1.0, 9.0));
+    assert_eq!(Vec3::from(packed), Vec3::new(2.0, 1.0, 9.0));
+}
+
+#[test]
+fn vec_arithmetic() {
+    let a = Vec3::new(1.0f32, 2.0, 3.0);
+    let b = Vec3::new(4.0f32, -5.0, 6.0);
+
+    assert_eq!(a + b, Vec3::new(5.0, -3.0, 9.0));
+    assert_eq!(a - b, Vec3::new(-3.0, 7.0, -3.0));
+    assert_eq!(a * b, Vec3::new(4.0, -10.0, 18.0));
+    assert_eq!(b / a, Vec3::new(4.0, -2.5, 2.0));
+    assert_eq!(a / 2.0, Vec3::new(0.5, 1.0, 1.5));
+    assert_eq!(-a, Vec3::new(-1.0, -2.0, -3.0));
+
+    let mut c = a;
+    c -= b;
+    c /= Vec3::splat(-1.0);
+    assert_eq!(c, b - a);
+
+    assert_eq!(Vec2::new(3u32, 4) * 2, Vec2::new(6, 8));
+    assert_eq!(
+        Vec4::new(1, -2, 3, -4) + Vec4::splat(1),
+        Vec4::new(2, -1, 4, -3)
+    );
+    assert_eq!(Vec2::new(7i32, -7) / 2, Vec2::new(3, -3));
+}
+
+#[test]
+fn vec_methods() {
+    let a = Vec3::new(1.0f32, 2.0, 3.0);
+    let b = Vec3::new(4.0f32, -5.0, 6.0);
+
+    assert_eq!(a.dot(b), 12.0);
+    assert_eq!(a.min(b), Vec3::new(1.0, -5.0, 3.0));
+    assert_eq!(a.max(b), Vec3::new(4.0, 2.0, 6.0));
+    assert_eq!(Vec2::new(3u32, 9).min(Vec2::new(5, 1)), Vec2::new(3, 1));
+    assert_eq!(Vec2::new(3i32, 4).dot(Vec2::new(-1, 2)), 5);
+
+    assert_eq!(Vec2::new(3.0f32, 4.0).length(), 5.0);
+    assert_eq!(
+        Vec4::new(0.0f64, 0.0, 2.0, 0.0).normalize(),
+        Vec4::new(0.0, 0.0, 1.0, 0.0)
+    );
+    assert!((Vec3::new(1.0f32, 1.0, 1.0).normalize().length() - 1.0).abs() < 1e-6);
+
+    assert_eq!(a.lerp(b, 0.0), a);
+    assert_eq!(a.lerp(b, 1.0), b);
+    assert_eq!(a.lerp(b, 0.5), Vec3::new(2.5, -1.5, 4.5));
+
+    const HALF: Vec2<f32> = Vec2::new(0.0f32, 2.0).lerp(Vec2::new(2.0, 4.0), 0.5);
+    const DOT: i32 = Vec3::new(1i32, 2, 3).dot(Vec3::splat(2));
+    assert_eq!(HALF, Vec2::new(1.0, 3.0));
+    assert_eq!(DOT, 12);
+}
+
+#[test]
+fn vec_array_conversions() {
+    assert_eq!(Vec3::from([1, 2, 3]), Vec3::new(1, 2, 3));
+    assert_eq!(<[u32; 4]>::from(Vec4::new(1, 2, 3, 4)), [1, 2, 3, 4]);
+    assert_eq!(Vec2::new(1.5f32, 2.5).to_array(), [1.5, 2.5]);
+
+    let array: [f32; 3] = Vec3::new(1.0, 2.0, 3.0).into();
+    assert_eq!(array, [1.0, 2.0, 3.0]);
+}
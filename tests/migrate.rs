@@ -0,0 +1,143 @@
+use shatter::*;
+
+mod v1 {
+    use shatter::*;
+
+    wgsl! {
+        struct Particle {
+            position: vec2<f32>;
+            velocity: vec2<f32>;
+            age: f32;
+        };
+
+        [[block]]
+        struct Particles {
+            particles: array<Particle>;
+        };
+
+        [[group(0), binding(0)]]
+        var<storage, read_write> particles: Particles;
+
+        [[stage(compute), workgroup_size(1)]]
+        fn step([[builtin(global_invocation_id)]] id: vec3<u32>) {
+            particles.particles[id.x].age = particles.particles[id.x].age + 1.0;
+        }
+    }
+}
+
+mod v2 {
+    use shatter::*;
+
+    wgsl! {
+        struct Particle {
+            position: vec2<f32>;
+            mass: f32;
+            velocity: vec2<f32>;
+        };
+
+        [[block]]
+        struct Particles {
+            particles: array<Particle>;
+        };
+
+        [[group(0), binding(0)]]
+        var<storage, read_write> particles: Particles;
+
+        [[stage(compute), workgroup_size(1)]]
+        fn step([[builtin(global_invocation_id)]] id: vec3<u32>) {
+            let particle = particles.particles[id.x];
+            particles.particles[id.x].position = particle.position + particle.velocity / particle.mass;
+        }
+    }
+}
+
+fn dump() -> Vec<u8> {
+    let particles = (0..4)
+        .map(|i| v1::Particle {
+            position: Vec2::new(i as f32, 0.0),
+            velocity: Vec2::new(0.0, i as f32 * 2.0),
+            age: 10.0,
+        })
+        .collect::<Vec<_>>();
+
+    items_as_bytes(&particles).to_vec()
+}
+
+#[test]
+fn migrate_auto_fields() {
+    let (bytes, report) = migrate_auto::<v1::Particle, v2::Particle>(&dump());
+
+    assert_eq!(report.items, 4);
+    assert_eq!(report.copied, ["position", "velocity"]);
+    assert_eq!(report.added, ["mass"]);
+    assert_eq!(report.dropped, ["age"]);
+
+    let particles = items_from_bytes::<v2::Particle>(&bytes);
+
+    assert_eq!(particles.len(), 4);
+
+    for (i, particle) in particles.iter().enumerate() {
+        assert_eq!(particle.position, Vec2::new(i as f32, 0.0));
+        assert_eq!(particle.velocity, Vec2::new(0.0, i as f32 * 2.0));
+        assert_eq!(particle.mass, 0.0);
+    }
+}
+
+#[test]
+fn migrate_with_fill() {
+    let bytes = migrate(&dump(), |old: &v1::Particle| v2::Particle {
+        position: old.position,
+        mass: 1.0 + old.age,
+        velocity: old.velocity,
+    });
+
+    let particles = items_from_bytes::<v2::Particle>(&bytes);
+
+    assert_eq!(particles.len(), 4);
+    assert_eq!(particles[3].velocity, Vec2::new(0.0, 6.0));
+    assert!(particles.iter().all(|particle| particle.mass == 11.0));
+}
+
+#[test]
+fn migrate_runtime_layout() {
+    // a layout read back from a manifest instead of a compiled type
+    let old = StructLayout {
+        name: "Particle".into(),
+        size: 8,
+        fields: vec![
+            FieldLayout {
+                name: "mass".into(),
+                ty: "f32".into(),
+                offset: 0,
+                size: 4,
+            },
+            FieldLayout {
+                name: "age".into(),
+                ty: "u32".into(),
+                offset: 4,
+                size: 4,
+            },
+        ]
+        .into(),
+    };
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&2.5f32.to_ne_bytes());
+    bytes.extend_from_slice(&7u32.to_ne_bytes());
+
+    let (bytes, report) = migrate_layout(&old, &v2::Particle::LAYOUT, &bytes);
+
+    assert_eq!(report.copied, ["mass"]);
+    assert_eq!(report.added, ["position", "velocity"]);
+    assert_eq!(report.dropped, ["age"]);
+
+    let particles = items_from_bytes::<v2::Particle>(&bytes);
+
+    assert_eq!(
+        particles,
+        [v2::Particle {
+            mass: 2.5,
+            ..Default::default()
+        }]
+    );
+}
@@ -0,0 +1,112 @@
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc, Mutex,
+};
+
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn double([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] * 2u;
+    }
+}
+
+fn values_in(instance: &'static Instance, len: u32) -> Buffer<Values> {
+    let mut values = Buffer::<Values>::new_in(instance);
+
+    for i in 0..len {
+        values.push(i);
+    }
+
+    values
+}
+
+#[test]
+fn oom_handler_reclaims_and_retries() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let instance = Instance::create(&InstanceDescriptor::default()).unwrap();
+
+    // 2 MiB the handler frees when the budget runs out
+    let dummy = values_in(instance, 1 << 19);
+    dummy.try_resize_buffer().unwrap();
+
+    let resident = instance.buffers.resident_bytes() + instance.textures.resident_bytes();
+    instance.set_memory_budget(Some(resident + 4096));
+
+    let dummy = Arc::new(Mutex::new(Some(dummy)));
+    let calls = Arc::new(AtomicU32::new(0));
+
+    let handler_dummy = dummy.clone();
+    let handler_calls = calls.clone();
+    instance.set_oom_handler(move |info| {
+        handler_calls.fetch_add(1, Ordering::SeqCst);
+
+        assert_eq!(info.kind, ResourceKind::Buffer);
+        assert_eq!(info.attempt, 0);
+
+        match handler_dummy.lock().unwrap().take() {
+            Some(dummy) => {
+                drop(dummy);
+                instance.clean();
+
+                OomAction::Retry
+            }
+            None => OomAction::Fail,
+        }
+    });
+
+    // 256 KiB, over the budget until the dummy is freed
+    let mut values = values_in(instance, 1 << 16);
+    values.try_resize_buffer().unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(dummy.lock().unwrap().is_none());
+
+    double::build(double::Bindings {
+        values: &mut values,
+    })
+    .instance(instance)
+    .dispatch(Dispatch::new(4, 1, 1));
+
+    assert_eq!(&values.values[..4], &[0, 2, 4, 6]);
+
+    // nothing left to free, the handler gives up
+    let large = values_in(instance, 1 << 20);
+    assert!(matches!(
+        large.try_resize_buffer(),
+        Err(Error::Device(DeviceError::OutOfMemory))
+    ));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // retrying without freeing anything is bounded
+    let retries = Arc::new(AtomicU32::new(0));
+    let handler_retries = retries.clone();
+    instance.set_oom_handler(move |_| {
+        handler_retries.fetch_add(1, Ordering::SeqCst);
+
+        OomAction::Retry
+    });
+
+    assert!(matches!(
+        large.try_resize_buffer(),
+        Err(Error::Device(DeviceError::OutOfMemory))
+    ));
+    assert_eq!(retries.load(Ordering::SeqCst), MAX_OOM_RETRIES);
+
+    instance.clear_oom_handler();
+    instance.set_memory_budget(None);
+
+    large.try_resize_buffer().unwrap();
+}
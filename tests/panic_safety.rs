@@ -0,0 +1,181 @@
+//! Panics injected into generated `BufferVec` implementations, checking the
+//! data is still sound to use and drop afterwards.
+//!
+//! Everything but the last test runs without a device, so the allocation
+//! handling can be checked under miri with
+//! `cargo +nightly miri test --features fault-injection --test panic_safety`.
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    ptr::NonNull,
+};
+
+use shatter::{
+    fault::{inject_panic, FaultPoint},
+    *,
+};
+
+wgsl! {
+    // the header is more aligned than the items, so allocations are padded
+    [[block]]
+    struct Samples {
+        origin: vec4<f32>;
+        samples: array<f32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> samples: Samples;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn scale([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&samples.samples)) {
+            return;
+        }
+
+        samples.samples[id.x] = samples.samples[id.x] * samples.origin.x;
+    }
+}
+
+/// Owns data the way `Buffer` does, without creating a gpu buffer.
+struct Raw {
+    ptr: NonNull<u8>,
+    state: <Samples as BufferData>::State,
+}
+
+impl Raw {
+    fn new() -> Self {
+        Self {
+            ptr: unsafe { Samples::alloc() },
+            state: Samples::init(),
+        }
+    }
+
+    fn push(&mut self, item: f32) {
+        unsafe { Samples::push(&mut self.ptr, &mut self.state, item) };
+    }
+
+    fn samples(&self) -> &[f32] {
+        unsafe { &(*Samples::as_ptr(self.ptr, &self.state)).samples }
+    }
+
+    fn capacity(&self) -> usize {
+        self.state.1
+    }
+}
+
+impl Drop for Raw {
+    fn drop(&mut self) {
+        unsafe { Samples::dealloc(self.ptr, &self.state) };
+    }
+}
+
+/// Runs `f` with a panic injected at `point`, returning whether it panicked.
+fn panics_at(point: FaultPoint, f: impl FnOnce()) -> bool {
+    inject_panic(point);
+
+    panic::catch_unwind(AssertUnwindSafe(f)).is_err()
+}
+
+#[test]
+fn panic_mid_grow() {
+    let mut raw = Raw::new();
+
+    for i in 0..4 {
+        raw.push(i as f32);
+    }
+
+    assert_eq!(raw.capacity(), 4);
+    assert!(panics_at(FaultPoint::Grow, || raw.push(4.0)));
+
+    // nothing was reallocated
+    assert_eq!(raw.capacity(), 4);
+    assert_eq!(raw.samples(), [0.0, 1.0, 2.0, 3.0]);
+
+    raw.push(4.0);
+    assert_eq!(raw.samples(), [0.0, 1.0, 2.0, 3.0, 4.0]);
+}
+
+#[test]
+fn panic_mid_push() {
+    let mut raw = Raw::new();
+    raw.push(1.0);
+
+    // grown, but the item isn't written
+    assert!(panics_at(FaultPoint::Push, || raw.push(2.0)));
+
+    assert_eq!(raw.capacity(), 2);
+    assert_eq!(raw.samples(), [1.0]);
+
+    assert!(panics_at(FaultPoint::Push, || raw.push(2.0)));
+    assert_eq!(raw.samples(), [1.0]);
+
+    raw.push(3.0);
+    assert_eq!(raw.samples(), [1.0, 3.0]);
+}
+
+#[test]
+fn panic_mid_reserve() {
+    let mut raw = Raw::new();
+    raw.push(1.0);
+
+    assert!(panics_at(FaultPoint::Grow, || unsafe {
+        Samples::reserve(&mut raw.ptr, &mut raw.state, 100)
+    }));
+
+    assert_eq!(raw.capacity(), 1);
+    assert_eq!(raw.samples(), [1.0]);
+
+    unsafe { Samples::reserve(&mut raw.ptr, &mut raw.state, 100) };
+    assert_eq!(raw.capacity(), 101);
+    assert_eq!(raw.samples(), [1.0]);
+}
+
+#[test]
+fn dropped_after_panic() {
+    // dropped while unwinding from a later panic
+    let result = panic::catch_unwind(|| {
+        let mut raw = Raw::new();
+        raw.push(1.0);
+
+        inject_panic(FaultPoint::Grow);
+        raw.push(2.0);
+    });
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn layouts_agree() {
+    let layout = buffer_vec_layout::<Samples_Sized, f32>(3);
+
+    assert_eq!(layout.align(), 16);
+    assert_eq!(layout.size(), 32);
+
+    // allocated, grown and deallocated with padded layouts
+    let mut raw = Raw::new();
+
+    for i in 0..33 {
+        raw.push(i as f32);
+    }
+
+    assert_eq!(raw.samples().len(), 33);
+}
+
+#[test]
+fn buffer_after_panic() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut buffer = Buffer::<Samples>::new();
+    buffer.push(1.0);
+
+    assert!(panics_at(FaultPoint::Grow, || buffer.push(2.0)));
+    assert!(panics_at(FaultPoint::Push, || buffer.push(2.0)));
+
+    assert_eq!(buffer.samples, [1.0]);
+    assert!(buffer.needs_upload());
+
+    buffer.push(2.0);
+    assert_eq!(buffer.samples, [1.0, 2.0]);
+}
@@ -0,0 +1,123 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+}
+
+const SOURCE: &str = "
+[[block]]
+struct Values {
+    values: array<u32>;
+};
+
+[[group(0), binding(0)]]
+var<storage, read> input: Values;
+
+[[group(1), binding(0)]]
+var<storage, read_write> output: Values;
+
+[[stage(compute), workgroup_size(4)]]
+fn double([[builtin(global_invocation_id)]] id: vec3<u32>) {
+    output.values[id.x] = input.values[id.x] * 2u;
+}
+";
+
+const LEN: u32 = 16;
+
+#[test]
+fn raw_shader_uses_caches() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let shader =
+        RawComputeShader::new(SOURCE, "double").with_work_group_size(WorkGroupSize::new(4, 1, 1));
+
+    let mut input = Buffer::<Values>::new();
+    let mut output = Buffer::<Values>::new();
+
+    for i in 0..LEN {
+        input.push(i);
+        output.push(0);
+    }
+
+    let dispatch = Dispatch::new(LEN / 4, 1, 1);
+
+    let bindings = DynamicBindings::new()
+        .storage_read(0, 0, &input)
+        .storage(1, 0, &mut output);
+    shader.dispatch(bindings, dispatch);
+
+    let pipelines = instance.compute_pipeline_descriptors.len();
+    let bind_groups = instance.bind_group_descriptors.len();
+    let modules = instance.shader_module_sources.len();
+
+    for _ in 0..10 {
+        let bindings = DynamicBindings::new()
+            .storage_read(0, 0, &input)
+            .storage(1, 0, &mut output);
+        shader.dispatch(bindings, dispatch);
+    }
+
+    assert_eq!(instance.compute_pipeline_descriptors.len(), pipelines);
+    assert_eq!(instance.bind_group_descriptors.len(), bind_groups);
+    assert_eq!(instance.shader_module_sources.len(), modules);
+
+    for i in 0..LEN {
+        assert_eq!(output.values[i as usize], i * 2);
+    }
+}
+
+#[test]
+fn raw_shader_dispatch_if_nonzero_sized() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let shader = RawComputeShader::new(SOURCE, "double");
+
+    let mut input = Buffer::<Values>::new();
+    let mut output = Buffer::<Values>::new();
+
+    for i in 0..LEN {
+        input.push(i + 1);
+        output.push(0);
+    }
+
+    let mut counter = Buffer::<Values>::new();
+    counter.push(LEN);
+    let counter = counter.into_gpu_only();
+
+    let bindings = DynamicBindings::new()
+        .storage_read(0, 0, &input)
+        .storage(1, 0, &mut output);
+    shader.build(bindings).dispatch_if_nonzero_sized(
+        &counter,
+        0,
+        WorkGroupSize::new(4, 1, 1),
+        Dispatch::new(LEN / 4, 1, 1),
+    );
+
+    for i in 0..LEN {
+        assert_eq!(output.values[i as usize], (i + 1) * 2);
+    }
+}
+
+#[test]
+#[should_panic(expected = "group 0 binding 0 is already bound")]
+fn dynamic_bindings_reject_duplicates() {
+    if Instance::try_global().is_none() {
+        panic!("group 0 binding 0 is already bound");
+    }
+
+    let input = Buffer::<Values>::new();
+    let mut output = Buffer::<Values>::new();
+
+    let _ = DynamicBindings::new()
+        .storage_read(0, 0, &input)
+        .storage(0, 0, &mut output);
+}
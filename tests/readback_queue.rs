@@ -0,0 +1,43 @@
+use shatter::{util::FrameReadbackQueue, *};
+
+#[test]
+fn frames_arrive_in_order() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut texture = Texture2d::<Rgba8Unorm>::new(3, 2);
+    let mut queue = FrameReadbackQueue::new(2);
+    let mut frames = Vec::new();
+
+    for frame in 0..10u8 {
+        for y in 0..texture.height() {
+            for x in 0..texture.width() {
+                texture[(x, y)] = Rgba8U::rgba(frame, x as u8, y as u8, 255);
+            }
+        }
+
+        if let Some(rgba) = queue.submit(&texture) {
+            frames.push(rgba);
+        }
+
+        assert!(queue.outstanding() <= queue.capacity());
+
+        while let Some(rgba) = queue.try_recv() {
+            frames.push(rgba);
+        }
+    }
+
+    frames.extend(queue.drain());
+
+    assert!(queue.is_empty());
+    assert_eq!(frames.len(), 10);
+
+    for (frame, rgba) in frames.iter().enumerate() {
+        assert_eq!(rgba.len(), 3 * 2 * 4);
+
+        for (i, texel) in rgba.chunks_exact(4).enumerate() {
+            assert_eq!(texel, [frame as u8, (i % 3) as u8, (i / 3) as u8, 255]);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Particles {
+        positions: array<vec2<f32>>;
+    };
+
+    [[block]]
+    struct Uniforms {
+        delta: f32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> particles: Particles;
+
+    [[group(0), binding(1)]]
+    var<uniform> uniforms: Uniforms;
+
+    [[group(0), binding(2)]]
+    var image: texture_storage_2d<rgba8unorm, write>;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn integrate([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        particles.positions[id.x] = particles.positions[id.x] + vec2<f32>(uniforms.delta);
+    }
+
+    [[stage(compute), workgroup_size(1)]]
+    fn splat([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        let position = vec2<i32>(particles.positions[id.x]);
+        textureStore(image, position, vec4<f32>(1.0));
+    }
+}
+
+/// Names of the resources in the frame graph asset.
+const RENAME: &[(&str, &str)] = &[
+    ("particles", "sim/particles.bin"),
+    ("uniforms", "sim/uniforms.bin"),
+    ("image", "out/splat.png"),
+];
+
+#[test]
+fn reports_every_missing_resource() {
+    let mut registry = ResourceRegistry::new();
+
+    let err = match integrate::Bindings::from_registry_renamed(&mut registry, RENAME) {
+        Ok(_) => panic!("registry is empty"),
+        Err(err) => err,
+    };
+
+    assert_eq!(err.errors.len(), 2);
+    assert!(
+        err.field_errors("particles")
+            .all(|error| error.name == "sim/particles.bin"
+                && error.kind == RegistryErrorKind::Missing)
+    );
+    assert!(err
+        .to_string()
+        .contains("`uniforms` (\"sim/uniforms.bin\")"));
+}
+
+#[test]
+fn bindings_from_registry() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut registry = ResourceRegistry::new();
+
+    let mut particles = Buffer::<Particles>::new();
+    particles.push(Vec2::new(1.0, 2.0));
+    registry.insert("sim/particles.bin", particles);
+
+    let mut uniforms = Buffer::<Uniforms>::new();
+    uniforms.delta = 0.5;
+    registry.insert("sim/uniforms.bin", uniforms);
+
+    registry.insert("out/splat.png", Texture2d::<Rgba8Unorm>::new(4, 4));
+
+    integrate(
+        integrate::Bindings::from_registry_renamed(&mut registry, RENAME).unwrap(),
+        Dispatch::new(1, 1, 1),
+    );
+    splat(
+        splat::Bindings::from_registry_renamed(&mut registry, RENAME).unwrap(),
+        Dispatch::new(1, 1, 1),
+    );
+
+    let particles = registry
+        .get::<Buffer<Particles>>("sim/particles.bin")
+        .unwrap();
+    assert_eq!(particles.positions[0], Vec2::new(1.5, 2.5));
+
+    let image = registry
+        .get::<Texture2d<Rgba8Unorm>>("out/splat.png")
+        .unwrap();
+    assert_eq!(image[(1, 1)], Rgba8U::WHITE);
+}
+
+#[test]
+fn reports_every_invalid_resource() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut registry = ResourceRegistry::new();
+
+    // the uniforms are stored where the particles are expected
+    registry.insert("sim/particles.bin", Buffer::<Uniforms>::new());
+    registry.insert("out/splat.png", Texture2d::<Rgba8Unorm>::new(4, 4));
+
+    // the particles are also the image
+    let rename = &[("particles", "out/splat.png"), ("image", "out/splat.png")];
+
+    let err = match splat::Bindings::from_registry_renamed(&mut registry, rename) {
+        Ok(_) => panic!("bindings are invalid"),
+        Err(err) => err,
+    };
+
+    assert_eq!(err.errors.len(), 2);
+    assert!(matches!(
+        err.errors[0].kind,
+        RegistryErrorKind::WrongType { .. }
+    ));
+    assert_eq!(
+        err.errors[1].kind,
+        RegistryErrorKind::Conflict { other: "image" }
+    );
+
+    let err = match integrate::Bindings::from_registry_renamed(&mut registry, RENAME) {
+        Ok(_) => panic!("bindings are invalid"),
+        Err(err) => err,
+    };
+
+    assert_eq!(err.errors.len(), 2);
+    assert!(matches!(
+        err.field_errors("particles").next().unwrap().kind,
+        RegistryErrorKind::WrongType { .. }
+    ));
+    assert_eq!(
+        err.field_errors("uniforms").next().unwrap().kind,
+        RegistryErrorKind::Missing
+    );
+
+    // renamed fields name the registry entry they were looked up as
+    let message = err.to_string();
+    assert!(
+        message.contains("`uniforms` (\"sim/uniforms.bin\"): no resource with this name"),
+        "{}",
+        message
+    );
+}
@@ -0,0 +1,88 @@
+use shatter::{convert, *};
+
+wgsl! {
+    [[group(0), binding(0)]]
+    var input: texture_storage_2d<rgba8unorm, read>;
+
+    [[group(0), binding(1)]]
+    var output: texture_storage_2d<rgba8unorm, write>;
+
+    [[stage(compute), workgroup_size(8, 8)]]
+    fn passthrough([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        let size = textureDimensions(input);
+
+        if (i32(id.x) >= size.x || i32(id.y) >= size.y) {
+            return;
+        }
+
+        textureStore(output, vec2<i32>(id.xy), textureLoad(input, vec2<i32>(id.xy)));
+    }
+}
+
+fn image(width: usize, height: usize) -> Vec<Rgb8U> {
+    (0..width * height)
+        .map(|i| Rgb8U::rgb(i as u8, (i / width) as u8, (i * 7) as u8))
+        .collect()
+}
+
+#[test]
+fn pack_and_strip_u8() {
+    // longer than a chunk, with a remainder
+    let rgb = image(37, 1);
+    let rgba = convert::pack_rgb_to_rgba(&rgb, 200);
+
+    assert_eq!(rgba.len(), rgb.len());
+    assert_eq!(rgba[5], Rgba8U::rgba(5, 0, 35, 200));
+    assert!(rgba.iter().all(|color| color.a == 200));
+
+    assert_eq!(convert::strip_alpha(&rgba), rgb);
+}
+
+#[test]
+fn pack_and_strip_f32() {
+    let rgb = (0..20)
+        .map(|i| Rgb32::rgb(i as f32, 0.5, -(i as f32)))
+        .collect::<Vec<_>>();
+    let rgba = convert::pack_rgb_to_rgba(&rgb, 1.0);
+
+    assert_eq!(rgba[3], Rgba32::rgba(3.0, 0.5, -3.0, 1.0));
+    assert_eq!(convert::strip_alpha(&rgba), rgb);
+}
+
+#[test]
+fn from_rgb() {
+    let color = Rgba8U::from_rgb(Rgb8U::rgb(1, 2, 3), 4);
+
+    assert_eq!(color, Rgba8U::rgba(1, 2, 3, 4));
+    assert_eq!(color.to_rgb(), Rgb8U::rgb(1, 2, 3));
+    assert_eq!(
+        Rgb32::rgb(0.25, 0.5, 1.0).with_alpha(0.0),
+        Rgba32::rgba(0.25, 0.5, 1.0, 0.0)
+    );
+}
+
+#[test]
+fn round_trip_through_kernel() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    // 30 texels of 4 bytes pad to 256 bytes per row
+    let rgb = image(30, 20);
+
+    let mut input = Texture2d::<Rgba8Unorm>::from_rgb_data(30, 20, &rgb, 255);
+    assert!(input.needs_upload());
+    assert_eq!(input[(3, 1)], Rgba8U::rgba(33, 1, 231, 255));
+
+    let mut output = Texture2d::<Rgba8Unorm>::new(30, 20);
+
+    passthrough(
+        passthrough::Bindings {
+            input: &mut input,
+            output: &mut output,
+        },
+        passthrough::dispatch_for_extent([30, 20, 1]),
+    );
+
+    assert_eq!(output.rgb_data(), rgb);
+}
@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use shatter::{
+    util::{Segment, SegmentedDispatch},
+    *,
+};
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[block]]
+    struct Offset {
+        offset: u32;
+        len: u32;
+    };
+
+    [[group(0), binding(1)]]
+    var<uniform> offset: Offset;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn fill([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= offset.len) {
+            return;
+        }
+
+        let i = offset.offset + id.x;
+        values.values[i] = i + 1u;
+    }
+}
+
+#[test]
+fn segments_cover_the_range() {
+    let mut segments = SegmentedDispatch::new(10, 4);
+
+    assert_eq!(segments.segments(), 3);
+    assert_eq!(segments.progress(), 0.0);
+    assert_eq!(
+        segments.next_segment(),
+        Some(Segment {
+            index: 0,
+            offset: 0,
+            len: 4,
+        })
+    );
+
+    let handle = segments.cancel_handle();
+    handle.cancel();
+
+    assert!(segments.is_cancelled());
+    assert!(!segments.is_done());
+    assert_eq!(segments.next_segment(), None);
+    assert_eq!(segments.step(|_| unreachable!()), None);
+
+    let empty = SegmentedDispatch::new(0, 4);
+    assert!(empty.is_done());
+    assert_eq!(empty.progress(), 1.0);
+}
+
+// the only test touching the instance, so the counters only see these submissions
+#[test]
+fn cancel_stops_submitting() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let mut values = Buffer::<Values>::new();
+
+    for _ in 0..1024 {
+        values.push(0);
+    }
+
+    let mut uniform = Buffer::<Offset>::new();
+
+    let mut segments = SegmentedDispatch::new(1024, 256).with_budget(Duration::from_secs(10));
+
+    let mut step = |segments: &mut SegmentedDispatch| {
+        segments.step(|segment| {
+            uniform.offset = segment.offset as u32;
+            uniform.len = segment.len as u32;
+
+            let bindings = fill::Bindings {
+                values: &mut values,
+                offset: &uniform,
+            };
+
+            fill(bindings, fill::dispatch_for(segment.len));
+        })
+    };
+
+    instance.stats_frame();
+
+    for i in 0..2 {
+        let report = step(&mut segments).unwrap();
+
+        assert_eq!(report.segment.index, i);
+        assert!(!report.over_budget);
+
+        // every segment is submitted before the next one starts
+        assert!(instance.stats_frame().submissions > 0);
+    }
+
+    assert_eq!(segments.progress(), 0.5);
+
+    segments.cancel();
+
+    assert!(step(&mut segments).is_none());
+    assert!(step(&mut segments).is_none());
+    assert_eq!(instance.stats_frame().submissions, 0);
+    assert_eq!(segments.progress(), 0.5);
+
+    // the partial result
+    for (i, &value) in values.values.iter().enumerate() {
+        let expected = if i < 512 { i as u32 + 1 } else { 0 };
+        assert_eq!(value, expected, "value {}", i);
+    }
+}
@@ -0,0 +1,74 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Params {
+        value: vec4<i32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<uniform> params: Params;
+
+    [[group(0), binding(1)]]
+    var<storage, read> input: Params;
+
+    [[group(1), binding(0)]]
+    var<storage, read> other: Params;
+
+    [[block]]
+    struct Output {
+        value: i32;
+    };
+
+    [[group(1), binding(1)]]
+    var<storage, read_write> output: Output;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn sum() {
+        output.value = params.value.x + input.value.y + other.value.z;
+    }
+}
+
+#[test]
+fn aliased_bindings() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut params = Buffer::<Params>::new();
+    let mut output = Buffer::<Output>::new();
+
+    params.value = Vec4::new(1, 2, 3, 4);
+
+    sum(
+        sum::Bindings {
+            params: &params,
+            input: &params,
+            other: &params,
+            output: &mut output,
+        },
+        Dispatch::new(1, 1, 1),
+    );
+
+    assert_eq!(output.value, 6);
+
+    params.value = Vec4::new(10, 20, 30, 40);
+
+    let mut builder = sum::build(sum::Bindings {
+        params: &params,
+        input: &params,
+        other: &params,
+        output: &mut output,
+    });
+    builder.dispatch(Dispatch::new(1, 1, 1));
+
+    // `params` is prepared and uploaded once, despite being bound three times
+    assert_transfers!(
+        builder.last_transfer_report(),
+        uploaded_bytes == 16,
+        buffers_touched == 3,
+    );
+
+    drop(builder);
+    assert_eq!(output.value, 60);
+}
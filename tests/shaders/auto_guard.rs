@@ -0,0 +1,104 @@
+use shatter::*;
+
+wgsl! {
+    #![auto_guard]
+
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[block]]
+    struct Counter {
+        value: atomic<u32>;
+    };
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> counter: Counter;
+
+    // no bounds check, out of range invocations would all increment the last element
+    [[stage(compute), workgroup_size(256)]]
+    fn increment([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] + 1u;
+    }
+
+    // the guard declares the builtins it needs
+    [[stage(compute), workgroup_size(16, 16)]]
+    fn count() {
+        let previous = atomicAdd(&counter.value, 1u);
+    }
+}
+
+const LEN: u32 = 1000;
+
+fn values() -> Buffer<Values> {
+    let mut values = Buffer::<Values>::new();
+
+    for i in 0..LEN {
+        values.push(i);
+    }
+
+    values
+}
+
+#[test]
+fn guards_partial_work_groups() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut values = values();
+
+    // 4 groups of 256 invocations, 24 past the end
+    increment(
+        increment::Bindings {
+            values: &mut values,
+        },
+        Dispatch::new(LEN.div_ceil(256), 1, 1),
+    );
+
+    for (i, value) in values.values.iter().enumerate() {
+        assert_eq!(*value, i as u32 + 1, "element {}", i);
+    }
+}
+
+#[test]
+fn guard_count_overrides_the_array_length() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut values = values();
+
+    increment::build(increment::Bindings {
+        values: &mut values,
+    })
+    .with_guard_count(500)
+    .dispatch(Dispatch::new(LEN.div_ceil(256), 1, 1));
+
+    for (i, value) in values.values.iter().enumerate() {
+        let expected = if i < 500 { i as u32 + 1 } else { i as u32 };
+        assert_eq!(*value, expected, "element {}", i);
+    }
+}
+
+#[test]
+fn guards_flattened_invocations() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut counter = Buffer::<Counter>::new();
+
+    // 4 groups of 16x16 invocations, without a runtime sized array to count
+    count::build(count::Bindings {
+        counter: &mut counter,
+    })
+    .with_guard_count(LEN)
+    .dispatch(Dispatch::new(4, 1, 1));
+
+    assert_eq!(counter.value, LEN);
+}
@@ -0,0 +1,106 @@
+use shatter::*;
+
+wgsl! {
+    let FACTOR: i32 = 3;
+    let OFFSET: f32 = 0.5;
+
+    [[block]]
+    struct Data {
+        int: i32;
+        float: f32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> data: Data;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn apply() {
+        data.int = data.int * FACTOR;
+        data.float = data.float + OFFSET;
+    }
+}
+
+#[test]
+fn constants() {
+    assert_eq!(FACTOR, 3);
+    assert_eq!(OFFSET, 0.5);
+
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut data = Buffer::<Data>::new();
+
+    data.int = 2;
+    data.float = 1.0;
+
+    apply(apply::Bindings { data: &mut data }, Dispatch::new(1, 1, 1));
+
+    assert_eq!(data.int, 2 * FACTOR);
+    assert_eq!(data.float, 1.0 + OFFSET);
+}
+
+wgsl!(mod composite {
+    let COUNT: u32 = 1024u;
+    let MASK: u32 = 4294967295u;
+    let DIR: vec2<f32> = vec2<f32>(1.0, 0.0);
+    let TINT: vec4<u32> = vec4<u32>(1u, 2u, 3u, 4294967295u);
+    let BASIS: mat2x2<f32> = mat2x2<f32>(vec2<f32>(0.0, 1.0), vec2<f32>(2.0, 0.5));
+    let WEIGHTS: array<f32, 3> = array<f32, 3>(0.25, 0.5, 0.25);
+
+    [[block]]
+    struct Output {
+        count: u32;
+        mask: u32;
+        dir: vec2<f32>;
+        tint: vec4<u32>;
+        basis: mat2x2<f32>;
+        weights: array<f32, 3>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> output: Output;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn write_constants() {
+        output.count = COUNT;
+        output.mask = MASK;
+        output.dir = DIR;
+        output.tint = TINT;
+        output.basis = BASIS;
+        output.weights = WEIGHTS;
+    }
+});
+
+#[test]
+fn unsigned_and_composite_constants() {
+    use composite::*;
+
+    assert_eq!(COUNT, 1024);
+    assert_eq!(MASK, u32::MAX);
+    assert_eq!(DIR, Vec2::new(1.0, 0.0));
+    assert_eq!(TINT, Vec4::new(1, 2, 3, u32::MAX));
+    assert_eq!(BASIS.cols, [Vec2::new(0.0, 1.0), Vec2::new(2.0, 0.5)]);
+    assert_eq!(WEIGHTS, [0.25, 0.5, 0.25]);
+
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut output = Buffer::<Output>::new();
+
+    write_constants(
+        write_constants::Bindings {
+            output: &mut output,
+        },
+        Dispatch::new(1, 1, 1),
+    );
+
+    // the values the shader sees are the generated ones
+    assert_eq!(output.count, COUNT);
+    assert_eq!(output.mask, MASK);
+    assert_eq!(output.dir, DIR);
+    assert_eq!(output.tint, TINT);
+    assert_eq!(output.basis, BASIS);
+    assert_eq!(output.weights, WEIGHTS);
+}
@@ -0,0 +1,79 @@
+use shatter::*;
+
+/// Declared by hand to derive `Default`, the macro only checks its layout.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Particle {
+    pub position: PackedVec3<f32>,
+    pub hits: u32,
+    pub velocity: Vec3<f32>,
+}
+
+wgsl! {
+    #![extern_type(Particle)]
+
+    // an atomic in the tail of a vec3 followed by a vec3, items of 32 bytes
+    // aligned to 16
+    struct Particle {
+        position: vec3<f32>;
+        hits: atomic<u32>;
+        velocity: vec3<f32>;
+    };
+
+    [[block]]
+    struct Particles {
+        particles: array<Particle>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> particles: Particles;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn step([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        let velocity = particles.particles[id.x].velocity;
+
+        particles.particles[id.x].position = particles.particles[id.x].position + velocity;
+        let hits = atomicAdd(&particles.particles[id.x].hits, 1u);
+    }
+}
+
+fn particle(i: u32) -> Particle {
+    let i = i as f32;
+
+    Particle {
+        position: PackedVec3::new(i, 0.0, 0.0),
+        velocity: Vec3::new(1.0, 2.0, i),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn extern_type_layout() {
+    assert_eq!(<Particles as BufferVec>::ITEM_STRIDE, 32);
+    assert_eq!(<Particles as BufferVec>::ITEM_ALIGN, 16);
+    assert_eq!(Particle::LAYOUT.field("hits").unwrap().offset, 12);
+}
+
+#[test]
+fn extern_type() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut particles = Buffer::<Particles>::new();
+    particles.extend((0..100).map(particle));
+
+    step(
+        step::Bindings {
+            particles: &mut particles,
+        },
+        Dispatch::new(100, 1, 1),
+    );
+
+    for (i, particle) in particles.particles.iter().enumerate() {
+        let i = i as f32;
+
+        assert_eq!(particle.position, PackedVec3::new(i + 1.0, 2.0, i));
+        assert_eq!(particle.hits, 1);
+    }
+}
@@ -0,0 +1,64 @@
+use shatter::*;
+
+wgsl! {
+    for SIZE in [1, 2, 4];
+
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(SIZE)]]
+    fn fill([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = u32(SIZE);
+    }
+
+    [[stage(compute), workgroup_size(SIZE)]]
+    fn noop() {}
+}
+
+#[test]
+fn variants() {
+    assert_eq!(fill_1::WORK_GROUP_SIZE, WorkGroupSize::new(1, 1, 1));
+    assert_eq!(fill_2::WORK_GROUP_SIZE, WorkGroupSize::new(2, 1, 1));
+    assert_eq!(fill_4::WORK_GROUP_SIZE, WorkGroupSize::new(4, 1, 1));
+
+    assert_eq!(fill::SIZE, &[1, 2, 4]);
+    assert!(fill::with_size(3).is_none());
+
+    for &size in fill::SIZE {
+        let shader = fill::with_size(size).unwrap();
+
+        assert_eq!(shader.entry_point(), "fill");
+        assert_eq!(shader.work_group_size(), WorkGroupSize::new(size, 1, 1));
+    }
+
+    assert_eq!(noop::with_size(2).unwrap().work_group_size().x, 2);
+}
+
+#[test]
+fn family() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    for &size in fill::SIZE {
+        let mut values = Buffer::<Values>::new();
+
+        for _ in 0..size {
+            values.push(0);
+        }
+
+        fill::with_size(size).unwrap().dispatch(
+            fill::Bindings {
+                values: &mut values,
+            },
+            Dispatch::new(1, 1, 1),
+        );
+
+        assert!(values.values.iter().all(|&value| value == size));
+    }
+}
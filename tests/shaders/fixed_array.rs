@@ -0,0 +1,38 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Data {
+        values: array<f32, 4>;
+        sum: f32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> data: Data;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn sum() {
+        var sum = 0.0;
+
+        for (var i = 0; i < 4; i = i + 1) {
+            sum = sum + data.values[i];
+        }
+
+        data.sum = sum;
+    }
+}
+
+#[test]
+fn fixed_array() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut data = Buffer::<Data>::new();
+
+    data.values = [1.0, 2.0, 3.0, 4.0];
+
+    sum(sum::Bindings { data: &mut data }, Dispatch::new(1, 1, 1));
+
+    assert_eq!(data.sum, 10.0);
+}
@@ -0,0 +1,22 @@
+use shatter::*;
+
+wgsl!(include "tests/shaders/include.wgsl");
+
+#[test]
+fn include() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut values = Buffer::<Values>::new();
+    values.extend_from_slice(&[1.0, 2.0, 3.0]);
+
+    square(
+        square::Bindings {
+            values: &mut values,
+        },
+        square::dispatch_for(3),
+    );
+
+    assert_eq!(values.as_slice(), &[1.0, 4.0, 9.0]);
+}
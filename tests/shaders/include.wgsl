@@ -0,0 +1,18 @@
+// kept outside of `include.rs` for syntax highlighting
+[[block]]
+struct Values {
+    values: array<f32>;
+};
+
+[[group(0), binding(0)]]
+var<storage, read_write> values: Values;
+
+/* block comments are skipped /* even nested */ */
+[[stage(compute), workgroup_size(64)]]
+fn square([[builtin(global_invocation_id)]] id: vec3<u32>) {
+    if (id.x >= arrayLength(&values.values)) {
+        return;
+    }
+
+    values.values[id.x] = values.values[id.x] * values.values[id.x];
+}
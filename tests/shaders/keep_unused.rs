@@ -0,0 +1,58 @@
+use shatter::*;
+
+wgsl! {
+    #![keep_unused_bindings]
+
+    [[block]]
+    struct Uniforms {
+        scale: f32;
+    };
+
+    // not read yet, but bound so the host code doesn't change
+    [[group(0), binding(0)]]
+    var<uniform> uniforms: Uniforms;
+
+    [[block]]
+    struct Output {
+        value: f32;
+    };
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> output: Output;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn scale() {
+        output.value = 2.0;
+    }
+}
+
+#[test]
+fn keep_unused_bindings() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let uniforms = Buffer::<Uniforms>::new();
+    let mut output = Buffer::<Output>::new();
+
+    assert_eq!(
+        scale::Bindings {
+            uniforms: &uniforms,
+            output: &mut output,
+        }
+        .bind_group_layout_descriptors()[0]
+            .entries
+            .len(),
+        2
+    );
+
+    scale(
+        scale::Bindings {
+            uniforms: &uniforms,
+            output: &mut output,
+        },
+        Dispatch::new(1, 1, 1),
+    );
+
+    assert_eq!(output.value, 2.0);
+}
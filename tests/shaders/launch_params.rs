@@ -0,0 +1,96 @@
+use shatter::*;
+
+wgsl! {
+    #![auto_guard, dispatch_offset]
+
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn increment([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        let i = shatter_dispatch_offset() + id.x;
+        values.values[i] = values.values[i] + 1u;
+    }
+}
+
+wgsl!(mod plain {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn increment([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] + 1u;
+    }
+});
+
+#[test]
+fn options_share_one_block() {
+    let params = <increment::Shader as ComputeShader>::LAUNCH_PARAMS;
+
+    assert_eq!(
+        params,
+        LaunchParams {
+            guard_count: true,
+            dispatch_offset: true,
+        }
+    );
+
+    let source = <increment::Shader as ComputeShader>::SOURCE;
+    assert_eq!(source.matches("var < uniform >").count(), 1);
+    assert_eq!(source.matches("struct ShatterLaunch").count(), 1);
+}
+
+#[test]
+fn no_options_no_binding() {
+    assert!(<plain::increment::Shader as ComputeShader>::LAUNCH_PARAMS.is_empty());
+
+    let source = <plain::increment::Shader as ComputeShader>::SOURCE;
+    assert!(!source.contains("shatter"));
+}
+
+#[test]
+fn segments_read_their_offset() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut values = Buffer::<Values>::new();
+    values.extend_from_slice(&[0; 1000]);
+
+    let mut segments = util::SegmentedDispatch::new(1000, 300);
+
+    // every segment but the last ends inside a work group
+    while segments
+        .step(|segment| {
+            increment::build(increment::Bindings {
+                values: &mut values,
+            })
+            .with_dispatch_offset(segment.offset as u32)
+            .with_guard_count(segment.len as u32)
+            .dispatch(increment::dispatch_for(segment.len));
+        })
+        .is_some()
+    {}
+
+    assert_eq!(values.as_slice(), &[1; 1000]);
+
+    // the offset defaults to zero, the count to the array length
+    increment(
+        increment::Bindings {
+            values: &mut values,
+        },
+        increment::dispatch_for(1000),
+    );
+
+    assert_eq!(values.as_slice(), &[2; 1000]);
+}
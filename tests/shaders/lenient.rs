@@ -0,0 +1,71 @@
+use shatter::*;
+
+wgsl! {
+    #![lenient_bindings]
+
+    [[block]]
+    struct Values {
+        values: array<f32>;
+    };
+
+    [[block]]
+    struct Debug {
+        enabled: u32;
+        scale: f32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    // only read when `enabled` is set, may be left unbound
+    [[group(0), binding(1)]]
+    var<storage, read> debug: Debug;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn double([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        var scale: f32 = 2.0;
+
+        if (debug.enabled != 0u) {
+            scale = debug.scale;
+        }
+
+        values.values[id.x] = values.values[id.x] * scale;
+    }
+}
+
+#[test]
+fn lenient_bindings() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut values = Buffer::<Values>::new();
+
+    for i in 1..=3 {
+        values.push(i as f32);
+    }
+
+    double(
+        double::Bindings {
+            values: &mut values,
+            debug: None,
+        },
+        Dispatch::new(3, 1, 1),
+    );
+
+    assert_eq!(&values.values, &[2.0, 4.0, 6.0]);
+
+    let mut debug = Buffer::<Debug>::new();
+    debug.enabled = 1;
+    debug.scale = 3.0;
+
+    double(
+        double::Bindings {
+            values: &mut values,
+            debug: Some(&debug),
+        },
+        Dispatch::new(3, 1, 1),
+    );
+
+    assert_eq!(&values.values, &[6.0, 12.0, 18.0]);
+}
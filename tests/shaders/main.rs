@@ -0,0 +1,53 @@
+//! Gallery of the WGSL constructs supported by `wgsl!`.
+//!
+//! Every module contains a single small shader together with a host side
+//! assertion. Tests are skipped when no adapter is available.
+//!
+//! Constructs that are intentionally unsupported live in `ui/` and pin the
+//! diagnostics emitted by the macro.
+
+mod aliased_bindings;
+mod auto_guard;
+mod constants;
+mod extern_type;
+mod family;
+mod fixed_array;
+mod include;
+mod keep_unused;
+mod launch_params;
+mod lenient;
+mod matrix;
+mod multiple_entry_points;
+mod multiple_groups;
+mod names;
+mod namespace;
+mod nested_struct;
+mod pipeline;
+mod push_constants;
+mod render;
+mod sampled_texture;
+mod select;
+mod shared_struct;
+mod storage_formats;
+mod storage_texture;
+mod storage_vec;
+mod struct_layout;
+mod uniform;
+mod uniform_array;
+mod workgroup_array;
+
+/// Returns `true` if an adapter is available, otherwise the calling test should return early.
+pub fn has_adapter() -> bool {
+    if shatter::Instance::try_global().is_some() {
+        true
+    } else {
+        eprintln!("no adapter available, skipping");
+        false
+    }
+}
+
+#[test]
+fn unsupported() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/shaders/ui/*.rs");
+}
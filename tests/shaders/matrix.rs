@@ -0,0 +1,93 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Uniforms {
+        transform: mat3x3<f32>;
+        offset: vec3<f32>;
+        projection: mat4x4<f32>;
+        rotation: mat2x2<f32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<uniform> uniforms: Uniforms;
+
+    [[block]]
+    struct Output {
+        transform: mat3x3<f32>;
+        offset: vec3<f32>;
+        transformed: vec3<f32>;
+        projected: vec4<f32>;
+        rotated: vec2<f32>;
+    };
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> output: Output;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn copy() {
+        output.transform = uniforms.transform;
+        output.offset = uniforms.offset;
+        output.transformed = uniforms.transform * uniforms.offset;
+        output.projected = uniforms.projection * vec4<f32>(uniforms.offset, 1.0);
+        output.rotated = uniforms.rotation * uniforms.offset.xy;
+    }
+}
+
+#[test]
+fn matrix_layout() {
+    assert_eq!(std::mem::size_of::<Mat3<f32>>(), 48);
+    assert_eq!(std::mem::align_of::<Mat3<f32>>(), 16);
+    assert_eq!(std::mem::offset_of!(Uniforms, offset), 48);
+    assert_eq!(std::mem::offset_of!(Uniforms, projection), 64);
+    assert_eq!(std::mem::offset_of!(Uniforms, rotation), 128);
+
+    assert_eq!(
+        Mat3::<f32>::identity() * Vec3::new(1.0, 2.0, 3.0),
+        Vec3::new(1.0, 2.0, 3.0)
+    );
+}
+
+#[test]
+fn matrix() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let transform = Mat3::from_cols(
+        Vec3::new(1.0, 2.0, 3.0),
+        Vec3::new(4.0, 5.0, 6.0),
+        Vec3::new(7.0, 8.0, 9.0),
+    );
+    let offset = Vec3::new(-1.0, 0.5, 2.0);
+
+    let mut projection = Mat4::<f32>::identity();
+    projection.cols[3] = Vec4::new(10.0, 20.0, 30.0, 1.0);
+
+    let rotation = Mat2::from_cols(Vec2::new(0.0, 1.0), Vec2::new(-1.0, 0.0));
+
+    let mut uniforms = Buffer::<Uniforms>::new();
+    uniforms.transform = transform;
+    uniforms.offset = offset;
+    uniforms.projection = projection;
+    uniforms.rotation = rotation;
+
+    let mut output = Buffer::<Output>::new();
+
+    copy(
+        copy::Bindings {
+            uniforms: &uniforms,
+            output: &mut output,
+        },
+        Dispatch::new(1, 1, 1),
+    );
+
+    assert_eq!(output.transform, transform);
+    assert_eq!(output.offset, offset);
+    assert_eq!(output.transformed, transform * offset);
+    assert_eq!(
+        output.projected,
+        projection * Vec4::new(offset.x, offset.y, offset.z, 1.0)
+    );
+    assert_eq!(output.rotated, rotation * Vec2::new(offset.x, offset.y));
+}
@@ -0,0 +1,44 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Counter {
+        value: i32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> counter: Counter;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn increment() {
+        counter.value = counter.value + 1;
+    }
+
+    [[stage(compute), workgroup_size(1)]]
+    fn decrement() {
+        counter.value = counter.value - 1;
+    }
+}
+
+#[test]
+fn multiple_entry_points() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut counter = Buffer::<Counter>::new();
+
+    increment::build(increment::Bindings {
+        counter: &mut counter,
+    })
+    .dispatch_multiple(&[Dispatch::new(1, 1, 1); 3]);
+
+    decrement(
+        decrement::Bindings {
+            counter: &mut counter,
+        },
+        Dispatch::new(1, 1, 1),
+    );
+
+    assert_eq!(counter.value, 2);
+}
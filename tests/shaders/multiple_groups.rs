@@ -0,0 +1,46 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Input {
+        value: i32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read> input: Input;
+
+    [[block]]
+    struct Output {
+        value: i32;
+    };
+
+    [[group(1), binding(0)]]
+    var<storage, read_write> output: Output;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn negate() {
+        output.value = -input.value;
+    }
+}
+
+#[test]
+fn multiple_groups() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut input = Buffer::<Input>::new();
+    let mut output = Buffer::<Output>::new();
+
+    input.value = 7;
+
+    negate(
+        negate::Bindings {
+            input: &input,
+            output: &mut output,
+        },
+        Dispatch::new(1, 1, 1),
+    );
+
+    assert_eq!(output.value, -7);
+}
@@ -0,0 +1,70 @@
+//! WGSL names breaking Rust conventions or clashing with Rust keywords.
+#![deny(warnings)]
+
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct counts {
+        match: u32;
+        self: u32;
+        Total: u32;
+    };
+
+    struct item {
+        mod: u32;
+    };
+
+    [[block]]
+    struct Items {
+        items: array<item>;
+    };
+
+    let base: u32 = 10u;
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> bindings: counts;
+
+    [[group(0), binding(1)]]
+    var<storage, read> impl: Items;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn Count() {
+        bindings.match = base + impl.items[0].mod;
+        bindings.self = arrayLength(&impl.items);
+        bindings.Total = bindings.match + bindings.self;
+    }
+}
+
+#[test]
+fn names() {
+    // keywords are raw, those that can't be are suffixed with `_`
+    assert_eq!(
+        counts::default(),
+        counts {
+            r#match: 0,
+            self_: 0,
+            Total: 0,
+        }
+    );
+
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut output = Buffer::<counts>::new();
+    let mut items = Buffer::<Items>::new();
+    items.extend_from_slice(&[item { r#mod: 5 }, item { r#mod: 7 }]);
+
+    Count(
+        Count::Bindings {
+            bindings: &mut output,
+            r#impl: &items,
+        },
+        Dispatch::new(1, 1, 1),
+    );
+
+    assert_eq!(output.r#match, 15);
+    assert_eq!(output.self_, 2);
+    assert_eq!(output.Total, 17);
+}
@@ -0,0 +1,61 @@
+use shatter::*;
+
+wgsl!(mod double {
+    [[block]]
+    struct Values {
+        values: array<f32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn step([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] * 2.0;
+    }
+
+    [[stage(compute), workgroup_size(64)]]
+    fn offset([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] + 1.0;
+    }
+});
+
+// same names as `double`, with the source of `include.rs`
+wgsl!(mod square {
+    include "tests/shaders/include.wgsl"
+});
+
+#[test]
+fn namespace() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut doubled = Buffer::<double::Values>::new();
+    doubled.extend_from_slice(&[1.0, 2.0, 3.0]);
+
+    // both entry points share the bindings of `step`
+    double::dispatch_selected_batch(
+        double::step::Bindings {
+            values: &mut doubled,
+        },
+        &[
+            (double::ShaderSelect::Step, Dispatch::new(1, 1, 1)),
+            (double::ShaderSelect::Offset, Dispatch::new(1, 1, 1)),
+        ],
+    );
+
+    assert_eq!(doubled.as_slice(), &[3.0, 5.0, 7.0]);
+
+    let mut squared = Buffer::<square::Values>::new();
+    squared.extend_from_slice(&[1.0, 2.0, 3.0]);
+
+    square::square(
+        square::square::Bindings {
+            values: &mut squared,
+        },
+        square::square::dispatch_for(3),
+    );
+
+    assert_eq!(squared.as_slice(), &[1.0, 4.0, 9.0]);
+}
@@ -0,0 +1,37 @@
+use shatter::*;
+
+wgsl! {
+    struct Pair {
+        a: u32;
+        b: u32;
+    };
+
+    [[block]]
+    struct Data {
+        pair: Pair;
+        sum: u32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> data: Data;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn add() {
+        data.sum = data.pair.a + data.pair.b;
+    }
+}
+
+#[test]
+fn nested_struct() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut data = Buffer::<Data>::new();
+
+    data.pair = Pair { a: 2, b: 3 };
+
+    add(add::Bindings { data: &mut data }, Dispatch::new(1, 1, 1));
+
+    assert_eq!(data.sum, 5);
+}
@@ -0,0 +1,90 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn seed([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x < arrayLength(&values.values)) {
+            values.values[id.x] = id.x * 3u;
+        }
+    }
+
+    [[stage(compute), workgroup_size(64)]]
+    fn scale([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x < arrayLength(&values.values)) {
+            values.values[id.x] = values.values[id.x] * 2u;
+        }
+    }
+
+    [[stage(compute), workgroup_size(64)]]
+    fn bias([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x < arrayLength(&values.values)) {
+            values.values[id.x] = values.values[id.x] + 7u;
+        }
+    }
+
+    pipeline! { seed -> scale -> bias }
+}
+
+fn values() -> Buffer<Values> {
+    let mut values = Buffer::<Values>::new();
+    values.extend((0..1000).map(|i| i * 11));
+
+    values
+}
+
+#[test]
+fn passes() {
+    assert_eq!(Pipeline::PASSES, &["seed", "scale", "bias"]);
+}
+
+#[test]
+fn run_matches_manual_passes() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut manual = values();
+    seed(
+        seed::Bindings {
+            values: &mut manual,
+        },
+        seed::dispatch_for(1000),
+    );
+    scale(
+        scale::Bindings {
+            values: &mut manual,
+        },
+        scale::dispatch_for(1000),
+    );
+    bias(
+        bias::Bindings {
+            values: &mut manual,
+        },
+        bias::dispatch_for(1000),
+    );
+
+    let mut piped = values();
+    Pipeline::run(
+        pipeline::Bindings { values: &mut piped },
+        [
+            seed::dispatch_for(1000),
+            scale::dispatch_for(1000),
+            bias::dispatch_for(1000),
+        ],
+    );
+
+    assert_eq!(piped.values[..], manual.values[..]);
+    assert!(piped
+        .values
+        .iter()
+        .enumerate()
+        .all(|(i, &value)| value == i as u32 * 6 + 7));
+}
@@ -0,0 +1,60 @@
+use shatter::*;
+
+wgsl! {
+    struct Params {
+        scale: f32;
+        offset: f32;
+    };
+
+    var<push_constant> params: Params;
+
+    [[block]]
+    struct Values {
+        values: array<f32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn affine([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] * params.scale + params.offset;
+    }
+}
+
+#[test]
+fn push_constants_layout() {
+    let _: fn(affine::PushConstants) -> Params = |params| params;
+    assert_eq!(std::mem::size_of::<affine::PushConstants>(), 8);
+}
+
+#[test]
+fn push_constants() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let device = &Instance::global().device;
+
+    // the global instance only has push constants if they were requested
+    if !device.features().contains(wgpu::Features::PUSH_CONSTANTS)
+        || device.limits().max_push_constant_size < 8
+    {
+        eprintln!("push constants not enabled, skipping");
+        return;
+    }
+
+    let mut values = Buffer::<Values>::new();
+    values.extend_from_slice(&[1.0; 64]);
+
+    for (scale, offset) in [(2.0, 1.0), (0.5, -1.0)] {
+        affine::build(affine::Bindings {
+            values: &mut values,
+        })
+        .push_constants(&Params { scale, offset })
+        .dispatch(Dispatch::new(1, 1, 1));
+    }
+
+    values.download();
+    assert_eq!(values.as_slice(), &[0.5; 64]);
+}
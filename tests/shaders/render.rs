@@ -0,0 +1,53 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Tint {
+        color: vec4<f32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<uniform> tint: Tint;
+
+    [[stage(vertex)]]
+    fn fullscreen([[builtin(vertex_index)]] index: u32) -> [[builtin(position)]] vec4<f32> {
+        let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+        return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    }
+
+    [[stage(fragment)]]
+    fn shade() -> [[location(0)]] vec4<f32> {
+        return tint.color;
+    }
+}
+
+#[test]
+fn render() {
+    assert_eq!(
+        <shade::Shader as RenderShader>::VERTEX_ENTRY_POINT,
+        "fullscreen"
+    );
+    assert_eq!(
+        <shade::Shader as RenderShader>::FRAGMENT_ENTRY_POINT,
+        "shade"
+    );
+
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut tint = Buffer::<Tint>::new();
+    tint.color = Vec4::new(0.0, 1.0, 0.0, 1.0);
+
+    let mut texture = Texture2d::<Rgba8Unorm>::new(4, 4);
+
+    shade::build(shade::Bindings { tint: &tint })
+        .with_color_target(&mut texture)
+        .draw_fullscreen_triangle();
+
+    for y in 0..4 {
+        for x in 0..4 {
+            assert_eq!(texture[(x, y)], Rgba8U::rgba(0, 255, 0, 255));
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use shatter::*;
+
+wgsl! {
+    [[group(0), binding(0)]]
+    var input: texture_2d<f32>;
+
+    [[group(0), binding(1)]]
+    var input_sampler: sampler;
+
+    [[group(0), binding(2)]]
+    var output: texture_storage_2d<rgba8unorm, write>;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn upscale([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        let uv = (vec2<f32>(id.xy) + 0.5) / vec2<f32>(textureDimensions(output));
+        let color = textureSampleLevel(input, input_sampler, uv, 0.0);
+
+        textureStore(output, vec2<i32>(id.xy), color);
+    }
+}
+
+#[test]
+fn sampled_texture() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut input = Texture2d::<Rgba8Unorm>::new(2, 2);
+    input[(0, 0)] = Rgba8U::rgba(255, 0, 0, 255);
+    input[(1, 0)] = Rgba8U::rgba(0, 255, 0, 255);
+    input[(0, 1)] = Rgba8U::rgba(0, 0, 255, 255);
+    input[(1, 1)] = Rgba8U::WHITE;
+
+    let sampler = Sampler::nearest();
+    let mut output = Texture2d::<Rgba8Unorm>::new(4, 4);
+
+    upscale(
+        upscale::Bindings {
+            input: &input,
+            input_sampler: &sampler,
+            output: &mut output,
+        },
+        Dispatch::new(4, 4, 1),
+    );
+
+    for y in 0..4 {
+        for x in 0..4 {
+            assert_eq!(
+                output[(x, y)],
+                input[(x / 2, y / 2)],
+                "texel ({}, {})",
+                x,
+                y
+            );
+        }
+    }
+}
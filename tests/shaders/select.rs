@@ -0,0 +1,118 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Output {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> output: Output;
+
+    [[stage(compute), workgroup_size(4)]]
+    fn lambert([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        output.values[id.x] = 1u;
+    }
+
+    [[stage(compute), workgroup_size(4)]]
+    fn phong([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        output.values[4u + id.x] = 2u;
+    }
+
+    [[stage(compute), workgroup_size(4)]]
+    fn toon([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        output.values[8u + id.x] = 3u;
+    }
+}
+
+fn output() -> Buffer<Output> {
+    let mut output = Buffer::<Output>::new();
+
+    for _ in 0..12 {
+        output.push(0);
+    }
+
+    output
+}
+
+fn expected(select: ShaderSelect) -> (usize, u32) {
+    match select {
+        ShaderSelect::Lambert => (0, 1),
+        ShaderSelect::Phong => (4, 2),
+        ShaderSelect::Toon => (8, 3),
+    }
+}
+
+#[test]
+fn variants() {
+    assert_eq!(
+        ShaderSelect::ALL,
+        &[
+            ShaderSelect::Lambert,
+            ShaderSelect::Phong,
+            ShaderSelect::Toon
+        ]
+    );
+
+    for &select in ShaderSelect::ALL {
+        let shader = select.shader();
+
+        assert_eq!(shader.entry_point(), select.entry_point());
+        assert_eq!(shader.work_group_size(), WorkGroupSize::new(4, 1, 1));
+    }
+
+    assert_eq!(ShaderSelect::Phong.entry_point(), "phong");
+}
+
+#[test]
+fn dispatch_each() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut output = output();
+
+    for &select in ShaderSelect::ALL {
+        dispatch_selected(
+            select,
+            lambert::Bindings {
+                output: &mut output,
+            },
+            Dispatch::new(1, 1, 1),
+        );
+
+        let (start, signature) = expected(select);
+        assert!(output.values[start..start + 4]
+            .iter()
+            .all(|&value| value == signature));
+    }
+}
+
+#[test]
+fn dispatch_batch() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut output = output();
+
+    let batch = ShaderSelect::ALL
+        .iter()
+        .map(|&select| (select, Dispatch::new(1, 1, 1)))
+        .collect::<Vec<_>>();
+
+    dispatch_selected_batch(
+        phong::Bindings {
+            output: &mut output,
+        },
+        &batch,
+    );
+
+    for &select in ShaderSelect::ALL {
+        let (start, signature) = expected(select);
+
+        assert!(output.values[start..start + 4]
+            .iter()
+            .all(|&value| value == signature));
+    }
+}
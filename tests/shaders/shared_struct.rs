@@ -0,0 +1,84 @@
+use shatter::*;
+
+wgsl! {
+    struct Params {
+        scale: f32;
+        offset: f32;
+    };
+
+    [[block]]
+    struct Uniforms {
+        params: Params;
+    };
+
+    [[block]]
+    struct ParamsArray {
+        params: array<Params>;
+    };
+
+    [[block]]
+    struct Frame {
+        params: Params;
+        result: f32;
+    };
+
+    // nested in a uniform
+    [[group(0), binding(0)]]
+    var<uniform> uniforms: Uniforms;
+
+    // nested in a runtime sized storage array
+    [[group(0), binding(1)]]
+    var<storage, read_write> history: ParamsArray;
+
+    // nested in a storage struct, also bound as a second global
+    [[group(0), binding(2)]]
+    var<storage, read_write> frame: Frame;
+
+    [[group(0), binding(3)]]
+    var<storage, read> previous_frame: Frame;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn record() {
+        history.params[0] = previous_frame.params;
+        frame.params = uniforms.params;
+        frame.result = uniforms.params.scale + uniforms.params.offset;
+    }
+}
+
+#[test]
+fn shared_struct() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let params = Params {
+        scale: 2.0,
+        offset: 1.0,
+    };
+
+    let mut uniforms = Buffer::<Uniforms>::new();
+    let mut history = Buffer::<ParamsArray>::new();
+    let mut frame = Buffer::<Frame>::new();
+    let mut previous_frame = Buffer::<Frame>::new();
+
+    uniforms.params = params;
+    history.push(Params::default());
+    previous_frame.params = Params {
+        scale: 4.0,
+        offset: 0.5,
+    };
+
+    record(
+        record::Bindings {
+            uniforms: &uniforms,
+            history: &mut history,
+            frame: &mut frame,
+            previous_frame: &previous_frame,
+        },
+        Dispatch::new(1, 1, 1),
+    );
+
+    assert_eq!(history.params[0], previous_frame.params);
+    assert_eq!(frame.params, params);
+    assert_eq!(frame.result, 3.0);
+}
@@ -0,0 +1,61 @@
+//! Every storage texture format bound to the `Texture2d` of the matching
+//! format, checked at compile time.
+
+use shatter::*;
+
+macro_rules! storage_format {
+    ($name:ident, $wgsl_format:ident, $format:ident, $texel:ty, $one:tt) => {
+        mod $name {
+            use shatter::*;
+
+            wgsl! {
+                [[group(0), binding(0)]]
+                var output: texture_storage_2d<$wgsl_format, write>;
+
+                [[stage(compute), workgroup_size(1)]]
+                fn fill([[builtin(global_invocation_id)]] id: vec3<u32>) {
+                    textureStore(output, vec2<i32>(id.xy), $texel($one, $one, $one, $one));
+                }
+            }
+
+            #[allow(dead_code)]
+            pub fn bindings(output: &mut Texture2d<$format>) -> fill::Bindings<'_> {
+                fill::Bindings { output }
+            }
+        }
+    };
+}
+
+storage_format!(rgba8unorm, rgba8unorm, Rgba8Unorm, vec4<f32>, 1.0);
+storage_format!(rgba8snorm, rgba8snorm, Rgba8Snorm, vec4<f32>, 1.0);
+storage_format!(rgba8uint, rgba8uint, Rgba8Uint, vec4<u32>, 1u);
+storage_format!(rgba8sint, rgba8sint, Rgba8Sint, vec4<i32>, 1);
+storage_format!(rgba16uint, rgba16uint, Rgba16Uint, vec4<u32>, 1u);
+storage_format!(rgba16sint, rgba16sint, Rgba16Sint, vec4<i32>, 1);
+storage_format!(rgba16float, rgba16float, Rgba16Float, vec4<f32>, 1.0);
+storage_format!(r32uint, r32uint, R32Uint, vec4<u32>, 1u);
+storage_format!(r32sint, r32sint, R32Sint, vec4<i32>, 1);
+storage_format!(r32float, r32float, R32Float, vec4<f32>, 1.0);
+storage_format!(rg32uint, rg32uint, Rg32Uint, vec4<u32>, 1u);
+storage_format!(rg32sint, rg32sint, Rg32Sint, vec4<i32>, 1);
+storage_format!(rg32float, rg32float, Rg32Float, vec4<f32>, 1.0);
+storage_format!(rgba32uint, rgba32uint, Rgba32Uint, vec4<u32>, 1u);
+storage_format!(rgba32sint, rgba32sint, Rgba32Sint, vec4<i32>, 1);
+storage_format!(rgba32float, rgba32float, Rgba32Float, vec4<f32>, 1.0);
+
+#[test]
+fn r32float() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut texture = Texture2d::<R32Float>::new(4, 4);
+
+    r32float::fill(r32float::bindings(&mut texture), Dispatch::new(4, 4, 1));
+
+    for y in 0..4 {
+        for x in 0..4 {
+            assert_eq!(texture[(x, y)], R32::r(1.0));
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use shatter::*;
+
+wgsl! {
+    [[group(0), binding(0)]]
+    var output: texture_storage_2d<rgba8unorm, write>;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn fill([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        textureStore(output, vec2<i32>(id.xy), vec4<f32>(1.0, 0.0, 0.0, 1.0));
+    }
+}
+
+#[test]
+fn storage_texture() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut texture = Texture2d::<Rgba8Unorm>::new(4, 4);
+
+    fill(
+        fill::Bindings {
+            output: &mut texture,
+        },
+        Dispatch::new(4, 4, 1),
+    );
+
+    for y in 0..4 {
+        for x in 0..4 {
+            assert_eq!(texture[(x, y)], Rgba8U::rgba(255, 0, 0, 255));
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn double([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] * 2u;
+    }
+}
+
+#[test]
+fn storage_vec() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut values = Buffer::<Values>::new();
+
+    for i in 1..=4 {
+        values.push(i);
+    }
+
+    let dispatch = Dispatch::new(values.len() as u32, 1, 1);
+
+    double(
+        double::Bindings {
+            values: &mut values,
+        },
+        dispatch,
+    );
+
+    assert_eq!(&values.values, &[2, 4, 6, 8]);
+}
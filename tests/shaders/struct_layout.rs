@@ -0,0 +1,96 @@
+use shatter::*;
+
+wgsl! {
+    // a scalar in the tail of a vec3, a matrix of padded columns and an
+    // explicitly sized member, none of which repr(C) reproduces on its own
+    struct Body {
+        position: vec3<f32>;
+        mass: f32;
+        transform: mat3x3<f32>;
+        [[size(16)]] weight: f32;
+        scale: vec2<f32>;
+        flags: u32;
+        normal: vec3<f32>;
+    };
+
+    [[block]]
+    struct Bodies {
+        bodies: array<Body>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> bodies: Bodies;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn integrate([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&bodies.bodies)) {
+            return;
+        }
+
+        let body = bodies.bodies[id.x];
+
+        bodies.bodies[id.x].position = body.transform * body.position * body.mass;
+        bodies.bodies[id.x].normal = body.normal * body.weight + vec3<f32>(body.scale, 0.0);
+        bodies.bodies[id.x].flags = body.flags + 1u;
+    }
+}
+
+fn body(i: u32) -> Body {
+    let i = i as f32;
+
+    Body {
+        position: PackedVec3::new(1.0, 2.0, 3.0),
+        mass: i,
+        transform: Mat3::from_cols(
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(0.0, 3.0, 0.0),
+            Vec3::new(0.0, 0.0, 4.0),
+        ),
+        weight: 0.5,
+        scale: Vec2::new(i, -i),
+        flags: 7,
+        normal: Vec3::new(2.0, 4.0, 6.0),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn struct_layout_matches_wgsl() {
+    assert_eq!(std::mem::size_of::<Body>(), 112);
+    assert_eq!(std::mem::offset_of!(Body, mass), 12);
+    assert_eq!(std::mem::offset_of!(Body, transform), 16);
+    assert_eq!(std::mem::offset_of!(Body, scale), 80);
+    assert_eq!(std::mem::offset_of!(Body, normal), 96);
+
+    // padding is skipped by the comparison
+    let mut padded = body(1);
+    padded._pad0 = [0xff; 12];
+    assert_eq!(padded, body(1));
+}
+
+#[test]
+fn struct_layout() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut bodies = Buffer::<Bodies>::new();
+    bodies.extend((0..100).map(body));
+
+    integrate(
+        integrate::Bindings {
+            bodies: &mut bodies,
+        },
+        integrate::dispatch_for(100),
+    );
+
+    for (i, body) in bodies.bodies.iter().enumerate() {
+        let i = i as f32;
+
+        assert_eq!(body.position, PackedVec3::new(2.0 * i, 6.0 * i, 12.0 * i));
+        assert_eq!(body.mass, i);
+        assert_eq!(body.weight, 0.5);
+        assert_eq!(body.flags, 8);
+        assert_eq!(body.normal, Vec3::new(1.0 + i, 2.0 - i, 3.0));
+    }
+}
@@ -0,0 +1,20 @@
+use shatter::*;
+
+wgsl! {
+    [[group(0), binding(0)]]
+    var shadow_sampler: sampler_comparison;
+
+    [[group(0), binding(1)]]
+    var shadow_map: texture_depth_2d;
+
+    [[group(0), binding(2)]]
+    var output: texture_storage_2d<rgba8unorm, write>;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn shade([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        let lit = textureSampleCompareLevel(shadow_map, shadow_sampler, vec2<f32>(0.5), 0.5);
+        textureStore(output, vec2<i32>(id.xy), vec4<f32>(lit));
+    }
+}
+
+fn main() {}
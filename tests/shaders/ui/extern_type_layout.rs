@@ -0,0 +1,33 @@
+use shatter::*;
+
+// the `vec3` takes 16 bytes, but WGSL places `hits` in its tail
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Particle {
+    pub position: Vec3<f32>,
+    pub hits: u32,
+}
+
+wgsl! {
+    #![extern_type(Particle)]
+
+    struct Particle {
+        position: vec3<f32>;
+        hits: atomic<u32>;
+    };
+
+    [[block]]
+    struct Particles {
+        particles: array<Particle>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> particles: Particles;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn count([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        let hits = atomicAdd(&particles.particles[id.x].hits, 1u);
+    }
+}
+
+fn main() {}
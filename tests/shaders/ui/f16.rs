@@ -0,0 +1,18 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<f16>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn clear([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = 0.0;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use shatter::*;
+
+wgsl! {
+    [[stage(fragment)]]
+    fn frag() -> [[location(0)]] vec4<f32> {
+        return vec4<f32>(1.0);
+    }
+}
+
+fn main() {}
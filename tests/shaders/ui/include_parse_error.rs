@@ -0,0 +1,6 @@
+use shatter::*;
+
+// trybuild builds the ui tests in `target/tests/trybuild/shatter`
+wgsl!(include "../../../../tests/shaders/ui/include_parse_error.wgsl");
+
+fn main() {}
@@ -0,0 +1,12 @@
+[[block]]
+struct Values {
+    values: array<f32>;
+};
+
+[[group(0), binding(0)]]
+var<storage, read_write> values: Values;
+
+[[stage(compute), workgroup_size(1)]]
+fn main() {
+    values.values[0] = 1.0 +;
+}
@@ -0,0 +1,28 @@
+#![deny(warnings)]
+
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<f32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn blur([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        var sum: f32 = 0.0;
+
+        for (var y: u32 = 0u; y < 512u; y = y + 1u) {
+            for (var x: u32 = 0u; x < 512u; x = x + 1u) {
+                sum = sum + values.values[y * 512u + x];
+            }
+        }
+
+        values.values[id.x] = sum;
+    }
+}
+
+fn main() {}
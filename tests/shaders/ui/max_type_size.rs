@@ -0,0 +1,22 @@
+#![deny(warnings)]
+
+use shatter::*;
+
+wgsl! {
+    #![max_type_size = 1024]
+
+    [[block]]
+    struct Lut {
+        values: array<vec4<f32>, 256>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> lut: Lut;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn clear([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        lut.values[id.x] = vec4<f32>(0.0);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Output {
+        self: u32;
+        self_: u32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> output: Output;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn fill() {
+        output.self = 1u;
+    }
+}
+
+fn main() {}
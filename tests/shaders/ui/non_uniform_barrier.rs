@@ -0,0 +1,29 @@
+#![deny(warnings)]
+
+use shatter::*;
+
+wgsl! {
+    var<workgroup> tile: array<f32, 64>;
+
+    [[block]]
+    struct Values {
+        values: array<f32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn reduce([[builtin(local_invocation_index)]] local: u32) {
+        tile[local] = values.values[local];
+
+        if (local < 32u) {
+            workgroupBarrier();
+            tile[local] = tile[local] + tile[local + 32u];
+        }
+
+        values.values[local] = tile[local];
+    }
+}
+
+fn main() {}
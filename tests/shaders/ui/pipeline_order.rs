@@ -0,0 +1,28 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32, 64>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> particles: Values;
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> visible: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn simulate([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        particles.values[id.x] = id.x;
+    }
+
+    [[stage(compute), workgroup_size(64)]]
+    fn cull([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        visible.values[id.x] = particles.values[id.x] % 2u;
+    }
+
+    pipeline! { cull -> simulate }
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+use shatter::*;
+
+wgsl! {
+    fn is_even(n: u32) -> bool {
+        if (n == 0u) {
+            return true;
+        }
+
+        return is_odd(n - 1u);
+    }
+
+    fn is_odd(n: u32) -> bool {
+        if (n == 0u) {
+            return false;
+        }
+
+        return is_even(n - 1u);
+    }
+
+    [[group(0), binding(0)]]
+    var output: texture_storage_2d<rgba8unorm, write>;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn parity([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        textureStore(output, vec2<i32>(id.xy), vec4<f32>(f32(is_even(id.x))));
+    }
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Bindings {
+        value: u32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> bindings: Bindings;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn main() {
+        bindings.value = 1u;
+    }
+}
+
+fn main() {}
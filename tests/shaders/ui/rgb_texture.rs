@@ -0,0 +1,6 @@
+use shatter::*;
+
+fn main() {
+    // 3 channel colors have no texture format
+    let _texture = Texture2d::<Rgb8U>::new(4, 4);
+}
@@ -0,0 +1,22 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Table {
+        values: array<f32, 4>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> table: Table;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn clear() {
+        table.values[0] = 0.0;
+    }
+}
+
+fn main() {
+    let mut table = Buffer::immutable_shared(&Table { values: [0.0; 4] });
+
+    clear::Bindings { table: &mut table };
+}
@@ -0,0 +1,13 @@
+use shatter::*;
+
+wgsl! {
+    [[group(0), binding(0)]]
+    var output: texture_storage_2d<rg11b10float, write>;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn fill([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        textureStore(output, vec2<i32>(id.xy), vec4<f32>(1.0));
+    }
+}
+
+fn main() {}
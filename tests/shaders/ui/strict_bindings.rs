@@ -0,0 +1,32 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<f32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[group(0), binding(1)]]
+    var<storage, read> scale: Values;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn scale_values([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] * scale.values[id.x];
+    }
+}
+
+fn main() {
+    let mut values = Buffer::<Values>::new();
+
+    // optional bindings require `#![lenient_bindings]`
+    scale_values(
+        scale_values::Bindings {
+            values: &mut values,
+            scale: None,
+        },
+        Dispatch::new(1, 1, 1),
+    );
+}
@@ -0,0 +1,25 @@
+use shatter::*;
+
+wgsl! {
+    struct Light {
+        position: vec3<f32>;
+        intensity: f32;
+    };
+
+    let SUN: Light = Light(vec3<f32>(0.0, 100.0, 0.0), 1.0);
+
+    [[block]]
+    struct Output {
+        values: array<f32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> output: Output;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn main() {
+        output.values[0] = SUN.intensity;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Uniforms {
+        scale: f32;
+    };
+
+    [[group(0), binding(0)]]
+    var<uniform> uniforms: Uniforms;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn scale() {
+        let scale = uniforms.scale;
+    }
+}
+
+#[derive(BufferDataTransparent)]
+struct Untagged(Uniforms);
+
+#[derive(BufferDataTransparent)]
+#[repr(transparent)]
+struct Pair(Uniforms, ());
+
+fn main() {}
@@ -0,0 +1,25 @@
+#![deny(warnings)]
+
+use shatter::*;
+
+wgsl! {
+    struct Light {
+        color: u32;
+        intensity: f32;
+    };
+
+    [[block]]
+    struct LightArray {
+        lights: array<Light, 16>;
+    };
+
+    [[group(0), binding(0)]]
+    var<uniform> lights: LightArray;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn first() {
+        let color = lights.lights[0].color;
+    }
+}
+
+fn main() {}
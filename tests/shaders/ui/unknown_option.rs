@@ -0,0 +1,10 @@
+use shatter::*;
+
+wgsl! {
+    #![strict_bindings]
+
+    [[stage(compute), workgroup_size(1)]]
+    fn main() {}
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+#![deny(warnings)]
+
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Uniforms {
+        scale: f32;
+    };
+
+    // never read, `scale` uses a literal instead
+    [[group(0), binding(0)]]
+    var<uniform> uniforms: Uniforms;
+
+    [[block]]
+    struct Output {
+        value: f32;
+    };
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> output: Output;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn scale() {
+        output.value = 2.0;
+    }
+}
+
+fn main() {}
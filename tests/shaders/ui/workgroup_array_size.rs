@@ -0,0 +1,16 @@
+#![deny(warnings)]
+
+use shatter::*;
+
+wgsl! {
+    let TILE: u32 = 32u;
+
+    var<workgroup> tile: array<f32, TILE>;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn reduce([[builtin(local_invocation_index)]] local: u32) {
+        tile[local % TILE] = 1.0;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,46 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Uniforms {
+        scale: f32;
+    };
+
+    [[group(0), binding(0)]]
+    var<uniform> uniforms: Uniforms;
+
+    [[block]]
+    struct Output {
+        value: f32;
+    };
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> output: Output;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn scale() {
+        output.value = uniforms.scale * 2.0;
+    }
+}
+
+#[test]
+fn uniform() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut uniforms = Buffer::<Uniforms>::new();
+    let mut output = Buffer::<Output>::new();
+
+    uniforms.scale = 1.5;
+
+    scale(
+        scale::Bindings {
+            uniforms: &uniforms,
+            output: &mut output,
+        },
+        Dispatch::new(1, 1, 1),
+    );
+
+    assert_eq!(output.value, 3.0);
+}
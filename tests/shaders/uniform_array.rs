@@ -0,0 +1,80 @@
+use shatter::*;
+
+wgsl! {
+    struct Light {
+        color: u32;
+        intensity: f32;
+    };
+
+    // uniform arrays need a stride that's a multiple of 16
+    [[block]]
+    struct LightArray {
+        lights: [[stride(16)]] array<Light, 16>;
+    };
+
+    [[group(0), binding(0)]]
+    var<uniform> lights: LightArray;
+
+    [[block]]
+    struct Colors {
+        colors: array<u32, 16>;
+    };
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> colors: Colors;
+
+    [[stage(compute), workgroup_size(16)]]
+    fn echo([[builtin(local_invocation_index)]] i: u32) {
+        colors.colors[i] = lights.lights[i].color;
+    }
+}
+
+#[test]
+fn padded_elements_match_the_shader_stride() {
+    assert_eq!(std::mem::size_of::<LightStd140>(), 16);
+    assert_eq!(std::mem::size_of::<LightArray>(), 16 * 16);
+
+    let layout = LightArray::LAYOUT;
+    let field = layout.field("lights").unwrap();
+    assert_eq!(field.ty, "[[stride(16)]] array<Light, 16>");
+    assert_eq!(field.size, 16 * 16);
+
+    let light = Light {
+        color: 7,
+        intensity: 0.5,
+    };
+    let padded = LightStd140::from(light);
+
+    assert_eq!(padded.color, 7);
+    assert_eq!(Light::from(padded), light);
+}
+
+#[test]
+fn uniform_array() {
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut lights = Buffer::<LightArray>::new();
+    let mut colors = Buffer::<Colors>::new();
+
+    for i in 0..16 {
+        lights.lights[i] = Light {
+            color: 0xff00 + i as u32,
+            intensity: i as f32,
+        }
+        .into();
+    }
+
+    echo(
+        echo::Bindings {
+            lights: &lights,
+            colors: &mut colors,
+        },
+        Dispatch::new(1, 1, 1),
+    );
+
+    for i in 0..16 {
+        assert_eq!(colors.colors[i], 0xff00 + i as u32);
+    }
+}
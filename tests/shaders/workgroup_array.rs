@@ -0,0 +1,80 @@
+use shatter::*;
+
+wgsl! {
+    let TILE: u32 = 64u;
+
+    [[block]]
+    struct Values {
+        values: array<f32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read> input: Values;
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> sums: Values;
+
+    var<workgroup> tile: array<f32, TILE>;
+
+    [[stage(compute), workgroup_size(8, 8)]]
+    fn reduce(
+        [[builtin(local_invocation_index)]] local: u32,
+        [[builtin(workgroup_id)]] group: vec3<u32>,
+    ) {
+        tile[local] = input.values[group.x * TILE + local];
+
+        workgroupBarrier();
+
+        if (local == 0u) {
+            var sum = 0.0;
+
+            for (var i = 0u; i < TILE; i = i + 1u) {
+                sum = sum + tile[i];
+            }
+
+            sums.values[group.x] = sum;
+        }
+    }
+}
+
+const GROUPS: u32 = 4;
+
+#[test]
+fn workgroup_array() {
+    assert_eq!(reduce::WORK_GROUP_INVOCATIONS, 8 * 8);
+    assert_eq!(
+        reduce::WORK_GROUP_INVOCATIONS,
+        reduce::WORK_GROUP_SIZE.x * 8
+    );
+    assert_eq!(TILE, reduce::WORK_GROUP_INVOCATIONS);
+
+    if !super::has_adapter() {
+        return;
+    }
+
+    let mut input = Buffer::<Values>::new();
+    let mut sums = Buffer::<Values>::new();
+
+    for i in 0..GROUPS * reduce::WORK_GROUP_INVOCATIONS {
+        input.push((i / reduce::WORK_GROUP_INVOCATIONS) as f32);
+    }
+
+    for _ in 0..GROUPS {
+        sums.push(0.0);
+    }
+
+    reduce(
+        reduce::Bindings {
+            input: &input,
+            sums: &mut sums,
+        },
+        Dispatch::new(GROUPS, 1, 1),
+    );
+
+    for group in 0..GROUPS {
+        assert_eq!(
+            sums.values[group as usize],
+            (group * reduce::WORK_GROUP_INVOCATIONS) as f32
+        );
+    }
+}
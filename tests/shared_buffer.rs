@@ -0,0 +1,127 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Table {
+        values: array<vec4<f32>, 256>;
+    };
+
+    [[block]]
+    struct Weights {
+        values: array<vec4<f32>, 256>;
+    };
+
+    [[group(0), binding(0)]]
+    var<uniform> table: Table;
+
+    [[block]]
+    struct Output {
+        values: array<f32>;
+    };
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> output: Output;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn lookup([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        output.values[id.x] = table.values[id.x].x;
+    }
+}
+
+fn values() -> [Vec4<f32>; 256] {
+    let mut values = [Vec4::new(0.0, 0.0, 0.0, 0.0); 256];
+
+    for (i, value) in values.iter_mut().enumerate() {
+        value.x = i as f32;
+    }
+
+    values
+}
+
+// every system builds its own copy of the table
+fn physics_table() -> SharedBuffer<Table> {
+    Buffer::immutable_shared(&Table { values: values() })
+}
+
+fn audio_table() -> SharedBuffer<Table> {
+    Buffer::immutable_shared(&Table { values: values() })
+}
+
+fn ai_table() -> SharedBuffer<Table> {
+    SharedBuffer::new(&Table { values: values() })
+}
+
+// buffer counts are global, so everything runs in a single test
+#[test]
+fn identical_contents_share_a_buffer() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    instance.buffers.clean();
+
+    let live = instance.buffers.live();
+    let resident = instance.buffers.resident_bytes();
+
+    let physics = physics_table();
+    let audio = audio_table();
+    let ai = ai_table();
+
+    // a single allocation
+    assert!(physics.ptr_eq(&audio) && audio.ptr_eq(&ai));
+    assert_eq!(instance.shared_buffer_count(), 1);
+    assert_eq!(instance.buffers.live(), live + 1);
+    assert_eq!(
+        instance.buffers.resident_bytes(),
+        resident + std::mem::size_of::<Table>() as u64
+    );
+    assert_eq!(ai.values[3].x, 3.0);
+
+    // identical bytes of another type don't alias
+    let weights = SharedBuffer::new(&Weights { values: values() });
+    assert_ne!(weights.id(), physics.id());
+
+    // nor do different contents
+    let mut values = values();
+    values[0].x = -1.0;
+
+    let other = Buffer::immutable_shared(&Table { values });
+    assert!(!other.ptr_eq(&physics));
+    assert_eq!(instance.shared_buffer_count(), 3);
+
+    drop(weights);
+    drop(other);
+
+    // the buffer lives until the last handle is dropped
+    drop(physics);
+    drop(audio);
+    assert_eq!(instance.shared_buffer_count(), 1);
+
+    drop(ai);
+    assert_eq!(instance.shared_buffer_count(), 0);
+
+    instance.buffers.clean();
+    assert_eq!(instance.buffers.live(), live);
+    assert_eq!(instance.buffers.resident_bytes(), resident);
+
+    // bound like any read only buffer, the table is never uploaded again
+    let table = physics_table();
+    let mut output = Buffer::<Output>::new();
+
+    for _ in 0..256 {
+        output.push(0.0);
+    }
+
+    let mut builder = lookup::build(lookup::Bindings {
+        table: &table,
+        output: &mut output,
+    });
+    builder.dispatch(Dispatch::new(4, 1, 1));
+
+    assert_eq!(builder.last_transfer_report().uploaded_bytes, 256 * 4);
+
+    for (i, value) in output.values.iter().enumerate() {
+        assert_eq!(*value, i as f32);
+    }
+}
@@ -0,0 +1,133 @@
+use shatter::*;
+
+wgsl_group! {
+    name: Globals,
+    group: 0,
+
+    [[block]]
+    struct Frame {
+        time: f32;
+        scale: f32;
+    };
+
+    [[binding(0)]]
+    var<uniform> frame: Frame;
+}
+
+wgsl! {
+    use_group!(Globals);
+
+    [[block]]
+    struct Positions {
+        positions: array<vec2<f32>>;
+    };
+
+    [[group(1), binding(0)]]
+    var<storage, read_write> positions: Positions;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn advance([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&positions.positions)) {
+            return;
+        }
+
+        positions.positions[id.x] = positions.positions[id.x] + vec2<f32>(frame.time);
+    }
+}
+
+wgsl! {
+    use_group!(Globals);
+
+    [[block]]
+    struct Weights {
+        weights: array<f32>;
+    };
+
+    [[group(1), binding(0)]]
+    var<storage, read_write> weights: Weights;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn decay([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&weights.weights)) {
+            return;
+        }
+
+        weights.weights[id.x] = weights.weights[id.x] * frame.scale;
+    }
+}
+
+#[test]
+fn kernels_share_the_group_layout() {
+    // `Frame` is only defined by `wgsl_group!`, a second definition wouldn't compile
+    let frame = Frame {
+        time: 1.0,
+        scale: 0.5,
+    };
+    assert_eq!(frame.scale, 0.5);
+
+    assert_eq!(Globals::GROUP, 0);
+    assert_eq!(Globals::bind_group_layout_descriptor().entries.len(), 1);
+}
+
+// the only test touching the instance, so the counters only see these lookups
+#[test]
+fn kernels_resolve_the_same_bind_group() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let mut frame = Buffer::<Frame>::new();
+    frame.time = 1.0;
+    frame.scale = 0.5;
+
+    let mut positions = Buffer::<Positions>::new();
+    positions.push(Vec2::new(1.0, 2.0));
+
+    let mut weights = Buffer::<Weights>::new();
+    weights.push(4.0);
+
+    advance(
+        advance::Bindings {
+            globals: Globals { frame: &frame },
+            positions: &mut positions,
+        },
+        advance::dispatch_for(1),
+    );
+    decay(
+        decay::Bindings {
+            globals: Globals { frame: &frame },
+            weights: &mut weights,
+        },
+        decay::dispatch_for(1),
+    );
+
+    let resolve = |globals: &Globals| {
+        let layout = instance.get_bind_group_layout(Globals::bind_group_layout_descriptor());
+        instance.get_bind_group(globals.bind_group_descriptor(&layout))
+    };
+
+    instance.stats_frame();
+
+    let advance_group = resolve(
+        &advance::Bindings {
+            globals: Globals { frame: &frame },
+            positions: &mut positions,
+        }
+        .globals,
+    );
+    let decay_group = resolve(
+        &decay::Bindings {
+            globals: Globals { frame: &frame },
+            weights: &mut weights,
+        }
+        .globals,
+    );
+
+    assert_eq!(advance_group, decay_group);
+
+    // both dispatches already created the group, every lookup is a hit
+    let lookups = instance.stats_frame();
+    assert_eq!(lookups.cache_hits, 4);
+    assert_eq!(lookups.cache_misses, 0);
+}
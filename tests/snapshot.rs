@@ -0,0 +1,145 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn increment([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&values.values)) {
+            return;
+        }
+
+        values.values[id.x] = values.values[id.x] + 1u;
+    }
+}
+
+fn entry(id: u64, key: Option<&str>) -> SnapshotEntry {
+    SnapshotEntry {
+        id,
+        key: key.map(String::from),
+    }
+}
+
+fn snapshot(categories: &[(&str, Vec<SnapshotEntry>)]) -> InstanceSnapshot {
+    InstanceSnapshot {
+        categories: categories
+            .iter()
+            .map(|(name, entries)| (name.to_string(), entries.clone()))
+            .collect(),
+    }
+}
+
+#[test]
+fn diff_matches_cache_entries_by_key() {
+    let before = snapshot(&[
+        ("buffers", vec![entry(0, None), entry(1, None)]),
+        ("shader_module_sources", vec![entry(2, Some("a"))]),
+    ]);
+    let after = snapshot(&[
+        ("buffers", vec![entry(1, None), entry(3, None)]),
+        ("shader_module_sources", vec![entry(4, Some("a"))]),
+    ]);
+
+    let diff = before.diff(&after);
+
+    assert_eq!(diff.added("buffers"), [entry(3, None)]);
+    assert_eq!(diff.removed("buffers"), [entry(0, None)]);
+
+    let sources = &diff.categories["shader_module_sources"];
+    assert!(sources.added.is_empty() && sources.removed.is_empty());
+    assert_eq!(
+        sources.changed,
+        [SnapshotChange {
+            before: entry(2, Some("a")),
+            after: entry(4, Some("a")),
+        }]
+    );
+
+    assert_eq!(
+        diff.to_string(),
+        "+ buffers: Id(3)\n- buffers: Id(0)\n~ shader_module_sources: Id(2) a -> Id(4)\n"
+    );
+
+    assert!(after.diff(&after).is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn snapshots_round_trip() {
+    let snapshot = snapshot(&[("group_keys", vec![entry(7, Some("GroupKey"))])]);
+
+    let json = serde_json::to_string(&snapshot).unwrap();
+    let parsed: InstanceSnapshot = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(parsed, snapshot);
+}
+
+// the only test touching the instance, so nothing else creates resources meanwhile
+#[test]
+fn diff_names_new_entries() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let before = instance.snapshot();
+
+    // entries are sorted, so snapshots of an unchanged instance are identical
+    assert_eq!(instance.snapshot(), before);
+
+    let mut values = Buffer::<Values>::new();
+
+    for i in 0..128 {
+        values.push(i);
+    }
+
+    increment::build(increment::Bindings {
+        values: &mut values,
+    })
+    .dispatch(Dispatch::new(2, 1, 1));
+
+    let after = instance.snapshot();
+    let diff = before.diff(&after);
+
+    let categories = diff
+        .categories
+        .keys()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        categories,
+        [
+            "bind_group_descriptors",
+            "bind_group_layout_descriptors",
+            "bind_group_layouts",
+            "bind_groups",
+            "buffers",
+            "compute_pipeline_descriptors",
+            "compute_pipelines",
+            "group_keys",
+            "pipeline_layout_descriptors",
+            "pipeline_layouts",
+            "shader_module_sources",
+            "shader_modules",
+        ],
+        "{}",
+        diff
+    );
+
+    for diff in diff.categories.values() {
+        assert_eq!(diff.added.len(), 1);
+        assert!(diff.removed.is_empty() && diff.changed.is_empty());
+    }
+
+    assert_eq!(diff.added("buffers"), [entry(values.id().raw(), None)]);
+
+    let source = diff.added("shader_module_sources")[0].key.as_ref().unwrap();
+    assert!(source.contains("fn increment"));
+}
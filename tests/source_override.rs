@@ -0,0 +1,169 @@
+use shatter::*;
+
+wgsl! {
+    struct Particle {
+        position: f32;
+        velocity: f32;
+    };
+
+    [[block]]
+    struct Particles {
+        particles: array<Particle>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> particles: Particles;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn step([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        let particle = &particles.particles[id.x];
+
+        (*particle).position = (*particle).position + (*particle).velocity;
+    }
+}
+
+const DOUBLE: &str = r#"
+    struct Particle {
+        position: f32;
+        velocity: f32;
+    };
+
+    [[block]]
+    struct Particles {
+        particles: array<Particle>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> particles: Particles;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn step([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        let particle = &particles.particles[id.x];
+
+        (*particle).position = (*particle).position * 2.0;
+    }
+"#;
+
+#[test]
+fn original_and_override_alternate() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut particles = Buffer::<Particles>::new();
+
+    for _ in 0..64 {
+        particles.push(Particle {
+            position: 0.0,
+            velocity: 1.0,
+        });
+    }
+
+    let mut original = step::build(step::Bindings {
+        particles: &mut particles,
+    });
+    original.dispatch(Dispatch::new(1, 1, 1));
+
+    let mut overridden = step::build(original.take_binding());
+    overridden.with_source(DOUBLE);
+    overridden.dispatch(Dispatch::new(1, 1, 1));
+
+    let mut original = step::build(overridden.take_binding());
+    original.dispatch(Dispatch::new(1, 1, 1));
+
+    let mut overridden = step::build(original.take_binding());
+    overridden
+        .with_source(String::from(DOUBLE))
+        .dispatch(Dispatch::new(1, 1, 1));
+    drop(overridden);
+
+    // ((0 + 1) * 2 + 1) * 2
+    assert!(particles
+        .particles
+        .iter()
+        .all(|particle| particle.position == 6.0));
+}
+
+#[cfg(feature = "validate-source")]
+mod interface {
+    use shatter::*;
+
+    fn layouts() -> Vec<BindGroupLayoutDescriptor> {
+        vec![BindGroupLayoutDescriptor::new(vec![BindGroupLayoutEntry {
+            binding: 0,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }])]
+    }
+
+    #[test]
+    fn matching_source() {
+        assert_eq!(
+            check_source_interface(super::DOUBLE, "step", &layouts()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn missing_entry_point() {
+        assert_eq!(
+            check_source_interface(super::DOUBLE, "integrate", &layouts()),
+            Err(SourceError::MissingEntryPoint("integrate".into()))
+        );
+    }
+
+    #[test]
+    fn interface_diff() {
+        let source = r#"
+            [[block]]
+            struct Positions {
+                positions: array<f32>;
+            };
+
+            [[group(0), binding(0)]]
+            var<storage, read> positions: Positions;
+
+            [[block]]
+            struct Scale {
+                scale: f32;
+            };
+
+            [[group(1), binding(0)]]
+            var<uniform> scale: Scale;
+
+            [[stage(compute), workgroup_size(1)]]
+            fn step() {
+                let position = positions.positions[0] * scale.scale;
+            }
+        "#;
+
+        let err = check_source_interface(source, "step", &layouts()).unwrap_err();
+
+        let diffs = match err {
+            SourceError::Interface(ref diffs) => diffs,
+            err => panic!("unexpected error: {}", err),
+        };
+
+        assert_eq!(diffs.len(), 2);
+        assert_eq!((diffs[0].group, diffs[0].binding), (0, 0));
+        assert_eq!(
+            diffs[0].found,
+            Some(BindingType::Buffer {
+                ty: BufferBindingType::Storage { read_only: true },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            })
+        );
+        assert_eq!(diffs[1].name, "scale");
+        assert_eq!(diffs[1].expected, None);
+
+        assert!(err
+            .to_string()
+            .contains("`scale`: not in the generated layout"));
+    }
+}
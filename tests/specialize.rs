@@ -0,0 +1,131 @@
+use std::sync::{Arc, Mutex};
+
+use shatter::*;
+
+wgsl! {
+    let HEIGHT: u32 = 1u;
+
+    [[block]]
+    struct Sums {
+        sums: array<f32>;
+    };
+
+    [[group(0), binding(0)]]
+    var input: texture_storage_2d<r32float, read>;
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> output: Sums;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn column_sum([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        var sum = 0.0;
+
+        for (var y = 0u; y < HEIGHT; y = y + 1u) {
+            sum = sum + textureLoad(input, vec2<i32>(i32(id.x), i32(y))).r;
+        }
+
+        output.sums[id.x] = sum;
+    }
+}
+
+const WIDTH: usize = 64;
+
+fn sum_columns(height: usize) -> Vec<f32> {
+    let mut input = Texture2d::<R32Float>::new(WIDTH, height);
+
+    for y in 0..height {
+        for x in 0..WIDTH {
+            input[(x, y)] = R32::r((x + y) as f32);
+        }
+    }
+
+    let mut output = Buffer::<Sums>::new();
+    output.extend_from_slice(&[0.0; WIDTH]);
+
+    column_sum::build(column_sum::Bindings {
+        input: &mut input,
+        output: &mut output,
+    })
+    .specialize_extent("HEIGHT", height as u32)
+    .dispatch(Dispatch::new(1, 1, 1));
+
+    output.sums.to_vec()
+}
+
+#[test]
+fn specialized_heights_share_pipelines() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let pipelines = instance.compute_pipeline_count();
+
+    for _ in 0..2 {
+        for height in [128, 256] {
+            let sums = sum_columns(height);
+
+            for (x, sum) in sums.into_iter().enumerate() {
+                let expected = (height * x + height * (height - 1) / 2) as f32;
+                assert_eq!(sum, expected, "column {} of height {}", x, height);
+            }
+        }
+    }
+
+    assert_eq!(instance.compute_pipeline_count() - pipelines, 2);
+}
+
+#[test]
+fn many_specializations_warn_once() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let warnings = Arc::new(Mutex::new(Vec::new()));
+    let hook_warnings = warnings.clone();
+    instance.set_specialization_hook(move |warning| {
+        if warning.constant == "HEIGHT_OF_MANY" {
+            hook_warnings.lock().unwrap().push(warning.clone());
+        }
+    });
+
+    let mut output = Buffer::<Sums>::new();
+    let mut input = Texture2d::<R32Float>::new(1, 1);
+
+    let mut builder = column_sum::build(column_sum::Bindings {
+        input: &mut input,
+        output: &mut output,
+    });
+
+    let max = CacheLimits::default().max_specializations as u32;
+
+    for height in (0..max * 2).chain(0..max * 2) {
+        builder.with_source("let HEIGHT_OF_MANY: u32 = 1u;");
+        builder.specialize_extent("HEIGHT_OF_MANY", height);
+    }
+
+    instance.clear_specialization_hook();
+
+    let warnings = warnings.lock().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].entry_point, "column_sum");
+    assert_eq!(warnings[0].values, max as usize + 1);
+}
+
+#[test]
+#[should_panic(expected = "no module scope `u32`, `i32` or `f32` constant `WIDTH`")]
+fn unknown_constant_panics() {
+    if Instance::try_global().is_none() {
+        panic!("no module scope `u32`, `i32` or `f32` constant `WIDTH`");
+    }
+
+    let mut output = Buffer::<Sums>::new();
+    let mut input = Texture2d::<R32Float>::new(1, 1);
+
+    column_sum::build(column_sum::Bindings {
+        input: &mut input,
+        output: &mut output,
+    })
+    .specialize_extent("WIDTH", 1);
+}
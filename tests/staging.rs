@@ -0,0 +1,74 @@
+use shatter::{texture_format::Rgba8Unorm, *};
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn increment([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] + 1u;
+    }
+}
+
+// the pool is global, so everything runs in a single test
+#[test]
+fn downloads_reuse_staging_buffers() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let mut values = Buffer::<Values>::new();
+    values.extend_from_slice(&[0; 64]);
+
+    let mut texture = Texture2d::<Rgba8Unorm>::new(100, 100);
+
+    let mut download_both = |values: &mut Buffer<Values>| {
+        increment(
+            increment::Bindings {
+                values: &mut *values,
+            },
+            Dispatch::new(1, 1, 1),
+        );
+        values.download();
+
+        texture.mark_needs_download();
+        texture.download();
+    };
+
+    download_both(&mut values);
+    let retained = instance.staging_bytes();
+    assert!(retained > 0);
+
+    for _ in 0..100 {
+        download_both(&mut values);
+    }
+
+    assert_eq!(instance.staging_bytes(), retained);
+    assert_eq!(values.as_slice(), &[101; 64]);
+
+    // nothing is retained past the maximum
+    instance.set_max_staging_bytes(0);
+    assert_eq!(instance.staging_bytes(), 0);
+
+    download_both(&mut values);
+    assert_eq!(instance.staging_bytes(), 0);
+    assert_eq!(values.as_slice(), &[102; 64]);
+
+    instance.set_max_staging_bytes(DEFAULT_MAX_STAGING_BYTES);
+
+    // a released buffer is handed out again
+    let staging = instance.acquire_staging(1000);
+    assert_eq!(staging.size(), 1024);
+    instance.release_staging(staging);
+    assert_eq!(instance.staging_bytes(), 1024);
+
+    let staging = instance.acquire_staging(600);
+    assert_eq!(instance.staging_bytes(), 0);
+    instance.release_staging(staging);
+}
@@ -0,0 +1,92 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Counter {
+        effective: atomic<u32>;
+    };
+
+    [[block]]
+    struct Size {
+        width: u32;
+        height: u32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> counter: Counter;
+
+    [[group(0), binding(1)]]
+    var<uniform> size: Size;
+
+    [[stage(compute), workgroup_size(8, 8, 1)]]
+    fn guarded([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= size.width || id.y >= size.height) {
+            return;
+        }
+
+        let previous = atomicAdd(&counter.effective, 1u);
+    }
+}
+
+#[test]
+fn launched_invocations_cover_whole_work_groups() {
+    let dispatch = Dispatch::new(13, 4, 1);
+
+    assert_eq!(dispatch.invocations(guarded::WORK_GROUP_SIZE), 13 * 4 * 64);
+    assert_eq!(
+        Dispatch::new(0, 4, 1).invocations(WorkGroupSize::new(8, 8, 1)),
+        0
+    );
+}
+
+#[test]
+fn statistics_count_launched_invocations() {
+    let instance = match Instance::try_init(&InstanceDescriptor {
+        optional_features: wgpu::Features::PIPELINE_STATISTICS_QUERY,
+        ..Default::default()
+    }) {
+        Ok(instance) => instance,
+        Err(_) => return,
+    };
+
+    let supported = instance
+        .device
+        .features()
+        .contains(wgpu::Features::PIPELINE_STATISTICS_QUERY);
+
+    let mut counter = Buffer::<Counter>::new();
+    let mut size = Buffer::<Size>::new();
+
+    // a 100x30 grid padded to whole 8x8 work groups
+    size.width = 100;
+    size.height = 30;
+
+    let dispatch = Dispatch::new(13, 4, 1);
+
+    let mut builder = guarded::build(guarded::Bindings {
+        counter: &mut counter,
+        size: &size,
+    });
+
+    builder.with_statistics().dispatch(dispatch);
+
+    match builder.last_statistics() {
+        Some(statistics) => {
+            assert!(supported);
+            assert_eq!(
+                statistics.invocations,
+                dispatch.invocations(guarded::WORK_GROUP_SIZE)
+            );
+            assert_eq!(builder.last_dispatch_report().statistics, Some(statistics));
+        }
+        None => assert!(!supported),
+    }
+
+    builder.without_statistics().dispatch(dispatch);
+    assert_eq!(builder.last_statistics(), None);
+
+    drop(builder);
+
+    // the guard lets through exactly the grid, twice
+    assert_eq!(counter.effective, 2 * 100 * 30);
+}
@@ -0,0 +1,101 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read> values: Values;
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> partials: Values;
+
+    // each invocation sums STRIDE values
+    [[stage(compute), workgroup_size(64)]]
+    fn sum([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&partials.values)) {
+            return;
+        }
+
+        let len = arrayLength(&values.values);
+        var partial: u32 = 0u;
+
+        for (var i: u32 = id.x * 1024u; i < min(len, (id.x + 1u) * 1024u); i = i + 1u) {
+            partial = partial + values.values[i];
+        }
+
+        partials.values[id.x] = partial;
+    }
+}
+
+const COUNT: usize = 10_000_000;
+const CHUNK_SIZE: usize = 1024 * 1024;
+const STRIDE: usize = 1024;
+
+fn item(i: usize) -> u32 {
+    (i as u32).wrapping_mul(2654435761) >> 24
+}
+
+#[test]
+fn stream_checksum() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut values = Buffer::<Values>::new();
+    let mut partials = Buffer::<Values>::new();
+
+    let mut chunks = 0;
+    let report = values.stream_items_with(COUNT, (0..COUNT).map(item), CHUNK_SIZE, |_| {
+        chunks += 1;
+    });
+
+    assert_eq!(report.items, COUNT);
+    assert_eq!(report.uploaded_bytes, (COUNT * 4) as u64);
+    assert!(report.peak_staging_bytes <= CHUNK_SIZE as u64);
+    assert_eq!(chunks, (COUNT * 4).div_ceil(CHUNK_SIZE));
+
+    let invocations = COUNT.div_ceil(STRIDE);
+
+    for _ in 0..invocations {
+        partials.push(0);
+    }
+
+    let groups = (invocations as u32).div_ceil(sum::WORK_GROUP_SIZE.x);
+
+    let mut builder = sum::build(sum::Bindings {
+        values: &values,
+        partials: &mut partials,
+    });
+    builder.dispatch(Dispatch::new(groups, 1, 1));
+
+    // the streamed values aren't uploaded again
+    assert_transfers!(
+        builder.last_transfer_report(),
+        uploaded_bytes == (invocations * 4) as u64,
+    );
+
+    let checksum = partials.values.iter().fold(0u32, |a, &b| a.wrapping_add(b));
+    let expected = (0..COUNT).map(item).fold(0u32, u32::wrapping_add);
+    assert_eq!(checksum, expected);
+
+    assert_eq!(values.len(), COUNT);
+    assert_eq!(values.values[12345], item(12345));
+}
+
+#[test]
+fn stream_past_hint() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut values = Buffer::<Values>::new();
+
+    let report = values.stream_items_with(16, (0..1000).map(item), 64, |_| {});
+
+    assert_eq!(report.items, 1000);
+    assert_eq!(values.len(), 1000);
+    assert_eq!(values.values[999], item(999));
+}
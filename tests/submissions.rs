@@ -0,0 +1,142 @@
+use std::{
+    borrow::Cow,
+    panic::{self, AssertUnwindSafe},
+};
+
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn increment([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x < arrayLength(&values.values)) {
+            values.values[id.x] = values.values[id.x] + 1u;
+        }
+    }
+}
+
+/// An instance of its own, so other tests don't submit in between.
+fn instance() -> Option<&'static Instance> {
+    Instance::create(&InstanceDescriptor::default()).ok()
+}
+
+fn validation_error() -> wgpu::Error {
+    wgpu::Error::ValidationError {
+        source: Box::new(std::io::Error::other("simulated")),
+        description: String::from("simulated validation error"),
+    }
+}
+
+#[test]
+fn uncaptured_errors_list_recent_submissions() {
+    let instance = match instance() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let mut values = Buffer::<Values>::new_in(instance);
+    values.extend(0..100);
+
+    increment::build(increment::Bindings {
+        values: &mut values,
+    })
+    .instance(instance)
+    .tag("simulate_particles")
+    .dispatch(increment::dispatch_for(100));
+
+    submit_encoded_in(instance, "resolve_collisions", |_| ());
+
+    let err = instance
+        .error_scope(|| instance.deliver_uncaptured_error(validation_error()))
+        .unwrap_err();
+
+    assert!(matches!(err, Error::Device(DeviceError::Validation(_))));
+
+    let message = err.to_string();
+    assert!(
+        message.contains("simulated validation error"),
+        "{}",
+        message
+    );
+    assert!(message.contains("`resolve_collisions`"), "{}", message);
+    assert!(
+        message.contains("`simulate_particles` dispatching increment"),
+        "{}",
+        message
+    );
+
+    // the most recent submission comes first
+    let collisions = message.find("resolve_collisions").unwrap();
+    let particles = message.find("simulate_particles").unwrap();
+    assert!(collisions < particles, "{}", message);
+
+    // outside of a scope the handler panics with the same context
+    let panic = panic::catch_unwind(AssertUnwindSafe(|| {
+        instance.deliver_uncaptured_error(validation_error())
+    }))
+    .unwrap_err();
+
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert!(message.contains("`resolve_collisions`"), "{}", message);
+
+    // the handler released the scope state before panicking
+    assert!(instance.error_scope(|| ()).is_ok());
+}
+
+#[test]
+fn recent_submissions_are_bounded() {
+    let instance = match instance() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let flood = 3 * RECENT_SUBMISSIONS as u64;
+
+    for i in 0..flood {
+        submit_encoded_in(instance, &format!("flood_{}", i), |_| ());
+    }
+
+    let recent = instance.recent_submissions();
+    assert_eq!(recent.len(), RECENT_SUBMISSIONS);
+
+    // the oldest are dropped, indices keep counting
+    let first = recent[0].index;
+    for (i, record) in recent.iter().enumerate() {
+        assert_eq!(record.index, first + i as u64);
+        assert!(record.entry_points.is_empty());
+    }
+
+    let last = recent.last().unwrap();
+    assert_eq!(last.tag.as_deref(), Some(&*format!("flood_{}", flood - 1)));
+}
+
+#[test]
+fn generated_functions_tag_with_their_entry_point() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let mut values = Buffer::<Values>::new();
+    values.extend(0..100);
+
+    increment(
+        increment::Bindings {
+            values: &mut values,
+        },
+        increment::dispatch_for(100),
+    );
+
+    let tagged = instance.recent_submissions().into_iter().any(|record| {
+        record.tag == Some(Cow::Borrowed("increment"))
+            && record.entry_points == [Cow::Borrowed("increment")]
+    });
+    assert!(tagged);
+}
@@ -0,0 +1,85 @@
+use shatter::{
+    texture_format::{Bgra8Unorm, Rgba8Unorm},
+    *,
+};
+
+wgsl! {
+    [[group(0), binding(0)]]
+    var output: texture_storage_2d<rgba8unorm, write>;
+
+    [[stage(compute), workgroup_size(8, 8)]]
+    fn fill([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        textureStore(output, vec2<i32>(id.xy), vec4<f32>(1.0, 0.0, 0.0, 1.0));
+    }
+}
+
+fn render(surface: &mut Surface) {
+    let mut frame = surface.frame().unwrap();
+
+    let dispatch = Dispatch::for_size(fill::WORK_GROUP_SIZE, (frame.width(), frame.height(), 1));
+
+    fill(fill::Bindings { output: &mut frame }, dispatch);
+
+    frame.present();
+}
+
+#[test]
+fn headless_fallback() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut surface = Surface::headless(16, 16, wgpu::TextureFormat::Rgba8Unorm);
+    surface.force_fallback();
+    assert!(!surface.is_direct());
+
+    render(&mut surface);
+
+    let target = surface.headless_target().unwrap();
+    let target = target.downcast_ref::<Rgba8Unorm>().unwrap();
+
+    for y in 0..target.height() {
+        for x in 0..target.width() {
+            assert_eq!(target[(x, y)], Rgba8U::rgba(255, 0, 0, 255));
+        }
+    }
+}
+
+#[test]
+fn headless_bgra() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    // bgra8unorm can't be bound as a storage texture
+    let mut surface = Surface::headless(16, 16, wgpu::TextureFormat::Bgra8Unorm);
+    assert!(!surface.is_direct());
+
+    render(&mut surface);
+
+    let target = surface.headless_target().unwrap();
+    let target = target.downcast_ref::<Bgra8Unorm>().unwrap();
+
+    for y in 0..target.height() {
+        for x in 0..target.width() {
+            assert_eq!(target[(x, y)], Bgra8U::rgba(255, 0, 0, 255));
+        }
+    }
+}
+
+#[test]
+fn dropped_frame() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut surface = Surface::headless(8, 8, wgpu::TextureFormat::Rgba8Unorm);
+
+    // dropping a frame without presenting it is fine
+    drop(surface.frame().unwrap());
+
+    surface.resize(16, 16);
+    assert_eq!(surface.width(), 16);
+
+    render(&mut surface);
+}
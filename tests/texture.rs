@@ -0,0 +1,285 @@
+use shatter::*;
+
+wgsl! {
+    [[group(0), binding(0)]]
+    var input: texture_2d<f32>;
+
+    [[group(0), binding(1)]]
+    var output: texture_storage_2d<rgba8unorm, write>;
+
+    [[stage(compute), workgroup_size(8, 8, 1)]]
+    fn copy([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        let size = textureDimensions(input);
+        let coords = vec2<i32>(id.xy);
+
+        if (coords.x >= size.x || coords.y >= size.y) {
+            return;
+        }
+
+        textureStore(output, coords, textureLoad(input, coords, 0));
+    }
+}
+
+fn pattern(x: usize, y: usize) -> Rgba8U {
+    Rgba8U::rgba(x as u8, y as u8, (x ^ y) as u8, 255)
+}
+
+#[test]
+fn banded_round_trip() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    // 100 texels of 4 bytes pad to 512 bytes per row, giving bands of 8 rows
+    // and a partial last band of 2 rows
+    let mut texture = Texture2d::<Rgba8Unorm>::new(100, 50);
+    texture.set_max_transfer_size(4096);
+
+    for y in 0..texture.height() {
+        for x in 0..texture.width() {
+            texture[(x, y)] = pattern(x, y);
+        }
+    }
+
+    assert_eq!(texture.upload(), 512 * 50);
+
+    // overwrite the CPU copy so the download has to restore every row
+    for y in 0..texture.height() {
+        for x in 0..texture.width() {
+            texture[(x, y)] = Rgba8U::TRANSPARENT;
+        }
+    }
+
+    texture.mark_needs_download();
+    assert_eq!(texture.download(), 512 * 50);
+
+    for y in 0..texture.height() {
+        for x in 0..texture.width() {
+            assert_eq!(texture[(x, y)], pattern(x, y), "texel ({}, {})", x, y);
+        }
+    }
+}
+
+#[test]
+fn typed_data_round_trip() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    // 250 texels of 4 bytes pad to 1024 bytes per row
+    let (width, height) = (250, 7);
+    let data = (0..width * height)
+        .map(|i| pattern(i % width, i / width))
+        .collect::<Vec<_>>();
+
+    let input = Texture2d::<Rgba8Unorm>::from_data(width, height, &data);
+    assert_eq!(input.bytes().len(), 1024 * height);
+
+    let mut output = Texture2d::<Rgba8Unorm>::new(width, height);
+
+    copy(
+        copy::Bindings {
+            input: &input,
+            output: &mut output,
+        },
+        copy::dispatch_for_extent([width as u64, height as u64, 1]),
+    );
+
+    assert_eq!(output.to_vec(), data);
+
+    for (y, row) in output.rows().enumerate() {
+        assert_eq!(row, &data[y * width..(y + 1) * width]);
+    }
+
+    // overwriting discards what the GPU wrote
+    output.mark_needs_download();
+    output.write_data(&vec![Rgba8U::TRANSPARENT; width * height]);
+    assert!(!output.needs_download());
+    assert_eq!(output.to_vec(), vec![Rgba8U::TRANSPARENT; width * height]);
+}
+
+/// A texture of `pattern` written by a dispatch, so it needs downloading.
+fn dispatched_pattern(output: &mut Texture2d<Rgba8Unorm>) {
+    let (width, height) = (output.width(), output.height());
+    let data = (0..width * height)
+        .map(|i| pattern(i % width, i / width))
+        .collect::<Vec<_>>();
+
+    let input = Texture2d::<Rgba8Unorm>::from_data(width, height, &data);
+    let dispatch = output.dispatch_for(copy::WORK_GROUP_SIZE);
+
+    copy(
+        copy::Bindings {
+            input: &input,
+            output,
+        },
+        dispatch,
+    );
+
+    assert!(output.needs_download());
+}
+
+#[test]
+fn indexing_downloads_the_rows_around_the_texel() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    // 1024 texels of 4 bytes per row, indexing downloads 16 rows at once
+    let (width, height) = (1024, 40);
+    let mut output = Texture2d::<Rgba8Unorm>::new(width, height);
+    dispatched_pattern(&mut output);
+
+    let texel = &output[(5, 20)];
+    assert_eq!(*texel, pattern(5, 20));
+    assert!(output.needs_download());
+
+    // the rows already downloaded are still borrowed
+    assert_eq!(output[(1000, 31)], pattern(1000, 31));
+    assert_eq!(output[(7, 3)], pattern(7, 3));
+    assert_eq!(*texel, pattern(5, 20));
+    assert!(output.needs_download());
+
+    for y in 0..height {
+        for x in [0, width - 1] {
+            assert_eq!(output[(x, y)], pattern(x, y), "texel ({}, {})", x, y);
+        }
+    }
+
+    // every row is downloaded, so the texture is as well
+    assert!(!output.needs_download());
+    assert_eq!(output.download(), 0);
+}
+
+#[test]
+fn partially_indexed_textures_download_the_rest() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let (width, height) = (1024, 40);
+    let mut output = Texture2d::<Rgba8Unorm>::new(width, height);
+    dispatched_pattern(&mut output);
+
+    assert_eq!(output[(9, 39)], pattern(9, 39));
+    assert_eq!(output.download(), (4096 * height) as u64);
+
+    for (y, row) in output.rows().enumerate() {
+        for (x, texel) in row.iter().enumerate() {
+            assert_eq!(*texel, pattern(x, y), "texel ({}, {})", x, y);
+        }
+    }
+}
+
+#[test]
+fn read_region_copies_only_the_region() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let (width, height) = (250, 30);
+    let expected = (5..9)
+        .flat_map(|y| (3..13).map(move |x| pattern(x, y)))
+        .collect::<Vec<_>>();
+
+    let mut output = Texture2d::<Rgba8Unorm>::new(width, height);
+    dispatched_pattern(&mut output);
+
+    assert_eq!(output.read_region((3, 5), (10, 4)), expected);
+    assert!(output.needs_download());
+    assert!(output.read_region((0, 0), (0, 4)).is_empty());
+
+    // read from the mirror once downloaded
+    output.download();
+    assert_eq!(output.read_region((3, 5), (10, 4)), expected);
+
+    let mut gpu_only = Texture2d::<Rgba8Unorm>::gpu_only(width, height);
+    dispatched_pattern(&mut gpu_only);
+
+    assert_eq!(gpu_only.read_region((3, 5), (10, 4)), expected);
+    assert_eq!(
+        gpu_only.read_region((0, height - 1), (width, 1)),
+        (0..width)
+            .map(|x| pattern(x, height - 1))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+#[should_panic(expected = "region of 7x1 at (10, 0) is out of bounds of a 16x16 texture")]
+fn read_region_out_of_bounds_panics() {
+    if Instance::try_global().is_none() {
+        panic!("region of 7x1 at (10, 0) is out of bounds of a 16x16 texture");
+    }
+
+    let texture = Texture2d::<Rgba8Unorm>::new(16, 16);
+    texture.read_region((10, 0), (7, 1));
+}
+
+#[test]
+fn tiled_round_trip() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    // rows of 1000 texels of 4 bytes don't fit in 1 KiB, so every row is
+    // copied in tiles of 256 texels and a partial last tile of 232
+    let (width, height) = (1000, 3);
+    let wide_pattern = |x: usize, y: usize| Rgba8U::rgba(x as u8, (x >> 8) as u8, y as u8, 255);
+    let data = (0..width * height)
+        .map(|i| wide_pattern(i % width, i / width))
+        .collect::<Vec<_>>();
+
+    let mut input = Texture2d::<Rgba8Unorm>::new(width, height);
+    input.set_max_transfer_size(1024);
+    input.write_data(&data);
+
+    let mut output = Texture2d::<Rgba8Unorm>::new(width, height);
+    output.set_max_transfer_size(1024);
+
+    copy(
+        copy::Bindings {
+            input: &input,
+            output: &mut output,
+        },
+        copy::dispatch_for_extent([width as u64, height as u64, 1]),
+    );
+
+    assert_eq!(output.to_vec(), data);
+
+    // overwrite the CPU copy so the download has to restore every tile
+    for y in 0..height {
+        for x in 0..width {
+            output[(x, y)] = Rgba8U::TRANSPARENT;
+        }
+    }
+
+    output.mark_needs_download();
+    assert_eq!(output.download(), 4096 * 3);
+    assert_eq!(output.to_vec(), data);
+}
+
+#[test]
+fn overflowing_rows_fail_to_create() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    // 300 million texels of 16 bytes don't fit in a u32 row
+    let err = match Texture2d::<Rgba32Float>::try_new(300_000_000, 1) {
+        Ok(_) => panic!("a row of 4.8 GB was accepted"),
+        Err(err) => err,
+    };
+
+    assert!(
+        matches!(
+            err,
+            Error::Resource(ResourceError::LimitExceeded {
+                limit: "bytes_per_row",
+                ..
+            })
+        ),
+        "{}",
+        err
+    );
+}
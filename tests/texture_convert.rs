@@ -0,0 +1,123 @@
+use shatter::*;
+
+const WIDTH: usize = 64;
+const HEIGHT: usize = 16;
+
+fn reference_srgb8(linear: f32) -> u8 {
+    (convert::linear_to_srgb_f32(linear.clamp(0.0, 1.0)) * 255.0).round() as u8
+}
+
+#[test]
+fn float_to_srgb() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut src = Texture2d::<Rgba32Float>::new(WIDTH, HEIGHT);
+    let mut dst = Texture2d::<Rgba8UnormSrgb>::new(WIDTH, HEIGHT);
+
+    // out of range values are clamped
+    let value =
+        |x: usize, y: usize| (x as f32 + y as f32 * WIDTH as f32) / (WIDTH * 4) as f32 - 0.5;
+
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let v = value(x, y);
+            src[(x, y)] = Rgba32::rgba(v, 1.0 - v, v * 0.5, 0.25);
+        }
+    }
+
+    let method = convert(&src, &mut dst).unwrap();
+    assert_eq!(method, ConvertMethod::KernelAndCopy);
+
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let v = value(x, y);
+            let expected = [
+                reference_srgb8(v),
+                reference_srgb8(1.0 - v),
+                reference_srgb8(v * 0.5),
+                // alpha isn't encoded
+                (0.25f32 * 255.0).round() as u8,
+            ];
+
+            let texel = dst[(x, y)];
+            let found = [texel.r, texel.g, texel.b, texel.a];
+
+            for (found, expected) in found.iter().zip(&expected) {
+                assert!(
+                    found.abs_diff(*expected) <= 1,
+                    "({}, {}): {:?} != {:?}",
+                    x,
+                    y,
+                    found,
+                    expected
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn broadcast_single_channel() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut src = Texture2d::<R32Float>::new(WIDTH, HEIGHT);
+    let mut dst = Texture2d::<Rgba8Unorm>::new(WIDTH, HEIGHT);
+
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            src[(x, y)] = R32::r(x as f32 / (WIDTH - 1) as f32);
+        }
+    }
+
+    assert_eq!(convert(&src, &mut dst).unwrap(), ConvertMethod::Kernel);
+
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let expected = x as f32 / (WIDTH - 1) as f32 * 255.0;
+            let texel = dst[(x, y)];
+
+            for channel in [texel.r, texel.g, texel.b] {
+                assert!((channel as f32 - expected).abs() <= 1.0);
+            }
+
+            assert_eq!(texel.a, 255);
+        }
+    }
+}
+
+#[test]
+fn same_layout_is_copied() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut src = Texture2d::<Rgba8Unorm>::new(WIDTH, HEIGHT);
+    let mut dst = Texture2d::<Rgba8UnormSrgb>::new(WIDTH, HEIGHT);
+
+    src[(3, 2)] = Rgba8U::rgba(10, 20, 30, 40);
+
+    assert_eq!(convert(&src, &mut dst).unwrap(), ConvertMethod::Copy);
+    assert_eq!(dst[(3, 2)], Rgba8U::rgba(10, 20, 30, 40));
+}
+
+#[test]
+fn extent_mismatch() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let src = Texture2d::<Rgba32Float>::gpu_only(WIDTH, HEIGHT);
+    let mut dst = Texture2d::<Rgba8Unorm>::gpu_only(WIDTH, HEIGHT / 2);
+
+    assert_eq!(
+        convert(&src, &mut dst),
+        Err(ConvertError::Extent {
+            src: (WIDTH as u32, HEIGHT as u32),
+            dst: (WIDTH as u32, HEIGHT as u32 / 2),
+        })
+    );
+}
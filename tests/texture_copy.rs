@@ -0,0 +1,95 @@
+use shatter::{texture_format::Rgba8Unorm, *};
+
+wgsl! {
+    [[block]]
+    struct Pixels {
+        count: u32;
+        pixels: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> pixels: Pixels;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn count() {
+        pixels.count = arrayLength(&pixels.pixels);
+    }
+}
+
+fn gradient(x: usize, y: usize) -> Rgba8U {
+    Rgba8U::rgba(x as u8, y as u8, (x + y) as u8, 255 - x as u8)
+}
+
+fn pack(color: Rgba8U) -> u32 {
+    u32::from_le_bytes(color.into())
+}
+
+fn round_trip(width: usize, height: usize) {
+    let mut buffer = Buffer::<Pixels>::new();
+    buffer.count = 7;
+
+    for y in 0..height {
+        for x in 0..width {
+            buffer.push(pack(gradient(x, y)));
+        }
+    }
+
+    let mut texture = Texture2d::<Rgba8Unorm>::new(width, height);
+    texture.copy_from_typed_buffer(&buffer);
+
+    for y in 0..height {
+        for x in 0..width {
+            assert_eq!(texture[(x, y)], gradient(x, y), "texel ({}, {})", x, y);
+        }
+    }
+
+    for y in 0..height {
+        for x in 0..width {
+            texture[(x, y)] = gradient(y, x);
+        }
+    }
+
+    texture.copy_to_typed_buffer(&mut buffer);
+
+    // the header is left alone
+    assert_eq!(buffer.count, 7);
+
+    for (i, &item) in buffer.as_slice().iter().enumerate() {
+        let (x, y) = (i % width, i / width);
+        assert_eq!(item, pack(gradient(y, x)), "item {}", i);
+    }
+}
+
+#[test]
+fn padded_rows() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    // 1000 bytes per row, copied row by row
+    round_trip(250, 37);
+}
+
+#[test]
+fn aligned_rows() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    // 256 bytes per row, copied at once
+    round_trip(64, 16);
+}
+
+#[test]
+#[should_panic(expected = "buffer holds 3 items, expected 2x2 texels")]
+fn length_mismatch() {
+    if Instance::try_global().is_none() {
+        panic!("buffer holds 3 items, expected 2x2 texels");
+    }
+
+    let mut buffer = Buffer::<Pixels>::new();
+    buffer.extend([0, 0, 0]);
+
+    let texture = Texture2d::<Rgba8Unorm>::new(2, 2);
+    texture.copy_to_typed_buffer(&mut buffer);
+}
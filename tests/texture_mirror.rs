@@ -0,0 +1,92 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use shatter::{
+    texture_format::{Rgba32Float, Rgba8Unorm},
+    texture_sample_type::Float,
+    texture_view_dimension::{TextureStorageD2, D2},
+    *,
+};
+
+wgsl! {
+    [[group(0), binding(0)]]
+    var output: texture_storage_2d<rgba8unorm, write>;
+
+    [[stage(compute), workgroup_size(8, 8)]]
+    fn fill([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        textureStore(output, vec2<i32>(id.xy), vec4<f32>(1.0));
+    }
+}
+
+// stats are global, so everything runs in a single test
+#[test]
+fn mirror_memory() {
+    let before = texture_memory_stats();
+
+    // dropping storage that was never accessed doesn't allocate
+    let storage = TextureStorageD2::<Rgba32>::new(4096, 4096);
+    assert!(!storage.has_cpu_mirror());
+    drop(storage);
+
+    assert_eq!(texture_memory_stats(), before);
+
+    // the first access allocates
+    let storage = TextureStorageD2::<Rgba32>::new(16, 16);
+    assert!(!storage.has_cpu_mirror());
+    assert_eq!(storage.bytes().len(), storage.size());
+    assert!(storage.has_cpu_mirror());
+
+    let stats = texture_memory_stats();
+    assert_eq!(stats.mirror_allocations, before.mirror_allocations + 1);
+    assert_eq!(
+        stats.mirror_bytes,
+        before.mirror_bytes + storage.size() as u64
+    );
+
+    drop(storage);
+
+    assert_eq!(texture_memory_stats().mirror_bytes, before.mirror_bytes);
+
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let before = texture_memory_stats();
+
+    // create -> dispatch -> drop of a large float intermediate
+    let mut texture = Texture2d::<Rgba32Float>::gpu_only(4096, 4096);
+
+    let mut report = TransferReport::ZERO;
+    Binding::<TextureBinding<Float<false>, D2, false>>::prepare(&texture, &mut report);
+    Binding::<TextureBinding<Float<false>, D2, false>>::read(&texture, &mut report);
+    BindingMut::<StorageTextureBinding<texel_format::Rgba32Float, D2>>::write(&mut texture);
+
+    assert_eq!(report.uploaded_bytes, 0);
+    assert!(!texture.has_cpu_mirror());
+
+    drop(texture);
+
+    let mut texture = Texture2d::<Rgba8Unorm>::gpu_only(256, 256);
+
+    fill(
+        fill::Bindings {
+            output: &mut texture,
+        },
+        Dispatch::new(32, 32, 1),
+    );
+
+    assert!(!texture.has_cpu_mirror());
+
+    drop(texture);
+
+    assert_eq!(texture_memory_stats(), before);
+
+    // CPU access to a GPU only texture panics
+    let texture = Texture2d::<Rgba8Unorm>::gpu_only(4, 4);
+
+    let error = panic::catch_unwind(AssertUnwindSafe(|| texture[(0, 0)])).unwrap_err();
+    let message = error.downcast_ref::<&str>().unwrap();
+    assert!(message.contains("GPU only"), "{}", message);
+
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| texture.bytes().len())).is_err());
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| texture.download())).is_err());
+}
@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<u32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn spin([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&values.values)) {
+            return;
+        }
+
+        var value = values.values[id.x];
+
+        for (var i = 0u; i < 256u; i = i + 1u) {
+            value = value * 1664525u + 1013904223u;
+        }
+
+        values.values[id.x] = value;
+    }
+}
+
+#[test]
+fn timed_dispatches_report_gpu_durations() {
+    let instance = match Instance::try_init(&InstanceDescriptor {
+        optional_features: wgpu::Features::TIMESTAMP_QUERY,
+        ..Default::default()
+    }) {
+        Ok(instance) => instance,
+        Err(_) => return,
+    };
+
+    let supported = instance
+        .device
+        .features()
+        .contains(wgpu::Features::TIMESTAMP_QUERY);
+
+    let mut values = Buffer::<Values>::new();
+    values.extend(0..4096);
+
+    let mut builder = spin::build(spin::Bindings {
+        values: &mut values,
+    });
+
+    let dispatch = spin::dispatch_for(4096);
+    builder
+        .timed()
+        .dispatch_multiple(&[dispatch, Dispatch::new(0, 1, 1), dispatch]);
+
+    match builder.last_timing() {
+        Some(timing) => {
+            assert!(supported);
+
+            let timings = builder.last_dispatch_timings().unwrap();
+            assert_eq!(timings.len(), 3);
+            assert_eq!(timings[1].gpu_duration, Duration::ZERO);
+            assert_eq!(
+                timings
+                    .iter()
+                    .map(|timing| timing.gpu_duration)
+                    .sum::<Duration>(),
+                timing.gpu_duration
+            );
+        }
+        None => {
+            assert!(!supported);
+            assert_eq!(builder.last_dispatch_timings(), None);
+        }
+    }
+
+    // a single dispatch is its own pass
+    builder.dispatch(dispatch);
+    assert_eq!(builder.last_timing().is_some(), supported);
+    assert_eq!(
+        builder.last_dispatch_timings().map(|timings| timings.len()),
+        supported.then_some(1)
+    );
+
+    // recorded into an encoder, the timestamps couldn't be read
+    let mut encoder = instance
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    builder.dispatch_in(&mut encoder, dispatch);
+    instance.queue.submit(Some(encoder.finish()));
+    assert_eq!(builder.last_timing(), None);
+
+    builder.untimed().dispatch(dispatch);
+    assert_eq!(builder.last_timing(), None);
+    assert_eq!(builder.last_dispatch_timings(), None);
+}
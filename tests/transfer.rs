@@ -0,0 +1,66 @@
+use shatter::*;
+
+wgsl! {
+    struct Particle {
+        position: vec2<f32>;
+        velocity: vec2<f32>;
+    };
+
+    [[block]]
+    struct Particles {
+        particles: array<Particle>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> particles: Particles;
+
+    [[block]]
+    struct Uniforms {
+        delta: f32;
+    };
+
+    [[group(0), binding(1)]]
+    var<uniform> uniforms: Uniforms;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn step([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        let particle = &particles.particles[id.x];
+
+        (*particle).position = (*particle).position + (*particle).velocity * uniforms.delta;
+    }
+}
+
+#[test]
+fn steady_state_uploads_nothing() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut particles = Buffer::<Particles>::new();
+    let mut uniforms = Buffer::<Uniforms>::new();
+
+    for _ in 0..256 {
+        particles.push(Particle {
+            position: Vec2::new(0.0, 0.0),
+            velocity: Vec2::new(1.0, 1.0),
+        });
+    }
+
+    uniforms.delta = 0.5;
+
+    let mut builder = step::build(step::Bindings {
+        particles: &mut particles,
+        uniforms: &uniforms,
+    });
+
+    builder.dispatch(Dispatch::new(256 / step::WORK_GROUP_SIZE.x, 1, 1));
+    assert_transfers!(builder.last_transfer_report(), buffers_touched == 2);
+    assert!(builder.last_transfer_report().uploaded_bytes > 0);
+
+    builder.dispatch(Dispatch::new(256 / step::WORK_GROUP_SIZE.x, 1, 1));
+    assert_transfers!(
+        builder.last_transfer_report(),
+        uploaded_bytes == 0,
+        downloaded_bytes == 0,
+    );
+}
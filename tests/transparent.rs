@@ -0,0 +1,111 @@
+use shatter::*;
+
+wgsl! {
+    struct Particle {
+        position: vec2<f32>;
+        velocity: vec2<f32>;
+    };
+
+    [[block]]
+    struct Particles {
+        particles: array<Particle>;
+    };
+
+    [[block]]
+    struct Uniforms {
+        delta_time: f32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> particles: Particles;
+
+    [[group(0), binding(1)]]
+    var<uniform> uniforms: Uniforms;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn step([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&particles.particles)) {
+            return;
+        }
+
+        let particle = particles.particles[id.x];
+        particles.particles[id.x].position = particle.position + particle.velocity * uniforms.delta_time;
+    }
+}
+
+#[derive(BufferDataTransparent)]
+#[repr(transparent)]
+struct Enemies(pub Particles);
+
+impl Enemies {
+    fn count(&self) -> usize {
+        self.0.particles.len()
+    }
+
+    fn position(&self, enemy: usize) -> Vec2<f32> {
+        self.0.particles[enemy].position
+    }
+}
+
+trait SpawnEnemies {
+    fn spawn(&mut self, position: Vec2<f32>, velocity: Vec2<f32>);
+}
+
+impl SpawnEnemies for Buffer<Enemies> {
+    fn spawn(&mut self, position: Vec2<f32>, velocity: Vec2<f32>) {
+        self.push(Particle { position, velocity });
+    }
+}
+
+#[derive(BufferDataTransparent)]
+#[repr(transparent)]
+struct Settings {
+    uniforms: Uniforms,
+}
+
+#[test]
+fn newtypes_forward_buffer_data() {
+    assert_eq!(
+        <Enemies as BufferData>::init(),
+        <Particles as BufferData>::init()
+    );
+
+    let settings = Settings {
+        uniforms: Uniforms { delta_time: 0.5 },
+    };
+    assert_eq!(settings.uniforms.delta_time, 0.5);
+    assert_eq!(
+        <Settings as BufferData>::size(&()),
+        <Uniforms as BufferData>::size(&())
+    );
+}
+
+#[test]
+fn wrapped_buffers_bind_as_inner() {
+    if Instance::try_global().is_none() {
+        return;
+    }
+
+    let mut enemies = Buffer::<Enemies>::new();
+    let mut settings = Buffer::<Settings>::new();
+
+    for i in 0..100 {
+        enemies.spawn(Vec2::new(i as f32, 0.0), Vec2::new(0.0, 2.0));
+    }
+
+    settings.uniforms.delta_time = 0.5;
+
+    step(
+        step::Bindings {
+            particles: enemies.as_inner_binding_mut(),
+            uniforms: settings.as_inner_binding(),
+        },
+        Dispatch::new(2, 1, 1),
+    );
+
+    assert_eq!(enemies.count(), 100);
+
+    for i in 0..100 {
+        assert_eq!(enemies.position(i), Vec2::new(i as f32, 1.0));
+    }
+}
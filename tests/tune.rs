@@ -0,0 +1,191 @@
+use std::{
+    env, fs,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use shatter::{tune::*, *};
+
+wgsl! {
+    for SIZE in [64, 128, 256];
+
+    [[block]]
+    struct Values {
+        values: array<f32>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[stage(compute), workgroup_size(SIZE)]]
+    fn double([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        values.values[id.x] = values.values[id.x] * 2.0;
+    }
+}
+
+/// Reports a fixed time per work group size without touching the GPU.
+struct MockTimer {
+    adapter: &'static str,
+    times: fn(WorkGroupSize) -> Option<Duration>,
+    calls: Arc<Mutex<Vec<WorkGroupSize>>>,
+    size: Arc<Mutex<WorkGroupSize>>,
+}
+
+impl Timer for MockTimer {
+    fn adapter(&self) -> String {
+        self.adapter.to_string()
+    }
+
+    fn time(&mut self, run: &mut dyn FnMut()) -> Option<Duration> {
+        run();
+
+        let size = *self.size.lock().unwrap();
+        self.calls.lock().unwrap().push(size);
+        (self.times)(size)
+    }
+}
+
+fn fastest_128(size: WorkGroupSize) -> Option<Duration> {
+    Some(Duration::from_micros(match size.x {
+        128 => 10,
+        _ => 20,
+    }))
+}
+
+fn fastest_256(size: WorkGroupSize) -> Option<Duration> {
+    Some(Duration::from_micros(match size.x {
+        256 => 10,
+        _ => 20,
+    }))
+}
+
+fn untimed(_: WorkGroupSize) -> Option<Duration> {
+    None
+}
+
+fn variants() -> Vec<&'static dyn DispatchableShader<double::Bindings<'static>>> {
+    double::SIZE
+        .iter()
+        .map(|&size| double::with_size(size).unwrap())
+        .collect()
+}
+
+struct Run {
+    picked: WorkGroupSize,
+    calls: Vec<WorkGroupSize>,
+    events: Vec<TuneEvent>,
+}
+
+/// Picks with a fresh tuner, as a later run of the program would.
+fn pick(
+    cache_file: &Path,
+    adapter: &'static str,
+    times: fn(WorkGroupSize) -> Option<Duration>,
+    retune: bool,
+) -> Run {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let size = Arc::new(Mutex::new(WorkGroupSize::default()));
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    let hook_events = events.clone();
+    let mut tuner = Tuner::with_timer(MockTimer {
+        adapter,
+        times,
+        calls: calls.clone(),
+        size: size.clone(),
+    })
+    .with_cache_file(Some(cache_file.to_path_buf()))
+    .with_iterations(1, 3)
+    .with_hook(move |event| hook_events.lock().unwrap().push(event.clone()));
+
+    let variants = variants();
+
+    // stands in for dispatching the variant with representative bindings
+    let dispatch = |i: usize, dispatch: Dispatch| {
+        let work_group_size = variants[i].work_group_size();
+        assert_eq!(dispatch.invocations(work_group_size), 1 << 20);
+
+        *size.lock().unwrap() = work_group_size;
+    };
+
+    let picked = if retune {
+        tuner.retune(&variants, [1 << 20, 1, 1], dispatch)
+    } else {
+        tuner.pick(&variants, [1 << 20, 1, 1], dispatch)
+    };
+
+    let calls = calls.lock().unwrap().clone();
+    let events = events.lock().unwrap().clone();
+
+    Run {
+        picked,
+        calls,
+        events,
+    }
+}
+
+#[test]
+fn tune_caches_winner() {
+    let cache_file = env::temp_dir().join("shatter_tune_caches_winner");
+    let _ = fs::remove_file(&cache_file);
+
+    // every variant is warmed up once and timed three times
+    let first = pick(&cache_file, "mock", fastest_128, false);
+    assert_eq!(first.picked, WorkGroupSize::new(128, 1, 1));
+    assert_eq!(first.calls.len(), 3 * 4);
+
+    match &first.events[..] {
+        [TuneEvent::Measured {
+            entry_point,
+            timings,
+            work_group_size,
+        }] => {
+            assert_eq!(entry_point, "double");
+            assert_eq!(timings.len(), 3);
+            assert_eq!(timings[1], (*work_group_size, Duration::from_micros(10)));
+        }
+        events => panic!("unexpected events {:?}", events),
+    }
+
+    // the winner is cached, even if the timings would change now
+    let second = pick(&cache_file, "mock", fastest_256, false);
+    assert_eq!(second.picked, WorkGroupSize::new(128, 1, 1));
+    assert!(second.calls.is_empty());
+    assert!(matches!(second.events[..], [TuneEvent::Cached { .. }]));
+
+    // per adapter
+    let other = pick(&cache_file, "other", fastest_256, false);
+    assert_eq!(other.picked, WorkGroupSize::new(256, 1, 1));
+    assert!(!other.calls.is_empty());
+    assert_eq!(fs::read_to_string(&cache_file).unwrap().lines().count(), 2);
+
+    // retuning replaces the cached winner
+    let retuned = pick(&cache_file, "mock", fastest_256, true);
+    assert_eq!(retuned.picked, WorkGroupSize::new(256, 1, 1));
+
+    let cached = pick(&cache_file, "mock", fastest_128, false);
+    assert_eq!(cached.picked, WorkGroupSize::new(256, 1, 1));
+    assert_eq!(fs::read_to_string(&cache_file).unwrap().lines().count(), 2);
+
+    fs::remove_file(&cache_file).unwrap();
+}
+
+#[test]
+fn tune_falls_back_untimed() {
+    let cache_file = env::temp_dir().join("shatter_tune_falls_back_untimed");
+    let _ = fs::remove_file(&cache_file);
+
+    let run = pick(&cache_file, "mock", untimed, false);
+    assert_eq!(run.picked, WorkGroupSize::new(64, 1, 1));
+
+    match &run.events[..] {
+        [event @ TuneEvent::Untimed { .. }] => {
+            assert!(event.to_string().contains("TIMESTAMP_QUERY"));
+        }
+        events => panic!("unexpected events {:?}", events),
+    }
+
+    // nothing is cached
+    assert!(!cache_file.exists());
+}
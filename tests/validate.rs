@@ -0,0 +1,260 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Data {
+        values: array<f32, 16>;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read> input: Data;
+
+    [[group(0), binding(1)]]
+    var<storage, read_write> output: Data;
+
+    [[group(0), binding(2)]]
+    var image: texture_storage_2d<rgba8unorm, write>;
+
+    [[stage(compute), workgroup_size(1)]]
+    fn copy() {
+        output.values[0] = input.values[0];
+        textureStore(image, vec2<i32>(0), vec4<f32>(output.values[0]));
+    }
+}
+
+mod shared {
+    use shatter::*;
+
+    wgsl! {
+        [[block]]
+        struct Data {
+            values: array<vec4<f32>, 4>;
+        };
+
+        [[group(0), binding(0)]]
+        var<uniform> params: Data;
+
+        [[group(0), binding(1)]]
+        var<storage, read> input: Data;
+
+        [[group(1), binding(0)]]
+        var<storage, read> other: Data;
+
+        [[group(1), binding(1)]]
+        var<storage, read_write> output: Data;
+
+        [[stage(compute), workgroup_size(1)]]
+        fn sum() {
+            output.values[0] = params.values[0] + input.values[0] + other.values[0];
+        }
+    }
+}
+
+/// A binding that only describes a resource, never touching the GPU.
+struct Fake(ResourceInfo);
+
+impl<T: ?Sized> Binding<T> for Fake {
+    fn binding_resource(&self) -> BindingResource {
+        unreachable!()
+    }
+
+    fn resource_info(&self) -> ResourceInfo {
+        self.0.clone()
+    }
+
+    fn prepare(&self, _: &mut TransferReport) {}
+
+    fn read(&self, _: &mut TransferReport) {}
+}
+
+impl<T: ?Sized> BindingMut<T> for Fake {
+    fn write(&mut self) {}
+}
+
+fn buffer(id: &BufferId, size: u64) -> Fake {
+    Fake(ResourceInfo::Buffer {
+        id: id.clone(),
+        size,
+        usage: BUFFER_USAGES,
+    })
+}
+
+fn texture(format: wgpu::TextureFormat) -> Fake {
+    Fake(ResourceInfo::Texture {
+        id: IdMap::<wgpu::Texture>::new().next_id(),
+        format,
+        view_dimension: wgpu::TextureViewDimension::D2,
+        usage: TEXTURE_USAGES,
+    })
+}
+
+#[test]
+fn valid_bindings() {
+    let buffers = IdMap::<wgpu::Buffer>::new();
+
+    let input = buffer(&buffers.next_id(), 64);
+    let mut output = buffer(&buffers.next_id(), 64);
+    let mut image = texture(wgpu::TextureFormat::Rgba8Unorm);
+
+    let bindings = copy::Bindings {
+        input: &input,
+        output: &mut output,
+        image: &mut image,
+    };
+
+    assert_eq!(copy::validate(&bindings), Ok(()));
+}
+
+#[test]
+fn reports_every_error() {
+    let id = IdMap::<wgpu::Buffer>::new().next_id();
+
+    let input = buffer(&id, 16);
+    let mut output = buffer(&id, 64);
+    let mut image = texture(wgpu::TextureFormat::Rgba8UnormSrgb);
+
+    let bindings = copy::Bindings {
+        input: &input,
+        output: &mut output,
+        image: &mut image,
+    };
+
+    let report = copy::validate(&bindings).unwrap_err();
+
+    assert_eq!(report.errors.len(), 3);
+    assert!(report.contains(
+        "input",
+        &BindingErrorKind::BufferTooSmall {
+            size: 16,
+            min_size: 64
+        }
+    ));
+    assert!(report.contains("input", &BindingErrorKind::Aliased { other: "output" }));
+    assert!(report.contains(
+        "image",
+        &BindingErrorKind::FormatMismatch {
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            expected: wgpu::TextureFormat::Rgba8Unorm,
+        }
+    ));
+}
+
+#[test]
+fn read_only_aliasing_is_allowed() {
+    let buffers = IdMap::<wgpu::Buffer>::new();
+
+    let id = buffers.next_id();
+    let shared = buffer(&id, 64);
+    let mut output = buffer(&buffers.next_id(), 64);
+
+    // uniform and storage reads of the same buffer, within and across groups
+    let bindings = shared::sum::Bindings {
+        params: &shared,
+        input: &shared,
+        other: &shared,
+        output: &mut output,
+    };
+
+    assert_eq!(shared::sum::validate(&bindings), Ok(()));
+
+    // writing to a buffer that is also read stays an error
+    let mut output = buffer(&id, 64);
+
+    let bindings = shared::sum::Bindings {
+        params: &shared,
+        input: &shared,
+        other: &shared,
+        output: &mut output,
+    };
+
+    let report = shared::sum::validate(&bindings).unwrap_err();
+
+    assert_eq!(report.errors.len(), 3);
+
+    for field in ["params", "input", "other"] {
+        assert!(report.contains(field, &BindingErrorKind::Aliased { other: "output" }));
+    }
+}
+
+#[test]
+fn buffer_usage_follows_declaration() {
+    let buffers = IdMap::<wgpu::Buffer>::new();
+
+    let usage_buffer = |usage: BufferUsage| {
+        Fake(ResourceInfo::Buffer {
+            id: buffers.next_id(),
+            size: 64,
+            usage: usage.buffer_usages(),
+        })
+    };
+
+    let params = usage_buffer(BufferUsage::Storage);
+    let input = usage_buffer(BufferUsage::Uniform);
+    let other = usage_buffer(BufferUsage::Any);
+    let mut output = usage_buffer(BufferUsage::Storage);
+
+    let bindings = shared::sum::Bindings {
+        params: &params,
+        input: &input,
+        other: &other,
+        output: &mut output,
+    };
+
+    let report = shared::sum::validate(&bindings).unwrap_err();
+
+    assert_eq!(report.errors.len(), 2);
+    assert!(report.contains(
+        "params",
+        &BindingErrorKind::MissingBufferUsage(wgpu::BufferUsages::UNIFORM)
+    ));
+    assert!(report.contains(
+        "input",
+        &BindingErrorKind::MissingBufferUsage(wgpu::BufferUsages::STORAGE)
+    ));
+}
+
+#[test]
+fn uniform_over_limit() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let max = instance.device.limits().max_uniform_buffer_binding_size as u64;
+    let buffers = IdMap::<wgpu::Buffer>::new();
+
+    let params = buffer(&buffers.next_id(), max + 16);
+    let input = buffer(&buffers.next_id(), max + 16);
+    let other = buffer(&buffers.next_id(), 64);
+    let mut output = buffer(&buffers.next_id(), 64);
+
+    let bindings = shared::sum::Bindings {
+        params: &params,
+        input: &input,
+        other: &other,
+        output: &mut output,
+    };
+
+    // storage bindings aren't limited to uniform sizes
+    let report = shared::sum::validate(&bindings).unwrap_err();
+    assert_eq!(
+        report.errors,
+        vec![BindingError {
+            binding: "params",
+            kind: BindingErrorKind::UniformTooLarge {
+                size: max + 16,
+                max
+            },
+        }]
+    );
+
+    // a storage only buffer doesn't request uniform usage
+    let storage = Buffer::<shared::Data>::with_usage(BufferUsage::Storage);
+    match Binding::<shared::Data>::resource_info(&storage) {
+        ResourceInfo::Buffer { usage, .. } => {
+            assert!(usage.contains(wgpu::BufferUsages::STORAGE));
+            assert!(!usage.contains(wgpu::BufferUsages::UNIFORM));
+        }
+        _ => unreachable!(),
+    }
+}
@@ -0,0 +1,88 @@
+use shatter::*;
+
+wgsl! {
+    [[block]]
+    struct Values {
+        values: array<f32>;
+    };
+
+    [[block]]
+    struct Uniforms {
+        scale: f32;
+    };
+
+    [[group(0), binding(0)]]
+    var<storage, read_write> values: Values;
+
+    [[group(0), binding(1)]]
+    var<uniform> uniforms: Uniforms;
+
+    [[stage(compute), workgroup_size(64)]]
+    fn scale([[builtin(global_invocation_id)]] id: vec3<u32>) {
+        if (id.x >= arrayLength(&values.values)) {
+            return;
+        }
+
+        values.values[id.x] = values.values[id.x] * uniforms.scale;
+    }
+}
+
+/// Entries of every cache a dispatch may insert into.
+fn cache_counts(instance: &Instance) -> [usize; 5] {
+    [
+        instance.shader_module_count(),
+        instance.compute_pipeline_count(),
+        instance.pipeline_layout_count(),
+        instance.bind_group_layout_count(),
+        instance.bind_group_count(),
+    ]
+}
+
+#[test]
+fn warm_dispatch_creates_nothing() {
+    let instance = match Instance::try_global() {
+        Some(instance) => instance,
+        None => return,
+    };
+
+    let mut values = Buffer::<Values>::new();
+    let mut uniforms = Buffer::<Uniforms>::new();
+
+    for i in 0..256 {
+        values.push(i as f32);
+    }
+
+    uniforms.scale = 2.0;
+
+    let mut builder = scale::build(scale::Bindings {
+        values: &mut values,
+        uniforms: &uniforms,
+    });
+
+    assert!(!builder.is_warm());
+
+    builder.warm_up();
+
+    assert!(builder.is_warm());
+
+    let counts = cache_counts(instance);
+
+    builder.dispatch(Dispatch::new(4, 1, 1));
+
+    assert_eq!(cache_counts(instance), counts);
+    assert_eq!(builder.last_transfer_report().uploaded_bytes, 0);
+
+    drop(builder);
+
+    // growing a buffer recreates it, so the bind group has to be recreated too
+    for _ in 0..4096 {
+        values.push(1.0);
+    }
+
+    let builder = scale::build(scale::Bindings {
+        values: &mut values,
+        uniforms: &uniforms,
+    });
+
+    assert!(!builder.is_warm());
+}